@@ -0,0 +1,238 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::events::*;
+use crate::option_navigation::navigate_options;
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::fmt::{self, Display};
+
+/// Sends an [`AccessibilityAnnouncementEvent`] for every presented line and set of options
+/// and whenever another option is highlighted in an [`OptionNavigation`].
+/// The events are only sent when this plugin is added.
+///
+/// The announced text is stripped of the character name, which is announced separately, and of all markup.
+/// Writers can adjust what is announced with two attributes:
+/// - Text marked with `[a11y_skip]...[/a11y_skip]` is not announced, e.g. decorative symbols.
+/// - Text marked with `[a11y alt="..."]...[/a11y]` is announced as the `alt` property instead, e.g. `[a11y alt="heart"]<3[/a11y]`.
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::{events::*, prelude::*};
+/// fn main() {
+///     App::new()
+///         .add_plugins(YarnSpinnerAccessibilityPlugin::default())
+///         .add_systems(Update, speak.after(YarnSpinnerSystemSet));
+/// }
+///
+/// fn speak(mut events: EventReader<AccessibilityAnnouncementEvent>) {
+///     for event in events.read() {
+///         let speech = event.announcement.to_string();
+///         // Pass `speech` to your text-to-speech engine
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct YarnSpinnerAccessibilityPlugin;
+
+impl YarnSpinnerAccessibilityPlugin {
+    /// The name of the attribute whose text is not announced.
+    pub const SKIP_ATTRIBUTE: &'static str = "a11y_skip";
+    /// The name of the attribute whose text is replaced by its [`YarnSpinnerAccessibilityPlugin::ALT_PROPERTY`] when announced.
+    pub const ALT_ATTRIBUTE: &'static str = "a11y";
+    /// The property of the [`YarnSpinnerAccessibilityPlugin::ALT_ATTRIBUTE`] holding the announced text.
+    pub const ALT_PROPERTY: &'static str = "alt";
+}
+
+impl Plugin for YarnSpinnerAccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AccessibilityAnnouncementEvent>()
+            .add_systems(
+                Update,
+                send_accessibility_announcements
+                    .after(DialogueExecutionSystemSet)
+                    .after(navigate_options)
+                    .in_set(YarnSpinnerSystemSet),
+            );
+    }
+}
+
+/// An announcement sent by the [`YarnSpinnerAccessibilityPlugin`].
+#[derive(Debug, Clone, PartialEq, Event)]
+pub struct AccessibilityAnnouncementEvent {
+    /// What to announce.
+    pub announcement: AccessibilityAnnouncement,
+    /// The [`DialogueRunner`] this announcement belongs to.
+    pub source: Entity,
+}
+
+/// The content of an [`AccessibilityAnnouncementEvent`].
+/// Its [`Display`] implementation formats it as a single English sentence ready for text-to-speech;
+/// format the fields yourself for other languages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccessibilityAnnouncement {
+    /// A line was presented, see [`PresentLineEvent`].
+    Line {
+        /// The ID of the presented line.
+        line_id: LineId,
+        /// The name of the character speaking the line, if any.
+        speaker: Option<String>,
+        /// The plain text of the line, without character name and markup.
+        text: String,
+    },
+    /// Options were presented, see [`PresentOptionsEvent`].
+    Options {
+        /// The presented options in the order they were presented in, including unavailable ones.
+        options: Vec<AnnouncedOption>,
+    },
+    /// Another option was highlighted in an [`OptionNavigation`], including the first one when options are presented.
+    OptionFocused {
+        /// The highlighted option.
+        option: AnnouncedOption,
+        /// The position of the option among the options that can be highlighted, starting at 1.
+        position: usize,
+        /// The number of options that can be highlighted.
+        count: usize,
+    },
+}
+
+impl Display for AccessibilityAnnouncement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Line {
+                speaker: Some(speaker),
+                text,
+                ..
+            } => write!(f, "{speaker}: {text}"),
+            Self::Line { text, .. } => f.write_str(text),
+            Self::Options { options } => {
+                write!(f, "{} options.", options.len())?;
+                for (index, option) in options.iter().enumerate() {
+                    write!(f, " Option {}", index + 1)?;
+                    if !option.is_available {
+                        f.write_str(", unavailable")?;
+                    }
+                    write!(f, ": {}", option.text)?;
+                    if !option.text.ends_with(['.', '!', '?']) {
+                        f.write_str(".")?;
+                    }
+                }
+                Ok(())
+            }
+            Self::OptionFocused {
+                option,
+                position,
+                count,
+            } => write!(f, "Option {position} of {count}: {}", option.text),
+        }
+    }
+}
+
+/// An option as announced by an [`AccessibilityAnnouncementEvent`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnouncedOption {
+    /// The ID to pass to [`DialogueRunner::select_option`].
+    pub id: OptionId,
+    /// The plain text of the option, without character name and markup.
+    pub text: String,
+    /// Whether the option can be selected, see [`DialogueOption::is_available`].
+    pub is_available: bool,
+}
+
+impl From<&DialogueOption> for AnnouncedOption {
+    fn from(option: &DialogueOption) -> Self {
+        Self {
+            id: option.id,
+            text: announced_text(&option.line),
+            is_available: option.is_available,
+        }
+    }
+}
+
+/// Returns the text of the line without character name, with [`YarnSpinnerAccessibilityPlugin::SKIP_ATTRIBUTE`]s removed,
+/// [`YarnSpinnerAccessibilityPlugin::ALT_ATTRIBUTE`]s replaced and whitespace collapsed.
+fn announced_text(line: &LocalizedLine) -> String {
+    let chars: Vec<_> = line.text.chars().collect();
+    let mut replacements: Vec<_> = line
+        .attributes
+        .iter()
+        .filter_map(|attribute| {
+            let replacement = match attribute.name.as_str() {
+                "character" | YarnSpinnerAccessibilityPlugin::SKIP_ATTRIBUTE => String::new(),
+                YarnSpinnerAccessibilityPlugin::ALT_ATTRIBUTE => attribute
+                    .property(YarnSpinnerAccessibilityPlugin::ALT_PROPERTY)
+                    .map(|alt| alt.to_string())
+                    .unwrap_or_default(),
+                _ => return None,
+            };
+            Some((attribute.position, attribute.length, replacement))
+        })
+        .collect();
+    replacements.sort_by_key(|(position, ..)| *position);
+
+    // Positions of markup attributes count characters, not bytes
+    let mut text = String::new();
+    let mut index = 0;
+    for (position, length, replacement) in replacements {
+        if position < index {
+            // Nested in a range that was already replaced
+            continue;
+        }
+        let position = position.min(chars.len());
+        text.extend(&chars[index..position]);
+        text.push_str(&replacement);
+        index = (position + length).min(chars.len());
+    }
+    text.extend(&chars[index..]);
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn send_accessibility_announcements(
+    mut present_line_events: EventReader<PresentLineEvent>,
+    mut present_options_events: EventReader<PresentOptionsEvent>,
+    navigations: Query<(Entity, &OptionNavigation)>,
+    mut focused_options: Local<HashMap<Entity, OptionId>>,
+    mut announcement_events: EventWriter<AccessibilityAnnouncementEvent>,
+) {
+    for event in present_line_events.read() {
+        announcement_events.send(AccessibilityAnnouncementEvent {
+            announcement: AccessibilityAnnouncement::Line {
+                line_id: event.line.id.clone(),
+                speaker: event.line.character_name().map(ToOwned::to_owned),
+                text: announced_text(&event.line),
+            },
+            source: event.source,
+        });
+    }
+    for event in present_options_events.read() {
+        focused_options.remove(&event.source);
+        announcement_events.send(AccessibilityAnnouncementEvent {
+            announcement: AccessibilityAnnouncement::Options {
+                options: event.options.iter().map(AnnouncedOption::from).collect(),
+            },
+            source: event.source,
+        });
+    }
+    for (source, navigation) in navigations.iter() {
+        let (Some(index), Some(option)) = (
+            navigation.highlighted_index(),
+            navigation.highlighted_option(),
+        ) else {
+            focused_options.remove(&source);
+            continue;
+        };
+        if focused_options.insert(source, option.id) == Some(option.id) {
+            continue;
+        }
+        announcement_events.send(AccessibilityAnnouncementEvent {
+            announcement: AccessibilityAnnouncement::OptionFocused {
+                option: option.into(),
+                position: index + 1,
+                count: navigation.options().len(),
+            },
+            source,
+        });
+    }
+}
@@ -0,0 +1,222 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::events::*;
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// Sends a [`DialogueAnalyticsEvent`] for every dialogue that starts or ends, every node that is entered,
+/// every set of options that is presented or chosen from and every command that is executed.
+/// The events are only sent when this plugin is added, so no telemetry is collected unless a game opts in.
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::{events::*, prelude::*};
+/// fn main() {
+///     App::new()
+///         .add_plugins(YarnSpinnerAnalyticsPlugin::default())
+///         .add_systems(Update, upload_analytics);
+/// }
+///
+/// fn upload_analytics(mut events: EventReader<DialogueAnalyticsEvent>) {
+///     for event in events.read() {
+///         let json = serde_json::to_string(event).unwrap();
+///         // Send `json` to your telemetry backend
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct YarnSpinnerAnalyticsPlugin;
+
+impl Plugin for YarnSpinnerAnalyticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DialogueAnalyticsEvent>().add_systems(
+            Update,
+            send_analytics_events
+                .after(DialogueExecutionSystemSet)
+                .in_set(YarnSpinnerSystemSet),
+        );
+    }
+}
+
+/// A structured event added by [`YarnSpinnerAnalyticsPlugin`]. Implements [`Serialize`] and [`Deserialize`]
+/// so it can be sent as-is to a telemetry backend, e.g. as JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Event)]
+pub struct DialogueAnalyticsEvent {
+    /// What happened.
+    pub kind: DialogueAnalyticsKind,
+    /// The node the [`DialogueRunner`] was in when this happened, if any.
+    pub node: Option<String>,
+    /// The seconds of real time that passed since the app started.
+    pub elapsed_seconds: f64,
+    /// The [`DialogueRunner`] this happened in. Serialized as [`Entity::to_bits`].
+    #[serde(with = "entity_bits")]
+    pub source: Entity,
+}
+
+/// The kind of a [`DialogueAnalyticsEvent`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DialogueAnalyticsKind {
+    /// The dialogue was started, see [`DialogueStartEvent`].
+    DialogueStarted,
+    /// The dialogue ended, see [`DialogueCompleteEvent`].
+    DialogueEnded,
+    /// A node was entered, see [`NodeStartEvent`].
+    NodeEntered,
+    /// Options were presented to the player, see [`PresentOptionsEvent`].
+    OptionsPresented {
+        /// The presented options in the order they were presented in.
+        options: Vec<AnalyticsOption>,
+    },
+    /// The player chose an option, see [`OptionSelectedEvent`].
+    OptionChosen {
+        /// The chosen option.
+        option: AnalyticsOption,
+    },
+    /// A command was executed, see [`ExecuteCommandEvent`].
+    CommandExecuted {
+        /// The name of the command.
+        name: String,
+        /// The parameters of the command, formatted as strings.
+        parameters: Vec<String>,
+    },
+}
+
+/// An option as reported by a [`DialogueAnalyticsEvent`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalyticsOption {
+    /// The position of the option among all options presented at the same time, starting at 0.
+    pub index: usize,
+    /// The ID of the line of the option, which stays the same across languages.
+    pub line_id: LineId,
+    /// Whether the option could be chosen, see [`DialogueOption::is_available`].
+    pub is_available: bool,
+}
+
+impl AnalyticsOption {
+    fn from_dialogue_option(index: usize, option: &DialogueOption) -> Self {
+        Self {
+            index,
+            line_id: option.line.id.clone(),
+            is_available: option.is_available,
+        }
+    }
+}
+
+mod entity_bits {
+    use bevy::prelude::*;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        entity: &Entity,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(entity.to_bits())
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Entity, D::Error> {
+        let bits = u64::deserialize(deserializer)?;
+        Entity::try_from_bits(bits).map_err(D::Error::custom)
+    }
+}
+
+fn send_analytics_events(
+    mut dialogue_start_events: EventReader<DialogueStartEvent>,
+    mut dialogue_complete_events: EventReader<DialogueCompleteEvent>,
+    mut node_start_events: EventReader<NodeStartEvent>,
+    mut present_options_events: EventReader<PresentOptionsEvent>,
+    mut option_selected_events: EventReader<OptionSelectedEvent>,
+    mut execute_command_events: EventReader<ExecuteCommandEvent>,
+    dialogue_runners: Query<&DialogueRunner>,
+    mut analytics_events: EventWriter<DialogueAnalyticsEvent>,
+    mut presented_options: Local<HashMap<Entity, PresentedOptions>>,
+    time: Res<Time<Real>>,
+) {
+    let elapsed_seconds = time.elapsed_seconds_f64();
+    let mut events = Vec::new();
+    // Options are selected before the dialogue continues, so these come first
+    for event in option_selected_events.read() {
+        let Some(presented) = presented_options.remove(&event.source) else {
+            continue;
+        };
+        let Some(index) = presented
+            .options
+            .iter()
+            .position(|option| option.id == event.option.id)
+        else {
+            continue;
+        };
+        let option = AnalyticsOption::from_dialogue_option(index, &event.option);
+        // The dialogue runner may already be in the node the option jumped to
+        let node = presented.node;
+        events.push((
+            DialogueAnalyticsKind::OptionChosen { option },
+            event.source,
+            node,
+        ));
+    }
+    for event in dialogue_start_events.read() {
+        events.push((DialogueAnalyticsKind::DialogueStarted, event.source, None));
+    }
+    for event in node_start_events.read() {
+        let node = Some(event.node_name.clone());
+        events.push((DialogueAnalyticsKind::NodeEntered, event.source, node));
+    }
+    for event in execute_command_events.read() {
+        let kind = DialogueAnalyticsKind::CommandExecuted {
+            name: event.command.name.clone(),
+            parameters: event
+                .command
+                .parameters
+                .iter()
+                .map(|parameter| parameter.to_string())
+                .collect(),
+        };
+        events.push((kind, event.source, None));
+    }
+    for event in present_options_events.read() {
+        let options = event
+            .options
+            .iter()
+            .enumerate()
+            .map(|(index, option)| AnalyticsOption::from_dialogue_option(index, option))
+            .collect();
+        let node = current_node(&dialogue_runners, event.source);
+        let kind = DialogueAnalyticsKind::OptionsPresented { options };
+        events.push((kind, event.source, node.clone()));
+        let options = event.options.clone();
+        presented_options.insert(event.source, PresentedOptions { options, node });
+    }
+    for event in dialogue_complete_events.read() {
+        events.push((DialogueAnalyticsKind::DialogueEnded, event.source, None));
+        presented_options.remove(&event.source);
+    }
+    let analytics_events_to_send: Vec<_> = events
+        .into_iter()
+        .map(|(kind, source, node)| DialogueAnalyticsEvent {
+            kind,
+            node: node.or_else(|| current_node(&dialogue_runners, source)),
+            elapsed_seconds,
+            source,
+        })
+        .collect();
+    analytics_events.send_batch(analytics_events_to_send);
+}
+
+#[derive(Debug, Clone)]
+struct PresentedOptions {
+    options: Vec<DialogueOption>,
+    node: Option<String>,
+}
+
+fn current_node(dialogue_runners: &Query<&DialogueRunner>, source: Entity) -> Option<String> {
+    dialogue_runners
+        .get(source)
+        .ok()
+        .and_then(|dialogue_runner| dialogue_runner.current_node())
+}
@@ -0,0 +1,200 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// A [`Resource`] mapping the character names used in Yarn files, e.g. `Hag` in `Hag: Now your *third* wish.`, to a [`Character`].
+/// Dialogue views can use it to look up who is speaking a line, and how to present them.
+///
+/// Not inserted by default. Insert this resource to use it, which also makes the content validation report lines spoken by
+/// characters that are not in the registry, see [`ContentValidationReport::unknown_speakers`].
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::prelude::*;
+/// fn register_characters(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     let hag = commands.spawn(SpatialBundle::default()).id();
+///     commands.insert_resource(
+///         CharacterRegistry::new()
+///             .with_character(
+///                 "Hag",
+///                 Character::new()
+///                     .with_entity(hag)
+///                     .with_display_name("Old Woman")
+///                     .with_localized_display_name("de-CH", "Alte Frau")
+///                     .with_color(Color::srgb(0.6, 0.2, 0.8))
+///                     .with_portrait(asset_server.load::<Image>("portraits/hag.png")),
+///             )
+///             .with_character("Man", Character::new().with_color(Color::WHITE)),
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Resource)]
+pub struct CharacterRegistry {
+    characters: HashMap<String, Character>,
+}
+
+/// A character registered in the [`CharacterRegistry`]. All data is optional.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Character {
+    /// The entity that represents the character in the world, e.g. for placing speech bubbles above it.
+    pub entity: Option<Entity>,
+    /// The name to show instead of the one used in the Yarn files. See [`Character::display_name`].
+    pub display_name: Option<String>,
+    /// Translations of the [`Character::display_name`], keyed by language.
+    pub localized_display_names: HashMap<Language, String>,
+    /// The color the character's name or text should be shown in.
+    pub color: Option<Color>,
+    /// A portrait of the character to show next to their lines. Untyped so that any image asset type can be used,
+    /// e.g. `Handle<Image>` when rendering with Bevy. Get a typed handle back with [`UntypedHandle::typed`].
+    pub portrait: Option<UntypedHandle>,
+}
+
+impl CharacterRegistry {
+    /// Creates a registry without any characters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a character under the name used for it in the Yarn files. Replaces any character previously registered under that name.
+    #[must_use]
+    pub fn with_character(mut self, name: impl Into<String>, character: Character) -> Self {
+        self.insert(name, character);
+        self
+    }
+
+    /// Registers a character under the name used for it in the Yarn files, returning the character previously registered under that name.
+    pub fn insert(&mut self, name: impl Into<String>, character: Character) -> Option<Character> {
+        self.characters.insert(name.into(), character)
+    }
+
+    /// Removes the character with the given name, returning it if it was registered.
+    pub fn remove(&mut self, name: &str) -> Option<Character> {
+        self.characters.remove(name)
+    }
+
+    /// Returns the character with the given name, as used in the Yarn files.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Character> {
+        self.characters.get(name)
+    }
+
+    /// Mutable version of [`CharacterRegistry::get`].
+    #[must_use]
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Character> {
+        self.characters.get_mut(name)
+    }
+
+    /// Returns whether a character with the given name is registered.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.characters.contains_key(name)
+    }
+
+    /// Returns the character speaking the given line, if the line has a character name and that character is registered.
+    #[must_use]
+    pub fn speaker(&self, line: &LocalizedLine) -> Option<&Character> {
+        self.get(line.character_name()?)
+    }
+
+    /// Returns the entity of the character with the given name, if it is registered and has one.
+    #[must_use]
+    pub fn entity(&self, name: &str) -> Option<Entity> {
+        self.get(name)?.entity
+    }
+
+    /// Returns the name and character whose [`Character::entity`] is the given entity.
+    #[must_use]
+    pub fn find_by_entity(&self, entity: Entity) -> Option<(&str, &Character)> {
+        self.characters
+            .iter()
+            .find(|(_, character)| character.entity == Some(entity))
+            .map(|(name, character)| (name.as_str(), character))
+    }
+
+    /// Returns the name to show for the character with the given name in the given language, see [`Character::display_name`].
+    /// Falls back to the name itself if the character is not registered or has no display name.
+    #[must_use]
+    pub fn display_name<'a>(&'a self, name: &'a str, language: Option<&Language>) -> &'a str {
+        self.get(name)
+            .and_then(|character| character.display_name(language))
+            .unwrap_or(name)
+    }
+
+    /// Iterates over all registered characters and their names in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Character)> {
+        self.characters
+            .iter()
+            .map(|(name, character)| (name.as_str(), character))
+    }
+
+    /// Returns the number of registered characters.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.characters.len()
+    }
+
+    /// Returns `true` if no characters are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.characters.is_empty()
+    }
+}
+
+impl Character {
+    /// Creates a character without any data.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets [`Character::entity`].
+    #[must_use]
+    pub fn with_entity(mut self, entity: Entity) -> Self {
+        self.entity = Some(entity);
+        self
+    }
+
+    /// Sets [`Character::display_name`].
+    #[must_use]
+    pub fn with_display_name(mut self, display_name: impl Into<String>) -> Self {
+        self.display_name = Some(display_name.into());
+        self
+    }
+
+    /// Adds a translation of the [`Character::display_name`].
+    #[must_use]
+    pub fn with_localized_display_name(
+        mut self,
+        language: impl Into<Language>,
+        display_name: impl Into<String>,
+    ) -> Self {
+        self.localized_display_names
+            .insert(language.into(), display_name.into());
+        self
+    }
+
+    /// Sets [`Character::color`].
+    #[must_use]
+    pub fn with_color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Sets [`Character::portrait`].
+    #[must_use]
+    pub fn with_portrait(mut self, portrait: impl Into<UntypedHandle>) -> Self {
+        self.portrait = Some(portrait.into());
+        self
+    }
+
+    /// Returns the display name in the given language, falling back to [`Character::display_name`] if it has no translation for it.
+    /// Pass the [`DialogueRunner::text_language`] of the runner presenting the line.
+    #[must_use]
+    pub fn display_name(&self, language: Option<&Language>) -> Option<&str> {
+        language
+            .and_then(|language| self.localized_display_names.get(language))
+            .or(self.display_name.as_ref())
+            .map(String::as_str)
+    }
+}
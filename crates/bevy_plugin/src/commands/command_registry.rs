@@ -48,6 +48,9 @@ impl YarnCommands {
     /// Adds a new method to the registry. Commands are valid Bevy systems with input and output.
     ///
     /// See the documentation of [`YarnCommand`] for more information about which methods are allowed.
+    ///
+    /// When Yarn calls the command with arguments that don't match the `In` parameter in number or type,
+    /// the [`DialogueRunner`] panics with a message naming the command, its signature and the received arguments.
     pub fn add_command<Marker, F>(
         &mut self,
         name: impl Into<Cow<'static, str>>,
@@ -58,7 +61,7 @@ impl YarnCommands {
         F: YarnCommand<Marker> + 'static + Clone,
     {
         let name = name.into();
-        let wrapped = YarnCommandWrapper::from(command);
+        let wrapped = YarnCommandWrapper::from(command).with_name(name.clone());
         self.0.insert(name, Box::new(wrapped));
         self
     }
@@ -183,7 +186,7 @@ mod tests {
         methods.add_command("test", |_: In<()>| -> () { panic!("It works!") });
         let method = methods.get_mut("test").unwrap();
         let mut app = App::new();
-        method.call(vec![], app.world_mut());
+        method.call(vec![], app.world_mut()).unwrap();
     }
 
     #[test]
@@ -193,7 +196,9 @@ mod tests {
         methods.add_command("test", |In(a): In<f32>| assert_eq!(1.0, a));
         let method = methods.get_mut("test").unwrap();
         let mut app = App::new();
-        method.call(to_method_params([1.0]), app.world_mut());
+        method
+            .call(to_method_params([1.0]), app.world_mut())
+            .unwrap();
     }
 
     #[test]
@@ -213,10 +218,12 @@ mod tests {
         let mut app = App::new();
         {
             let method1 = methods.get_mut("test1").unwrap();
-            method1.call(vec![], app.world_mut());
+            method1.call(vec![], app.world_mut()).unwrap();
         }
         let method2 = methods.get_mut("test2").unwrap();
-        method2.call(to_method_params([1.0]), app.world_mut());
+        method2
+            .call(to_method_params([1.0]), app.world_mut())
+            .unwrap();
     }
 
     #[test]
@@ -232,7 +239,9 @@ mod tests {
         let method = methods.get_mut("test").unwrap();
 
         let mut app = App::new();
-        method.call(to_method_params([1.0]), app.world_mut());
+        method
+            .call(to_method_params([1.0]), app.world_mut())
+            .unwrap();
         let data = app.world().resource::<Data>();
         assert_eq!(data.0, 1.0);
     }
@@ -251,7 +260,7 @@ mod tests {
         let method = methods.get_mut("test").unwrap();
 
         let mut app = App::new();
-        let task = method.call(vec![], app.world_mut());
+        let task = method.call(vec![], app.world_mut()).unwrap();
         assert!(!task.is_finished());
         sleep(Duration::from_millis(600));
         assert!(task.is_finished());
@@ -1,9 +1,8 @@
 //! In an ideal world, this would just be a new thread doing `sleep`.
 //! Alas, Wasm forces us to do this
 
-use crate::prelude::YarnSpinnerSystemSet;
+use crate::prelude::{DialogueRunner, YarnSpinnerSystemSet};
 use bevy::prelude::*;
-use bevy::utils::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
@@ -14,36 +13,98 @@ pub(crate) fn wait_command_plugin(app: &mut App) {
 }
 
 #[derive(Debug, Clone, Resource, Default)]
-pub(crate) struct Wait(HashMap<Duration, WaitPeriod>);
+pub(crate) struct Wait(Vec<WaitPeriod>);
 
 #[derive(Debug, Clone)]
 pub(crate) struct WaitPeriod {
-    duration: Duration,
+    timer: Timer,
     done: Arc<AtomicBool>,
+    /// The [`DialogueRunner`] whose command started this period. Periods of paused runners are frozen.
+    source: Option<Entity>,
 }
 
 impl Wait {
     pub(crate) fn add(&mut self, duration: Duration) -> Arc<AtomicBool> {
         let done = Arc::new(AtomicBool::new(false));
-        self.0.insert(
-            duration,
-            WaitPeriod {
-                duration,
-                done: done.clone(),
-            },
-        );
+        self.0.push(WaitPeriod {
+            timer: Timer::new(duration, TimerMode::Once),
+            done: done.clone(),
+            source: None,
+        });
         done
     }
-}
 
-pub(crate) fn update_wait(time: Res<Time>, mut wait: ResMut<Wait>) {
-    for period in wait.0.values_mut() {
-        if period.duration <= time.delta() {
-            period.duration = Duration::from_secs(0);
-            period.done.store(true, Ordering::Relaxed);
-        } else {
-            period.duration -= time.delta();
+    /// Returns the number of periods, used to find the periods added by a command.
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Assigns all periods after the first `start` ones to the given [`DialogueRunner`].
+    pub(crate) fn set_source_of_periods_since(&mut self, start: usize, source: Entity) {
+        for period in self.0.iter_mut().skip(start) {
+            period.source.get_or_insert(source);
         }
     }
-    wait.0.remove(&Duration::from_secs(0));
+
+    fn tick(&mut self, delta: Duration, is_paused: impl Fn(Entity) -> bool) {
+        for period in self
+            .0
+            .iter_mut()
+            .filter(|period| !period.source.is_some_and(&is_paused))
+        {
+            if period.timer.tick(delta).finished() {
+                period.done.store(true, Ordering::Relaxed);
+            }
+        }
+        self.0.retain(|period| !period.timer.finished());
+    }
+}
+
+pub(crate) fn update_wait(
+    time: Res<Time>,
+    mut wait: ResMut<Wait>,
+    dialogue_runners: Query<&DialogueRunner>,
+) {
+    wait.tick(time.delta(), |source| {
+        dialogue_runners
+            .get(source)
+            .is_ok_and(|dialogue_runner| dialogue_runner.is_paused())
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn waits_of_equal_length_finish_independently() {
+        let mut wait = Wait::default();
+        let first = wait.add(Duration::from_secs(1));
+        wait.tick(Duration::from_millis(500), |_| false);
+        let second = wait.add(Duration::from_secs(1));
+
+        wait.tick(Duration::from_millis(500), |_| false);
+        assert!(first.load(Ordering::Relaxed));
+        assert!(!second.load(Ordering::Relaxed));
+
+        wait.tick(Duration::from_millis(500), |_| false);
+        assert!(second.load(Ordering::Relaxed));
+        assert!(wait.0.is_empty());
+    }
+
+    #[test]
+    fn periods_of_paused_runners_are_frozen() {
+        let mut wait = Wait::default();
+        let paused_runner = Entity::from_raw(1);
+        let paused = wait.add(Duration::from_secs(1));
+        wait.set_source_of_periods_since(0, paused_runner);
+        let running = wait.add(Duration::from_secs(1));
+
+        wait.tick(Duration::from_secs(1), |source| source == paused_runner);
+        assert!(!paused.load(Ordering::Relaxed));
+        assert!(running.load(Ordering::Relaxed));
+
+        wait.tick(Duration::from_secs(1), |_| false);
+        assert!(paused.load(Ordering::Relaxed));
+    }
 }
@@ -1,13 +1,15 @@
 use crate::prelude::*;
+use anyhow::{anyhow, bail};
 use bevy::ecs::system::{SystemParam, SystemParamItem, SystemState};
 use bevy::prelude::*;
 use bevy::tasks::Task;
 use bevy::utils::all_tuples;
+use std::borrow::Cow;
 use std::fmt::{Debug, Display, Formatter};
 use std::marker::PhantomData;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
-use yarnspinner::core::{YarnFnParam, YarnFnParamItem, YarnValueWrapper};
+use yarnspinner::core::{YarnFnContext, YarnFnParam, YarnFnParamItem, YarnValueWrapper};
 
 pub(crate) fn command_wrapping_plugin(_app: &mut App) {}
 
@@ -158,7 +160,11 @@ all_tuples!(impl_task_finished_indicator, 0, 16, F);
 /// A type-erased [`YarnCommand`] as it appears in the [`YarnCommands`].
 pub trait UntypedYarnCommand: Debug + Send + Sync + 'static {
     #[doc(hidden)]
-    fn call(&mut self, input: Vec<YarnValue>, world: &mut World) -> Box<dyn TaskFinishedIndicator>;
+    fn call(
+        &mut self,
+        input: Vec<YarnValue>,
+        world: &mut World,
+    ) -> Result<Box<dyn TaskFinishedIndicator>>;
     #[doc(hidden)]
     fn clone_box(&self) -> Box<dyn UntypedYarnCommand>;
 }
@@ -174,19 +180,33 @@ where
     Marker: 'static,
     T: YarnCommand<Marker>,
 {
-    fn call(&mut self, input: Vec<YarnValue>, world: &mut World) -> Box<dyn TaskFinishedIndicator> {
+    fn call(
+        &mut self,
+        input: Vec<YarnValue>,
+        world: &mut World,
+    ) -> Result<Box<dyn TaskFinishedIndicator>> {
         let mut system_state: SystemState<T::Param> = SystemState::new(world);
         let param = system_state.get_mut(world);
+        let arguments = format_arguments(&input);
         let mut input: Vec<_> = input.into_iter().map(YarnValueWrapper::from).collect();
         let mut iter = input.iter_mut().peekable();
-        let input = T::In::retrieve(&mut iter);
-        assert!(
-            iter.next().is_none(),
-            "Passed too many arguments to Command"
-        );
+        let context = YarnFnContext::default();
+        let input = T::In::retrieve(&mut iter, &context).map_err(|e| {
+            anyhow!(
+                "Failed to pass the arguments ({arguments}) to the command {}: {e}",
+                self.describe()
+            )
+        })?;
+        let superfluous_arguments = iter.count();
+        if superfluous_arguments != 0 {
+            bail!(
+                "Passed {superfluous_arguments} more argument(s) than accepted to the command {}. Received: ({arguments})",
+                self.describe()
+            );
+        }
         let task = YarnCommand::run(&mut self.function, input, param);
         system_state.apply(world);
-        Box::new(task)
+        Ok(Box::new(task))
     }
 
     fn clone_box(&self) -> Box<dyn UntypedYarnCommand> {
@@ -194,11 +214,23 @@ where
     }
 }
 
+fn format_arguments(input: &[YarnValue]) -> String {
+    input
+        .iter()
+        .map(|value| match value {
+            YarnValue::String(value) => format!("{value:?}"),
+            value => value.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 pub(crate) struct YarnCommandWrapper<Marker, F>
 where
     F: YarnCommand<Marker>,
 {
     function: F,
+    name: Option<Cow<'static, str>>,
 
     // NOTE: PhantomData<fn()-> T> gives this safe Send/Sync impls
     _marker: PhantomData<fn() -> Marker>,
@@ -211,6 +243,7 @@ where
     fn clone(&self) -> Self {
         Self {
             function: self.function.clone(),
+            name: self.name.clone(),
             _marker: PhantomData,
         }
     }
@@ -223,11 +256,31 @@ where
     fn from(function: F) -> Self {
         Self {
             function,
+            name: None,
             _marker: PhantomData,
         }
     }
 }
 
+impl<Marker, F> YarnCommandWrapper<Marker, F>
+where
+    F: YarnCommand<Marker>,
+{
+    pub(crate) fn with_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.name.replace(name.into());
+        self
+    }
+
+    /// The name under which the command was registered, followed by its signature. Used for error messages.
+    fn describe(&self) -> String {
+        let signature = std::any::type_name::<Marker>();
+        match &self.name {
+            Some(name) => format!("\"{name}\" with the signature {signature}"),
+            None => signature.to_owned(),
+        }
+    }
+}
+
 impl<Marker, F> Debug for YarnCommandWrapper<Marker, F>
 where
     F: YarnCommand<Marker>,
@@ -1,7 +1,10 @@
+use crate::commands::command_registry::wait::Wait;
 use crate::commands::UntypedYarnCommand;
 use crate::dialogue_runner::DialogueExecutionSystemSet;
-use crate::events::ExecuteCommandEvent;
+use crate::error_handling::{report_error_in_world, YarnErrorContext};
+use crate::events::{ExecuteCommandEvent, YarnErrorEvent};
 use crate::prelude::*;
+use crate::scene_transition::SceneTransitions;
 use bevy::ecs::event::ManualEventReader;
 use bevy::prelude::*;
 
@@ -21,7 +24,28 @@ fn execute_commands(world: &mut World, mut reader: Local<ManualEventReader<Execu
             continue;
         };
         let params = event.command.parameters;
-        let task_finished_indicator = command.call(params, world);
+        let wait_periods_before = world.resource::<Wait>().len();
+        let scene_transitions_before = world
+            .get_resource::<SceneTransitions>()
+            .map(SceneTransitions::len);
+        let result = command.call(params, world);
+        world
+            .resource_mut::<Wait>()
+            .set_source_of_periods_since(wait_periods_before, event.source);
+        if let Some(scene_transitions_before) = scene_transitions_before {
+            world
+                .resource_mut::<SceneTransitions>()
+                .set_source_of_transitions_since(scene_transitions_before, event.source);
+        }
+        let task_finished_indicator = match result {
+            Ok(task_finished_indicator) => task_finished_indicator,
+            Err(error) => {
+                let event = YarnErrorEvent::new(error, YarnErrorContext::CommandExecution)
+                    .with_source(event.source);
+                report_error_in_world(world, event);
+                continue;
+            }
+        };
         if !task_finished_indicator.is_finished() {
             get_dialogue_runner_mut(world, event.source).add_command_task(task_finished_indicator);
         }
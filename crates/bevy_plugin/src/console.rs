@@ -0,0 +1,230 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::prelude::*;
+use anyhow::{anyhow, bail};
+use bevy::prelude::*;
+use std::fmt::Write;
+use std::str::FromStr;
+
+pub(crate) fn console_plugin(app: &mut App) {
+    app.add_event::<DialogueConsoleCommandEvent>().add_systems(
+        Update,
+        run_console_commands
+            .before(DialogueExecutionSystemSet)
+            .in_set(YarnSpinnerSystemSet),
+    );
+}
+
+/// Send this event to run a [`DialogueConsoleCommand`] on a [`DialogueRunner`], e.g. from a developer console or a keyboard shortcut.
+/// Commands that cannot be run, like starting a node that does not exist, are logged as errors instead of panicking.
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::{events::*, prelude::*};
+/// fn on_console_input(input: In<String>, mut events: EventWriter<DialogueConsoleCommandEvent>) {
+///     match input.0.parse::<DialogueConsoleCommand>() {
+///         Ok(command) => {
+///             events.send(DialogueConsoleCommandEvent::new(command));
+///         }
+///         Err(e) => error!("{e}"),
+///     }
+/// }
+///
+/// fn skip_on_f5(keys: Res<ButtonInput<KeyCode>>, mut events: EventWriter<DialogueConsoleCommandEvent>) {
+///     if keys.just_pressed(KeyCode::F5) {
+///         events.send(DialogueConsoleCommandEvent::new(DialogueConsoleCommand::ToggleSkipMode));
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Event)]
+pub struct DialogueConsoleCommandEvent {
+    /// The command to run.
+    pub command: DialogueConsoleCommand,
+    /// The [`DialogueRunner`] to run the command on. If [`None`], the command is run on all dialogue runners.
+    pub target: Option<Entity>,
+}
+
+impl DialogueConsoleCommandEvent {
+    /// Creates an event that runs the command on all dialogue runners.
+    pub fn new(command: DialogueConsoleCommand) -> Self {
+        Self {
+            command,
+            target: None,
+        }
+    }
+
+    /// Runs the command only on the [`DialogueRunner`] of the given entity.
+    #[must_use]
+    pub fn with_target(mut self, dialogue_runner: Entity) -> Self {
+        self.target = Some(dialogue_runner);
+        self
+    }
+}
+
+/// A development command for [`DialogueRunner`]s, run by sending a [`DialogueConsoleCommandEvent`].
+///
+/// Can be parsed from text typed into a console:
+/// - `start <node>` or `jump <node>`: [`DialogueConsoleCommand::StartNode`]
+/// - `stop`: [`DialogueConsoleCommand::Stop`]
+/// - `set <$variable> <value>`: [`DialogueConsoleCommand::SetVariable`]. The value is parsed as a bool or number if possible and as a string otherwise. Quotes around strings are optional.
+/// - `skip`, `skip on` or `skip off`: [`DialogueConsoleCommand::ToggleSkipMode`] or [`DialogueConsoleCommand::SetSkipMode`]
+/// - `dump`: [`DialogueConsoleCommand::DumpState`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum DialogueConsoleCommand {
+    /// Starts the given node, stopping the current dialogue first if necessary.
+    StartNode(String),
+    /// Stops the dialogue, see [`DialogueRunner::stop`].
+    Stop,
+    /// Sets a variable in the [`VariableStorage`] of the dialogue runner.
+    SetVariable {
+        /// The name of the variable, including the leading `$`.
+        name: String,
+        /// The new value.
+        value: YarnValue,
+    },
+    /// Turns skip mode on or off, see [`DialogueRunner::set_skip_mode`].
+    SetSkipMode(bool),
+    /// Toggles skip mode, see [`DialogueRunner::set_skip_mode`].
+    ToggleSkipMode,
+    /// Logs the output of [`DialogueRunner::dump_state`].
+    DumpState,
+}
+
+impl FromStr for DialogueConsoleCommand {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let input = input.trim();
+        let (command, arguments) = input
+            .split_once(char::is_whitespace)
+            .map(|(command, arguments)| (command, arguments.trim()))
+            .unwrap_or((input, ""));
+        let command = match (command, arguments) {
+            ("start" | "jump", "") => bail!("Usage: {command} <node>"),
+            ("start" | "jump", node_name) => Self::StartNode(node_name.to_owned()),
+            ("stop", "") => Self::Stop,
+            ("set", arguments) => {
+                let (name, value) = arguments
+                    .split_once(char::is_whitespace)
+                    .ok_or_else(|| anyhow!("Usage: set <$variable> <value>"))?;
+                if !name.starts_with('$') {
+                    bail!("Variable names must start with a '$', but found \"{name}\"");
+                }
+                Self::SetVariable {
+                    name: name.to_owned(),
+                    value: parse_value(value.trim()),
+                }
+            }
+            ("skip", "") => Self::ToggleSkipMode,
+            ("skip", "on") => Self::SetSkipMode(true),
+            ("skip", "off") => Self::SetSkipMode(false),
+            ("dump", "") => Self::DumpState,
+            _ => bail!(
+                "Unknown dialogue command \"{input}\". Expected one of: start <node>, jump <node>, stop, set <$variable> <value>, skip [on|off], dump"
+            ),
+        };
+        Ok(command)
+    }
+}
+
+fn parse_value(value: &str) -> YarnValue {
+    if let Ok(boolean) = value.parse::<bool>() {
+        return boolean.into();
+    }
+    if let Ok(number) = value.parse::<f64>() {
+        return number.into();
+    }
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value)
+        .to_owned()
+        .into()
+}
+
+impl DialogueRunner {
+    /// Returns a human-readable summary of the state of this runner for debugging: the current node, whether it is running, paused, skipping or waiting for an option,
+    /// and the values of all variables in its [`VariableStorage`].
+    #[must_use]
+    pub fn dump_state(&self) -> String {
+        let mut dump = String::new();
+        let node = self.current_node();
+        writeln!(dump, "Current node: {}", node.as_deref().unwrap_or("-")).unwrap();
+        writeln!(dump, "Running: {}", self.is_running()).unwrap();
+        writeln!(dump, "Paused: {}", self.is_paused()).unwrap();
+        writeln!(dump, "Skipping: {}", self.is_skipping()).unwrap();
+        writeln!(
+            dump,
+            "Waiting for option selection: {}",
+            self.is_waiting_for_option_selection()
+        )
+        .unwrap();
+        let mut variables: Vec<_> = self.variable_storage().variables().into_iter().collect();
+        variables.sort_by(|(a, _), (b, _)| a.cmp(b));
+        writeln!(dump, "Variables:").unwrap();
+        for (name, value) in variables {
+            writeln!(dump, "  {name} = {value}").unwrap();
+        }
+        dump
+    }
+
+    fn run_console_command(&mut self, command: &DialogueConsoleCommand) -> Result<()> {
+        match command {
+            DialogueConsoleCommand::StartNode(node_name) => {
+                if !self.node_exists(node_name) {
+                    bail!("Can't start node \"{node_name}\": no node with that name exists");
+                }
+                if self.is_running() {
+                    self.stop();
+                }
+                self.start_node(node_name);
+            }
+            DialogueConsoleCommand::Stop => {
+                self.stop();
+            }
+            DialogueConsoleCommand::SetVariable { name, value } => {
+                self.variable_storage_mut()
+                    .set(name.clone(), value.clone())?;
+            }
+            DialogueConsoleCommand::SetSkipMode(skipping) => {
+                self.set_skip_mode(*skipping);
+            }
+            DialogueConsoleCommand::ToggleSkipMode => {
+                let skipping = !self.is_skipping();
+                self.set_skip_mode(skipping);
+            }
+            DialogueConsoleCommand::DumpState => {
+                info!("{}", self.dump_state());
+            }
+        }
+        Ok(())
+    }
+}
+
+fn run_console_commands(
+    mut events: EventReader<DialogueConsoleCommandEvent>,
+    mut dialogue_runners: Query<(Entity, &mut DialogueRunner)>,
+) {
+    for event in events.read() {
+        let mut found_target = false;
+        for (entity, mut dialogue_runner) in dialogue_runners.iter_mut() {
+            if event.target.is_some_and(|target| target != entity) {
+                continue;
+            }
+            found_target = true;
+            if let Err(e) = dialogue_runner.run_console_command(&event.command) {
+                error!(
+                    "Failed to run dialogue command {:?} on entity {entity}: {e}",
+                    event.command
+                );
+            }
+        }
+        if !found_target {
+            error!(
+                "Failed to run dialogue command {:?}: no matching DialogueRunner exists",
+                event.command
+            );
+        }
+    }
+}
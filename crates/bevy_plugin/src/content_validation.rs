@@ -0,0 +1,422 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::events::YarnProjectCompiledEvent;
+use crate::plugin::AssetRoot;
+use crate::prelude::*;
+use crate::project::CompilationSystemSet;
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use std::fmt::{self, Display};
+use std::path::Path;
+use yarnspinner::core::{Instruction, OpCode};
+
+pub(crate) fn content_validation_plugin(app: &mut App) {
+    app.add_event::<ValidateContentEvent>()
+        .init_resource::<ContentValidationReport>()
+        .add_systems(
+            Update,
+            validate_content
+                .after(CompilationSystemSet)
+                .before(DialogueExecutionSystemSet)
+                .run_if(resource_exists::<YarnProject>)
+                .in_set(YarnSpinnerSystemSet),
+        );
+}
+
+/// Sets when the content of the [`YarnProject`] is validated. Set with [`YarnSpinnerPlugin::with_content_validation`]
+/// or [`LoadYarnProjectEvent::with_content_validation`](crate::deferred_loading::LoadYarnProjectEvent::with_content_validation).
+///
+/// The validation checks that
+/// - every line has a translation in the strings file of every [`Localization`] in the [`Localizations`],
+/// - every asset expected by the [`AssetProvider`]s of a [`DialogueRunner`] exists, see [`AssetProvider::expected_assets`],
+/// - every command used in the Yarn files is registered in the [`YarnCommands`] of a [`DialogueRunner`],
+/// - every character speaking a line is registered in the [`CharacterRegistry`], if that resource exists.
+///
+/// The files are looked up in the `assets` folder on disk, so the checks for translations and assets are meant to run on desktop platforms, e.g. in a CI build.
+/// The result is written to the [`ContentValidationReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ContentValidation {
+    /// The content is only validated when a [`ValidateContentEvent`] is sent.
+    #[default]
+    Disabled,
+    /// The content is validated every time the project is compiled and every time a [`DialogueRunner`] is spawned.
+    /// Problems are logged as warnings.
+    Report,
+    /// Like [`ContentValidation::Report`], but panics if any problem is found. Useful for failing a shipping build loudly.
+    Strict,
+}
+
+/// Send this event to validate the content of the [`YarnProject`] against all [`DialogueRunner`]s regardless of the configured [`ContentValidation`].
+/// See [`ContentValidation`] for what is checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Event)]
+pub struct ValidateContentEvent {
+    /// Whether to panic if any problem is found. Validation always panics if the project uses [`ContentValidation::Strict`].
+    pub strict: bool,
+}
+
+/// A [`Resource`] containing the result of the most recent content validation. See [`ContentValidation`] for when it is updated.
+/// Its [`Display`] implementation lists all problems in a human-readable way.
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::prelude::*;
+/// fn print_missing_translations(report: Res<ContentValidationReport>) {
+///     for missing_translation in report.missing_translations() {
+///         println!(
+///             "[{}] {} is not translated",
+///             missing_translation.language, missing_translation.line_id,
+///         );
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Resource)]
+pub struct ContentValidationReport {
+    missing_translations: Vec<MissingTranslation>,
+    missing_assets: Vec<MissingAsset>,
+    unregistered_commands: Vec<UnregisteredCommand>,
+    unknown_speakers: Vec<UnknownSpeaker>,
+}
+
+/// A line that has no translation in the strings file of a language. Part of the [`ContentValidationReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MissingTranslation {
+    /// The language that the line is not translated to.
+    pub language: Language,
+    /// The ID of the untranslated line.
+    pub line_id: LineId,
+}
+
+/// An asset that an [`AssetProvider`] of a [`DialogueRunner`] expects but that does not exist. Part of the [`ContentValidationReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MissingAsset {
+    /// The entity of the [`DialogueRunner`] whose [`AssetProvider`] expects the asset.
+    pub dialogue_runner: Entity,
+    /// The missing asset.
+    pub asset: ExpectedAsset,
+}
+
+/// A command that is used in the Yarn files but not registered in the [`YarnCommands`] of a [`DialogueRunner`]. Part of the [`ContentValidationReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnregisteredCommand {
+    /// The entity of the [`DialogueRunner`] that is missing the command.
+    pub dialogue_runner: Entity,
+    /// The name of the command.
+    pub name: String,
+    /// The node in which the command is used.
+    pub node: String,
+}
+
+/// A line spoken by a character that is not registered in the [`CharacterRegistry`]. Part of the [`ContentValidationReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnknownSpeaker {
+    /// The character name as written in the line, e.g. `Hag` for `Hag: Now your *third* wish.`
+    pub name: String,
+    /// The ID of the line spoken by the character.
+    pub line_id: LineId,
+}
+
+impl ContentValidationReport {
+    /// Returns all lines that are missing a translation, sorted by language and [`LineId`].
+    #[must_use]
+    pub fn missing_translations(&self) -> &[MissingTranslation] {
+        &self.missing_translations
+    }
+
+    /// Returns all assets that are expected by an [`AssetProvider`] but could not be found.
+    #[must_use]
+    pub fn missing_assets(&self) -> &[MissingAsset] {
+        &self.missing_assets
+    }
+
+    /// Returns the missing assets that are localized for the given language, e.g. voice lines that were not recorded in it yet.
+    pub fn missing_assets_for_language<'a>(
+        &'a self,
+        language: &'a Language,
+    ) -> impl Iterator<Item = &'a MissingAsset> + 'a {
+        self.missing_assets
+            .iter()
+            .filter(move |missing_asset| missing_asset.asset.language.as_ref() == Some(language))
+    }
+
+    /// Groups the missing localized assets by their language. Missing assets that are the same in all languages are left out.
+    #[must_use]
+    pub fn missing_assets_by_language(&self) -> HashMap<Language, Vec<&MissingAsset>> {
+        let mut missing_assets_by_language: HashMap<_, Vec<_>> = HashMap::new();
+        for missing_asset in &self.missing_assets {
+            if let Some(language) = missing_asset.asset.language.as_ref() {
+                missing_assets_by_language
+                    .entry(language.clone())
+                    .or_default()
+                    .push(missing_asset);
+            }
+        }
+        missing_assets_by_language
+    }
+
+    /// Returns all commands that are used in the Yarn files but are not registered.
+    #[must_use]
+    pub fn unregistered_commands(&self) -> &[UnregisteredCommand] {
+        &self.unregistered_commands
+    }
+
+    /// Returns all lines spoken by characters that are not in the [`CharacterRegistry`], sorted by [`LineId`].
+    /// Always empty if the [`CharacterRegistry`] resource does not exist.
+    #[must_use]
+    pub fn unknown_speakers(&self) -> &[UnknownSpeaker] {
+        &self.unknown_speakers
+    }
+
+    /// Returns the number of problems found.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.missing_translations.len()
+            + self.missing_assets.len()
+            + self.unregistered_commands.len()
+            + self.unknown_speakers.len()
+    }
+
+    /// Returns `true` if no problems were found.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn validate<'a>(
+        project: &YarnProject,
+        dialogue_runners: impl Iterator<Item = (Entity, &'a DialogueRunner)>,
+        asset_root: &Path,
+        character_registry: Option<&CharacterRegistry>,
+    ) -> Self {
+        let mut line_ids: Vec<_> = project.compilation.string_table.keys().cloned().collect();
+        line_ids.sort_by(|lhs, rhs| lhs.0.cmp(&rhs.0));
+        let commands = used_commands(project);
+
+        let mut report = Self {
+            missing_translations: missing_translations(project, &line_ids, asset_root),
+            unknown_speakers: character_registry
+                .map(|registry| unknown_speakers(project, &line_ids, registry))
+                .unwrap_or_default(),
+            ..default()
+        };
+        for (entity, dialogue_runner) in dialogue_runners {
+            for asset_provider in dialogue_runner.asset_providers() {
+                report.missing_assets.extend(
+                    asset_provider
+                        .expected_assets(&line_ids)
+                        .into_iter()
+                        .filter(|asset| {
+                            !asset
+                                .paths
+                                .iter()
+                                .any(|path| asset_root.join(path).is_file())
+                        })
+                        .map(|asset| MissingAsset {
+                            dialogue_runner: entity,
+                            asset,
+                        }),
+                );
+            }
+            report.unregistered_commands.extend(
+                commands
+                    .iter()
+                    .filter(|(name, _node)| !dialogue_runner.commands().contains_key(name))
+                    .map(|(name, node)| UnregisteredCommand {
+                        dialogue_runner: entity,
+                        name: name.clone(),
+                        node: node.clone(),
+                    }),
+            );
+        }
+        report.missing_assets.sort_by(|lhs, rhs| {
+            lhs.dialogue_runner
+                .cmp(&rhs.dialogue_runner)
+                .then_with(|| lhs.asset.paths.cmp(&rhs.asset.paths))
+        });
+        report
+    }
+}
+
+impl Display for ContentValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Found {} problems in the Yarn project:", self.len())?;
+        for MissingTranslation { language, line_id } in &self.missing_translations {
+            write!(f, "\n- Line {line_id} is not translated to {language}")?;
+        }
+        for MissingAsset {
+            dialogue_runner,
+            asset,
+        } in &self.missing_assets
+        {
+            let paths = asset
+                .paths
+                .iter()
+                .map(|path| format!("\"{}\"", path.display()))
+                .collect::<Vec<_>>()
+                .join(" or ");
+            write!(
+                f,
+                "\n- Dialogue runner {dialogue_runner} is missing the asset {paths}"
+            )?;
+            if let Some(line_id) = &asset.line_id {
+                write!(f, " for line {line_id}")?;
+            }
+            if let Some(language) = &asset.language {
+                write!(f, " in {language}")?;
+            }
+        }
+        for UnregisteredCommand {
+            dialogue_runner,
+            name,
+            node,
+        } in &self.unregistered_commands
+        {
+            write!(
+                f,
+                "\n- Dialogue runner {dialogue_runner} has no command named \"{name}\", which is used in node \"{node}\""
+            )?;
+        }
+        for UnknownSpeaker { name, line_id } in &self.unknown_speakers {
+            write!(
+                f,
+                "\n- Line {line_id} is spoken by \"{name}\", who is not in the character registry"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn validate_content(
+    mut validate_content_events: EventReader<ValidateContentEvent>,
+    mut compiled_events: EventReader<YarnProjectCompiledEvent>,
+    added_dialogue_runners: Query<(), Added<DialogueRunner>>,
+    dialogue_runners: Query<(Entity, &DialogueRunner)>,
+    project: Res<YarnProject>,
+    asset_root: Res<AssetRoot>,
+    character_registry: Option<Res<CharacterRegistry>>,
+    mut report: ResMut<ContentValidationReport>,
+) {
+    let requested_strictness = validate_content_events.read().fold(None, |strict, event| {
+        Some(strict.unwrap_or_default() || event.strict)
+    });
+    let project_changed = compiled_events.read().any(|event| event.project.is_none())
+        || !added_dialogue_runners.is_empty();
+    let automatic = project.content_validation != ContentValidation::Disabled && project_changed;
+    if requested_strictness.is_none() && !automatic {
+        return;
+    }
+    let strict = requested_strictness == Some(true)
+        || project.content_validation == ContentValidation::Strict;
+
+    let dialogue_runners = dialogue_runners
+        .iter()
+        .filter(|(_, dialogue_runner)| dialogue_runner.project_id() == project.id());
+    *report = ContentValidationReport::validate(
+        &project,
+        dialogue_runners,
+        &asset_root.0,
+        character_registry.as_deref(),
+    );
+    if report.is_empty() {
+        info!("Content validation found no problems in the Yarn project");
+        return;
+    }
+    assert!(
+        !strict,
+        "Content validation failed. {}\nFix these problems or disable strict content validation.",
+        *report
+    );
+    warn!("{}", *report);
+}
+
+fn missing_translations(
+    project: &YarnProject,
+    line_ids: &[LineId],
+    asset_root: &Path,
+) -> Vec<MissingTranslation> {
+    let Some(localizations) = project.localizations.as_ref() else {
+        return Vec::new();
+    };
+    localizations
+        .translations
+        .iter()
+        .flat_map(|localization| {
+            let path = asset_root.join(&localization.strings_file);
+            let translated: HashSet<_> = match StringsFile::read_asset(&path) {
+                Ok(strings_file) => strings_file
+                    .iter()
+                    .map(|(line_id, _record)| line_id.clone())
+                    .collect(),
+                Err(e) => {
+                    warn!("{e}");
+                    HashSet::new()
+                }
+            };
+            line_ids
+                .iter()
+                .filter(move |line_id| !translated.contains(*line_id))
+                .map(|line_id| MissingTranslation {
+                    language: localization.language.clone(),
+                    line_id: line_id.clone(),
+                })
+        })
+        .collect()
+}
+
+fn unknown_speakers(
+    project: &YarnProject,
+    line_ids: &[LineId],
+    character_registry: &CharacterRegistry,
+) -> Vec<UnknownSpeaker> {
+    line_ids
+        .iter()
+        .filter_map(|line_id| {
+            let text = &project.compilation.string_table.get(line_id)?.text;
+            let name = speaker_name(text)?;
+            (!character_registry.contains(name)).then(|| UnknownSpeaker {
+                name: name.to_owned(),
+                line_id: line_id.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Returns the character name of a line the same way [`LocalizedLine::character_name`] does, i.e. the text before the first colon.
+/// Names that are only known at runtime because they contain an interpolated expression or markup are skipped.
+fn speaker_name(text: &str) -> Option<&str> {
+    let (name, _) = text.split_once(':')?;
+    (!name.is_empty() && !name.contains(['{', '['])).then_some(name)
+}
+
+/// Returns the names of all commands used in the program together with the node they are used in, sorted by node.
+/// Commands whose name is only known at runtime because it contains an interpolated expression are skipped.
+fn used_commands(project: &YarnProject) -> Vec<(String, String)> {
+    let Some(program) = project.compilation.program.as_ref() else {
+        return Vec::new();
+    };
+    let mut commands: Vec<_> = program
+        .nodes
+        .iter()
+        .flat_map(|(node_name, node)| {
+            node.instructions
+                .iter()
+                .filter_map(command_name)
+                .map(move |name| (name, node_name.clone()))
+        })
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    commands.sort_by(|(lhs_name, lhs_node), (rhs_name, rhs_node)| {
+        lhs_node.cmp(rhs_node).then_with(|| lhs_name.cmp(rhs_name))
+    });
+    commands
+}
+
+fn command_name(instruction: &Instruction) -> Option<String> {
+    if instruction.opcode != OpCode::RunCommand as i32 {
+        return None;
+    }
+    let command_text: String = instruction.read_operand(0);
+    let name = command_text.split_whitespace().next()?;
+    (!name.contains('{')).then(|| name.to_owned())
+}
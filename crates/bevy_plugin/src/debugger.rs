@@ -0,0 +1,279 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::events::*;
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_egui::{egui, EguiContexts, EguiPlugin};
+use std::collections::VecDeque;
+
+/// Adds an [egui](https://docs.rs/egui) overlay that shows the current node, the most recent lines and the pending options of a [`DialogueRunner`].
+/// It also lets you edit all Yarn variables and jump to any node. Press [`DialogueDebugger::toggle_key`] to show or hide it.
+///
+/// Adds [`EguiPlugin`] if it was not added yet. Requires the `debugger` feature.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct YarnSpinnerDebuggerPlugin;
+
+impl Plugin for YarnSpinnerDebuggerPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin);
+        }
+        app.init_resource::<DialogueDebugger>().add_systems(
+            Update,
+            (
+                toggle_debugger,
+                record_dialogue_history,
+                show_debugger.run_if(debugger_visible),
+            )
+                .chain()
+                .after(DialogueExecutionSystemSet)
+                .in_set(YarnSpinnerSystemSet),
+        );
+    }
+}
+
+/// The state of the overlay added by [`YarnSpinnerDebuggerPlugin`].
+#[derive(Debug, Clone, Resource)]
+pub struct DialogueDebugger {
+    /// Whether the overlay is shown. Defaults to `false`.
+    pub visible: bool,
+    /// The key that shows or hides the overlay. Set to [`None`] to only toggle it through [`DialogueDebugger::visible`]. Defaults to [`KeyCode::F12`].
+    pub toggle_key: Option<KeyCode>,
+    /// The [`DialogueRunner`] that is inspected. If [`None`] or if the entity has no runner, the first running one is used.
+    pub dialogue_runner: Option<Entity>,
+    /// How many of the most recent lines are remembered per [`DialogueRunner`]. Defaults to 20.
+    pub history_length: usize,
+    history: HashMap<Entity, VecDeque<String>>,
+    pending_options: HashMap<Entity, Vec<DialogueOption>>,
+    node_search: String,
+}
+
+impl Default for DialogueDebugger {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            toggle_key: Some(KeyCode::F12),
+            dialogue_runner: None,
+            history_length: 20,
+            history: default(),
+            pending_options: default(),
+            node_search: default(),
+        }
+    }
+}
+
+impl DialogueDebugger {
+    /// Returns the most recent lines presented by the given [`DialogueRunner`], oldest first.
+    pub fn recent_lines(&self, dialogue_runner: Entity) -> impl Iterator<Item = &str> {
+        self.history
+            .get(&dialogue_runner)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    /// Returns the options the given [`DialogueRunner`] is waiting on, if any.
+    #[must_use]
+    pub fn pending_options(&self, dialogue_runner: Entity) -> &[DialogueOption] {
+        self.pending_options
+            .get(&dialogue_runner)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+fn debugger_visible(debugger: Res<DialogueDebugger>) -> bool {
+    debugger.visible
+}
+
+fn toggle_debugger(
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    mut debugger: ResMut<DialogueDebugger>,
+) {
+    let Some(toggle_key) = debugger.toggle_key else {
+        return;
+    };
+    if keys.is_some_and(|keys| keys.just_pressed(toggle_key)) {
+        debugger.visible = !debugger.visible;
+    }
+}
+
+fn record_dialogue_history(
+    mut present_line_events: EventReader<PresentLineEvent>,
+    mut present_options_events: EventReader<PresentOptionsEvent>,
+    mut dialogue_complete_events: EventReader<DialogueCompleteEvent>,
+    mut debugger: ResMut<DialogueDebugger>,
+) {
+    let debugger = debugger.as_mut();
+    for event in present_line_events.read() {
+        debugger.pending_options.remove(&event.source);
+        let history = debugger.history.entry(event.source).or_default();
+        history.push_back(event.line.text.clone());
+        while history.len() > debugger.history_length {
+            history.pop_front();
+        }
+    }
+    for event in present_options_events.read() {
+        debugger
+            .pending_options
+            .insert(event.source, event.options.clone());
+    }
+    for event in dialogue_complete_events.read() {
+        debugger.pending_options.remove(&event.source);
+    }
+}
+
+fn show_debugger(
+    mut contexts: EguiContexts,
+    mut debugger: ResMut<DialogueDebugger>,
+    mut dialogue_runners: Query<(Entity, &mut DialogueRunner)>,
+    project: Option<Res<YarnProject>>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+    let debugger = debugger.as_mut();
+    let selected = debugger
+        .dialogue_runner
+        .filter(|entity| dialogue_runners.contains(*entity))
+        .or_else(|| {
+            dialogue_runners
+                .iter()
+                .find(|(_, dialogue_runner)| dialogue_runner.is_running())
+                .or_else(|| dialogue_runners.iter().next())
+                .map(|(entity, _)| entity)
+        });
+    let runner_entities: Vec<_> = dialogue_runners.iter().map(|(entity, _)| entity).collect();
+
+    egui::Window::new("Yarn Spinner").show(ctx, |ui| {
+        let mut choice = selected;
+        egui::ComboBox::from_label("Dialogue runner")
+            .selected_text(selected.map_or("None".to_owned(), |entity| entity.to_string()))
+            .show_ui(ui, |ui| {
+                for entity in &runner_entities {
+                    ui.selectable_value(&mut choice, Some(*entity), entity.to_string());
+                }
+            });
+        if choice != selected {
+            debugger.dialogue_runner = choice;
+        }
+        let Some(entity) = selected else {
+            ui.label("No dialogue runner exists.");
+            return;
+        };
+        let Ok((_, mut dialogue_runner)) = dialogue_runners.get_mut(entity) else {
+            return;
+        };
+
+        let status = if !dialogue_runner.is_running() {
+            "stopped"
+        } else if dialogue_runner.is_paused() {
+            "paused"
+        } else {
+            "running"
+        };
+        ui.label(format!(
+            "Node: {} ({status})",
+            dialogue_runner.current_node().as_deref().unwrap_or("-")
+        ));
+
+        ui.collapsing("Recent lines", |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for line in debugger.recent_lines(entity) {
+                        ui.label(line);
+                    }
+                });
+        });
+
+        let options = debugger.pending_options(entity).to_vec();
+        if dialogue_runner.is_waiting_for_option_selection() && !options.is_empty() {
+            ui.collapsing("Options", |ui| {
+                for option in options {
+                    let text = if option.is_available {
+                        option.line.text.clone()
+                    } else {
+                        format!("{} (unavailable)", option.line.text)
+                    };
+                    if ui.button(text).clicked() {
+                        if let Err(e) = dialogue_runner.select_option(option.id) {
+                            error!("Failed to select option from dialogue debugger: {e}");
+                        }
+                    }
+                }
+            });
+        }
+
+        ui.collapsing("Variables", |ui| {
+            show_variables(ui, &mut dialogue_runner, project.as_deref());
+        });
+
+        ui.collapsing("Jump to node", |ui| {
+            ui.text_edit_singleline(&mut debugger.node_search);
+            let search = debugger.node_search.to_lowercase();
+            let mut node_names: Vec<_> = dialogue_runner
+                .inner()
+                .node_names()
+                .filter(|name| name.to_lowercase().contains(&search))
+                .map(ToOwned::to_owned)
+                .collect();
+            node_names.sort();
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    for node_name in node_names {
+                        if ui.button(&node_name).clicked() {
+                            if dialogue_runner.is_running() {
+                                dialogue_runner.stop();
+                            }
+                            dialogue_runner.start_node(&node_name);
+                        }
+                    }
+                });
+        });
+    });
+}
+
+fn show_variables(
+    ui: &mut egui::Ui,
+    dialogue_runner: &mut DialogueRunner,
+    project: Option<&YarnProject>,
+) {
+    let mut variables = dialogue_runner.variable_storage().variables();
+    for declaration in project
+        .into_iter()
+        .flat_map(|project| &project.compilation().declarations)
+    {
+        if let Some(default_value) = &declaration.default_value {
+            variables
+                .entry(declaration.name.clone())
+                .or_insert_with(|| default_value.clone());
+        }
+    }
+    let mut variables: Vec<_> = variables.into_iter().collect();
+    variables.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    egui::Grid::new("yarn_variables").show(ui, |ui| {
+        for (name, mut value) in variables {
+            ui.label(&name);
+            let changed = match &mut value {
+                YarnValue::Number(number) => ui.add(egui::DragValue::new(number)).changed(),
+                YarnValue::String(string) => ui.text_edit_singleline(string).changed(),
+                YarnValue::Boolean(boolean) => ui.checkbox(boolean, "").changed(),
+                other => {
+                    ui.label(other.to_string());
+                    false
+                }
+            };
+            if changed {
+                if let Err(e) = dialogue_runner.variable_storage_mut().set(name, value) {
+                    error!("Failed to set variable from dialogue debugger: {e}");
+                }
+            }
+            ui.end_row();
+        }
+    });
+}
@@ -18,7 +18,8 @@ pub enum DevelopmentFileGeneration {
     /// - Marks lines in strings files that have been changed since they were translated by appending "NEEDS UPDATE" to the respective line texts.
     ///
     /// It is recommended to combine this setting with Bevy's [hot reload functionality](https://bevy-cheatbook.github.io/assets/hot-reload.html).
-    /// Note that because of the extensive use of the filesystem, this setting is not available on Wasm or Android.
+    /// Note that because of the extensive use of the filesystem, this setting is not available on Wasm or Android
+    /// and requires the `development_file_generation` feature, which is enabled by default.
     Full,
     /// The recommended setting for shipping the game:
     /// - Does not change any Yarn or strings files on disk.
@@ -27,17 +28,26 @@ pub enum DevelopmentFileGeneration {
 }
 
 impl DevelopmentFileGeneration {
-    /// [`DevelopmentFileGeneration::Full`] on all platforms except Wasm and Android, [`DevelopmentFileGeneration::None`] otherwise.
-    pub const TRY_FULL: Self = {
-        #[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
-        {
-            Self::Full
-        }
-        #[cfg(any(target_arch = "wasm32", target_os = "android"))]
-        {
-            Self::None
-        }
+    /// [`DevelopmentFileGeneration::Full`] on all platforms except Wasm and Android if the `development_file_generation` feature is enabled,
+    /// [`DevelopmentFileGeneration::None`] otherwise.
+    pub const TRY_FULL: Self = if Self::FULL_SUPPORTED {
+        Self::Full
+    } else {
+        Self::None
     };
+
+    const FULL_SUPPORTED: bool = cfg!(all(
+        feature = "development_file_generation",
+        not(any(target_arch = "wasm32", target_os = "android"))
+    ));
+
+    pub(crate) fn assert_supported(self) {
+        assert!(
+            self == Self::None || Self::FULL_SUPPORTED,
+            "Failed to build Yarn Spinner plugin: Only `DevelopmentFileGeneration::None` is supported on this platform. \
+            `DevelopmentFileGeneration::Full` requires the \"development_file_generation\" feature and a platform with filesystem access."
+        );
+    }
 }
 
 impl Default for DevelopmentFileGeneration {
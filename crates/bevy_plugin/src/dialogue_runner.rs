@@ -1,9 +1,11 @@
 pub use self::events::{
     DialogueCompleteEvent, DialogueStartEvent, ExecuteCommandEvent, LineHintsEvent,
-    NodeCompleteEvent, NodeStartEvent, PresentLineEvent, PresentOptionsEvent,
+    LineHintsReadyEvent, MissingTranslationEvent, NodeCompleteEvent, NodeStartEvent,
+    OptionSelectedEvent, PresentLineEvent, PresentOptionsEvent, VariableChangedEvent,
 };
 pub use self::{
     builder::DialogueRunnerBuilder,
+    choice_timer::{ChoiceFallback, ChoiceTimer, ChoiceTimerTickEvent},
     dialogue_option::DialogueOption,
     inner::{InnerDialogue, InnerDialogueMut},
     localized_line::LocalizedLine,
@@ -11,25 +13,39 @@ pub use self::{
 use crate::commands::TaskFinishedIndicator;
 use crate::line_provider::LineAssets;
 use crate::prelude::*;
+use crate::replication::{RemotePresenter, ReplicatedDialogueEvent};
+use crate::system_functions::SystemFunctionCalls;
+use crate::variable_bindings::VariableBinding;
 use crate::UnderlyingYarnLine;
 use anyhow::{anyhow, bail};
+use auto_advance::AutoAdvance;
 use bevy::asset::LoadedUntypedAsset;
 use bevy::utils::HashSet;
 use bevy::{prelude::*, utils::HashMap};
-pub(crate) use runtime_interaction::DialogueExecutionSystemSet;
+use choice_timer::ChoiceTimers;
+use line_acknowledgment::LineAcknowledgments;
+use random::DialogueRandom;
+pub use runtime_interaction::DialogueExecutionSystemSet;
 use std::any::TypeId;
 use std::fmt::Debug;
+use yarnspinner::compiler::SignatureManifest;
 use yarnspinner::core::Library;
 
+mod auto_advance;
 mod builder;
+mod choice_timer;
 mod dialogue_option;
 mod events;
 mod inner;
+mod line_acknowledgment;
 mod localized_line;
+mod random;
 mod runtime_interaction;
 
 pub(crate) fn dialogue_plugin(app: &mut App) {
     app.add_plugins(runtime_interaction::runtime_interaction_plugin)
+        .add_plugins(auto_advance::auto_advance_plugin)
+        .add_plugins(choice_timer::choice_timer_plugin)
         .add_plugins(localized_line::localized_line_plugin)
         .add_plugins(events::dialogue_runner_events_plugin)
         .add_plugins(dialogue_option::dialogue_option_plugin)
@@ -46,14 +62,27 @@ pub struct DialogueRunner {
     asset_providers: HashMap<TypeId, Box<dyn AssetProvider>>,
     pub(crate) will_continue_in_next_update: bool,
     pub(crate) last_selected_option: Option<OptionId>,
+    pub(crate) unreported_selected_option: Option<OptionId>,
     pub(crate) commands: YarnCommands,
     command_tasks: Vec<Box<dyn TaskFinishedIndicator>>,
     localizations: Option<Localizations>,
     pub(crate) is_running: bool,
+    is_paused: bool,
     run_selected_options_as_lines: bool,
     pub(crate) just_started: bool,
     pub(crate) popped_line_hints: Option<Vec<LineId>>,
+    pub(crate) preloading_line_hints: Option<Vec<LineId>>,
     pub(crate) unsent_events: Vec<DialogueEvent>,
+    pub(crate) has_replayed_events: bool,
+    pub(crate) will_relocalize_current_line: bool,
+    pub(crate) system_function_calls: SystemFunctionCalls,
+    pub(crate) variable_bindings: Vec<VariableBinding>,
+    pub(crate) auto_advance: AutoAdvance,
+    pub(crate) choice_timers: ChoiceTimers,
+    pub(crate) line_acknowledgments: LineAcknowledgments,
+    pub(crate) project_id: YarnProjectId,
+    pub(crate) random: DialogueRandom,
+    pub(crate) presenter: Option<RemotePresenter>,
 }
 
 impl DialogueRunner {
@@ -64,6 +93,8 @@ impl DialogueRunner {
     /// - The text provider has finished loading its lines, indicated by [`TextProvider::are_lines_available`](yarnspinner::prelude::TextProvider::are_lines_available) returning `true`.
     /// - The asset providers have finished loading their assets, indicated by all [`AssetProvider::update_asset_availability`] calls returning `true`.
     /// - All previously called [`YarnCommand`]s are finished, indicated by their return type's [`TaskFinishedIndicator::is_finished`] returning `true`.
+    /// - The async Yarn function the dialogue called last, if any, has resolved. Its future is polled once per update.
+    /// - All views registered with [`DialogueRunner::register_view`] have acknowledged the current line with [`DialogueRunner::acknowledge_line`].
     pub fn continue_in_next_update(&mut self) -> &mut Self {
         if !self.is_running {
             panic!("Can't continue dialogue that isn't running. Please call `DialogueRunner::start_node()` before calling `DialogueRunner::continue_in_next_update()`.");
@@ -85,10 +116,19 @@ impl DialogueRunner {
         if !self.is_running {
             bail!("Can't select option {option}: the dialogue is currently not running. Please call `DialogueRunner::continue_in_next_update()` only after receiving a `PresentOptionsEvent`.")
         }
+        if let Some(presenter) = self.presenter.as_mut() {
+            if !presenter.is_waiting_for_option_selection {
+                bail!("Can't select option {option}: the remote dialogue is not waiting for an option selection.")
+            }
+            presenter.requested_option.replace(option);
+            return Ok(self);
+        }
         self.dialogue
             .set_selected_option(option)
             .map_err(Error::from)?;
         self.last_selected_option.replace(option);
+        self.unreported_selected_option.replace(option);
+        self.choice_timers.cancel_countdown();
         self.continue_in_next_update();
         Ok(self)
     }
@@ -102,12 +142,22 @@ impl DialogueRunner {
         self.is_running
     }
 
+    /// Returns whether the providers of this runner are still loading the lines announced by the last [`LineHintsEvent`].
+    /// A [`LineHintsReadyEvent`] is sent once they are done.
+    #[must_use]
+    pub fn is_preloading(&self) -> bool {
+        self.preloading_line_hints.is_some()
+    }
+
     /// Returns whether the dialogue runner is currently waiting for the user to select an option.
     /// If this is true, [`DialogueRunner::select_option`] must be called before the dialogue can continue.
     /// Calling [`DialogueRunner::continue_in_next_update`] will panic in this case.
     #[must_use]
     pub fn is_waiting_for_option_selection(&self) -> bool {
-        self.dialogue.is_waiting_for_option_selection()
+        match &self.presenter {
+            Some(presenter) => presenter.is_waiting_for_option_selection,
+            None => self.dialogue.is_waiting_for_option_selection(),
+        }
     }
 
     /// If set, every line the user selects will emit a [`PresentLineEvent`]. Defaults to `false`.
@@ -125,14 +175,49 @@ impl DialogueRunner {
         self.run_selected_options_as_lines
     }
 
+    /// Pauses the dialogue without stopping it, e.g. while a pause menu is open or a cutscene interrupts the conversation.
+    /// While paused, the dialogue does not advance, no new lines, options or commands are delivered and the builtin `wait` command is frozen.
+    /// Calls to [`DialogueRunner::continue_in_next_update`] are remembered and take effect after [`DialogueRunner::resume`].
+    ///
+    /// Custom [`YarnCommand`]s that take time to finish should check [`DialogueRunner::is_paused`] to freeze themselves as well.
+    pub fn pause(&mut self) -> &mut Self {
+        self.is_paused = true;
+        self
+    }
+
+    /// Resumes a dialogue paused with [`DialogueRunner::pause`] exactly where it was.
+    pub fn resume(&mut self) -> &mut Self {
+        self.is_paused = false;
+        self
+    }
+
+    /// Returns whether the dialogue was paused with [`DialogueRunner::pause`]. Stopping the dialogue also resumes it.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
     /// Stops the execution of the dialogue. Any pending dialogue events will still be sent in the next update, including a [`DialogueCompleteEvent`].
     /// After this, [`DialogueRunner::start_node`] must be called before the dialogue can be advanced again.
     pub fn stop(&mut self) -> &mut Self {
         self.is_running = false;
+        self.is_paused = false;
+        self.auto_advance.cancel_countdown();
+        self.choice_timers.cancel_countdown();
+        self.line_acknowledgments.clear();
         self.last_selected_option = None;
+        self.unreported_selected_option = None;
         self.popped_line_hints = None;
+        self.preloading_line_hints = None;
         self.will_continue_in_next_update = false;
         self.just_started = false;
+        if let Some(presenter) = self.presenter.as_mut() {
+            presenter.pending_events.clear();
+            presenter
+                .pending_events
+                .push_back(ReplicatedDialogueEvent::DialogueComplete);
+            return self;
+        }
         let stop_events = self.dialogue.stop();
         self.unsent_events.extend(stop_events);
         self
@@ -154,6 +239,9 @@ impl DialogueRunner {
         if self.is_running {
             bail!("Can't start dialogue from node {node_name}: the dialogue is currently in the middle of running. Stop the dialogue first.");
         }
+        if self.presenter.is_some() {
+            bail!("Can't start dialogue from node {node_name}: the dialogue runner is presenter-only and mirrors a remote dialogue.");
+        }
         self.is_running = true;
         self.just_started = true;
         self.dialogue
@@ -185,7 +273,10 @@ impl DialogueRunner {
     /// This is [`None`] if [`DialogueRunner::is_running`] is `false`.
     #[must_use]
     pub fn current_node(&self) -> Option<String> {
-        self.dialogue.current_node()
+        match &self.presenter {
+            Some(presenter) => presenter.current_node.clone(),
+            None => self.dialogue.current_node(),
+        }
     }
 
     /// Returns a shallow clone of the registered [`VariableStorage`]. The storage used can be overridden by calling [`DialogueRunnerBuilder::with_variable_storage`].
@@ -200,6 +291,24 @@ impl DialogueRunner {
         self.dialogue.variable_storage_mut()
     }
 
+    /// Returns how many lines, options, commands and nodes this runner delivered. See [`DialogueMetrics`].
+    #[must_use]
+    pub fn metrics(&self) -> DialogueMetrics {
+        self.dialogue.metrics()
+    }
+
+    /// Resets the counters returned by [`DialogueRunner::metrics`] to zero.
+    pub fn reset_metrics(&mut self) -> &mut Self {
+        self.dialogue.reset_metrics();
+        self
+    }
+
+    /// Returns typed access to the flags, counters and visited nodes in the registered [`VariableStorage`]. See [`StoryState`].
+    #[must_use]
+    pub fn story_state(&self) -> StoryState {
+        self.dialogue.story_state()
+    }
+
     /// Returns whether both the text and asset providers have loaded all their lines.
     #[must_use]
     pub fn update_line_availability(
@@ -235,23 +344,113 @@ impl DialogueRunner {
     }
 
     /// Sets the language of the text provider.
+    /// If the dialogue is currently presenting a line, it is presented again in the new language as soon as its translation is loaded.
     pub fn set_text_language(&mut self, language: impl Into<Language>) -> &mut Self {
         let language = language.into();
         self.assert_localizations_available_for_language(&language);
-        self.dialogue.set_language_code(language);
+        self.dialogue.set_language_code(language.clone());
+        self.will_relocalize_current_line = self.is_running;
+        self
+    }
+
+    /// Keeps the text of the given language loaded in addition to the [text language](DialogueRunner::text_language),
+    /// e.g. to display subtitles in two languages at once or to quickly preview another translation.
+    /// Its lines can be looked up with [`TextProvider::get_text_in_language`] once its strings file is loaded.
+    /// Does not change the language the dialogue is presented in.
+    pub fn load_additional_text_language(&mut self, language: impl Into<Language>) -> &mut Self {
+        let language = language.into();
+        self.assert_localizations_available_for_language(&language);
+        self.text_provider.load_additional_language(language);
+        self
+    }
+
+    /// Stops keeping the text of a language loaded by [`DialogueRunner::load_additional_text_language`].
+    pub fn unload_additional_text_language(&mut self, language: impl Into<Language>) -> &mut Self {
+        self.text_provider
+            .unload_additional_language(&language.into());
         self
     }
 
     /// Sets the language of all asset providers. If no asset providers where added via [`DialogueRunnerBuilder::add_asset_provider`], this will do nothing.
+    /// If the dialogue is currently presenting a line, it is presented again with the assets in the new language as soon as they are loaded.
     pub fn set_asset_language(&mut self, language: impl Into<Language>) -> &mut Self {
         let language = language.into();
         self.assert_localizations_available_for_language(&language);
         for asset_provider in self.asset_providers.values_mut() {
             asset_provider.set_language(language.clone().into());
         }
+        self.will_relocalize_current_line = self.is_running;
         self
     }
 
+    /// Seeds the random number generator used by the `random`, `random_range` and `dice` functions and by [`ChoiceFallback::Random`],
+    /// so that the dialogue makes the same random decisions every time it is run from here on. By default, a random seed is used.
+    pub fn set_random_seed(&mut self, seed: u64) -> &mut Self {
+        self.random.reseed(seed);
+        self
+    }
+
+    /// Returns the seed last passed to [`DialogueRunner::set_random_seed`] or [`DialogueRunnerBuilder::with_random_seed`],
+    /// or the random seed chosen when the runner was built.
+    #[must_use]
+    pub fn random_seed(&self) -> u64 {
+        self.random.seed()
+    }
+
+    /// Starts recording every decision of the dialogue into a [`DialogueTrace`], replacing any previous recording.
+    /// See [`Dialogue::start_recording_trace`].
+    pub fn start_recording_trace(&mut self) -> &mut Self {
+        self.dialogue.start_recording_trace();
+        self
+    }
+
+    /// Stops recording and returns the recorded [`DialogueTrace`], if [`DialogueRunner::start_recording_trace`] was called before.
+    pub fn stop_recording_trace(&mut self) -> Option<DialogueTrace> {
+        self.dialogue.stop_recording_trace()
+    }
+
+    /// Re-drives the dialogue from a [`DialogueTrace`] recorded by [`DialogueRunner::start_recording_trace`], see [`Dialogue::replay_trace`].
+    /// All events emitted along the way are sent in the next update like regular ones, e.g. as [`PresentLineEvent`]s.
+    /// Afterwards, the dialogue runs on from where the recording ended.
+    ///
+    /// Fails if the dialogue is already running or diverges from the trace.
+    pub fn replay_trace(&mut self, trace: DialogueTrace) -> Result<&mut Self> {
+        if self.is_running {
+            bail!("Can't replay dialogue trace: the dialogue is currently in the middle of running. Stop the dialogue first.");
+        }
+        if self.presenter.is_some() {
+            bail!("Can't replay dialogue trace: the dialogue runner is presenter-only and mirrors a remote dialogue.");
+        }
+        let events = self
+            .dialogue
+            .replay_trace(trace)
+            .map_err(|e| anyhow!("Can't replay dialogue trace: {e}"))?;
+        self.is_running = self.dialogue.is_active();
+        self.just_started = true;
+        self.has_replayed_events = true;
+        self.unsent_events.extend(events);
+        Ok(self)
+    }
+
+    /// Returns whether this runner was built with [`DialogueRunnerBuilder::presenter_only`].
+    #[must_use]
+    pub fn is_presenter_only(&self) -> bool {
+        self.presenter.is_some()
+    }
+
+    /// Queues an event received from a remote runner marked with [`ReplicateDialogue`] on this presenter-only runner.
+    /// Queued events are presented in order as if this runner had produced them, sending the corresponding [`PresentLineEvent`]s, [`PresentOptionsEvent`]s etc.
+    /// with this runner as their source, so regular dialogue views can display them. At most one line or set of options is presented per update.
+    ///
+    /// Fails if the runner is not presenter-only.
+    pub fn apply_remote_event(&mut self, event: ReplicatedDialogueEvent) -> Result<&mut Self> {
+        let Some(presenter) = self.presenter.as_mut() else {
+            bail!("Can't apply remote dialogue event: the dialogue runner is not presenter-only. Build it with `DialogueRunnerBuilder::presenter_only()`.");
+        };
+        presenter.pending_events.push_back(event);
+        Ok(self)
+    }
+
     fn assert_localizations_available_for_language(&self, language: &Language) {
         let localizations = self.localizations.as_ref().expect(
             "Tried to set language, but no localizations are available. \
@@ -287,6 +486,20 @@ impl DialogueRunner {
         &mut self.commands
     }
 
+    /// Returns the signatures of all functions and the names of all commands registered to this runner, e.g. to write them to disk with
+    /// [`SignatureManifest::to_json`] and type check Yarn files with [`Compiler::import_manifest`](yarnspinner::compiler::Compiler::import_manifest) where the game is not available.
+    #[must_use]
+    pub fn signature_manifest(&self) -> SignatureManifest {
+        SignatureManifest::from_library(self.library()).with_commands(self.commands.names())
+    }
+
+    /// Returns the ID of the [`YarnProject`] this dialogue runner was built from.
+    /// Use it with [`YarnProjects::for_dialogue_runner`] when more than one project is loaded.
+    #[must_use]
+    pub fn project_id(&self) -> YarnProjectId {
+        self.project_id
+    }
+
     /// Returns the language used by the [`TextProvider`]. If there are no [`Localizations`] available, this will return [`None`].
     #[must_use]
     pub fn text_language(&self) -> Option<Language> {
@@ -359,4 +572,33 @@ impl DialogueRunner {
         self.command_tasks.retain(|task| !task.is_finished());
         self.command_tasks.is_empty()
     }
+
+    /// Polls the future of the async function the dialogue is waiting on, if any. Called once per update.
+    pub(crate) fn poll_pending_function_call_and_check_if_done(&mut self) -> Result<bool> {
+        if !self.dialogue.is_waiting_for_function_call() {
+            return Ok(true);
+        }
+        let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+        match self.dialogue.poll_pending_function_call(&mut context) {
+            std::task::Poll::Ready(result) => result.map(|_| true).map_err(Error::from),
+            std::task::Poll::Pending => Ok(false),
+        }
+    }
+}
+
+/// A run condition that is true while any [`DialogueRunner`] [is running](DialogueRunner::is_running), e.g. to pause gameplay or disable player movement during dialogue.
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::prelude::*;
+/// # let mut app = App::new();
+/// app.add_systems(Update, move_player.run_if(not(dialogue_active)));
+///
+/// fn move_player() {
+///     // ...
+/// }
+/// ```
+pub fn dialogue_active(dialogue_runners: Query<&DialogueRunner>) -> bool {
+    dialogue_runners
+        .iter()
+        .any(|dialogue_runner| dialogue_runner.is_running())
 }
@@ -0,0 +1,126 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::prelude::*;
+use bevy::prelude::*;
+use std::time::Duration;
+
+pub(crate) fn auto_advance_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        update_auto_advance
+            .before(DialogueExecutionSystemSet)
+            .in_set(YarnSpinnerSystemSet),
+    );
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AutoAdvance {
+    delay: Option<Duration>,
+    countdown: Option<Timer>,
+    holds: usize,
+    skipping: bool,
+}
+
+impl AutoAdvance {
+    pub(crate) fn start_countdown(&mut self) {
+        let delay = if self.skipping {
+            Some(Duration::ZERO)
+        } else {
+            self.delay
+        };
+        self.countdown = delay.map(|delay| Timer::new(delay, TimerMode::Once));
+        self.holds = 0;
+    }
+
+    pub(crate) fn cancel_countdown(&mut self) {
+        self.countdown = None;
+        self.holds = 0;
+    }
+}
+
+impl DialogueRunner {
+    /// If set, the dialogue continues on its own once a line has been fully presented and the given delay has passed,
+    /// as if [`DialogueRunner::continue_in_next_update`] was called. Pass [`None`] to disable auto-advance again. Defaults to [`None`].
+    ///
+    /// A line counts as fully presented as soon as it is delivered with a [`PresentLineEvent`](crate::events::PresentLineEvent),
+    /// unless something holds it with [`DialogueRunner::hold_auto_advance`], e.g. a dialogue view that is still revealing the text,
+    /// or a view registered with [`DialogueRunner::register_view`] has not yet acknowledged it.
+    /// Voice-over played by an [`AudioAssetProvider`](crate::default_impl::AudioAssetProvider) holds the line until it has finished.
+    /// Options are never selected automatically, and the countdown is frozen while the runner is [paused](DialogueRunner::pause).
+    pub fn set_auto_advance(&mut self, delay: impl Into<Option<Duration>>) -> &mut Self {
+        self.auto_advance.delay = delay.into();
+        if self.auto_advance.delay.is_none() {
+            self.auto_advance.cancel_countdown();
+        }
+        self
+    }
+
+    /// Returns the delay after which the dialogue continues on its own, see [`DialogueRunner::set_auto_advance`].
+    #[must_use]
+    pub fn auto_advance(&self) -> Option<Duration> {
+        self.auto_advance.delay
+    }
+
+    /// Prevents the auto-advance countdown of the current line from starting until [`DialogueRunner::release_auto_advance`] is called.
+    /// Dialogue views that present lines over time, e.g. with a typewriter effect, should call this when they start presenting a line
+    /// and release it once the line is fully visible. Every call must be matched by a release; all holds are dropped when the next line is delivered.
+    pub fn hold_auto_advance(&mut self) -> &mut Self {
+        self.auto_advance.holds += 1;
+        self
+    }
+
+    /// Releases a hold placed with [`DialogueRunner::hold_auto_advance`]. The countdown starts once all holds are released.
+    pub fn release_auto_advance(&mut self) -> &mut Self {
+        self.auto_advance.holds = self.auto_advance.holds.saturating_sub(1);
+        self
+    }
+
+    /// If set, lines are skipped as soon as they are delivered, ignoring the delay of [`DialogueRunner::set_auto_advance`] and any [holds](DialogueRunner::hold_auto_advance).
+    /// The dialogue still stops at options, and views registered with [`DialogueRunner::register_view`] still have to acknowledge each line.
+    /// Meant for quickly getting through known dialogue during development. Defaults to `false`.
+    pub fn set_skip_mode(&mut self, skipping: bool) -> &mut Self {
+        self.auto_advance.skipping = skipping;
+        if skipping {
+            // Also skip the line that is currently presented
+            self.auto_advance.countdown = Some(Timer::new(Duration::ZERO, TimerMode::Once));
+        } else {
+            // Give the current line the regular delay again, if any
+            let delay = self.auto_advance.delay;
+            self.auto_advance.countdown = self
+                .auto_advance
+                .countdown
+                .take()
+                .and(delay)
+                .map(|delay| Timer::new(delay, TimerMode::Once));
+        }
+        self
+    }
+
+    /// Returns whether lines are skipped, see [`DialogueRunner::set_skip_mode`].
+    #[must_use]
+    pub fn is_skipping(&self) -> bool {
+        self.auto_advance.skipping
+    }
+}
+
+fn update_auto_advance(time: Res<Time>, mut dialogue_runners: Query<&mut DialogueRunner>) {
+    for mut dialogue_runner in dialogue_runners.iter_mut() {
+        let is_held = dialogue_runner.auto_advance.holds > 0 && !dialogue_runner.is_skipping();
+        if dialogue_runner.auto_advance.countdown.is_none()
+            || is_held
+            || !dialogue_runner.line_acknowledgments.are_complete()
+            || dialogue_runner.is_paused()
+        {
+            continue;
+        }
+        if !dialogue_runner.is_running() || dialogue_runner.is_waiting_for_option_selection() {
+            dialogue_runner.auto_advance.cancel_countdown();
+            continue;
+        }
+        let countdown = dialogue_runner.auto_advance.countdown.as_mut().unwrap();
+        if !countdown.tick(time.delta()).finished() {
+            continue;
+        }
+        dialogue_runner.auto_advance.cancel_countdown();
+        dialogue_runner.continue_in_next_update();
+    }
+}
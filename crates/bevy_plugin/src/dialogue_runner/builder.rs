@@ -1,10 +1,11 @@
+use super::random::DialogueRandom;
 use crate::default_impl::{MemoryVariableStorage, StringsFileTextProvider};
 use crate::fmt_utils::SkipDebug;
 use crate::line_provider::SharedTextProvider;
 use crate::prelude::*;
 use bevy::prelude::*;
 use bevy::utils::HashMap;
-use rand::{rngs::SmallRng, Rng, SeedableRng};
+use rand::Rng;
 use std::any::{Any, TypeId};
 use std::fmt::Debug;
 
@@ -20,36 +21,59 @@ pub struct DialogueRunnerBuilder {
     commands: YarnCommands,
     compilation: Compilation,
     localizations: Option<Localizations>,
+    text_language: Option<Language>,
+    asset_language: Option<Language>,
     asset_server: SkipDebug<AssetServer>,
+    project_id: YarnProjectId,
+    random: DialogueRandom,
+    presenter_only: bool,
 }
 
 impl DialogueRunnerBuilder {
     #[must_use]
     pub(crate) fn from_yarn_project(yarn_project: &YarnProject) -> Self {
+        let random = DialogueRandom::from_entropy();
         Self {
-            variable_storage: Box::new(MemoryVariableStorage::new()),
+            variable_storage: yarn_project.variable_storage.clone_shallow(),
             text_provider: SharedTextProvider::new(StringsFileTextProvider::from_yarn_project(
                 yarn_project,
             )),
             asset_providers: HashMap::new(),
-            library: create_extended_standard_library(),
+            library: create_extended_standard_library(&random),
             commands: YarnCommands::builtin_commands(),
             compilation: yarn_project.compilation().clone(),
             localizations: yarn_project.localizations().cloned(),
+            text_language: yarn_project.text_language(),
+            asset_language: yarn_project.asset_language(),
             asset_server: yarn_project.asset_server.clone(),
+            project_id: yarn_project.id,
+            random,
+            presenter_only: false,
         }
     }
 
-    /// Replaces the [`VariableStorage`] used by the [`DialogueRunner`]. By default, this is a [`MemoryVariableStorage`].
+    /// Replaces the [`VariableStorage`] used by the [`DialogueRunner`]. By default, this is the storage shared by all dialogue runners of the project,
+    /// see [`YarnProject::variable_storage`].
     #[must_use]
     pub fn with_variable_storage(mut self, storage: Box<dyn VariableStorage>) -> Self {
         self.variable_storage = storage;
         self
     }
 
+    /// Gives the [`DialogueRunner`] its own empty [`MemoryVariableStorage`] instead of the one shared by all dialogue runners of the project.
+    /// Use this for dialogues whose variables should not affect any other dialogue, e.g. ambient chatter of NPCs,
+    /// while the runners of the main story keep using [`YarnProject::variable_storage`].
+    #[must_use]
+    pub fn with_isolated_variable_storage(self) -> Self {
+        self.with_variable_storage(Box::new(MemoryVariableStorage::new()))
+    }
+
     /// Replaces the [`TextProvider`] used by the [`DialogueRunner`]. By default, this is a [`StringsFileTextProvider`].
+    /// The provider is passed the base language strings of the project with [`TextProvider::set_base_string_table`],
+    /// so it only needs to take care of the translations, e.g. by fetching them from a database or a server.
     #[must_use]
-    pub fn with_text_provider(mut self, provider: impl TextProvider + 'static) -> Self {
+    pub fn with_text_provider(mut self, mut provider: impl TextProvider + 'static) -> Self {
+        provider.set_base_string_table(self.compilation.string_table.clone());
         self.text_provider.replace(provider);
         self
     }
@@ -62,6 +86,25 @@ impl DialogueRunnerBuilder {
         self
     }
 
+    /// Seeds the random number generator used by the `random`, `random_range` and `dice` functions and by [`ChoiceFallback::Random`],
+    /// so that the dialogue makes the same random decisions every time it is run. By default, a random seed is used.
+    /// See [`DialogueRunner::set_random_seed`].
+    #[must_use]
+    pub fn with_random_seed(self, seed: u64) -> Self {
+        self.random.reseed(seed);
+        self
+    }
+
+    /// Makes the [`DialogueRunner`] presenter-only: instead of running the dialogue itself, it presents the events of a remote runner
+    /// passed to [`DialogueRunner::apply_remote_event`], e.g. to mirror the conversation of another player in a co-op game.
+    /// Selecting an option on it sends a [`RemoteOptionRequestEvent`](crate::events::RemoteOptionRequestEvent) instead of selecting it.
+    /// See [`ReplicateDialogue`].
+    #[must_use]
+    pub fn presenter_only(mut self) -> Self {
+        self.presenter_only = true;
+        self
+    }
+
     /// Builds the [`DialogueRunner`]. See [`DialogueRunnerBuilder::try_build`] for the fallible version.
     pub fn build(self) -> DialogueRunner {
         self.try_build().unwrap_or_else(|error| {
@@ -76,9 +119,12 @@ impl DialogueRunnerBuilder {
         let mut dialogue = Dialogue::new(self.variable_storage, text_provider.clone());
         dialogue
             .set_line_hints_enabled(true)
+            .set_variable_changed_events_enabled(true)
             .library_mut()
             .extend(self.library);
-        dialogue.add_program(self.compilation.program.unwrap());
+        dialogue
+            .add_program(self.compilation.program.unwrap())
+            .add_debug_info(self.compilation.debug_info);
 
         for asset_provider in self.asset_providers.values_mut() {
             if let Some(ref localizations) = self.localizations {
@@ -90,91 +136,108 @@ impl DialogueRunnerBuilder {
 
         let popped_line_hints = dialogue.pop_line_hints();
 
-        let base_language = self
-            .localizations
-            .as_ref()
-            .map(|l| &l.base_localization.language)
-            .cloned();
-
         let mut dialogue_runner = DialogueRunner {
             dialogue,
             text_provider,
             popped_line_hints,
+            preloading_line_hints: default(),
             run_selected_options_as_lines: false,
             asset_providers: self.asset_providers,
             commands: self.commands,
             is_running: default(),
+            is_paused: default(),
             command_tasks: default(),
             will_continue_in_next_update: default(),
             last_selected_option: default(),
+            unreported_selected_option: default(),
             just_started: default(),
             unsent_events: default(),
+            has_replayed_events: default(),
+            will_relocalize_current_line: default(),
+            system_function_calls: default(),
+            variable_bindings: default(),
+            auto_advance: default(),
+            choice_timers: default(),
+            line_acknowledgments: default(),
             localizations: self.localizations,
+            project_id: self.project_id,
+            random: self.random,
+            presenter: self.presenter_only.then(default),
         };
 
-        if let Some(base_language) = base_language {
-            dialogue_runner.set_language(base_language);
+        if let Some(text_language) = self.text_language {
+            dialogue_runner.set_text_language(text_language);
+        }
+        if let Some(asset_language) = self.asset_language {
+            dialogue_runner.set_asset_language(asset_language);
         }
 
         Ok(dialogue_runner)
     }
 }
 
-fn create_extended_standard_library() -> YarnLibrary {
+fn create_extended_standard_library(random: &DialogueRandom) -> YarnLibrary {
     let mut library = YarnLibrary::standard_library();
+    let (random_range, dice, random) = (random.clone(), random.clone(), random.clone());
     library
-        .add_function("random", || SmallRng::from_entropy().gen_range(0.0..1.0))
-        .add_function("random_range", |min: f32, max: f32| {
+        .add_function("random", move || {
+            random.with_rng(|rng| rng.gen_range(0.0..1.0))
+        })
+        .add_function("random_range", move |min: f64, max: f64| {
             if let Some(min) = min.as_int() {
                 if let Some(max_inclusive) = max.as_int() {
-                    return SmallRng::from_entropy().gen_range(min..=max_inclusive) as f32;
+                    return random_range.with_rng(|rng| rng.gen_range(min..=max_inclusive)) as f64;
                 }
             }
-            SmallRng::from_entropy().gen_range(min..max)
+            random_range.with_rng(|rng| rng.gen_range(min..max))
         })
-        .add_function("dice", |sides: u32| {
+        .add_function("dice", move |sides: u32| {
             if sides == 0 {
                 return 1;
             }
-            SmallRng::from_entropy().gen_range(1..=sides)
+            dice.with_rng(|rng| rng.gen_range(1..=sides))
         })
-        .add_function("round", |num: f32| num.round() as i32)
-        .add_function("round_places", |num: f32, places: u32| {
+        .add_function("round", |num: f64| num.round() as i64)
+        .add_function("round_places", |num: f64, places: u32| {
             num.round_places(places)
         })
-        .add_function("floor", |num: f32| num.floor() as i32)
-        .add_function("ceil", |num: f32| num.ceil() as i32)
-        .add_function("inc", |num: f32| {
+        .add_function("floor", |num: f64| num.floor() as i64)
+        .add_function("ceil", |num: f64| num.ceil() as i64)
+        .add_function("inc", |num: f64| {
             if let Some(num) = num.as_int() {
                 num + 1
             } else {
-                num.ceil() as i32
+                num.ceil() as i64
             }
         })
-        .add_function("dec", |num: f32| {
+        .add_function("dec", |num: f64| {
             if let Some(num) = num.as_int() {
                 num - 1
             } else {
-                num.floor() as i32
+                num.floor() as i64
             }
         })
-        .add_function("decimal", |num: f32| num.fract())
-        .add_function("int", |num: f32| num.trunc() as i32);
+        .add_function("decimal", |num: f64| num.fract())
+        .add_function("int", |num: f64| num.trunc() as i64);
     library
 }
 
 trait FloatExt: Copy {
-    fn as_int(self) -> Option<i32>;
+    fn as_int(self) -> Option<i64>;
     fn round_places(self, places: u32) -> Self;
 }
 
-impl FloatExt for f32 {
-    fn as_int(self) -> Option<i32> {
-        (self.fract() <= f32::EPSILON).then_some(self as i32)
+impl FloatExt for f64 {
+    /// Returns the number as an integer if it is a whole number that fits into an [`i64`].
+    fn as_int(self) -> Option<i64> {
+        // `i64::MAX` rounds up to 2^63 as a float, which `as` would saturate back to `i64::MAX`.
+        let range = i64::MIN as f64..i64::MAX as f64 + 1.0;
+        let int = self as i64;
+        (range.contains(&self) && int as f64 == self).then_some(int)
     }
 
     fn round_places(self, places: u32) -> Self {
-        let factor = 10_u32.pow(places) as f32;
+        let factor = 10_u32.pow(places) as f64;
         (self * factor).round() / factor
     }
 }
@@ -198,4 +261,21 @@ mod tests {
             assert_eq!(expected, num.round_places(places));
         }
     }
+
+    #[test]
+    fn recognizes_whole_numbers() {
+        for (num, expected) in [
+            (1.0, Some(1)),
+            (-3.0, Some(-3)),
+            (1_700_000_000.0, Some(1_700_000_000)),
+            (-2.5, None),
+            (0.1, None),
+            (f64::NAN, None),
+            (f64::INFINITY, None),
+            (-(2.0_f64.powi(63)), Some(i64::MIN)),
+            (2.0_f64.powi(63), None),
+        ] {
+            assert_eq!(expected, num.as_int());
+        }
+    }
 }
@@ -0,0 +1,216 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::prelude::*;
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use std::time::Duration;
+
+pub(crate) fn choice_timer_plugin(app: &mut App) {
+    app.add_event::<ChoiceTimerTickEvent>().add_systems(
+        Update,
+        update_choice_timers
+            .before(DialogueExecutionSystemSet)
+            .in_set(YarnSpinnerSystemSet),
+    );
+}
+
+/// Marks the option that is selected by [`ChoiceFallback::Default`].
+const DEFAULT_OPTION_TAG: &str = "default";
+
+/// Gives an options set its own time limit in seconds.
+const TIMEOUT_TAG_PREFIX: &str = "timeout:";
+
+/// A time limit for selecting an option, set with [`DialogueRunner::set_choice_timer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChoiceTimer {
+    /// How long the player has to select an option after the options were presented.
+    pub duration: Duration,
+    /// Which option is selected when the time runs out.
+    pub fallback: ChoiceFallback,
+}
+
+impl ChoiceTimer {
+    /// Creates a timer that selects the [default option](ChoiceFallback::Default) after the given duration.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            fallback: default(),
+        }
+    }
+
+    /// Sets which option is selected when the time runs out.
+    pub fn with_fallback(mut self, fallback: ChoiceFallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
+}
+
+/// Which option is selected when a [`ChoiceTimer`] runs out. Unavailable options are never selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ChoiceFallback {
+    /// Selects the option tagged with `#default`, or the first available option if no option is tagged.
+    #[default]
+    Default,
+    /// Selects a random available option.
+    Random,
+}
+
+/// An event that is sent every update while a [`ChoiceTimer`] is counting down, so that dialogue views can render the remaining time.
+/// The last event of an options set has a `remaining` time of zero and is sent in the same update in which the fallback option is selected.
+/// No events are sent while the [`DialogueRunner`] is [paused](DialogueRunner::pause), as the countdown is frozen then.
+#[derive(Debug, Clone, PartialEq, Event)]
+pub struct ChoiceTimerTickEvent {
+    /// The time left until an option is selected automatically.
+    pub remaining: Duration,
+    /// The total time limit of the current options set.
+    pub duration: Duration,
+    /// The [`DialogueRunner`] presenting the options.
+    pub source: Entity,
+}
+
+impl ChoiceTimerTickEvent {
+    /// Returns the fraction of the time limit that is left, from `1.0` when the options were presented down to `0.0`.
+    #[must_use]
+    pub fn fraction_remaining(&self) -> f32 {
+        if self.duration.is_zero() {
+            return 0.0;
+        }
+        self.remaining.as_secs_f32() / self.duration.as_secs_f32()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ChoiceTimers {
+    timer: Option<ChoiceTimer>,
+    countdown: Option<ChoiceCountdown>,
+}
+
+#[derive(Debug, Clone)]
+struct ChoiceCountdown {
+    timer: Timer,
+    fallback: ChoiceFallback,
+    default_option: Option<OptionId>,
+    available_options: Vec<OptionId>,
+}
+
+impl ChoiceTimers {
+    pub(crate) fn start_countdown(&mut self, options: &[DialogueOption]) {
+        let timeout = options
+            .iter()
+            .flat_map(|option| &option.line.metadata)
+            .find_map(|tag| tag.strip_prefix(TIMEOUT_TAG_PREFIX))
+            .and_then(|seconds| seconds.trim().parse::<f32>().ok())
+            .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+            .map(Duration::from_secs_f32);
+        let Some(timer) = timeout
+            .map(|duration| ChoiceTimer {
+                duration,
+                fallback: self.timer.map(|timer| timer.fallback).unwrap_or_default(),
+            })
+            .or(self.timer)
+        else {
+            self.countdown = None;
+            return;
+        };
+        let available_options: Vec<_> = options
+            .iter()
+            .filter(|option| option.is_available)
+            .map(|option| option.id)
+            .collect();
+        let default_option = options
+            .iter()
+            .filter(|option| option.is_available)
+            .find(|option| {
+                option
+                    .line
+                    .metadata
+                    .iter()
+                    .any(|tag| tag == DEFAULT_OPTION_TAG)
+            })
+            .map(|option| option.id)
+            .or_else(|| available_options.first().copied());
+        self.countdown = Some(ChoiceCountdown {
+            timer: Timer::new(timer.duration, TimerMode::Once),
+            fallback: timer.fallback,
+            default_option,
+            available_options,
+        });
+    }
+
+    pub(crate) fn cancel_countdown(&mut self) {
+        self.countdown = None;
+    }
+}
+
+impl DialogueRunner {
+    /// If set, every options set has to be answered within the given time, otherwise an option is selected automatically.
+    /// Pass [`None`] to disable the time limit again. Defaults to [`None`].
+    ///
+    /// Individual options sets can set their own time limit in seconds by tagging any of their options with `#timeout:<seconds>`,
+    /// which also works when no timer is set on the runner. Tag the option to select when the time runs out with `#default`:
+    /// ```yarn
+    /// -> Cut the red wire. #timeout:5
+    /// -> Cut the blue wire. #default
+    /// ```
+    ///
+    /// While the countdown runs, a [`ChoiceTimerTickEvent`] is sent every update. The countdown is frozen while the runner is [paused](DialogueRunner::pause)
+    /// and stops as soon as an option is selected with [`DialogueRunner::select_option`].
+    pub fn set_choice_timer(&mut self, timer: impl Into<Option<ChoiceTimer>>) -> &mut Self {
+        self.choice_timers.timer = timer.into();
+        self
+    }
+
+    /// Returns the time limit for selecting options, see [`DialogueRunner::set_choice_timer`].
+    #[must_use]
+    pub fn choice_timer(&self) -> Option<ChoiceTimer> {
+        self.choice_timers.timer
+    }
+
+    /// Returns the time left to select an option from the current options set, or [`None`] if there is no countdown running.
+    #[must_use]
+    pub fn remaining_choice_time(&self) -> Option<Duration> {
+        self.choice_timers
+            .countdown
+            .as_ref()
+            .map(|countdown| countdown.timer.remaining())
+    }
+}
+
+fn update_choice_timers(
+    time: Res<Time>,
+    mut dialogue_runners: Query<(Entity, &mut DialogueRunner)>,
+    mut tick_events: EventWriter<ChoiceTimerTickEvent>,
+) {
+    for (source, mut dialogue_runner) in dialogue_runners.iter_mut() {
+        if dialogue_runner.choice_timers.countdown.is_none() || dialogue_runner.is_paused() {
+            continue;
+        }
+        if !dialogue_runner.is_running() || !dialogue_runner.is_waiting_for_option_selection() {
+            dialogue_runner.choice_timers.cancel_countdown();
+            continue;
+        }
+        let countdown = dialogue_runner.choice_timers.countdown.as_mut().unwrap();
+        countdown.timer.tick(time.delta());
+        tick_events.send(ChoiceTimerTickEvent {
+            remaining: countdown.timer.remaining(),
+            duration: countdown.timer.duration(),
+            source,
+        });
+        if !countdown.timer.finished() {
+            continue;
+        }
+        let countdown = dialogue_runner.choice_timers.countdown.take().unwrap();
+        let option = match countdown.fallback {
+            ChoiceFallback::Default => countdown.default_option,
+            ChoiceFallback::Random => dialogue_runner
+                .random
+                .with_rng(|rng| countdown.available_options.choose(rng).copied()),
+        };
+        let Some(option) = option else {
+            warn!("Choice timer of dialogue runner {source} ran out, but none of the options is available to be selected");
+            continue;
+        };
+        dialogue_runner
+            .select_option(option)
+            .unwrap_or_else(|e| panic!("{e}"));
+    }
+}
@@ -2,11 +2,13 @@ use crate::line_provider::LineAssets;
 use crate::prelude::*;
 use bevy::prelude::*;
 
-pub(crate) fn dialogue_option_plugin(_app: &mut App) {}
+pub(crate) fn dialogue_option_plugin(app: &mut App) {
+    app.register_type::<DialogueOption>();
+}
 
 /// An option that can be presented to the user during a dialogue.
 /// Given to you by a [`PresentOptionsEvent`](crate::events::PresentOptionsEvent).
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Reflect)]
 pub struct DialogueOption {
     /// The [`LocalizedLine`] that should be presented to the user for this option.
     pub line: LocalizedLine,
@@ -39,7 +41,7 @@ impl DialogueOption {
         metadata: Vec<String>,
     ) -> Self {
         Self {
-            line: LocalizedLine::from_yarn_line(yarn_dialogue_option.line, assets, metadata),
+            line: LocalizedLine::from_line(yarn_dialogue_option.line, metadata, assets),
             id: yarn_dialogue_option.id,
             destination_node: yarn_dialogue_option.destination_node,
             is_available: yarn_dialogue_option.is_available,
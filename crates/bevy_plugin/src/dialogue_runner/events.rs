@@ -5,18 +5,34 @@ use bevy::prelude::*;
 pub(crate) fn dialogue_runner_events_plugin(app: &mut App) {
     app.add_event::<PresentLineEvent>()
         .add_event::<PresentOptionsEvent>()
+        .add_event::<OptionSelectedEvent>()
         .add_event::<ExecuteCommandEvent>()
         .add_event::<NodeCompleteEvent>()
         .add_event::<NodeStartEvent>()
         .add_event::<LineHintsEvent>()
+        .add_event::<LineHintsReadyEvent>()
         .add_event::<DialogueCompleteEvent>()
-        .add_event::<DialogueStartEvent>();
+        .add_event::<DialogueStartEvent>()
+        .add_event::<MissingTranslationEvent>()
+        .add_event::<VariableChangedEvent>()
+        .register_type::<PresentLineEvent>()
+        .register_type::<PresentOptionsEvent>()
+        .register_type::<OptionSelectedEvent>()
+        .register_type::<ExecuteCommandEvent>()
+        .register_type::<NodeCompleteEvent>()
+        .register_type::<NodeStartEvent>()
+        .register_type::<LineHintsEvent>()
+        .register_type::<LineHintsReadyEvent>()
+        .register_type::<DialogueCompleteEvent>()
+        .register_type::<DialogueStartEvent>()
+        .register_type::<MissingTranslationEvent>()
+        .register_type::<VariableChangedEvent>();
 }
 
 /// An event that is fired after a dialogue advances and wishes to present a line to the user.
 /// A dialogue view should listen for this event and draw it to the screen.
 /// Handling this event is **mandatory** for dialogue views.
-#[derive(Debug, Clone, PartialEq, Event)]
+#[derive(Debug, Clone, PartialEq, Event, Reflect)]
 pub struct PresentLineEvent {
     /// The line to present to the user.
     pub line: LocalizedLine,
@@ -28,7 +44,7 @@ pub struct PresentLineEvent {
 /// A dialogue view should listen for this event and draw it to the screen.
 /// You need to handle this event by calling [`DialogueRunner::select_option`] with the ID found in the provided [`DialogueOption`]s.
 /// Handling this event is **mandatory** for dialogue views.
-#[derive(Debug, Clone, PartialEq, Event)]
+#[derive(Debug, Clone, PartialEq, Event, Reflect)]
 pub struct PresentOptionsEvent {
     /// The options to present to the user.
     pub options: Vec<DialogueOption>,
@@ -36,13 +52,24 @@ pub struct PresentOptionsEvent {
     pub source: Entity,
 }
 
+/// An event that is fired when a dialogue continues with an option that was chosen through [`DialogueRunner::select_option`],
+/// right before the content of the option is run.
+/// Handling this event is **optional** for dialogue views.
+#[derive(Debug, Clone, PartialEq, Event, Reflect)]
+pub struct OptionSelectedEvent {
+    /// The option that was selected, as it was presented in the last [`PresentOptionsEvent`].
+    pub option: DialogueOption,
+    /// The [`DialogueRunner`] the option was selected in.
+    pub source: Entity,
+}
+
 /// An event that is fired after a dialogue advances and wishes to execute a command.
 /// Events are generally handled by looking them up in the [`YarnCommands`] of a [`DialogueRunner`],
 /// accessed via [`DialogueRunner::commands`] and [`DialogueRunner::commands_mut`].
 /// However, a command is allowed much more freedom in its syntax than one might think, and as such, not all commands are registerable.
 /// Thus, you can listen for this event and handle it yourself if you wish to build your own command syntax for e.g. a DSL.
 /// Handling this event is optional for dialogue views.
-#[derive(Debug, Clone, PartialEq, Event)]
+#[derive(Debug, Clone, PartialEq, Event, Reflect)]
 pub struct ExecuteCommandEvent {
     /// The command to execute.
     pub command: UnderlyingYarnCommand,
@@ -52,7 +79,7 @@ pub struct ExecuteCommandEvent {
 
 /// An event that is fired after a node has been completed, i.e. all of its lines, commands, options, etc. have been exhausted.
 /// Handling this event is **optional** for dialogue views.
-#[derive(Debug, Clone, PartialEq, Event)]
+#[derive(Debug, Clone, PartialEq, Event, Reflect)]
 pub struct NodeCompleteEvent {
     /// The name of the node that has been completed.
     pub node_name: String,
@@ -62,7 +89,7 @@ pub struct NodeCompleteEvent {
 
 /// An event that is fired after a node has been started, i.e. the first line, command, option, etc. has been executed.
 /// Handling this event is **optional** for dialogue views
-#[derive(Debug, Clone, PartialEq, Event)]
+#[derive(Debug, Clone, PartialEq, Event, Reflect)]
 pub struct NodeStartEvent {
     /// The name of the node that has been started.
     pub node_name: String,
@@ -73,7 +100,7 @@ pub struct NodeStartEvent {
 /// An event that is fired when a new node has been started. Contains the IDs of all lines in the node as a general hint
 /// for asset providing systems to pre-load the lines. The lines are not guaranteed to be presented in the order of the IDs or at all.
 /// Handling this event is **optional** for dialogue views.
-#[derive(Debug, Clone, PartialEq, Event)]
+#[derive(Debug, Clone, PartialEq, Event, Reflect)]
 pub struct LineHintsEvent {
     /// The IDs of all lines in the node.
     pub line_ids: Vec<LineId>,
@@ -81,17 +108,57 @@ pub struct LineHintsEvent {
     pub source: Entity,
 }
 
+/// An event that is fired once the lines announced by a [`LineHintsEvent`] have been preloaded,
+/// i.e. the text provider and all asset providers of the [`DialogueRunner`] report their lines and assets as available.
+/// From then on, the lines of the node can be presented without waiting for assets to load.
+/// Handling this event is **optional** for dialogue views, but can be used to e.g. hide a loading indicator.
+#[derive(Debug, Clone, PartialEq, Event, Reflect)]
+pub struct LineHintsReadyEvent {
+    /// The IDs of the lines that were preloaded.
+    pub line_ids: Vec<LineId>,
+    /// The [`DialogueRunner`] whose providers preloaded the lines.
+    pub source: Entity,
+}
+
 /// An event that is fired when a dialogue has been started via [`DialogueRunner::start_node`]/
 /// Handling this event is **optional** for dialogue views.
-#[derive(Debug, Clone, PartialEq, Event)]
+#[derive(Debug, Clone, PartialEq, Event, Reflect)]
 pub struct DialogueStartEvent {
     /// The [`DialogueRunner`] that has started this dialogue.
     pub source: Entity,
 }
 
 /// An event that is fired when a dialogue has been completed or stopped via [`DialogueRunner::stop`].
-#[derive(Debug, Clone, PartialEq, Event)]
+#[derive(Debug, Clone, PartialEq, Event, Reflect)]
 pub struct DialogueCompleteEvent {
     /// The [`DialogueRunner`] that has completed this dialogue.
     pub source: Entity,
 }
+
+/// An event that is fired when a line is missing in the strings file of the selected text language, so it was presented in the base language instead.
+/// Use [`YarnSpinnerPlugin::with_strict_translations`] to panic on missing translations instead.
+/// Handling this event is **optional** for dialogue views.
+#[derive(Debug, Clone, PartialEq, Eq, Event, Reflect)]
+pub struct MissingTranslationEvent {
+    /// The ID of the line without translation.
+    pub line_id: LineId,
+    /// The language the line is missing in.
+    pub language: Language,
+    /// The [`DialogueRunner`] that presented the line.
+    pub source: Entity,
+}
+
+/// An event that is fired when a Yarn script sets a variable, e.g. via `<<set $met_hag to true>>`.
+/// Changes made to the [`VariableStorage`] from outside the dialogue, e.g. via [`DialogueRunner::variable_storage_mut`], do not fire this event.
+/// Handling this event is **optional** for dialogue views.
+#[derive(Debug, Clone, PartialEq, Event, Reflect)]
+pub struct VariableChangedEvent {
+    /// The name of the variable, including the leading `$`.
+    pub name: String,
+    /// The value of the variable before it was set, falling back to its declared initial value. [`None`] if neither exists.
+    pub old_value: Option<YarnValue>,
+    /// The value the variable was set to.
+    pub new_value: YarnValue,
+    /// The [`DialogueRunner`] whose dialogue set the variable.
+    pub source: Entity,
+}
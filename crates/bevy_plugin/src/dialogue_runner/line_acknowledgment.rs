@@ -0,0 +1,78 @@
+use crate::prelude::*;
+use bevy::utils::HashMap;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LineAcknowledgments {
+    views: HashMap<String, bool>,
+    current_line: Option<LineId>,
+}
+
+impl LineAcknowledgments {
+    pub(crate) fn expect(&mut self, line_id: LineId) {
+        self.current_line = Some(line_id);
+        self.views
+            .values_mut()
+            .for_each(|acknowledged| *acknowledged = false);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.current_line = None;
+    }
+
+    pub(crate) fn are_complete(&self) -> bool {
+        self.current_line.is_none() || self.views.values().all(|acknowledged| *acknowledged)
+    }
+}
+
+impl DialogueRunner {
+    /// Registers a dialogue view under the given name. From now on, the dialogue does not advance past a line delivered with a [`PresentLineEvent`](crate::events::PresentLineEvent)
+    /// until the view has called [`DialogueRunner::acknowledge_line`] for it, even if [`DialogueRunner::continue_in_next_update`] was called.
+    /// Use this when several views present the same line, e.g. a text box that continues on user input and a voice player that must finish speaking first.
+    ///
+    /// Views that are not registered are not waited for. Registering a name twice has no effect.
+    /// A view registered while a line is presented has to acknowledge that line as well.
+    pub fn register_view(&mut self, name: impl Into<String>) -> &mut Self {
+        let acknowledged = self.line_acknowledgments.current_line.is_none();
+        self.line_acknowledgments
+            .views
+            .entry(name.into())
+            .or_insert(acknowledged);
+        self
+    }
+
+    /// Removes a view registered with [`DialogueRunner::register_view`], so that the dialogue no longer waits for it.
+    pub fn unregister_view(&mut self, name: &str) -> &mut Self {
+        self.line_acknowledgments.views.remove(name);
+        self
+    }
+
+    /// Returns whether a view with the given name was registered with [`DialogueRunner::register_view`].
+    #[must_use]
+    pub fn is_view_registered(&self, name: &str) -> bool {
+        self.line_acknowledgments.views.contains_key(name)
+    }
+
+    /// Tells the dialogue runner that the registered view with the given name has finished presenting the line with the given ID.
+    /// Acknowledgments of lines other than the one currently presented are ignored, so a view that finishes late cannot release the next line by accident.
+    ///
+    /// Panics if no view with the given name was registered.
+    pub fn acknowledge_line(&mut self, name: &str, line_id: &LineId) -> &mut Self {
+        let Some(acknowledged) = self.line_acknowledgments.views.get_mut(name) else {
+            panic!("Can't acknowledge line {line_id}: no view named \"{name}\" is registered. Please call `DialogueRunner::register_view()` first.");
+        };
+        if self.line_acknowledgments.current_line.as_ref() == Some(line_id) {
+            *acknowledged = true;
+        }
+        self
+    }
+
+    /// Returns the names of the registered views that have not yet acknowledged the line currently presented.
+    pub fn unacknowledged_views(&self) -> impl Iterator<Item = &str> {
+        let has_line = self.line_acknowledgments.current_line.is_some();
+        self.line_acknowledgments
+            .views
+            .iter()
+            .filter(move |(_, acknowledged)| has_line && !**acknowledged)
+            .map(|(name, _)| name.as_str())
+    }
+}
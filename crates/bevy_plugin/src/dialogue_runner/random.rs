@@ -0,0 +1,38 @@
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use std::sync::{Arc, Mutex};
+
+/// The random number generator of a [`DialogueRunner`](crate::prelude::DialogueRunner), shared with the random functions of its library
+/// so that reseeding it makes the whole dialogue deterministic.
+#[derive(Debug, Clone)]
+pub(crate) struct DialogueRandom(Arc<Mutex<SeededRng>>);
+
+#[derive(Debug)]
+struct SeededRng {
+    seed: u64,
+    rng: SmallRng,
+}
+
+impl DialogueRandom {
+    pub(crate) fn from_entropy() -> Self {
+        let seed = SmallRng::from_entropy().gen();
+        Self(Arc::new(Mutex::new(SeededRng {
+            seed,
+            rng: SmallRng::seed_from_u64(seed),
+        })))
+    }
+
+    pub(crate) fn seed(&self) -> u64 {
+        self.0.lock().unwrap().seed
+    }
+
+    pub(crate) fn reseed(&self, seed: u64) {
+        *self.0.lock().unwrap() = SeededRng {
+            seed,
+            rng: SmallRng::seed_from_u64(seed),
+        };
+    }
+
+    pub(crate) fn with_rng<T>(&self, f: impl FnOnce(&mut SmallRng) -> T) -> T {
+        f(&mut self.0.lock().unwrap().rng)
+    }
+}
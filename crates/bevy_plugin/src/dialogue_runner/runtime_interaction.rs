@@ -1,5 +1,6 @@
 use crate::commands::update_wait;
 use crate::dialogue_runner::events::DialogueStartEvent;
+use crate::error_handling::{handle_error, YarnErrorContext};
 use crate::events::*;
 use crate::line_provider::LineProviderSystemSet;
 use crate::prelude::*;
@@ -12,10 +13,9 @@ pub(crate) fn runtime_interaction_plugin(app: &mut App) {
     app.add_systems(
         Update,
         (
-            continue_runtime
-                .pipe(panic_on_err)
-                .run_if(resource_exists::<YarnProject>),
+            continue_runtime.pipe(handle_error(YarnErrorContext::DialogueExecution)),
             accept_line_hints,
+            report_preloaded_line_hints,
         )
             .chain()
             .after(LineProviderSystemSet)
@@ -25,30 +25,42 @@ pub(crate) fn runtime_interaction_plugin(app: &mut App) {
     );
 }
 
+/// The [`SystemSet`] containing the systems that advance [`DialogueRunner`]s and send their events, such as [`PresentLineEvent`] and [`ExecuteCommandEvent`].
+/// Systems that react to these events should run after it to handle them in the same frame, while systems that call
+/// [`DialogueRunner::continue_in_next_update`] or [`DialogueRunner::select_option`] should run before it to take effect in the same frame.
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, SystemSet)]
-pub(crate) struct DialogueExecutionSystemSet;
+pub struct DialogueExecutionSystemSet;
 
 fn continue_runtime(
     mut dialogue_runners: Query<(Entity, &mut DialogueRunner)>,
     mut present_line_events: EventWriter<PresentLineEvent>,
     mut present_options_events: EventWriter<PresentOptionsEvent>,
+    mut option_selected_events: EventWriter<OptionSelectedEvent>,
     mut execute_command_events: EventWriter<ExecuteCommandEvent>,
     mut node_complete_events: EventWriter<NodeCompleteEvent>,
     mut node_start_events: EventWriter<NodeStartEvent>,
     mut line_hints_events: EventWriter<LineHintsEvent>,
     mut dialogue_complete_events: EventWriter<DialogueCompleteEvent>,
     mut dialogue_start_events: EventWriter<DialogueStartEvent>,
+    mut variable_changed_events: EventWriter<VariableChangedEvent>,
     mut last_options: Local<HashMap<Entity, Vec<DialogueOption>>>,
     loaded_untyped_assets: Res<Assets<LoadedUntypedAsset>>,
-    project: Res<YarnProject>,
+    projects: YarnProjects,
 ) -> SystemResult {
     for (source, mut dialogue_runner) in dialogue_runners.iter_mut() {
+        if dialogue_runner.is_paused() || dialogue_runner.is_presenter_only() {
+            continue;
+        }
+        let project = projects.for_dialogue_runner(&dialogue_runner);
         let is_sending_missed_events = !dialogue_runner.unsent_events.is_empty();
+        // Events of a replayed trace are sent like missed ones, but belong to a dialogue that just started
+        let is_sending_replayed_events = std::mem::take(&mut dialogue_runner.has_replayed_events);
+        if dialogue_runner.just_started && (!is_sending_missed_events || is_sending_replayed_events)
+        {
+            dialogue_start_events.send(DialogueStartEvent { source });
+            dialogue_runner.just_started = false;
+        }
         if !is_sending_missed_events {
-            if dialogue_runner.just_started {
-                dialogue_start_events.send(DialogueStartEvent { source });
-                dialogue_runner.just_started = false;
-            }
             if !dialogue_runner.is_running {
                 dialogue_runner.will_continue_in_next_update = false;
                 continue;
@@ -58,13 +70,50 @@ fn continue_runtime(
                 line_hints_events.send(LineHintsEvent { line_ids, source });
             }
 
+            if dialogue_runner.will_relocalize_current_line
+                && !dialogue_runner.will_continue_in_next_update
+                && dialogue_runner.update_line_availability(&loaded_untyped_assets)
+            {
+                dialogue_runner.will_relocalize_current_line = false;
+                if let Some(line) = dialogue_runner.dialogue.relocalize_current_line() {
+                    let line = line?;
+                    dialogue_runner.line_acknowledgments.expect(line.id.clone());
+                    let assets = dialogue_runner.get_assets(&line);
+                    let metadata = project
+                        .and_then(|project| project.line_metadata(&line.id))
+                        .unwrap_or_default()
+                        .to_vec();
+                    present_line_events.send(PresentLineEvent {
+                        line: LocalizedLine::from_line(line, metadata, assets),
+                        source,
+                    });
+                }
+            }
+
             if !(dialogue_runner.will_continue_in_next_update
+                && dialogue_runner.line_acknowledgments.are_complete()
                 && dialogue_runner.poll_tasks_and_check_if_done()
+                && dialogue_runner.poll_pending_function_call_and_check_if_done()?
                 && dialogue_runner.update_line_availability(&loaded_untyped_assets))
             {
                 continue;
             }
             dialogue_runner.will_continue_in_next_update = false;
+            dialogue_runner.will_relocalize_current_line = false;
+            dialogue_runner.auto_advance.cancel_countdown();
+            dialogue_runner.line_acknowledgments.clear();
+
+            if let Some(option) = dialogue_runner.unreported_selected_option.take() {
+                let selected_option = last_options
+                    .get(&source)
+                    .and_then(|options| options.iter().find(|o| o.id == option));
+                if let Some(option) = selected_option {
+                    option_selected_events.send(OptionSelectedEvent {
+                        option: option.clone(),
+                        source,
+                    });
+                }
+            }
 
             if dialogue_runner.run_selected_options_as_lines {
                 if let Some(option) = dialogue_runner.last_selected_option.take() {
@@ -80,10 +129,14 @@ fn continue_runtime(
                             .join(", ");
                         bail!("Dialogue options does not contain selected option. Expected one of [{expected_options}], but found {option}");
                     };
+                    dialogue_runner
+                        .line_acknowledgments
+                        .expect(option.line.id.clone());
                     present_line_events.send(PresentLineEvent {
                         line: option.line,
                         source,
                     });
+                    dialogue_runner.auto_advance.start_countdown();
                     continue;
                 }
             }
@@ -97,12 +150,17 @@ fn continue_runtime(
         for event in events {
             match event {
                 DialogueEvent::Line(line) => {
+                    dialogue_runner.line_acknowledgments.expect(line.id.clone());
                     let assets = dialogue_runner.get_assets(&line);
-                    let metadata = project.line_metadata(&line.id).unwrap_or_default().to_vec();
+                    let metadata = project
+                        .and_then(|project| project.line_metadata(&line.id))
+                        .unwrap_or_default()
+                        .to_vec();
                     present_line_events.send(PresentLineEvent {
-                        line: LocalizedLine::from_yarn_line(line, assets, metadata),
+                        line: LocalizedLine::from_line(line, metadata, assets),
                         source,
                     });
+                    dialogue_runner.auto_advance.start_countdown();
                 }
                 DialogueEvent::Options(options) => {
                     let options: Vec<DialogueOption> = options
@@ -110,18 +168,21 @@ fn continue_runtime(
                         .map(|option| {
                             let assets = dialogue_runner.get_assets(&option.line);
                             let metadata = project
-                                .line_metadata(&option.line.id)
+                                .and_then(|project| project.line_metadata(&option.line.id))
                                 .unwrap_or_default()
                                 .to_vec();
                             DialogueOption::from_yarn_dialogue_option(option, assets, metadata)
                         })
                         .collect();
+                    dialogue_runner.choice_timers.start_countdown(&options);
                     last_options.insert(source, options.clone());
                     present_options_events.send(PresentOptionsEvent { options, source });
                 }
                 DialogueEvent::Command(command) => {
                     execute_command_events.send(ExecuteCommandEvent { command, source });
-                    dialogue_runner.continue_in_next_update();
+                    if !is_sending_missed_events {
+                        dialogue_runner.continue_in_next_update();
+                    }
                 }
                 DialogueEvent::NodeComplete(node_name) => {
                     node_complete_events.send(NodeCompleteEvent { node_name, source });
@@ -132,6 +193,20 @@ fn continue_runtime(
                 DialogueEvent::LineHints(line_ids) => {
                     line_hints_events.send(LineHintsEvent { line_ids, source });
                 }
+                DialogueEvent::VariableChanged {
+                    name,
+                    old_value,
+                    new_value,
+                } => {
+                    variable_changed_events.send(VariableChangedEvent {
+                        name,
+                        old_value,
+                        new_value,
+                        source,
+                    });
+                }
+                // Only sent when enabled on the underlying dialogue, which the runner does not do
+                DialogueEvent::ExecutionStateChanged { .. } => {}
                 DialogueEvent::DialogueComplete => {
                     if !is_sending_missed_events {
                         dialogue_runner.is_running = false;
@@ -140,6 +215,10 @@ fn continue_runtime(
                 }
             }
         }
+        if dialogue_runner.dialogue.is_waiting_for_function_call() {
+            // Resume on our own as soon as the async function has resolved
+            dialogue_runner.continue_in_next_update();
+        }
     }
     Ok(())
 }
@@ -153,5 +232,23 @@ fn accept_line_hints(
         for asset_provider in dialogue_runner.asset_providers.values_mut() {
             asset_provider.accept_line_hints(&event.line_ids);
         }
+        dialogue_runner.preloading_line_hints = Some(event.line_ids.clone());
+    }
+}
+
+fn report_preloaded_line_hints(
+    mut dialogue_runners: Query<(Entity, &mut DialogueRunner)>,
+    mut line_hints_ready_events: EventWriter<LineHintsReadyEvent>,
+    loaded_untyped_assets: Res<Assets<LoadedUntypedAsset>>,
+) {
+    for (source, mut dialogue_runner) in dialogue_runners.iter_mut() {
+        if dialogue_runner.preloading_line_hints.is_none()
+            || !dialogue_runner.update_line_availability(&loaded_untyped_assets)
+        {
+            continue;
+        }
+        if let Some(line_ids) = dialogue_runner.preloading_line_hints.take() {
+            line_hints_ready_events.send(LineHintsReadyEvent { line_ids, source });
+        }
     }
 }
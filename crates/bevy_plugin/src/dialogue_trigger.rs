@@ -0,0 +1,207 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::prelude::*;
+use bevy::prelude::*;
+use std::time::Duration;
+
+pub(crate) fn dialogue_trigger_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        start_triggered_dialogues
+            .before(DialogueExecutionSystemSet)
+            .in_set(YarnSpinnerSystemSet),
+    );
+}
+
+/// Starts a node of a [`DialogueRunner`] when an entity with a [`DialogueInteraction`] comes within the radius of this entity
+/// or presses its interaction key while in range, depending on the [`DialogueTriggerActivation`].
+/// Distances are measured between the [`GlobalTransform`]s of both entities.
+///
+/// By default, the runner on the same entity as the trigger is used, see [`DialogueTrigger::with_dialogue_runner`] for using another one.
+/// Dialogue runners that are already running are not started again.
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::prelude::*;
+/// # use std::time::Duration;
+/// fn spawn_npcs(mut commands: Commands, project: Res<YarnProject>) {
+///     commands.spawn((
+///         SpatialBundle::from_transform(Transform::from_xyz(5.0, 0.0, 0.0)),
+///         project.create_dialogue_runner(),
+///         DialogueTrigger::on_interaction("Shopkeeper", 2.0),
+///     ));
+///     commands.spawn((
+///         SpatialBundle::from_transform(Transform::from_xyz(-5.0, 0.0, 0.0)),
+///         project.create_dialogue_runner(),
+///         DialogueTrigger::on_proximity("GuardShouts", 4.0).with_cooldown(Duration::from_secs(30)),
+///     ));
+/// }
+///
+/// fn spawn_player(mut commands: Commands) {
+///     commands.spawn((SpatialBundle::default(), DialogueInteraction::default()));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Component)]
+pub struct DialogueTrigger {
+    /// The node that is started when the trigger activates.
+    pub node_name: String,
+    /// What activates the trigger.
+    pub activation: DialogueTriggerActivation,
+    /// The distance within which an entity with a [`DialogueInteraction`] activates the trigger.
+    pub radius: f32,
+    /// The minimum time between two starts of the dialogue. Defaults to zero.
+    pub cooldown: Duration,
+    /// Whether the trigger deactivates after starting the dialogue once. Defaults to `false`.
+    pub only_once: bool,
+    /// The entity of the [`DialogueRunner`] that is started. If [`None`], the runner on the entity of this trigger is used.
+    pub dialogue_runner: Option<Entity>,
+    last_started: Option<Duration>,
+    interactor_was_in_range: bool,
+}
+
+/// What activates a [`DialogueTrigger`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DialogueTriggerActivation {
+    /// An entity with a [`DialogueInteraction`] enters the radius of the trigger.
+    /// Staying in range does not activate the trigger again, the entity has to leave and come back.
+    #[default]
+    Proximity,
+    /// An entity with a [`DialogueInteraction`] presses its [`DialogueInteraction::key`] while in the radius of the trigger.
+    Interaction,
+}
+
+impl DialogueTrigger {
+    /// Creates a trigger that starts the given node when an entity with a [`DialogueInteraction`] comes within the given radius.
+    pub fn on_proximity(node_name: impl Into<String>, radius: f32) -> Self {
+        Self::new(node_name, DialogueTriggerActivation::Proximity, radius)
+    }
+
+    /// Creates a trigger that starts the given node when an entity with a [`DialogueInteraction`] presses its key within the given radius.
+    pub fn on_interaction(node_name: impl Into<String>, radius: f32) -> Self {
+        Self::new(node_name, DialogueTriggerActivation::Interaction, radius)
+    }
+
+    /// Creates a trigger with the given activation. See [`DialogueTrigger::on_proximity`] and [`DialogueTrigger::on_interaction`].
+    pub fn new(
+        node_name: impl Into<String>,
+        activation: DialogueTriggerActivation,
+        radius: f32,
+    ) -> Self {
+        Self {
+            node_name: node_name.into(),
+            activation,
+            radius,
+            cooldown: Duration::ZERO,
+            only_once: false,
+            dialogue_runner: None,
+            last_started: None,
+            interactor_was_in_range: false,
+        }
+    }
+
+    /// Sets the minimum time between two starts of the dialogue.
+    #[must_use]
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Makes the trigger start the dialogue only the first time it activates.
+    #[must_use]
+    pub fn only_once(mut self) -> Self {
+        self.only_once = true;
+        self
+    }
+
+    /// Starts the [`DialogueRunner`] on the given entity instead of the one on the entity of this trigger.
+    #[must_use]
+    pub fn with_dialogue_runner(mut self, dialogue_runner: Entity) -> Self {
+        self.dialogue_runner = Some(dialogue_runner);
+        self
+    }
+
+    /// Returns whether the trigger has started its dialogue at least once.
+    #[must_use]
+    pub fn has_started(&self) -> bool {
+        self.last_started.is_some()
+    }
+
+    /// Resets the trigger as if it had never started its dialogue, which reactivates triggers that are [`DialogueTrigger::only_once`].
+    pub fn reset(&mut self) -> &mut Self {
+        self.last_started = None;
+        self
+    }
+
+    fn is_ready(&self, now: Duration) -> bool {
+        match self.last_started {
+            None => true,
+            Some(_) if self.only_once => false,
+            Some(last_started) => now.saturating_sub(last_started) >= self.cooldown,
+        }
+    }
+}
+
+/// Marks an entity, usually the player, as able to activate [`DialogueTrigger`]s.
+#[derive(Debug, Clone, PartialEq, Component)]
+pub struct DialogueInteraction {
+    /// The key that activates [`DialogueTriggerActivation::Interaction`] triggers in range. Defaults to [`KeyCode::KeyE`].
+    pub key: KeyCode,
+}
+
+impl Default for DialogueInteraction {
+    fn default() -> Self {
+        Self { key: KeyCode::KeyE }
+    }
+}
+
+impl DialogueInteraction {
+    /// Creates an interaction that uses the given key for [`DialogueTriggerActivation::Interaction`] triggers.
+    pub fn with_key(key: KeyCode) -> Self {
+        Self { key }
+    }
+}
+
+fn start_triggered_dialogues(
+    time: Res<Time>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    interactors: Query<(&GlobalTransform, &DialogueInteraction)>,
+    mut triggers: Query<(Entity, &GlobalTransform, &mut DialogueTrigger)>,
+    mut dialogue_runners: Query<&mut DialogueRunner>,
+) {
+    let now = time.elapsed();
+    for (entity, transform, mut trigger) in triggers.iter_mut() {
+        let position = transform.translation();
+        let mut interactors_in_range = interactors.iter().filter(|(interactor_transform, _)| {
+            interactor_transform.translation().distance(position) <= trigger.radius
+        });
+        let is_activated = match trigger.activation {
+            DialogueTriggerActivation::Proximity => {
+                let is_in_range = interactors_in_range.next().is_some();
+                let was_in_range =
+                    std::mem::replace(&mut trigger.interactor_was_in_range, is_in_range);
+                is_in_range && !was_in_range
+            }
+            DialogueTriggerActivation::Interaction => keys.as_ref().is_some_and(|keys| {
+                interactors_in_range.any(|(_, interaction)| keys.just_pressed(interaction.key))
+            }),
+        };
+        if !is_activated || !trigger.is_ready(now) {
+            continue;
+        }
+
+        let dialogue_runner_entity = trigger.dialogue_runner.unwrap_or(entity);
+        let Ok(mut dialogue_runner) = dialogue_runners.get_mut(dialogue_runner_entity) else {
+            warn!(
+                "Dialogue trigger on entity {entity} wanted to start node \"{}\", but entity {dialogue_runner_entity} has no DialogueRunner",
+                trigger.node_name
+            );
+            continue;
+        };
+        if dialogue_runner.is_running() {
+            continue;
+        }
+        dialogue_runner.start_node(&trigger.node_name);
+        trigger.last_started = Some(now);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use std::fmt::{self, Display};
+use std::sync::Arc;
+
+pub(crate) fn error_handling_plugin(app: &mut App) {
+    app.add_event::<YarnErrorEvent>()
+        .init_resource::<YarnErrorHandling>();
+}
+
+/// A [`Resource`] deciding what happens when Yarn Spinner runs into a recoverable error, such as a Yarn project that fails to load,
+/// a strings file that is missing or malformed, or a command that is called with the wrong arguments.
+/// Set it with [`YarnSpinnerPlugin::with_error_handling`] or by inserting the resource yourself, which is needed when using [`YarnSpinnerPlugin::deferred`].
+///
+/// Errors caused by calling the API incorrectly, e.g. continuing a dialogue that is not running, always panic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Resource)]
+pub enum YarnErrorHandling {
+    /// Panics on errors. Useful during development, as problems cannot go unnoticed.
+    #[default]
+    Panic,
+    /// Logs errors and sends them as [`YarnErrorEvent`]s. The part of the dialogue that failed is skipped,
+    /// e.g. a translation that fails to load falls back to the base language and a failing command is treated as finished.
+    SendEvent,
+}
+
+/// An event that is sent for every recoverable error if the [`YarnErrorHandling`] is set to [`YarnErrorHandling::SendEvent`].
+#[derive(Debug, Clone, Event)]
+pub struct YarnErrorEvent {
+    /// The error that occurred.
+    pub error: Arc<Error>,
+    /// What Yarn Spinner was doing when the error occurred.
+    pub context: YarnErrorContext,
+    /// The [`DialogueRunner`] that ran into the error, if the error is specific to one.
+    pub source: Option<Entity>,
+}
+
+/// What Yarn Spinner was doing when a [`YarnErrorEvent`] occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum YarnErrorContext {
+    /// Loading or compiling the [`YarnProject`].
+    ProjectLoading,
+    /// Loading or writing localization files such as strings files.
+    Localization,
+    /// Running a dialogue.
+    DialogueExecution,
+    /// Running a command registered in the [`YarnCommands`].
+    CommandExecution,
+}
+
+impl Display for YarnErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Self::ProjectLoading => "loading the Yarn project",
+            Self::Localization => "localizing",
+            Self::DialogueExecution => "running a dialogue",
+            Self::CommandExecution => "running a command",
+        };
+        f.write_str(description)
+    }
+}
+
+impl Display for YarnErrorEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Error in Yarn Spinner plugin while {}", self.context)?;
+        if let Some(source) = self.source {
+            write!(f, " (dialogue runner {source})")?;
+        }
+        write!(f, ": {}", self.error)
+    }
+}
+
+impl YarnErrorEvent {
+    pub(crate) fn new(error: Error, context: YarnErrorContext) -> Self {
+        Self {
+            error: Arc::new(error),
+            context,
+            source: None,
+        }
+    }
+
+    pub(crate) fn with_source(mut self, source: Entity) -> Self {
+        self.source = Some(source);
+        self
+    }
+}
+
+/// Returns a system to pipe fallible systems into, which handles their errors according to the [`YarnErrorHandling`].
+pub(crate) fn handle_error(
+    context: YarnErrorContext,
+) -> impl FnMut(In<SystemResult>, Res<YarnErrorHandling>, EventWriter<YarnErrorEvent>) {
+    move |In(result), error_handling, mut error_events| {
+        if let Err(error) = result {
+            report_error(
+                *error_handling,
+                &mut error_events,
+                YarnErrorEvent::new(error, context),
+            );
+        }
+    }
+}
+
+pub(crate) fn report_error(
+    error_handling: YarnErrorHandling,
+    error_events: &mut EventWriter<YarnErrorEvent>,
+    event: YarnErrorEvent,
+) {
+    match error_handling {
+        YarnErrorHandling::Panic => panic!("{event}"),
+        YarnErrorHandling::SendEvent => {
+            error!("{event}");
+            error_events.send(event);
+        }
+    }
+}
+
+pub(crate) fn report_error_in_world(world: &mut World, event: YarnErrorEvent) {
+    match *world.resource::<YarnErrorHandling>() {
+        YarnErrorHandling::Panic => panic!("{event}"),
+        YarnErrorHandling::SendEvent => {
+            error!("{event}");
+            world.send_event(event);
+        }
+    }
+}
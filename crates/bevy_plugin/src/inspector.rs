@@ -0,0 +1,177 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::error_handling::{handle_error, YarnErrorContext};
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+pub(crate) fn inspector_plugin(app: &mut App) {
+    app.register_type::<DialogueRunnerState>()
+        .register_type::<YarnProjectInfo>()
+        .register_type::<YarnProjectId>()
+        .add_systems(
+            Update,
+            (
+                sync_dialogue_runner_states
+                    .pipe(handle_error(YarnErrorContext::DialogueExecution))
+                    .after(DialogueExecutionSystemSet),
+                update_yarn_project_info.run_if(resource_exists_and_changed::<YarnProject>),
+            )
+                .in_set(YarnSpinnerSystemSet),
+        );
+}
+
+/// A reflected snapshot of the [`DialogueRunner`] on the same entity, refreshed every frame.
+/// Add it to the dialogue runners you want to look at in a reflection-based inspector such as `bevy-inspector-egui`.
+///
+/// Edits to [`DialogueRunnerState::is_paused`] and [`DialogueRunnerState::variables`] are written back to the runner,
+/// all other fields are overwritten by the next refresh.
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::prelude::*;
+/// fn spawn_dialogue_runner(mut commands: Commands, project: Res<YarnProject>) {
+///     commands.spawn((
+///         project.create_dialogue_runner(),
+///         DialogueRunnerState::default(),
+///     ));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Component, Reflect)]
+#[reflect(Component, Debug, PartialEq, Default)]
+pub struct DialogueRunnerState {
+    /// See [`DialogueRunner::current_node`].
+    pub current_node: Option<String>,
+    /// See [`DialogueRunner::is_running`].
+    pub is_running: bool,
+    /// See [`DialogueRunner::is_paused`]. Editing it pauses or resumes the runner.
+    pub is_paused: bool,
+    /// See [`DialogueRunner::is_waiting_for_option_selection`].
+    pub is_waiting_for_option_selection: bool,
+    /// See [`DialogueRunner::text_language`].
+    pub text_language: Option<Language>,
+    /// See [`DialogueRunner::asset_language`].
+    pub asset_language: Option<Language>,
+    /// The contents of the runner's [`VariableStorage`]. Editing a value sets the variable in the storage.
+    pub variables: HashMap<String, YarnValue>,
+    /// See [`DialogueRunner::metrics`].
+    pub metrics: DialogueMetrics,
+}
+
+impl DialogueRunnerState {
+    fn of(dialogue_runner: &DialogueRunner) -> Self {
+        Self {
+            current_node: dialogue_runner.current_node(),
+            is_running: dialogue_runner.is_running(),
+            is_paused: dialogue_runner.is_paused(),
+            is_waiting_for_option_selection: dialogue_runner.is_waiting_for_option_selection(),
+            text_language: dialogue_runner.text_language(),
+            asset_language: dialogue_runner.asset_language(),
+            variables: dialogue_runner
+                .variable_storage()
+                .variables()
+                .into_iter()
+                .collect(),
+            metrics: dialogue_runner.metrics(),
+        }
+    }
+
+    fn apply_to(&self, dialogue_runner: &mut DialogueRunner) -> Result<()> {
+        if self.is_paused != dialogue_runner.is_paused() {
+            if self.is_paused {
+                dialogue_runner.pause();
+            } else {
+                dialogue_runner.resume();
+            }
+        }
+        let variable_storage = dialogue_runner.variable_storage_mut();
+        for (name, value) in &self.variables {
+            if variable_storage.get(name).ok().as_ref() != Some(value) {
+                variable_storage
+                    .set(name.clone(), value.clone())
+                    .with_context(|| format!("Failed to set variable {name} from the inspector"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A reflected summary of the [`YarnProject`], kept up to date whenever the project changes.
+/// Inserted once the project is compiled so that reflection-based inspectors such as `bevy-inspector-egui` can display it.
+/// Editing it has no effect.
+#[derive(Debug, Clone, PartialEq, Resource, Reflect)]
+#[reflect(Resource, Debug, PartialEq)]
+pub struct YarnProjectInfo {
+    /// See [`YarnProject::id`].
+    pub id: YarnProjectId,
+    /// The asset paths of the [`YarnFile`]s the project was compiled from, sorted.
+    pub yarn_files: Vec<String>,
+    /// The names of all nodes in the project, sorted.
+    pub node_names: Vec<String>,
+    /// The number of lines in the project's string table.
+    pub line_count: usize,
+    /// The language of the Yarn files, see [`Localizations::base_localization`].
+    pub base_language: Option<Language>,
+    /// The languages of the translations, see [`Localizations::translations`].
+    pub translations: Vec<Language>,
+    /// See [`YarnProject::text_language`].
+    pub text_language: Option<Language>,
+    /// See [`YarnProject::asset_language`].
+    pub asset_language: Option<Language>,
+}
+
+impl From<&YarnProject> for YarnProjectInfo {
+    fn from(project: &YarnProject) -> Self {
+        let mut yarn_files: Vec<_> = project
+            .yarn_files()
+            .map(|handle| {
+                handle
+                    .path()
+                    .map(ToString::to_string)
+                    .unwrap_or_else(|| format!("{:?}", handle.id()))
+            })
+            .collect();
+        yarn_files.sort();
+        let compilation = project.compilation();
+        let mut node_names: Vec<_> = compilation
+            .program
+            .iter()
+            .flat_map(|program| program.nodes.keys().cloned())
+            .collect();
+        node_names.sort();
+        let localizations = project.localizations();
+        Self {
+            id: project.id(),
+            yarn_files,
+            node_names,
+            line_count: compilation.string_table.len(),
+            base_language: localizations
+                .map(|localizations| localizations.base_localization.language.clone()),
+            translations: localizations
+                .iter()
+                .flat_map(|localizations| &localizations.translations)
+                .map(|localization| localization.language.clone())
+                .collect(),
+            text_language: project.text_language(),
+            asset_language: project.asset_language(),
+        }
+    }
+}
+
+fn sync_dialogue_runner_states(
+    mut dialogue_runners: Query<(&mut DialogueRunner, &mut DialogueRunnerState)>,
+) -> SystemResult {
+    for (mut dialogue_runner, mut state) in dialogue_runners.iter_mut() {
+        // Our own refreshes are not seen as changes by this system, so a change means the state was edited elsewhere
+        if state.is_changed() && !state.is_added() {
+            state.apply_to(&mut dialogue_runner)?;
+        }
+        state.set_if_neq(DialogueRunnerState::of(&dialogue_runner));
+    }
+    Ok(())
+}
+
+fn update_yarn_project_info(mut commands: Commands, project: Res<YarnProject>) {
+    commands.insert_resource(YarnProjectInfo::from(project.as_ref()));
+}
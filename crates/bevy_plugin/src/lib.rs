@@ -97,16 +97,40 @@
 #![allow(clippy::too_many_arguments, clippy::type_complexity)]
 #![warn(missing_docs, missing_debug_implementations)]
 
+mod accessibility;
+mod analytics;
+mod character_registry;
 mod commands;
+mod console;
+mod content_validation;
+#[cfg(feature = "debugger")]
+mod debugger;
 mod development_file_generation;
 mod dialogue_runner;
+mod dialogue_trigger;
+mod error_handling;
 mod fmt_utils;
+mod inspector;
 mod line_provider;
 mod localization;
+#[cfg(feature = "text")]
+mod markup_text;
+mod option_navigation;
 mod plugin;
 mod project;
+mod replay;
+mod replication;
+mod scene_transition;
+mod standard_commands;
+mod system_functions;
+#[cfg(feature = "test_utils")]
+pub mod test_utils;
+mod transcript;
 mod utils;
+mod variable_bindings;
+mod variable_defaults_asset;
 mod yarn_file_asset;
+mod yarn_program_asset;
 pub use anyhow::{Error, Result};
 
 pub mod default_impl {
@@ -114,43 +138,111 @@ pub mod default_impl {
     #[cfg(feature = "audio_assets")]
     pub use crate::line_provider::AudioAssetProvider;
     pub use crate::line_provider::{
-        file_extensions, FileExtensionAssetProvider, StringsFileTextProvider,
+        file_extensions, CharacterAssetProvider, FileExtensionAssetProvider,
+        StringsFileTextProvider,
+    };
+    pub use yarnspinner::runtime::{
+        MemoryVariableStorage, ScopedVariableStorage, StringTableTextProvider,
     };
-    pub use yarnspinner::runtime::{MemoryVariableStorage, StringTableTextProvider};
 }
 
 pub mod events {
     //! Events that are sent by the [`DialogueRunner`](crate::prelude::DialogueRunner). A dialogue view is expected to at least handle [`PresentLineEvent`] and [`PresentOptionsEvent`].
+    pub use crate::accessibility::AccessibilityAnnouncementEvent;
+    pub use crate::analytics::DialogueAnalyticsEvent;
+    pub use crate::console::DialogueConsoleCommandEvent;
+    pub use crate::content_validation::ValidateContentEvent;
     pub use crate::dialogue_runner::{
-        DialogueCompleteEvent, DialogueStartEvent, ExecuteCommandEvent, LineHintsEvent,
-        NodeCompleteEvent, NodeStartEvent, PresentLineEvent, PresentOptionsEvent,
+        ChoiceTimerTickEvent, DialogueCompleteEvent, DialogueStartEvent, ExecuteCommandEvent,
+        LineHintsEvent, LineHintsReadyEvent, MissingTranslationEvent, NodeCompleteEvent,
+        NodeStartEvent, OptionSelectedEvent, PresentLineEvent, PresentOptionsEvent,
+        VariableChangedEvent,
+    };
+    pub use crate::error_handling::{YarnErrorContext, YarnErrorEvent};
+    pub use crate::localization::{
+        GenerateStringsFilesEvent, LanguageChangedEvent, StaleTranslationEvent,
     };
+    pub use crate::option_navigation::OptionNavigationEvent;
+    pub use crate::project::YarnProjectCompiledEvent;
+    pub use crate::replication::{DialogueReplicationEvent, RemoteOptionRequestEvent};
+    pub use crate::scene_transition::{LoadSceneEvent, SceneLoadedEvent};
 }
 
 pub mod prelude {
     //! Everything you need to get starting using Yarn Spinner.
 
-    #[cfg(feature = "audio_assets")]
-    pub use crate::default_impl::AudioAssetProvider;
+    #[cfg(feature = "debugger")]
+    pub use crate::debugger::{DialogueDebugger, YarnSpinnerDebuggerPlugin};
+    #[cfg(feature = "development_file_generation")]
+    pub use crate::localization::convert_unity_strings_file;
     pub use crate::{
+        accessibility::{
+            AccessibilityAnnouncement, AnnouncedOption, YarnSpinnerAccessibilityPlugin,
+        },
+        analytics::{AnalyticsOption, DialogueAnalyticsKind, YarnSpinnerAnalyticsPlugin},
+        character_registry::{Character, CharacterRegistry},
         commands::{YarnCommand, YarnCommands},
-        default_impl::FileExtensionAssetProvider,
+        console::DialogueConsoleCommand,
+        content_validation::{
+            ContentValidation, ContentValidationReport, MissingAsset, MissingTranslation,
+            UnknownSpeaker, UnregisteredCommand,
+        },
+        default_impl::{CharacterAssetProvider, FileExtensionAssetProvider},
         development_file_generation::DevelopmentFileGeneration,
-        dialogue_runner::{DialogueOption, DialogueRunner, DialogueRunnerBuilder, LocalizedLine},
-        line_provider::{AssetProvider, LineAssets, TextProvider},
-        localization::{Localization, Localizations},
+        dialogue_runner::{
+            dialogue_active, ChoiceFallback, ChoiceTimer, DialogueExecutionSystemSet,
+            DialogueOption, DialogueRunner, DialogueRunnerBuilder, LocalizedLine,
+        },
+        dialogue_trigger::{DialogueInteraction, DialogueTrigger, DialogueTriggerActivation},
+        error_handling::YarnErrorHandling,
+        inspector::{DialogueRunnerState, YarnProjectInfo},
+        line_provider::{
+            AssetPathConvention, AssetProvider, ExpectedAsset, LineAssets, LineProviderSystemSet,
+            ProvidedText, TextProvider,
+        },
+        localization::{
+            Localization, LocalizationSystemSet, Localizations, StaleTranslationReport,
+        },
+        option_navigation::{OptionNavigation, OptionNavigationAction, OptionNavigationBindings},
         plugin::{YarnFileSource, YarnSpinnerPlugin, YarnSpinnerSystemSet},
-        project::YarnProject,
+        project::{
+            yarn_project_ready, AdditionalYarnProject, CompilationSystemSet, YarnProject,
+            YarnProjectId, YarnProjectLoading, YarnProjects,
+        },
+        replay::DialogueTraceAsset,
+        replication::{
+            ReplicateDialogue, ReplicatedDialogueEvent, ReplicatedLine, ReplicatedOption,
+        },
+        scene_transition::{SceneTransition, SceneTransitions},
+        standard_commands::{
+            DialogueCamera, ScreenFade, StandardCommand, YarnSpinnerStandardCommandsPlugin,
+        },
+        system_functions::YarnSystemFunction,
+        transcript::{DialogueTranscript, TranscriptEntry},
+        variable_defaults_asset::VariableDefaults,
         yarn_file_asset::YarnFile,
+        yarn_program_asset::YarnProgram,
+    };
+    #[cfg(feature = "audio_assets")]
+    pub use crate::{
+        default_impl::AudioAssetProvider, line_provider::VoiceOver,
+        standard_commands::DialogueMusic,
     };
     pub(crate) use crate::{localization::StringsFile, utils::*};
+    #[cfg(feature = "text")]
+    pub use crate::{
+        localization::{LanguageFontSystemSet, LanguageFontText, LanguageFonts},
+        markup_text::MarkupTextStyles,
+    };
     pub(crate) use anyhow::{Context, Error, Result};
     pub(crate) use serde::{Deserialize, Serialize};
     pub(crate) use yarnspinner::prelude::*;
     pub use yarnspinner::prelude::{
-        IntoYarnValueFromNonYarnValue, Language, LineId, MarkupAttribute, MarkupValue, OptionId,
-        VariableStorage, YarnFn, YarnLibrary, YarnValue,
+        CustomType, DialogueMetrics, IntoYarnValueFromNonYarnValue, Language, LineId,
+        MarkupAttribute, MarkupValue, OptionId, StoryState, VariableStorage, YarnFn, YarnFnContext,
+        YarnLibrary, YarnList, YarnObject, YarnObjectType, YarnValue,
     };
+    pub use yarnspinner::runtime::{DialogueTrace, TraceStep};
     pub(crate) type SystemResult = Result<()>;
 }
 
@@ -1,9 +1,12 @@
+pub use asset_provider::{
+    file_extensions, AssetPathConvention, AssetProvider, CharacterAssetProvider, ExpectedAsset,
+    FileExtensionAssetProvider, LineAssets,
+};
 #[cfg(feature = "audio_assets")]
-pub use asset_provider::AudioAssetProvider;
-pub use asset_provider::{file_extensions, AssetProvider, FileExtensionAssetProvider, LineAssets};
+pub use asset_provider::{AudioAssetProvider, VoiceOver};
 use bevy::prelude::*;
 pub(crate) use text_provider::SharedTextProvider;
-pub use text_provider::{StringsFileTextProvider, TextProvider};
+pub use text_provider::{ProvidedText, StringsFileTextProvider, TextProvider};
 
 mod asset_provider;
 mod text_provider;
@@ -13,5 +16,7 @@ pub(crate) fn line_provider_plugin(app: &mut App) {
         .add_plugins(text_provider::text_provider_plugin);
 }
 
+/// The [`SystemSet`] containing the systems that load the text and assets of lines for [`TextProvider`]s and [`AssetProvider`]s.
+/// It runs before the [`DialogueExecutionSystemSet`](crate::prelude::DialogueExecutionSystemSet).
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, SystemSet)]
-pub(crate) struct LineProviderSystemSet;
+pub struct LineProviderSystemSet;
@@ -1,20 +1,26 @@
 use crate::prelude::*;
 use crate::UnderlyingYarnLine;
+pub use asset_path_convention::AssetPathConvention;
 #[cfg(feature = "audio_assets")]
-pub use audio_asset_provider_plugin::AudioAssetProvider;
+pub use audio_asset_provider_plugin::{AudioAssetProvider, VoiceOver};
 use bevy::asset::{Asset, LoadedUntypedAsset};
 use bevy::prelude::*;
 use bevy::utils::HashMap;
+pub use character_asset_provider_plugin::CharacterAssetProvider;
 pub use file_extension_asset_provider_plugin::{file_extensions, FileExtensionAssetProvider};
 use std::any::Any;
 use std::fmt::Debug;
+use std::path::PathBuf;
 
+mod asset_path_convention;
 #[cfg(feature = "audio_assets")]
 mod audio_asset_provider_plugin;
+mod character_asset_provider_plugin;
 mod file_extension_asset_provider_plugin;
 
 pub(crate) fn asset_provider_plugin(app: &mut App) {
-    app.add_plugins(file_extension_asset_provider_plugin::file_extension_asset_provider_plugin);
+    app.add_plugins(file_extension_asset_provider_plugin::file_extension_asset_provider_plugin)
+        .add_plugins(character_asset_provider_plugin::character_asset_provider_plugin);
 
     #[cfg(feature = "audio_assets")]
     app.add_plugins(audio_asset_provider_plugin::audio_asset_provider_plugin);
@@ -22,7 +28,8 @@ pub(crate) fn asset_provider_plugin(app: &mut App) {
 
 /// Trait for providing assets for lines, e.g. audio files or character portraits.
 /// If the `audio_assets` feature is enabled, you can use the bundled [`AudioAssetProvider`] struct to retrieve audio files.
-/// You can also fetch assets in a similar way by using the [`FileExtensionAssetProvider`] struct.
+/// You can also fetch assets in a similar way by using the [`FileExtensionAssetProvider`] struct,
+/// or provide an asset per speaking character, such as a portrait, with the [`CharacterAssetProvider`] struct.
 pub trait AssetProvider: Debug + Send + Sync {
     /// Returns the type as a [`dyn Any`]. Used for polymorphism. Should be implemented like this:
     /// ```
@@ -149,10 +156,28 @@ pub trait AssetProvider: Debug + Send + Sync {
     /// Returns the [`LineAssets`] for the given [`UnderlyingYarnLine`]. Will only be called if [`AssetProvider::update_asset_availability`] returns `true`,
     /// so an implementor is expected to panic if the assets are not available.
     fn get_assets(&self, line: &UnderlyingYarnLine) -> LineAssets;
+
+    /// Returns the assets this [`AssetProvider`] expects to find for the given lines in all supported languages. Used by [`ContentValidation`] to report missing assets.
+    /// The default implementation expects no assets, so nothing is reported.
+    fn expected_assets(&self, _line_ids: &[LineId]) -> Vec<ExpectedAsset> {
+        Vec::new()
+    }
+}
+
+/// An asset that an [`AssetProvider`] expects to exist, see [`AssetProvider::expected_assets`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExpectedAsset {
+    /// The line the asset belongs to, or [`None`] if it is not tied to a single line, like a character portrait.
+    pub line_id: Option<LineId>,
+    /// The language the asset is localized for, or [`None`] if it is the same in all languages, like a character portrait.
+    pub language: Option<Language>,
+    /// The paths inside the `assets` folder at which the asset may be found. The asset counts as missing if none of them exists,
+    /// so this can list the same asset in different file formats.
+    pub paths: Vec<PathBuf>,
 }
 
 /// Assets that were provided by one or more [`AssetProvider`]s. Stores them in the form of [`Handle`]s.
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, TypePath)]
 pub struct LineAssets(HashMap<&'static str, UntypedHandle>);
 impl LineAssets {
     /// Creates a new empty [`LineAssets`] struct.
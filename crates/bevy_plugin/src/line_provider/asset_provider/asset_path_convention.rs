@@ -0,0 +1,86 @@
+use crate::prelude::*;
+use std::path::PathBuf;
+
+/// Describes where a [`FileExtensionAssetProvider`] looks for the asset of a line in a given language.
+/// Set it with [`FileExtensionAssetProvider::with_path_convention`].
+///
+/// The path is built from a template relative to the `assets` folder, in which these placeholders are replaced:
+/// - `{folder}`: the [`Localization::assets_sub_folder`] of the language, e.g. `dialogue/de-CH`
+/// - `{language}`: the language itself, e.g. `de-CH`
+/// - `{line_id}`: the ID of the line without the `line:` prefix, e.g. `123`
+/// - `{extension}`: one of the file extensions registered for the asset type, e.g. `ogg`
+///
+/// The default template is [`AssetPathConvention::DEFAULT_TEMPLATE`].
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy_yarnspinner::prelude::*;
+/// let convention = AssetPathConvention::new("voice/{language}/{line_id}.{extension}")
+///     .with_base_language_fallback(true);
+/// let localization = Localization::from("de-CH");
+/// assert_eq!(
+///     std::path::PathBuf::from("voice/de-CH/123.ogg"),
+///     convention.path(&localization, &LineId::from("line:123"), "ogg")
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssetPathConvention {
+    template: String,
+    fallback_to_base_language: bool,
+}
+
+impl Default for AssetPathConvention {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TEMPLATE)
+    }
+}
+
+impl AssetPathConvention {
+    /// Looks up assets in the [`Localization::assets_sub_folder`] of the language, named after the line ID.
+    pub const DEFAULT_TEMPLATE: &'static str = "{folder}/{line_id}.{extension}";
+
+    /// Creates a convention from a template as described in the type documentation, without fallback to the base language.
+    pub fn new(template: impl Into<String>) -> Self {
+        Self {
+            template: template.into(),
+            fallback_to_base_language: false,
+        }
+    }
+
+    /// If set, the asset of the base language is used whenever a line has no asset in the current language. Defaults to `false`.
+    /// Missing assets are still reported per language by the [`ContentValidationReport`].
+    #[must_use]
+    pub fn with_base_language_fallback(mut self, fallback_to_base_language: bool) -> Self {
+        self.fallback_to_base_language = fallback_to_base_language;
+        self
+    }
+
+    /// Returns the template passed to [`AssetPathConvention::new`].
+    #[must_use]
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Returns whether the asset of the base language is used when a line has no asset in the current language.
+    #[must_use]
+    pub fn falls_back_to_base_language(&self) -> bool {
+        self.fallback_to_base_language
+    }
+
+    /// Returns the path inside the `assets` folder at which the asset of the line is expected in the given localization.
+    #[must_use]
+    pub fn path(&self, localization: &Localization, line_id: &LineId, extension: &str) -> PathBuf {
+        let folder = localization
+            .assets_sub_folder
+            .to_string_lossy()
+            .replace('\\', "/");
+        let path = self
+            .template
+            .replace("{folder}", folder.trim_end_matches('/'))
+            .replace("{language}", &localization.language.to_string())
+            .replace("{line_id}", line_id.0.trim_start_matches("line:"))
+            .replace("{extension}", extension.trim_start_matches('.'));
+        PathBuf::from(path)
+    }
+}
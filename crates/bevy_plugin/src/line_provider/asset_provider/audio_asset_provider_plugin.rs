@@ -1,10 +1,20 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::events::{DialogueCompleteEvent, PresentLineEvent};
 use crate::prelude::*;
 use bevy::asset::LoadedUntypedAsset;
 use bevy::prelude::*;
 use std::any::Any;
 use std::fmt::Debug;
 
-pub(crate) fn audio_asset_provider_plugin(_app: &mut App) {}
+pub(crate) fn audio_asset_provider_plugin(app: &mut App) {
+    app.register_type::<VoiceOver>().add_systems(
+        Update,
+        (play_voice_over, advance_after_voice_over)
+            .chain()
+            .after(DialogueExecutionSystemSet)
+            .in_set(YarnSpinnerSystemSet),
+    );
+}
 
 /// A wrapper around [`FileExtensionAssetProvider`] that is configured to load audio assets.
 /// See [`FileExtensionAssetProvider`] for information on how assets are searched.
@@ -12,17 +22,29 @@ pub(crate) fn audio_asset_provider_plugin(_app: &mut App) {}
 /// Because this asset provider requires knowledge of the current language, it will only fetch assets if you set up Yarn Spinner with [`Localizations`] using
 /// [`YarnSpinnerPlugin::with_localizations`] or [`LoadYarnProjectEvent::with_localizations`](crate::deferred_loading::LoadYarnProjectEvent::with_localizations).
 ///
+/// By default, the loaded [`AudioSource`]s are only handed to the dialogue view through [`LocalizedLine::assets`].
+/// Call [`AudioAssetProvider::with_voice_over_playback`] to have them played as voice-over instead,
+/// and [`AudioAssetProvider::with_auto_advance`] to additionally continue the dialogue once a clip has finished.
+///
 /// Requires the `audio_assets` feature, in which case it can be used in a [`DialogueRunner`] by calling [`DialogueRunnerBuilder::add_asset_provider`].
 #[derive(Debug, Clone)]
-pub struct AudioAssetProvider(FileExtensionAssetProvider);
+pub struct AudioAssetProvider {
+    provider: FileExtensionAssetProvider,
+    play_voice_over: bool,
+    auto_advance: bool,
+}
 
 impl Default for AudioAssetProvider {
     fn default() -> Self {
-        Self(
-            FileExtensionAssetProvider::new().with_file_extensions(crate::file_extensions! {
-                AudioSource: ["mp3", "ogg", "wav"],
-            }),
-        )
+        Self {
+            provider: FileExtensionAssetProvider::new().with_file_extensions(
+                crate::file_extensions! {
+                    AudioSource: ["mp3", "ogg", "wav"],
+                },
+            ),
+            play_voice_over: false,
+            auto_advance: false,
+        }
     }
 }
 
@@ -31,6 +53,133 @@ impl AudioAssetProvider {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// If set, the audio of a line is played when the line is presented. The clip is stopped when the [`DialogueRunner`] presents the next line or completes.
+    /// The playing clip can be found through the [`VoiceOver`] component. Defaults to `false`.
+    pub fn with_voice_over_playback(mut self, play_voice_over: bool) -> Self {
+        self.play_voice_over = play_voice_over;
+        self
+    }
+
+    /// If set, the [`DialogueRunner`] continues as soon as the voice-over of the current line has finished playing,
+    /// as if [`DialogueRunner::continue_in_next_update`] was called. Implies [`AudioAssetProvider::with_voice_over_playback`]. Defaults to `false`.
+    ///
+    /// Lines without audio are not advanced automatically.
+    pub fn with_auto_advance(mut self, auto_advance: bool) -> Self {
+        self.auto_advance = auto_advance;
+        self.play_voice_over |= auto_advance;
+        self
+    }
+
+    /// Sets where the audio files of a line are looked up for each language, see [`FileExtensionAssetProvider::with_path_convention`].
+    pub fn with_path_convention(mut self, path_convention: AssetPathConvention) -> Self {
+        self.provider = self.provider.with_path_convention(path_convention);
+        self
+    }
+
+    /// Returns whether the audio of a line is played when it is presented, see [`AudioAssetProvider::with_voice_over_playback`].
+    #[must_use]
+    pub fn plays_voice_over(&self) -> bool {
+        self.play_voice_over
+    }
+
+    /// Returns whether the dialogue continues after the voice-over finished playing, see [`AudioAssetProvider::with_auto_advance`].
+    #[must_use]
+    pub fn auto_advances(&self) -> bool {
+        self.auto_advance
+    }
+}
+
+/// Marks an entity playing the voice-over of a line, spawned for [`DialogueRunner`]s using an [`AudioAssetProvider`]
+/// configured with [`AudioAssetProvider::with_voice_over_playback`].
+/// The entity is despawned once the clip has finished playing or the dialogue has moved on.
+#[derive(Debug, Clone, PartialEq, Eq, Component, Reflect)]
+#[reflect(Debug, PartialEq, Component)]
+pub struct VoiceOver {
+    /// The ID of the line being spoken.
+    pub line_id: LineId,
+    /// The [`DialogueRunner`] that presented the line.
+    pub source: Entity,
+}
+
+fn play_voice_over(
+    mut commands: Commands,
+    mut present_line_events: EventReader<PresentLineEvent>,
+    mut dialogue_complete_events: EventReader<DialogueCompleteEvent>,
+    mut dialogue_runners: Query<&mut DialogueRunner>,
+    voice_overs: Query<(Entity, &VoiceOver)>,
+) {
+    for event in dialogue_complete_events.read() {
+        stop_voice_over(&mut commands, &voice_overs, event.source);
+    }
+    for event in present_line_events.read() {
+        stop_voice_over(&mut commands, &voice_overs, event.source);
+        let Ok(mut dialogue_runner) = dialogue_runners.get_mut(event.source) else {
+            continue;
+        };
+        let plays_voice_over = dialogue_runner
+            .asset_provider::<AudioAssetProvider>()
+            .is_some_and(AudioAssetProvider::plays_voice_over);
+        if !plays_voice_over {
+            continue;
+        }
+        let Some(source) = event.line.assets.get_handle::<AudioSource>() else {
+            continue;
+        };
+        commands.spawn((
+            Name::new(format!(
+                "Yarn Spinner voice-over for line {}",
+                event.line.id
+            )),
+            AudioBundle {
+                source,
+                settings: PlaybackSettings::ONCE,
+            },
+            VoiceOver {
+                line_id: event.line.id.clone(),
+                source: event.source,
+            },
+        ));
+        dialogue_runner.hold_auto_advance();
+    }
+}
+
+fn stop_voice_over(
+    commands: &mut Commands,
+    voice_overs: &Query<(Entity, &VoiceOver)>,
+    source: Entity,
+) {
+    for (entity, voice_over) in voice_overs.iter() {
+        if voice_over.source == source {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn advance_after_voice_over(
+    mut commands: Commands,
+    voice_overs: Query<(Entity, &VoiceOver, &AudioSink)>,
+    mut dialogue_runners: Query<&mut DialogueRunner>,
+) {
+    for (entity, voice_over, sink) in voice_overs.iter() {
+        if !sink.empty() {
+            continue;
+        }
+        commands.entity(entity).despawn_recursive();
+        let Ok(mut dialogue_runner) = dialogue_runners.get_mut(voice_over.source) else {
+            continue;
+        };
+        dialogue_runner.release_auto_advance();
+        let auto_advances = dialogue_runner
+            .asset_provider::<AudioAssetProvider>()
+            .is_some_and(AudioAssetProvider::auto_advances);
+        if auto_advances
+            && dialogue_runner.is_running()
+            && !dialogue_runner.is_waiting_for_option_selection()
+        {
+            dialogue_runner.continue_in_next_update();
+        }
+    }
 }
 
 impl AssetProvider for AudioAssetProvider {
@@ -43,33 +192,38 @@ impl AssetProvider for AudioAssetProvider {
     }
 
     fn get_language(&self) -> Option<Language> {
-        self.0.get_language()
+        self.provider.get_language()
     }
 
     fn set_language(&mut self, language: Option<Language>) {
-        self.0.set_language(language)
+        self.provider.set_language(language)
     }
 
     fn set_localizations(&mut self, localizations: Localizations) {
-        self.0.set_localizations(localizations)
+        self.provider.set_localizations(localizations)
     }
 
     fn set_asset_server(&mut self, asset_server: AssetServer) {
-        self.0.set_asset_server(asset_server)
+        self.provider.set_asset_server(asset_server)
     }
 
     fn update_asset_availability(
         &mut self,
         loaded_untyped_assets: &Assets<LoadedUntypedAsset>,
     ) -> bool {
-        self.0.update_asset_availability(loaded_untyped_assets)
+        self.provider
+            .update_asset_availability(loaded_untyped_assets)
     }
 
     fn accept_line_hints(&mut self, line_ids: &[LineId]) {
-        self.0.accept_line_hints(line_ids)
+        self.provider.accept_line_hints(line_ids)
     }
 
     fn get_assets(&self, line: &YarnLine) -> LineAssets {
-        self.0.get_assets(line)
+        self.provider.get_assets(line)
+    }
+
+    fn expected_assets(&self, line_ids: &[LineId]) -> Vec<ExpectedAsset> {
+        self.provider.expected_assets(line_ids)
     }
 }
@@ -0,0 +1,192 @@
+use crate::fmt_utils::SkipDebug;
+use crate::prelude::*;
+use crate::UnderlyingYarnLine;
+use bevy::asset::{LoadState, LoadedUntypedAsset};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::any::Any;
+use std::fmt;
+
+pub(crate) fn character_asset_provider_plugin(_app: &mut App) {}
+
+/// An [`AssetProvider`] that provides an asset per character instead of per line, e.g. a portrait or sprite of the speaker.
+/// The character of a line is the one set by its `character` attribute, which Yarn adds for lines like `Hag: Now your *third* wish.`.
+/// Lines spoken by a character that is not registered via [`CharacterAssetProvider::with_character`] and lines without a character get no asset.
+///
+/// All registered assets are loaded as soon as the provider is added to a [`DialogueRunner`] with [`DialogueRunnerBuilder::add_asset_provider`].
+/// The provided asset of a line can then be retrieved from the [`LocalizedLine::assets`] of a [`PresentLineEvent`](crate::events::PresentLineEvent).
+/// Assets that fail to load are skipped.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_yarnspinner::prelude::*;
+/// use bevy_yarnspinner::events::PresentLineEvent;
+///
+/// fn setup(mut commands: Commands, project: Res<YarnProject>) {
+///     let portraits = CharacterAssetProvider::<Image>::new()
+///         .with_character("Hag", "portraits/hag.png")
+///         .with_character("Man", "portraits/man.png");
+///     let dialogue_runner = project
+///         .build_dialogue_runner()
+///         .add_asset_provider(portraits)
+///         .build();
+///     commands.spawn(dialogue_runner);
+/// }
+///
+/// #[derive(Component)]
+/// struct SpeakerPortrait;
+///
+/// fn show_portrait(
+///     mut events: EventReader<PresentLineEvent>,
+///     mut portrait: Query<&mut Handle<Image>, With<SpeakerPortrait>>,
+/// ) {
+///     for event in events.read() {
+///         if let Some(image) = event.line.assets.get_handle::<Image>() {
+///             *portrait.single_mut() = image;
+///         }
+///     }
+/// }
+/// ```
+pub struct CharacterAssetProvider<T: Asset> {
+    language: Option<Language>,
+    asset_server: SkipDebug<Option<AssetServer>>,
+    paths: HashMap<String, String>,
+    handles: HashMap<String, Handle<T>>,
+}
+
+impl<T: Asset> CharacterAssetProvider<T> {
+    /// Initializes a new [`CharacterAssetProvider`] without any characters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the asset at the given path for all lines spoken by the character with the given name.
+    /// The path is relative to the assets folder, just like for [`AssetServer::load`].
+    pub fn with_character(mut self, name: impl Into<String>, path: impl Into<String>) -> Self {
+        self.paths.insert(name.into(), path.into());
+        self
+    }
+
+    /// Registers multiple characters at once. See [`CharacterAssetProvider::with_character`].
+    pub fn with_characters<N, P>(mut self, characters: impl IntoIterator<Item = (N, P)>) -> Self
+    where
+        N: Into<String>,
+        P: Into<String>,
+    {
+        self.paths.extend(
+            characters
+                .into_iter()
+                .map(|(name, path)| (name.into(), path.into())),
+        );
+        self
+    }
+
+    /// Returns the handle of the asset registered for the given character, if the provider was already added to a [`DialogueRunner`].
+    #[must_use]
+    pub fn get_handle(&self, character_name: &str) -> Option<Handle<T>> {
+        self.handles.get(character_name).cloned()
+    }
+}
+
+impl<T: Asset> Default for CharacterAssetProvider<T> {
+    fn default() -> Self {
+        Self {
+            language: None,
+            asset_server: default(),
+            paths: default(),
+            handles: default(),
+        }
+    }
+}
+
+impl<T: Asset> Clone for CharacterAssetProvider<T> {
+    fn clone(&self) -> Self {
+        Self {
+            language: self.language.clone(),
+            asset_server: self.asset_server.clone(),
+            paths: self.paths.clone(),
+            handles: self.handles.clone(),
+        }
+    }
+}
+
+impl<T: Asset> fmt::Debug for CharacterAssetProvider<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CharacterAssetProvider")
+            .field("asset_type", &T::type_path())
+            .field("language", &self.language)
+            .field("asset_server", &self.asset_server)
+            .field("paths", &self.paths)
+            .field("handles", &self.handles)
+            .finish()
+    }
+}
+
+impl<T: Asset> AssetProvider for CharacterAssetProvider<T> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn get_language(&self) -> Option<Language> {
+        self.language.clone()
+    }
+
+    fn set_language(&mut self, language: Option<Language>) {
+        self.language = language;
+    }
+
+    fn set_localizations(&mut self, _localizations: Localizations) {}
+
+    fn set_asset_server(&mut self, asset_server: AssetServer) {
+        self.handles = self
+            .paths
+            .iter()
+            .map(|(name, path)| (name.clone(), asset_server.load(path.clone())))
+            .collect();
+        self.asset_server.replace(asset_server);
+    }
+
+    fn update_asset_availability(
+        &mut self,
+        _loaded_untyped_assets: &Assets<LoadedUntypedAsset>,
+    ) -> bool {
+        let Some(asset_server) = self.asset_server.as_ref() else {
+            return false;
+        };
+        self.handles.retain(|_name, handle| {
+            !matches!(
+                asset_server.get_load_state(handle.id()),
+                Some(LoadState::Failed(..))
+            )
+        });
+        self.handles
+            .values()
+            .all(|handle| asset_server.is_loaded_with_dependencies(handle.id()))
+    }
+
+    fn accept_line_hints(&mut self, _line_ids: &[LineId]) {}
+
+    fn get_assets(&self, line: &UnderlyingYarnLine) -> LineAssets {
+        line.character_name()
+            .and_then(|name| self.handles.get(name))
+            .map(|handle| LineAssets::with_assets([(T::type_path(), handle.clone().untyped())]))
+            .unwrap_or_default()
+    }
+
+    fn expected_assets(&self, _line_ids: &[LineId]) -> Vec<ExpectedAsset> {
+        self.paths
+            .values()
+            .map(|path| ExpectedAsset {
+                line_id: None,
+                language: None,
+                paths: vec![path.into()],
+            })
+            .collect()
+    }
+}
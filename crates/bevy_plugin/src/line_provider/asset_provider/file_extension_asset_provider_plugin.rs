@@ -6,6 +6,7 @@ use bevy::prelude::*;
 use bevy::utils::{HashMap, HashSet};
 use std::any::Any;
 use std::fmt::Debug;
+use std::iter;
 use std::path::PathBuf;
 
 pub(crate) fn file_extension_asset_provider_plugin(_app: &mut App) {}
@@ -17,6 +18,7 @@ pub(crate) fn file_extension_asset_provider_plugin(_app: &mut App) {}
 ///
 /// By default, the line asset subdirectory will be `"dialogue/<language>"`. So for the language "en-US" and the line ID "123", the provider will
 /// specifically look for "assets/dialogue/en-US/123.png" when calling [`FileExtensionAssetProvider::get_assets`].
+/// Use [`FileExtensionAssetProvider::with_path_convention`] to look for the assets somewhere else or to fall back to the assets of the base language.
 /// Because this requires knowledge of the current language, this provider will only fetch assets if you set up Yarn Spinner with [`Localizations`] using
 /// [`YarnSpinnerPlugin::with_localizations`] or [`LoadYarnProjectEvent::with_localizations`](crate::deferred_loading::LoadYarnProjectEvent::with_localizations).
 ///
@@ -33,6 +35,7 @@ pub struct FileExtensionAssetProvider {
     loaded_handles: HashMap<PathBuf, UntypedHandle>,
     line_ids: HashSet<LineId>,
     file_extensions: HashMap<&'static str, Vec<String>>,
+    path_convention: AssetPathConvention,
 }
 
 /// A convenience macro for specifying file extensions used by [`FileExtensionAssetProvider::with_file_extensions`].
@@ -100,6 +103,36 @@ impl FileExtensionAssetProvider {
             }));
         self
     }
+
+    /// Sets where the assets of a line are looked up for each language. Defaults to [`AssetPathConvention::default`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_yarnspinner::file_extensions;
+    /// use bevy_yarnspinner::prelude::*;
+    ///
+    /// let file_extension_provider = FileExtensionAssetProvider::new()
+    ///     .with_file_extensions(file_extensions! {
+    ///        AudioSource: ["ogg"],
+    ///     })
+    ///     .with_path_convention(
+    ///         AssetPathConvention::new("dialogue/{language}/{line_id}.{extension}")
+    ///             .with_base_language_fallback(true),
+    ///     );
+    /// ```
+    pub fn with_path_convention(mut self, path_convention: AssetPathConvention) -> Self {
+        self.path_convention = path_convention;
+        self.reload_assets();
+        self
+    }
+
+    /// Returns where the assets of a line are looked up, see [`FileExtensionAssetProvider::with_path_convention`].
+    #[must_use]
+    pub fn path_convention(&self) -> &AssetPathConvention {
+        &self.path_convention
+    }
 }
 
 impl AssetProvider for FileExtensionAssetProvider {
@@ -180,59 +213,92 @@ impl AssetProvider for FileExtensionAssetProvider {
     }
 
     fn get_assets(&self, line: &UnderlyingYarnLine) -> LineAssets {
-        if let Some(language) = self.language.as_ref() {
-            if let Some(localizations) = self.localizations.as_ref() {
-                if let Some(localization) = localizations.supported_localization(language) {
-                    let dir = localization.assets_sub_folder.as_path();
-                    let file_name_without_extension = line.id.0.trim_start_matches("line:");
-                    let assets = self
-                        .file_extensions
-                        .iter()
-                        .filter_map(|(type_id, exts)| {
-                            exts.iter().find_map(|ext| {
-                                let file_name = format!("{}.{}", file_name_without_extension, ext);
-                                let path = dir.join(file_name);
-                                self.loaded_handles
-                                    .get(&path)
-                                    .map(|handle| (*type_id, handle.clone()))
-                            })
+        let localizations = self.active_localizations();
+        let assets = self
+            .file_extensions
+            .iter()
+            .filter_map(|(type_id, exts)| {
+                localizations.iter().find_map(|localization| {
+                    exts.iter().find_map(|ext| {
+                        let path = self.path_convention.path(localization, &line.id, ext);
+                        self.loaded_handles
+                            .get(&path)
+                            .map(|handle| (*type_id, handle.clone()))
+                    })
+                })
+            })
+            .collect::<HashSet<_>>();
+        LineAssets::with_assets(assets)
+    }
+
+    fn expected_assets(&self, line_ids: &[LineId]) -> Vec<ExpectedAsset> {
+        let Some(localizations) = self.localizations.as_ref() else {
+            return Vec::new();
+        };
+        let localizations =
+            iter::once(&localizations.base_localization).chain(localizations.translations.iter());
+        localizations
+            .flat_map(|localization| {
+                line_ids.iter().flat_map(move |line_id| {
+                    self.file_extensions
+                        .values()
+                        .map(move |exts| ExpectedAsset {
+                            line_id: Some(line_id.clone()),
+                            language: Some(localization.language.clone()),
+                            paths: exts
+                                .iter()
+                                .map(|ext| self.path_convention.path(localization, line_id, ext))
+                                .collect(),
                         })
-                        .collect::<HashSet<_>>();
-                    return LineAssets::with_assets(assets);
-                } else {
-                    panic!("Tried to find an asset for \"{language}\", which is a language that is not supported by localizations");
-                }
-            }
-        }
-        default()
+                })
+            })
+            .collect()
     }
 }
 
 impl FileExtensionAssetProvider {
+    /// Returns the localizations whose assets are used, in order of preference:
+    /// the one of the current language, followed by the base localization if the [`AssetPathConvention`] falls back to it.
+    fn active_localizations(&self) -> Vec<&Localization> {
+        let (Some(language), Some(localizations)) =
+            (self.language.as_ref(), self.localizations.as_ref())
+        else {
+            return Vec::new();
+        };
+        let Some(localization) = localizations.supported_localization(language) else {
+            panic!("Tried to find an asset for \"{language}\", which is a language that is not supported by localizations");
+        };
+        let base_localization = &localizations.base_localization;
+        let mut active_localizations = vec![localization];
+        if self.path_convention.falls_back_to_base_language()
+            && base_localization.language != localization.language
+        {
+            active_localizations.push(base_localization);
+        }
+        active_localizations
+    }
+
     fn reload_assets(&mut self) {
-        if let Some(language) = self.language.as_ref() {
-            if let Some(localizations) = self.localizations.as_ref() {
-                if let Some(localization) = localizations.supported_localization(language) {
-                    let dir = localization.assets_sub_folder.as_path();
-                    self.loading_handles.clear();
-                    self.loaded_handles.clear();
-                    let Some(asset_server) = self.asset_server.as_ref() else {
-                        return;
-                    };
-                    for line_id in self.line_ids.iter() {
-                        for extension in self.file_extensions.values().flatten() {
-                            let file_name =
-                                format!("{}.{extension}", line_id.0.trim_start_matches("line:"));
-                            let path = dir.join(file_name);
-                            let asset_path = path.to_string_lossy().replace('\\', "/");
-                            let handle = asset_server.load_untyped(asset_path);
-                            self.loading_handles.insert(path, handle);
-                        }
-                    }
-                } else {
-                    panic!("Tried to find an asset for \"{language}\", which is a language that is not supported by localizations");
+        let mut paths = Vec::new();
+        for localization in self.active_localizations() {
+            for line_id in self.line_ids.iter() {
+                for extension in self.file_extensions.values().flatten() {
+                    paths.push(self.path_convention.path(localization, line_id, extension));
                 }
             }
         }
+        if self.language.is_none() || self.localizations.is_none() {
+            return;
+        }
+        self.loading_handles.clear();
+        self.loaded_handles.clear();
+        let Some(asset_server) = self.asset_server.as_ref() else {
+            return;
+        };
+        for path in paths {
+            let asset_path = path.to_string_lossy().replace('\\', "/");
+            let handle = asset_server.load_untyped(asset_path);
+            self.loading_handles.insert(path, handle);
+        }
     }
 }
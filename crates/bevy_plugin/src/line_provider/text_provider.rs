@@ -1,3 +1,6 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::error_handling::{report_error, YarnErrorContext};
+use crate::events::{MissingTranslationEvent, YarnErrorEvent};
 use crate::line_provider::LineProviderSystemSet;
 use crate::prelude::*;
 use crate::UnderlyingTextProvider;
@@ -15,12 +18,30 @@ pub(crate) fn text_provider_plugin(app: &mut App) {
         .add_plugins(strings_file_text_provider::strings_file_text_provider_plugin)
         .add_systems(
             Update,
-            fetch_resources
-                .in_set(LineProviderSystemSet)
-                .in_set(YarnSpinnerSystemSet),
+            (
+                fetch_resources
+                    .in_set(LineProviderSystemSet)
+                    .in_set(YarnSpinnerSystemSet),
+                send_missing_translation_events
+                    .after(DialogueExecutionSystemSet)
+                    .in_set(YarnSpinnerSystemSet),
+                report_text_provider_errors
+                    .after(LineProviderSystemSet)
+                    .in_set(YarnSpinnerSystemSet),
+            ),
         );
 }
 
+/// The text of a line as returned by [`TextProvider::get_text_with_fallback_info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProvidedText {
+    /// The text of the line.
+    pub text: String,
+    /// Whether the text is in the base language instead of the language set by [`TextProvider::set_language`],
+    /// e.g. because the line is not translated or the translation has not been loaded yet.
+    pub is_fallback: bool,
+}
+
 /// Trait for the provider the [`DialogueRunner`]s text. By default, this is a [`StringsFileTextProvider`].
 /// You can override this with [`DialogueRunnerBuilder::with_text_provider`] if you want a custom localization strategy.
 /// For most users however, the default is fine.
@@ -40,6 +61,50 @@ pub trait TextProvider: UnderlyingTextProvider {
     /// This functionality is split into two functions because [`TextProvider::take_fetched_assets`] is mutable,
     /// so we lose access to the [`World`] when calling it since it contains this very [`TextProvider`].
     fn fetch_assets(&self, world: &World) -> Option<Box<dyn Any + 'static>>;
+
+    /// Like [`TextProvider::get_text`], but also tells whether the text had to fall back to the base language.
+    /// Unlike [`TextProvider::get_text`], this does not report the line as a missing translation.
+    /// The default implementation assumes that [`TextProvider::get_text`] never falls back.
+    fn get_text_with_fallback_info(&self, id: &LineId) -> Option<ProvidedText> {
+        self.get_text(id).map(|text| ProvidedText {
+            text,
+            is_fallback: false,
+        })
+    }
+
+    /// Keeps the text of the given language available in addition to the one set by [`UnderlyingTextProvider::set_language`],
+    /// so that it can be looked up with [`TextProvider::get_text_in_language`], e.g. to display subtitles in two languages at once.
+    /// The default implementation does nothing, so only the current language is available.
+    fn load_additional_language(&mut self, _language: Language) {}
+
+    /// Stops keeping the text of a language loaded by [`TextProvider::load_additional_language`] available.
+    /// The default implementation does nothing.
+    fn unload_additional_language(&mut self, _language: &Language) {}
+
+    /// Returns the text for the given [`LineId`] in the given language, which is either the current language,
+    /// the base language or one loaded by [`TextProvider::load_additional_language`].
+    /// Returns `None` if the language is not available (yet) or has no text for the line. Never falls back to the base language.
+    /// The default implementation only knows the current language.
+    fn get_text_in_language(&self, id: &LineId, language: &Language) -> Option<String> {
+        if self.get_language().as_ref() != Some(language) {
+            return None;
+        }
+        self.get_text_with_fallback_info(id)
+            .filter(|provided_text| !provided_text.is_fallback)
+            .map(|provided_text| provided_text.text)
+    }
+
+    /// Returns the IDs of all lines that were missing in the current language since the last call and were thus presented in the base language instead.
+    /// These are sent as [`MissingTranslationEvent`]s. The default implementation never reports missing translations.
+    fn take_missing_translations(&mut self) -> Vec<LineId> {
+        Vec::new()
+    }
+
+    /// Returns all errors that occurred since the last call, e.g. because a strings file failed to load.
+    /// These are handled according to the [`YarnErrorHandling`]. The default implementation never reports errors.
+    fn take_errors(&mut self) -> Vec<Error> {
+        Vec::new()
+    }
 }
 
 pub(crate) fn fetch_resources(world: &mut World) {
@@ -59,3 +124,37 @@ pub(crate) fn fetch_resources(world: &mut World) {
         }
     }
 }
+
+fn send_missing_translation_events(
+    mut dialogue_runners: Query<(Entity, &mut DialogueRunner)>,
+    mut missing_translation_events: EventWriter<MissingTranslationEvent>,
+) {
+    for (source, mut dialogue_runner) in dialogue_runners.iter_mut() {
+        let missing_translations = dialogue_runner.text_provider.take_missing_translations();
+        if missing_translations.is_empty() {
+            continue;
+        }
+        let language = dialogue_runner.text_language().unwrap_or_default();
+        missing_translation_events.send_batch(missing_translations.into_iter().map(|line_id| {
+            MissingTranslationEvent {
+                line_id,
+                language: language.clone(),
+                source,
+            }
+        }));
+    }
+}
+
+fn report_text_provider_errors(
+    mut dialogue_runners: Query<(Entity, &mut DialogueRunner)>,
+    error_handling: Res<YarnErrorHandling>,
+    mut error_events: EventWriter<YarnErrorEvent>,
+) {
+    for (source, mut dialogue_runner) in dialogue_runners.iter_mut() {
+        for error in dialogue_runner.text_provider.take_errors() {
+            let event =
+                YarnErrorEvent::new(error, YarnErrorContext::Localization).with_source(source);
+            report_error(*error_handling, &mut error_events, event);
+        }
+    }
+}
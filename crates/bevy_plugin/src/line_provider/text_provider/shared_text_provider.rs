@@ -43,6 +43,30 @@ impl TextProvider for SharedTextProvider {
     fn fetch_assets(&self, world: &World) -> Option<Box<dyn Any + 'static>> {
         self.0.read().unwrap().fetch_assets(world)
     }
+
+    fn get_text_with_fallback_info(&self, id: &LineId) -> Option<ProvidedText> {
+        self.0.read().unwrap().get_text_with_fallback_info(id)
+    }
+
+    fn load_additional_language(&mut self, language: Language) {
+        self.0.write().unwrap().load_additional_language(language)
+    }
+
+    fn unload_additional_language(&mut self, language: &Language) {
+        self.0.write().unwrap().unload_additional_language(language)
+    }
+
+    fn get_text_in_language(&self, id: &LineId, language: &Language) -> Option<String> {
+        self.0.read().unwrap().get_text_in_language(id, language)
+    }
+
+    fn take_missing_translations(&mut self) -> Vec<LineId> {
+        self.0.write().unwrap().take_missing_translations()
+    }
+
+    fn take_errors(&mut self) -> Vec<Error> {
+        self.0.write().unwrap().take_errors()
+    }
 }
 
 impl UnderlyingTextProvider for SharedTextProvider {
@@ -2,11 +2,13 @@ use crate::fmt_utils::SkipDebug;
 use crate::prelude::*;
 use crate::UnderlyingTextProvider;
 
+use anyhow::anyhow;
+use bevy::asset::LoadState;
 use bevy::ecs::event::ManualEventReader;
 use bevy::prelude::*;
 use std::any::Any;
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 
 pub(crate) fn strings_file_text_provider_plugin(_app: &mut App) {}
 
@@ -15,6 +17,11 @@ pub(crate) fn strings_file_text_provider_plugin(_app: &mut App) {}
 /// this will send the lines as they appear in the Yarn file. If [`DialogueRunner::set_language`] or [`DialogueRunner::set_text_language`] were used to
 /// set the language to a language supported by a translation in the [`Localizations`], this loads the strings file for that translation from the disk at the
 /// specified path. If this fails, the base language is used as a fallback.
+/// The same happens for single lines missing in the strings file, which are reported with a [`MissingTranslationEvent`](crate::events::MissingTranslationEvent)
+/// unless [`YarnSpinnerPlugin::with_strict_translations`] is used, in which case they panic.
+///
+/// Strings files of further translations can be kept loaded at the same time with [`TextProvider::load_additional_language`],
+/// e.g. to display subtitles in two languages. These never become the current language on their own and are looked up with [`TextProvider::get_text_in_language`].
 #[derive(Debug, Clone)]
 pub struct StringsFileTextProvider {
     asset_server: SkipDebug<AssetServer>,
@@ -23,7 +30,11 @@ pub struct StringsFileTextProvider {
     base_string_table: HashMap<LineId, StringInfo>,
     strings_file_handle: Option<Handle<StringsFile>>,
     translation_string_table: Option<HashMap<LineId, String>>,
+    additional_translations: HashMap<Language, AdditionalTranslation>,
     event_reader: Arc<RwLock<ManualEventReader<AssetEvent<StringsFile>>>>,
+    strict_translations: bool,
+    missing_translations: Arc<Mutex<Vec<LineId>>>,
+    errors: Arc<Mutex<Vec<Error>>>,
 }
 
 impl UnderlyingTextProvider for StringsFileTextProvider {
@@ -36,22 +47,19 @@ impl UnderlyingTextProvider for StringsFileTextProvider {
     }
 
     fn get_text(&self, id: &LineId) -> Option<String> {
-        if self.is_base_language() {
-            return self.base_string_table.get(id).map(|info| info.text.clone());
+        let provided_text = self.get_text_with_fallback_info(id)?;
+        if provided_text.is_fallback {
+            let language = self.language.as_ref().unwrap();
+            if self.translation_string_table.is_none() {
+                warn!("Did not find translation for line {id} in language {language} because the strings file has not been loaded yet, falling back to base language.");
+            } else {
+                assert!(!self.strict_translations, "Did not find translation for line {id} in language {language} because it is untranslated. \
+                    Add it to the strings file or disable strict translations to fall back to the base language.");
+                warn!("Did not find translation for line {id} in language {language} because it is untranslated, falling back to base language.");
+                self.missing_translations.lock().unwrap().push(id.clone());
+            }
         }
-
-        self.translation_string_table
-            .as_ref()
-            .and_then(|table| table.get(id).cloned())
-            .or_else(|| {
-                let language = self.language.as_ref().unwrap();
-                if self.translation_string_table.is_some() {
-                    warn!("Did not find translation for line {id} in language {language} because it is untranslated, falling back to base language.");
-                } else {
-                    warn!("Did not find translation for line {id} in language {language} because the strings file has not been loaded yet, falling back to base language.");
-                }
-                self.base_string_table.get(id).map(|info| info.text.clone())
-            })
+        Some(provided_text.text)
     }
 
     fn set_language(&mut self, language: Option<Language>) {
@@ -65,25 +73,12 @@ impl UnderlyingTextProvider for StringsFileTextProvider {
         }
         let language = language.unwrap();
 
-        let Some(localizations) = self.localizations.clone() else {
-            panic!("Set language to {language}, but no localizations have been registered as supported.");
-        };
-        if language == localizations.base_localization.language {
+        if self.is_base_language_code(&language) {
             self.set_language_invalidating_translation(None);
             return;
         }
-        let Some(localization) = localizations.translation(&language) else {
-            let languages = localizations
-                .supported_languages()
-                .map(ToString::to_string)
-                .collect::<Vec<_>>()
-                .join(", ");
-            panic!("Set language to {language}, but that language is not supported. Expected one of {languages}.");
-        };
-        let path = localization.strings_file.as_path();
-        let asset_path = path.to_string_lossy().replace('\\', "/");
-        self.strings_file_handle
-            .replace(self.asset_server.load(asset_path));
+        let handle = self.load_strings_file(&language);
+        self.strings_file_handle.replace(handle);
     }
 
     fn get_language(&self) -> Option<Language> {
@@ -115,7 +110,11 @@ impl StringsFileTextProvider {
             base_string_table: yarn_project.compilation.string_table.clone(),
             strings_file_handle: None,
             translation_string_table: None,
+            additional_translations: HashMap::new(),
             event_reader: Default::default(),
+            strict_translations: yarn_project.strict_translations,
+            missing_translations: Default::default(),
+            errors: Default::default(),
         }
     }
     fn set_language_invalidating_translation(&mut self, language: impl Into<Option<Language>>) {
@@ -125,12 +124,63 @@ impl StringsFileTextProvider {
     }
 
     fn is_base_language(&self) -> bool {
-        self.language.is_none()
-            || self.language.as_ref()
-                == self
-                    .localizations
-                    .as_ref()
-                    .map(|localizations| &localizations.base_localization.language)
+        self.language
+            .as_ref()
+            .is_none_or(|language| self.is_base_language_code(language))
+    }
+
+    fn is_base_language_code(&self, language: &Language) -> bool {
+        self.localizations
+            .as_ref()
+            .is_some_and(|localizations| localizations.base_localization.language == *language)
+    }
+
+    fn load_strings_file(&self, language: &Language) -> Handle<StringsFile> {
+        let Some(localizations) = self.localizations.as_ref() else {
+            panic!("Set language to {language}, but no localizations have been registered as supported.");
+        };
+        let Some(localization) = localizations.translation(language) else {
+            let languages = localizations
+                .supported_languages()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            panic!("Set language to {language}, but that language is not supported. Expected one of {languages}.");
+        };
+        let path = localization.strings_file.as_path();
+        let asset_path = path.to_string_lossy().replace('\\', "/");
+        self.asset_server.load(asset_path)
+    }
+
+    fn fetch_string_table(
+        &self,
+        world: &World,
+        handle: &Handle<StringsFile>,
+        language: &Language,
+    ) -> Option<HashMap<LineId, String>> {
+        if let Some(LoadState::Failed(error)) = self.asset_server.get_load_state(handle) {
+            self.errors.lock().unwrap().push(anyhow!(
+                "Failed to load strings file for language {language}, falling back to base language: {error}"
+            ));
+            return Some(HashMap::new());
+        }
+        if !self.asset_server.is_loaded_with_dependencies(handle) {
+            return None;
+        }
+        let strings_file = world.resource::<Assets<StringsFile>>().get(handle).unwrap();
+        if let Some(record) = strings_file.get_offending_language(language) {
+            let path = self.asset_server.get_path(handle).unwrap();
+            self.errors.lock().unwrap().push(anyhow!("Expected strings file at {path} to only contain language {language}, but its entry with id \"{id}\" is for language {actual_language}.",
+                       path = path.path().display(),
+                       id = record.id,
+                       actual_language = record.language,
+                ));
+        }
+        let string_table = strings_file
+            .iter()
+            .map(|(id, record)| (id.clone(), record.text.clone()))
+            .collect();
+        Some(string_table)
     }
 }
 
@@ -144,45 +194,132 @@ impl TextProvider for StringsFileTextProvider {
     }
 
     fn take_fetched_assets(&mut self, asset: Box<dyn Any>) {
-        let string_table: Box<HashMap<LineId, String>> = asset.downcast().unwrap();
-        self.translation_string_table.replace(*string_table);
+        let fetched: Box<FetchedStringTables> = asset.downcast().unwrap();
+        if let Some(string_table) = fetched.translation {
+            self.translation_string_table.replace(string_table);
+        }
+        for (language, string_table) in fetched.additional_translations {
+            if let Some(translation) = self.additional_translations.get_mut(&language) {
+                translation.string_table.replace(string_table);
+            }
+        }
     }
 
-    fn fetch_assets(&self, world: &World) -> Option<Box<dyn Any + 'static>> {
-        if self.is_base_language() {
-            return None;
+    fn get_text_with_fallback_info(&self, id: &LineId) -> Option<ProvidedText> {
+        if !self.is_base_language() {
+            let translation = self
+                .translation_string_table
+                .as_ref()
+                .and_then(|table| table.get(id).cloned());
+            if let Some(text) = translation {
+                return Some(ProvidedText {
+                    text,
+                    is_fallback: false,
+                });
+            }
         }
-        let handle = self.strings_file_handle.as_ref()?;
-        if !self.asset_server.is_loaded_with_dependencies(handle) {
-            return None;
+        let text = self
+            .base_string_table
+            .get(id)
+            .map(|info| info.text.clone())?;
+        Some(ProvidedText {
+            text,
+            is_fallback: !self.is_base_language(),
+        })
+    }
+
+    fn load_additional_language(&mut self, language: Language) {
+        if self.is_base_language_code(&language)
+            || self.additional_translations.contains_key(&language)
+        {
+            return;
+        }
+        let handle = self.load_strings_file(&language);
+        self.additional_translations.insert(
+            language,
+            AdditionalTranslation {
+                handle,
+                string_table: None,
+            },
+        );
+    }
+
+    fn unload_additional_language(&mut self, language: &Language) {
+        self.additional_translations.remove(language);
+    }
+
+    fn get_text_in_language(&self, id: &LineId, language: &Language) -> Option<String> {
+        if self.is_base_language_code(language) {
+            return self.base_string_table.get(id).map(|info| info.text.clone());
         }
+        let string_table = if self.language.as_ref() == Some(language) {
+            self.translation_string_table.as_ref()
+        } else {
+            self.additional_translations
+                .get(language)
+                .and_then(|translation| translation.string_table.as_ref())
+        };
+        string_table.and_then(|table| table.get(id).cloned())
+    }
+
+    fn take_missing_translations(&mut self) -> Vec<LineId> {
+        std::mem::take(&mut self.missing_translations.lock().unwrap())
+    }
+
+    fn take_errors(&mut self) -> Vec<Error> {
+        std::mem::take(&mut self.errors.lock().unwrap())
+    }
+
+    fn fetch_assets(&self, world: &World) -> Option<Box<dyn Any + 'static>> {
         let asset_events = world.resource::<Events<AssetEvent<StringsFile>>>();
-        let strings_file_has_changed = || {
-            let mut reader = self.event_reader.write().unwrap();
-            reader.read(asset_events).any(|event| match event {
-                AssetEvent::Modified { id } => *id == handle.id(),
-                _ => false,
+        let modified_strings_files: Vec<_> = self
+            .event_reader
+            .write()
+            .unwrap()
+            .read(asset_events)
+            .filter_map(|event| match event {
+                AssetEvent::Modified { id } => Some(*id),
+                _ => None,
             })
+            .collect();
+        let is_outdated = |handle: &Handle<StringsFile>, string_table: &Option<_>| {
+            string_table.is_none() || modified_strings_files.contains(&handle.id())
         };
-        let has_no_translation_yet = self.translation_string_table.is_none();
-        if has_no_translation_yet || strings_file_has_changed() {
-            let strings_file = world.resource::<Assets<StringsFile>>().get(handle).unwrap();
-            let expected_language = self.language.as_ref().unwrap();
-            if let Some(record) = strings_file.get_offending_language(expected_language) {
-                let path = self.asset_server.get_path(handle).unwrap();
-                panic!("Expected strings file at {path} to only contain language {expected_language}, but its entry with id \"{id}\" is for language {actual_language}.",
-                           path = path.path().display(),
-                           id = record.id,
-                           actual_language = record.language,
-                    );
+
+        let mut fetched = FetchedStringTables::default();
+        if !self.is_base_language() {
+            if let Some(handle) = self.strings_file_handle.as_ref() {
+                if is_outdated(handle, &self.translation_string_table) {
+                    let language = self.language.as_ref().unwrap();
+                    fetched.translation = self.fetch_string_table(world, handle, language);
+                }
+            }
+        }
+        for (language, translation) in &self.additional_translations {
+            if is_outdated(&translation.handle, &translation.string_table) {
+                if let Some(string_table) =
+                    self.fetch_string_table(world, &translation.handle, language)
+                {
+                    fetched
+                        .additional_translations
+                        .push((language.clone(), string_table));
+                }
             }
-            let string_table: HashMap<LineId, String> = strings_file
-                .iter()
-                .map(|(id, record)| (id.clone(), record.text.clone()))
-                .collect();
-            Some(Box::new(string_table))
-        } else {
-            None
         }
+        let has_fetched_anything =
+            fetched.translation.is_some() || !fetched.additional_translations.is_empty();
+        has_fetched_anything.then(|| Box::new(fetched) as Box<dyn Any>)
     }
 }
+
+#[derive(Debug, Clone)]
+struct AdditionalTranslation {
+    handle: Handle<StringsFile>,
+    string_table: Option<HashMap<LineId, String>>,
+}
+
+#[derive(Debug, Default)]
+struct FetchedStringTables {
+    translation: Option<HashMap<LineId, String>>,
+    additional_translations: Vec<(Language, HashMap<LineId, String>)>,
+}
@@ -1,16 +1,31 @@
-pub use self::localizations::*;
-pub(crate) use self::{
-    line_id_generation::LineIdUpdateSystemSet,
-    strings_file::UpdateAllStringsFilesForStringTableEvent, strings_file::*,
+#[cfg(feature = "text")]
+pub use self::language_fonts::{LanguageFontSystemSet, LanguageFontText, LanguageFonts};
+#[cfg(feature = "development_file_generation")]
+pub use self::strings_file::convert_unity_strings_file;
+pub use self::{
+    language_change::LanguageChangedEvent,
+    localizations::*,
+    strings_file::{GenerateStringsFilesEvent, StaleTranslationEvent, StaleTranslationReport},
 };
+pub(crate) use self::{line_id_generation::LineIdUpdateSystemSet, strings_file::*};
 use bevy::prelude::*;
 
+mod language_change;
+#[cfg(feature = "text")]
+mod language_fonts;
 mod line_id_generation;
 mod localizations;
 mod strings_file;
 
+/// The [`SystemSet`] containing the systems that handle [`Localizations`]: generating line IDs, updating and reporting strings files and applying language changes.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, SystemSet)]
+pub struct LocalizationSystemSet;
+
 pub(crate) fn localization_plugin(app: &mut App) {
     app.add_plugins(localizations::localization_config_plugin)
         .add_plugins(line_id_generation::line_id_generation_plugin)
-        .add_plugins(strings_file::strings_file_plugin);
+        .add_plugins(strings_file::strings_file_plugin)
+        .add_plugins(language_change::language_change_plugin);
+    #[cfg(feature = "text")]
+    app.add_plugins(language_fonts::language_fonts_plugin);
 }
@@ -0,0 +1,74 @@
+use crate::line_provider::LineProviderSystemSet;
+use crate::prelude::*;
+use bevy::prelude::*;
+
+pub(crate) fn language_change_plugin(app: &mut App) {
+    app.add_event::<LanguageChangedEvent>().add_systems(
+        Update,
+        (
+            apply_language_change.run_if(resource_exists_and_changed::<YarnProject>),
+            apply_language_change_of_additional_projects,
+        )
+            .before(LineProviderSystemSet)
+            .in_set(LocalizationSystemSet)
+            .in_set(YarnSpinnerSystemSet),
+    );
+}
+
+/// An event that is fired after the language of a [`YarnProject`] was changed with [`YarnProject::set_text_language`] or [`YarnProject::set_asset_language`]
+/// and the change was applied to all [`DialogueRunner`]s built from it.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Event)]
+pub struct LanguageChangedEvent {
+    /// The new language of the lines, or [`None`] if only the language of the assets changed.
+    pub text_language: Option<Language>,
+    /// The new language of the assets, or [`None`] if only the language of the lines changed.
+    pub asset_language: Option<Language>,
+}
+
+fn apply_language_change(
+    mut project: ResMut<YarnProject>,
+    mut dialogue_runners: Query<&mut DialogueRunner>,
+    mut language_changed_events: EventWriter<LanguageChangedEvent>,
+) {
+    apply_pending_language_change(
+        project.bypass_change_detection(),
+        &mut dialogue_runners,
+        &mut language_changed_events,
+    );
+}
+
+fn apply_language_change_of_additional_projects(
+    mut projects: Query<&mut YarnProject, Changed<YarnProject>>,
+    mut dialogue_runners: Query<&mut DialogueRunner>,
+    mut language_changed_events: EventWriter<LanguageChangedEvent>,
+) {
+    for mut project in projects.iter_mut() {
+        apply_pending_language_change(
+            project.bypass_change_detection(),
+            &mut dialogue_runners,
+            &mut language_changed_events,
+        );
+    }
+}
+
+fn apply_pending_language_change(
+    project: &mut YarnProject,
+    dialogue_runners: &mut Query<&mut DialogueRunner>,
+    language_changed_events: &mut EventWriter<LanguageChangedEvent>,
+) {
+    let Some(language_change) = project.pending_language_change.take() else {
+        return;
+    };
+    for mut dialogue_runner in dialogue_runners
+        .iter_mut()
+        .filter(|dialogue_runner| dialogue_runner.project_id() == project.id())
+    {
+        if let Some(language) = language_change.text_language.clone() {
+            dialogue_runner.set_text_language(language);
+        }
+        if let Some(language) = language_change.asset_language.clone() {
+            dialogue_runner.set_asset_language(language);
+        }
+    }
+    language_changed_events.send(language_change);
+}
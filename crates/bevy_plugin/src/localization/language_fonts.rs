@@ -0,0 +1,139 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+pub(crate) fn language_fonts_plugin(app: &mut App) {
+    app.init_resource::<LanguageFonts>().add_systems(
+        Update,
+        apply_language_fonts
+            .run_if(resource_exists::<YarnProject>)
+            .after(YarnSpinnerSystemSet)
+            .in_set(LanguageFontSystemSet),
+    );
+}
+
+/// The [`SystemSet`] that applies the [`LanguageFonts`] to all [`LanguageFontText`]s. Runs after the [`YarnSpinnerSystemSet`].
+/// Dialogue views that rebuild their [`Text`] every frame, e.g. for a typewriter effect, should run before it.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, SystemSet)]
+pub struct LanguageFontSystemSet;
+
+/// A [`Resource`] holding the fonts to use for specific text languages, e.g. a CJK font for `ja`, `zh` and `ko`.
+/// A font registered for a language without region also applies to all regional variants of it, so a font for `zh` is used for `zh-CN` and `zh-TW`,
+/// unless a font is registered for the exact variant as well.
+///
+/// The fonts are picked up by [`LanguageFontText`] and [`MarkupTextStyles::for_language`], and you can query them with [`LanguageFonts::font`] in your own views.
+/// Requires the `text` feature.
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::prelude::*;
+/// fn register_fonts(mut fonts: ResMut<LanguageFonts>, asset_server: Res<AssetServer>) {
+///     let cjk_font = asset_server.load("fonts/NotoSansCJK-Regular.ttc");
+///     fonts
+///         .insert("ja", cjk_font.clone())
+///         .insert("zh", cjk_font.clone())
+///         .insert("ko", cjk_font);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Resource)]
+pub struct LanguageFonts {
+    fonts: HashMap<Language, Handle<Font>>,
+}
+
+impl LanguageFonts {
+    /// Creates an empty set of fonts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the font to use for the given language, replacing any font registered for it before.
+    #[must_use]
+    pub fn with_font(mut self, language: impl Into<Language>, font: Handle<Font>) -> Self {
+        self.insert(language, font);
+        self
+    }
+
+    /// Registers the font to use for the given language, replacing any font registered for it before.
+    pub fn insert(&mut self, language: impl Into<Language>, font: Handle<Font>) -> &mut Self {
+        self.fonts.insert(language.into(), font);
+        self
+    }
+
+    /// Removes the font registered for exactly the given language.
+    pub fn remove(&mut self, language: &Language) -> Option<Handle<Font>> {
+        self.fonts.remove(language)
+    }
+
+    /// Returns the font to use for the given language: the font registered for the language itself, or else the one registered for its primary language subtag.
+    #[must_use]
+    pub fn font(&self, language: &Language) -> Option<&Handle<Font>> {
+        self.fonts.get(language).or_else(|| {
+            let language = language.to_string();
+            let (primary_language, _region) = language.split_once('-')?;
+            let primary_language = primary_language.parse::<Language>().ok()?;
+            self.fonts.get(&primary_language)
+        })
+    }
+
+    /// Returns the font to use for the given language, or the given default font if none is registered for it.
+    #[must_use]
+    pub fn font_or(&self, language: Option<&Language>, default: &Handle<Font>) -> Handle<Font> {
+        language
+            .and_then(|language| self.font(language))
+            .unwrap_or(default)
+            .clone()
+    }
+}
+
+/// Add this component to an entity with a [`Text`] to render all of its sections in the font that the [`LanguageFonts`] register for the
+/// [text language](YarnProject::text_language) of the [`YarnProject`]. The font is updated as soon as the language changes.
+/// Requires the `text` feature.
+#[derive(Debug, Clone, PartialEq, Eq, Component)]
+pub struct LanguageFontText {
+    /// The font used when no font is registered for the current language.
+    pub default_font: Handle<Font>,
+}
+
+impl LanguageFontText {
+    /// Creates a component that falls back to the given font for languages without a registered font.
+    pub fn new(default_font: Handle<Font>) -> Self {
+        Self { default_font }
+    }
+}
+
+impl MarkupTextStyles {
+    /// Returns a copy of these styles whose base font is the one registered in the [`LanguageFonts`] for the given language, if any.
+    /// Rules that set fonts, like those from [`MarkupTextStyles::with_font`], are kept as they are.
+    #[must_use]
+    pub fn for_language(&self, language: &Language, fonts: &LanguageFonts) -> Self {
+        let mut styles = self.clone();
+        if let Some(font) = fonts.font(language) {
+            styles.base.font = font.clone();
+        }
+        styles
+    }
+}
+
+fn apply_language_fonts(
+    project: Res<YarnProject>,
+    fonts: Res<LanguageFonts>,
+    mut texts: Query<(&mut Text, &LanguageFontText)>,
+) {
+    let language = project.text_language();
+    for (mut text, language_font_text) in texts.iter_mut() {
+        let font = fonts.font_or(language.as_ref(), &language_font_text.default_font);
+        // Only touch the text when needed, as every change makes Bevy lay it out again
+        if text
+            .sections
+            .iter()
+            .all(|section| section.style.font == font)
+        {
+            continue;
+        }
+        for section in text.sections.iter_mut() {
+            section.style.font = font.clone();
+        }
+    }
+}
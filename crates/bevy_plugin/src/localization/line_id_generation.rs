@@ -1,28 +1,39 @@
-use crate::localization::UpdateAllStringsFilesForStringTableEvent;
-use crate::plugin::AssetRoot;
 use crate::prelude::*;
 use crate::project::{RecompileLoadedYarnFilesEvent, YarnFilesBeingLoaded};
 use bevy::prelude::*;
-use bevy::utils::HashSet;
 use std::hash::Hash;
+#[cfg(feature = "development_file_generation")]
+use {
+    crate::error_handling::{handle_error, YarnErrorContext},
+    crate::localization::UpdateAllStringsFilesForStringTableEvent,
+    crate::plugin::AssetRoot,
+    bevy::utils::HashSet,
+};
 
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, SystemSet)]
 pub(crate) struct LineIdUpdateSystemSet;
 
 pub(crate) fn line_id_generation_plugin(app: &mut App) {
+    #[cfg(feature = "development_file_generation")]
     app.add_systems(
         Update,
-        (
-            handle_yarn_file_events
-                .pipe(panic_on_err)
-                .run_if(in_development.and_then(has_localizations)),
-            handle_yarn_file_events_outside_development.run_if(
+        handle_yarn_file_events
+            .pipe(handle_error(YarnErrorContext::Localization))
+            .run_if(in_development.and_then(has_localizations))
+            .before(handle_yarn_file_events_outside_development)
+            .in_set(LineIdUpdateSystemSet)
+            .in_set(LocalizationSystemSet)
+            .in_set(YarnSpinnerSystemSet),
+    );
+    app.add_systems(
+        Update,
+        handle_yarn_file_events_outside_development
+            .run_if(
                 resource_exists::<YarnProject>
                     .and_then(not(in_development.and_then(has_localizations))),
-            ),
-        )
-            .chain()
+            )
             .in_set(LineIdUpdateSystemSet)
+            .in_set(LocalizationSystemSet)
             .in_set(YarnSpinnerSystemSet),
     );
 }
@@ -45,6 +56,7 @@ fn handle_yarn_file_events_outside_development(
     }
 }
 
+#[cfg(feature = "development_file_generation")]
 fn handle_yarn_file_events(
     mut events: EventReader<AssetEvent<YarnFile>>,
     mut assets: ResMut<Assets<YarnFile>>,
@@ -61,10 +73,17 @@ fn handle_yarn_file_events(
     let mut recompilation_needed = false;
     let mut already_handled = HashSet::new();
     for event in events.read() {
-        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event
+        let (AssetEvent::LoadedWithDependencies { id }
+        | AssetEvent::Modified { id }
+        | AssetEvent::Added { id }) = event
         else {
             continue;
         };
+        let is_in_memory = asset_server.get_path(*id).is_none();
+        if matches!(event, AssetEvent::Added { .. }) && !is_in_memory {
+            // Files loaded from disk are handled once their `LoadedWithDependencies` event arrives
+            continue;
+        }
 
         let handle = Handle::Weak(*id);
         if already_handled.contains(&handle) {
@@ -86,7 +105,10 @@ fn handle_yarn_file_events(
         ));
 
         let Some(source_with_added_ids) = add_tags_to_lines(yarn_file)? else {
-            if matches!(event, AssetEvent::LoadedWithDependencies { .. }) {
+            if matches!(
+                event,
+                AssetEvent::LoadedWithDependencies { .. } | AssetEvent::Added { .. }
+            ) {
                 continue;
             }
             if last_recompiled_yarn_file.as_ref() == Some(yarn_file) {
@@ -107,6 +129,17 @@ fn handle_yarn_file_events(
         if added_tags.contains(&handle) {
             continue;
         }
+        if is_in_memory {
+            // There is no file to write to, so the IDs only live as long as the game runs.
+            // Changing the asset sends the `AssetEvent::Modified` that triggers the recompilation.
+            let yarn_file = assets.get_mut(&handle).unwrap();
+            yarn_file.set_content(source_with_added_ids)?;
+            info!(
+                "Automatically generated line IDs for in-memory Yarn file \"{}\"",
+                yarn_file.file_name()
+            );
+            continue;
+        }
         let asset_path = asset_server
             .get_path(handle.id())
             .with_context(|| format!("Failed to overwrite Yarn file \"{}\" with new IDs because it was not found on disk",
@@ -150,6 +183,7 @@ fn handle_yarn_file_events(
 }
 
 /// Adapted from <https://github.com/YarnSpinnerTool/YarnSpinner-Console/blob/main/src/YarnSpinner.Console/Commands/TagCommand.cs#L11>
+#[cfg(feature = "development_file_generation")]
 fn add_tags_to_lines(yarn_file: &YarnFile) -> YarnCompilerResult<Option<String>> {
     let existing_tags = yarn_file
         .string_table
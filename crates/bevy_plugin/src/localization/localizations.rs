@@ -2,7 +2,7 @@ use crate::prelude::*;
 use crate::project::DEFAULT_ASSET_DIR;
 use bevy::prelude::*;
 use std::iter;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
 pub(crate) fn localization_config_plugin(_app: &mut App) {}
 
@@ -58,7 +58,11 @@ impl Localizations {
         )
     }
 
-    pub(crate) fn strings_file_path(&self, language: impl Into<Language>) -> Option<&Path> {
+    #[cfg(feature = "development_file_generation")]
+    pub(crate) fn strings_file_path(
+        &self,
+        language: impl Into<Language>,
+    ) -> Option<&std::path::Path> {
         let language = language.into();
         self.translations
             .iter()
@@ -80,6 +84,7 @@ pub struct Localization {
     pub language: Language,
     /// The path to the strings file for this localization inside the `assets` folder.
     /// Defaults to `dialogue/{language}.strings.csv`. So, for the language "de-CH", you'd end up with "assets/dialogue/de-CH.strings.csv".
+    /// Strings files with the extension `.po` are read and written in the gettext format instead of CSV.
     pub strings_file: PathBuf,
     /// The path to the subdirectory containing the assets for this localization inside the `assets` folder.
     /// Defaults to `dialogue/{language}/`.  So, for the language "de-CH", you'd end up with "assets/dialogue/de-CH/".
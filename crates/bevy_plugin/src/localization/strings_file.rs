@@ -1,10 +1,26 @@
-pub(crate) use self::{asset::StringsFile, updating::UpdateAllStringsFilesForStringTableEvent};
+pub(crate) use self::asset::StringsFile;
+#[cfg(feature = "development_file_generation")]
+pub use self::unity::convert_unity_strings_file;
+#[cfg(feature = "development_file_generation")]
+pub(crate) use self::updating::{
+    write_missing_strings_files, UpdateAllStringsFilesForStringTableEvent,
+};
+pub use self::{
+    stale_translations::{StaleTranslationEvent, StaleTranslationReport},
+    updating::GenerateStringsFilesEvent,
+};
 use bevy::prelude::*;
 
+// Most of the strings file generation is only needed to write the files during development
+#[cfg_attr(not(feature = "development_file_generation"), allow(dead_code))]
 mod asset;
+mod stale_translations;
+#[cfg(feature = "development_file_generation")]
+mod unity;
 mod updating;
 
 pub(crate) fn strings_file_plugin(app: &mut App) {
     app.add_plugins(asset::strings_file_asset_plugin)
-        .add_plugins(updating::strings_file_updating_plugin);
+        .add_plugins(updating::strings_file_updating_plugin)
+        .add_plugins(stale_translations::stale_translations_plugin);
 }
@@ -8,9 +8,12 @@ use bevy::reflect::TypePath;
 use bevy::utils::HashMap;
 use sha2::{Digest, Sha256};
 use std::fs;
+#[cfg(feature = "development_file_generation")]
 use std::fs::File;
 use std::path::Path;
 
+mod gettext;
+
 pub(crate) fn strings_file_asset_plugin(app: &mut App) {
     app.init_asset::<StringsFile>()
         .init_asset_loader::<StringsFileAssetLoader>();
@@ -27,18 +30,24 @@ impl AssetLoader for StringsFileAssetLoader {
         &'a self,
         reader: &'a mut Reader<'_>,
         _settings: &'a (),
-        _load_context: &'a mut LoadContext<'_>,
+        load_context: &'a mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
         let mut bytes = Vec::new();
         reader.read_to_end(&mut bytes).await?;
-        let mut csv_reader = csv::Reader::from_reader(bytes.as_slice());
-        let records: csv::Result<Vec<_>> = csv_reader.deserialize().collect();
-        let strings_file = StringsFile::new_with_single_language(records?)?;
-        Ok(strings_file)
+        if gettext::is_gettext_path(load_context.path()) {
+            gettext::parse(std::str::from_utf8(&bytes)?)
+        } else {
+            StringsFile::from_csv(&bytes)
+        }
     }
 
     fn extensions(&self) -> &[&str] {
-        &["strings.csv"]
+        const EXTENSIONS: [&str; 3] = [
+            "strings.csv",
+            gettext::EXTENSIONS[0],
+            gettext::EXTENSIONS[1],
+        ];
+        &EXTENSIONS
     }
 }
 
@@ -65,6 +74,14 @@ impl StringsFile {
         Ok(Self(records))
     }
 
+    pub(crate) fn from_csv(bytes: &[u8]) -> Result<Self> {
+        // Yarn Spinner for Unity writes a byte order mark
+        let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+        let mut csv_reader = csv::Reader::from_reader(bytes);
+        let records: csv::Result<Vec<_>> = csv_reader.deserialize().collect();
+        Self::new_with_single_language(records?)
+    }
+
     pub(crate) fn language(&self) -> Option<&Language> {
         self.0.iter().next().map(|(_id, record)| &record.language)
     }
@@ -149,25 +166,75 @@ impl StringsFile {
                     string_info.file_name
                 )
             }
-            let lock = Lock::compute_from(&string_info.text);
-            records.insert(
-                id.clone(),
-                StringsFileRecord {
-                    language: language.clone(),
-                    id,
-                    text: string_info.text,
-                    file: string_info.file_name,
-                    node: string_info.node_name,
-                    line_number: string_info.line_number,
-                    lock,
-                    comment: read_comments(string_info.metadata),
-                },
-            );
+            let record = StringsFileRecord::from_string_info(language.clone(), id, string_info);
+            records.insert(record.id.clone(), record);
         }
 
         Ok(Self(records))
     }
 
+    /// Like [`StringsFile::from_string_table`], but also accepts implicit line IDs.
+    /// These are stable for a given compilation, so they can be used to store the lines of a precompiled program.
+    pub(crate) fn from_compiled_string_table(
+        language: impl Into<Language>,
+        string_table: impl IntoIterator<Item = (LineId, StringInfo)>,
+    ) -> Self {
+        let language = language.into();
+        let records = string_table
+            .into_iter()
+            .map(|(id, string_info)| {
+                let record = StringsFileRecord::from_string_info(language.clone(), id, string_info);
+                (record.id.clone(), record)
+            })
+            .collect();
+        Self(records)
+    }
+
+    /// Converts the records back into a string table, restoring the line metadata stored in their comments.
+    pub(crate) fn into_string_table(self) -> std::collections::HashMap<LineId, StringInfo> {
+        self.0
+            .into_iter()
+            .map(|(id, record)| {
+                let metadata = record
+                    .comment
+                    .split(LINE_METADATA_PREFIX)
+                    .nth(1)
+                    .map(|metadata| metadata.split(' ').map(ToOwned::to_owned).collect())
+                    .unwrap_or_default();
+                let string_info = StringInfo {
+                    text: record.text,
+                    node_name: record.node,
+                    line_number: record.line_number,
+                    file_name: record.file,
+                    is_implicit_tag: false,
+                    metadata,
+                    parent_line_id: None,
+                };
+                (id, string_info)
+            })
+            .collect()
+    }
+
+    pub(crate) fn read_asset(path: &Path) -> Result<Self> {
+        let bytes = fs::read(path)
+            .map_err(|e| anyhow!("Failed to read strings file \"{}\": {e}", path.display()))?;
+        if gettext::is_gettext_path(path) {
+            gettext::parse(std::str::from_utf8(&bytes)?)
+        } else {
+            Self::from_csv(&bytes)
+        }
+    }
+
+    /// Turns paths like `Assets/Dialogue/Intro.yarn`, as written by Yarn Spinner for Unity, into file names like `Intro.yarn`.
+    pub(crate) fn strip_directories_from_file_names(&mut self) {
+        for record in self.0.values_mut() {
+            if let Some(file_name) = record.file.rsplit(['/', '\\']).next() {
+                record.file = file_name.to_owned();
+            }
+        }
+    }
+
+    #[cfg(feature = "development_file_generation")]
     pub(crate) fn write_asset(&self, path: &Path) -> Result<()> {
         if let Some(parent_dir) = path.parent() {
             fs::create_dir_all(parent_dir).map_err(|e| {
@@ -177,15 +244,20 @@ impl StringsFile {
                 )
             })?;
         }
-        let file = File::create(path)
-            .map_err(|e| anyhow!("Failed to create strings file \"{}\": {e}", path.display(),))?;
-        let mut writer = csv::Writer::from_writer(file);
         let mut records = self.0.iter().map(|(_, record)| record).collect::<Vec<_>>();
         records.sort_by(|lhs, rhs| {
             lhs.file
                 .cmp(&rhs.file)
                 .then(lhs.line_number.cmp(&rhs.line_number))
         });
+        if gettext::is_gettext_path(path) {
+            let contents = gettext::write(&records, gettext::is_template_path(path));
+            return fs::write(path, contents)
+                .map_err(|e| anyhow!("Failed to write strings file \"{}\": {e}", path.display()));
+        }
+        let file = File::create(path)
+            .map_err(|e| anyhow!("Failed to create strings file \"{}\": {e}", path.display(),))?;
+        let mut writer = csv::Writer::from_writer(file);
         for record in records {
             writer.serialize(record)?;
         }
@@ -209,6 +281,20 @@ impl StringsFile {
     pub(crate) fn records(&self) -> impl Iterator<Item = &StringsFileRecord> {
         self.0.values()
     }
+
+    /// Returns the records whose translation is out of date because the text of their line in the base language changed since they were translated.
+    /// This is the case when their lock does not match the base text or when they were marked with "(NEEDS UPDATE)" during development.
+    pub(crate) fn stale_records<'a>(
+        &'a self,
+        base_string_table: &'a std::collections::HashMap<LineId, StringInfo>,
+    ) -> impl Iterator<Item = (&'a StringsFileRecord, &'a StringInfo)> {
+        self.0.iter().filter_map(|(id, record)| {
+            let base = base_string_table.get(id)?;
+            let is_stale = record.text.starts_with(UPDATE_PREFIX)
+                || record.lock != Lock::compute_from(&base.text);
+            is_stale.then_some((record, base))
+        })
+    }
 }
 
 fn records_equal_except_for_text(lhs: &StringsFileRecord, rhs: &StringsFileRecord) -> bool {
@@ -260,6 +346,9 @@ pub(crate) struct StringsFileRecord {
 
     /// The 1-indexed line number in the file indicated by [`file`](StringsFileRecord::file) at
     /// which the original version of this line can be found.
+    ///
+    /// Written as `lineNumber` like Yarn Spinner for Unity does. Files written by earlier versions used `line_number`, which is still read.
+    #[serde(rename = "lineNumber", alias = "line_number")]
     pub(crate) line_number: usize,
     /// A string used as part of a mechanism for checking if translated
     /// versions of this string are out of date.
@@ -279,6 +368,27 @@ pub(crate) struct StringsFileRecord {
     pub(crate) lock: Lock,
     /// A comment used to describe this line to translators.
     pub(crate) comment: String,
+    /// The text of this line in the base language, if known.
+    /// Not part of the CSV format, but needed for the `msgid` of gettext files.
+    #[serde(skip)]
+    pub(crate) base_text: Option<String>,
+}
+
+impl StringsFileRecord {
+    fn from_string_info(language: Language, id: LineId, string_info: StringInfo) -> Self {
+        let lock = Lock::compute_from(&string_info.text);
+        Self {
+            language,
+            id,
+            base_text: Some(string_info.text.clone()),
+            text: string_info.text,
+            file: string_info.file_name,
+            node: string_info.node_name,
+            line_number: string_info.line_number,
+            lock,
+            comment: read_comments(string_info.metadata),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
@@ -402,4 +512,113 @@ mod test {
         let combined = combine_comments(old, new);
         assert_eq!(new, &combined)
     }
+
+    #[test]
+    fn marks_changed_translations_as_stale() {
+        let mut strings_file = strings_file([record("line:1", "Hallo", "Hello")]);
+        let changed = strings_file
+            .update_file(strings_file_from_base([("line:1", "Hello there")]))
+            .unwrap();
+        assert!(changed);
+
+        let record = &strings_file.0[&LineId::from("line:1")];
+        assert_eq!("(NEEDS UPDATE) Hallo", record.text);
+        assert_eq!(Lock::compute_from("Hello there"), record.lock);
+    }
+
+    #[test]
+    fn replaces_untranslated_lines() {
+        let mut strings_file = strings_file([record("line:1", "Hello", "Hello")]);
+        strings_file
+            .update_file(strings_file_from_base([("line:1", "Hello there")]))
+            .unwrap();
+
+        let record = &strings_file.0[&LineId::from("line:1")];
+        assert_eq!("Hello there", record.text);
+        assert_eq!(Lock::compute_from("Hello there"), record.lock);
+    }
+
+    #[test]
+    fn keeps_unchanged_translations() {
+        let mut strings_file = strings_file([record("line:1", "Hallo", "Hello")]);
+        let changed = strings_file
+            .update_file(strings_file_from_base([("line:1", "Hello")]))
+            .unwrap();
+        assert!(!changed);
+        assert_eq!("Hallo", strings_file.0[&LineId::from("line:1")].text);
+    }
+
+    #[test]
+    fn adds_new_lines_in_base_language() {
+        let mut strings_file = strings_file([record("line:1", "Hallo", "Hello")]);
+        let changed = strings_file
+            .update_file(strings_file_from_base([
+                ("line:1", "Hello"),
+                ("line:2", "Goodbye"),
+            ]))
+            .unwrap();
+        assert!(changed);
+
+        let record = &strings_file.0[&LineId::from("line:2")];
+        assert_eq!("Goodbye", record.text);
+        assert_eq!(Lock::compute_from("Goodbye"), record.lock);
+    }
+
+    #[test]
+    fn reads_strings_file_exported_from_unity() {
+        let csv = "\u{feff}language,id,text,file,node,lineNumber,lock,comment\r\n\
+                   de-CH,line:1,Hallo,Assets/Dialogue/lines.yarn,Start,3,185f8db3,\"Line metadata: happy\"\r\n";
+        let mut strings_file = StringsFile::from_csv(csv.as_bytes()).unwrap();
+        strings_file.strip_directories_from_file_names();
+
+        let record = &strings_file.0[&LineId::from("line:1")];
+        assert_eq!("Hallo", record.text);
+        assert_eq!("lines.yarn", record.file);
+        assert_eq!(3, record.line_number);
+        assert_eq!(Lock::compute_from("Hello"), record.lock);
+        assert_eq!("Line metadata: happy", record.comment);
+    }
+
+    #[test]
+    fn reads_strings_file_with_snake_case_line_number() {
+        let csv = "language,id,text,file,node,line_number,lock,comment\n\
+                   de-CH,line:1,Hallo,lines.yarn,Start,3,185f8db3,\n";
+        let strings_file = StringsFile::from_csv(csv.as_bytes()).unwrap();
+        assert_eq!(3, strings_file.0[&LineId::from("line:1")].line_number);
+    }
+
+    fn strings_file(records: impl IntoIterator<Item = StringsFileRecord>) -> StringsFile {
+        StringsFile::new_with_single_language(records.into_iter().collect()).unwrap()
+    }
+
+    fn strings_file_from_base<'a>(
+        lines: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> StringsFile {
+        let string_table = lines.into_iter().map(|(id, text)| {
+            (
+                LineId::from(id),
+                StringInfo {
+                    text: text.to_owned(),
+                    file_name: "lines.yarn".to_owned(),
+                    node_name: "Start".to_owned(),
+                    ..default()
+                },
+            )
+        });
+        StringsFile::from_string_table("de-CH", string_table).unwrap()
+    }
+
+    fn record(id: &str, text: &str, base_text: &str) -> StringsFileRecord {
+        StringsFileRecord {
+            language: "de-CH".into(),
+            id: id.into(),
+            text: text.to_owned(),
+            file: "lines.yarn".to_owned(),
+            node: "Start".to_owned(),
+            line_number: 0,
+            lock: Lock::compute_from(base_text),
+            comment: String::new(),
+            base_text: Some(base_text.to_owned()),
+        }
+    }
 }
@@ -0,0 +1,403 @@
+//! Reading and writing strings files in the gettext format, see <https://www.gnu.org/software/gettext/manual/html_node/PO-Files.html>.
+//!
+//! Every line is an entry whose `msgctxt` is the line ID, whose `msgid` is the text in the base language and whose `msgstr` is the translation.
+//! The remaining fields of a [`StringsFileRecord`] are stored in comments, e.g.
+//! ```text
+//! #. Line metadata: important
+//! #. node: Start
+//! #. lock: 23beac47
+//! #: lines.yarn:3
+//! #, fuzzy
+//! msgctxt "line:1"
+//! msgid "Hello"
+//! msgstr "Hallo"
+//! ```
+//! An empty `msgstr` means that the line was not translated yet, while the `fuzzy` flag corresponds to a translation marked with "(NEEDS UPDATE)".
+
+use super::{Lock, StringsFile, StringsFileRecord, UPDATE_PREFIX};
+use crate::prelude::*;
+use anyhow::{anyhow, bail};
+use std::fmt::Write;
+use std::path::Path;
+
+/// The extensions of gettext files, i.e. portable object files with translations and their templates.
+pub(crate) const EXTENSIONS: [&str; 2] = ["po", "pot"];
+const TEMPLATE_EXTENSION: &str = "pot";
+const UNDETERMINED_LANGUAGE: &str = "und";
+const NODE_PREFIX: &str = "node: ";
+const LOCK_PREFIX: &str = "lock: ";
+
+pub(crate) fn is_gettext_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| EXTENSIONS.contains(&extension))
+}
+
+pub(crate) fn is_template_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == TEMPLATE_EXTENSION)
+}
+
+#[derive(Debug, Default)]
+struct Entry {
+    comments: Vec<String>,
+    references: Vec<String>,
+    flags: Vec<String>,
+    msgctxt: Option<String>,
+    msgid: Option<String>,
+    msgstr: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Keyword {
+    Msgctxt,
+    Msgid,
+    Msgstr,
+}
+
+impl Entry {
+    fn is_empty(&self) -> bool {
+        self.msgctxt.is_none() && self.msgid.is_none() && self.msgstr.is_none()
+    }
+
+    fn field_mut(&mut self, keyword: Keyword) -> &mut Option<String> {
+        match keyword {
+            Keyword::Msgctxt => &mut self.msgctxt,
+            Keyword::Msgid => &mut self.msgid,
+            Keyword::Msgstr => &mut self.msgstr,
+        }
+    }
+}
+
+pub(crate) fn parse(source: &str) -> Result<StringsFile> {
+    let mut entries = Vec::new();
+    let mut entry = Entry::default();
+    let mut last_keyword = None;
+    for (index, line) in source.lines().enumerate() {
+        let line = line.trim();
+        let line_number = index + 1;
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            // A comment after a complete entry starts the next one
+            if entry.msgstr.is_some() {
+                entries.push(std::mem::take(&mut entry));
+            }
+            last_keyword = None;
+            if let Some(comment) = comment.strip_prefix('.') {
+                entry.comments.push(comment.trim().to_owned());
+            } else if let Some(references) = comment.strip_prefix(':') {
+                entry
+                    .references
+                    .extend(references.split_whitespace().map(ToOwned::to_owned));
+            } else if let Some(flags) = comment.strip_prefix(',') {
+                entry
+                    .flags
+                    .extend(flags.split(',').map(|flag| flag.trim().to_owned()));
+            } else if comment.starts_with(['|', '~']) {
+                // Previous and obsolete entries are not needed
+            } else {
+                entry.comments.push(comment.trim().to_owned());
+            }
+            continue;
+        }
+        if line.starts_with('"') {
+            let Some(keyword) = last_keyword else {
+                bail!("Unexpected string on line {line_number} of gettext file: {line}");
+            };
+            let value = unquote(line, line_number)?;
+            entry
+                .field_mut(keyword)
+                .get_or_insert_with(String::new)
+                .push_str(&value);
+            continue;
+        }
+        let Some((keyword, value)) = line.split_once(char::is_whitespace) else {
+            bail!("Unexpected line {line_number} in gettext file: {line}");
+        };
+        let keyword = match keyword {
+            "msgctxt" => Keyword::Msgctxt,
+            "msgid" => Keyword::Msgid,
+            "msgstr" => Keyword::Msgstr,
+            _ => bail!("Unsupported keyword \"{keyword}\" on line {line_number} of gettext file. Plural forms are not supported."),
+        };
+        if matches!(keyword, Keyword::Msgctxt | Keyword::Msgid) && entry.msgstr.is_some() {
+            entries.push(std::mem::take(&mut entry));
+        }
+        *entry.field_mut(keyword) = Some(unquote(value.trim(), line_number)?);
+        last_keyword = Some(keyword);
+    }
+    if !entry.is_empty() {
+        entries.push(entry);
+    }
+
+    let mut language = None;
+    let mut records = Vec::new();
+    for entry in entries {
+        let Some(id) = entry.msgctxt.clone() else {
+            if entry.msgid.as_deref() == Some("") {
+                language = parse_header_language(entry.msgstr.as_deref().unwrap_or_default())?;
+                continue;
+            }
+            bail!(
+                "Entry {:?} in gettext file has no msgctxt. It must contain the ID of the line.",
+                entry.msgid.unwrap_or_default()
+            );
+        };
+        records.push((id, entry));
+    }
+    let language = language.unwrap_or_else(|| UNDETERMINED_LANGUAGE.into());
+    let records = records
+        .into_iter()
+        .map(|(id, entry)| to_record(language.clone(), id, entry))
+        .collect::<Result<Vec<_>>>()?;
+    StringsFile::new_with_single_language(records)
+}
+
+fn parse_header_language(header: &str) -> Result<Option<Language>> {
+    let Some(language) = header
+        .lines()
+        .find_map(|line| line.strip_prefix("Language:"))
+        .map(str::trim)
+        .filter(|language| !language.is_empty())
+    else {
+        return Ok(None);
+    };
+    let language = language
+        .parse()
+        .map_err(|e| anyhow!("Invalid language \"{language}\" in gettext file header: {e}"))?;
+    Ok(Some(language))
+}
+
+fn to_record(language: Language, id: String, entry: Entry) -> Result<StringsFileRecord> {
+    let base_text = entry.msgid.unwrap_or_default();
+    let translation = entry.msgstr.unwrap_or_default();
+    let mut text = if translation.is_empty() {
+        base_text.clone()
+    } else {
+        translation
+    };
+    if entry.flags.iter().any(|flag| flag == "fuzzy") && !text.starts_with(UPDATE_PREFIX) {
+        text = format!("{UPDATE_PREFIX}{text}");
+    }
+
+    let mut node = String::new();
+    let mut lock = None;
+    let mut comments = Vec::new();
+    for comment in entry.comments {
+        if let Some(value) = comment.strip_prefix(NODE_PREFIX) {
+            node = value.to_owned();
+        } else if let Some(value) = comment.strip_prefix(LOCK_PREFIX) {
+            lock = Some(Lock(value.to_owned()));
+        } else if !comment.is_empty() {
+            comments.push(comment);
+        }
+    }
+    let (file, line_number) = match entry.references.first() {
+        Some(reference) => match reference.rsplit_once(':') {
+            Some((file, line_number)) => (
+                file.to_owned(),
+                line_number.parse().with_context(|| {
+                    format!("Invalid line number in reference \"{reference}\" of line {id}")
+                })?,
+            ),
+            None => (reference.clone(), 0),
+        },
+        None => (String::new(), 0),
+    };
+    Ok(StringsFileRecord {
+        language,
+        id: id.into(),
+        lock: lock.unwrap_or_else(|| Lock::compute_from(&base_text)),
+        text,
+        file,
+        node,
+        line_number,
+        comment: comments.join("\n"),
+        base_text: Some(base_text),
+    })
+}
+
+/// Writes the records in the gettext format. Templates contain no language and no translations.
+pub(crate) fn write(records: &[&StringsFileRecord], template: bool) -> String {
+    let language = match records.first() {
+        Some(record) if !template => record.language.to_string(),
+        _ => String::new(),
+    };
+    let mut output = String::new();
+    writeln!(output, "msgid \"\"").unwrap();
+    writeln!(output, "msgstr \"\"").unwrap();
+    for header in [
+        format!("Language: {language}"),
+        "MIME-Version: 1.0".to_owned(),
+        "Content-Type: text/plain; charset=UTF-8".to_owned(),
+        "Content-Transfer-Encoding: 8bit".to_owned(),
+    ] {
+        writeln!(output, "{}", quote(&format!("{header}\n"))).unwrap();
+    }
+    for record in records {
+        let base_text = record.base_text.as_deref().unwrap_or(&record.text);
+        let (translation, is_fuzzy) = match record.text.strip_prefix(UPDATE_PREFIX) {
+            Some(text) => (text, true),
+            None => (record.text.as_str(), false),
+        };
+        let is_untranslated =
+            record.text == base_text && Lock::compute_from(base_text) == record.lock;
+        let translation = if template || is_untranslated {
+            ""
+        } else {
+            translation
+        };
+
+        writeln!(output).unwrap();
+        for comment in record.comment.lines().filter(|line| !line.is_empty()) {
+            writeln!(output, "#. {comment}").unwrap();
+        }
+        writeln!(output, "#. {NODE_PREFIX}{}", record.node).unwrap();
+        writeln!(output, "#. {LOCK_PREFIX}{}", record.lock.0).unwrap();
+        writeln!(output, "#: {}:{}", record.file, record.line_number).unwrap();
+        if is_fuzzy && !template {
+            writeln!(output, "#, fuzzy").unwrap();
+        }
+        writeln!(output, "msgctxt {}", quote(&record.id.0)).unwrap();
+        writeln!(output, "msgid {}", quote(base_text)).unwrap();
+        writeln!(output, "msgstr {}", quote(translation)).unwrap();
+    }
+    output
+}
+
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for character in text.chars() {
+        match character {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(character),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn unquote(text: &str, line_number: usize) -> Result<String> {
+    let Some(inner) = text
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+    else {
+        bail!("Expected a quoted string on line {line_number} of gettext file, but found: {text}");
+    };
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut characters = inner.chars();
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            unquoted.push(character);
+            continue;
+        }
+        match characters.next() {
+            Some('n') => unquoted.push('\n'),
+            Some('r') => unquoted.push('\r'),
+            Some('t') => unquoted.push('\t'),
+            Some(escaped @ ('"' | '\\')) => unquoted.push(escaped),
+            other => bail!(
+                "Unsupported escape sequence \"\\{}\" on line {line_number} of gettext file",
+                other.map(String::from).unwrap_or_default()
+            ),
+        }
+    }
+    Ok(unquoted)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PO: &str = r#"msgid ""
+msgstr ""
+"Language: de-CH\n"
+"Content-Type: text/plain; charset=UTF-8\n"
+
+#. Line metadata: greeting
+#. node: Start
+#. lock: 185f8db3
+#: lines.yarn:3
+msgctxt "line:1"
+msgid "Hello"
+msgstr "Hallo"
+
+#. node: Start
+#. lock: 0f0be1c5
+#: lines.yarn:4
+#, fuzzy
+msgctxt "line:2"
+msgid "He said \"Goodbye\""
+msgstr ""
+"Er sagte \"Tschüss\"\n"
+"und ging"
+
+#. node: Start
+#: lines.yarn:5
+msgctxt "line:3"
+msgid "Untranslated"
+msgstr ""
+"#;
+
+    #[test]
+    fn parses_translations() {
+        let strings_file = parse(PO).unwrap();
+        let record = &strings_file.0[&LineId::from("line:1")];
+        assert_eq!(Language::from("de-CH"), record.language);
+        assert_eq!("Hallo", record.text);
+        assert_eq!(Some("Hello"), record.base_text.as_deref());
+        assert_eq!("lines.yarn", record.file);
+        assert_eq!("Start", record.node);
+        assert_eq!(3, record.line_number);
+        assert_eq!(Lock("185f8db3".to_owned()), record.lock);
+        assert_eq!("Line metadata: greeting", record.comment);
+    }
+
+    #[test]
+    fn parses_fuzzy_translations_as_needing_update() {
+        let strings_file = parse(PO).unwrap();
+        let record = &strings_file.0[&LineId::from("line:2")];
+        assert_eq!("(NEEDS UPDATE) Er sagte \"Tschüss\"\nund ging", record.text);
+    }
+
+    #[test]
+    fn parses_untranslated_lines_as_base_text() {
+        let strings_file = parse(PO).unwrap();
+        let record = &strings_file.0[&LineId::from("line:3")];
+        assert_eq!("Untranslated", record.text);
+        assert_eq!(Lock::compute_from("Untranslated"), record.lock);
+    }
+
+    #[test]
+    fn round_trips() {
+        let strings_file = parse(PO).unwrap();
+        let mut records: Vec<_> = strings_file.records().collect();
+        records.sort_by_key(|record| record.line_number);
+        let written = write(&records, false);
+        assert_eq!(strings_file, parse(&written).unwrap());
+    }
+
+    #[test]
+    fn writes_templates_without_translations() {
+        let strings_file = parse(PO).unwrap();
+        let records: Vec<_> = strings_file.records().collect();
+        let template = parse(&write(&records, true)).unwrap();
+        assert!(template.records().all(|record| {
+            record.language == Language::from(UNDETERMINED_LANGUAGE)
+                && Some(&record.text) == record.base_text.as_ref()
+        }));
+    }
+
+    #[test]
+    fn rejects_entries_without_line_id() {
+        let error = parse("msgid \"Hello\"\nmsgstr \"Hallo\"\n").unwrap_err();
+        assert!(error.to_string().contains("msgctxt"));
+    }
+}
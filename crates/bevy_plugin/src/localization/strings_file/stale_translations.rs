@@ -0,0 +1,140 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+
+pub(crate) fn stale_translations_plugin(app: &mut App) {
+    app.add_event::<StaleTranslationEvent>()
+        .init_resource::<StaleTranslationReport>()
+        .add_systems(
+            Update,
+            report_stale_translations
+                .run_if(resource_exists::<YarnProject>.and_then(has_localizations))
+                .in_set(LocalizationSystemSet)
+                .in_set(YarnSpinnerSystemSet),
+        );
+}
+
+/// An event that is fired for every line of a loaded strings file whose translation is out of date,
+/// i.e. whose text in the base language changed since it was translated.
+/// All stale translations are also collected in the [`StaleTranslationReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Event)]
+pub struct StaleTranslationEvent {
+    /// The language of the translation.
+    pub language: Language,
+    /// The ID of the line.
+    pub line_id: LineId,
+    /// The outdated translation as it appears in the strings file.
+    pub translation: String,
+    /// The current text of the line in the base language.
+    pub base_text: String,
+}
+
+/// A [`Resource`] listing all translations that are out of date, grouped by language, so that they can be re-translated.
+/// The entry of a language is replaced every time its strings file is loaded or changed.
+/// See [`StaleTranslationEvent`] for reacting to individual lines instead.
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::prelude::*;
+/// fn print_stale_translations(report: Res<StaleTranslationReport>) {
+///     for stale_translation in report.iter() {
+///         println!(
+///             "[{}] {}: \"{}\" is out of date, the text is now \"{}\"",
+///             stale_translation.language,
+///             stale_translation.line_id,
+///             stale_translation.translation,
+///             stale_translation.base_text,
+///         );
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Resource)]
+pub struct StaleTranslationReport(HashMap<Language, Vec<StaleTranslationEvent>>);
+
+impl StaleTranslationReport {
+    /// Returns the stale translations of the given language, sorted by their [`LineId`].
+    #[must_use]
+    pub fn get(&self, language: &Language) -> &[StaleTranslationEvent] {
+        self.0.get(language).map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterates over the stale translations of all languages.
+    pub fn iter(&self) -> impl Iterator<Item = &StaleTranslationEvent> {
+        self.0.values().flatten()
+    }
+
+    /// Returns the number of stale translations across all languages.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.values().map(Vec::len).sum()
+    }
+
+    /// Returns `true` if no stale translations were found.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn report_stale_translations(
+    mut asset_events: EventReader<AssetEvent<StringsFile>>,
+    strings_files: Res<Assets<StringsFile>>,
+    asset_server: Res<AssetServer>,
+    project: Res<YarnProject>,
+    mut report: ResMut<StaleTranslationReport>,
+    mut stale_translation_events: EventWriter<StaleTranslationEvent>,
+) {
+    let loaded_ids: HashSet<_> = asset_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    let base_language = &project
+        .localizations
+        .as_ref()
+        .unwrap()
+        .base_localization
+        .language;
+    for id in loaded_ids {
+        let Some(strings_file) = strings_files.get(id) else {
+            continue;
+        };
+        let Some(language) = strings_file.language().cloned() else {
+            continue;
+        };
+        if &language == base_language {
+            continue;
+        }
+        let mut stale_translations: Vec<_> = strings_file
+            .stale_records(&project.compilation.string_table)
+            .map(|(record, base)| StaleTranslationEvent {
+                language: language.clone(),
+                line_id: record.id.clone(),
+                translation: record.text.clone(),
+                base_text: base.text.clone(),
+            })
+            .collect();
+        stale_translations.sort_by(|lhs, rhs| lhs.line_id.0.cmp(&rhs.line_id.0));
+        if !stale_translations.is_empty() {
+            let source = asset_server
+                .get_path(id)
+                .map(|asset_path| format!("at {}", asset_path.path().display()))
+                .unwrap_or_else(|| "created at runtime".to_owned());
+            let line_ids = stale_translations
+                .iter()
+                .map(|stale_translation| stale_translation.line_id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            warn!(
+                "Strings file {source} (lang: {language}) contains {count} translations that are out of date with the base language: {line_ids}",
+                count = stale_translations.len(),
+            );
+        }
+        stale_translation_events.send_batch(stale_translations.iter().cloned());
+        report.0.insert(language, stale_translations);
+    }
+}
@@ -0,0 +1,37 @@
+use crate::prelude::*;
+use std::path::Path;
+
+/// Converts a strings file exported from a Yarn Spinner for Unity project into one that can be used as the [`Localization::strings_file`] of a translation.
+///
+/// Both engines use the same columns (`language`, `id`, `text`, `file`, `node`, `lineNumber`, `lock` and `comment`) and compute the `lock` the same way,
+/// so translations that were up to date in Unity are up to date in Bevy as well.
+/// What differs is the `file` column: Unity stores the path of the Yarn file inside the Unity project, e.g. `Assets/Dialogue/Intro.yarn`,
+/// while Bevy stores just its file name. The converted file uses file names, so that [`DevelopmentFileGeneration::Full`] updates the existing lines
+/// instead of treating them as belonging to another Yarn file.
+///
+/// The destination can also be a gettext `.po` file. Missing parent directories are created.
+/// Requires the `development_file_generation` feature.
+///
+/// ## Example
+///
+/// ```no_run
+/// bevy_yarnspinner::prelude::convert_unity_strings_file(
+///     "../my_unity_game/Assets/Dialogue/Project (de-CH).csv",
+///     "assets/dialogue/de-CH.strings.csv",
+/// )
+/// .unwrap();
+/// ```
+pub fn convert_unity_strings_file(
+    unity_strings_file: impl AsRef<Path>,
+    destination: impl AsRef<Path>,
+) -> Result<()> {
+    let unity_strings_file = unity_strings_file.as_ref();
+    let mut strings_file = StringsFile::read_asset(unity_strings_file).with_context(|| {
+        format!(
+            "Failed to convert Unity strings file \"{}\"",
+            unity_strings_file.display()
+        )
+    })?;
+    strings_file.strip_directories_from_file_names();
+    strings_file.write_asset(destination.as_ref())
+}
@@ -1,32 +1,102 @@
-use crate::plugin::AssetRoot;
-use crate::{localization::line_id_generation::LineIdUpdateSystemSet, prelude::*};
+use crate::prelude::*;
 use bevy::prelude::*;
-use bevy::utils::{HashMap, HashSet};
+#[cfg(feature = "development_file_generation")]
+use {
+    crate::error_handling::{handle_error, YarnErrorContext},
+    crate::localization::line_id_generation::LineIdUpdateSystemSet,
+    crate::plugin::AssetRoot,
+    crate::project::CompilationSystemSet,
+    bevy::utils::{HashMap, HashSet},
+    std::path::Path,
+};
 
 pub(crate) fn strings_file_updating_plugin(app: &mut App) {
     app.add_event::<UpdateAllStringsFilesForStringTableEvent>()
-        .add_systems(
-            Update,
-            (update_all_strings_files_for_string_table
-                .pipe(panic_on_err)
-                .after(LineIdUpdateSystemSet)
-                .in_set(YarnSpinnerSystemSet)
-                .run_if(
-                    in_development
-                        .and_then(has_localizations)
-                        .and_then(resource_exists::<YarnProject>)
-                        .and_then(events_in_queue::<UpdateAllStringsFilesForStringTableEvent>()),
-                ),)
-                .chain(),
-        );
+        .add_event::<GenerateStringsFilesEvent>();
+    #[cfg(feature = "development_file_generation")]
+    app.add_systems(
+        Update,
+        (
+            generate_strings_files.pipe(handle_error(YarnErrorContext::Localization)),
+            update_all_strings_files_for_string_table
+                .pipe(handle_error(YarnErrorContext::Localization)),
+        )
+            .chain()
+            .after(LineIdUpdateSystemSet)
+            .before(CompilationSystemSet)
+            .in_set(LocalizationSystemSet)
+            .in_set(YarnSpinnerSystemSet)
+            .run_if(
+                in_development
+                    .and_then(has_localizations)
+                    .and_then(resource_exists::<YarnProject>),
+            ),
+    );
 }
 
+/// Send this event to write the strings files of all translations of the [`YarnProject`], e.g. after adding a new [`Localization`] to the [`Localizations`].
+/// Missing strings files are generated from the lines of the base language, while existing ones are updated with all lines that were added or changed since they were written.
+/// Translated lines whose text changed in the base language are marked with "(NEEDS UPDATE)".
+///
+/// This happens automatically when the project is compiled, so this event is only needed to regenerate the files while the game is running.
+/// It is ignored unless the project uses [`DevelopmentFileGeneration::Full`], which requires the `development_file_generation` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Event)]
+pub struct GenerateStringsFilesEvent;
+
 #[derive(Debug, Clone, PartialEq, Eq, Default, Reflect, Event)]
 #[reflect(Debug, Default, PartialEq)]
 pub(crate) struct UpdateAllStringsFilesForStringTableEvent(
     pub(crate) std::collections::HashMap<LineId, StringInfo>,
 );
 
+#[cfg(feature = "development_file_generation")]
+fn generate_strings_files(
+    mut events: EventReader<GenerateStringsFilesEvent>,
+    mut update_strings_files_writer: EventWriter<UpdateAllStringsFilesForStringTableEvent>,
+    project: Res<YarnProject>,
+    asset_root: Res<AssetRoot>,
+) -> SystemResult {
+    if events.read().count() == 0 {
+        return Ok(());
+    }
+    let localizations = project.localizations.as_ref().unwrap();
+    write_missing_strings_files(
+        localizations,
+        &project.compilation.string_table,
+        &asset_root.0,
+    )?;
+    update_strings_files_writer.send(UpdateAllStringsFilesForStringTableEvent(
+        project.compilation.string_table.clone(),
+    ));
+    Ok(())
+}
+
+#[cfg(feature = "development_file_generation")]
+pub(crate) fn write_missing_strings_files(
+    localizations: &Localizations,
+    string_table: &std::collections::HashMap<LineId, StringInfo>,
+    asset_root: &Path,
+) -> Result<()> {
+    for localization in &localizations.translations {
+        let path = asset_root.join(localization.strings_file.as_path());
+        if path.is_file() {
+            continue;
+        }
+        let strings_file =
+            StringsFile::from_string_table(localization.language.clone(), string_table.clone())
+                .unwrap_or_default();
+
+        strings_file.write_asset(&path)?;
+        info!(
+            "Generated \"{}\" (lang: {}).",
+            path.display(),
+            localization.language
+        );
+    }
+    Ok(())
+}
+
+#[cfg(feature = "development_file_generation")]
 fn update_all_strings_files_for_string_table(
     mut events: ResMut<Events<UpdateAllStringsFilesForStringTableEvent>>,
     mut strings_files: ResMut<Assets<StringsFile>>,
@@ -34,11 +104,17 @@ fn update_all_strings_files_for_string_table(
     project: Res<YarnProject>,
     mut languages_to_handles: Local<HashMap<Language, Handle<StringsFile>>>,
     mut expected_file_names: Local<HashSet<String>>,
+    mut pending_string_tables: Local<Vec<std::collections::HashMap<LineId, StringInfo>>>,
     asset_root: Res<AssetRoot>,
 ) -> SystemResult {
+    // Kept around until the strings files are loaded, as events would expire in the meantime
+    pending_string_tables.extend(events.drain().map(|e| e.0));
+    if pending_string_tables.is_empty() {
+        return Ok(());
+    }
     let localizations = project.localizations.as_ref().unwrap();
     if localizations.translations.is_empty() {
-        events.clear();
+        pending_string_tables.clear();
         return Ok(());
     }
 
@@ -50,7 +126,7 @@ fn update_all_strings_files_for_string_table(
         languages_to_handles.insert(language.clone(), handle);
     }
     if languages_to_handles.is_empty() {
-        events.clear();
+        pending_string_tables.clear();
         return Ok(());
     }
     if languages_to_handles
@@ -70,7 +146,7 @@ fn update_all_strings_files_for_string_table(
     }
 
     let mut dirty_paths = HashSet::new();
-    for string_table in events.drain().map(|e| e.0) {
+    for string_table in pending_string_tables.drain(..) {
         let file_names: HashSet<_> = string_table
             .values()
             .map(|s| s.file_name.as_str())
@@ -124,6 +200,7 @@ fn update_all_strings_files_for_string_table(
     Ok(())
 }
 
+#[cfg(feature = "development_file_generation")]
 fn lint_strings_file(
     strings_file: &StringsFile,
     expected_file_names: &HashSet<String>,
@@ -0,0 +1,187 @@
+use crate::fmt_utils::SkipDebug;
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::sync::Arc;
+use unicode_segmentation::UnicodeSegmentation;
+
+type StyleRule = Arc<dyn Fn(&MarkupAttribute, &mut TextStyle) + Send + Sync>;
+
+/// Converts the markup of a [`LocalizedLine`] into [`TextSection`]s by applying a style rule for every attribute.
+/// Text outside of any attribute uses the base [`TextStyle`]. Where attributes overlap, the rules are applied in the order the attributes appear in the line.
+/// Attributes without a rule, such as the `character` attribute, are ignored.
+///
+/// The `color` attribute is supported out of the box and accepts CSS color names and hex codes, e.g. `[color=red]` or `[color=#ff8800]`.
+/// Since fonts cannot be made bold or italic on the fly, attributes like `[b]` need a font registered with [`MarkupTextStyles::with_font`].
+///
+/// Requires the `text` feature.
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::{events::*, prelude::*};
+/// fn present_line(
+///     mut events: EventReader<PresentLineEvent>,
+///     asset_server: Res<AssetServer>,
+///     mut text: Query<&mut Text>,
+/// ) {
+///     let styles = MarkupTextStyles::new(TextStyle::default())
+///         .with_font("b", asset_server.load("fonts/FiraSans-Bold.ttf"))
+///         .with_color("angry", Color::srgb(0.8, 0.1, 0.1))
+///         .with_rule("big", |_attribute, style| style.font_size *= 1.5);
+///     for event in events.read() {
+///         *text.single_mut() = styles.to_text(&event.line);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MarkupTextStyles {
+    /// The style of text that is not covered by any attribute with a rule.
+    pub base: TextStyle,
+    rules: HashMap<String, SkipDebug<StyleRule>>,
+}
+
+impl Default for MarkupTextStyles {
+    fn default() -> Self {
+        Self::new(TextStyle::default())
+    }
+}
+
+impl MarkupTextStyles {
+    /// Creates styles that use the given base style and support the builtin `color` attribute.
+    pub fn new(base: TextStyle) -> Self {
+        Self {
+            base,
+            rules: HashMap::new(),
+        }
+        .with_rule("color", |attribute, style| {
+            let Some(value) = attribute.properties.get("color") else {
+                return;
+            };
+            match parse_color(&value.to_string()) {
+                Some(color) => style.color = color,
+                None => {
+                    warn!("Ignoring markup attribute {attribute}: \"{value}\" is not a valid color")
+                }
+            }
+        })
+    }
+
+    /// Adds a rule that modifies the style of all text covered by the attribute with the given name, replacing any previous rule for it.
+    /// The rule also receives the attribute itself, so it can read its properties.
+    #[must_use]
+    pub fn with_rule(
+        mut self,
+        attribute_name: impl Into<String>,
+        rule: impl Fn(&MarkupAttribute, &mut TextStyle) + Send + Sync + 'static,
+    ) -> Self {
+        self.rules
+            .insert(attribute_name.into(), SkipDebug(Arc::new(rule)));
+        self
+    }
+
+    /// Renders text covered by the attribute with the given name in the given font, e.g. a bold font for `[b]`.
+    #[must_use]
+    pub fn with_font(self, attribute_name: impl Into<String>, font: Handle<Font>) -> Self {
+        self.with_rule(attribute_name, move |_, style| style.font = font.clone())
+    }
+
+    /// Renders text covered by the attribute with the given name in the given color.
+    #[must_use]
+    pub fn with_color(self, attribute_name: impl Into<String>, color: impl Into<Color>) -> Self {
+        let color = color.into();
+        self.with_rule(attribute_name, move |_, style| style.color = color)
+    }
+
+    /// Renders text covered by the attribute with the given name in the given font size.
+    #[must_use]
+    pub fn with_font_size(self, attribute_name: impl Into<String>, font_size: f32) -> Self {
+        self.with_rule(attribute_name, move |_, style| style.font_size = font_size)
+    }
+
+    /// Removes the rule of the attribute with the given name, including the builtin `color` rule.
+    #[must_use]
+    pub fn without_rule(mut self, attribute_name: &str) -> Self {
+        self.rules.remove(attribute_name);
+        self
+    }
+
+    /// Returns whether a rule is registered for the attribute with the given name.
+    #[must_use]
+    pub fn has_rule(&self, attribute_name: &str) -> bool {
+        self.rules.contains_key(attribute_name)
+    }
+
+    /// Converts the text of the line into a [`Text`] with one section per differently styled range.
+    #[must_use]
+    pub fn to_text(&self, line: &LocalizedLine) -> Text {
+        Text::from_sections(self.to_sections(&line.text, &line.attributes))
+    }
+
+    /// Converts plain text and the markup attributes that were parsed from it into [`TextSection`]s.
+    /// Attribute positions and lengths are measured in grapheme clusters, as in [`MarkupAttribute`].
+    #[must_use]
+    pub fn to_sections(&self, text: &str, attributes: &[MarkupAttribute]) -> Vec<TextSection> {
+        let byte_offsets: Vec<_> = text
+            .grapheme_indices(true)
+            .map(|(index, _)| index)
+            .chain(std::iter::once(text.len()))
+            .collect();
+        let grapheme_count = byte_offsets.len() - 1;
+        let attributes: Vec<_> = attributes
+            .iter()
+            .filter(|attribute| attribute.length > 0 && self.rules.contains_key(&attribute.name))
+            .collect();
+        let mut boundaries: Vec<_> = attributes
+            .iter()
+            .flat_map(|attribute| [attribute.position, attribute.position + attribute.length])
+            .chain([0, grapheme_count])
+            .map(|position| position.min(grapheme_count))
+            .collect();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        boundaries
+            .windows(2)
+            .map(|range| {
+                let (start, end) = (range[0], range[1]);
+                let mut style = self.base.clone();
+                for attribute in attributes.iter().filter(|attribute| {
+                    attribute.position <= start && end <= attribute.position + attribute.length
+                }) {
+                    (self.rules[&attribute.name])(attribute, &mut style);
+                }
+                TextSection {
+                    value: text[byte_offsets[start]..byte_offsets[end]].to_owned(),
+                    style,
+                }
+            })
+            .collect()
+    }
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    use bevy::color::palettes::css;
+    if value.starts_with('#') {
+        return Srgba::hex(value).ok().map(Color::from);
+    }
+    let color = match value.to_lowercase().as_str() {
+        "black" => css::BLACK,
+        "white" => css::WHITE,
+        "gray" | "grey" => css::GRAY,
+        "red" => css::RED,
+        "green" => css::GREEN,
+        "lime" => css::LIME,
+        "blue" => css::BLUE,
+        "yellow" => css::YELLOW,
+        "orange" => css::ORANGE,
+        "purple" => css::PURPLE,
+        "pink" => css::PINK,
+        "cyan" | "aqua" => css::AQUA,
+        "magenta" | "fuchsia" => css::FUCHSIA,
+        "brown" => css::BROWN,
+        _ => return None,
+    };
+    Some(color.into())
+}
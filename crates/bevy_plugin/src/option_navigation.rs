@@ -0,0 +1,338 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::events::PresentOptionsEvent;
+use crate::prelude::*;
+use bevy::prelude::*;
+
+pub(crate) fn option_navigation_plugin(app: &mut App) {
+    app.add_event::<OptionNavigationEvent>().add_systems(
+        Update,
+        (
+            track_presented_options,
+            read_navigation_input,
+            navigate_options,
+        )
+            .chain()
+            .after(DialogueExecutionSystemSet)
+            .in_set(YarnSpinnerSystemSet),
+    );
+}
+
+/// Add this component to the entity of a [`DialogueRunner`] to navigate its options with the keyboard or a gamepad.
+/// Whenever the runner presents options, the available ones are stored here and the first one is highlighted.
+/// Confirming an option calls [`DialogueRunner::select_option`].
+/// Dialogue views render [`OptionNavigation::options`] and mark [`OptionNavigation::highlighted_option`], e.g. in a system filtered by `Changed<OptionNavigation>`.
+///
+/// Mouse and touch input depends on how the options are drawn, so views translate it into [`OptionNavigationEvent`]s themselves:
+/// send [`OptionNavigationAction::Highlight`] when an option is hovered and [`OptionNavigationAction::Select`] when it is clicked.
+/// The same event is the integration point for other input sources like `leafwing-input-manager`; use [`OptionNavigationBindings::none`]
+/// to turn off the builtin bindings in that case.
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::{events::*, prelude::*};
+/// fn spawn_dialogue_runner(mut commands: Commands, project: Res<YarnProject>) {
+///     commands.spawn((project.create_dialogue_runner(), OptionNavigation::default()));
+/// }
+///
+/// fn print_highlighted_option(navigations: Query<&OptionNavigation, Changed<OptionNavigation>>) {
+///     for navigation in navigations.iter() {
+///         if let Some(option) = navigation.highlighted_option() {
+///             println!("> {}", option.line.text);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Component)]
+pub struct OptionNavigation {
+    /// The keys and gamepad buttons that navigate the options.
+    pub bindings: OptionNavigationBindings,
+    /// Whether moving past the last option highlights the first one and vice versa. Defaults to `false`.
+    pub wrap_around: bool,
+    options: Vec<DialogueOption>,
+    highlighted: Option<usize>,
+}
+
+impl OptionNavigation {
+    /// Creates a navigation with the given bindings.
+    pub fn with_bindings(bindings: OptionNavigationBindings) -> Self {
+        Self {
+            bindings,
+            ..default()
+        }
+    }
+
+    /// Sets whether moving past the last option highlights the first one and vice versa.
+    #[must_use]
+    pub fn with_wrap_around(mut self, wrap_around: bool) -> Self {
+        self.wrap_around = wrap_around;
+        self
+    }
+
+    /// Returns the available options of the options set that is currently presented, in the order they were presented.
+    /// Empty if the [`DialogueRunner`] is not waiting for an option to be selected.
+    #[must_use]
+    pub fn options(&self) -> &[DialogueOption] {
+        &self.options
+    }
+
+    /// Returns the index into [`OptionNavigation::options`] of the highlighted option.
+    #[must_use]
+    pub fn highlighted_index(&self) -> Option<usize> {
+        self.highlighted
+    }
+
+    /// Returns the option that is selected when the player confirms.
+    #[must_use]
+    pub fn highlighted_option(&self) -> Option<&DialogueOption> {
+        self.options.get(self.highlighted?)
+    }
+
+    /// Returns whether options are currently presented and can be navigated.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        !self.options.is_empty()
+    }
+
+    fn step(&mut self, offset: isize) {
+        let Some(highlighted) = self.highlighted else {
+            return;
+        };
+        let len = self.options.len() as isize;
+        let target = highlighted as isize + offset;
+        let target = if self.wrap_around {
+            target.rem_euclid(len)
+        } else {
+            target.clamp(0, len - 1)
+        };
+        self.highlighted = Some(target as usize);
+    }
+
+    fn index_of(&self, option: OptionId) -> Option<usize> {
+        self.options.iter().position(|o| o.id == option)
+    }
+
+    fn clear(&mut self) {
+        self.options.clear();
+        self.highlighted = None;
+    }
+}
+
+/// The keys and gamepad buttons used by an [`OptionNavigation`]. Gamepad buttons are read from all connected gamepads.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OptionNavigationBindings {
+    /// Keys that highlight the previous option. Defaults to the up arrow and W.
+    pub previous_keys: Vec<KeyCode>,
+    /// Keys that highlight the next option. Defaults to the down arrow and S.
+    pub next_keys: Vec<KeyCode>,
+    /// Keys that select the highlighted option. Defaults to enter and space.
+    pub confirm_keys: Vec<KeyCode>,
+    /// Gamepad buttons that highlight the previous option. Defaults to the D-pad up button.
+    pub previous_buttons: Vec<GamepadButtonType>,
+    /// Gamepad buttons that highlight the next option. Defaults to the D-pad down button.
+    pub next_buttons: Vec<GamepadButtonType>,
+    /// Gamepad buttons that select the highlighted option. Defaults to the south face button, e.g. A on an Xbox controller.
+    pub confirm_buttons: Vec<GamepadButtonType>,
+    /// Whether the number keys 1 to 9, including those of the numpad, directly select the option at that position. Defaults to `true`.
+    pub number_keys: bool,
+}
+
+impl Default for OptionNavigationBindings {
+    fn default() -> Self {
+        Self {
+            previous_keys: vec![KeyCode::ArrowUp, KeyCode::KeyW],
+            next_keys: vec![KeyCode::ArrowDown, KeyCode::KeyS],
+            confirm_keys: vec![KeyCode::Enter, KeyCode::Space],
+            previous_buttons: vec![GamepadButtonType::DPadUp],
+            next_buttons: vec![GamepadButtonType::DPadDown],
+            confirm_buttons: vec![GamepadButtonType::South],
+            number_keys: true,
+        }
+    }
+}
+
+impl OptionNavigationBindings {
+    /// No bindings at all, for driving the navigation exclusively through [`OptionNavigationEvent`]s.
+    pub fn none() -> Self {
+        Self {
+            previous_keys: Vec::new(),
+            next_keys: Vec::new(),
+            confirm_keys: Vec::new(),
+            previous_buttons: Vec::new(),
+            next_buttons: Vec::new(),
+            confirm_buttons: Vec::new(),
+            number_keys: false,
+        }
+    }
+}
+
+/// Send this event to navigate the options of a [`DialogueRunner`] with an [`OptionNavigation`].
+/// The builtin keyboard and gamepad bindings send these events as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Event)]
+pub struct OptionNavigationEvent {
+    /// What to do.
+    pub action: OptionNavigationAction,
+    /// The [`DialogueRunner`] whose options are navigated.
+    pub source: Entity,
+}
+
+/// What an [`OptionNavigationEvent`] does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OptionNavigationAction {
+    /// Highlights the previous option.
+    Previous,
+    /// Highlights the next option.
+    Next,
+    /// Highlights the given option, e.g. because the mouse hovers over it.
+    Highlight(OptionId),
+    /// Selects the highlighted option.
+    Confirm,
+    /// Selects the given option directly, e.g. because it was clicked.
+    Select(OptionId),
+}
+
+fn track_presented_options(
+    mut present_options_events: EventReader<PresentOptionsEvent>,
+    mut navigations: Query<(&mut OptionNavigation, &DialogueRunner)>,
+) {
+    for event in present_options_events.read() {
+        let Ok((mut navigation, _)) = navigations.get_mut(event.source) else {
+            continue;
+        };
+        navigation.options = event
+            .options
+            .iter()
+            .filter(|option| option.is_available)
+            .cloned()
+            .collect();
+        navigation.highlighted = (!navigation.options.is_empty()).then_some(0);
+    }
+    for (mut navigation, dialogue_runner) in navigations.iter_mut() {
+        let is_stale = navigation.is_active()
+            && !(dialogue_runner.is_running() && dialogue_runner.is_waiting_for_option_selection());
+        if is_stale {
+            // The options were selected by someone else, e.g. a choice timer
+            navigation.clear();
+        }
+    }
+}
+
+fn read_navigation_input(
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    gamepad_buttons: Option<Res<ButtonInput<GamepadButton>>>,
+    gamepads: Option<Res<Gamepads>>,
+    navigations: Query<(Entity, &OptionNavigation, &DialogueRunner)>,
+    mut navigation_events: EventWriter<OptionNavigationEvent>,
+) {
+    let key_pressed = |bound_keys: &[KeyCode]| {
+        keys.as_ref()
+            .is_some_and(|keys| keys.any_just_pressed(bound_keys.iter().copied()))
+    };
+    let button_pressed = |bound_buttons: &[GamepadButtonType]| {
+        let (Some(gamepad_buttons), Some(gamepads)) = (&gamepad_buttons, &gamepads) else {
+            return false;
+        };
+        gamepads.iter().any(|gamepad| {
+            bound_buttons
+                .iter()
+                .any(|&button| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button)))
+        })
+    };
+    for (source, navigation, dialogue_runner) in navigations.iter() {
+        if !navigation.is_active() || dialogue_runner.is_paused() {
+            continue;
+        }
+        let bindings = &navigation.bindings;
+        let mut send = |action| {
+            navigation_events.send(OptionNavigationEvent { action, source });
+        };
+        if key_pressed(&bindings.previous_keys) || button_pressed(&bindings.previous_buttons) {
+            send(OptionNavigationAction::Previous);
+        }
+        if key_pressed(&bindings.next_keys) || button_pressed(&bindings.next_buttons) {
+            send(OptionNavigationAction::Next);
+        }
+        if bindings.number_keys {
+            let pressed_number = NUMBER_KEYS
+                .into_iter()
+                .zip(NUMPAD_KEYS)
+                .zip(&navigation.options)
+                .find(|((number_key, numpad_key), _)| key_pressed(&[*number_key, *numpad_key]));
+            if let Some((_, option)) = pressed_number {
+                send(OptionNavigationAction::Select(option.id));
+            }
+        }
+        if key_pressed(&bindings.confirm_keys) || button_pressed(&bindings.confirm_buttons) {
+            send(OptionNavigationAction::Confirm);
+        }
+    }
+}
+
+pub(crate) fn navigate_options(
+    mut navigation_events: EventReader<OptionNavigationEvent>,
+    mut navigations: Query<(&mut OptionNavigation, &mut DialogueRunner)>,
+) {
+    for event in navigation_events.read() {
+        let Ok((mut navigation, mut dialogue_runner)) = navigations.get_mut(event.source) else {
+            warn!(
+                "Received an option navigation event for entity {}, which has no DialogueRunner with an OptionNavigation",
+                event.source
+            );
+            continue;
+        };
+        if !navigation.is_active() {
+            // E.g. a second confirmation in the same frame
+            continue;
+        }
+        let selection = match event.action {
+            OptionNavigationAction::Previous => {
+                navigation.step(-1);
+                None
+            }
+            OptionNavigationAction::Next => {
+                navigation.step(1);
+                None
+            }
+            OptionNavigationAction::Highlight(option) => {
+                if let Some(index) = navigation.index_of(option) {
+                    navigation.highlighted = Some(index);
+                }
+                None
+            }
+            OptionNavigationAction::Confirm => navigation.highlighted_option().map(|o| o.id),
+            OptionNavigationAction::Select(option) => navigation.index_of(option).map(|_| option),
+        };
+        let Some(option) = selection else {
+            continue;
+        };
+        navigation.clear();
+        dialogue_runner
+            .select_option(option)
+            .unwrap_or_else(|e| panic!("{e}"));
+    }
+}
+
+const NUMBER_KEYS: [KeyCode; 9] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+const NUMPAD_KEYS: [KeyCode; 9] = [
+    KeyCode::Numpad1,
+    KeyCode::Numpad2,
+    KeyCode::Numpad3,
+    KeyCode::Numpad4,
+    KeyCode::Numpad5,
+    KeyCode::Numpad6,
+    KeyCode::Numpad7,
+    KeyCode::Numpad8,
+    KeyCode::Numpad9,
+];
@@ -28,13 +28,19 @@ mod yarn_file_source;
 ///
 /// Note that the above does not work on Wasm or Android, since Bevy cannot query folders on these platforms. See [`YarnSpinnerPlugin::new`] for more information.
 ///
+/// When Yarn files are recompiled because of hot reloading, running [`DialogueRunner`]s keep their variables.
+/// If the node they are in did not change, they also keep their position, so edited lines show up without restarting the conversation.
+/// Otherwise, they restart the node.
+///
 /// For more information on how this plugin interacts with the rest of the crate, see the crate-level documentation.
 #[derive(Debug, Default)]
 pub struct YarnSpinnerPlugin {
     project: LoadYarnProjectEvent,
+    error_handling: YarnErrorHandling,
 }
 
 /// The [`SystemSet`] containing all systems used by the [`YarnSpinnerPlugin`].
+/// Use the [`CompilationSystemSet`], [`LocalizationSystemSet`], [`LineProviderSystemSet`] and [`DialogueExecutionSystemSet`] to order your systems relative to individual stages.
 #[derive(Debug, Default, Clone, Copy, SystemSet, Eq, PartialEq, Hash)]
 pub struct YarnSpinnerSystemSet;
 
@@ -87,6 +93,7 @@ impl YarnSpinnerPlugin {
     {
         Self {
             project: LoadYarnProjectEvent::with_yarn_sources(yarn_files),
+            error_handling: default(),
         }
     }
 
@@ -109,6 +116,26 @@ impl YarnSpinnerPlugin {
     pub fn with_yarn_source(yarn_file_source: impl Into<YarnFileSource>) -> Self {
         Self {
             project: LoadYarnProjectEvent::with_yarn_source(yarn_file_source),
+            error_handling: default(),
+        }
+    }
+
+    /// Creates a new plugin that loads a [`YarnProgram`] from the given `.yarnc` file inside the `assets` folder instead of compiling Yarn files.
+    /// The strings file next to it is loaded as well, see [`YarnProgram`].
+    ///
+    /// Since there are no Yarn files, nothing is recompiled during runtime and no development files are generated.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_yarnspinner::prelude::*;
+    /// let plugin = YarnSpinnerPlugin::with_precompiled_program("dialogue/game.yarnc");
+    /// ```
+    #[must_use]
+    pub fn with_precompiled_program(path: impl Into<PathBuf>) -> Self {
+        Self {
+            project: LoadYarnProjectEvent::with_precompiled_program(path),
+            error_handling: default(),
         }
     }
 
@@ -155,15 +182,56 @@ impl YarnSpinnerPlugin {
             .with_development_file_generation(development_file_generation);
         self
     }
+
+    /// Enables or disables strict translations. By default, a line that is missing in the strings file of the selected language
+    /// falls back to the text of the base language and sends a [`MissingTranslationEvent`](crate::events::MissingTranslationEvent).
+    /// In strict mode, a missing translation panics instead, which helps to find untranslated lines during development.
+    #[must_use]
+    pub fn with_strict_translations(mut self, strict_translations: bool) -> Self {
+        self.project = self.project.with_strict_translations(strict_translations);
+        self
+    }
+
+    /// Sets whether the content of the [`YarnProject`] is checked for problems that would break a shipped game: untranslated lines, missing line assets and unregistered commands.
+    /// See [`ContentValidation`] for the available modes and [`ContentValidationReport`] for the result. Defaults to [`ContentValidation::Disabled`].
+    #[must_use]
+    pub fn with_content_validation(mut self, content_validation: ContentValidation) -> Self {
+        self.project = self.project.with_content_validation(content_validation);
+        self
+    }
+
+    /// Loads a [`VariableDefaults`] file from the given path inside the `assets` folder and uses its values instead of the defaults
+    /// of the `<<declare>>` statements, e.g. to select a difficulty preset. The project is only inserted once the file is loaded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_yarnspinner::prelude::*;
+    /// let plugin = YarnSpinnerPlugin::new().with_variable_defaults("dialogue/hard.yarnvars.json");
+    /// ```
+    #[must_use]
+    pub fn with_variable_defaults(mut self, path: impl Into<PathBuf>) -> Self {
+        self.project = self.project.with_variable_defaults(path);
+        self
+    }
+
+    /// Sets whether recoverable errors panic or are sent as [`YarnErrorEvent`](crate::events::YarnErrorEvent)s. Defaults to [`YarnErrorHandling::Panic`].
+    /// See [`YarnErrorHandling`] for details.
+    #[must_use]
+    pub fn with_error_handling(mut self, error_handling: YarnErrorHandling) -> Self {
+        self.error_handling = error_handling;
+        self
+    }
 }
 
 impl Plugin for YarnSpinnerPlugin {
     fn build(&self, app: &mut App) {
-        assert!(!self.project.yarn_files.is_empty(), "Cannot initialize Yarn Spinner plugin because no Yarn files were specified. \
+        assert!(!self.project.yarn_files.is_empty() || self.project.precompiled_program.is_some(), "Cannot initialize Yarn Spinner plugin because no Yarn files were specified. \
         Did you call `YarnSpinnerPlugin::with_yarn_files()` without any Yarn file sources? \
         If you really want to load no Yarn files right now and do that later, use `YarnSpinnerPlugin::deferred()` instead.\
         If you wanted to load from the default directory instead, use `YarnSpinnerPlugin::default()`.");
         app.add_plugins(Self::deferred())
+            .insert_resource(self.error_handling)
             .world_mut()
             .send_event(self.project.clone());
     }
@@ -223,16 +291,31 @@ impl YarnApp for App {
             .register_type::<yarnspinner::runtime::MarkupParseError>()
             .register_type::<MarkupAttribute>()
             .register_type::<MarkupValue>()
+            .register_type::<Language>()
+            .register_type::<DialogueMetrics>()
     }
 
     fn register_sub_plugins(&mut self) -> &mut Self {
         self.add_plugins(crate::yarn_file_asset::yarnspinner_asset_loader_plugin)
+            .add_plugins(crate::yarn_program_asset::yarn_program_asset_plugin)
+            .add_plugins(crate::variable_defaults_asset::variable_defaults_asset_plugin)
             .add_plugins(crate::localization::localization_plugin)
             .add_plugins(crate::dialogue_runner::dialogue_plugin)
+            .add_plugins(crate::dialogue_trigger::dialogue_trigger_plugin)
+            .add_plugins(crate::option_navigation::option_navigation_plugin)
             .add_plugins(crate::line_provider::line_provider_plugin)
             .add_plugins(crate::project::project_plugin)
             .add_plugins(crate::commands::commands_plugin)
+            .add_plugins(crate::system_functions::system_functions_plugin)
+            .add_plugins(crate::variable_bindings::variable_bindings_plugin)
             .add_plugins(crate::development_file_generation::development_file_generation_plugin)
+            .add_plugins(crate::console::console_plugin)
+            .add_plugins(crate::transcript::transcript_plugin)
+            .add_plugins(crate::replay::replay_plugin)
+            .add_plugins(crate::replication::replication_plugin)
+            .add_plugins(crate::inspector::inspector_plugin)
+            .add_plugins(crate::content_validation::content_validation_plugin)
+            .add_plugins(crate::error_handling::error_handling_plugin)
     }
 
     fn register_watching_for_changes(&mut self) -> &mut Self {
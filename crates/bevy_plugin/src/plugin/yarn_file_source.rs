@@ -12,7 +12,8 @@ use std::path::PathBuf;
 pub enum YarnFileSource {
     /// A [`YarnFile`] that is already present in the asset server, addressed by its [`Handle`].
     Handle(Handle<YarnFile>),
-    /// A [`YarnFile`] that is already present in memory, created with [`YarnFile::new`].
+    /// A [`YarnFile`] that is already present in memory, created with [`YarnFile::new`] or [`include_yarn!`](crate::include_yarn).
+    /// Works on all platforms, as nothing is read from disk. With [`DevelopmentFileGeneration::Full`], missing line IDs are added in memory only.
     InMemory(YarnFile),
     /// A [`YarnFile`] inside the `assets` folder. This will be loaded into the [`AssetServer`].
     /// Use [`YarnFileSource::file`] for convenience.
@@ -1,25 +1,38 @@
 use crate::fmt_utils::SkipDebug;
+use crate::localization::LanguageChangedEvent;
 use crate::prelude::*;
+pub use additional::AdditionalYarnProject;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::utils::{HashMap, HashSet};
+pub use compilation::{yarn_project_ready, YarnProjectCompiledEvent, YarnProjectLoading};
 pub(crate) use compilation::{
     RecompileLoadedYarnFilesEvent, YarnFilesBeingLoaded, YarnProjectConfigToLoad,
 };
 use std::fmt::Debug;
 use std::iter;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 
+mod added_files;
+mod additional;
 mod compilation;
 
 pub(crate) fn project_plugin(app: &mut App) {
     app.add_plugins(compilation::project_compilation_plugin)
+        .add_plugins(additional::additional_project_plugin)
+        .add_plugins(added_files::added_files_plugin)
         .add_event::<LoadYarnProjectEvent>();
 }
 
+/// The [`SystemSet`] containing the systems that load and compile Yarn files and insert or update the [`YarnProject`].
+/// Systems that run after it in the same frame see a [`YarnProject`] that was just compiled or recompiled.
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, SystemSet)]
-pub(crate) struct CompilationSystemSet;
+pub struct CompilationSystemSet;
 
 /// The compiled Yarn project built from the Yarn files passed to the [`YarnSpinnerPlugin`], or, in the deferred loading case, the Yarn files passed to the [`LoadYarnProjectEvent`].
-/// This [`Resource`](bevy::prelude::Resource) is inserted into the world automatically for you once all files have been loaded and compiled. You can react to this by configuring a system like this:
+/// This [`Resource`](bevy::prelude::Resource) is inserted into the world automatically for you once all files have been loaded and compiled, which happens in the background while [`YarnProjectLoading`] exists.
+/// You can react to this by configuring a system like this:
 /// ```rust
 /// # use bevy::prelude::*;
 /// # use bevy_yarnspinner::prelude::*;
@@ -30,18 +43,37 @@ pub(crate) struct CompilationSystemSet;
 ///    commands.spawn(project.create_dialogue_runner());
 /// }
 /// ```
-#[derive(Resource, Debug)]
+///
+/// Further projects, e.g. for DLCs or mods, can be loaded next to it with [`AdditionalYarnProject`]. These are inserted as a [`Component`] instead,
+/// see [`YarnProjects`] for accessing all loaded projects at once.
+#[derive(Resource, Component, Debug)]
 pub struct YarnProject {
+    pub(crate) id: YarnProjectId,
     pub(crate) yarn_files: HashSet<Handle<YarnFile>>,
     pub(crate) compilation: Compilation,
     pub(crate) localizations: Option<Localizations>,
     pub(crate) asset_server: SkipDebug<AssetServer>,
     pub(crate) metadata: HashMap<LineId, Vec<String>>,
+    #[cfg_attr(not(feature = "development_file_generation"), allow(dead_code))]
     pub(crate) watching_for_changes: bool,
     pub(crate) development_file_generation: DevelopmentFileGeneration,
+    pub(crate) strict_translations: bool,
+    pub(crate) content_validation: ContentValidation,
+    pub(crate) text_language: Option<Language>,
+    pub(crate) asset_language: Option<Language>,
+    pub(crate) pending_language_change: Option<LanguageChangedEvent>,
+    pub(crate) variable_storage: Box<dyn VariableStorage>,
+    pub(crate) variable_defaults: Option<Handle<VariableDefaults>>,
+    pub(crate) added_files: added_files::AddedYarnFiles,
 }
 
 impl YarnProject {
+    /// Returns the ID of this project, which is shared by all [`DialogueRunner`]s built from it. See [`DialogueRunner::project_id`].
+    #[must_use]
+    pub fn id(&self) -> YarnProjectId {
+        self.id
+    }
+
     /// Iterates over the [`YarnFile`]s that were used to compile this project. These will be the files passed to the [`YarnSpinnerPlugin`] or the [`LoadYarnProjectEvent`].
     pub fn yarn_files(&self) -> impl Iterator<Item = &Handle<YarnFile>> {
         self.yarn_files.iter()
@@ -57,6 +89,88 @@ impl YarnProject {
         self.localizations.as_ref()
     }
 
+    /// Returns the [`VariableStorage`] shared by all [`DialogueRunner`]s of this project that were not built with
+    /// [`DialogueRunnerBuilder::with_isolated_variable_storage`] or [`DialogueRunnerBuilder::with_variable_storage`].
+    #[must_use]
+    pub fn variable_storage(&self) -> &dyn VariableStorage {
+        self.variable_storage.as_ref()
+    }
+
+    /// Returns the [`VariableStorage`] shared by all [`DialogueRunner`]s of this project mutably, e.g. to set variables before any dialogue runs.
+    /// See [`YarnProject::variable_storage`].
+    #[must_use]
+    pub fn variable_storage_mut(&mut self) -> &mut dyn VariableStorage {
+        self.variable_storage.as_mut()
+    }
+
+    /// Returns the language of the lines presented by [`DialogueRunner`]s of this project.
+    /// This is the base language of the [`Localizations`] until changed with [`YarnProject::set_text_language`].
+    /// If there are no [`Localizations`] available, this will return [`None`].
+    #[must_use]
+    pub fn text_language(&self) -> Option<Language> {
+        self.text_language.clone().or_else(|| self.base_language())
+    }
+
+    /// Returns the language of the assets provided to [`DialogueRunner`]s of this project.
+    /// This is the base language of the [`Localizations`] until changed with [`YarnProject::set_asset_language`].
+    /// If there are no [`Localizations`] available, this will return [`None`].
+    #[must_use]
+    pub fn asset_language(&self) -> Option<Language> {
+        self.asset_language.clone().or_else(|| self.base_language())
+    }
+
+    /// Sets the language of both the lines and the assets. Same as calling [`YarnProject::set_text_language`] and [`YarnProject::set_asset_language`].
+    pub fn set_language(&mut self, language: impl Into<Language>) -> &mut Self {
+        let language = language.into();
+        self.set_text_language(language.clone())
+            .set_asset_language(language)
+    }
+
+    /// Sets the language of the lines for all existing [`DialogueRunner`]s and all that are created from this project afterwards, e.g. from an in-game language menu.
+    /// The change is applied in the next update, which also sends a [`LanguageChangedEvent`](crate::events::LanguageChangedEvent).
+    /// A line that is currently presented by a running dialogue is presented again in the new language with a new [`PresentLineEvent`](crate::events::PresentLineEvent)
+    /// as soon as its translation is loaded.
+    ///
+    /// Panics if the [`Localizations`] do not support the language. Use [`DialogueRunner::set_text_language`] to only change the language of a single dialogue runner.
+    pub fn set_text_language(&mut self, language: impl Into<Language>) -> &mut Self {
+        let language = language.into();
+        self.assert_localizations_available_for_language(&language);
+        self.text_language = Some(language.clone());
+        self.pending_language_change
+            .get_or_insert_with(default)
+            .text_language = Some(language);
+        self
+    }
+
+    /// Sets the language of the assets for all existing [`DialogueRunner`]s and all that are created from this project afterwards.
+    /// Behaves like [`YarnProject::set_text_language`], so a currently presented line is presented again with its assets in the new language.
+    pub fn set_asset_language(&mut self, language: impl Into<Language>) -> &mut Self {
+        let language = language.into();
+        self.assert_localizations_available_for_language(&language);
+        self.asset_language = Some(language.clone());
+        self.pending_language_change
+            .get_or_insert_with(default)
+            .asset_language = Some(language);
+        self
+    }
+
+    fn base_language(&self) -> Option<Language> {
+        self.localizations
+            .as_ref()
+            .map(|localizations| localizations.base_localization.language.clone())
+    }
+
+    fn assert_localizations_available_for_language(&self, language: &Language) {
+        let localizations = self.localizations.as_ref().expect(
+            "Tried to set language, but no localizations are available. \
+            Did you forget to call `YarnSpinnerApp::with_localizations(..)` on the plugin setup?",
+        );
+        assert!(
+            localizations.supports_language(language),
+            "Tried to set language to {language}, but no localizations are available for that language."
+        );
+    }
+
     /// Constructs a [`DialogueRunner`] from this project using all defaults of [`DialogueRunnerBuilder`] .
     /// This is a convenience method for calling [`DialogueRunnerBuilder::build`] on an unconfigured builder returned by [`YarnProject::build_dialogue_runner`].
     pub fn create_dialogue_runner(&self) -> DialogueRunner {
@@ -68,6 +182,51 @@ impl YarnProject {
         DialogueRunnerBuilder::from_yarn_project(self)
     }
 
+    /// Writes the compiled program and its lines to the given path as a [`YarnProgram`], so that shipped builds can load it with
+    /// [`YarnSpinnerPlugin::with_precompiled_program`] instead of compiling the Yarn files at runtime.
+    /// The lines are written in the base language of the [`Localizations`], if any.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bevy::prelude::*;
+    /// # use bevy_yarnspinner::prelude::*;
+    /// fn write_precompiled_program(project: Res<YarnProject>) {
+    ///     project
+    ///         .write_precompiled_program("assets/dialogue/game.yarnc")
+    ///         .unwrap();
+    /// }
+    /// ```
+    ///
+    /// Requires the `development_file_generation` feature.
+    #[cfg(feature = "development_file_generation")]
+    pub fn write_precompiled_program(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
+        let program = YarnProgram {
+            program: self
+                .compilation
+                .program
+                .clone()
+                .context("Cannot write a Yarn project without a program")?,
+            string_table: self.compilation.string_table.clone(),
+        };
+        let language = self.base_language().unwrap_or_default();
+        program.write(path, language)
+    }
+
+    /// Writes the lines of this project in the base language to the given path as a gettext template (`.pot`),
+    /// which localization tools use to create translations in the gettext format.
+    /// The resulting `.po` files can be used as the [`Localization::strings_file`] of a translation.
+    /// Fails if not all lines have line IDs. Requires the `development_file_generation` feature.
+    #[cfg(feature = "development_file_generation")]
+    pub fn write_gettext_template(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
+        let path = path.as_ref().with_extension("pot");
+        StringsFile::from_string_table(
+            self.base_language().unwrap_or_default(),
+            self.compilation.string_table.clone(),
+        )?
+        .write_asset(&path)
+    }
+
     /// Returns the metadata associated with the given [`LineId`], if any. This can also be accessed on a given [`LocalizedLine`] via its `metadata` field.
     pub fn line_metadata(&self, line_id: &LineId) -> Option<&[String]> {
         self.metadata.get(line_id).map(|v| v.as_slice())
@@ -93,13 +252,77 @@ impl YarnProject {
     }
 }
 
+/// Identifies a loaded [`YarnProject`]. Every project gets its own ID when it is compiled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Reflect)]
+pub struct YarnProjectId(u64);
+
+impl YarnProjectId {
+    pub(crate) fn new_unique() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// A [`SystemParam`] giving access to all loaded [`YarnProject`]s: the one inserted as a [`Resource`] by the [`YarnSpinnerPlugin`]
+/// as well as the ones inserted as a [`Component`] by [`AdditionalYarnProject`].
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::prelude::*;
+/// fn print_node_headers(projects: YarnProjects, dialogue_runners: Query<&DialogueRunner>) {
+///     for dialogue_runner in dialogue_runners.iter() {
+///         let Some(node) = dialogue_runner.current_node() else {
+///             continue;
+///         };
+///         let project = projects.for_dialogue_runner(dialogue_runner).unwrap();
+///         println!("{:?}", project.headers_for_node(&node));
+///     }
+/// }
+/// ```
+#[derive(Debug, SystemParam)]
+pub struct YarnProjects<'w, 's> {
+    primary: Option<Res<'w, YarnProject>>,
+    additional: Query<'w, 's, &'static YarnProject>,
+}
+
+impl<'w, 's> YarnProjects<'w, 's> {
+    /// Returns the project loaded by the [`YarnSpinnerPlugin`] or the [`LoadYarnProjectEvent`], if it is loaded already.
+    #[must_use]
+    pub fn primary(&self) -> Option<&YarnProject> {
+        self.primary.as_deref()
+    }
+
+    /// Returns the project with the given ID, if it is loaded.
+    #[must_use]
+    pub fn get(&self, id: YarnProjectId) -> Option<&YarnProject> {
+        self.iter().find(|project| project.id == id)
+    }
+
+    /// Returns the project the given [`DialogueRunner`] was built from, if it is still loaded.
+    #[must_use]
+    pub fn for_dialogue_runner(&self, dialogue_runner: &DialogueRunner) -> Option<&YarnProject> {
+        self.get(dialogue_runner.project_id())
+    }
+
+    /// Iterates over all loaded projects, starting with the [primary](YarnProjects::primary) one.
+    pub fn iter(&self) -> impl Iterator<Item = &YarnProject> {
+        self.primary().into_iter().chain(self.additional.iter())
+    }
+}
+
 /// Used to late initialize a [`YarnProject`] with a set of Yarn files when using [`YarnSpinnerPlugin::deferred`].
 /// If you know the Yarn files at the start of the game, you should use [`YarnSpinnerPlugin::with_yarn_sources`] instead.
 #[derive(Debug, Clone, PartialEq, Eq, Event)]
 pub struct LoadYarnProjectEvent {
     pub(crate) localizations: Option<Localizations>,
     pub(crate) yarn_files: HashSet<YarnFileSource>,
+    pub(crate) precompiled_program: Option<PathBuf>,
     pub(crate) development_file_generation: DevelopmentFileGeneration,
+    pub(crate) strict_translations: bool,
+    pub(crate) content_validation: ContentValidation,
+    pub(crate) variable_defaults: Option<PathBuf>,
 }
 
 impl Default for LoadYarnProjectEvent {
@@ -107,7 +330,11 @@ impl Default for LoadYarnProjectEvent {
         Self {
             localizations: None,
             yarn_files: HashSet::from([YarnFileSource::Folder(DEFAULT_ASSET_DIR.into())]),
+            precompiled_program: None,
             development_file_generation: default(),
+            strict_translations: false,
+            content_validation: default(),
+            variable_defaults: None,
         }
     }
 }
@@ -143,7 +370,25 @@ impl LoadYarnProjectEvent {
         Self {
             localizations: None,
             yarn_files,
+            precompiled_program: None,
             development_file_generation: default(),
+            strict_translations: false,
+            content_validation: default(),
+            variable_defaults: None,
+        }
+    }
+
+    /// See [`YarnSpinnerPlugin::with_precompiled_program`].
+    #[must_use]
+    pub fn with_precompiled_program(path: impl Into<PathBuf>) -> Self {
+        Self {
+            localizations: None,
+            yarn_files: default(),
+            precompiled_program: Some(path.into()),
+            development_file_generation: DevelopmentFileGeneration::None,
+            strict_translations: false,
+            content_validation: default(),
+            variable_defaults: None,
         }
     }
 
@@ -185,11 +430,29 @@ impl LoadYarnProjectEvent {
         mut self,
         development_file_generation: DevelopmentFileGeneration,
     ) -> Self {
+        development_file_generation.assert_supported();
         self.development_file_generation = development_file_generation;
-        if cfg!(any(target_arch = "wasm32", target_os = "android")) {
-            assert_eq!(self.development_file_generation, DevelopmentFileGeneration::None,
-                       "Failed to build Yarn Spinner plugin: On `DevelopmentFileGeneration::None` is supported on this platform.");
-        }
+        self
+    }
+
+    /// See [`YarnSpinnerPlugin::with_strict_translations`].
+    #[must_use]
+    pub fn with_strict_translations(mut self, strict_translations: bool) -> Self {
+        self.strict_translations = strict_translations;
+        self
+    }
+
+    /// See [`YarnSpinnerPlugin::with_content_validation`].
+    #[must_use]
+    pub fn with_content_validation(mut self, content_validation: ContentValidation) -> Self {
+        self.content_validation = content_validation;
+        self
+    }
+
+    /// See [`YarnSpinnerPlugin::with_variable_defaults`].
+    #[must_use]
+    pub fn with_variable_defaults(mut self, path: impl Into<PathBuf>) -> Self {
+        self.variable_defaults = Some(path.into());
         self
     }
 }
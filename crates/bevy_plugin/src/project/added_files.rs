@@ -0,0 +1,220 @@
+use super::compilation::line_ids_are_ready;
+use crate::error_handling::{handle_error, YarnErrorContext};
+use crate::events::YarnProjectCompiledEvent;
+use crate::plugin::AssetRoot;
+use crate::prelude::*;
+use crate::project::CompilationSystemSet;
+use anyhow::{bail, Context};
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use std::collections::HashMap;
+use yarnspinner::compiler::{DebugInfo, Diagnostic};
+use yarnspinner::core::Program;
+
+pub(crate) fn added_files_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        add_files_to_projects
+            .pipe(handle_error(YarnErrorContext::ProjectLoading))
+            .in_set(CompilationSystemSet)
+            .in_set(YarnSpinnerSystemSet),
+    );
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AddedYarnFiles {
+    to_load: HashSet<YarnFileSource>,
+    being_loaded: HashSet<Handle<YarnFile>>,
+}
+
+impl AddedYarnFiles {
+    fn is_empty(&self) -> bool {
+        self.to_load.is_empty() && self.being_loaded.is_empty()
+    }
+}
+
+impl YarnProject {
+    /// Compiles the given Yarn files and merges their nodes and lines into this project while the game is running,
+    /// e.g. for DLCs, mods or downloadable event content. The files are loaded in the background and merged as soon as all of them are available,
+    /// which sends a [`YarnProjectCompiledEvent`]. Existing [`DialogueRunner`]s of this project can run the new nodes from then on, without being rebuilt.
+    ///
+    /// The new files may use the variables declared in the project, but must not reuse its node names or line IDs.
+    /// Such conflicts are reported as an error and leave the project untouched.
+    /// If the project has [`Localizations`], the new lines need line IDs already, as no development files are generated for them.
+    pub fn add_files<T, U>(&mut self, yarn_files: T) -> &mut Self
+    where
+        T: IntoIterator<Item = U>,
+        U: Into<YarnFileSource>,
+    {
+        self.added_files
+            .to_load
+            .extend(yarn_files.into_iter().map(|yarn_file| yarn_file.into()));
+        self
+    }
+
+    /// Compiles the given Yarn file and merges it into this project. See [`YarnProject::add_files`].
+    pub fn add_file(&mut self, yarn_file: impl Into<YarnFileSource>) -> &mut Self {
+        self.add_files(std::iter::once(yarn_file))
+    }
+
+    /// Returns whether files passed to [`YarnProject::add_files`] are still being loaded and compiled.
+    #[must_use]
+    pub fn is_adding_files(&self) -> bool {
+        !self.added_files.is_empty()
+    }
+}
+
+struct MergedFiles {
+    program: Program,
+    string_table: HashMap<LineId, StringInfo>,
+    debug_info: HashMap<String, DebugInfo>,
+    warnings: Vec<Diagnostic>,
+}
+
+fn add_files_to_projects(
+    primary_project: Option<ResMut<YarnProject>>,
+    mut additional_projects: Query<(Entity, &mut YarnProject)>,
+    mut dialogue_runners: Query<&mut DialogueRunner>,
+    mut yarn_files: ResMut<Assets<YarnFile>>,
+    mut compiled_events: EventWriter<YarnProjectCompiledEvent>,
+    asset_server: Res<AssetServer>,
+    asset_root: Res<AssetRoot>,
+) -> SystemResult {
+    let primary_project = primary_project.map(|project| (None, Mut::from(project)));
+    let additional_projects = additional_projects
+        .iter_mut()
+        .map(|(entity, project)| (Some(entity), project));
+    for (entity, mut project) in primary_project.into_iter().chain(additional_projects) {
+        if project.added_files.is_empty() {
+            continue;
+        }
+        let merged_files =
+            match merge_added_files(&mut project, &mut yarn_files, &asset_server, &asset_root) {
+                Ok(Some(merged_files)) => merged_files,
+                Ok(None) => continue,
+                Err(e) => {
+                    // Dropping the files keeps the error from being reported again every update
+                    project.added_files = default();
+                    return Err(e);
+                }
+            };
+        let project_id = project.id();
+        for mut dialogue_runner in dialogue_runners
+            .iter_mut()
+            .filter(|dialogue_runner| dialogue_runner.project_id() == project_id)
+        {
+            dialogue_runner
+                .dialogue
+                .try_add_program(merged_files.program.clone())?
+                .add_debug_info(merged_files.debug_info.clone());
+            dialogue_runner
+                .text_provider
+                .extend_base_string_table(merged_files.string_table.clone());
+        }
+        compiled_events.send(YarnProjectCompiledEvent {
+            diagnostics: merged_files.warnings,
+            project: entity,
+        });
+        info!("Successfully added Yarn files to the Yarn project");
+    }
+    Ok(())
+}
+
+fn merge_added_files(
+    project: &mut YarnProject,
+    yarn_files: &mut ResMut<Assets<YarnFile>>,
+    asset_server: &AssetServer,
+    asset_root: &AssetRoot,
+) -> Result<Option<MergedFiles>> {
+    let sources = std::mem::take(&mut project.added_files.to_load);
+    for source in sources {
+        let handles = source.load(asset_server, yarn_files, asset_root)?;
+        project.added_files.being_loaded.extend(handles);
+    }
+    let handles = &project.added_files.being_loaded;
+    if let Some(LoadState::Failed(error)) = handles
+        .iter()
+        .filter_map(|handle| asset_server.get_load_state(handle))
+        .find(|state| matches!(state, LoadState::Failed(_)))
+    {
+        bail!("Failed to load Yarn file to add to the Yarn project: {error}");
+    }
+    if !handles.iter().all(|handle| yarn_files.contains(handle)) {
+        return Ok(None);
+    }
+    line_ids_are_ready(
+        handles,
+        yarn_files,
+        project.localizations.as_ref(),
+        DevelopmentFileGeneration::None,
+    )?;
+
+    let mut compiler = YarnCompiler::new();
+    for declaration in project
+        .compilation
+        .declarations
+        .iter()
+        .filter(|declaration| !declaration.is_implicit)
+    {
+        compiler.declare_variable(declaration.clone());
+    }
+    let files = handles
+        .iter()
+        .map(|handle| yarn_files.get(handle).unwrap().file.clone());
+    let added = compiler.add_files(files).compile()?;
+    let added_program = added
+        .program
+        .context("Compiling the added Yarn files produced no program")?;
+    let duplicate_line_ids: Vec<_> = added
+        .string_table
+        .keys()
+        .filter(|line_id| project.compilation.string_table.contains_key(*line_id))
+        .map(|line_id| line_id.to_string())
+        .collect();
+    if !duplicate_line_ids.is_empty() {
+        bail!(
+            "Failed to add Yarn files to the Yarn project: the line IDs {} are already used by the project",
+            duplicate_line_ids.join(", ")
+        );
+    }
+    let existing_program = project
+        .compilation
+        .program
+        .clone()
+        .context("Cannot add Yarn files to a Yarn project without a program")?;
+    let program = Program::try_combine([existing_program, added_program.clone()])?;
+
+    let known_declarations: HashSet<_> = project
+        .compilation
+        .declarations
+        .iter()
+        .map(|declaration| declaration.name.clone())
+        .collect();
+    let compilation = &mut project.compilation;
+    compilation.program = Some(program);
+    compilation.string_table.extend(added.string_table.clone());
+    compilation.declarations.extend(
+        added
+            .declarations
+            .into_iter()
+            .filter(|declaration| !known_declarations.contains(&declaration.name)),
+    );
+    compilation.debug_info.extend(added.debug_info.clone());
+    compilation.file_tags.extend(added.file_tags);
+    project.metadata.extend(
+        added
+            .string_table
+            .iter()
+            .map(|(line_id, string_info)| (line_id.clone(), string_info.metadata.clone())),
+    );
+    let added_files = std::mem::take(&mut project.added_files.being_loaded);
+    project.yarn_files.extend(added_files);
+
+    Ok(Some(MergedFiles {
+        program: added_program,
+        string_table: added.string_table,
+        debug_info: added.debug_info,
+        warnings: added.warnings,
+    }))
+}
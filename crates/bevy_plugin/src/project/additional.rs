@@ -0,0 +1,208 @@
+use super::compilation::{apply_variable_defaults, line_ids_are_ready, variable_defaults_loaded};
+use crate::default_impl::MemoryVariableStorage;
+use crate::error_handling::{handle_error, YarnErrorContext};
+use crate::events::YarnProjectCompiledEvent;
+use crate::fmt_utils::SkipDebug;
+use crate::plugin::AssetRoot;
+use crate::prelude::*;
+use crate::project::{CompilationSystemSet, LoadYarnProjectEvent, YarnProjectId};
+use anyhow::bail;
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+pub(crate) fn additional_project_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        load_additional_projects
+            .pipe(handle_error(YarnErrorContext::ProjectLoading))
+            .in_set(CompilationSystemSet)
+            .in_set(YarnSpinnerSystemSet),
+    );
+}
+
+/// Spawn this [`Component`] to load a Yarn project in addition to the one loaded by the [`YarnSpinnerPlugin`],
+/// e.g. the dialogue of a DLC or a mod. Each project has its own Yarn files, [`Localizations`], string tables and [`VariableStorage`].
+///
+/// Once the project is compiled, this component is replaced by a [`YarnProject`] component on the same entity
+/// and a [`YarnProjectCompiledEvent`] pointing to the entity is sent. [`DialogueRunner`]s built from that project only run its nodes,
+/// and changing its language with [`YarnProject::set_language`] only affects them.
+/// Use [`YarnProjects`] to look up the project of a given [`DialogueRunner`].
+///
+/// Unlike the primary project, additional projects are compiled in a single update, are not recompiled when their Yarn files change
+/// and never generate development files, so their lines need line IDs already if they use [`Localizations`].
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::prelude::*;
+/// fn load_dlc(mut commands: Commands) {
+///     commands.spawn(AdditionalYarnProject::new([YarnFileSource::file(
+///         "dlc/dialogue.yarn",
+///     )]));
+/// }
+///
+/// fn spawn_dlc_dialogue_runner(mut commands: Commands, projects: Query<&YarnProject, Added<YarnProject>>) {
+///     for project in projects.iter() {
+///         commands.spawn(project.create_dialogue_runner());
+///     }
+/// }
+/// ```
+#[derive(Debug, Component)]
+pub struct AdditionalYarnProject {
+    config: LoadYarnProjectEvent,
+    yarn_files_being_loaded: Option<HashSet<Handle<YarnFile>>>,
+    precompiled_program_being_loaded: Option<Handle<YarnProgram>>,
+    variable_defaults_being_loaded: Option<Handle<VariableDefaults>>,
+}
+
+impl AdditionalYarnProject {
+    /// Loads the project described by the given configuration, which supports the same options as for the primary project.
+    /// Development file generation is not supported and always treated as [`DevelopmentFileGeneration::None`].
+    pub fn new(config: impl Into<LoadYarnProjectEvent>) -> Self {
+        Self {
+            config: config.into(),
+            yarn_files_being_loaded: None,
+            precompiled_program_being_loaded: None,
+            variable_defaults_being_loaded: None,
+        }
+    }
+}
+
+fn load_additional_projects(
+    mut commands: Commands,
+    mut projects: Query<(Entity, &mut AdditionalYarnProject)>,
+    mut yarn_files: ResMut<Assets<YarnFile>>,
+    yarn_programs: Res<Assets<YarnProgram>>,
+    variable_defaults: Res<Assets<VariableDefaults>>,
+    mut compiled_events: EventWriter<YarnProjectCompiledEvent>,
+    asset_server: Res<AssetServer>,
+    asset_root: Res<AssetRoot>,
+) -> SystemResult {
+    for (entity, mut project) in projects.iter_mut() {
+        let mut compilation = match try_compile(
+            &mut project,
+            &mut yarn_files,
+            &yarn_programs,
+            &variable_defaults,
+            &asset_server,
+            &asset_root,
+        ) {
+            Ok(Some(compilation)) => compilation,
+            Ok(None) => continue,
+            Err(e) => {
+                // Removing the component keeps the error from being reported again every update
+                commands.entity(entity).remove::<AdditionalYarnProject>();
+                return Err(e);
+            }
+        };
+        apply_variable_defaults(
+            project.variable_defaults_being_loaded.as_ref(),
+            &variable_defaults,
+            &mut compilation,
+        );
+        let metadata = compilation
+            .string_table
+            .iter()
+            .map(|(line_id, string_info)| (line_id.clone(), string_info.metadata.clone()))
+            .collect();
+        compiled_events.send(YarnProjectCompiledEvent {
+            diagnostics: compilation.warnings.clone(),
+            project: Some(entity),
+        });
+        let yarn_files = project.yarn_files_being_loaded.take().unwrap_or_default();
+        let config = &project.config;
+        commands
+            .entity(entity)
+            .remove::<AdditionalYarnProject>()
+            .insert(YarnProject {
+                id: YarnProjectId::new_unique(),
+                yarn_files,
+                compilation,
+                localizations: config.localizations.clone(),
+                asset_server: SkipDebug(asset_server.clone()),
+                metadata,
+                watching_for_changes: false,
+                development_file_generation: DevelopmentFileGeneration::None,
+                strict_translations: config.strict_translations,
+                content_validation: config.content_validation,
+                text_language: None,
+                asset_language: None,
+                pending_language_change: None,
+                variable_storage: Box::new(MemoryVariableStorage::new()),
+                variable_defaults: project.variable_defaults_being_loaded.clone(),
+                added_files: default(),
+            });
+        info!("Successfully compiled additional Yarn project of entity {entity}");
+    }
+    Ok(())
+}
+
+fn try_compile(
+    project: &mut AdditionalYarnProject,
+    yarn_files: &mut ResMut<Assets<YarnFile>>,
+    yarn_programs: &Assets<YarnProgram>,
+    variable_defaults: &Assets<VariableDefaults>,
+    asset_server: &AssetServer,
+    asset_root: &AssetRoot,
+) -> Result<Option<Compilation>> {
+    if let Some(path) = project.config.variable_defaults.clone() {
+        project
+            .variable_defaults_being_loaded
+            .get_or_insert_with(|| asset_server.load(path));
+    }
+    if !variable_defaults_loaded(
+        project.variable_defaults_being_loaded.as_ref(),
+        variable_defaults,
+        asset_server,
+    )? {
+        return Ok(None);
+    }
+    if let Some(path) = project.config.precompiled_program.clone() {
+        if !project.config.yarn_files.is_empty() {
+            bail!("Failed to load Yarn project: a precompiled program cannot be combined with Yarn files.");
+        }
+        let handle = project
+            .precompiled_program_being_loaded
+            .get_or_insert_with(|| asset_server.load(path));
+        if let Some(LoadState::Failed(error)) = asset_server.get_load_state(&*handle) {
+            bail!("Failed to load precompiled Yarn program: {error}");
+        }
+        return Ok(yarn_programs
+            .get(&*handle)
+            .map(|yarn_program| yarn_program.to_compilation()));
+    }
+
+    if project.yarn_files_being_loaded.is_none() {
+        let handles: Result<Vec<_>> = project
+            .config
+            .yarn_files
+            .iter()
+            .map(|source| source.load(asset_server, yarn_files, asset_root))
+            .collect();
+        project.yarn_files_being_loaded = Some(handles?.into_iter().flatten().collect());
+    }
+    let handles = project.yarn_files_being_loaded.as_ref().unwrap();
+    if let Some(LoadState::Failed(error)) = handles
+        .iter()
+        .filter_map(|handle| asset_server.get_load_state(handle))
+        .find(|state| matches!(state, LoadState::Failed(_)))
+    {
+        bail!("Failed to load Yarn file of additional Yarn project: {error}");
+    }
+    if !handles.iter().all(|handle| yarn_files.contains(handle)) {
+        return Ok(None);
+    }
+    line_ids_are_ready(
+        handles,
+        yarn_files,
+        project.config.localizations.as_ref(),
+        DevelopmentFileGeneration::None,
+    )?;
+    let files = handles
+        .iter()
+        .map(|handle| yarn_files.get(handle).unwrap().file.clone());
+    let compilation = YarnCompiler::new().add_files(files).compile()?;
+    Ok(Some(compilation))
+}
@@ -1,28 +1,44 @@
+use crate::default_impl::MemoryVariableStorage;
+use crate::error_handling::{handle_error, YarnErrorContext};
 use crate::fmt_utils::SkipDebug;
-use crate::localization::{LineIdUpdateSystemSet, UpdateAllStringsFilesForStringTableEvent};
+use crate::localization::LineIdUpdateSystemSet;
+#[cfg(feature = "development_file_generation")]
+use crate::localization::{write_missing_strings_files, UpdateAllStringsFilesForStringTableEvent};
 use crate::plugin::AssetRoot;
 use crate::prelude::*;
 use crate::project::{CompilationSystemSet, LoadYarnProjectEvent, WatchingForChanges};
 use anyhow::bail;
+use bevy::asset::LoadState;
 use bevy::prelude::*;
+use bevy::tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task};
 use bevy::utils::{error, HashSet};
 use std::fmt::Debug;
+use yarnspinner::compiler::{Diagnostic, File as YarnCompilerFile};
 
 pub(crate) fn project_compilation_plugin(app: &mut App) {
     app.register_type::<YarnFilesToLoad>()
         .init_resource::<YarnFilesToLoad>()
         .init_resource::<YarnFilesBeingLoaded>()
+        .init_resource::<PrecompiledProgramBeingLoaded>()
         .add_event::<RecompileLoadedYarnFilesEvent>()
+        .add_event::<YarnProjectCompiledEvent>()
         .add_systems(
             Update,
             (
-                load_project.pipe(panic_on_err),
+                load_project.pipe(handle_error(YarnErrorContext::ProjectLoading)),
                 add_yarn_files_to_load_queue
-                    .pipe(panic_on_err)
+                    .pipe(handle_error(YarnErrorContext::ProjectLoading))
                     .run_if(resource_exists_and_changed::<YarnFilesToLoad>),
                 compile_loaded_yarn_files
-                    .pipe(panic_on_err)
-                    .run_if(resource_exists::<YarnFilesToLoad>),
+                    .pipe(handle_error(YarnErrorContext::ProjectLoading))
+                    .run_if(resource_exists::<YarnFilesToLoad>)
+                    .run_if(not(resource_exists::<YarnProjectLoading>)),
+                finish_compiling_loaded_yarn_files
+                    .pipe(handle_error(YarnErrorContext::ProjectLoading))
+                    .run_if(resource_exists::<YarnProjectLoading>),
+                load_precompiled_program
+                    .pipe(handle_error(YarnErrorContext::ProjectLoading))
+                    .run_if(resource_exists::<YarnProjectConfigToLoad>),
                 recompile_loaded_yarn_files
                     .map(error)
                     .run_if(events_in_queue::<RecompileLoadedYarnFilesEvent>()),
@@ -40,6 +56,9 @@ pub(crate) struct YarnProjectConfigToLoad {
     pub(crate) localizations: Option<Option<Localizations>>,
     pub(crate) watching_for_changes: bool,
     pub(crate) development_file_generation: DevelopmentFileGeneration,
+    pub(crate) strict_translations: bool,
+    pub(crate) content_validation: ContentValidation,
+    pub(crate) variable_defaults: Option<Handle<VariableDefaults>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Resource, Reflect)]
@@ -50,16 +69,74 @@ pub(crate) struct YarnFilesToLoad(pub(crate) HashSet<YarnFileSource>);
 #[reflect(Debug, Resource, Default, PartialEq)]
 pub(crate) struct YarnFilesBeingLoaded(pub(crate) HashSet<Handle<YarnFile>>);
 
+/// A [`Resource`] that exists while the Yarn project is being compiled in the background.
+/// Compilation runs on the [`AsyncComputeTaskPool`] so that large projects don't stall the first frames of the game.
+/// Once it is done, this resource is removed, the [`YarnProject`] is inserted and a [`YarnProjectCompiledEvent`] is sent.
+/// Use [`yarn_project_ready`] as a run condition for systems that need the [`YarnProject`].
+#[derive(Debug, Resource)]
+pub struct YarnProjectLoading {
+    task: Task<std::result::Result<Compilation, CompilerError>>,
+    compiled_files: Vec<(Handle<YarnFile>, YarnCompilerFile)>,
+}
+
+/// An event that is sent when the Yarn files of the [`YarnProject`] have been compiled,
+/// both after loading the project and after recompiling it because of changes in the Yarn files.
+/// The first time this is sent, the [`YarnProject`] is available in the same frame.
+#[derive(Debug, Clone, PartialEq, Event)]
+pub struct YarnProjectCompiledEvent {
+    /// The warnings the compiler reported. Errors cause the compilation to fail instead.
+    pub diagnostics: Vec<Diagnostic>,
+    /// The entity holding the compiled project if it was loaded with an [`AdditionalYarnProject`],
+    /// or [`None`] for the project that is inserted as a [`Resource`].
+    pub project: Option<Entity>,
+}
+
+/// A run condition that is true as soon as the [`YarnProject`] is available, i.e. when it is no longer [`YarnProjectLoading`].
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::prelude::*;
+/// # let mut app = App::new();
+/// app.add_systems(Update, update_dialogue.run_if(yarn_project_ready));
+///
+/// fn update_dialogue(project: Res<YarnProject>) {
+///     // ...
+/// }
+/// ```
+pub fn yarn_project_ready(yarn_project: Option<Res<YarnProject>>) -> bool {
+    yarn_project.is_some()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Resource)]
+pub(crate) struct PrecompiledProgramBeingLoaded(pub(crate) Option<Handle<YarnProgram>>);
+
 fn load_project(
     mut commands: Commands,
     mut events: ResMut<Events<LoadYarnProjectEvent>>,
     is_watching_for_changes: Res<WatchingForChanges>,
+    mut precompiled_program_being_loaded: ResMut<PrecompiledProgramBeingLoaded>,
+    asset_server: Res<AssetServer>,
     mut already_loaded: Local<bool>,
 ) -> SystemResult {
     for event in events.drain() {
         if *already_loaded {
             bail!("Yarn project already loaded. Sending multiple LoadYarnProjectEvent is not allowed.");
         }
+        if let Some(path) = event.precompiled_program {
+            if !event.yarn_files.is_empty() {
+                bail!("Failed to load Yarn project: a precompiled program cannot be combined with Yarn files.");
+            }
+            commands.insert_resource(YarnProjectConfigToLoad {
+                localizations: Some(event.localizations),
+                watching_for_changes: is_watching_for_changes.0,
+                development_file_generation: DevelopmentFileGeneration::None,
+                strict_translations: event.strict_translations,
+                content_validation: event.content_validation,
+                variable_defaults: event.variable_defaults.map(|path| asset_server.load(path)),
+            });
+            precompiled_program_being_loaded.0 = Some(asset_server.load(path));
+            *already_loaded = true;
+            continue;
+        }
         assert!(!event.yarn_files.is_empty(),
             "Failed to load Yarn project in deferred mode: no Yarn files were specified. \
             Did run `LoadYarnProjectEvent::empty()` without adding any Yarn files with `LoadYarnProjectEvent::add_yarn_file` and `LoadYarnProjectEvent::add_yarn_files`? \
@@ -75,6 +152,9 @@ fn load_project(
             localizations: Some(event.localizations),
             watching_for_changes: is_watching_for_changes.0,
             development_file_generation: event.development_file_generation,
+            strict_translations: event.strict_translations,
+            content_validation: event.content_validation,
+            variable_defaults: event.variable_defaults.map(|path| asset_server.load(path)),
         });
         commands.insert_resource(YarnFilesToLoad(event.yarn_files));
         *already_loaded = true;
@@ -113,11 +193,14 @@ fn recompile_loaded_yarn_files(
     yarn_project: Option<ResMut<YarnProject>>,
     mut dialogue_runners: Query<&mut DialogueRunner>,
     mut events: ResMut<Events<RecompileLoadedYarnFilesEvent>>,
+    mut compiled_events: EventWriter<YarnProjectCompiledEvent>,
+    variable_defaults: Res<Assets<VariableDefaults>>,
+    #[cfg(feature = "development_file_generation")] asset_root: Res<AssetRoot>,
 ) -> SystemResult {
     let Some(mut yarn_project) = yarn_project else {
         return Ok(());
     };
-    let Some(compilation) = compile_yarn_files(
+    let Some(mut compilation) = compile_yarn_files(
         &yarn_project.yarn_files,
         &yarn_files,
         yarn_project.localizations.as_ref(),
@@ -126,30 +209,58 @@ fn recompile_loaded_yarn_files(
     else {
         return Ok(());
     };
+    apply_variable_defaults(
+        yarn_project.variable_defaults.as_ref(),
+        &variable_defaults,
+        &mut compilation,
+    );
     let metadata = compilation
         .string_table
         .iter()
         .map(|(line_id, string_info)| (line_id.clone(), string_info.metadata.clone()))
         .collect();
+    compiled_events.send(YarnProjectCompiledEvent {
+        diagnostics: compilation.warnings.clone(),
+        project: None,
+    });
+    #[cfg(feature = "development_file_generation")]
+    if yarn_project.development_file_generation == DevelopmentFileGeneration::Full {
+        if let Some(localizations) = yarn_project.localizations.as_ref() {
+            // Strings files may have been deleted while developing
+            write_missing_strings_files(localizations, &compilation.string_table, &asset_root.0)?;
+        }
+    }
     yarn_project.compilation = compilation;
     yarn_project.metadata = metadata;
     let program = yarn_project.compilation.program.clone().unwrap();
-    for mut dialogue_runner in dialogue_runners.iter_mut() {
+    let project_id = yarn_project.id;
+    for mut dialogue_runner in dialogue_runners
+        .iter_mut()
+        .filter(|dialogue_runner| dialogue_runner.project_id == project_id)
+    {
         let current_node = dialogue_runner.current_node();
-        dialogue_runner.dialogue.replace_program(program.clone());
+        dialogue_runner
+            .dialogue
+            .replace_program(program.clone())
+            .add_debug_info(yarn_project.compilation.debug_info.clone());
         dialogue_runner
             .text_provider
             .set_base_string_table(yarn_project.compilation.string_table.clone());
-        if let Some(current_node) = current_node {
-            dialogue_runner
-                .stop()
-                .try_start_node(current_node)
-                .map(|_| ())
-                .ok()
-                .unwrap_or_else(|| {
-                    dialogue_runner.start_node("Start");
-                });
+        let Some(current_node) = current_node else {
+            continue;
+        };
+        if dialogue_runner.current_node().as_ref() == Some(&current_node) {
+            // The running node did not change, so the dialogue keeps its position and continues with the new lines.
+            continue;
         }
+        dialogue_runner
+            .stop()
+            .try_start_node(current_node)
+            .map(|_| ())
+            .ok()
+            .unwrap_or_else(|| {
+                dialogue_runner.start_node("Start");
+            });
     }
     events.clear();
     info!("Successfully recompiled Yarn project because of changes in Yarn files.");
@@ -158,13 +269,10 @@ fn recompile_loaded_yarn_files(
 
 fn compile_loaded_yarn_files(
     mut commands: Commands,
-    mut yarn_files_being_loaded: ResMut<YarnFilesBeingLoaded>,
+    yarn_files_being_loaded: Res<YarnFilesBeingLoaded>,
     yarn_files: Res<Assets<YarnFile>>,
-    mut update_strings_files_writer: EventWriter<UpdateAllStringsFilesForStringTableEvent>,
     mut dirty: Local<bool>,
     yarn_project_config_to_load: Option<Res<YarnProjectConfigToLoad>>,
-    asset_server: Res<AssetServer>,
-    asset_root: Res<AssetRoot>,
 ) -> SystemResult {
     if yarn_files_being_loaded.is_changed() {
         *dirty = true;
@@ -189,43 +297,88 @@ fn compile_loaded_yarn_files(
         .as_ref()
         .unwrap()
         .as_ref();
-    let development_file_generation = yarn_project_config_to_load.development_file_generation;
-    let Some(compilation) = compile_yarn_files(
+    if !line_ids_are_ready(
         &yarn_files_being_loaded.0,
         &yarn_files,
         localizations,
-        development_file_generation,
-    )?
-    else {
+        yarn_project_config_to_load.development_file_generation,
+    )? {
+        return Ok(());
+    }
+    let compiled_files: Vec<_> = yarn_files_being_loaded
+        .0
+        .iter()
+        .map(|handle| (handle.clone(), yarn_files.get(handle).unwrap().file.clone()))
+        .collect();
+    let inner_yarn_files: Vec<_> = compiled_files
+        .iter()
+        .map(|(_, file)| file.clone())
+        .collect();
+    let task = AsyncComputeTaskPool::get()
+        .spawn(async move { YarnCompiler::new().add_files(inner_yarn_files).compile() });
+    commands.insert_resource(YarnProjectLoading {
+        task,
+        compiled_files,
+    });
+
+    *dirty = false;
+    Ok(())
+}
+
+fn finish_compiling_loaded_yarn_files(
+    mut commands: Commands,
+    mut yarn_project_loading: ResMut<YarnProjectLoading>,
+    mut yarn_files_being_loaded: ResMut<YarnFilesBeingLoaded>,
+    yarn_files: Res<Assets<YarnFile>>,
+    #[cfg(feature = "development_file_generation")] mut update_strings_files_writer: EventWriter<
+        UpdateAllStringsFilesForStringTableEvent,
+    >,
+    mut compiled_events: EventWriter<YarnProjectCompiledEvent>,
+    yarn_project_config_to_load: Res<YarnProjectConfigToLoad>,
+    variable_defaults: Res<Assets<VariableDefaults>>,
+    asset_server: Res<AssetServer>,
+    #[cfg(feature = "development_file_generation")] asset_root: Res<AssetRoot>,
+) -> SystemResult {
+    if !variable_defaults_loaded(
+        yarn_project_config_to_load.variable_defaults.as_ref(),
+        &variable_defaults,
+        &asset_server,
+    )? {
+        return Ok(());
+    }
+    let Some(compilation) = block_on(future::poll_once(&mut yarn_project_loading.task)) else {
         return Ok(());
     };
-    let file_count = yarn_files_being_loaded.0.len();
+    commands.remove_resource::<YarnProjectLoading>();
+    let files_changed_while_compiling =
+        yarn_project_loading
+            .compiled_files
+            .iter()
+            .any(|(handle, file)| {
+                yarn_files.get(handle).map(|yarn_file| &yarn_file.file) != Some(file)
+            });
+    if files_changed_while_compiling {
+        // E.g. because line IDs were generated in the meantime. Marking the files as changed compiles them again.
+        yarn_files_being_loaded.set_changed();
+        return Ok(());
+    }
+    let mut compilation = compilation?;
+    apply_variable_defaults(
+        yarn_project_config_to_load.variable_defaults.as_ref(),
+        &variable_defaults,
+        &mut compilation,
+    );
+    let yarn_files = std::mem::take(&mut yarn_files_being_loaded.0);
+    let file_count = yarn_files.len();
+    let development_file_generation = yarn_project_config_to_load.development_file_generation;
 
+    #[cfg(feature = "development_file_generation")]
     if development_file_generation == DevelopmentFileGeneration::Full {
         if let Some(localizations) = yarn_project_config_to_load.localizations.as_ref().unwrap() {
             update_strings_files_writer.send(UpdateAllStringsFilesForStringTableEvent(
                 compilation.string_table.clone(),
             ));
-            for localization in &localizations.translations {
-                let path = localization.strings_file.as_path();
-                let path = asset_root.0.join(path);
-
-                if path.is_file() {
-                    continue;
-                }
-                let strings_file = StringsFile::from_string_table(
-                    localization.language.clone(),
-                    compilation.string_table.clone(),
-                )
-                .unwrap_or_default();
-
-                strings_file.write_asset(&path)?;
-                info!(
-                    "Generated \"{}\" (lang: {}).",
-                    path.display(),
-                    localization.language
-                );
-            }
+            write_missing_strings_files(localizations, &compilation.string_table, &asset_root.0)?;
         }
     }
 
@@ -234,20 +387,89 @@ fn compile_loaded_yarn_files(
         .iter()
         .map(|(line_id, string_info)| (line_id.clone(), string_info.metadata.clone()))
         .collect();
+    compiled_events.send(YarnProjectCompiledEvent {
+        diagnostics: compilation.warnings.clone(),
+        project: None,
+    });
     commands.insert_resource(YarnProject {
-        yarn_files: std::mem::take(&mut yarn_files_being_loaded.0),
+        id: YarnProjectId::new_unique(),
+        yarn_files,
         compilation,
         localizations: yarn_project_config_to_load.localizations.clone().unwrap(),
         asset_server: SkipDebug(asset_server.clone()),
         watching_for_changes: yarn_project_config_to_load.watching_for_changes,
         development_file_generation,
         metadata,
+        strict_translations: yarn_project_config_to_load.strict_translations,
+        content_validation: yarn_project_config_to_load.content_validation,
+        text_language: None,
+        asset_language: None,
+        pending_language_change: None,
+        variable_storage: Box::new(MemoryVariableStorage::new()),
+        variable_defaults: yarn_project_config_to_load.variable_defaults.clone(),
+        added_files: default(),
     });
 
     let file_plural = if file_count == 1 { "file" } else { "files" };
     info!("Successfully compiled {file_count} Yarn {file_plural}");
+    Ok(())
+}
 
-    *dirty = false;
+fn load_precompiled_program(
+    mut commands: Commands,
+    mut precompiled_program_being_loaded: ResMut<PrecompiledProgramBeingLoaded>,
+    yarn_programs: Res<Assets<YarnProgram>>,
+    yarn_project_config_to_load: Res<YarnProjectConfigToLoad>,
+    variable_defaults: Res<Assets<VariableDefaults>>,
+    asset_server: Res<AssetServer>,
+) -> SystemResult {
+    let Some(handle) = precompiled_program_being_loaded.0.as_ref() else {
+        return Ok(());
+    };
+    if let Some(LoadState::Failed(error)) = asset_server.get_load_state(handle) {
+        bail!("Failed to load precompiled Yarn program: {error}");
+    }
+    let Some(yarn_program) = yarn_programs.get(handle) else {
+        return Ok(());
+    };
+    if !variable_defaults_loaded(
+        yarn_project_config_to_load.variable_defaults.as_ref(),
+        &variable_defaults,
+        &asset_server,
+    )? {
+        return Ok(());
+    }
+    let mut compilation = yarn_program.to_compilation();
+    apply_variable_defaults(
+        yarn_project_config_to_load.variable_defaults.as_ref(),
+        &variable_defaults,
+        &mut compilation,
+    );
+    let metadata = compilation
+        .string_table
+        .iter()
+        .map(|(line_id, string_info)| (line_id.clone(), string_info.metadata.clone()))
+        .collect();
+    commands.insert_resource(YarnProject {
+        id: YarnProjectId::new_unique(),
+        yarn_files: default(),
+        compilation,
+        localizations: yarn_project_config_to_load.localizations.clone().unwrap(),
+        asset_server: SkipDebug(asset_server.clone()),
+        watching_for_changes: yarn_project_config_to_load.watching_for_changes,
+        development_file_generation: yarn_project_config_to_load.development_file_generation,
+        metadata,
+        strict_translations: yarn_project_config_to_load.strict_translations,
+        content_validation: yarn_project_config_to_load.content_validation,
+        text_language: None,
+        asset_language: None,
+        pending_language_change: None,
+        variable_storage: Box::new(MemoryVariableStorage::new()),
+        variable_defaults: yarn_project_config_to_load.variable_defaults.clone(),
+        added_files: default(),
+    });
+    precompiled_program_being_loaded.0 = None;
+    info!("Successfully loaded precompiled Yarn program");
     Ok(())
 }
 
@@ -256,34 +478,80 @@ fn clear_temp_yarn_project(mut commands: Commands) {
     commands.remove_resource::<YarnProjectConfigToLoad>();
 }
 
+/// Returns whether the [`VariableDefaults`] of a project, if any, are ready to be applied.
+pub(crate) fn variable_defaults_loaded(
+    handle: Option<&Handle<VariableDefaults>>,
+    variable_defaults: &Assets<VariableDefaults>,
+    asset_server: &AssetServer,
+) -> Result<bool> {
+    let Some(handle) = handle else {
+        return Ok(true);
+    };
+    if let Some(LoadState::Failed(error)) = asset_server.get_load_state(handle) {
+        bail!("Failed to load variable defaults: {error}");
+    }
+    Ok(variable_defaults.contains(handle))
+}
+
+/// Applies the [`VariableDefaults`] of a project, if any, and adds the overrides that were ignored to the warnings of the compilation.
+pub(crate) fn apply_variable_defaults(
+    handle: Option<&Handle<VariableDefaults>>,
+    variable_defaults: &Assets<VariableDefaults>,
+    compilation: &mut Compilation,
+) {
+    let Some(variable_defaults) = handle.and_then(|handle| variable_defaults.get(handle)) else {
+        return;
+    };
+    let warnings = variable_defaults.apply(compilation);
+    compilation.warnings.extend(warnings);
+}
+
 fn compile_yarn_files(
     yarn_file_handles: &HashSet<Handle<YarnFile>>,
-    yarn_files: &Res<Assets<YarnFile>>,
+    yarn_files: &Assets<YarnFile>,
     localizations: Option<&Localizations>,
     development_file_generation: DevelopmentFileGeneration,
 ) -> Result<Option<Compilation>> {
-    let yarn_files = yarn_file_handles
-        .iter()
-        .map(|handle| yarn_files.get(handle).unwrap());
-    if localizations.is_some() {
-        if let Some(untagged_file) = yarn_files
-            .clone()
-            .find(|file| file.string_table.values().any(|v| v.is_implicit_tag))
-        {
-            if development_file_generation == DevelopmentFileGeneration::Full {
-                info!(
-                    "Waiting with compilation until \"{}\" gets its line IDs generated",
-                    untagged_file.file.file_name
-                );
-                return Ok(None);
-            } else {
-                bail!("Failed to compile Yarn files: Localization mode is on, but \"{}\" is not does not have full line IDs. \
-                    Cannot generate the line IDs automatically either because we are not in `DevelopmentFileGeneration::Full`",
-                    untagged_file.file.file_name);
-            }
-        }
+    if !line_ids_are_ready(
+        yarn_file_handles,
+        yarn_files,
+        localizations,
+        development_file_generation,
+    )? {
+        return Ok(None);
     }
-    let inner_yarn_files = yarn_files.map(|file| file.file.clone());
+    let inner_yarn_files = yarn_file_handles
+        .iter()
+        .map(|handle| yarn_files.get(handle).unwrap().file.clone());
     let compilation = YarnCompiler::new().add_files(inner_yarn_files).compile()?;
     Ok(Some(compilation))
 }
+
+pub(crate) fn line_ids_are_ready(
+    yarn_file_handles: &HashSet<Handle<YarnFile>>,
+    yarn_files: &Assets<YarnFile>,
+    localizations: Option<&Localizations>,
+    development_file_generation: DevelopmentFileGeneration,
+) -> Result<bool> {
+    if localizations.is_none() {
+        return Ok(true);
+    }
+    let untagged_file = yarn_file_handles
+        .iter()
+        .map(|handle| yarn_files.get(handle).unwrap())
+        .find(|file| file.string_table.values().any(|v| v.is_implicit_tag));
+    let Some(untagged_file) = untagged_file else {
+        return Ok(true);
+    };
+    if development_file_generation == DevelopmentFileGeneration::Full {
+        info!(
+            "Waiting with compilation until \"{}\" gets its line IDs generated",
+            untagged_file.file.file_name
+        );
+        Ok(false)
+    } else {
+        bail!("Failed to compile Yarn files: Localization mode is on, but \"{}\" is not does not have full line IDs. \
+            Cannot generate the line IDs automatically either because we are not in `DevelopmentFileGeneration::Full`",
+            untagged_file.file.file_name);
+    }
+}
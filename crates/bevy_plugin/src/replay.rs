@@ -0,0 +1,82 @@
+use crate::prelude::*;
+use bevy::asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+
+pub(crate) fn replay_plugin(app: &mut App) {
+    app.init_asset::<DialogueTraceAsset>()
+        .init_asset_loader::<DialogueTraceAssetLoader>();
+}
+
+/// A [`DialogueTrace`] stored as JSON, e.g. one attached to a bug report by QA.
+/// Record one with [`DialogueRunner::start_recording_trace`], load `.yarntrace` files with the [`AssetServer`]
+/// and play them back with [`DialogueRunner::replay_trace`].
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::prelude::*;
+/// fn replay_bug_report(
+///     mut commands: Commands,
+///     project: Res<YarnProject>,
+///     asset_server: Res<AssetServer>,
+///     traces: Res<Assets<DialogueTraceAsset>>,
+/// ) -> Result<(), Box<dyn std::error::Error>> {
+///     let handle = asset_server.load::<DialogueTraceAsset>("bug_reports/1234.yarntrace");
+///     if let Some(trace) = traces.get(&handle) {
+///         let mut dialogue_runner = project.create_dialogue_runner();
+///         dialogue_runner.replay_trace(trace.0.clone())?;
+///         commands.spawn(dialogue_runner);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Asset, TypePath, Deref, DerefMut)]
+pub struct DialogueTraceAsset(pub DialogueTrace);
+
+impl DialogueTraceAsset {
+    /// The file extension of traces.
+    pub const EXTENSION: &'static str = "yarntrace";
+
+    /// Serializes the trace to JSON.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(&self.0).context("Failed to serialize dialogue trace")
+    }
+
+    /// Deserializes a trace from JSON written by [`DialogueTraceAsset::to_json`].
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map(Self)
+            .context("Failed to deserialize dialogue trace")
+    }
+}
+
+impl From<DialogueTrace> for DialogueTraceAsset {
+    fn from(trace: DialogueTrace) -> Self {
+        Self(trace)
+    }
+}
+
+#[derive(Debug, Default)]
+struct DialogueTraceAssetLoader;
+
+impl AssetLoader for DialogueTraceAssetLoader {
+    type Asset = DialogueTraceAsset;
+    type Settings = ();
+    type Error = anyhow::Error;
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut json = String::new();
+        reader.read_to_string(&mut json).await?;
+        DialogueTraceAsset::from_json(&json)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[DialogueTraceAsset::EXTENSION]
+    }
+}
@@ -0,0 +1,389 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::events::*;
+use crate::prelude::*;
+use crate::UnderlyingYarnCommand;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+pub(crate) fn replication_plugin(app: &mut App) {
+    app.add_event::<DialogueReplicationEvent>()
+        .add_event::<RemoteOptionRequestEvent>()
+        .register_type::<ReplicateDialogue>()
+        .add_systems(
+            Update,
+            (
+                present_remote_events.in_set(DialogueExecutionSystemSet),
+                send_replication_events.after(DialogueExecutionSystemSet),
+            )
+                .in_set(YarnSpinnerSystemSet),
+        );
+}
+
+/// Marks a [`DialogueRunner`] whose events should be replicated to other clients.
+/// For every dialogue event of the runner, a [`DialogueReplicationEvent`] is sent that your networking code can forward
+/// to the clients, which pass it on to a presenter-only runner with [`DialogueRunner::apply_remote_event`].
+/// See [`DialogueRunnerBuilder::presenter_only`].
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::{events::*, prelude::*};
+/// fn spawn_host_dialogue_runner(mut commands: Commands, project: Res<YarnProject>) {
+///     commands.spawn((project.create_dialogue_runner(), ReplicateDialogue));
+/// }
+///
+/// fn broadcast(mut events: EventReader<DialogueReplicationEvent>) {
+///     for event in events.read() {
+///         let message = event.event.to_json().unwrap();
+///         // Send `message` to the other clients
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Component, Reflect)]
+#[reflect(Component, Debug, PartialEq, Default)]
+pub struct ReplicateDialogue;
+
+/// Sent for every dialogue event of a [`DialogueRunner`] marked with [`ReplicateDialogue`], after [`DialogueExecutionSystemSet`].
+/// Within an update, the events are sent in the order a dialogue produces them: start, selected option, completed and started nodes,
+/// variable changes, commands, the presented line or options and finally completion.
+#[derive(Debug, Clone, PartialEq, Event)]
+pub struct DialogueReplicationEvent {
+    /// The entity-agnostic event to send to other clients.
+    pub event: ReplicatedDialogueEvent,
+    /// The [`DialogueRunner`] that produced the event.
+    pub source: Entity,
+}
+
+/// Sent when a dialogue view calls [`DialogueRunner::select_option`] on a presenter-only runner.
+/// The option is not selected locally. Forward the request to the client running the dialogue if other players may make choices,
+/// or ignore it if only the host decides.
+#[derive(Debug, Clone, PartialEq, Eq, Event)]
+pub struct RemoteOptionRequestEvent {
+    /// The requested option.
+    pub option: OptionId,
+    /// The presenter-only [`DialogueRunner`] the option was requested on.
+    pub source: Entity,
+}
+
+/// A serializable version of the dialogue events without any [`Entity`], to be sent over the network.
+/// Line hints, preloading and missing translations are local concerns and are not replicated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReplicatedDialogueEvent {
+    /// See [`DialogueStartEvent`].
+    DialogueStart,
+    /// See [`NodeStartEvent`].
+    NodeStart {
+        /// The name of the started node.
+        node_name: String,
+    },
+    /// See [`PresentLineEvent`].
+    PresentLine {
+        /// The presented line.
+        line: ReplicatedLine,
+    },
+    /// See [`PresentOptionsEvent`].
+    PresentOptions {
+        /// The presented options.
+        options: Vec<ReplicatedOption>,
+    },
+    /// See [`OptionSelectedEvent`].
+    OptionSelected {
+        /// The selected option.
+        option: ReplicatedOption,
+    },
+    /// See [`ExecuteCommandEvent`].
+    ExecuteCommand {
+        /// The executed command.
+        command: UnderlyingYarnCommand,
+    },
+    /// See [`VariableChangedEvent`].
+    VariableChanged {
+        /// The name of the variable, including the leading `$`.
+        name: String,
+        /// The value the variable was set to.
+        new_value: YarnValue,
+    },
+    /// See [`NodeCompleteEvent`].
+    NodeComplete {
+        /// The name of the completed node.
+        node_name: String,
+    },
+    /// See [`DialogueCompleteEvent`].
+    DialogueComplete,
+}
+
+impl ReplicatedDialogueEvent {
+    /// Serializes the event to JSON.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).context("Failed to serialize replicated dialogue event")
+    }
+
+    /// Deserializes an event from JSON written by [`ReplicatedDialogueEvent::to_json`].
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).context("Failed to deserialize replicated dialogue event")
+    }
+}
+
+/// A [`LocalizedLine`] without its [`LineAssets`], which are looked up again by the receiving runner's [`AssetProvider`]s.
+/// The text is in the language of the sending runner.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplicatedLine {
+    /// See [`LocalizedLine::id`].
+    pub id: LineId,
+    /// See [`LocalizedLine::text`].
+    pub text: String,
+    /// See [`LocalizedLine::attributes`].
+    pub attributes: Vec<MarkupAttribute>,
+    /// See [`LocalizedLine::metadata`].
+    pub metadata: Vec<String>,
+}
+
+impl From<&LocalizedLine> for ReplicatedLine {
+    fn from(line: &LocalizedLine) -> Self {
+        Self {
+            id: line.id.clone(),
+            text: line.text.clone(),
+            attributes: line.attributes.clone(),
+            metadata: line.metadata.clone(),
+        }
+    }
+}
+
+/// A [`DialogueOption`] without the [`LineAssets`] of its line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplicatedOption {
+    /// See [`DialogueOption::line`].
+    pub line: ReplicatedLine,
+    /// See [`DialogueOption::id`].
+    pub id: OptionId,
+    /// See [`DialogueOption::destination_node`].
+    pub destination_node: String,
+    /// See [`DialogueOption::is_available`].
+    pub is_available: bool,
+}
+
+impl From<&DialogueOption> for ReplicatedOption {
+    fn from(option: &DialogueOption) -> Self {
+        Self {
+            line: (&option.line).into(),
+            id: option.id,
+            destination_node: option.destination_node.clone(),
+            is_available: option.is_available,
+        }
+    }
+}
+
+/// The state of a presenter-only [`DialogueRunner`], which mirrors a remote runner instead of running the dialogue itself.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct RemotePresenter {
+    pub(crate) pending_events: VecDeque<ReplicatedDialogueEvent>,
+    pub(crate) current_node: Option<String>,
+    pub(crate) is_waiting_for_option_selection: bool,
+    pub(crate) requested_option: Option<OptionId>,
+}
+
+impl DialogueRunner {
+    fn localize_remote_line(&self, line: ReplicatedLine) -> LocalizedLine {
+        let yarn_line = crate::UnderlyingYarnLine {
+            id: line.id,
+            text: line.text,
+            attributes: line.attributes,
+        };
+        let assets = self.get_assets(&yarn_line);
+        LocalizedLine::from_line(yarn_line, line.metadata, assets)
+    }
+
+    fn localize_remote_option(&self, option: ReplicatedOption) -> DialogueOption {
+        DialogueOption {
+            line: self.localize_remote_line(option.line),
+            id: option.id,
+            destination_node: option.destination_node,
+            is_available: option.is_available,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn present_remote_events(
+    mut dialogue_runners: Query<(Entity, &mut DialogueRunner)>,
+    mut dialogue_start_events: EventWriter<DialogueStartEvent>,
+    mut node_start_events: EventWriter<NodeStartEvent>,
+    mut present_line_events: EventWriter<PresentLineEvent>,
+    mut present_options_events: EventWriter<PresentOptionsEvent>,
+    mut option_selected_events: EventWriter<OptionSelectedEvent>,
+    mut execute_command_events: EventWriter<ExecuteCommandEvent>,
+    mut variable_changed_events: EventWriter<VariableChangedEvent>,
+    mut node_complete_events: EventWriter<NodeCompleteEvent>,
+    mut dialogue_complete_events: EventWriter<DialogueCompleteEvent>,
+    mut option_request_events: EventWriter<RemoteOptionRequestEvent>,
+) {
+    for (source, mut dialogue_runner) in dialogue_runners.iter_mut() {
+        let dialogue_runner = dialogue_runner.as_mut();
+        let Some(presenter) = dialogue_runner.presenter.as_mut() else {
+            continue;
+        };
+        if let Some(option) = presenter.requested_option.take() {
+            option_request_events.send(RemoteOptionRequestEvent { option, source });
+        }
+        dialogue_runner.will_continue_in_next_update = false;
+        if dialogue_runner.is_paused() {
+            continue;
+        }
+        // Present at most one line or set of options per update so that dialogue views see each of them
+        while let Some(event) = dialogue_runner
+            .presenter
+            .as_mut()
+            .and_then(|presenter| presenter.pending_events.pop_front())
+        {
+            let presenter = dialogue_runner.presenter.as_mut().unwrap();
+            match event {
+                ReplicatedDialogueEvent::DialogueStart => {
+                    dialogue_runner.is_running = true;
+                    dialogue_start_events.send(DialogueStartEvent { source });
+                }
+                ReplicatedDialogueEvent::NodeStart { node_name } => {
+                    presenter.current_node = Some(node_name.clone());
+                    node_start_events.send(NodeStartEvent { node_name, source });
+                }
+                ReplicatedDialogueEvent::PresentLine { line } => {
+                    presenter.is_waiting_for_option_selection = false;
+                    let line = dialogue_runner.localize_remote_line(line);
+                    present_line_events.send(PresentLineEvent { line, source });
+                    break;
+                }
+                ReplicatedDialogueEvent::PresentOptions { options } => {
+                    presenter.is_waiting_for_option_selection = true;
+                    let options = options
+                        .into_iter()
+                        .map(|option| dialogue_runner.localize_remote_option(option))
+                        .collect();
+                    present_options_events.send(PresentOptionsEvent { options, source });
+                    break;
+                }
+                ReplicatedDialogueEvent::OptionSelected { option } => {
+                    presenter.is_waiting_for_option_selection = false;
+                    let option = dialogue_runner.localize_remote_option(option);
+                    option_selected_events.send(OptionSelectedEvent { option, source });
+                }
+                ReplicatedDialogueEvent::ExecuteCommand { command } => {
+                    execute_command_events.send(ExecuteCommandEvent { command, source });
+                }
+                ReplicatedDialogueEvent::VariableChanged { name, new_value } => {
+                    let old_value = dialogue_runner.variable_storage().get(&name).ok();
+                    if let Err(error) = dialogue_runner
+                        .variable_storage_mut()
+                        .set(name.clone(), new_value.clone())
+                    {
+                        warn!("Failed to mirror remote variable {name}: {error}");
+                    }
+                    variable_changed_events.send(VariableChangedEvent {
+                        name,
+                        old_value,
+                        new_value,
+                        source,
+                    });
+                }
+                ReplicatedDialogueEvent::NodeComplete { node_name } => {
+                    presenter.current_node = None;
+                    node_complete_events.send(NodeCompleteEvent { node_name, source });
+                }
+                ReplicatedDialogueEvent::DialogueComplete => {
+                    presenter.current_node = None;
+                    presenter.is_waiting_for_option_selection = false;
+                    presenter.requested_option = None;
+                    dialogue_runner.is_running = false;
+                    dialogue_complete_events.send(DialogueCompleteEvent { source });
+                }
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn send_replication_events(
+    mut dialogue_start_events: EventReader<DialogueStartEvent>,
+    mut option_selected_events: EventReader<OptionSelectedEvent>,
+    mut node_complete_events: EventReader<NodeCompleteEvent>,
+    mut node_start_events: EventReader<NodeStartEvent>,
+    mut variable_changed_events: EventReader<VariableChangedEvent>,
+    mut execute_command_events: EventReader<ExecuteCommandEvent>,
+    mut present_line_events: EventReader<PresentLineEvent>,
+    mut present_options_events: EventReader<PresentOptionsEvent>,
+    mut dialogue_complete_events: EventReader<DialogueCompleteEvent>,
+    replicated: Query<(), (With<ReplicateDialogue>, With<DialogueRunner>)>,
+    mut replication_events: EventWriter<DialogueReplicationEvent>,
+) {
+    // Chained in the order a dialogue produces them within an update, so that presenters replay them in the same order
+    let events = dialogue_start_events
+        .read()
+        .map(|event| (event.source, ReplicatedDialogueEvent::DialogueStart))
+        .chain(option_selected_events.read().map(|event| {
+            (
+                event.source,
+                ReplicatedDialogueEvent::OptionSelected {
+                    option: (&event.option).into(),
+                },
+            )
+        }))
+        .chain(node_complete_events.read().map(|event| {
+            (
+                event.source,
+                ReplicatedDialogueEvent::NodeComplete {
+                    node_name: event.node_name.clone(),
+                },
+            )
+        }))
+        .chain(node_start_events.read().map(|event| {
+            (
+                event.source,
+                ReplicatedDialogueEvent::NodeStart {
+                    node_name: event.node_name.clone(),
+                },
+            )
+        }))
+        .chain(variable_changed_events.read().map(|event| {
+            (
+                event.source,
+                ReplicatedDialogueEvent::VariableChanged {
+                    name: event.name.clone(),
+                    new_value: event.new_value.clone(),
+                },
+            )
+        }))
+        .chain(execute_command_events.read().map(|event| {
+            (
+                event.source,
+                ReplicatedDialogueEvent::ExecuteCommand {
+                    command: event.command.clone(),
+                },
+            )
+        }))
+        .chain(present_line_events.read().map(|event| {
+            (
+                event.source,
+                ReplicatedDialogueEvent::PresentLine {
+                    line: (&event.line).into(),
+                },
+            )
+        }))
+        .chain(present_options_events.read().map(|event| {
+            (
+                event.source,
+                ReplicatedDialogueEvent::PresentOptions {
+                    options: event.options.iter().map(ReplicatedOption::from).collect(),
+                },
+            )
+        }))
+        .chain(
+            dialogue_complete_events
+                .read()
+                .map(|event| (event.source, ReplicatedDialogueEvent::DialogueComplete)),
+        );
+    for (source, event) in events {
+        if replicated.contains(source) {
+            replication_events.send(DialogueReplicationEvent { event, source });
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::prelude::*;
+use bevy::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+pub(crate) fn scene_transition_plugin(app: &mut App) {
+    app.init_resource::<SceneTransitions>()
+        .add_event::<LoadSceneEvent>()
+        .add_event::<SceneLoadedEvent>()
+        .add_systems(
+            Update,
+            (start_scene_transitions, finish_scene_transitions)
+                .chain()
+                .after(DialogueExecutionSystemSet)
+                .in_set(YarnSpinnerSystemSet),
+        );
+}
+
+/// Sent when a Yarn script calls `<<load_scene scene_name>>` or `<<load_scene scene_name end>>`, which is registered by the
+/// [`YarnSpinnerStandardCommandsPlugin`]. Loading the scene is up to the game, e.g. by despawning the entities of the old level
+/// and spawning the new one, or by switching the game state.
+///
+/// The [`DialogueRunner`] that called the command is paused until the game sends a [`SceneLoadedEvent`] with the same [`LoadSceneEvent::source`].
+/// After that, it continues right after the command or, if [`LoadSceneEvent::ends_dialogue`] is `true`, stops.
+/// Since all dialogue state lives in the [`DialogueRunner`], it is preserved as long as its entity is not despawned while the scene is swapped.
+/// Keep it out of the entities that belong to a scene, e.g. by spawning it without a parent.
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::{events::*, prelude::*};
+/// #[derive(Component)]
+/// struct Level;
+///
+/// fn load_scenes(
+///     mut commands: Commands,
+///     mut load_scene_events: EventReader<LoadSceneEvent>,
+///     mut scene_loaded_events: EventWriter<SceneLoadedEvent>,
+///     levels: Query<Entity, With<Level>>,
+/// ) {
+///     for event in load_scene_events.read() {
+///         for level in levels.iter() {
+///             commands.entity(level).despawn_recursive();
+///         }
+///         commands.spawn((Name::new(event.scene.clone()), Level));
+///         // Send this later instead if the level takes a while to load
+///         scene_loaded_events.send(SceneLoadedEvent { source: event.source });
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Event)]
+pub struct LoadSceneEvent {
+    /// The name of the scene passed to the command.
+    pub scene: String,
+    /// Whether the dialogue stops once the scene is loaded, i.e. whether the command was called as `<<load_scene scene_name end>>`.
+    pub ends_dialogue: bool,
+    /// The [`DialogueRunner`] that called the command.
+    pub source: Entity,
+}
+
+/// Send this event when the scene requested by a [`LoadSceneEvent`] finished loading, so that its [`DialogueRunner`] continues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Event)]
+pub struct SceneLoadedEvent {
+    /// The [`DialogueRunner`] that requested the scene, see [`LoadSceneEvent::source`].
+    pub source: Entity,
+}
+
+/// A [`Resource`] containing the scene transitions started by `<<load_scene>>` that have not finished yet. See [`LoadSceneEvent`].
+/// Useful for e.g. showing a loading screen while [`SceneTransitions::is_empty`] is `false`.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct SceneTransitions(Vec<SceneTransition>);
+
+/// A scene transition in progress, see [`SceneTransitions`].
+#[derive(Debug, Clone)]
+pub struct SceneTransition {
+    scene: String,
+    ends_dialogue: bool,
+    source: Option<Entity>,
+    started: bool,
+    paused_dialogue_runner: bool,
+    done: Arc<AtomicBool>,
+}
+
+impl SceneTransitions {
+    /// Returns the transition requested by the given [`DialogueRunner`], if any.
+    #[must_use]
+    pub fn get(&self, dialogue_runner: Entity) -> Option<&SceneTransition> {
+        self.iter()
+            .find(|transition| transition.source == Some(dialogue_runner))
+    }
+
+    /// Returns `true` if the given [`DialogueRunner`] is waiting for a scene to load.
+    #[must_use]
+    pub fn is_transitioning(&self, dialogue_runner: Entity) -> bool {
+        self.get(dialogue_runner).is_some()
+    }
+
+    /// Iterates over all transitions in progress.
+    pub fn iter(&self) -> impl Iterator<Item = &SceneTransition> {
+        self.0.iter().filter(|transition| transition.started)
+    }
+
+    /// Returns `true` if no transition is in progress.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    pub(crate) fn add(&mut self, scene: String, ends_dialogue: bool) -> Arc<AtomicBool> {
+        let done = Arc::new(AtomicBool::new(false));
+        self.0.push(SceneTransition {
+            scene,
+            ends_dialogue,
+            source: None,
+            started: false,
+            paused_dialogue_runner: false,
+            done: done.clone(),
+        });
+        done
+    }
+
+    /// Returns the number of transitions, used to find the transitions added by a command.
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Assigns all transitions after the first `start` ones to the given [`DialogueRunner`].
+    pub(crate) fn set_source_of_transitions_since(&mut self, start: usize, source: Entity) {
+        for transition in self.0.iter_mut().skip(start) {
+            transition.source.get_or_insert(source);
+        }
+    }
+}
+
+impl SceneTransition {
+    /// The name of the scene being loaded.
+    #[must_use]
+    pub fn scene(&self) -> &str {
+        &self.scene
+    }
+
+    /// Whether the dialogue stops once the scene is loaded, see [`LoadSceneEvent::ends_dialogue`].
+    #[must_use]
+    pub fn ends_dialogue(&self) -> bool {
+        self.ends_dialogue
+    }
+
+    /// The [`DialogueRunner`] waiting for the scene.
+    #[must_use]
+    pub fn source(&self) -> Entity {
+        self.source
+            .expect("Only transitions of known dialogue runners are exposed")
+    }
+}
+
+/// The `load_scene` command registered by the [`YarnSpinnerStandardCommandsPlugin`].
+pub(crate) fn load_scene(
+    In((scene, mode)): In<(String, Option<String>)>,
+    mut transitions: ResMut<SceneTransitions>,
+) -> Arc<AtomicBool> {
+    let ends_dialogue = match mode.as_deref() {
+        None => false,
+        Some("end") => true,
+        Some(mode) => {
+            warn!("Unknown mode \"{mode}\" in <<load_scene {scene} {mode}>>. The only supported mode is \"end\". Continuing the dialogue after loading instead.");
+            false
+        }
+    };
+    transitions.add(scene, ends_dialogue)
+}
+
+fn start_scene_transitions(
+    mut transitions: ResMut<SceneTransitions>,
+    mut dialogue_runners: Query<&mut DialogueRunner>,
+    mut load_scene_events: EventWriter<LoadSceneEvent>,
+) {
+    for transition in transitions.0.iter_mut() {
+        let Some(source) = transition.source.filter(|_| !transition.started) else {
+            continue;
+        };
+        transition.started = true;
+        if let Ok(mut dialogue_runner) = dialogue_runners.get_mut(source) {
+            transition.paused_dialogue_runner = !dialogue_runner.is_paused();
+            dialogue_runner.pause();
+        }
+        load_scene_events.send(LoadSceneEvent {
+            scene: transition.scene.clone(),
+            ends_dialogue: transition.ends_dialogue,
+            source,
+        });
+    }
+}
+
+fn finish_scene_transitions(
+    mut transitions: ResMut<SceneTransitions>,
+    mut dialogue_runners: Query<&mut DialogueRunner>,
+    mut scene_loaded_events: EventReader<SceneLoadedEvent>,
+) {
+    let loaded: Vec<_> = scene_loaded_events
+        .read()
+        .map(|event| event.source)
+        .collect();
+    transitions.0.retain(|transition| {
+        let Some(source) = transition.source.filter(|_| transition.started) else {
+            return true;
+        };
+        let Ok(mut dialogue_runner) = dialogue_runners.get_mut(source) else {
+            warn!(
+                "Dialogue runner {source} was despawned while loading the scene \"{}\", so its dialogue state is lost",
+                transition.scene
+            );
+            transition.done.store(true, Ordering::Relaxed);
+            return false;
+        };
+        if !loaded.contains(&source) {
+            return true;
+        }
+        transition.done.store(true, Ordering::Relaxed);
+        if transition.ends_dialogue {
+            dialogue_runner.stop();
+        } else if transition.paused_dialogue_runner {
+            dialogue_runner.resume();
+        }
+        false
+    });
+}
@@ -0,0 +1,358 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::prelude::*;
+use crate::scene_transition::load_scene;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Registers the [`StandardCommand`]s in every [`DialogueRunner`] when it is spawned. Each command can be turned off individually.
+/// Commands that a [`DialogueRunner`] already has are not replaced, so you can override any of them with your own implementation.
+/// The builtin commands `wait` and `stop` are available in every [`DialogueRunner`] regardless of this plugin, see [`YarnCommands::builtin_commands`].
+///
+/// Since this crate does not depend on Bevy's rendering, the commands work on plain components and resources:
+/// - `<<jump_camera x y>>` and `<<jump_camera x y z>>` move every entity marked with [`DialogueCamera`] to the given position.
+/// - `<<screen_shake intensity seconds>>` shakes every entity marked with [`DialogueCamera`] by up to `intensity` units, fading out over the given seconds.
+/// - `<<fade_out>>` and `<<fade_in>>` animate the [`ScreenFade`] resource to black and back over the given seconds, defaulting to 1.
+///   The dialogue continues once the fade is done. Draw a full screen overlay with [`ScreenFade::alpha`] to make it visible.
+/// - `<<load_scene scene_name>>` sends a [`LoadSceneEvent`](crate::events::LoadSceneEvent) for the game to load the scene and pauses the dialogue until it is loaded.
+///   `<<load_scene scene_name end>>` stops the dialogue once the scene is loaded instead of continuing it.
+/// - `<<set_music "music/theme.ogg">>` loops the given audio asset, replacing the music started by a previous call. `<<set_music>>` stops the music.
+///   Only registered with the `audio_assets` feature.
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::prelude::*;
+/// fn build_app(app: &mut App) {
+///     app.add_plugins(YarnSpinnerPlugin::new())
+///         .add_plugins(
+///             YarnSpinnerStandardCommandsPlugin::new().without_command(StandardCommand::ScreenShake),
+///         )
+///         .add_systems(Startup, spawn_camera);
+/// }
+///
+/// fn spawn_camera(mut commands: Commands) {
+///     commands.spawn((TransformBundle::default(), DialogueCamera));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YarnSpinnerStandardCommandsPlugin {
+    commands: HashSet<StandardCommand>,
+}
+
+/// A command registered by the [`YarnSpinnerStandardCommandsPlugin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StandardCommand {
+    /// `<<jump_camera x y z>>`
+    JumpCamera,
+    /// `<<screen_shake intensity seconds>>`
+    ScreenShake,
+    /// `<<fade_in seconds>>`
+    FadeIn,
+    /// `<<fade_out seconds>>`
+    FadeOut,
+    /// `<<load_scene scene_name>>`
+    LoadScene,
+    /// `<<set_music "path">>`, only registered with the `audio_assets` feature.
+    SetMusic,
+}
+
+impl StandardCommand {
+    /// All standard commands.
+    pub const ALL: [Self; 6] = [
+        Self::JumpCamera,
+        Self::ScreenShake,
+        Self::FadeIn,
+        Self::FadeOut,
+        Self::LoadScene,
+        Self::SetMusic,
+    ];
+
+    /// The name the command is called with from Yarn.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::JumpCamera => "jump_camera",
+            Self::ScreenShake => "screen_shake",
+            Self::FadeIn => "fade_in",
+            Self::FadeOut => "fade_out",
+            Self::LoadScene => "load_scene",
+            Self::SetMusic => "set_music",
+        }
+    }
+}
+
+impl Default for YarnSpinnerStandardCommandsPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl YarnSpinnerStandardCommandsPlugin {
+    /// Creates a plugin that registers all [`StandardCommand`]s.
+    pub fn new() -> Self {
+        Self {
+            commands: StandardCommand::ALL.into_iter().collect(),
+        }
+    }
+
+    /// Creates a plugin that registers no commands, to be used together with [`YarnSpinnerStandardCommandsPlugin::with_command`].
+    pub fn empty() -> Self {
+        Self {
+            commands: HashSet::new(),
+        }
+    }
+
+    /// Registers the given command.
+    #[must_use]
+    pub fn with_command(mut self, command: StandardCommand) -> Self {
+        self.commands.insert(command);
+        self
+    }
+
+    /// Does not register the given command.
+    #[must_use]
+    pub fn without_command(mut self, command: StandardCommand) -> Self {
+        self.commands.remove(&command);
+        self
+    }
+}
+
+impl Plugin for YarnSpinnerStandardCommandsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(crate::scene_transition::scene_transition_plugin)
+            .insert_resource(EnabledStandardCommands(self.commands.clone()))
+            .init_resource::<ScreenFade>()
+            .register_type::<DialogueCamera>()
+            .add_systems(
+                Update,
+                (
+                    register_standard_commands.before(DialogueExecutionSystemSet),
+                    (shake_cameras, update_screen_fade).after(DialogueExecutionSystemSet),
+                )
+                    .in_set(YarnSpinnerSystemSet),
+            );
+        #[cfg(feature = "audio_assets")]
+        app.register_type::<DialogueMusic>();
+    }
+}
+
+/// Marks the entities moved by the `jump_camera` and `screen_shake` commands of the [`YarnSpinnerStandardCommandsPlugin`].
+/// Usually added to a camera, but any entity with a [`Transform`] works.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Component, Reflect)]
+#[reflect(Debug, Component, Default, PartialEq, Hash)]
+pub struct DialogueCamera;
+
+/// A [`Resource`] animated by the `fade_in` and `fade_out` commands of the [`YarnSpinnerStandardCommandsPlugin`].
+/// Nothing is drawn by this crate, so use [`ScreenFade::alpha`] as the opacity of a full screen overlay.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct ScreenFade {
+    alpha: f32,
+    transition: Option<FadeTransition>,
+}
+
+#[derive(Debug, Clone)]
+struct FadeTransition {
+    from: f32,
+    to: f32,
+    timer: Timer,
+    done: Arc<AtomicBool>,
+}
+
+impl ScreenFade {
+    /// The opacity of the overlay, from `0.0` when faded in to `1.0` when faded out.
+    #[must_use]
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    /// Returns `true` while a fade is in progress.
+    #[must_use]
+    pub fn is_fading(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Starts fading to the given opacity. A fade that is still in progress is considered done and replaced.
+    /// Returns the indicator of when the new fade is done.
+    pub fn fade_to(&mut self, alpha: f32, duration: Duration) -> Arc<AtomicBool> {
+        self.finish_transition();
+        let done = Arc::new(AtomicBool::new(false));
+        self.transition = Some(FadeTransition {
+            from: self.alpha,
+            to: alpha,
+            timer: Timer::new(duration, TimerMode::Once),
+            done: done.clone(),
+        });
+        // Lets fades with a duration of zero finish immediately
+        self.tick(Duration::ZERO);
+        done
+    }
+
+    fn tick(&mut self, delta: Duration) {
+        let Some(transition) = self.transition.as_mut() else {
+            return;
+        };
+        transition.timer.tick(delta);
+        let fraction = if transition.timer.duration().is_zero() {
+            1.0
+        } else {
+            transition.timer.fraction()
+        };
+        self.alpha = transition.from + (transition.to - transition.from) * fraction;
+        if fraction >= 1.0 {
+            self.finish_transition();
+        }
+    }
+
+    fn finish_transition(&mut self) {
+        if let Some(transition) = self.transition.take() {
+            self.alpha = transition.to;
+            transition.done.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Marks the entity playing the music started by the `set_music` command of the [`YarnSpinnerStandardCommandsPlugin`].
+#[cfg(feature = "audio_assets")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Component, Reflect)]
+#[reflect(Debug, Component, Default, PartialEq, Hash)]
+pub struct DialogueMusic;
+
+#[derive(Debug, Clone, Resource)]
+struct EnabledStandardCommands(HashSet<StandardCommand>);
+
+/// The current offset of an entity shaken by the `screen_shake` command.
+#[derive(Debug, Clone, Component)]
+struct ScreenShake {
+    intensity: f32,
+    timer: Timer,
+    offset: Vec3,
+}
+
+fn register_standard_commands(
+    mut dialogue_runners: Query<&mut DialogueRunner, Added<DialogueRunner>>,
+    enabled: Res<EnabledStandardCommands>,
+) {
+    for mut dialogue_runner in dialogue_runners.iter_mut() {
+        let commands = dialogue_runner.commands_mut();
+        for command in StandardCommand::ALL {
+            let name = command.name();
+            if !enabled.0.contains(&command) || commands.contains_key(name) {
+                continue;
+            }
+            match command {
+                StandardCommand::JumpCamera => {
+                    commands.add_command(name, jump_camera);
+                }
+                StandardCommand::ScreenShake => {
+                    commands.add_command(name, screen_shake);
+                }
+                StandardCommand::FadeIn => {
+                    commands.add_command(name, fade_in);
+                }
+                StandardCommand::FadeOut => {
+                    commands.add_command(name, fade_out);
+                }
+                StandardCommand::LoadScene => {
+                    commands.add_command(name, load_scene);
+                }
+                #[cfg(feature = "audio_assets")]
+                StandardCommand::SetMusic => {
+                    commands.add_command(name, set_music);
+                }
+                #[cfg(not(feature = "audio_assets"))]
+                StandardCommand::SetMusic => {}
+            }
+        }
+    }
+}
+
+fn jump_camera(
+    In((x, y, z)): In<(f32, f32, Option<f32>)>,
+    mut cameras: Query<(&mut Transform, Option<&ScreenShake>), With<DialogueCamera>>,
+) {
+    for (mut transform, shake) in cameras.iter_mut() {
+        // The shake offset is removed again on the next update, so it needs to stay applied to the new position
+        let offset = shake.map_or(Vec3::ZERO, |shake| shake.offset);
+        let z = z.unwrap_or(transform.translation.z - offset.z);
+        transform.translation = Vec3::new(x, y, z) + offset;
+    }
+}
+
+fn screen_shake(
+    In((intensity, seconds)): In<(f32, f32)>,
+    mut commands: Commands,
+    cameras: Query<(Entity, Option<&ScreenShake>), With<DialogueCamera>>,
+) {
+    for (entity, previous_shake) in cameras.iter() {
+        commands.entity(entity).insert(ScreenShake {
+            intensity,
+            timer: Timer::from_seconds(seconds.max(0.0), TimerMode::Once),
+            offset: previous_shake.map_or(Vec3::ZERO, |shake| shake.offset),
+        });
+    }
+}
+
+fn fade_in(In(seconds): In<Option<f32>>, mut fade: ResMut<ScreenFade>) -> Arc<AtomicBool> {
+    fade.fade_to(0.0, fade_duration(seconds))
+}
+
+fn fade_out(In(seconds): In<Option<f32>>, mut fade: ResMut<ScreenFade>) -> Arc<AtomicBool> {
+    fade.fade_to(1.0, fade_duration(seconds))
+}
+
+fn fade_duration(seconds: Option<f32>) -> Duration {
+    Duration::from_secs_f32(seconds.unwrap_or(1.0).max(0.0))
+}
+
+#[cfg(feature = "audio_assets")]
+fn set_music(
+    In(path): In<Option<String>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    music: Query<Entity, With<DialogueMusic>>,
+) {
+    for entity in music.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let Some(path) = path.filter(|path| !path.is_empty()) else {
+        return;
+    };
+    commands.spawn((
+        Name::new("Yarn Spinner music"),
+        AudioBundle {
+            source: asset_server.load(path),
+            settings: PlaybackSettings::LOOP,
+        },
+        DialogueMusic,
+    ));
+}
+
+fn shake_cameras(
+    mut commands: Commands,
+    mut cameras: Query<(Entity, &mut Transform, &mut ScreenShake)>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, mut shake) in cameras.iter_mut() {
+        transform.translation -= shake.offset;
+        shake.timer.tick(time.delta());
+        if shake.timer.finished() {
+            commands.entity(entity).remove::<ScreenShake>();
+            continue;
+        }
+        // Incommensurable frequencies give an irregular shake without needing a random number generator
+        let t = time.elapsed_seconds();
+        let strength = shake.intensity * (1.0 - shake.timer.fraction());
+        shake.offset = Vec3::new((t * 47.0).sin(), (t * 61.0 + 1.0).sin(), 0.0) * strength;
+        transform.translation += shake.offset;
+    }
+}
+
+fn update_screen_fade(mut fade: ResMut<ScreenFade>, time: Res<Time>) {
+    if fade.is_fading() {
+        fade.tick(time.delta());
+    }
+}
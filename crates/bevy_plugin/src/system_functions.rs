@@ -0,0 +1,276 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::prelude::*;
+use bevy::ecs::system::{ReadOnlySystemParam, SystemParamItem, SystemState};
+use bevy::prelude::*;
+use bevy::utils::all_tuples;
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+use std::task::Poll;
+use yarnspinner::core::{
+    UntypedYarnFn, YarnFnContext, YarnFnError, YarnFnOutput, YarnFnParam, YarnFnParamItem,
+    YarnValueFuture, YarnValueWrapper,
+};
+
+pub(crate) fn system_functions_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        run_system_function_calls
+            .after(DialogueExecutionSystemSet)
+            .in_set(YarnSpinnerSystemSet),
+    );
+}
+
+/// A Bevy system that can be registered as a function for Yarn files via [`DialogueRunner::add_system_function`].
+///
+/// The signature of the system must adhere to the following rules:
+/// The first parameter must be of the type `In<T>`, where `T` is a [`YarnFnParam`]. This stands for the arguments passed to the function from Yarn.
+/// Multiple arguments are supported as values wrapped in a tuple, just like for a [`YarnCommand`].
+/// The parameters following the `In` parameter are read-only [`SystemParam`](bevy::ecs::system::SystemParam)s taken from the Bevy ECS, e.g. [`Res`] or [`Query`] without mutable access.
+/// The return value must be of a type that a [`YarnFn`] may return.
+///
+/// For example, the following function is called from Yarn like `{distance_to("guard")}`:
+/// ```rust
+/// # use bevy_yarnspinner::prelude::*;
+/// # use bevy::prelude::*;
+/// # fn register(dialogue_runner: &mut DialogueRunner) {
+/// dialogue_runner.add_system_function("distance_to", distance_to);
+/// # }
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn distance_to(
+///     In(name): In<String>,
+///     player: Query<&Transform, With<Player>>,
+///     others: Query<(&Name, &Transform)>,
+/// ) -> f32 {
+///     let player = player.single();
+///     others
+///         .iter()
+///         .find(|(other, _)| other.as_str() == name)
+///         .map_or(f32::INFINITY, |(_, other)| player.translation.distance(other.translation))
+/// }
+/// ```
+pub trait YarnSystemFunction<Marker>: Send + Sync + 'static + Clone {
+    /// The input type used to determine the arguments passed to the function from Yarn. A tuple of values will be interpreted as multiple arguments.
+    type In: YarnFnParam + 'static;
+    /// The return type of the function, which is passed back to Yarn.
+    type Out: YarnFnOutput + 'static;
+    /// The parameters passed to the function from the Bevy ECS.
+    type Param: ReadOnlySystemParam;
+
+    #[doc(hidden)]
+    fn run(
+        &self,
+        input: YarnFnParamItem<Self::In>,
+        param_value: SystemParamItem<Self::Param>,
+    ) -> Self::Out;
+}
+
+macro_rules! impl_system_function {
+    ($($param: ident),*) => {
+        #[allow(non_snake_case)]
+        impl<Input, Func: Send + Sync + 'static, Output, $($param: ReadOnlySystemParam),*> YarnSystemFunction<fn(In<Input>, $($param,)*) -> Output> for Func
+        where
+            Input: YarnFnParam + 'static,
+            Output: YarnFnOutput + 'static,
+            Func: Clone,
+        for <'a> &'a Func:
+            Fn(In<Input>, $($param), *) -> Output +
+            Fn(In<YarnFnParamItem<Input>>, $(SystemParamItem<$param>),*) -> Output
+        {
+            type In = Input;
+            type Out = Output;
+            type Param = ($($param,)*);
+            #[inline]
+            fn run(&self, input: YarnFnParamItem<Input>, param_value: SystemParamItem< ($($param,)*)>) -> Self::Out {
+                #[allow(clippy::too_many_arguments)]
+                fn call_inner<Input: YarnFnParam, Output, $($param,)*>(
+                    f: impl Fn(In<YarnFnParamItem<Input>>, $($param,)*) -> Output,
+                    input: In<YarnFnParamItem<Input>>,
+                    $($param: $param,)*
+                ) -> Output {
+                    f(input, $($param,)*)
+                }
+                let ($($param,)*) = param_value;
+                call_inner(self, In(input), $($param),*)
+            }
+        }
+    };
+}
+
+all_tuples!(impl_system_function, 0, 16, F);
+
+impl DialogueRunner {
+    /// Registers a Bevy system as a function that can be called from Yarn files. See [`YarnSystemFunction`] for what kind of systems are allowed.
+    ///
+    /// Since the system needs access to the [`World`], it is run by Yarn Spinner after the dialogue paused to wait for its result.
+    /// The dialogue then resumes in the next update, so calling such a function takes a frame.
+    pub fn add_system_function<Marker, F>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        function: F,
+    ) -> &mut Self
+    where
+        Marker: 'static,
+        F: YarnSystemFunction<Marker>,
+    {
+        let wrapped: Box<dyn UntypedYarnFn> = Box::new(YarnSystemFunctionWrapper {
+            function,
+            calls: self.system_function_calls.clone(),
+            _marker: PhantomData::<fn() -> Marker>,
+        });
+        self.library_mut().extend([(name.into(), wrapped)]);
+        self
+    }
+}
+
+/// The calls to [`YarnSystemFunction`]s that a [`DialogueRunner`] is waiting on.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SystemFunctionCalls(Arc<Mutex<Vec<SystemFunctionCall>>>);
+
+impl SystemFunctionCalls {
+    fn push(&self, call: SystemFunctionCall) {
+        self.0.lock().unwrap().push(call);
+    }
+
+    fn take(&self) -> Vec<SystemFunctionCall> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+#[derive(Debug)]
+struct SystemFunctionCall {
+    function: Box<dyn RunSystemFunction>,
+    input: Vec<YarnValue>,
+    result: Arc<Mutex<Option<Result<YarnValue, YarnFnError>>>>,
+}
+
+trait RunSystemFunction: Debug + Send + Sync + 'static {
+    fn run(&self, input: Vec<YarnValue>, world: &mut World) -> Result<YarnValue, YarnFnError>;
+}
+
+struct YarnSystemFunctionWrapper<Marker, F> {
+    function: F,
+    calls: SystemFunctionCalls,
+    // NOTE: PhantomData<fn()-> T> gives this safe Send/Sync impls
+    _marker: PhantomData<fn() -> Marker>,
+}
+
+impl<Marker, F: Clone> Clone for YarnSystemFunctionWrapper<Marker, F> {
+    fn clone(&self) -> Self {
+        Self {
+            function: self.function.clone(),
+            calls: self.calls.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<Marker, F> Debug for YarnSystemFunctionWrapper<Marker, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let signature = std::any::type_name::<Marker>();
+        let function_path = std::any::type_name::<F>();
+        let debug_message = format!("{signature} {{{function_path}}}");
+        f.debug_struct(&debug_message).finish()
+    }
+}
+
+impl<Marker, F> Display for YarnSystemFunctionWrapper<Marker, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(std::any::type_name::<Marker>())
+    }
+}
+
+impl<Marker, F> RunSystemFunction for YarnSystemFunctionWrapper<Marker, F>
+where
+    Marker: 'static,
+    F: YarnSystemFunction<Marker>,
+{
+    fn run(&self, input: Vec<YarnValue>, world: &mut World) -> Result<YarnValue, YarnFnError> {
+        let mut system_state: SystemState<F::Param> = SystemState::new(world);
+        let param = system_state.get(world);
+        let mut input: Vec<_> = input.into_iter().map(YarnValueWrapper::from).collect();
+        let mut iter = input.iter_mut().peekable();
+        let context = YarnFnContext::default();
+        let input = F::In::retrieve(&mut iter, &context)?;
+        if iter.next().is_some() {
+            return Err(YarnFnError::InvalidArguments {
+                message: "Passed too many arguments to YarnFn".to_owned(),
+            });
+        }
+        self.function.run(input, param).into_yarn_fn_result()
+    }
+}
+
+impl<Marker, F> UntypedYarnFn for YarnSystemFunctionWrapper<Marker, F>
+where
+    Marker: 'static,
+    F: YarnSystemFunction<Marker>,
+{
+    fn call(
+        &self,
+        _input: Vec<YarnValue>,
+        _context: &YarnFnContext,
+    ) -> Result<YarnValue, YarnFnError> {
+        Err(YarnFnError::Failed {
+            message: "Functions registered with `DialogueRunner::add_system_function` can only be called by a running DialogueRunner".to_owned(),
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn UntypedYarnFn> {
+        Box::new(self.clone())
+    }
+
+    fn parameter_types(&self) -> Vec<TypeId> {
+        F::In::parameter_types()
+    }
+
+    fn variadic_parameter_type(&self) -> Option<TypeId> {
+        F::In::variadic_type()
+    }
+
+    fn return_type(&self) -> TypeId {
+        TypeId::of::<<F::Out as YarnFnOutput>::Value>()
+    }
+
+    fn is_async(&self) -> bool {
+        true
+    }
+
+    fn call_async(
+        &self,
+        input: Vec<YarnValue>,
+        _context: &YarnFnContext,
+    ) -> Result<YarnValueFuture, YarnFnError> {
+        let result = Arc::new(Mutex::new(None));
+        self.calls.push(SystemFunctionCall {
+            function: Box::new(self.clone()),
+            input,
+            result: result.clone(),
+        });
+        // The dialogue runner polls this once per update, so there is no need to wake it
+        Ok(Box::pin(std::future::poll_fn(move |_| {
+            result
+                .lock()
+                .unwrap()
+                .take()
+                .map_or(Poll::Pending, Poll::Ready)
+        })))
+    }
+}
+
+fn run_system_function_calls(world: &mut World) {
+    let calls: Vec<_> = world
+        .query::<&DialogueRunner>()
+        .iter(world)
+        .flat_map(|dialogue_runner| dialogue_runner.system_function_calls.take())
+        .collect();
+    for call in calls {
+        let result = call.function.run(call.input, world);
+        *call.result.lock().unwrap() = Some(result);
+    }
+}
@@ -0,0 +1,428 @@
+//! Helpers for testing dialogue in a headless [`App`], e.g. in CI.
+//! Only available with the `test_utils` feature.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use bevy_yarnspinner::prelude::*;
+//! use bevy_yarnspinner::test_utils::*;
+//!
+//! let mut app = headless_app([YarnFile::new(
+//!     "shop.yarn",
+//!     "title: Shop\n---\nShopkeeper: What do you want?\n-> Bread\n    Shopkeeper: Here you go.\n-> Nothing\n===\n",
+//! )]);
+//! let recording = app.run_dialogue("Shop", DialogueScript::new().choose_text("Bread"));
+//! assert_eq!(
+//!     vec!["Shopkeeper: What do you want?", "Shopkeeper: Here you go."],
+//!     recording.line_texts()
+//! );
+//! ```
+
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::events::*;
+use crate::prelude::*;
+use crate::UnderlyingYarnCommand;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// The number of updates after which [`YarnSpinnerTestAppExt::run_dialogue`] and [`YarnSpinnerTestAppExt::load_yarn_project`] give up.
+pub const MAX_TEST_UPDATES: usize = 10_000;
+
+/// Creates a headless [`App`] with the minimal set of plugins needed to run the given Yarn files,
+/// without a window, renderer or audio. Development files are not generated, so tests never write to disk.
+/// The [`YarnSpinnerTestPlugin`] is added as well.
+///
+/// Use [`YarnSpinnerTestAppExt::add_headless_plugins`] instead if you need to configure the [`YarnSpinnerPlugin`] yourself.
+#[must_use]
+pub fn headless_app<T, U>(yarn_files: T) -> App
+where
+    T: IntoIterator<Item = U>,
+    U: Into<YarnFileSource>,
+{
+    let mut app = App::new();
+    app.add_headless_plugins().add_plugins(
+        YarnSpinnerPlugin::with_yarn_sources(yarn_files)
+            .with_development_file_generation(DevelopmentFileGeneration::None),
+    );
+    app
+}
+
+/// Drives [`DialogueRunner`]s that have a [`DialogueScript`] and records what they present into their [`DialogueRecording`].
+/// Added by [`headless_app`] and [`YarnSpinnerTestAppExt::add_headless_plugins`].
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct YarnSpinnerTestPlugin;
+
+impl YarnSpinnerTestPlugin {
+    /// Creates a new test plugin.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Plugin for YarnSpinnerTestPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (record_dialogue, follow_dialogue_scripts)
+                .chain()
+                .after(DialogueExecutionSystemSet)
+                .in_set(YarnSpinnerSystemSet),
+        );
+    }
+}
+
+/// Convenience methods for tests on [`App`].
+pub trait YarnSpinnerTestAppExt {
+    /// Adds [`MinimalPlugins`], an [`AssetPlugin`] that does not watch for changes and the [`YarnSpinnerTestPlugin`].
+    /// Add a [`YarnSpinnerPlugin`] afterwards.
+    fn add_headless_plugins(&mut self) -> &mut App;
+
+    /// Updates the app until the [`YarnProject`] is compiled.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the project is not compiled after [`MAX_TEST_UPDATES`] updates.
+    fn load_yarn_project(&mut self) -> &YarnProject;
+
+    /// Spawns a new [`DialogueRunner`] that follows the given [`DialogueScript`] and records into a [`DialogueRecording`].
+    /// Loads the [`YarnProject`] first if needed.
+    fn spawn_scripted_dialogue_runner(&mut self, script: DialogueScript) -> Entity;
+
+    /// Runs the given node on a new scripted [`DialogueRunner`] until the dialogue completes and returns what was recorded.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the dialogue does not complete after [`MAX_TEST_UPDATES`] updates,
+    /// e.g. because a command never finishes, or if the script has no choice left when options are presented.
+    fn run_dialogue(&mut self, node_name: &str, script: DialogueScript) -> DialogueRecording;
+
+    /// Starts collecting all events of type `T` so they can be inspected with [`YarnSpinnerTestAppExt::recorded_events`].
+    fn record_events<T: Event + Clone>(&mut self) -> &mut App;
+
+    /// Returns the events of type `T` sent since [`YarnSpinnerTestAppExt::record_events`] was called, oldest first.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if [`YarnSpinnerTestAppExt::record_events`] was not called for `T`.
+    #[must_use]
+    fn recorded_events<T: Event + Clone>(&self) -> &[T];
+}
+
+impl YarnSpinnerTestAppExt for App {
+    fn add_headless_plugins(&mut self) -> &mut App {
+        self.add_plugins(MinimalPlugins)
+            .add_plugins(AssetPlugin {
+                watch_for_changes_override: Some(false),
+                ..default()
+            })
+            .add_plugins(YarnSpinnerTestPlugin)
+    }
+
+    fn load_yarn_project(&mut self) -> &YarnProject {
+        for _ in 0..MAX_TEST_UPDATES {
+            if self.world().contains_resource::<YarnProject>() {
+                break;
+            }
+            self.update();
+        }
+        self.world()
+            .get_resource::<YarnProject>()
+            .unwrap_or_else(|| {
+                panic!("The Yarn project was not compiled after {MAX_TEST_UPDATES} updates")
+            })
+    }
+
+    fn spawn_scripted_dialogue_runner(&mut self, script: DialogueScript) -> Entity {
+        let dialogue_runner = self.load_yarn_project().create_dialogue_runner();
+        self.world_mut()
+            .spawn((dialogue_runner, script, DialogueRecording::default()))
+            .id()
+    }
+
+    fn run_dialogue(&mut self, node_name: &str, script: DialogueScript) -> DialogueRecording {
+        let entity = self.spawn_scripted_dialogue_runner(script);
+        self.world_mut()
+            .get_mut::<DialogueRunner>(entity)
+            .unwrap()
+            .start_node(node_name);
+        for _ in 0..MAX_TEST_UPDATES {
+            self.update();
+            if !self
+                .world()
+                .get::<DialogueRunner>(entity)
+                .unwrap()
+                .is_running()
+            {
+                return self
+                    .world_mut()
+                    .entity_mut(entity)
+                    .take::<DialogueRecording>()
+                    .unwrap();
+            }
+        }
+        panic!("The dialogue starting at node \"{node_name}\" did not complete after {MAX_TEST_UPDATES} updates")
+    }
+
+    fn record_events<T: Event + Clone>(&mut self) -> &mut App {
+        if !self.world().contains_resource::<RecordedEvents<T>>() {
+            self.init_resource::<RecordedEvents<T>>()
+                .add_systems(Update, record_events::<T>.after(YarnSpinnerSystemSet));
+        }
+        self
+    }
+
+    fn recorded_events<T: Event + Clone>(&self) -> &[T] {
+        &self
+            .world()
+            .get_resource::<RecordedEvents<T>>()
+            .unwrap_or_else(|| {
+                panic!(
+                    "Events of type {} are not recorded. Call `App::record_events` first",
+                    std::any::type_name::<T>()
+                )
+            })
+            .0
+    }
+}
+
+/// The choices a [`DialogueRunner`] on the same entity makes, in order. Lines are continued automatically.
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy_yarnspinner::test_utils::*;
+/// let script = DialogueScript::new().choose_text("Bread").choose_index(0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Component)]
+pub struct DialogueScript {
+    choices: VecDeque<ScriptedChoice>,
+}
+
+impl DialogueScript {
+    /// Creates a script that makes no choices.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Chooses the option at the given position among the presented options, starting at 0.
+    #[must_use]
+    pub fn choose_index(mut self, index: usize) -> Self {
+        self.choices.push_back(ScriptedChoice::Index(index));
+        self
+    }
+
+    /// Chooses the option with the given text. The character name is ignored, so `"Bread"` matches both `Bread` and `Player: Bread`.
+    #[must_use]
+    pub fn choose_text(mut self, text: impl Into<String>) -> Self {
+        self.choices.push_back(ScriptedChoice::Text(text.into()));
+        self
+    }
+
+    /// Returns the choices that were not made yet.
+    pub fn remaining_choices(&self) -> impl ExactSizeIterator<Item = &ScriptedChoice> {
+        self.choices.iter()
+    }
+}
+
+/// A choice of a [`DialogueScript`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptedChoice {
+    /// Chooses the option at this position among the presented options, starting at 0.
+    Index(usize),
+    /// Chooses the option with this text, with or without character name.
+    Text(String),
+}
+
+impl ScriptedChoice {
+    fn find(&self, options: &[DialogueOption]) -> Option<OptionId> {
+        match self {
+            Self::Index(index) => options.get(*index),
+            Self::Text(text) => options.iter().find(|option| {
+                option.line.text == *text || option.line.text_without_character_name() == *text
+            }),
+        }
+        .map(|option| option.id)
+    }
+}
+
+/// Everything that happened in the [`DialogueRunner`] on the same entity, in order.
+/// Added by [`YarnSpinnerTestAppExt::spawn_scripted_dialogue_runner`], but can also be inserted manually.
+#[derive(Debug, Clone, PartialEq, Default, Component)]
+pub struct DialogueRecording {
+    /// The recorded events, oldest first.
+    pub events: Vec<RecordedDialogueEvent>,
+}
+
+impl DialogueRecording {
+    /// Returns the presented lines.
+    pub fn lines(&self) -> impl Iterator<Item = &LocalizedLine> {
+        self.events.iter().filter_map(|event| match event {
+            RecordedDialogueEvent::Line(line) => Some(line),
+            _ => None,
+        })
+    }
+
+    /// Returns the texts of the presented lines, including character names.
+    #[must_use]
+    pub fn line_texts(&self) -> Vec<&str> {
+        self.lines().map(|line| line.text.as_str()).collect()
+    }
+
+    /// Returns the chosen options.
+    pub fn selected_options(&self) -> impl Iterator<Item = &DialogueOption> {
+        self.events.iter().filter_map(|event| match event {
+            RecordedDialogueEvent::OptionSelected(option) => Some(option),
+            _ => None,
+        })
+    }
+
+    /// Returns the executed commands.
+    pub fn commands(&self) -> impl Iterator<Item = &UnderlyingYarnCommand> {
+        self.events.iter().filter_map(|event| match event {
+            RecordedDialogueEvent::Command(command) => Some(command),
+            _ => None,
+        })
+    }
+
+    /// Returns the names of the started nodes.
+    #[must_use]
+    pub fn visited_nodes(&self) -> Vec<&str> {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                RecordedDialogueEvent::NodeStarted(node_name) => Some(node_name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns whether a line with the given text was presented. The character name is ignored if `text` does not contain one.
+    #[must_use]
+    pub fn contains_line(&self, text: &str) -> bool {
+        self.lines()
+            .any(|line| line.text == text || line.text_without_character_name() == text)
+    }
+}
+
+/// An event of a [`DialogueRecording`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordedDialogueEvent {
+    /// A node was started, see [`NodeStartEvent`].
+    NodeStarted(String),
+    /// A line was presented, see [`PresentLineEvent`].
+    Line(LocalizedLine),
+    /// Options were presented, see [`PresentOptionsEvent`].
+    Options(Vec<DialogueOption>),
+    /// An option was chosen, see [`OptionSelectedEvent`].
+    OptionSelected(DialogueOption),
+    /// A command was executed, see [`ExecuteCommandEvent`].
+    Command(UnderlyingYarnCommand),
+    /// A node was completed, see [`NodeCompleteEvent`].
+    NodeCompleted(String),
+    /// The dialogue completed, see [`DialogueCompleteEvent`].
+    DialogueCompleted,
+}
+
+#[derive(Debug, Resource)]
+struct RecordedEvents<T>(Vec<T>);
+
+impl<T> Default for RecordedEvents<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+fn record_events<T: Event + Clone>(
+    mut events: EventReader<T>,
+    mut recorded: ResMut<RecordedEvents<T>>,
+) {
+    recorded.0.extend(events.read().cloned());
+}
+
+fn record_dialogue(
+    mut option_selected_events: EventReader<OptionSelectedEvent>,
+    mut node_start_events: EventReader<NodeStartEvent>,
+    mut execute_command_events: EventReader<ExecuteCommandEvent>,
+    mut present_line_events: EventReader<PresentLineEvent>,
+    mut present_options_events: EventReader<PresentOptionsEvent>,
+    mut node_complete_events: EventReader<NodeCompleteEvent>,
+    mut dialogue_complete_events: EventReader<DialogueCompleteEvent>,
+    mut recordings: Query<&mut DialogueRecording>,
+) {
+    // The events are read in the order the dialogue runner sends them in
+    let events = option_selected_events
+        .read()
+        .map(|e| {
+            (
+                e.source,
+                RecordedDialogueEvent::OptionSelected(e.option.clone()),
+            )
+        })
+        .chain(node_start_events.read().map(|e| {
+            (
+                e.source,
+                RecordedDialogueEvent::NodeStarted(e.node_name.clone()),
+            )
+        }))
+        .chain(
+            execute_command_events
+                .read()
+                .map(|e| (e.source, RecordedDialogueEvent::Command(e.command.clone()))),
+        )
+        .chain(
+            present_line_events
+                .read()
+                .map(|e| (e.source, RecordedDialogueEvent::Line(e.line.clone()))),
+        )
+        .chain(
+            present_options_events
+                .read()
+                .map(|e| (e.source, RecordedDialogueEvent::Options(e.options.clone()))),
+        )
+        .chain(node_complete_events.read().map(|e| {
+            (
+                e.source,
+                RecordedDialogueEvent::NodeCompleted(e.node_name.clone()),
+            )
+        }))
+        .chain(
+            dialogue_complete_events
+                .read()
+                .map(|e| (e.source, RecordedDialogueEvent::DialogueCompleted)),
+        );
+    for (source, event) in events {
+        if let Ok(mut recording) = recordings.get_mut(source) {
+            recording.events.push(event);
+        }
+    }
+}
+
+fn follow_dialogue_scripts(
+    mut present_line_events: EventReader<PresentLineEvent>,
+    mut present_options_events: EventReader<PresentOptionsEvent>,
+    mut dialogue_runners: Query<(&mut DialogueRunner, &mut DialogueScript)>,
+) {
+    for event in present_line_events.read() {
+        if let Ok((mut dialogue_runner, _)) = dialogue_runners.get_mut(event.source) {
+            dialogue_runner.continue_in_next_update();
+        }
+    }
+    for event in present_options_events.read() {
+        let Ok((mut dialogue_runner, mut script)) = dialogue_runners.get_mut(event.source) else {
+            continue;
+        };
+        let texts: Vec<_> = event.options.iter().map(|o| o.line.text.as_str()).collect();
+        let choice = script.choices.pop_front().unwrap_or_else(|| {
+            panic!("The dialogue script has no choice left for the options {texts:?}")
+        });
+        let option = choice.find(&event.options).unwrap_or_else(|| {
+            panic!("The scripted choice {choice:?} does not match any of the options {texts:?}")
+        });
+        dialogue_runner
+            .select_option(option)
+            .unwrap_or_else(|e| panic!("{e}"));
+    }
+}
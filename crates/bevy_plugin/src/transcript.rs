@@ -0,0 +1,141 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::events::*;
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::collections::VecDeque;
+
+pub(crate) fn transcript_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        record_transcript
+            .after(DialogueExecutionSystemSet)
+            .in_set(YarnSpinnerSystemSet)
+            .run_if(resource_exists::<DialogueTranscript>),
+    );
+}
+
+/// A backlog of the lines presented by each [`DialogueRunner`] and of the options chosen in it, in the order they happened.
+/// Entries are stored as [`LocalizedLine`]s, i.e. in the language they were shown in.
+///
+/// Not recorded by default. Insert this resource to start recording, e.g. with `app.init_resource::<DialogueTranscript>()`.
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_yarnspinner::prelude::*;
+/// fn print_backlog(transcript: Res<DialogueTranscript>, dialogue_runners: Query<Entity, With<DialogueRunner>>) {
+///     for dialogue_runner in dialogue_runners.iter() {
+///         for entry in transcript.entries(dialogue_runner) {
+///             match entry {
+///                 TranscriptEntry::Line(line) => println!("{}", line.text),
+///                 TranscriptEntry::SelectedOption(line) => println!("> {}", line.text),
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Resource)]
+pub struct DialogueTranscript {
+    /// How many entries are kept per [`DialogueRunner`]. The oldest ones are dropped first. Defaults to 200.
+    pub max_entries: usize,
+    entries: HashMap<Entity, VecDeque<TranscriptEntry>>,
+}
+
+impl Default for DialogueTranscript {
+    fn default() -> Self {
+        Self {
+            max_entries: 200,
+            entries: default(),
+        }
+    }
+}
+
+impl DialogueTranscript {
+    /// Creates an empty transcript that keeps the given number of entries per [`DialogueRunner`].
+    pub fn with_max_entries(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            ..default()
+        }
+    }
+
+    /// Returns the recorded entries of the given [`DialogueRunner`], oldest first.
+    pub fn entries(
+        &self,
+        dialogue_runner: Entity,
+    ) -> impl DoubleEndedIterator<Item = &TranscriptEntry> + ExactSizeIterator {
+        self.entries
+            .get(&dialogue_runner)
+            .map(|entries| entries.iter())
+            .unwrap_or_default()
+    }
+
+    /// Returns the [`DialogueRunner`]s that have recorded entries.
+    pub fn dialogue_runners(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.entries.keys().copied()
+    }
+
+    /// Removes all entries of the given [`DialogueRunner`], e.g. when a new conversation starts.
+    pub fn clear(&mut self, dialogue_runner: Entity) {
+        self.entries.remove(&dialogue_runner);
+    }
+
+    fn record(&mut self, dialogue_runner: Entity, entry: TranscriptEntry) {
+        let entries = self.entries.entry(dialogue_runner).or_default();
+        match (entries.back(), &entry) {
+            // Shown again because `DialogueRunner::run_selected_options_as_lines` is set
+            (Some(TranscriptEntry::SelectedOption(last)), TranscriptEntry::Line(line))
+                if last.id == line.id =>
+            {
+                return;
+            }
+            // Shown again because the language changed
+            (Some(TranscriptEntry::Line(last)), TranscriptEntry::Line(line))
+                if last.id == line.id =>
+            {
+                entries.pop_back();
+            }
+            _ => {}
+        }
+        entries.push_back(entry);
+        while entries.len() > self.max_entries {
+            entries.pop_front();
+        }
+    }
+}
+
+/// An entry of the [`DialogueTranscript`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TranscriptEntry {
+    /// A line that was presented through a [`PresentLineEvent`].
+    Line(LocalizedLine),
+    /// The line of an option that was chosen, see [`OptionSelectedEvent`].
+    SelectedOption(LocalizedLine),
+}
+
+impl TranscriptEntry {
+    /// Returns the line of this entry, regardless of whether it was presented or chosen.
+    #[must_use]
+    pub fn line(&self) -> &LocalizedLine {
+        match self {
+            Self::Line(line) | Self::SelectedOption(line) => line,
+        }
+    }
+}
+
+fn record_transcript(
+    mut transcript: ResMut<DialogueTranscript>,
+    mut option_selected_events: EventReader<OptionSelectedEvent>,
+    mut present_line_events: EventReader<PresentLineEvent>,
+) {
+    // Options are selected before the dialogue continues, so these come first
+    for event in option_selected_events.read() {
+        let entry = TranscriptEntry::SelectedOption(event.option.line.clone());
+        transcript.record(event.source, entry);
+    }
+    for event in present_line_events.read() {
+        transcript.record(event.source, TranscriptEntry::Line(event.line.clone()));
+    }
+}
@@ -2,12 +2,6 @@ use crate::prelude::*;
 use crate::project::YarnProjectConfigToLoad;
 use bevy::prelude::*;
 
-pub(crate) fn panic_on_err(In(result): In<SystemResult>) {
-    if let Err(e) = result {
-        panic!("Error in Yarn Spinner plugin: {e}");
-    }
-}
-
 pub(crate) fn in_development(
     project: Option<Res<YarnProject>>,
     project_to_load: Option<Res<YarnProjectConfigToLoad>>,
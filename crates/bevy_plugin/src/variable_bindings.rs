@@ -0,0 +1,260 @@
+use crate::dialogue_runner::DialogueExecutionSystemSet;
+use crate::fmt_utils::SkipDebug;
+use crate::prelude::*;
+use anyhow::{anyhow, bail};
+use bevy::prelude::*;
+use bevy::reflect::{ParsedPath, ReflectPath};
+use std::any::type_name;
+
+pub(crate) fn variable_bindings_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        sync_variable_bindings
+            .before(DialogueExecutionSystemSet)
+            .in_set(YarnSpinnerSystemSet),
+    );
+}
+
+impl DialogueRunner {
+    /// Binds a Yarn variable to a field of the [`Resource`] `R`, which must implement [`Reflect`].
+    /// The field is given as a [reflection path](bevy::reflect::GetPath), e.g. `"health"` or `"stats.health"`. An empty path binds the whole resource.
+    ///
+    /// The variable and the field are kept in sync in both directions every update before the dialogue runs:
+    /// when the field changes, its value is written to the [`VariableStorage`] of this runner, and when a script changes the variable, e.g. with `<<set $player_health to 10>>`,
+    /// its value is written to the field. The bound field must be a `bool`, a `String` or a number.
+    /// While the resource does not exist, the variable is left as it is.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_yarnspinner::prelude::*;
+    /// #[derive(Resource, Reflect)]
+    /// struct Player {
+    ///     health: f32,
+    /// }
+    ///
+    /// fn bind_health(dialogue_runner: &mut DialogueRunner) {
+    ///     dialogue_runner.bind_variable_to_resource::<Player>("$player_health", "health");
+    /// }
+    /// ```
+    ///
+    /// Panics if the variable name does not start with `$` or if the path is invalid.
+    pub fn bind_variable_to_resource<R: Resource + Reflect>(
+        &mut self,
+        variable_name: impl Into<String>,
+        field_path: impl AsRef<str>,
+    ) -> &mut Self {
+        let target = BindingTarget {
+            description: format!("resource {}", type_name::<R>()),
+            read: Box::new(|world| world.get_resource::<R>().map(|r| r as &dyn Reflect)),
+            write: Box::new(|world| {
+                world
+                    .get_resource_mut::<R>()
+                    .map(|r| r.map_unchanged(|r| r as &mut dyn Reflect))
+            }),
+        };
+        self.add_variable_binding(variable_name.into(), field_path.as_ref(), target)
+    }
+
+    /// Binds a Yarn variable to a field of the [`Component`] `C` on the given entity, which must implement [`Reflect`].
+    /// Behaves like [`DialogueRunner::bind_variable_to_resource`]. While the entity or its component does not exist, the variable is left as it is.
+    ///
+    /// Panics if the variable name does not start with `$` or if the path is invalid.
+    pub fn bind_variable_to_component<C: Component + Reflect>(
+        &mut self,
+        variable_name: impl Into<String>,
+        entity: Entity,
+        field_path: impl AsRef<str>,
+    ) -> &mut Self {
+        let target = BindingTarget {
+            description: format!("component {} of entity {entity}", type_name::<C>()),
+            read: Box::new(move |world| world.get::<C>(entity).map(|c| c as &dyn Reflect)),
+            write: Box::new(move |world| {
+                world
+                    .get_mut::<C>(entity)
+                    .map(|c| c.map_unchanged(|c| c as &mut dyn Reflect))
+            }),
+        };
+        self.add_variable_binding(variable_name.into(), field_path.as_ref(), target)
+    }
+
+    /// Removes all bindings of the given variable that were added with [`DialogueRunner::bind_variable_to_resource`] or [`DialogueRunner::bind_variable_to_component`].
+    /// The variable keeps its last value.
+    pub fn unbind_variable(&mut self, variable_name: &str) -> &mut Self {
+        self.variable_bindings
+            .retain(|binding| binding.variable_name != variable_name);
+        self
+    }
+
+    fn add_variable_binding(
+        &mut self,
+        variable_name: String,
+        field_path: &str,
+        target: BindingTarget,
+    ) -> &mut Self {
+        assert!(
+            variable_name.starts_with('$'),
+            "Failed to bind variable \"{variable_name}\": variable names must start with a '$'"
+        );
+        let field_path = ParsedPath::parse(field_path).unwrap_or_else(|error| {
+            panic!("Failed to bind variable \"{variable_name}\": invalid path \"{field_path}\": {error}")
+        });
+        self.variable_bindings.push(VariableBinding {
+            variable_name,
+            field_path,
+            target: SkipDebug(target),
+            last_synced_value: None,
+        });
+        self
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct VariableBinding {
+    variable_name: String,
+    field_path: ParsedPath,
+    target: SkipDebug<BindingTarget>,
+    last_synced_value: Option<YarnValue>,
+}
+
+struct BindingTarget {
+    description: String,
+    read: Box<dyn Fn(&World) -> Option<&dyn Reflect> + Send + Sync>,
+    write: Box<dyn Fn(&mut World) -> Option<Mut<dyn Reflect>> + Send + Sync>,
+}
+
+impl VariableBinding {
+    fn sync(&mut self, world: &mut World, variable_storage: &mut dyn VariableStorage) {
+        let stored_value = variable_storage.get(&self.variable_name).ok();
+        // The first sync always goes from the world to the variable, as gameplay owns the initial value
+        if self.last_synced_value.is_some() && stored_value != self.last_synced_value {
+            if let Some(value) = stored_value {
+                if let Err(e) = self.write_to_world(world, &value) {
+                    error!(
+                        "Failed to write variable \"{}\" to {}: {e}",
+                        self.variable_name, self.target.description
+                    );
+                }
+                self.last_synced_value = Some(value);
+                return;
+            }
+        }
+
+        let Some(value) = self.read_from_world(world) else {
+            return;
+        };
+        if self.last_synced_value.as_ref() == Some(&value) {
+            return;
+        }
+        if let Err(e) = variable_storage.set(self.variable_name.clone(), value.clone()) {
+            error!(
+                "Failed to write {} to variable \"{}\": {e}",
+                self.target.description, self.variable_name
+            );
+        }
+        self.last_synced_value = Some(value);
+    }
+
+    fn read_from_world(&self, world: &World) -> Option<YarnValue> {
+        let root = (self.target.read)(world)?;
+        let field = self.field(root);
+        let value = reflect_to_yarn_value(field).unwrap_or_else(|| {
+            panic!(
+                "Failed to read variable \"{}\" from {}: fields of type {} cannot be bound to Yarn variables. \
+                Only bools, Strings and numbers are supported.",
+                self.variable_name,
+                self.target.description,
+                field.reflect_type_path()
+            )
+        });
+        Some(value)
+    }
+
+    fn write_to_world(&self, world: &mut World, value: &YarnValue) -> Result<()> {
+        let Some(mut root) = (self.target.write)(world) else {
+            return Ok(());
+        };
+        let field = (&self.field_path)
+            .reflect_element_mut(&mut *root)
+            .map_err(|error| anyhow!("invalid path \"{}\": {error}", self.field_path))?;
+        apply_yarn_value(field, value)
+    }
+
+    fn field<'a>(&self, root: &'a dyn Reflect) -> &'a dyn Reflect {
+        (&self.field_path)
+            .reflect_element(root)
+            .unwrap_or_else(|error| {
+                panic!(
+                    "Failed to read variable \"{}\" from {}: invalid path \"{}\": {error}",
+                    self.variable_name, self.target.description, self.field_path
+                )
+            })
+    }
+}
+
+macro_rules! impl_reflect_conversions {
+    ($($number_type:ty),*) => {
+        fn reflect_to_yarn_value(value: &dyn Reflect) -> Option<YarnValue> {
+            if let Some(value) = value.downcast_ref::<bool>() {
+                return Some((*value).into());
+            }
+            if let Some(value) = value.downcast_ref::<String>() {
+                return Some(value.clone().into());
+            }
+            $(
+                if let Some(value) = value.downcast_ref::<$number_type>() {
+                    return Some((*value).into());
+                }
+            )*
+            None
+        }
+
+        fn apply_yarn_value(target: &mut dyn Reflect, value: &YarnValue) -> Result<()> {
+            if let Some(target) = target.downcast_mut::<bool>() {
+                *target = value.try_into()?;
+                return Ok(());
+            }
+            if let Some(target) = target.downcast_mut::<String>() {
+                *target = value.into();
+                return Ok(());
+            }
+            $(
+                if let Some(target) = target.downcast_mut::<$number_type>() {
+                    *target = value.try_into()?;
+                    return Ok(());
+                }
+            )*
+            bail!(
+                "fields of type {} cannot be bound to Yarn variables",
+                target.reflect_type_path()
+            )
+        }
+    };
+}
+
+impl_reflect_conversions!(f32, f64, i8, i16, i32, i64, u8, u16, u32, u64, isize, usize);
+
+fn sync_variable_bindings(world: &mut World) {
+    let mut dialogue_runners = world.query::<(Entity, &mut DialogueRunner)>();
+    let bindings: Vec<_> = dialogue_runners
+        .iter_mut(world)
+        .filter(|(_, dialogue_runner)| !dialogue_runner.variable_bindings.is_empty())
+        .map(|(entity, mut dialogue_runner)| {
+            let dialogue_runner = dialogue_runner.bypass_change_detection();
+            (
+                entity,
+                std::mem::take(&mut dialogue_runner.variable_bindings),
+                dialogue_runner.variable_storage().clone_shallow(),
+            )
+        })
+        .collect();
+    for (entity, mut variable_bindings, mut variable_storage) in bindings {
+        for binding in &mut variable_bindings {
+            binding.sync(world, variable_storage.as_mut());
+        }
+        if let Some(mut dialogue_runner) = world.get_mut::<DialogueRunner>(entity) {
+            dialogue_runner.bypass_change_detection().variable_bindings = variable_bindings;
+        }
+    }
+}
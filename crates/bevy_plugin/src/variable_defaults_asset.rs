@@ -0,0 +1,209 @@
+use crate::prelude::*;
+use bevy::asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use std::collections::BTreeMap;
+use yarnspinner::compiler::{Diagnostic, DiagnosticSeverity};
+use yarnspinner::core::Type;
+
+pub(crate) fn variable_defaults_asset_plugin(app: &mut App) {
+    app.init_asset::<VariableDefaults>()
+        .init_asset_loader::<VariableDefaultsAssetLoader>();
+}
+
+/// A set of default values for variables that replace the ones given by `<<declare>>` statements when the [`YarnProject`] is loaded,
+/// e.g. to tweak the game for a difficulty preset or a platform without touching the Yarn files.
+/// Load one with [`YarnSpinnerPlugin::with_variable_defaults`].
+///
+/// The file contains a map from variable names to booleans, numbers or strings, either as JSON in a `.yarnvars.json` file
+/// or as RON in a `.yarnvars.ron` file. The leading `$` of the variable names is optional:
+/// ```json
+/// {
+///     "$enemy_health": 50,
+///     "$hints_enabled": true,
+///     "player_title": "Rookie"
+/// }
+/// ```
+///
+/// Every override is checked against the declarations of the project. Overrides of undeclared variables and overrides whose value
+/// does not match the declared type are ignored and reported as warnings in the [`YarnProjectCompiledEvent`](crate::events::YarnProjectCompiledEvent).
+/// The overrides are applied again whenever the project is recompiled.
+#[derive(Debug, Clone, PartialEq, Default, Asset, TypePath)]
+pub struct VariableDefaults {
+    values: BTreeMap<String, YarnValue>,
+}
+
+impl VariableDefaults {
+    /// The file extension of variable defaults written as JSON.
+    pub const JSON_EXTENSION: &'static str = "yarnvars.json";
+    /// The file extension of variable defaults written as RON.
+    pub const RON_EXTENSION: &'static str = "yarnvars.ron";
+
+    /// Parses variable defaults written as JSON, see [`VariableDefaults`] for the format.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let values: BTreeMap<String, DefaultValue> =
+            serde_json::from_str(json).context("Failed to parse variable defaults as JSON")?;
+        Ok(values.into_iter().collect())
+    }
+
+    /// Parses variable defaults written as RON, see [`VariableDefaults`] for the format.
+    pub fn from_ron(ron: &str) -> Result<Self> {
+        let values: BTreeMap<String, DefaultValue> =
+            ron::from_str(ron).context("Failed to parse variable defaults as RON")?;
+        Ok(values.into_iter().collect())
+    }
+
+    /// Returns the override for the given variable, if any. The name may be given with or without the leading `$`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&YarnValue> {
+        self.values.get(&variable_name(name))
+    }
+
+    /// Iterates over the overridden variables, sorted by name. The names always start with `$`.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &YarnValue)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// Replaces the initial values of the compiled program with the overrides that match a declaration
+    /// and returns a warning for each override that was ignored.
+    pub(crate) fn apply(&self, compilation: &mut Compilation) -> Vec<Diagnostic> {
+        let Some(program) = compilation.program.as_mut() else {
+            return vec![];
+        };
+        let mut diagnostics = Vec::new();
+        for (name, value) in &self.values {
+            let declaration = compilation
+                .declarations
+                .iter_mut()
+                .find(|declaration| &declaration.name == name);
+            // Precompiled programs have no declarations, but still know the type of each initial value
+            let declared_type = declaration
+                .as_ref()
+                .map(|declaration| declaration.r#type.clone())
+                .or_else(|| {
+                    program
+                        .initial_values
+                        .get(name)
+                        .map(|operand| value_type(&operand.clone().into()))
+                });
+            let Some(declared_type) = declared_type else {
+                diagnostics.push(warning(format!(
+                    "Ignoring the default value {value} for {name} because the variable is not declared in the Yarn project"
+                )));
+                continue;
+            };
+            if value_type(value) != declared_type {
+                diagnostics.push(warning(format!(
+                    "Ignoring the default value {value} for {name} because the variable is declared as {declared_type}, but the value is a {}",
+                    value_type(value)
+                )));
+                continue;
+            }
+            if let Some(declaration) = declaration {
+                declaration.default_value = Some(value.clone());
+            }
+            program
+                .initial_values
+                .insert(name.clone(), value.clone().into());
+        }
+        for diagnostic in &diagnostics {
+            warn!("{}", diagnostic.message);
+        }
+        diagnostics
+    }
+}
+
+impl<T: Into<String>, U: Into<YarnValue>> FromIterator<(T, U)> for VariableDefaults {
+    fn from_iter<I: IntoIterator<Item = (T, U)>>(iter: I) -> Self {
+        Self {
+            values: iter
+                .into_iter()
+                .map(|(name, value)| (variable_name(&name.into()), value.into()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum DefaultValue {
+    Boolean(bool),
+    Number(f64),
+    String(String),
+}
+
+impl From<DefaultValue> for YarnValue {
+    fn from(value: DefaultValue) -> Self {
+        match value {
+            DefaultValue::Boolean(value) => value.into(),
+            DefaultValue::Number(value) => value.into(),
+            DefaultValue::String(value) => value.into(),
+        }
+    }
+}
+
+fn variable_name(name: &str) -> String {
+    if name.starts_with('$') {
+        name.to_owned()
+    } else {
+        format!("${name}")
+    }
+}
+
+fn value_type(value: &YarnValue) -> Type {
+    match value {
+        YarnValue::Number(_) => Type::Number,
+        YarnValue::String(_) => Type::String,
+        YarnValue::Boolean(_) => Type::Boolean,
+        YarnValue::List(_) => Type::List,
+        // Objects only exist at runtime, so no declaration can match them
+        YarnValue::Object(_) => Type::Any,
+    }
+}
+
+fn warning(message: String) -> Diagnostic {
+    Diagnostic {
+        file_name: None,
+        range: None,
+        message,
+        context: None,
+        severity: DiagnosticSeverity::Warning,
+        start_line: 0,
+    }
+}
+
+#[derive(Debug, Default)]
+struct VariableDefaultsAssetLoader;
+
+impl AssetLoader for VariableDefaultsAssetLoader {
+    type Asset = VariableDefaults;
+    type Settings = ();
+    type Error = anyhow::Error;
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text).await?;
+        let is_ron = load_context
+            .path()
+            .to_string_lossy()
+            .ends_with(VariableDefaults::RON_EXTENSION);
+        if is_ron {
+            VariableDefaults::from_ron(&text)
+        } else {
+            VariableDefaults::from_json(&text)
+        }
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[
+            VariableDefaults::JSON_EXTENSION,
+            VariableDefaults::RON_EXTENSION,
+        ]
+    }
+}
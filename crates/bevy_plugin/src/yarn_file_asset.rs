@@ -15,16 +15,23 @@ pub struct YarnFile {
 }
 
 impl YarnFile {
-    /// Creates a new Yarn file from a filename and file content.
+    /// Creates a new Yarn file from a filename and file content, e.g. for dialogue embedded with [`include_yarn!`](crate::include_yarn)
+    /// or generated at runtime. Pass it to [`YarnSpinnerPlugin::with_yarn_source`] to compile it without any asset on disk.
+    ///
+    /// Panics if the content is not valid Yarn, see [`YarnFile::try_new`] for a non-panicking version.
     pub fn new(filename: impl Into<String>, content: impl Into<String>) -> Self {
-        let filename = filename.into();
-        let content = content.into();
+        Self::try_new(filename, content).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Creates a new Yarn file from a filename and file content like [`YarnFile::new`],
+    /// but fails instead of panicking if the content is not valid Yarn. Useful for dialogue that is generated procedurally.
+    pub fn try_new(filename: impl Into<String>, content: impl Into<String>) -> Result<Self> {
         let file = InnerYarnFile {
-            file_name: filename,
-            source: content,
+            file_name: filename.into(),
+            source: content.into(),
         };
-        let string_table = compile_string_table(file.clone()).unwrap();
-        Self { file, string_table }
+        let string_table = compile_string_table(file.clone())?;
+        Ok(Self { file, string_table })
     }
 
     /// Returns the filename of the Yarn file.
@@ -46,6 +53,24 @@ impl YarnFile {
     }
 }
 
+/// Embeds a Yarn file into the binary with [`include_str!`] and creates a [`YarnFile`] from it, using the path as the file name.
+/// This needs no asset files at runtime, which is handy for Wasm builds.
+/// The path is relative to the file the macro is called in.
+///
+/// ## Example
+///
+/// ```rust
+/// # use bevy_yarnspinner::prelude::*;
+/// # use bevy_yarnspinner::include_yarn;
+/// let plugin = YarnSpinnerPlugin::with_yarn_source(include_yarn!("../assets/lines.yarn"));
+/// ```
+#[macro_export]
+macro_rules! include_yarn {
+    ($path:expr $(,)?) => {
+        $crate::prelude::YarnFile::new($path, include_str!($path))
+    };
+}
+
 fn compile_string_table(
     file: InnerYarnFile,
 ) -> Result<std::collections::HashMap<LineId, StringInfo>> {
@@ -0,0 +1,117 @@
+use crate::prelude::*;
+use bevy::asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use std::collections::HashMap;
+#[cfg(feature = "development_file_generation")]
+use std::{fs, path::Path};
+use yarnspinner::core::Program;
+
+pub(crate) fn yarn_program_asset_plugin(app: &mut App) {
+    app.init_asset::<YarnProgram>()
+        .init_asset_loader::<YarnProgramAssetLoader>();
+}
+
+/// A precompiled Yarn program along with the text of its lines. Loading one skips compiling Yarn files at runtime,
+/// which makes loading dialogue in shipped games faster, especially on Wasm.
+///
+/// A precompiled program consists of two files next to each other: a `.yarnc` file containing the [`Program`]
+/// in the binary format of the original Yarn Spinner compiler and a `.strings.csv` file with the same name containing its lines,
+/// e.g. `dialogue/game.yarnc` and `dialogue/game.strings.csv`. Loading the `.yarnc` file with the [`AssetServer`] loads both.
+///
+/// Write these files during development with [`YarnProject::write_precompiled_program`], then load them with [`YarnSpinnerPlugin::with_precompiled_program`].
+#[derive(Debug, Clone, PartialEq, Asset, TypePath)]
+pub struct YarnProgram {
+    pub(crate) program: Program,
+    pub(crate) string_table: HashMap<LineId, StringInfo>,
+}
+
+impl YarnProgram {
+    /// The file extension of precompiled programs.
+    pub const EXTENSION: &'static str = "yarnc";
+    /// The file extension of the strings file next to a precompiled program.
+    pub const STRINGS_FILE_EXTENSION: &'static str = "strings.csv";
+
+    /// Returns the compiled program.
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    /// Returns the lines of the program, keyed by their [`LineId`].
+    pub fn string_table(&self) -> &HashMap<LineId, StringInfo> {
+        &self.string_table
+    }
+
+    /// Writes the program to the given path and its lines to a strings file next to it, see [`YarnProgram`].
+    /// The lines are written in the given language, which is only informational.
+    /// Requires the `development_file_generation` feature.
+    #[cfg(feature = "development_file_generation")]
+    pub fn write(&self, path: impl AsRef<Path>, language: impl Into<Language>) -> Result<()> {
+        let path = path.as_ref().with_extension(Self::EXTENSION);
+        if let Some(parent_dir) = path.parent() {
+            fs::create_dir_all(parent_dir).with_context(|| {
+                format!(
+                    "Failed to create directory for precompiled Yarn program \"{}\"",
+                    parent_dir.display()
+                )
+            })?;
+        }
+        fs::write(&path, self.program.to_bytes()).with_context(|| {
+            format!(
+                "Failed to write precompiled Yarn program \"{}\"",
+                path.display()
+            )
+        })?;
+        StringsFile::from_compiled_string_table(language, self.string_table.clone())
+            .write_asset(&path.with_extension(Self::STRINGS_FILE_EXTENSION))
+    }
+
+    pub(crate) fn to_compilation(&self) -> Compilation {
+        Compilation {
+            program: Some(self.program.clone()),
+            string_table: self.string_table.clone(),
+            ..default()
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct YarnProgramAssetLoader;
+
+impl AssetLoader for YarnProgramAssetLoader {
+    type Asset = YarnProgram;
+    type Settings = ();
+    type Error = anyhow::Error;
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let program = Program::from_bytes(&bytes)?;
+
+        let strings_file_path = load_context
+            .path()
+            .with_extension(YarnProgram::STRINGS_FILE_EXTENSION);
+        let strings_file_bytes = load_context
+            .read_asset_bytes(strings_file_path.clone())
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to read the strings file \"{}\" of the precompiled Yarn program",
+                    strings_file_path.display()
+                )
+            })?;
+        let string_table = StringsFile::from_csv(&strings_file_bytes)?.into_string_table();
+        Ok(YarnProgram {
+            program,
+            string_table,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[YarnProgram::EXTENSION]
+    }
+}
@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, prelude::*};
+use utils::prelude::*;
+
+mod utils;
+
+const CAFE: &str = r#"title: Start
+---
+Barista: Welcome to the café! [a11y_skip]~~~[/a11y_skip]
+I [a11y alt="love"]<3[/a11y] coffee.
+-> Player: An espresso, please.
+-> A [b]large[/b] latte <<if false>>
+-> Nothing.
+===
+"#;
+
+#[test]
+fn announces_lines_without_markup() {
+    let mut app = setup_app();
+
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    app.continue_dialogue_and_update();
+
+    let lines: Vec<_> = take_announcements(&mut app)
+        .into_iter()
+        .map(|announcement| {
+            let AccessibilityAnnouncement::Line { speaker, text, .. } = announcement else {
+                panic!("Expected a line, but got {announcement:?}");
+            };
+            (speaker, text)
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            (
+                Some("Barista".to_string()),
+                "Welcome to the café!".to_string()
+            ),
+            (None, "I love coffee.".to_string()),
+        ],
+        lines
+    );
+}
+
+#[test]
+fn announces_options_and_focus_changes() {
+    let mut app = setup_app();
+    let entity = app.dialogue_runner_entity();
+    app.world_mut()
+        .entity_mut(entity)
+        .insert(OptionNavigation::with_bindings(
+            OptionNavigationBindings::none(),
+        ));
+
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    app.continue_dialogue_and_update_n_times(2);
+    let texts: Vec<_> = take_announcements(&mut app)
+        .iter()
+        .skip(2)
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        vec![
+            "3 options. Option 1: An espresso, please. Option 2, unavailable: A large latte. Option 3: Nothing.",
+            "Option 1 of 2: An espresso, please.",
+        ],
+        texts
+    );
+
+    app.world_mut().send_event(OptionNavigationEvent {
+        action: OptionNavigationAction::Next,
+        source: entity,
+    });
+    app.update();
+    assert_eq!(
+        vec![AccessibilityAnnouncement::OptionFocused {
+            option: AnnouncedOption {
+                id: OptionId(2),
+                text: "Nothing.".to_string(),
+                is_available: true,
+            },
+            position: 2,
+            count: 2,
+        }],
+        take_announcements(&mut app)
+    );
+}
+
+#[test]
+fn does_not_announce_without_plugin() {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFile::new(
+            "cafe.yarn",
+            CAFE,
+        )));
+
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    assert!(!app
+        .world()
+        .contains_resource::<Events<AccessibilityAnnouncementEvent>>());
+}
+
+fn setup_app() -> App {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFile::new(
+            "cafe.yarn",
+            CAFE,
+        )))
+        .add_plugins(YarnSpinnerAccessibilityPlugin::default())
+        .init_resource::<Announcements>()
+        .add_systems(Update, record_announcements.after(YarnSpinnerSystemSet));
+    app
+}
+
+#[derive(Debug, Default, Resource)]
+struct Announcements(Vec<AccessibilityAnnouncement>);
+
+fn record_announcements(
+    mut events: EventReader<AccessibilityAnnouncementEvent>,
+    mut announcements: ResMut<Announcements>,
+) {
+    announcements
+        .0
+        .extend(events.read().map(|event| event.announcement.clone()));
+}
+
+fn take_announcements(app: &mut App) -> Vec<AccessibilityAnnouncement> {
+    std::mem::take(&mut app.world_mut().resource_mut::<Announcements>().0)
+}
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, prelude::*};
+use utils::prelude::*;
+
+mod utils;
+
+const DLC_FILE: &str = "title: Dlc
+---
+<<set $visited_dlc to true>>
+Hag: Welcome to the expansion! #mood:happy
+===
+";
+
+#[test]
+fn existing_dialogue_runners_run_added_nodes() {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "lines.yarn",
+        )));
+    // Spawned before the files are added, so it has to pick up the new nodes
+    let _ = app.dialogue_runner_entity();
+    app.load_project_mut()
+        .add_file(YarnFile::new("dlc.yarn", DLC_FILE));
+    while app.load_project().is_adding_files() {
+        app.update();
+    }
+    assert!(app.load_project().headers_for_node("Dlc").is_some());
+    assert!(app.load_project().headers_for_node("Start").is_some());
+    let mut asserter = EventAsserter::new();
+    asserter.clear_events(&mut app);
+
+    app.dialogue_runner_mut().start_node("Dlc");
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text == "Hag: Welcome to the expansion!" && event.line.metadata == vec!["mood:happy".to_string()],
+    ]);
+    assert_eq!(
+        Some(true),
+        app.dialogue_runner()
+            .variable_storage()
+            .get("$visited_dlc")
+            .ok()
+            .and_then(|value| bool::try_from(value).ok())
+    );
+}
+
+#[test]
+fn reports_conflicting_node_names() {
+    let mut app = App::new();
+    app.setup_default_plugins().add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines.yarn"))
+            .with_error_handling(YarnErrorHandling::SendEvent),
+    );
+    let _ = app.dialogue_runner_entity();
+    app.load_project_mut().add_file(YarnFile::new(
+        "conflict.yarn",
+        "title: Start\n---\nHag: Again?\n===\n",
+    ));
+    while app.load_project().is_adding_files() {
+        app.update();
+    }
+
+    let events = app.world().resource::<Events<YarnErrorEvent>>();
+    let errors: Vec<_> = events.get_reader().read(events).cloned().collect();
+    assert_eq!(1, errors.len());
+    assert_eq!(YarnErrorContext::ProjectLoading, errors[0].context);
+
+    let mut asserter = EventAsserter::new();
+    asserter.clear_events(&mut app);
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text.starts_with("An elderly man was sitting alone on a dark path."),
+    ]);
+}
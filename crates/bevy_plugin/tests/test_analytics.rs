@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, prelude::*};
+use utils::prelude::*;
+
+mod utils;
+
+const CHOICES: &str = r#"title: Start
+---
+<<fade_out 1.5 "slow">>
+-> Stay
+-> Leave
+    <<jump Exit>>
+===
+title: Exit
+---
+Narrator: Goodbye.
+===
+"#;
+
+#[test]
+fn reports_dialogue_flow() {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFile::new(
+            "choices.yarn",
+            CHOICES,
+        )))
+        .add_plugins(YarnSpinnerAnalyticsPlugin::default())
+        .init_resource::<RecordedAnalytics>()
+        .add_systems(Update, record_analytics.after(YarnSpinnerSystemSet));
+
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    app.continue_dialogue_and_update();
+    app.dialogue_runner_mut()
+        .select_option(OptionId(1))
+        .unwrap();
+    app.update();
+    app.continue_dialogue_and_update();
+
+    let runner = app.dialogue_runner_entity();
+    let events = &app.world().resource::<RecordedAnalytics>().0;
+    let kinds: Vec<_> = events
+        .iter()
+        .map(|event| (event.kind.clone(), event.node.as_deref()))
+        .collect();
+    let DialogueAnalyticsKind::OptionsPresented { options } = &kinds[3].0 else {
+        panic!("Expected options to be presented, but got {:?}", kinds[3].0);
+    };
+    assert_eq!(2, options.len());
+    assert_eq!(
+        vec![
+            (DialogueAnalyticsKind::DialogueStarted, Some("Start")),
+            (DialogueAnalyticsKind::NodeEntered, Some("Start")),
+            (
+                DialogueAnalyticsKind::CommandExecuted {
+                    name: "fade_out".to_string(),
+                    parameters: vec!["1.5".to_string(), "slow".to_string()],
+                },
+                Some("Start")
+            ),
+            (
+                DialogueAnalyticsKind::OptionsPresented {
+                    options: options.clone()
+                },
+                Some("Start")
+            ),
+            (
+                DialogueAnalyticsKind::OptionChosen {
+                    option: options[1].clone()
+                },
+                Some("Start")
+            ),
+            (DialogueAnalyticsKind::NodeEntered, Some("Exit")),
+            (DialogueAnalyticsKind::DialogueEnded, None),
+        ],
+        kinds
+    );
+    assert!(events.iter().all(|event| event.source == runner));
+}
+
+#[test]
+fn serializes_to_json() {
+    let event = DialogueAnalyticsEvent {
+        kind: DialogueAnalyticsKind::OptionChosen {
+            option: AnalyticsOption {
+                index: 1,
+                line_id: LineId::from("line:leave"),
+                is_available: true,
+            },
+        },
+        node: Some("Start".to_string()),
+        elapsed_seconds: 2.5,
+        source: Entity::from_raw(42),
+    };
+
+    let json = serde_json::to_value(&event).unwrap();
+    assert_eq!("option_chosen", json["kind"]["type"]);
+    assert_eq!(1, json["kind"]["option"]["index"]);
+    assert_eq!(Entity::from_raw(42).to_bits(), json["source"]);
+    assert_eq!(event, serde_json::from_value(json).unwrap());
+}
+
+#[test]
+fn does_not_send_events_without_plugin() {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFile::new(
+            "choices.yarn",
+            CHOICES,
+        )));
+
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    assert!(!app
+        .world()
+        .contains_resource::<Events<DialogueAnalyticsEvent>>());
+}
+
+#[derive(Debug, Default, Resource)]
+struct RecordedAnalytics(Vec<DialogueAnalyticsEvent>);
+
+fn record_analytics(
+    mut events: EventReader<DialogueAnalyticsEvent>,
+    mut recorded: ResMut<RecordedAnalytics>,
+) {
+    recorded.0.extend(events.read().cloned());
+}
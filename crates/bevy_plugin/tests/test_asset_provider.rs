@@ -2,7 +2,7 @@
 use anyhow::{bail, Result};
 use bevy::prelude::*;
 use bevy::utils::Instant;
-use bevy_yarnspinner::prelude::*;
+use bevy_yarnspinner::{events::LineHintsReadyEvent, prelude::*};
 use utils::prelude::*;
 
 mod utils;
@@ -27,8 +27,7 @@ fn does_not_load_asset_without_localizations() -> Result<()> {
     app.load_project();
     let start = Instant::now();
     loop {
-        let assets = app.clone_loaded_untyped_assets();
-        if app.dialogue_runner_mut().update_line_availability(&assets) {
+        if app.update_line_availability() {
             break;
         }
 
@@ -104,6 +103,57 @@ fn loads_asset_from_base_language_localization() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn preloads_assets_of_hinted_lines() -> Result<()> {
+    let mut app = App::new();
+
+    app.setup_default_plugins().add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(Localizations {
+                base_localization: "en-US".into(),
+                translations: vec![],
+            })
+            .with_development_file_generation(DevelopmentFileGeneration::None),
+    );
+
+    let project = app.load_project();
+    let mut dialogue_runner = project
+        .build_dialogue_runner()
+        .add_asset_provider(AudioAssetProvider::new())
+        .build();
+    dialogue_runner.start_node("Start");
+    app.world_mut().spawn(dialogue_runner);
+    app.update();
+    assert!(app.dialogue_runner().is_preloading());
+
+    let start = Instant::now();
+    while app.dialogue_runner().is_preloading() {
+        if start.elapsed().as_secs() > 2 {
+            bail!("Timeout while waiting for line hints to be preloaded");
+        }
+        app.update();
+    }
+    let line_ids = app
+        .world()
+        .resource::<Events<LineHintsReadyEvent>>()
+        .iter_current_update_events()
+        .next()
+        .unwrap()
+        .line_ids
+        .clone();
+    assert!(line_ids.contains(&LineId("line:9".to_owned())));
+
+    let asset: Handle<AudioSource> = app
+        .dialogue_runner()
+        .get_assets_for_id("line:9")
+        .get_handle()
+        .unwrap();
+    let asset_server = app.world().resource::<AssetServer>();
+    assert!(asset_server.is_loaded_with_dependencies(asset.id()));
+
+    Ok(())
+}
+
 #[test]
 fn loads_asset_from_translated_localization() -> Result<()> {
     let mut app = App::new();
@@ -139,6 +189,56 @@ fn loads_asset_from_translated_localization() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn falls_back_to_base_language_asset_if_configured() -> Result<()> {
+    for fallback in [false, true] {
+        let mut app = App::new();
+        app.setup_default_plugins().add_plugins(
+            YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+                .with_localizations(Localizations {
+                    base_localization: "en-US".into(),
+                    translations: vec!["de-CH".into()],
+                })
+                .with_development_file_generation(DevelopmentFileGeneration::None),
+        );
+
+        let project = app.load_project();
+        let mut dialogue_runner = project
+            .build_dialogue_runner()
+            .add_asset_provider(
+                AudioAssetProvider::new().with_path_convention(
+                    AssetPathConvention::new("dialogue/{language}/{line_id}.{extension}")
+                        .with_base_language_fallback(fallback),
+                ),
+            )
+            .build();
+        dialogue_runner
+            .set_asset_language("de-CH")
+            .start_node("Start");
+        app.world_mut().spawn(dialogue_runner);
+        app.load_lines();
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let path_of = |assets: LineAssets| {
+            let asset: Handle<AudioSource> = assets.get_handle()?;
+            asset_server
+                .get_path(asset.id())
+                .map(|path| path.path().to_str().unwrap().to_owned())
+        };
+        assert_eq!(
+            Some("dialogue/de-CH/10.ogg".to_owned()),
+            path_of(app.dialogue_runner().get_assets_for_id("line:10"))
+        );
+        let fallback_path = path_of(app.dialogue_runner().get_assets_for_id("line:9"));
+        if fallback {
+            assert_eq!(Some("dialogue/en-US/9.ogg".to_owned()), fallback_path);
+        } else {
+            assert_eq!(None, fallback_path);
+        }
+    }
+    Ok(())
+}
+
 #[test]
 #[should_panic]
 fn panics_on_invalid_language() {
@@ -197,3 +297,43 @@ fn does_not_load_asset_with_invalid_type() -> Result<()> {
     assert!(asset.is_none());
     Ok(())
 }
+
+#[test]
+fn plays_voice_over_until_next_line() -> Result<()> {
+    let mut app = App::new();
+
+    app.setup_default_plugins().add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(Localizations {
+                base_localization: "en-US".into(),
+                translations: vec![],
+            })
+            .with_development_file_generation(DevelopmentFileGeneration::None),
+    );
+
+    let project = app.load_project();
+    let mut dialogue_runner = project
+        .build_dialogue_runner()
+        .add_asset_provider(AudioAssetProvider::new().with_voice_over_playback(true))
+        .build();
+    dialogue_runner.start_node("Start");
+    app.world_mut().spawn(dialogue_runner);
+    app.load_lines();
+    app.update();
+    assert!(voice_over_line_ids(&mut app).is_empty());
+
+    app.continue_dialogue_and_update_n_times(8);
+    assert_eq!(vec![LineId::from("line:9")], voice_over_line_ids(&mut app));
+
+    app.continue_dialogue_and_update();
+    assert!(voice_over_line_ids(&mut app).is_empty());
+    Ok(())
+}
+
+fn voice_over_line_ids(app: &mut App) -> Vec<LineId> {
+    app.world_mut()
+        .query::<&VoiceOver>()
+        .iter(app.world())
+        .map(|voice_over| voice_over.line_id.clone())
+        .collect()
+}
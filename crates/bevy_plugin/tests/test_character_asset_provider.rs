@@ -0,0 +1,51 @@
+use anyhow::Result;
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, prelude::*};
+use utils::prelude::*;
+
+mod utils;
+
+#[test]
+fn provides_assets_of_registered_characters() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "lines_with_ids.yarn",
+        )));
+
+    // Any asset type works, so we use Yarn files as stand-ins for character portraits.
+    let project = app.load_project();
+    let mut dialogue_runner = project
+        .build_dialogue_runner()
+        .add_asset_provider(
+            CharacterAssetProvider::<YarnFile>::new().with_character("Hag", "lines.yarn"),
+        )
+        .build();
+    dialogue_runner.start_node("Start");
+    app.world_mut().spawn(dialogue_runner);
+    app.load_lines();
+
+    assert_events!(asserter, app contains
+        PresentLineEvent with |event| event.line.id == LineId::from("line:1") && event.line.assets.is_empty());
+
+    app.continue_dialogue_and_update();
+    let events = app.world().resource::<Events<PresentLineEvent>>();
+    let line = &asserter
+        .present_line_reader
+        .read(events)
+        .last()
+        .unwrap()
+        .line;
+    assert_eq!(Some("Hag"), line.character_name());
+    let asset: Handle<YarnFile> = line.assets.get_handle().unwrap();
+    let asset_server = app.world().resource::<AssetServer>();
+    let path = asset_server.get_path(asset.id()).unwrap();
+    assert_eq!("lines.yarn", path.path().to_str().unwrap());
+
+    app.continue_dialogue_and_update();
+    assert_events!(asserter, app contains
+        PresentLineEvent with |event| event.line.character_name() == Some("Man") && event.line.assets.is_empty());
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use anyhow::Result;
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, prelude::*};
+use utils::prelude::*;
+
+mod utils;
+
+#[test]
+fn parses_console_input() -> Result<()> {
+    assert_eq!(
+        DialogueConsoleCommand::StartNode("Start".to_owned()),
+        "jump Start".parse()?
+    );
+    assert_eq!(
+        DialogueConsoleCommand::SetVariable {
+            name: "$gold".to_owned(),
+            value: 12.into()
+        },
+        "set $gold 12".parse()?
+    );
+    assert_eq!(
+        DialogueConsoleCommand::SetVariable {
+            name: "$name".to_owned(),
+            value: "Old Man".into()
+        },
+        "set $name \"Old Man\"".parse()?
+    );
+    assert_eq!(DialogueConsoleCommand::ToggleSkipMode, "skip".parse()?);
+    assert_eq!(
+        DialogueConsoleCommand::SetSkipMode(false),
+        "skip off".parse()?
+    );
+    assert_eq!(DialogueConsoleCommand::DumpState, " dump ".parse()?);
+    assert!("set gold 12".parse::<DialogueConsoleCommand>().is_err());
+    assert!("fly".parse::<DialogueConsoleCommand>().is_err());
+    Ok(())
+}
+
+#[test]
+fn starts_node_by_name() {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    setup_dialogue_runner(&mut app);
+    app.update();
+    assert!(!app.dialogue_runner().is_running());
+
+    send_command(
+        &mut app,
+        DialogueConsoleCommand::StartNode("Start".to_owned()),
+    );
+    app.update();
+    assert!(app.dialogue_runner().is_running());
+    assert_events!(asserter, app contains [DialogueStartEvent, PresentLineEvent]);
+}
+
+#[test]
+fn ignores_unknown_node() {
+    let mut app = App::new();
+    setup_dialogue_runner(&mut app);
+    app.update();
+
+    send_command(
+        &mut app,
+        DialogueConsoleCommand::StartNode("DoesNotExist".to_owned()),
+    );
+    app.update();
+    assert!(!app.dialogue_runner().is_running());
+}
+
+#[test]
+fn sets_variable() -> Result<()> {
+    let mut app = App::new();
+    setup_dialogue_runner(&mut app);
+    app.update();
+
+    send_command(&mut app, "set $wishes 3".parse()?);
+    app.update();
+    assert_eq!(
+        YarnValue::from(3),
+        app.dialogue_runner().variable_storage().get("$wishes")?
+    );
+    assert!(app.dialogue_runner().dump_state().contains("$wishes = 3"));
+    Ok(())
+}
+
+#[test]
+fn skip_mode_runs_through_lines() {
+    let mut app = App::new();
+    setup_dialogue_runner(&mut app).start_node("Start");
+    app.update();
+
+    send_command(&mut app, DialogueConsoleCommand::ToggleSkipMode);
+    for _ in 0..20 {
+        app.update();
+    }
+    assert!(app.dialogue_runner().is_skipping());
+    assert!(!app.dialogue_runner().is_running());
+}
+
+fn send_command(app: &mut App, command: DialogueConsoleCommand) {
+    app.world_mut()
+        .send_event(DialogueConsoleCommandEvent::new(command));
+}
+
+fn setup_dialogue_runner(app: &mut App) -> Mut<'_, DialogueRunner> {
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "lines.yarn",
+        )))
+        .dialogue_runner_mut()
+}
@@ -0,0 +1,211 @@
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, prelude::*};
+use std::path::PathBuf;
+use utils::prelude::*;
+
+mod utils;
+
+#[test]
+fn does_not_validate_by_default() {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "commands.yarn",
+        )));
+    spawn_dialogue_runner(&mut app);
+    app.update();
+
+    assert!(app.world().resource::<ContentValidationReport>().is_empty());
+}
+
+#[test]
+fn reports_unregistered_commands_on_request() {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "commands.yarn",
+        )));
+    let dialogue_runner = app.dialogue_runner_entity();
+    app.dialogue_runner_mut()
+        .commands_mut()
+        .add_command("set_data", |_: In<String>| {});
+    app.world_mut().send_event(ValidateContentEvent::default());
+    app.update();
+
+    let report = app.world().resource::<ContentValidationReport>();
+    assert_eq!(
+        report.unregistered_commands(),
+        &[UnregisteredCommand {
+            dialogue_runner,
+            name: "unregistered".to_owned(),
+            node: "Start".to_owned(),
+        }]
+    );
+    assert_eq!(1, report.len());
+}
+
+#[test]
+fn reports_missing_translations_on_load() {
+    let mut app = App::new();
+    app.setup_default_plugins().add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(Localizations {
+                base_localization: "en-US".into(),
+                translations: vec!["de-CH".into()],
+            })
+            .with_development_file_generation(DevelopmentFileGeneration::None)
+            .with_content_validation(ContentValidation::Report),
+    );
+    spawn_dialogue_runner(&mut app);
+    app.update();
+
+    let report = app.world().resource::<ContentValidationReport>();
+    assert_eq!(
+        report.missing_translations(),
+        &[MissingTranslation {
+            language: "de-CH".into(),
+            line_id: LineId("line:10".to_owned()),
+        }]
+    );
+    assert!(report.to_string().contains("line:10"));
+}
+
+#[test]
+#[should_panic]
+fn panics_in_strict_mode() {
+    let mut app = App::new();
+    app.setup_default_plugins().add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("commands.yarn"))
+            .with_content_validation(ContentValidation::Strict),
+    );
+    spawn_dialogue_runner(&mut app);
+    app.update();
+}
+
+#[test]
+fn reports_missing_character_assets() {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "lines.yarn",
+        )));
+    let project = app.load_project();
+    let dialogue_runner = project
+        .build_dialogue_runner()
+        .add_asset_provider(
+            CharacterAssetProvider::<YarnFile>::new()
+                .with_character("Man", "lines.yarn")
+                .with_character("Hag", "portraits/hag.yarn"),
+        )
+        .build();
+    let dialogue_runner = app.world_mut().spawn(dialogue_runner).id();
+    app.world_mut().send_event(ValidateContentEvent::default());
+    app.update();
+
+    let report = app.world().resource::<ContentValidationReport>();
+    assert_eq!(
+        report.missing_assets(),
+        &[MissingAsset {
+            dialogue_runner,
+            asset: ExpectedAsset {
+                line_id: None,
+                language: None,
+                paths: vec![PathBuf::from("portraits/hag.yarn")],
+            },
+        }]
+    );
+}
+
+#[cfg(feature = "audio_assets")]
+#[test]
+fn reports_missing_voice_lines() {
+    let mut app = App::new();
+    app.setup_default_plugins().add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(Localizations {
+                base_localization: "en-US".into(),
+                translations: vec!["de-CH".into()],
+            })
+            .with_development_file_generation(DevelopmentFileGeneration::None),
+    );
+    let project = app.load_project();
+    let dialogue_runner = project
+        .build_dialogue_runner()
+        .add_asset_provider(AudioAssetProvider::new())
+        .build();
+    app.world_mut().spawn(dialogue_runner);
+    app.world_mut().send_event(ValidateContentEvent::default());
+    app.update();
+
+    let report = app.world().resource::<ContentValidationReport>();
+    let missing_assets: Vec<_> = report
+        .missing_assets()
+        .iter()
+        .map(|missing_asset| missing_asset.asset.paths[0].clone())
+        .collect();
+    // 12 lines in 2 languages, of which "en-US/9", "de-CH/8" and "de-CH/10" exist
+    assert_eq!(21, missing_assets.len());
+    assert!(missing_assets.contains(&PathBuf::from("dialogue/en-US/8.mp3")));
+    assert!(!missing_assets.contains(&PathBuf::from("dialogue/en-US/9.mp3")));
+    assert!(!missing_assets.contains(&PathBuf::from("dialogue/de-CH/8.mp3")));
+
+    let missing_assets_by_language = report.missing_assets_by_language();
+    assert_eq!(2, missing_assets_by_language.len());
+    assert_eq!(
+        11,
+        missing_assets_by_language[&Language::new("en-US")].len()
+    );
+    assert_eq!(
+        10,
+        report
+            .missing_assets_for_language(&Language::new("de-CH"))
+            .count()
+    );
+}
+
+#[test]
+fn reports_unknown_speakers_when_character_registry_exists() {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "lines_with_ids.yarn",
+        )))
+        .insert_resource(CharacterRegistry::new().with_character("Hag", Character::new()));
+    spawn_dialogue_runner(&mut app);
+    app.world_mut().send_event(ValidateContentEvent::default());
+    app.update();
+
+    let report = app.world().resource::<ContentValidationReport>();
+    let unknown_men: Vec<_> = report
+        .unknown_speakers()
+        .iter()
+        .filter(|speaker| speaker.name == "Man")
+        .map(|speaker| speaker.line_id.0.as_str())
+        .collect();
+    assert_eq!(vec!["line:3", "line:5", "line:9"], unknown_men);
+    assert!(report
+        .unknown_speakers()
+        .iter()
+        .all(|speaker| speaker.name != "Hag"));
+    assert!(report.to_string().contains("\"Man\""));
+}
+
+#[test]
+fn does_not_report_unknown_speakers_without_character_registry() {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "lines_with_ids.yarn",
+        )));
+    spawn_dialogue_runner(&mut app);
+    app.world_mut().send_event(ValidateContentEvent::default());
+    app.update();
+
+    let report = app.world().resource::<ContentValidationReport>();
+    assert!(report.unknown_speakers().is_empty());
+}
+
+fn spawn_dialogue_runner(app: &mut App) {
+    let dialogue_runner = app.load_project().create_dialogue_runner();
+    app.world_mut().spawn(dialogue_runner);
+}
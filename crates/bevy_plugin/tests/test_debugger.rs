@@ -0,0 +1,60 @@
+#![cfg(feature = "debugger")]
+
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::{ButtonState, InputPlugin};
+use bevy::prelude::*;
+use bevy::window::ExitCondition;
+use bevy_yarnspinner::prelude::*;
+use utils::prelude::*;
+
+mod utils;
+
+#[test]
+fn records_recent_lines() {
+    let mut app = App::new();
+    setup_debugger(&mut app).history_length = 2;
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    app.continue_dialogue_and_update_n_times(2);
+
+    let dialogue_runner = app.dialogue_runner_entity();
+    let debugger = app.world().resource::<DialogueDebugger>();
+    let lines: Vec<_> = debugger.recent_lines(dialogue_runner).collect();
+    assert_eq!(2, lines.len());
+    assert_eq!("Man: Third wish?", lines[1]);
+}
+
+#[test]
+fn toggles_visibility_with_key() {
+    let mut app = App::new();
+    setup_debugger(&mut app);
+    app.update();
+    assert!(!app.world().resource::<DialogueDebugger>().visible);
+
+    app.world_mut().send_event(KeyboardInput {
+        key_code: KeyCode::F12,
+        logical_key: Key::F12,
+        state: ButtonState::Pressed,
+        window: Entity::PLACEHOLDER,
+    });
+    app.update();
+    assert!(app.world().resource::<DialogueDebugger>().visible);
+}
+
+fn setup_debugger(app: &mut App) -> Mut<'_, DialogueDebugger> {
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "lines.yarn",
+        )))
+        // Egui reads input and window events, but there is no window to draw the overlay in
+        .add_plugins((
+            InputPlugin,
+            WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                close_when_requested: false,
+            },
+        ))
+        .add_plugins(YarnSpinnerDebuggerPlugin::default());
+    app.world_mut().resource_mut::<DialogueDebugger>()
+}
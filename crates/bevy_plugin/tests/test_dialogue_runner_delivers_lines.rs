@@ -1,6 +1,7 @@
 use anyhow::Result;
 use bevy::prelude::*;
 use bevy_yarnspinner::{events::*, prelude::*};
+use std::time::Duration;
 use utils::prelude::*;
 
 mod utils;
@@ -63,6 +64,172 @@ fn stop_sends_events() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn pause_halts_dialogue_until_resumed() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    setup_dialogue_runner_without_localizations(&mut app).start_node("Start");
+    app.update();
+    asserter.clear_events(&mut app);
+
+    app.dialogue_runner_mut().pause().continue_in_next_update();
+    app.update();
+    app.update();
+    assert!(app.dialogue_runner().is_paused());
+    assert!(app.dialogue_runner().is_running());
+    assert_events!(asserter, app contains [PresentLineEvent (n = 0)]);
+
+    app.dialogue_runner_mut().resume();
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text == english_lines()[1],
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn auto_advance_continues_after_line_is_presented() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    setup_dialogue_runner_without_localizations(&mut app)
+        .set_auto_advance(Duration::ZERO)
+        .start_node("Start");
+    app.update();
+    asserter.clear_events(&mut app);
+
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text == english_lines()[1],
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn auto_advance_waits_for_delay() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    setup_dialogue_runner_without_localizations(&mut app)
+        .set_auto_advance(Duration::from_secs(3600))
+        .start_node("Start");
+    app.update();
+    asserter.clear_events(&mut app);
+
+    app.update();
+    app.update();
+    assert_events!(asserter, app contains [PresentLineEvent (n = 0)]);
+
+    Ok(())
+}
+
+#[test]
+fn auto_advance_waits_until_holds_are_released() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    setup_dialogue_runner_without_localizations(&mut app)
+        .set_auto_advance(Duration::ZERO)
+        .start_node("Start");
+    app.update();
+    asserter.clear_events(&mut app);
+
+    app.dialogue_runner_mut().hold_auto_advance();
+    app.update();
+    app.update();
+    assert_events!(asserter, app contains [PresentLineEvent (n = 0)]);
+
+    app.dialogue_runner_mut().release_auto_advance();
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text == english_lines()[1],
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn waits_for_all_registered_views_to_acknowledge_line() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    setup_dialogue_runner_without_localizations(&mut app)
+        .register_view("text")
+        .register_view("voice")
+        .start_node("Start");
+    app.update();
+    let line_id = last_presented_line_id(&app);
+    asserter.clear_events(&mut app);
+
+    app.dialogue_runner_mut()
+        .acknowledge_line("text", &line_id)
+        .continue_in_next_update();
+    app.update();
+    assert_events!(asserter, app contains [PresentLineEvent (n = 0)]);
+    assert_eq!(
+        vec!["voice"],
+        app.dialogue_runner()
+            .unacknowledged_views()
+            .collect::<Vec<_>>()
+    );
+
+    app.dialogue_runner_mut()
+        .acknowledge_line("voice", &line_id);
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text == english_lines()[1],
+    ]);
+    assert_eq!(2, app.dialogue_runner().unacknowledged_views().count());
+
+    Ok(())
+}
+
+#[test]
+fn ignores_acknowledgment_of_other_line() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    setup_dialogue_runner_without_localizations(&mut app)
+        .register_view("voice")
+        .start_node("Start");
+    app.update();
+    asserter.clear_events(&mut app);
+
+    app.dialogue_runner_mut()
+        .acknowledge_line("voice", &LineId("line:does_not_exist".to_owned()))
+        .continue_in_next_update();
+    app.update();
+    assert_events!(asserter, app contains [PresentLineEvent (n = 0)]);
+
+    app.dialogue_runner_mut().unregister_view("voice");
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text == english_lines()[1],
+    ]);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "no view named \"voice\" is registered")]
+fn panics_on_acknowledgment_of_unregistered_view() {
+    let mut app = App::new();
+    setup_dialogue_runner_without_localizations(&mut app).start_node("Start");
+    app.update();
+    let line_id = last_presented_line_id(&app);
+    app.dialogue_runner_mut()
+        .acknowledge_line("voice", &line_id);
+}
+
+#[test]
+fn reports_line_hints_as_ready_without_asset_providers() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    setup_dialogue_runner_without_localizations(&mut app).start_node("Start");
+    app.update();
+    assert_events!(asserter, app contains [LineHintsEvent, LineHintsReadyEvent]);
+    assert!(!app.dialogue_runner().is_preloading());
+
+    Ok(())
+}
+
 #[test]
 fn stop_resets_dialogue() -> Result<()> {
     let mut app = App::new();
@@ -133,6 +300,49 @@ fn presents_all_lines() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn keeps_position_and_variables_on_hot_reload() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    setup_dialogue_runner_without_localizations(&mut app).start_node("Start");
+    app.continue_dialogue_and_update_n_times(2);
+    app.dialogue_runner_mut()
+        .variable_storage_mut()
+        .set("$wishes".to_owned(), 2.into())?;
+    asserter.clear_events(&mut app);
+
+    {
+        let project = app.world().resource::<YarnProject>();
+        let handle = project.yarn_files().next().unwrap().clone();
+        let mut yarn_file_assets = app.world_mut().resource_mut::<Assets<YarnFile>>();
+        let yarn_file = yarn_file_assets.get_mut(&handle).unwrap();
+        let content = yarn_file
+            .content()
+            .replace("Man: Third wish?", "Man: A third wish?");
+        yarn_file.set_content(content)?;
+    }
+    // The project is compiled in the background, so the asset events of loading it may already be gone
+    app.update();
+    while !app
+        .world()
+        .resource::<Events<AssetEvent<YarnFile>>>()
+        .is_empty()
+    {
+        app.update();
+    }
+    assert_events!(asserter, app contains [
+        PresentLineEvent (n = 0),
+        DialogueCompleteEvent (n = 0),
+    ]);
+
+    app.continue_dialogue_and_update();
+    assert_events!(asserter, app contains
+        PresentLineEvent with |event| event.line.text == "Man: A third wish?");
+    let wishes = app.dialogue_runner().variable_storage().get("$wishes")?;
+    assert_eq!(YarnValue::from(2), wishes);
+    Ok(())
+}
+
 #[test]
 #[should_panic]
 fn panics_on_continue_after_all_lines() {
@@ -215,6 +425,141 @@ fn serves_translations() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn relocalizes_current_line_when_project_language_changes() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    setup_dialogue_runner_with_localizations(&mut app).start_node("Start");
+    app.load_lines().update();
+    assert_events!(asserter, app contains
+        PresentLineEvent with |event| event.line.text == english_lines()[0]
+    );
+
+    app.world_mut()
+        .resource_mut::<YarnProject>()
+        .set_text_language("de-CH");
+    app.update();
+    let language_changed_events = app.world().resource::<Events<LanguageChangedEvent>>();
+    let language_changed_events: Vec<_> = language_changed_events
+        .get_reader()
+        .read(language_changed_events)
+        .cloned()
+        .collect();
+    assert_eq!(
+        vec![LanguageChangedEvent {
+            text_language: Some("de-CH".into()),
+            asset_language: None,
+        }],
+        language_changed_events
+    );
+    assert_eq!(
+        Some(Language::from("de-CH")),
+        app.dialogue_runner().text_language()
+    );
+
+    app.load_lines().update();
+    assert_events!(asserter, app contains
+        PresentLineEvent with |event| event.line.text == german_lines()[0]
+    );
+
+    app.continue_dialogue_and_update();
+    assert_events!(asserter, app contains
+        PresentLineEvent with |event| event.line.text == german_lines()[1]
+    );
+    Ok(())
+}
+
+#[test]
+fn dialogue_runners_use_language_of_project() {
+    let mut app = App::new();
+    setup_dialogue_runner_with_localizations(&mut app);
+    let mut project = app.world_mut().resource_mut::<YarnProject>();
+    project.set_text_language("de-CH");
+    assert_eq!(Some(Language::from("de-CH")), project.text_language());
+    assert_eq!(Some(Language::from("en-US")), project.asset_language());
+
+    let dialogue_runner = project.create_dialogue_runner();
+    assert_eq!(
+        Some(Language::from("de-CH")),
+        dialogue_runner.text_language()
+    );
+}
+
+#[test]
+fn dialogue_runners_share_variable_storage_of_project_unless_isolated() -> Result<()> {
+    let mut app = App::new();
+    setup_dialogue_runner_without_localizations(&mut app);
+    let project = app.world().resource::<YarnProject>();
+    let mut main_story_runner = project.create_dialogue_runner();
+    let other_main_story_runner = project.create_dialogue_runner();
+    let ambient_runner = project
+        .build_dialogue_runner()
+        .with_isolated_variable_storage()
+        .build();
+
+    main_story_runner
+        .variable_storage_mut()
+        .set("$met_hag".to_owned(), true.into())?;
+    assert_eq!(
+        YarnValue::from(true),
+        other_main_story_runner.variable_storage().get("$met_hag")?
+    );
+    assert_eq!(
+        YarnValue::from(true),
+        project.variable_storage().get("$met_hag")?
+    );
+    assert!(ambient_runner.variable_storage().get("$met_hag").is_err());
+    Ok(())
+}
+
+#[test]
+fn systems_after_dialogue_execution_see_events_in_same_frame() {
+    #[derive(Resource, Default)]
+    struct PresentedLines(Vec<String>);
+
+    let mut app = App::new();
+    setup_dialogue_runner_without_localizations(&mut app).start_node("Start");
+    app.init_resource::<PresentedLines>().add_systems(
+        Update,
+        (|mut events: EventReader<PresentLineEvent>, mut lines: ResMut<PresentedLines>| {
+            lines
+                .0
+                .extend(events.read().map(|event| event.line.text.clone()));
+        })
+        .after(DialogueExecutionSystemSet),
+    );
+    app.update();
+    assert_eq!(
+        vec![english_lines()[0].clone()],
+        app.world().resource::<PresentedLines>().0
+    );
+}
+
+#[test]
+fn dialogue_active_is_true_while_a_dialogue_runs() {
+    #[derive(Resource, Default)]
+    struct ActiveFrames(usize);
+
+    let mut app = App::new();
+    setup_dialogue_runner_without_localizations(&mut app);
+    app.init_resource::<ActiveFrames>().add_systems(
+        Update,
+        (|mut frames: ResMut<ActiveFrames>| frames.0 += 1)
+            .run_if(dialogue_active)
+            .after(DialogueExecutionSystemSet),
+    );
+    app.update();
+    assert_eq!(0, app.world().resource::<ActiveFrames>().0);
+
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    assert_eq!(1, app.world().resource::<ActiveFrames>().0);
+
+    app.dialogue_runner_mut().stop();
+    app.update();
+    assert_eq!(1, app.world().resource::<ActiveFrames>().0);
+}
+
 #[test]
 fn default_language_is_none_without_localizations() {
     let mut app = App::new();
@@ -260,6 +605,17 @@ fn default_language_is_base_language() {
     }
 }
 
+fn last_presented_line_id(app: &App) -> LineId {
+    app.world()
+        .resource::<Events<PresentLineEvent>>()
+        .iter_current_update_events()
+        .last()
+        .unwrap()
+        .line
+        .id
+        .clone()
+}
+
 fn setup_dialogue_runner_without_localizations(app: &mut App) -> Mut<DialogueRunner> {
     app.setup_default_plugins()
         .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
@@ -306,7 +662,6 @@ fn english_lines() -> Vec<String> {
     lines
 }
 
-#[cfg(feature = "audio_assets")]
 fn german_lines() -> Vec<String> {
     let file = include_str!("../assets/dialogue/de-CH.strings.csv");
     let mut reader = csv::Reader::from_reader(file.as_bytes());
@@ -2,6 +2,7 @@ use anyhow::Result;
 use bevy::prelude::*;
 use bevy_yarnspinner::{events::*, prelude::*};
 use std::fs;
+use std::time::Duration;
 use tempfile::tempdir;
 use utils::prelude::*;
 
@@ -75,6 +76,25 @@ fn option_selection_implies_continue() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn reports_selected_option() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    app.setup_dialogue_runner().start_node("Start");
+    app.continue_dialogue_and_update_n_times(4);
+    assert_events!(asserter, app contains OptionSelectedEvent (n = 0));
+    asserter.clear_events(&mut app);
+
+    app.dialogue_runner_mut().select_option(OptionId(1))?;
+    app.update();
+    assert_events!(asserter, app contains [
+        OptionSelectedEvent with |event| event.option.id == OptionId(1) && event.option.line.text == "You: (Simply keep on non-existing.)",
+        PresentLineEvent,
+    ]);
+
+    Ok(())
+}
+
 #[test]
 fn can_show_option_selection_as_line() -> Result<()> {
     let mut app = App::new();
@@ -158,6 +178,7 @@ fn can_select_unavailable_choice() -> Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "development_file_generation")]
 #[test]
 fn generates_files_in_dev_mode() -> Result<()> {
     let dir = tempdir()?;
@@ -178,8 +199,99 @@ fn generates_files_in_dev_mode() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn choice_timer_selects_first_available_option() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    app.setup_dialogue_runner()
+        .set_choice_timer(ChoiceTimer::new(Duration::ZERO))
+        .start_node("Start");
+    app.continue_dialogue_and_update_n_times(4);
+    asserter.clear_events(&mut app);
+
+    app.update();
+    assert_events!(asserter, app contains [
+        ChoiceTimerTickEvent with |event| event.remaining == Duration::ZERO,
+        PresentLineEvent with |event| event.line.text == lines()[6],
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn choice_timer_selects_random_available_option() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    app.setup_dialogue_runner()
+        .set_choice_timer(ChoiceTimer::new(Duration::ZERO).with_fallback(ChoiceFallback::Random))
+        .start_node("Start");
+    app.continue_dialogue_and_update_n_times(4);
+    asserter.clear_events(&mut app);
+
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text == lines()[6] || event.line.text == lines()[10],
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn choice_timer_ticks_until_option_is_selected() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    app.setup_dialogue_runner()
+        .set_choice_timer(ChoiceTimer::new(Duration::from_secs(3600)))
+        .start_node("Start");
+    app.continue_dialogue_and_update_n_times(4);
+    asserter.clear_events(&mut app);
+
+    app.update();
+    assert_events!(asserter, app contains [
+        ChoiceTimerTickEvent with |event| event.duration == Duration::from_secs(3600) && event.remaining > Duration::ZERO,
+        PresentLineEvent (n = 0),
+    ]);
+    assert!(app.dialogue_runner().remaining_choice_time().is_some());
+
+    app.dialogue_runner_mut().select_option(OptionId(1))?;
+    app.update();
+    assert_events!(asserter, app contains [
+        ChoiceTimerTickEvent (n = 0),
+        PresentLineEvent with |event| event.line.text == lines()[10],
+    ]);
+    assert!(app.dialogue_runner().remaining_choice_time().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn choice_timer_respects_timeout_and_default_tags() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(
+        dir.path().join("timed_options.yarn"),
+        "title: Start\n---\n-> Cut the red wire. #timeout:0\n    Boom.\n-> Cut the blue wire. #default\n    Phew.\n===\n",
+    )?;
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    app.setup_default_plugins_for_path(dir.path()).add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("timed_options.yarn"))
+            .with_development_file_generation(DevelopmentFileGeneration::None),
+    );
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    asserter.clear_events(&mut app);
+
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text == "Phew.",
+    ]);
+
+    Ok(())
+}
+
 trait OptionTestAppExt {
     fn setup_dialogue_runner(&mut self) -> Mut<DialogueRunner>;
+    #[cfg(feature = "development_file_generation")]
     fn setup_dialogue_runner_in_dev_mode(&mut self) -> Mut<DialogueRunner>;
 }
 
@@ -192,6 +304,7 @@ impl OptionTestAppExt for App {
             .dialogue_runner_mut()
     }
 
+    #[cfg(feature = "development_file_generation")]
     fn setup_dialogue_runner_in_dev_mode(&mut self) -> Mut<DialogueRunner> {
         self.add_plugins(
             YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("options.yarn"))
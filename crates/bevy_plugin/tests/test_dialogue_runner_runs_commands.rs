@@ -43,6 +43,37 @@ fn waits_on_command() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn pausing_freezes_wait_command() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    app.setup_dialogue_runner_for_wait().start_node("Start");
+    app.update();
+    app.continue_dialogue_and_update();
+    assert_events!(asserter, app contains [PresentLineEvent, ExecuteCommandEvent]);
+
+    app.dialogue_runner_mut().pause();
+    let now = Instant::now();
+    while now.elapsed().as_millis() <= 1200 {
+        app.continue_dialogue_and_update();
+        assert_events!(asserter, app contains [PresentLineEvent (n = 0)]);
+    }
+
+    app.dialogue_runner_mut().resume();
+    let now = Instant::now();
+    while now.elapsed().as_millis() <= 950 {
+        app.continue_dialogue_and_update();
+        assert_events!(asserter, app contains [PresentLineEvent (n = 0)]);
+    }
+    sleep(std::time::Duration::from_millis(150));
+    app.continue_dialogue_and_update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text == "Ended wait",
+    ]);
+
+    Ok(())
+}
+
 #[test]
 fn executes_commands_and_fns() -> Result<()> {
     let mut app = App::new();
@@ -110,9 +141,76 @@ fn executes_commands_and_fns() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn sends_event_when_script_sets_variable() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    app.setup_dialogue_runner().start_node("Start");
+    app.update();
+    assert_events!(asserter, app contains [VariableChangedEvent (n = 0)]);
+
+    app.continue_dialogue_and_update();
+    assert_events!(asserter, app contains [
+        VariableChangedEvent with |event|
+            event.name == "$data" &&
+            event.new_value == YarnValue::from("foo"),
+    ]);
+
+    app.dialogue_runner_mut()
+        .variable_storage_mut()
+        .set("$data".to_owned(), "bar".into())?;
+    app.continue_dialogue_and_update();
+    assert_events!(asserter, app contains [VariableChangedEvent (n = 0)]);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(
+    expected = r#"Failed to pass the arguments ("foo") to the command "set_data" with the signature"#
+)]
+fn panics_with_command_name_on_argument_mismatch() {
+    let mut app = App::new();
+    let mut dialogue_runner = app.setup_dialogue_runner();
+    dialogue_runner
+        .commands_mut()
+        .add_command("set_data", |_: In<(String, f32)>| {});
+    dialogue_runner.start_node("Start");
+    app.update();
+    app.continue_dialogue_and_update_n_times(2);
+}
+
+#[test]
+fn calls_system_functions() -> Result<()> {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    app.insert_resource(Repetitions(2));
+    let mut dialogue_runner = app.setup_dialogue_runner();
+    dialogue_runner.add_system_function(
+        "triplicate_data",
+        |In(data): In<String>, repetitions: Res<Repetitions>| data.repeat(repetitions.0),
+    );
+    dialogue_runner.start_node("Start");
+    app.update();
+    app.continue_dialogue_and_update_n_times(3);
+    asserter.clear_events(&mut app);
+
+    app.continue_dialogue_and_update();
+    assert_events!(asserter, app contains PresentLineEvent (n = 0));
+
+    app.update(); // The function ran after the dialogue paused for it
+    assert_events!(asserter, app contains
+        PresentLineEvent with |event| event.line.text == "Data three times is foofoo");
+
+    Ok(())
+}
+
 #[derive(Debug, Resource)]
 struct Data(String);
 
+#[derive(Debug, Resource)]
+struct Repetitions(usize);
+
 trait CommandAppExt {
     fn setup_dialogue_runner(&mut self) -> Mut<DialogueRunner>;
     fn setup_dialogue_runner_for_wait(&mut self) -> Mut<DialogueRunner>;
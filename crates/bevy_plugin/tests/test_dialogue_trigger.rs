@@ -0,0 +1,130 @@
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, prelude::*};
+use std::time::Duration;
+use utils::prelude::*;
+
+mod utils;
+
+#[test]
+fn proximity_trigger_starts_dialogue_when_interactor_enters_radius() {
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    let player = app.setup_npc(DialogueTrigger::on_proximity("Start", 2.0));
+    app.update();
+    assert!(!app.dialogue_runner().is_running());
+
+    move_to(&mut app, player, Vec3::X);
+    app.update();
+    assert!(app.dialogue_runner().is_running());
+    assert_events!(asserter, app contains [DialogueStartEvent, PresentLineEvent]);
+}
+
+#[test]
+fn proximity_trigger_does_not_restart_while_interactor_stays_in_range() {
+    let mut app = App::new();
+    let player = app.setup_npc(DialogueTrigger::on_proximity("Start", 2.0));
+    move_to(&mut app, player, Vec3::X);
+    app.update();
+    app.dialogue_runner_mut().stop();
+    app.update();
+    app.update();
+    assert!(!app.dialogue_runner().is_running());
+
+    move_to(&mut app, player, Vec3::X * 10.0);
+    app.update();
+    move_to(&mut app, player, Vec3::X);
+    app.update();
+    assert!(app.dialogue_runner().is_running());
+}
+
+#[test]
+fn interaction_trigger_starts_dialogue_on_key_press_in_range() {
+    let mut app = App::new();
+    let player = app.setup_npc(DialogueTrigger::on_interaction("Start", 2.0));
+    app.init_resource::<ButtonInput<KeyCode>>();
+    move_to(&mut app, player, Vec3::X);
+    app.update();
+    assert!(!app.dialogue_runner().is_running());
+
+    press_interaction_key(&mut app);
+    app.update();
+    assert!(app.dialogue_runner().is_running());
+}
+
+#[test]
+fn interaction_trigger_ignores_key_press_out_of_range() {
+    let mut app = App::new();
+    app.setup_npc(DialogueTrigger::on_interaction("Start", 2.0));
+    app.init_resource::<ButtonInput<KeyCode>>();
+    press_interaction_key(&mut app);
+    app.update();
+    assert!(!app.dialogue_runner().is_running());
+}
+
+#[test]
+fn only_once_trigger_does_not_start_dialogue_again() {
+    let mut app = App::new();
+    let player = app.setup_npc(DialogueTrigger::on_proximity("Start", 2.0).only_once());
+    move_to(&mut app, player, Vec3::X);
+    app.update();
+    app.dialogue_runner_mut().stop();
+    move_to(&mut app, player, Vec3::X * 10.0);
+    app.update();
+
+    move_to(&mut app, player, Vec3::X);
+    app.update();
+    assert!(!app.dialogue_runner().is_running());
+}
+
+#[test]
+fn trigger_waits_for_cooldown() {
+    let mut app = App::new();
+    let player = app.setup_npc(
+        DialogueTrigger::on_proximity("Start", 2.0).with_cooldown(Duration::from_secs(3600)),
+    );
+    move_to(&mut app, player, Vec3::X);
+    app.update();
+    app.dialogue_runner_mut().stop();
+    move_to(&mut app, player, Vec3::X * 10.0);
+    app.update();
+
+    move_to(&mut app, player, Vec3::X);
+    app.update();
+    assert!(!app.dialogue_runner().is_running());
+}
+
+fn move_to(app: &mut App, entity: Entity, translation: Vec3) {
+    *app.world_mut().get_mut::<GlobalTransform>(entity).unwrap() =
+        GlobalTransform::from_translation(translation);
+}
+
+fn press_interaction_key(app: &mut App) {
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.release(KeyCode::KeyE);
+    keys.clear();
+    keys.press(KeyCode::KeyE);
+}
+
+trait TriggerAppExt {
+    /// Spawns a dialogue runner with the given trigger at the origin and returns a player far away from it.
+    fn setup_npc(&mut self, trigger: DialogueTrigger) -> Entity;
+}
+
+impl TriggerAppExt for App {
+    fn setup_npc(&mut self, trigger: DialogueTrigger) -> Entity {
+        self.setup_default_plugins()
+            .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+                "lines.yarn",
+            )));
+        let npc = self.dialogue_runner_entity();
+        self.world_mut()
+            .entity_mut(npc)
+            .insert((GlobalTransform::default(), trigger));
+        self.world_mut()
+            .spawn((
+                GlobalTransform::from_translation(Vec3::X * 10.0),
+                DialogueInteraction::default(),
+            ))
+            .id()
+    }
+}
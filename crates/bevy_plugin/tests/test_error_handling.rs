@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, prelude::*};
+use utils::prelude::*;
+
+mod utils;
+
+#[test]
+fn sends_event_for_failing_command() {
+    let mut app = App::new();
+    setup_dialogue_runner_with_mismatched_command(&mut app, YarnErrorHandling::SendEvent);
+    app.update();
+    app.continue_dialogue_and_update_n_times(2);
+
+    let dialogue_runner = app.dialogue_runner_entity();
+    let errors = error_events(&app);
+    assert_eq!(1, errors.len());
+    assert_eq!(YarnErrorContext::CommandExecution, errors[0].context);
+    assert_eq!(Some(dialogue_runner), errors[0].source);
+    assert!(errors[0].error.to_string().contains("set_data"));
+
+    app.update();
+    assert!(app.dialogue_runner().is_running());
+}
+
+#[test]
+#[should_panic(expected = "set_data")]
+fn panics_on_failing_command_by_default() {
+    let mut app = App::new();
+    setup_dialogue_runner_with_mismatched_command(&mut app, YarnErrorHandling::Panic);
+    app.update();
+    app.continue_dialogue_and_update_n_times(2);
+}
+
+#[test]
+fn falls_back_to_base_language_on_missing_strings_file() {
+    let mut app = App::new();
+    app.setup_default_plugins().add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(Localizations {
+                base_localization: "en-US".into(),
+                translations: vec!["fr-FR".into()],
+            })
+            .with_development_file_generation(DevelopmentFileGeneration::None)
+            .with_error_handling(YarnErrorHandling::SendEvent),
+    );
+    app.dialogue_runner_mut().set_text_language("fr-FR");
+    app.load_lines();
+
+    let line = app
+        .dialogue_runner()
+        .text_provider()
+        .get_text(&LineId("line:3".to_owned()))
+        .unwrap();
+    assert_eq!("Man: Third wish?", line);
+    let errors = error_events(&app);
+    assert_eq!(1, errors.len());
+    assert_eq!(YarnErrorContext::Localization, errors[0].context);
+}
+
+fn error_events(app: &App) -> Vec<YarnErrorEvent> {
+    let events = app.world().resource::<Events<YarnErrorEvent>>();
+    events.get_reader().read(events).cloned().collect()
+}
+
+fn setup_dialogue_runner_with_mismatched_command(app: &mut App, error_handling: YarnErrorHandling) {
+    app.setup_default_plugins().add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("commands.yarn"))
+            .with_error_handling(error_handling),
+    );
+    let mut dialogue_runner = app.dialogue_runner_mut();
+    dialogue_runner
+        .commands_mut()
+        .add_command("set_data", |_: In<(String, f32)>| {});
+    dialogue_runner.start_node("Start");
+}
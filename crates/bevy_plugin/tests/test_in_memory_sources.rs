@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, include_yarn, prelude::*};
+#[cfg(feature = "development_file_generation")]
+use tempfile::tempdir;
+use utils::prelude::*;
+
+mod utils;
+
+#[test]
+fn runs_embedded_yarn_file() {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(include_yarn!(
+            "../assets/lines.yarn"
+        )));
+    let mut asserter = EventAsserter::new();
+
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text.starts_with("An elderly man was sitting alone on a dark path."),
+    ]);
+}
+
+#[test]
+fn runs_yarn_file_generated_at_runtime() {
+    let content = (1..=3)
+        .map(|n| format!("Narrator: Line number {n}."))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let yarn_file = YarnFile::try_new(
+        "generated.yarn",
+        format!("title: Start\n---\n{content}\n===\n"),
+    )
+    .unwrap();
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(yarn_file));
+    let mut asserter = EventAsserter::new();
+
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    asserter.clear_events(&mut app);
+    app.continue_dialogue_and_update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text == "Narrator: Line number 2.",
+    ]);
+}
+
+#[test]
+fn fails_on_invalid_generated_content() {
+    assert!(YarnFile::try_new("broken.yarn", "title: Start\n---\n<<if>>\n").is_err());
+}
+
+#[cfg(feature = "development_file_generation")]
+#[test]
+fn generates_line_ids_in_memory() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let mut app = App::new();
+    app.setup_default_plugins_for_path(dir.path()).add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(include_yarn!("../assets/lines.yarn"))
+            .with_localizations(Localizations {
+                base_localization: "en-US".into(),
+                translations: vec![],
+            })
+            .with_development_file_generation(DevelopmentFileGeneration::Full),
+    );
+
+    let handle = app.load_project().yarn_files().next().unwrap().clone();
+    let yarn_files = app.world().resource::<Assets<YarnFile>>();
+    let yarn_file = yarn_files.get(&handle).unwrap();
+    assert!(yarn_file.content().contains("#line:"));
+    assert!(!dir.path().join("lines.yarn").exists());
+    Ok(())
+}
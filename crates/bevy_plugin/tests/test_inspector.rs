@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, prelude::*};
+use utils::prelude::*;
+
+mod utils;
+
+const QUEST: &str = r#"title: Start
+---
+<<declare $gold = 0>>
+Guard: Halt!
+<<set $gold to 10>>
+Guard: Move along.
+===
+title: Other
+---
+Nothing here.
+===
+"#;
+
+#[test]
+fn refreshes_dialogue_runner_state() {
+    let mut app = setup_app();
+    let entity = app.dialogue_runner_entity();
+    app.world_mut()
+        .entity_mut(entity)
+        .insert(DialogueRunnerState::default());
+
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    let state = app.world().get::<DialogueRunnerState>(entity).unwrap();
+    assert_eq!(Some("Start".to_string()), state.current_node);
+    assert!(state.is_running);
+    assert!(!state.is_paused);
+    assert_eq!(Some(&YarnValue::Number(0.0)), state.variables.get("$gold"));
+    assert_eq!(1, state.metrics.lines_delivered);
+
+    app.continue_dialogue_and_update();
+    let state = app.world().get::<DialogueRunnerState>(entity).unwrap();
+    assert_eq!(Some(&YarnValue::Number(10.0)), state.variables.get("$gold"));
+    assert_eq!(2, state.metrics.lines_delivered);
+}
+
+#[test]
+fn writes_edited_state_back_to_dialogue_runner() {
+    let mut app = setup_app();
+    let entity = app.dialogue_runner_entity();
+    app.world_mut()
+        .entity_mut(entity)
+        .insert(DialogueRunnerState::default());
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+
+    {
+        let mut state = app
+            .world_mut()
+            .get_mut::<DialogueRunnerState>(entity)
+            .unwrap();
+        state.is_paused = true;
+        state
+            .variables
+            .insert("$gold".to_string(), YarnValue::Number(99.0));
+    }
+    app.update();
+
+    let dialogue_runner = app.dialogue_runner();
+    assert!(dialogue_runner.is_paused());
+    assert_eq!(
+        YarnValue::Number(99.0),
+        dialogue_runner.variable_storage().get("$gold").unwrap()
+    );
+}
+
+#[test]
+fn summarizes_yarn_project() {
+    let mut app = setup_app();
+    app.load_project();
+    app.update();
+
+    let info = app.world().resource::<YarnProjectInfo>();
+    assert_eq!(
+        vec!["Other".to_string(), "Start".to_string()],
+        info.node_names
+    );
+    assert_eq!(3, info.line_count);
+    assert_eq!(app.world().resource::<YarnProject>().id(), info.id);
+}
+
+#[test]
+fn registers_public_types_for_reflection() {
+    let app = setup_app();
+    let registry = app.world().resource::<AppTypeRegistry>().read();
+    assert!(registry.contains(std::any::TypeId::of::<DialogueRunnerState>()));
+    assert!(registry.contains(std::any::TypeId::of::<YarnProjectInfo>()));
+    assert!(registry.contains(std::any::TypeId::of::<LocalizedLine>()));
+    assert!(registry.contains(std::any::TypeId::of::<DialogueOption>()));
+    assert!(registry.contains(std::any::TypeId::of::<PresentLineEvent>()));
+    assert!(registry.contains(std::any::TypeId::of::<VariableChangedEvent>()));
+    assert!(registry.contains(std::any::TypeId::of::<Language>()));
+}
+
+fn setup_app() -> App {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFile::new(
+            "quest.yarn",
+            QUEST,
+        )));
+    app
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "text")]
+
+use bevy::prelude::*;
+use bevy_yarnspinner::prelude::*;
+use utils::prelude::*;
+
+mod utils;
+
+const DEFAULT_FONT: Handle<Font> = Handle::weak_from_u128(1);
+const GERMAN_FONT: Handle<Font> = Handle::weak_from_u128(2);
+const SWISS_GERMAN_FONT: Handle<Font> = Handle::weak_from_u128(3);
+
+#[test]
+fn falls_back_to_primary_language() {
+    let fonts = LanguageFonts::new()
+        .with_font("de", GERMAN_FONT)
+        .with_font("de-CH", SWISS_GERMAN_FONT);
+
+    assert_eq!(Some(&SWISS_GERMAN_FONT), fonts.font(&"de-CH".into()));
+    assert_eq!(Some(&GERMAN_FONT), fonts.font(&"de-AT".into()));
+    assert_eq!(None, fonts.font(&"en-US".into()));
+    assert_eq!(
+        DEFAULT_FONT,
+        fonts.font_or(Some(&"en-US".into()), &DEFAULT_FONT)
+    );
+}
+
+#[test]
+fn switches_font_of_text_when_language_changes() {
+    let mut app = App::new();
+    app.setup_default_plugins().add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(Localizations {
+                base_localization: "en-US".into(),
+                translations: vec!["de-CH".into()],
+            })
+            .with_development_file_generation(DevelopmentFileGeneration::None),
+    );
+    app.load_project();
+    app.world_mut()
+        .resource_mut::<LanguageFonts>()
+        .insert("de", GERMAN_FONT);
+    let text = app
+        .world_mut()
+        .spawn((
+            Text::from_sections([
+                TextSection::new("Hag: ", TextStyle::default()),
+                TextSection::new("Third wish?", TextStyle::default()),
+            ]),
+            LanguageFontText::new(DEFAULT_FONT),
+        ))
+        .id();
+    app.update();
+    assert_fonts(&app, text, &DEFAULT_FONT);
+
+    app.world_mut()
+        .resource_mut::<YarnProject>()
+        .set_text_language("de-CH");
+    app.update();
+    assert_fonts(&app, text, &GERMAN_FONT);
+}
+
+#[test]
+fn markup_styles_use_language_font() {
+    let fonts = LanguageFonts::new().with_font("de", GERMAN_FONT);
+    let styles = MarkupTextStyles::new(TextStyle {
+        font: DEFAULT_FONT,
+        ..default()
+    });
+
+    assert_eq!(
+        GERMAN_FONT,
+        styles.for_language(&"de-CH".into(), &fonts).base.font
+    );
+    assert_eq!(
+        DEFAULT_FONT,
+        styles.for_language(&"en-US".into(), &fonts).base.font
+    );
+}
+
+fn assert_fonts(app: &App, entity: Entity, font: &Handle<Font>) {
+    let text = app.world().get::<Text>(entity).unwrap();
+    assert!(text
+        .sections
+        .iter()
+        .all(|section| section.style.font == *font));
+}
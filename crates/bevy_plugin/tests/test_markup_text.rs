@@ -0,0 +1,80 @@
+#![cfg(feature = "text")]
+
+use bevy::color::palettes::css;
+use bevy::prelude::*;
+use bevy_yarnspinner::prelude::*;
+use std::collections::HashMap;
+
+#[test]
+fn converts_plain_text_into_single_section() {
+    let styles = MarkupTextStyles::default();
+    let sections = styles.to_sections("Hello there", &[]);
+    assert_eq!(1, sections.len());
+    assert_eq!("Hello there", sections[0].value);
+}
+
+#[test]
+fn applies_color_attribute() {
+    let styles = MarkupTextStyles::default();
+    let sections = styles.to_sections(
+        "I am very angry",
+        &[attribute("color", 5, 4, Some(("color", "red")))],
+    );
+    let values: Vec<_> = sections.iter().map(|s| s.value.as_str()).collect();
+    assert_eq!(vec!["I am ", "very", " angry"], values);
+    assert_eq!(Color::from(css::RED), sections[1].style.color);
+    assert_eq!(styles.base.color, sections[0].style.color);
+    assert_eq!(styles.base.color, sections[2].style.color);
+}
+
+#[test]
+fn applies_custom_rules_to_overlapping_attributes() {
+    let styles = MarkupTextStyles::default()
+        .with_font_size("big", 50.0)
+        .with_color("angry", css::RED);
+    let sections = styles.to_sections(
+        "abcdef",
+        &[attribute("big", 0, 4, None), attribute("angry", 2, 4, None)],
+    );
+    let values: Vec<_> = sections.iter().map(|s| s.value.as_str()).collect();
+    assert_eq!(vec!["ab", "cd", "ef"], values);
+    assert_eq!(50.0, sections[0].style.font_size);
+    assert_eq!(styles.base.color, sections[0].style.color);
+    assert_eq!(50.0, sections[1].style.font_size);
+    assert_eq!(Color::from(css::RED), sections[1].style.color);
+    assert_eq!(styles.base.font_size, sections[2].style.font_size);
+    assert_eq!(Color::from(css::RED), sections[2].style.color);
+}
+
+#[test]
+fn ignores_attributes_without_rule() {
+    let styles = MarkupTextStyles::default();
+    let sections = styles.to_sections("Man: Hi", &[attribute("character", 0, 5, None)]);
+    assert_eq!(1, sections.len());
+}
+
+#[test]
+fn measures_attributes_in_graphemes() {
+    let styles = MarkupTextStyles::default().with_font_size("big", 50.0);
+    let sections = styles.to_sections("héllo wörld", &[attribute("big", 6, 5, None)]);
+    let values: Vec<_> = sections.iter().map(|s| s.value.as_str()).collect();
+    assert_eq!(vec!["héllo ", "wörld"], values);
+}
+
+fn attribute(
+    name: &str,
+    position: usize,
+    length: usize,
+    property: Option<(&str, &str)>,
+) -> MarkupAttribute {
+    MarkupAttribute {
+        name: name.to_owned(),
+        position,
+        length,
+        properties: property
+            .map(|(key, value)| (key.to_owned(), MarkupValue::String(value.to_owned())))
+            .into_iter()
+            .collect::<HashMap<_, _>>(),
+        source_position: 0,
+    }
+}
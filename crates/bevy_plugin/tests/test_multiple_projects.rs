@@ -0,0 +1,130 @@
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+use bevy_yarnspinner::{deferred_loading::LoadYarnProjectEvent, events::*, prelude::*};
+use utils::prelude::*;
+
+mod utils;
+
+#[test]
+fn loads_additional_project_next_to_primary_project() {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "lines.yarn",
+        )));
+    let primary_id = app.load_project().id();
+    let entity = app
+        .world_mut()
+        .spawn(AdditionalYarnProject::new([YarnFileSource::file(
+            "options.yarn",
+        )]))
+        .id();
+    let additional_id = load_additional_project(&mut app, entity).id();
+    assert_ne!(primary_id, additional_id);
+    assert!(app.world().get::<AdditionalYarnProject>(entity).is_none());
+
+    let mut system_state: SystemState<YarnProjects> = SystemState::new(app.world_mut());
+    let projects = system_state.get(app.world());
+    let ids: Vec<_> = projects.iter().map(|project| project.id()).collect();
+    assert_eq!(vec![primary_id, additional_id], ids);
+    assert_eq!(
+        Some(primary_id),
+        projects.primary().map(|project| project.id())
+    );
+    assert!(projects
+        .get(additional_id)
+        .unwrap()
+        .headers_for_node("Hub0")
+        .is_some());
+}
+
+#[test]
+fn dialogue_runners_run_nodes_of_their_own_project() {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "lines.yarn",
+        )));
+    app.load_project();
+    let entity = app
+        .world_mut()
+        .spawn(AdditionalYarnProject::new([YarnFileSource::file(
+            "options.yarn",
+        )]))
+        .id();
+    let dialogue_runner = load_additional_project(&mut app, entity).create_dialogue_runner();
+    let runner = app.world_mut().spawn(dialogue_runner).id();
+    let mut asserter = EventAsserter::new();
+    asserter.clear_events(&mut app);
+
+    app.world_mut()
+        .get_mut::<DialogueRunner>(runner)
+        .unwrap()
+        .start_node("Start");
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text.starts_with("Ancient Reptilian Brain: There is nothing."),
+    ]);
+}
+
+#[test]
+fn language_change_only_affects_dialogue_runners_of_changed_project() {
+    let localizations = Localizations {
+        base_localization: "en-US".into(),
+        translations: vec!["de-CH".into()],
+    };
+    let mut app = App::new();
+    app.setup_default_plugins().add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(localizations.clone())
+            .with_development_file_generation(DevelopmentFileGeneration::None),
+    );
+    let primary_runner = app.load_project().create_dialogue_runner();
+    let primary_runner = app.world_mut().spawn(primary_runner).id();
+    let entity = app
+        .world_mut()
+        .spawn(AdditionalYarnProject::new(
+            LoadYarnProjectEvent::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+                .with_localizations(localizations),
+        ))
+        .id();
+    let additional_runner = load_additional_project(&mut app, entity).create_dialogue_runner();
+    let additional_runner = app.world_mut().spawn(additional_runner).id();
+
+    app.world_mut()
+        .resource_mut::<YarnProject>()
+        .set_text_language("de-CH");
+    app.update();
+    assert_eq!(
+        Some(Language::from("de-CH")),
+        text_language(&app, primary_runner)
+    );
+    assert_eq!(
+        Some(Language::from("en-US")),
+        text_language(&app, additional_runner)
+    );
+
+    app.world_mut()
+        .get_mut::<YarnProject>(entity)
+        .unwrap()
+        .set_text_language("de-CH");
+    app.update();
+    assert_eq!(
+        Some(Language::from("de-CH")),
+        text_language(&app, additional_runner)
+    );
+}
+
+fn load_additional_project(app: &mut App, entity: Entity) -> &YarnProject {
+    while app.world().get::<YarnProject>(entity).is_none() {
+        app.update();
+    }
+    app.world().get::<YarnProject>(entity).unwrap()
+}
+
+fn text_language(app: &App, entity: Entity) -> Option<Language> {
+    app.world()
+        .get::<DialogueRunner>(entity)
+        .unwrap()
+        .text_language()
+}
@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, prelude::*};
+use utils::prelude::*;
+
+mod utils;
+
+#[test]
+fn highlights_first_available_option_when_options_are_presented() {
+    let mut app = App::new();
+    setup_options(&mut app, OptionNavigation::default());
+
+    let navigation = navigation(&mut app);
+    assert_eq!(2, navigation.options().len());
+    assert_eq!(Some(0), navigation.highlighted_index());
+    assert_eq!(
+        "You: Never ever ever?",
+        navigation.highlighted_option().unwrap().line.text
+    );
+}
+
+#[test]
+fn keys_move_highlight_and_confirm_selection() {
+    let mut app = App::new();
+    setup_options(&mut app, OptionNavigation::default());
+
+    press(&mut app, KeyCode::ArrowDown);
+    assert_eq!(Some(1), navigation(&mut app).highlighted_index());
+    press(&mut app, KeyCode::ArrowDown);
+    assert_eq!(Some(1), navigation(&mut app).highlighted_index());
+
+    let mut asserter = EventAsserter::new();
+    asserter.clear_events(&mut app);
+    press(&mut app, KeyCode::Enter);
+    assert!(!navigation(&mut app).is_active());
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text.starts_with("Ancient Reptilian Brain: An inordinate amount of time passes."),
+    ]);
+}
+
+#[test]
+fn number_keys_select_option_directly() {
+    let mut app = App::new();
+    setup_options(&mut app, OptionNavigation::default());
+
+    let mut asserter = EventAsserter::new();
+    asserter.clear_events(&mut app);
+    press(&mut app, KeyCode::Digit1);
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text == "Ancient Reptilian Brain: Never ever ever ever, baby!",
+    ]);
+}
+
+#[test]
+fn wraps_around_if_enabled() {
+    let mut app = App::new();
+    setup_options(&mut app, OptionNavigation::default().with_wrap_around(true));
+
+    press(&mut app, KeyCode::ArrowUp);
+    assert_eq!(Some(1), navigation(&mut app).highlighted_index());
+}
+
+#[test]
+fn events_drive_navigation_without_bindings() {
+    let mut app = App::new();
+    let source = setup_options(
+        &mut app,
+        OptionNavigation::with_bindings(OptionNavigationBindings::none()),
+    );
+
+    press(&mut app, KeyCode::ArrowDown);
+    assert_eq!(Some(0), navigation(&mut app).highlighted_index());
+
+    app.world_mut().send_event(OptionNavigationEvent {
+        action: OptionNavigationAction::Highlight(OptionId(1)),
+        source,
+    });
+    app.update();
+    assert_eq!(Some(1), navigation(&mut app).highlighted_index());
+
+    app.world_mut().send_event(OptionNavigationEvent {
+        action: OptionNavigationAction::Select(OptionId(0)),
+        source,
+    });
+    app.update();
+    assert!(!navigation(&mut app).is_active());
+    assert!(!app.dialogue_runner().is_waiting_for_option_selection());
+}
+
+fn setup_options(app: &mut App, navigation: OptionNavigation) -> Entity {
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "options.yarn",
+        )))
+        .init_resource::<ButtonInput<KeyCode>>();
+    let entity = app.dialogue_runner_entity();
+    app.world_mut().entity_mut(entity).insert(navigation);
+    app.dialogue_runner_mut().start_node("Start");
+    app.continue_dialogue_and_update_n_times(4);
+    entity
+}
+
+fn navigation(app: &mut App) -> &OptionNavigation {
+    let entity = app.dialogue_runner_entity();
+    app.world().get::<OptionNavigation>(entity).unwrap()
+}
+
+fn press(app: &mut App, key: KeyCode) {
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(key);
+    app.update();
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.release(key);
+    keys.clear();
+}
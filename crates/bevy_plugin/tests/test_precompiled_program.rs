@@ -0,0 +1,42 @@
+#![cfg(feature = "development_file_generation")]
+
+use anyhow::Result;
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, prelude::*};
+use tempfile::tempdir;
+use utils::prelude::*;
+
+mod utils;
+
+#[test]
+fn runs_written_precompiled_program() -> Result<()> {
+    let dir = tempdir()?;
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "lines.yarn",
+        )));
+    app.load_project()
+        .write_precompiled_program(dir.path().join("lines.yarnc"))?;
+    assert!(dir.path().join("lines.strings.csv").exists());
+
+    let mut app = App::new();
+    let mut asserter = EventAsserter::new();
+    app.setup_default_plugins_for_path(dir.path())
+        .add_plugins(YarnSpinnerPlugin::with_precompiled_program("lines.yarnc"));
+
+    assert_eq!(0, app.load_project().yarn_files().count());
+    app.dialogue_runner_mut().start_node("Start");
+    app.load_lines();
+    app.update();
+    assert_events!(asserter, app contains [
+        DialogueStartEvent,
+        NodeStartEvent,
+        PresentLineEvent with |event| event.line.text.starts_with("An elderly man was sitting alone on a dark path."),
+    ]);
+
+    app.continue_dialogue_and_update();
+    assert_events!(asserter, app contains
+        PresentLineEvent with |event| event.line.text_without_character_name() == "Now your *third* wish. What will it be?");
+    Ok(())
+}
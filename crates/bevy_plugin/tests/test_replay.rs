@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, prelude::*};
+use utils::prelude::*;
+
+mod utils;
+
+const GAMBLE: &str = r#"title: Start
+---
+<<declare $roll = 0>>
+<<set $roll to dice(1000000)>>
+Dealer: You rolled {$roll}.
+-> Roll again
+    <<jump Start>>
+-> Stop
+Dealer: Goodbye.
+===
+"#;
+
+#[test]
+fn same_seed_makes_same_random_decisions() {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFile::new(
+            "gamble.yarn",
+            GAMBLE,
+        )));
+
+    let rolls: Vec<_> = (0..2)
+        .map(|_| {
+            let project = app.load_project();
+            let mut dialogue_runner = project.build_dialogue_runner().with_random_seed(42).build();
+            dialogue_runner.start_node("Start");
+            let entity = app.world_mut().spawn(dialogue_runner).id();
+            app.update();
+            app.world()
+                .get::<DialogueRunner>(entity)
+                .unwrap()
+                .variable_storage()
+                .get("$roll")
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(rolls[0], rolls[1]);
+}
+
+#[test]
+fn replays_recorded_session() {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFile::new(
+            "gamble.yarn",
+            GAMBLE,
+        )))
+        .init_resource::<PresentedLines>()
+        .add_systems(Update, record_lines.after(YarnSpinnerSystemSet));
+
+    app.dialogue_runner_mut()
+        .start_recording_trace()
+        .start_node("Start");
+    app.update();
+    app.continue_dialogue_and_update();
+    app.dialogue_runner_mut()
+        .select_option(OptionId(0))
+        .unwrap();
+    app.update();
+    app.continue_dialogue_and_update();
+    app.dialogue_runner_mut()
+        .select_option(OptionId(1))
+        .unwrap();
+    app.update();
+    let trace = app.dialogue_runner_mut().stop_recording_trace().unwrap();
+    let recorded_lines = std::mem::take(&mut app.world_mut().resource_mut::<PresentedLines>().0);
+    assert_eq!(3, recorded_lines.len());
+    assert_eq!(
+        2,
+        trace
+            .steps
+            .iter()
+            .filter(|step| matches!(step, TraceStep::FunctionCalled { name, .. } if name == "dice"))
+            .count()
+    );
+
+    let trace = DialogueTraceAsset::from_json(&DialogueTraceAsset::from(trace).to_json().unwrap())
+        .unwrap()
+        .0;
+    let mut dialogue_runner = app.load_project().create_dialogue_runner();
+    dialogue_runner.replay_trace(trace).unwrap();
+    let entity = app.world_mut().spawn(dialogue_runner).id();
+    app.update();
+
+    assert_eq!(recorded_lines, app.world().resource::<PresentedLines>().0);
+    let mut dialogue_runner = app.world_mut().get_mut::<DialogueRunner>(entity).unwrap();
+    assert!(dialogue_runner.is_running());
+    assert!(dialogue_runner
+        .replay_trace(DialogueTrace::default())
+        .is_err());
+}
+
+#[test]
+fn loads_trace_asset() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let trace = DialogueTraceAsset(DialogueTrace {
+        steps: vec![
+            TraceStep::NodeStarted {
+                node_name: "Start".to_string(),
+            },
+            TraceStep::Continued,
+        ],
+    });
+    std::fs::write(dir.path().join("bug.yarntrace"), trace.to_json()?)?;
+    let mut app = App::new();
+    app.setup_default_plugins_for_path(dir.path()).add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFile::new("gamble.yarn", GAMBLE)),
+    );
+
+    let handle = app
+        .world()
+        .resource::<AssetServer>()
+        .load::<DialogueTraceAsset>("bug.yarntrace");
+    while !app
+        .world()
+        .resource::<Assets<DialogueTraceAsset>>()
+        .contains(&handle)
+    {
+        app.update();
+    }
+    let loaded = app
+        .world()
+        .resource::<Assets<DialogueTraceAsset>>()
+        .get(&handle);
+    assert_eq!(Some(&trace), loaded);
+    Ok(())
+}
+
+#[derive(Debug, Default, Resource)]
+struct PresentedLines(Vec<String>);
+
+fn record_lines(mut events: EventReader<PresentLineEvent>, mut lines: ResMut<PresentedLines>) {
+    lines
+        .0
+        .extend(events.read().map(|event| event.line.text.clone()));
+}
@@ -0,0 +1,198 @@
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, prelude::*};
+use utils::prelude::*;
+
+mod utils;
+
+const COOP: &str = r#"title: Start
+---
+<<declare $trust = 0>>
+Ranger: The bridge is out.
+<<set $trust to 1>>
+-> Swim across
+    Ranger: Brave.
+-> Go around
+===
+"#;
+
+#[test]
+fn mirrors_dialogue_on_presenter_only_runner() {
+    let mut app = setup_app();
+    let host = app.dialogue_runner_entity();
+    app.world_mut().entity_mut(host).insert(ReplicateDialogue);
+    let presenter = spawn_presenter(&mut app);
+
+    runner_mut(&mut app, host).start_node("Start");
+    app.update();
+    app.update();
+    assert_eq!(
+        vec![
+            (host, "Ranger: The bridge is out.".to_string()),
+            (presenter, "Ranger: The bridge is out.".to_string()),
+        ],
+        take_lines(&mut app)
+    );
+    let presenter_runner = app.world().get::<DialogueRunner>(presenter).unwrap();
+    assert!(presenter_runner.is_running());
+    assert_eq!(Some("Start".to_string()), presenter_runner.current_node());
+
+    runner_mut(&mut app, host).continue_in_next_update();
+    app.update();
+    app.update();
+    let presenter_runner = app.world().get::<DialogueRunner>(presenter).unwrap();
+    assert!(presenter_runner.is_waiting_for_option_selection());
+    assert_eq!(
+        YarnValue::Number(1.0),
+        presenter_runner.variable_storage().get("$trust").unwrap()
+    );
+
+    runner_mut(&mut app, host)
+        .select_option(OptionId(0))
+        .unwrap();
+    app.update();
+    app.update();
+    assert_eq!(
+        vec![
+            (host, "Ranger: Brave.".to_string()),
+            (presenter, "Ranger: Brave.".to_string()),
+        ],
+        take_lines(&mut app)
+    );
+
+    runner_mut(&mut app, host).continue_in_next_update();
+    app.update();
+    app.update();
+    let presenter_runner = app.world().get::<DialogueRunner>(presenter).unwrap();
+    assert!(!presenter_runner.is_running());
+}
+
+#[test]
+fn presenter_only_runner_requests_options_instead_of_selecting_them() {
+    let mut app = setup_app();
+    let presenter = spawn_presenter(&mut app);
+    let options = ReplicatedDialogueEvent::PresentOptions {
+        options: vec![ReplicatedOption {
+            line: ReplicatedLine {
+                id: "line:1".into(),
+                text: "Swim across".to_string(),
+                attributes: vec![],
+                metadata: vec![],
+            },
+            id: OptionId(0),
+            destination_node: "Start".to_string(),
+            is_available: true,
+        }],
+    };
+    for event in [ReplicatedDialogueEvent::DialogueStart, options] {
+        let event = ReplicatedDialogueEvent::from_json(&event.to_json().unwrap()).unwrap();
+        runner_mut(&mut app, presenter)
+            .apply_remote_event(event)
+            .unwrap();
+    }
+    app.update();
+    assert!(app
+        .world()
+        .get::<DialogueRunner>(presenter)
+        .unwrap()
+        .is_waiting_for_option_selection());
+
+    runner_mut(&mut app, presenter)
+        .select_option(OptionId(0))
+        .unwrap();
+    app.update();
+    let requests: Vec<_> = app
+        .world_mut()
+        .resource_mut::<Events<RemoteOptionRequestEvent>>()
+        .drain()
+        .collect();
+    assert_eq!(
+        vec![RemoteOptionRequestEvent {
+            option: OptionId(0),
+            source: presenter,
+        }],
+        requests
+    );
+    assert!(app
+        .world()
+        .resource::<Events<OptionSelectedEvent>>()
+        .is_empty());
+    assert!(app
+        .world()
+        .get::<DialogueRunner>(presenter)
+        .unwrap()
+        .is_waiting_for_option_selection());
+}
+
+#[test]
+fn only_presenter_only_runners_accept_remote_events() {
+    let mut app = setup_app();
+    let host = app.dialogue_runner_entity();
+    let presenter = spawn_presenter(&mut app);
+
+    assert!(runner_mut(&mut app, host)
+        .apply_remote_event(ReplicatedDialogueEvent::DialogueStart)
+        .is_err());
+    assert!(runner_mut(&mut app, presenter)
+        .try_start_node("Start")
+        .is_err());
+}
+
+fn setup_app() -> App {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFile::new(
+            "coop.yarn",
+            COOP,
+        )))
+        .init_resource::<PresentedLines>()
+        .add_systems(
+            Update,
+            (forward_to_presenters, record_lines).after(YarnSpinnerSystemSet),
+        );
+    app
+}
+
+fn spawn_presenter(app: &mut App) -> Entity {
+    let dialogue_runner = app
+        .load_project()
+        .build_dialogue_runner()
+        .with_isolated_variable_storage()
+        .presenter_only()
+        .build();
+    app.world_mut().spawn(dialogue_runner).id()
+}
+
+fn runner_mut(app: &mut App, entity: Entity) -> Mut<'_, DialogueRunner> {
+    app.world_mut().get_mut::<DialogueRunner>(entity).unwrap()
+}
+
+fn forward_to_presenters(
+    mut events: EventReader<DialogueReplicationEvent>,
+    mut dialogue_runners: Query<&mut DialogueRunner>,
+) {
+    for event in events.read() {
+        let message = event.event.to_json().unwrap();
+        for mut dialogue_runner in dialogue_runners.iter_mut() {
+            if dialogue_runner.is_presenter_only() {
+                dialogue_runner
+                    .apply_remote_event(ReplicatedDialogueEvent::from_json(&message).unwrap())
+                    .unwrap();
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Resource)]
+struct PresentedLines(Vec<(Entity, String)>);
+
+fn record_lines(mut events: EventReader<PresentLineEvent>, mut lines: ResMut<PresentedLines>) {
+    lines.0.extend(
+        events
+            .read()
+            .map(|event| (event.source, event.line.text.clone())),
+    );
+}
+
+fn take_lines(app: &mut App) -> Vec<(Entity, String)> {
+    std::mem::take(&mut app.world_mut().resource_mut::<PresentedLines>().0)
+}
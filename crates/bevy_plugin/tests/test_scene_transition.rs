@@ -0,0 +1,94 @@
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, prelude::*};
+use utils::prelude::*;
+
+mod utils;
+
+#[test]
+fn pauses_dialogue_until_scene_is_loaded() {
+    let mut app = App::new();
+    setup_app(
+        &mut app,
+        "<<set $visited_forest to true>>\nLeaving\n<<load_scene forest>>\nArrived",
+    );
+    let mut asserter = EventAsserter::new();
+    let mut load_scene_reader = ManualEventReader::<LoadSceneEvent>::default();
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text == "Leaving",
+    ]);
+
+    app.continue_dialogue_and_update();
+    app.update();
+    let source = app.dialogue_runner_entity();
+    let load_scene_events: Vec<_> = load_scene_reader
+        .read(app.world().resource::<Events<LoadSceneEvent>>())
+        .cloned()
+        .collect();
+    assert_eq!(
+        vec![LoadSceneEvent {
+            scene: "forest".to_owned(),
+            ends_dialogue: false,
+            source,
+        }],
+        load_scene_events
+    );
+    assert_events!(asserter, app contains [PresentLineEvent (n = 0)]);
+    assert!(app.dialogue_runner().is_paused());
+    assert!(app
+        .world()
+        .resource::<SceneTransitions>()
+        .is_transitioning(source));
+
+    app.continue_dialogue_and_update_n_times(3);
+    assert_events!(asserter, app contains [PresentLineEvent (n = 0)]);
+
+    app.world_mut().send_event(SceneLoadedEvent { source });
+    app.update();
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text == "Arrived",
+    ]);
+    assert!(!app.dialogue_runner().is_paused());
+    assert!(app.world().resource::<SceneTransitions>().is_empty());
+    assert!(app.dialogue_runner().story_state().flag("$visited_forest"));
+}
+
+#[test]
+fn ends_dialogue_after_loading_scene() {
+    let mut app = App::new();
+    setup_app(&mut app, "<<load_scene forest end>>\nArrived");
+    let mut asserter = EventAsserter::new();
+    let mut load_scene_reader = ManualEventReader::<LoadSceneEvent>::default();
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    app.update();
+    let source = app.dialogue_runner_entity();
+    let load_scene_event = load_scene_reader
+        .read(app.world().resource::<Events<LoadSceneEvent>>())
+        .next()
+        .unwrap();
+    assert!(load_scene_event.ends_dialogue);
+
+    app.world_mut().send_event(SceneLoadedEvent { source });
+    app.update();
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent (n = 0),
+        DialogueCompleteEvent,
+    ]);
+    assert!(!app.dialogue_runner().is_running());
+}
+
+fn setup_app(app: &mut App, body: &str) {
+    let yarn_file = YarnFile::try_new(
+        "scene_transition.yarn",
+        format!("title: Start\n---\n{body}\n===\n"),
+    )
+    .unwrap();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(yarn_file))
+        .add_plugins(YarnSpinnerStandardCommandsPlugin::new());
+}
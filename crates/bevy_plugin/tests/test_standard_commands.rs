@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, prelude::*};
+use utils::prelude::*;
+
+mod utils;
+
+#[test]
+fn registers_enabled_commands() {
+    let mut app = App::new();
+    setup_app(
+        &mut app,
+        "<<fade_in>>",
+        YarnSpinnerStandardCommandsPlugin::new().without_command(StandardCommand::ScreenShake),
+    );
+    let _dialogue_runner = app.dialogue_runner_entity();
+    app.update();
+
+    let commands = app.dialogue_runner().commands();
+    assert!(commands.contains_key("jump_camera"));
+    assert!(commands.contains_key("fade_in"));
+    assert!(commands.contains_key("fade_out"));
+    assert!(!commands.contains_key("screen_shake"));
+    assert!(commands.contains_key("wait"));
+}
+
+#[test]
+fn keeps_commands_registered_by_user() {
+    let mut app = App::new();
+    setup_app(
+        &mut app,
+        "<<jump_camera 1 2>>",
+        YarnSpinnerStandardCommandsPlugin::new(),
+    );
+    app.dialogue_runner_mut()
+        .commands_mut()
+        .add_command("jump_camera", |_: In<(f32, f32)>| {});
+    let camera = app
+        .world_mut()
+        .spawn((Transform::default(), DialogueCamera))
+        .id();
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+
+    let transform = app.world().get::<Transform>(camera).unwrap();
+    assert_eq!(Vec3::ZERO, transform.translation);
+}
+
+#[test]
+fn jumps_camera() {
+    let mut app = App::new();
+    setup_app(
+        &mut app,
+        "<<jump_camera 1 2>>\nLanded",
+        YarnSpinnerStandardCommandsPlugin::new(),
+    );
+    let camera = app
+        .world_mut()
+        .spawn((Transform::from_xyz(0.0, 0.0, 5.0), DialogueCamera))
+        .id();
+    let mut asserter = EventAsserter::new();
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    app.update();
+
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text == "Landed",
+    ]);
+    let transform = app.world().get::<Transform>(camera).unwrap();
+    assert_eq!(Vec3::new(1.0, 2.0, 5.0), transform.translation);
+}
+
+#[test]
+fn fades_out_and_in() {
+    let mut app = App::new();
+    setup_app(
+        &mut app,
+        "<<fade_out 0>>\nDark\n<<fade_in 0>>\nBright",
+        YarnSpinnerStandardCommandsPlugin::new(),
+    );
+    let mut asserter = EventAsserter::new();
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    app.update();
+
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text == "Dark",
+    ]);
+    assert_eq!(1.0, app.world().resource::<ScreenFade>().alpha());
+
+    app.continue_dialogue_and_update();
+    app.update();
+    assert_events!(asserter, app contains [
+        PresentLineEvent with |event| event.line.text == "Bright",
+    ]);
+    assert_eq!(0.0, app.world().resource::<ScreenFade>().alpha());
+}
+
+fn setup_app(app: &mut App, body: &str, plugin: YarnSpinnerStandardCommandsPlugin) {
+    let yarn_file = YarnFile::try_new(
+        "standard_commands.yarn",
+        format!("title: Start\n---\n{body}\n===\n"),
+    )
+    .unwrap();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(yarn_file))
+        .add_plugins(plugin);
+}
@@ -1,10 +1,14 @@
-use bevy::asset::LoadState;
 use bevy::prelude::*;
-use bevy_yarnspinner::prelude::*;
+use bevy_yarnspinner::{events::YarnProjectCompiledEvent, prelude::*};
 use std::fs;
 use tempfile::tempdir;
 use utils::prelude::*;
-use yarnspinner::prelude::{CompilationType, YarnCompiler};
+#[cfg(feature = "development_file_generation")]
+use {
+    bevy::asset::{LoadState, LoadedUntypedAsset},
+    bevy_yarnspinner::events::GenerateStringsFilesEvent,
+    yarnspinner::prelude::{CompilationType, YarnCompiler},
+};
 
 mod utils;
 
@@ -28,6 +32,30 @@ fn loads_yarn_assets() {
     assert_eq!("lines.yarn", yarn_file.file_name());
 }
 
+#[test]
+fn compiles_yarn_files_in_background() {
+    let mut app = App::new();
+
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "lines.yarn",
+        )));
+
+    while !app.world().contains_resource::<YarnProjectLoading>() {
+        app.update();
+    }
+    assert!(!app.world().contains_resource::<YarnProject>());
+
+    app.load_project();
+    assert!(!app.world().contains_resource::<YarnProjectLoading>());
+    let events = app
+        .world()
+        .resource::<Events<YarnProjectCompiledEvent>>()
+        .iter_current_update_events()
+        .count();
+    assert_eq!(1, events);
+}
+
 #[test]
 #[should_panic]
 fn panics_on_localization_without_line_ids_in_production() {
@@ -45,6 +73,7 @@ fn panics_on_localization_without_line_ids_in_production() {
     let _yarn_file = app.load_project();
 }
 
+#[cfg(feature = "development_file_generation")]
 #[test]
 fn generates_line_ids() -> anyhow::Result<()> {
     let dir = tempdir()?;
@@ -92,6 +121,7 @@ fn generates_line_ids() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "development_file_generation")]
 #[test]
 fn generates_strings_file() -> anyhow::Result<()> {
     let dir = tempdir()?;
@@ -138,6 +168,193 @@ fn generates_strings_file() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "development_file_generation")]
+#[test]
+fn regenerates_deleted_strings_file_on_request() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let original_yarn_path = project_root_path().join("assets/lines_with_ids.yarn");
+    let yarn_path = dir.path().join("lines_with_ids.yarn");
+    fs::copy(original_yarn_path, yarn_path)?;
+
+    let mut app = App::new();
+
+    app.setup_default_plugins_for_path(dir.path()).add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(Localizations {
+                base_localization: "en-US".into(),
+                translations: vec!["de-CH".into()],
+            })
+            .with_development_file_generation(DevelopmentFileGeneration::Full),
+    );
+
+    app.load_project();
+    app.update();
+    let strings_file_path = dir.path().join("dialogue/de-CH.strings.csv");
+    let generated_strings_file = fs::read_to_string(&strings_file_path)?;
+    fs::remove_file(&strings_file_path)?;
+
+    app.world_mut().send_event(GenerateStringsFilesEvent);
+    app.update();
+    assert_eq!(
+        generated_strings_file,
+        fs::read_to_string(&strings_file_path)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reports_stale_translations() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let original_yarn_path = project_root_path().join("assets/lines_with_ids.yarn");
+    let yarn_path = dir.path().join("lines_with_ids.yarn");
+    let yarn_source = fs::read_to_string(original_yarn_path)?.replace(
+        "Hag: Now your *third* wish.",
+        "Hag: Now your *fourth* wish.",
+    );
+    fs::write(yarn_path, yarn_source)?;
+    fs::create_dir(dir.path().join("dialogue"))?;
+    fs::copy(
+        project_root_path().join("assets/dialogue/de-CH.strings.csv"),
+        dir.path().join("dialogue/de-CH.strings.csv"),
+    )?;
+
+    let mut app = App::new();
+
+    app.setup_default_plugins_for_path(dir.path()).add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(Localizations {
+                base_localization: "en-US".into(),
+                translations: vec!["de-CH".into()],
+            })
+            .with_development_file_generation(DevelopmentFileGeneration::None),
+    );
+
+    let mut dialogue_runner = app.load_project().create_dialogue_runner();
+    dialogue_runner.set_text_language("de-CH");
+    app.world_mut().spawn(dialogue_runner);
+    app.load_lines().update();
+
+    let report = app.world().resource::<StaleTranslationReport>();
+    let stale_translations = report.get(&"de-CH".into());
+    assert_eq!(1, report.len());
+    assert_eq!(LineId::from("line:2"), stale_translations[0].line_id);
+    assert_eq!(
+        "Hexe: Und jetzt zu deinem *dritten* Wunsch. Was wünschst du dir also?",
+        stale_translations[0].translation
+    );
+    assert_eq!(
+        "Hag: Now your *fourth* wish. What will it be?",
+        stale_translations[0].base_text
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "development_file_generation")]
+#[test]
+fn generates_and_loads_gettext_strings_file() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let original_yarn_path = project_root_path().join("assets/lines_with_ids.yarn");
+    let yarn_path = dir.path().join("lines_with_ids.yarn");
+    fs::copy(original_yarn_path, yarn_path)?;
+    let localizations = Localizations {
+        base_localization: "en-US".into(),
+        translations: vec![
+            Localization::with_language("de-CH").with_strings_file("dialogue/de-CH.po")
+        ],
+    };
+
+    let mut app = App::new();
+    app.setup_default_plugins_for_path(dir.path()).add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(localizations.clone())
+            .with_development_file_generation(DevelopmentFileGeneration::Full),
+    );
+    app.load_project();
+    app.update(); // Generate the strings file
+
+    let strings_file_path = dir.path().join("dialogue/de-CH.po");
+    let untranslated_entry = "msgctxt \"line:3\"\nmsgid \"Man: Third wish?\"\nmsgstr \"\"\n";
+    let strings_file_source = fs::read_to_string(&strings_file_path)?;
+    assert!(strings_file_source.contains("\"Language: de-CH\\n\""));
+    assert!(strings_file_source.contains(untranslated_entry));
+    fs::write(
+        &strings_file_path,
+        strings_file_source.replace(
+            untranslated_entry,
+            "msgctxt \"line:3\"\nmsgid \"Man: Third wish?\"\nmsgstr \"Mann: Dritter Wunsch?\"\n",
+        ),
+    )?;
+
+    let mut app = App::new();
+    app.setup_default_plugins_for_path(dir.path()).add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(localizations)
+            .with_development_file_generation(DevelopmentFileGeneration::None),
+    );
+    app.dialogue_runner_mut().set_text_language("de-CH");
+    app.load_lines();
+
+    let text_provider = app.dialogue_runner().text_provider();
+    let translated_line = text_provider.get_text(&LineId::from("line:3")).unwrap();
+    let untranslated_line = text_provider.get_text(&LineId::from("line:4")).unwrap();
+    assert_eq!("Mann: Dritter Wunsch?", translated_line);
+    assert_eq!("The man was baffled.", untranslated_line);
+
+    Ok(())
+}
+
+#[cfg(feature = "development_file_generation")]
+#[test]
+fn keeps_translations_of_converted_unity_strings_file() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let original_yarn_path = project_root_path().join("assets/lines_with_ids.yarn");
+    fs::copy(original_yarn_path, dir.path().join("lines_with_ids.yarn"))?;
+    let unity_strings_file_path = dir.path().join("Project (de-CH).csv");
+    fs::write(
+        &unity_strings_file_path,
+        "\u{feff}language,id,text,file,node,lineNumber,lock,comment\r\n\
+         de-CH,line:3,Mann: Dritter Wunsch?,Assets/Dialogue/lines_with_ids.yarn,Start,5,14900043,\r\n\
+         de-CH,line:4,Der Mann war perplex.,Assets/Dialogue/lines_with_ids.yarn,Start,6,b81f8e6a,\r\n",
+    )?;
+
+    let strings_file_path = dir.path().join("dialogue/de-CH.strings.csv");
+    convert_unity_strings_file(&unity_strings_file_path, &strings_file_path)?;
+    let strings_file_source = fs::read_to_string(&strings_file_path)?;
+    assert_eq!(
+        vec![
+            "language,id,text,file,node,lineNumber,lock,comment",
+            "de-CH,line:3,Mann: Dritter Wunsch?,lines_with_ids.yarn,Start,5,14900043,",
+            "de-CH,line:4,Der Mann war perplex.,lines_with_ids.yarn,Start,6,b81f8e6a,",
+        ],
+        strings_file_source.lines().collect::<Vec<_>>()
+    );
+
+    let mut app = App::new();
+    app.setup_default_plugins_for_path(dir.path()).add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(Localizations {
+                base_localization: "en-US".into(),
+                translations: vec!["de-CH".into()],
+            })
+            .with_development_file_generation(DevelopmentFileGeneration::Full),
+    );
+    app.dialogue_runner_mut().set_text_language("de-CH");
+    app.load_lines();
+    app.update(); // Add the missing lines to the strings file
+
+    let text_provider = app.dialogue_runner().text_provider();
+    let translated_line = text_provider.get_text(&LineId::from("line:3")).unwrap();
+    assert_eq!("Mann: Dritter Wunsch?", translated_line);
+    let strings_file_source = fs::read_to_string(&strings_file_path)?;
+    assert!(strings_file_source.contains("line:3,Mann: Dritter Wunsch?,lines_with_ids.yarn"));
+    assert!(strings_file_source.contains("line:5,Man: How can it be a third wish"));
+
+    Ok(())
+}
+
+#[cfg(feature = "development_file_generation")]
 #[test]
 fn appends_to_pre_existing_strings_file() -> anyhow::Result<()> {
     let dir = tempdir()?;
@@ -180,6 +397,21 @@ fn appends_to_pre_existing_strings_file() -> anyhow::Result<()> {
     {
         app.update();
     }
+    // The untyped handle may finish loading before the strings file itself when the latter was already being loaded
+    let strings_file_handle = app
+        .world()
+        .resource::<Assets<LoadedUntypedAsset>>()
+        .get(&handle)
+        .unwrap()
+        .handle
+        .clone();
+    while !app
+        .world()
+        .resource::<AssetServer>()
+        .is_loaded_with_dependencies(&strings_file_handle)
+    {
+        app.update();
+    }
 
     let string_table = YarnCompiler::new()
         .read_file(&yarn_path)
@@ -207,6 +439,7 @@ fn appends_to_pre_existing_strings_file() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "development_file_generation")]
 #[test]
 fn replaces_entries_in_strings_file() -> anyhow::Result<()> {
     let dir = tempdir()?;
@@ -250,6 +483,8 @@ fn replaces_entries_in_strings_file() -> anyhow::Result<()> {
         yarn_file.set_content(lines.join("\n"))?;
     }
 
+    // The project is compiled in the background, so the asset events of loading it may already be gone
+    app.update();
     while !app
         .world()
         .resource::<Events<AssetEvent<YarnFile>>>()
@@ -0,0 +1,99 @@
+#![cfg(feature = "test_utils")]
+
+use bevy_yarnspinner::{events::*, prelude::*, test_utils::*};
+
+const SHOP: &str = r#"title: Shop
+---
+<<set $gold to 3>>
+Shopkeeper: What do you want?
+-> Player: Bread
+    <<set $gold to $gold - 1>>
+    <<give_item "bread">>
+    Shopkeeper: Here you go.
+-> Player: Nothing
+    <<jump Goodbye>>
+Shopkeeper: Anything else?
+-> Player: No
+===
+title: Goodbye
+---
+Shopkeeper: Come again.
+===
+"#;
+
+#[test]
+fn runs_dialogue_by_script() {
+    let mut app = headless_app([YarnFile::new("shop.yarn", SHOP)]);
+
+    let recording = app.run_dialogue(
+        "Shop",
+        DialogueScript::new().choose_text("Bread").choose_index(0),
+    );
+
+    assert_eq!(
+        vec![
+            "Shopkeeper: What do you want?",
+            "Shopkeeper: Here you go.",
+            "Shopkeeper: Anything else?",
+        ],
+        recording.line_texts()
+    );
+    assert_eq!(
+        vec!["Player: Bread", "Player: No"],
+        recording
+            .selected_options()
+            .map(|option| option.line.text.as_str())
+            .collect::<Vec<_>>()
+    );
+    let commands: Vec<_> = recording.commands().collect();
+    assert_eq!(1, commands.len());
+    assert_eq!("give_item", commands[0].name);
+    assert_eq!(
+        Some(&RecordedDialogueEvent::DialogueCompleted),
+        recording.events.last()
+    );
+}
+
+#[test]
+fn follows_jumps() {
+    let mut app = headless_app([YarnFile::new("shop.yarn", SHOP)]);
+
+    let recording = app.run_dialogue("Shop", DialogueScript::new().choose_text("Nothing"));
+
+    assert_eq!(vec!["Shop", "Goodbye"], recording.visited_nodes());
+    assert!(recording.contains_line("Come again."));
+    assert!(!recording.contains_line("Shopkeeper: Anything else?"));
+}
+
+#[test]
+fn records_arbitrary_events() {
+    let mut app = headless_app([YarnFile::new("shop.yarn", SHOP)]);
+    app.record_events::<VariableChangedEvent>();
+
+    let _recording = app.run_dialogue(
+        "Shop",
+        DialogueScript::new().choose_text("Bread").choose_index(0),
+    );
+
+    let gold: Vec<_> = app
+        .recorded_events::<VariableChangedEvent>()
+        .iter()
+        .filter(|event| event.name == "$gold")
+        .map(|event| event.new_value.clone())
+        .collect();
+    assert_eq!(vec![YarnValue::from(3.0), YarnValue::from(2.0)], gold);
+}
+
+#[test]
+#[should_panic(expected = "no choice left")]
+fn panics_when_script_runs_out_of_choices() {
+    let mut app = headless_app([YarnFile::new("shop.yarn", SHOP)]);
+    let _recording = app.run_dialogue("Shop", DialogueScript::new());
+}
+
+#[test]
+#[should_panic(expected = "does not match any of the options")]
+fn panics_on_unknown_choice() {
+    let mut app = headless_app([YarnFile::new("shop.yarn", SHOP)]);
+    let _recording = app.run_dialogue("Shop", DialogueScript::new().choose_text("Milk"));
+}
@@ -1,5 +1,9 @@
 use bevy::prelude::*;
-use bevy_yarnspinner::prelude::*;
+use bevy_yarnspinner::{
+    events::MissingTranslationEvent, prelude::*, StringInfo, UnderlyingTextProvider,
+};
+use std::any::Any;
+use std::collections::HashMap;
 use utils::prelude::*;
 
 mod utils;
@@ -153,3 +157,250 @@ fn loads_line_from_translated_language() {
         .unwrap();
     assert_eq!("Mann: Also gut. Ich glaub das zwar nicht, aber es kann ja nicht schaden, wenn ich mir was wünsche. Ich möchte wissen, wer ich bin.", line);
 }
+
+#[test]
+fn sends_event_for_missing_translation() {
+    let mut app = App::new();
+
+    app.setup_default_plugins().add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(Localizations {
+                base_localization: "en-US".into(),
+                translations: vec!["de-CH".into()],
+            })
+            .with_development_file_generation(DevelopmentFileGeneration::None),
+    );
+
+    app.dialogue_runner_mut().set_text_language("de-CH");
+    app.load_lines();
+
+    let text_provider = app.dialogue_runner().text_provider();
+    text_provider
+        .get_text(&LineId("line:9".to_owned()))
+        .unwrap();
+    text_provider
+        .get_text(&LineId("line:10".to_owned()))
+        .unwrap();
+    app.update();
+
+    let source = app.dialogue_runner_entity();
+    let events = app.world().resource::<Events<MissingTranslationEvent>>();
+    let events: Vec<_> = events.get_reader().read(events).cloned().collect();
+    assert_eq!(
+        vec![MissingTranslationEvent {
+            line_id: LineId("line:10".to_owned()),
+            language: "de-CH".into(),
+            source,
+        }],
+        events
+    );
+}
+
+#[test]
+#[should_panic]
+fn panics_on_missing_translation_in_strict_mode() {
+    let mut app = App::new();
+
+    app.setup_default_plugins().add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(Localizations {
+                base_localization: "en-US".into(),
+                translations: vec!["de-CH".into()],
+            })
+            .with_development_file_generation(DevelopmentFileGeneration::None)
+            .with_strict_translations(true),
+    );
+
+    app.dialogue_runner_mut().set_text_language("de-CH");
+    app.load_lines();
+
+    app.dialogue_runner()
+        .text_provider()
+        .get_text(&LineId("line:10".to_owned()));
+}
+
+#[test]
+fn reports_fallback_to_base_language() {
+    let mut app = App::new();
+
+    app.setup_default_plugins().add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(Localizations {
+                base_localization: "en-US".into(),
+                translations: vec!["de-CH".into()],
+            })
+            .with_development_file_generation(DevelopmentFileGeneration::None),
+    );
+
+    app.dialogue_runner_mut().set_text_language("de-CH");
+    app.load_lines();
+
+    let text_provider = app.dialogue_runner().text_provider();
+    let translated = text_provider
+        .get_text_with_fallback_info(&LineId("line:9".to_owned()))
+        .unwrap();
+    assert!(!translated.is_fallback);
+    let untranslated = text_provider
+        .get_text_with_fallback_info(&LineId("line:10".to_owned()))
+        .unwrap();
+    assert_eq!("Hag: Funny,", &*untranslated.text);
+    assert!(untranslated.is_fallback);
+    app.update();
+
+    let events = app.world().resource::<Events<MissingTranslationEvent>>();
+    assert!(events.is_empty());
+}
+
+#[test]
+fn loads_additional_language_next_to_current_language() {
+    let mut app = App::new();
+
+    app.setup_default_plugins().add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(Localizations {
+                base_localization: "en-US".into(),
+                translations: vec!["de-CH".into()],
+            })
+            .with_development_file_generation(DevelopmentFileGeneration::None),
+    );
+
+    app.load_lines();
+    app.dialogue_runner_mut()
+        .load_additional_text_language("de-CH");
+    let line_id = LineId("line:9".to_owned());
+    let de_ch = Language::new("de-CH");
+    while app
+        .dialogue_runner()
+        .text_provider()
+        .get_text_in_language(&line_id, &de_ch)
+        .is_none()
+    {
+        app.update();
+    }
+
+    let text_provider = app.dialogue_runner().text_provider();
+    assert_eq!(
+        "Mann: Also gut. Ich glaub das zwar nicht, aber es kann ja nicht schaden, wenn ich mir was wünsche. Ich möchte wissen, wer ich bin.",
+        text_provider.get_text_in_language(&line_id, &de_ch).unwrap()
+    );
+    assert_eq!(
+        "Man: All right. I don't believe this; but there's no harm in wishing. I wish to know who I am.",
+        text_provider.get_text(&line_id).unwrap()
+    );
+    assert_eq!(
+        "Man: All right. I don't believe this; but there's no harm in wishing. I wish to know who I am.",
+        text_provider
+            .get_text_in_language(&line_id, &Language::new("en-US"))
+            .unwrap()
+    );
+    assert!(text_provider
+        .get_text_in_language(&LineId("line:10".to_owned()), &de_ch)
+        .is_none());
+    assert_eq!(Some("en-US".into()), app.dialogue_runner().text_language());
+
+    app.dialogue_runner_mut()
+        .unload_additional_text_language("de-CH");
+    assert!(app
+        .dialogue_runner()
+        .text_provider()
+        .get_text_in_language(&line_id, &de_ch)
+        .is_none());
+}
+
+#[test]
+#[should_panic]
+fn panics_when_loading_missing_additional_language() {
+    let mut app = App::new();
+
+    app.setup_default_plugins().add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("lines_with_ids.yarn"))
+            .with_localizations(Localizations {
+                base_localization: "en-US".into(),
+                translations: vec!["de-CH".into()],
+            })
+            .with_development_file_generation(DevelopmentFileGeneration::None),
+    );
+
+    app.dialogue_runner_mut()
+        .load_additional_text_language("fr-FR");
+}
+
+#[test]
+fn uses_custom_text_provider() {
+    let mut app = App::new();
+
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "lines_with_ids.yarn",
+        )));
+
+    let dialogue_runner = app
+        .load_project()
+        .build_dialogue_runner()
+        .with_text_provider(ShoutingTextProvider::default())
+        .build();
+    app.world_mut().spawn(dialogue_runner);
+
+    let line = app
+        .dialogue_runner()
+        .text_provider()
+        .get_text(&LineId("line:10".to_owned()))
+        .unwrap();
+    assert_eq!("HAG: FUNNY,", line);
+}
+
+/// Stands in for a provider that fetches its text from somewhere else than strings files, e.g. a database.
+#[derive(Debug, Clone, Default)]
+struct ShoutingTextProvider {
+    base_string_table: HashMap<LineId, StringInfo>,
+    language: Option<Language>,
+}
+
+impl UnderlyingTextProvider for ShoutingTextProvider {
+    fn clone_shallow(&self) -> Box<dyn UnderlyingTextProvider> {
+        Box::new(self.clone())
+    }
+
+    fn accept_line_hints(&mut self, _line_ids: &[LineId]) {}
+
+    fn get_text(&self, id: &LineId) -> Option<String> {
+        let info = self.base_string_table.get(id)?;
+        Some(info.text.to_uppercase())
+    }
+
+    fn set_language(&mut self, language: Option<Language>) {
+        self.language = language;
+    }
+
+    fn get_language(&self) -> Option<Language> {
+        self.language.clone()
+    }
+
+    fn are_lines_available(&self) -> bool {
+        !self.base_string_table.is_empty()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl TextProvider for ShoutingTextProvider {
+    fn set_base_string_table(&mut self, string_table: HashMap<LineId, StringInfo>) {
+        self.base_string_table = string_table;
+    }
+
+    fn extend_base_string_table(&mut self, string_table: HashMap<LineId, StringInfo>) {
+        self.base_string_table.extend(string_table);
+    }
+
+    fn take_fetched_assets(&mut self, _asset: Box<dyn Any>) {}
+
+    fn fetch_assets(&self, _world: &World) -> Option<Box<dyn Any + 'static>> {
+        None
+    }
+}
@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use bevy_yarnspinner::prelude::*;
+use utils::prelude::*;
+
+mod utils;
+
+#[test]
+fn records_lines_and_selected_options() -> anyhow::Result<()> {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "options.yarn",
+        )))
+        .init_resource::<DialogueTranscript>();
+    app.dialogue_runner_mut().start_node("Start");
+    app.continue_dialogue_and_update_n_times(4);
+    app.dialogue_runner_mut().select_option(OptionId(1))?;
+    app.update();
+
+    let dialogue_runner = app.dialogue_runner_entity();
+    let transcript = app.world().resource::<DialogueTranscript>();
+    let entries: Vec<_> = transcript
+        .entries(dialogue_runner)
+        .map(|entry| match entry {
+            TranscriptEntry::Line(line) => line.text.clone(),
+            TranscriptEntry::SelectedOption(line) => format!("> {}", line.text),
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            "Ancient Reptilian Brain: There is nothing. Only warm, primordial blackness. Your conscience ferments in it -- no larger than a single grain of malt. You don't have to do anything anymore.".to_string(),
+            "Ancient Reptilian Brain: Ever.".to_string(),
+            "Ancient Reptilian Brain: Never ever.".to_string(),
+            "> You: (Simply keep on non-existing.)".to_string(),
+            "Ancient Reptilian Brain: An inordinate amount of time passes. It is utterly void of struggle. No ex-wives are contained within it.".to_string(),
+        ],
+        entries
+    );
+    Ok(())
+}
+
+#[test]
+fn does_not_repeat_options_shown_as_lines() -> anyhow::Result<()> {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "options.yarn",
+        )))
+        .init_resource::<DialogueTranscript>();
+    app.dialogue_runner_mut()
+        .run_selected_options_as_lines(true)
+        .start_node("Start");
+    app.continue_dialogue_and_update_n_times(4);
+    app.dialogue_runner_mut().select_option(OptionId(1))?;
+    app.update();
+
+    let dialogue_runner = app.dialogue_runner_entity();
+    let transcript = app.world().resource::<DialogueTranscript>();
+    let last_entry = transcript.entries(dialogue_runner).last().unwrap();
+    assert_eq!(
+        &TranscriptEntry::SelectedOption(last_entry.line().clone()),
+        last_entry
+    );
+    assert_eq!(4, transcript.entries(dialogue_runner).len());
+    Ok(())
+}
+
+#[test]
+fn keeps_only_most_recent_entries() {
+    let mut app = App::new();
+    app.setup_default_plugins()
+        .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+            "lines.yarn",
+        )))
+        .insert_resource(DialogueTranscript::with_max_entries(2));
+    app.dialogue_runner_mut().start_node("Start");
+    app.continue_dialogue_and_update_n_times(3);
+
+    let dialogue_runner = app.dialogue_runner_entity();
+    let transcript = app.world().resource::<DialogueTranscript>();
+    let entries: Vec<_> = transcript.entries(dialogue_runner).collect();
+    assert_eq!(2, entries.len());
+    assert!(entries[1].line().text.starts_with("Man: Third wish?"));
+}
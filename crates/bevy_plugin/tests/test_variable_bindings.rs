@@ -0,0 +1,129 @@
+use anyhow::Result;
+use bevy::prelude::*;
+use bevy_yarnspinner::prelude::*;
+use utils::prelude::*;
+
+mod utils;
+
+#[derive(Debug, Resource, Reflect)]
+struct Story {
+    data: String,
+}
+
+#[derive(Debug, Component, Reflect)]
+struct Health(u32);
+
+#[test]
+fn syncs_variable_with_resource_field_in_both_directions() -> Result<()> {
+    let mut app = App::new();
+    app.insert_resource(Story {
+        data: "initial".to_owned(),
+    })
+    .setup_dialogue_runner()
+    .bind_variable_to_resource::<Story>("$data", "data");
+    app.update();
+    assert_eq!("initial", stored_string(&mut app, "$data")?);
+
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    app.continue_dialogue_and_update(); // Runs `<<set $data to "foo">>`
+    app.update();
+    assert_eq!("foo", app.world().resource::<Story>().data);
+
+    app.world_mut().resource_mut::<Story>().data = "bar".to_owned();
+    app.update();
+    assert_eq!("bar", stored_string(&mut app, "$data")?);
+
+    Ok(())
+}
+
+#[test]
+fn syncs_variable_with_component_field() -> Result<()> {
+    let mut app = App::new();
+    let player = app.world_mut().spawn(Health(100)).id();
+    app.setup_dialogue_runner()
+        .bind_variable_to_component::<Health>("$player_health", player, ".0");
+    app.update();
+    assert_eq!(
+        YarnValue::from(100),
+        app.dialogue_runner()
+            .variable_storage()
+            .get("$player_health")?
+    );
+
+    app.dialogue_runner_mut()
+        .variable_storage_mut()
+        .set("$player_health".to_owned(), 42.into())?;
+    app.update();
+    assert_eq!(42, app.world().get::<Health>(player).unwrap().0);
+
+    Ok(())
+}
+
+#[test]
+fn keeps_variable_while_bound_entity_is_missing() -> Result<()> {
+    let mut app = App::new();
+    let player = app.world_mut().spawn(Health(100)).id();
+    app.setup_dialogue_runner()
+        .bind_variable_to_component::<Health>("$player_health", player, ".0");
+    app.update();
+
+    app.world_mut().despawn(player);
+    app.update();
+    assert_eq!(
+        YarnValue::from(100),
+        app.dialogue_runner()
+            .variable_storage()
+            .get("$player_health")?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn stops_syncing_unbound_variable() -> Result<()> {
+    let mut app = App::new();
+    app.insert_resource(Story {
+        data: "initial".to_owned(),
+    })
+    .setup_dialogue_runner()
+    .bind_variable_to_resource::<Story>("$data", "data");
+    app.update();
+
+    app.dialogue_runner_mut().unbind_variable("$data");
+    app.world_mut().resource_mut::<Story>().data = "bar".to_owned();
+    app.update();
+    assert_eq!("initial", stored_string(&mut app, "$data")?);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "variable names must start with a '$'")]
+fn panics_on_binding_variable_without_dollar_sign() {
+    let mut app = App::new();
+    app.setup_dialogue_runner()
+        .bind_variable_to_resource::<Story>("data", "data");
+}
+
+fn stored_string(app: &mut App, variable_name: &str) -> Result<String> {
+    let value = app
+        .dialogue_runner()
+        .variable_storage()
+        .get(variable_name)?;
+    Ok(value.into())
+}
+
+trait VariableBindingsAppExt {
+    fn setup_dialogue_runner(&mut self) -> Mut<DialogueRunner>;
+}
+
+impl VariableBindingsAppExt for App {
+    fn setup_dialogue_runner(&mut self) -> Mut<DialogueRunner> {
+        self.setup_default_plugins()
+            .add_plugins(YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file(
+                "commands.yarn",
+            )))
+            .dialogue_runner_mut()
+    }
+}
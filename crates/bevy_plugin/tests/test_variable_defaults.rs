@@ -0,0 +1,124 @@
+use anyhow::Result;
+use bevy::prelude::*;
+use bevy_yarnspinner::{events::*, prelude::*};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+use utils::prelude::*;
+
+mod utils;
+
+const GAME: &str = r#"title: Start
+---
+<<declare $enemy_health = 100>>
+<<declare $hints_enabled = false>>
+<<declare $player_title = "Rookie">>
+The enemy has {$enemy_health} health, {$player_title}.
+===
+"#;
+
+#[test]
+fn overrides_declared_defaults_from_json() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(
+        dir.path().join("hard.yarnvars.json"),
+        r#"{ "$enemy_health": 250, "player_title": "Veteran" }"#,
+    )?;
+    let mut app = setup_app(dir.path(), "hard.yarnvars.json")?;
+
+    let project = app.load_project();
+    let initial_values = &project
+        .compilation()
+        .program
+        .as_ref()
+        .unwrap()
+        .initial_values;
+    assert_eq!(
+        YarnValue::Number(250.0),
+        initial_values["$enemy_health"].clone().into()
+    );
+    assert_eq!(
+        YarnValue::Boolean(false),
+        initial_values["$hints_enabled"].clone().into()
+    );
+    assert!(compiled_diagnostics(&app).is_empty());
+
+    app.dialogue_runner_mut().start_node("Start");
+    app.update();
+    let events = app.world().resource::<Events<PresentLineEvent>>();
+    let line = events
+        .get_reader()
+        .read(events)
+        .last()
+        .unwrap()
+        .line
+        .clone();
+    assert_eq!("The enemy has 250 health, Veteran.", line.text);
+    Ok(())
+}
+
+#[test]
+fn ignores_undeclared_and_mistyped_overrides_from_ron() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(
+        dir.path().join("easy.yarnvars.ron"),
+        r#"{ "$enemy_health": "lots", "$hints_enabled": true, "$unknown": 1 }"#,
+    )?;
+    let mut app = setup_app(dir.path(), "easy.yarnvars.ron")?;
+
+    let project = app.load_project();
+    let initial_values = &project
+        .compilation()
+        .program
+        .as_ref()
+        .unwrap()
+        .initial_values;
+    assert_eq!(
+        YarnValue::Number(100.0),
+        initial_values["$enemy_health"].clone().into()
+    );
+    assert_eq!(
+        YarnValue::Boolean(true),
+        initial_values["$hints_enabled"].clone().into()
+    );
+    assert!(!initial_values.contains_key("$unknown"));
+
+    let diagnostics = compiled_diagnostics(&app);
+    assert_eq!(2, diagnostics.len());
+    assert!(diagnostics[0].contains("$enemy_health") && diagnostics[0].contains("Number"));
+    assert!(diagnostics[1].contains("$unknown") && diagnostics[1].contains("not declared"));
+    Ok(())
+}
+
+#[test]
+fn parses_variable_names_with_and_without_dollar_sign() -> Result<()> {
+    let defaults = VariableDefaults::from_json(r#"{ "$a": 1, "b": true, "c": "text" }"#)?;
+    assert_eq!(Some(&YarnValue::Number(1.0)), defaults.get("a"));
+    assert_eq!(Some(&YarnValue::Boolean(true)), defaults.get("$b"));
+    assert_eq!(
+        vec!["$a", "$b", "$c"],
+        defaults.iter().map(|(name, _)| name).collect::<Vec<_>>()
+    );
+    assert!(VariableDefaults::from_ron("{ \"$a\": [1] }").is_err());
+    Ok(())
+}
+
+fn setup_app(dir: &Path, variable_defaults: &str) -> Result<App> {
+    fs::write(dir.join("game.yarn"), GAME)?;
+    let mut app = App::new();
+    app.setup_default_plugins_for_path(dir).add_plugins(
+        YarnSpinnerPlugin::with_yarn_source(YarnFileSource::file("game.yarn"))
+            .with_variable_defaults(variable_defaults),
+    );
+    Ok(app)
+}
+
+fn compiled_diagnostics(app: &App) -> Vec<String> {
+    let events = app.world().resource::<Events<YarnProjectCompiledEvent>>();
+    events
+        .get_reader()
+        .read(events)
+        .flat_map(|event| &event.diagnostics)
+        .map(|diagnostic| diagnostic.message.clone())
+        .collect()
+}
@@ -6,12 +6,16 @@ use bevy_yarnspinner::events::*;
 pub struct EventAsserter {
     pub present_line_reader: ManualEventReader<PresentLineEvent>,
     pub present_options_reader: ManualEventReader<PresentOptionsEvent>,
+    pub option_selected_reader: ManualEventReader<OptionSelectedEvent>,
     pub dialogue_start_reader: ManualEventReader<DialogueStartEvent>,
     pub dialogue_complete_reader: ManualEventReader<DialogueCompleteEvent>,
     pub node_start_reader: ManualEventReader<NodeStartEvent>,
     pub node_complete_reader: ManualEventReader<NodeCompleteEvent>,
     pub line_hints_reader: ManualEventReader<LineHintsEvent>,
     pub execute_command_reader: ManualEventReader<ExecuteCommandEvent>,
+    pub variable_changed_reader: ManualEventReader<VariableChangedEvent>,
+    pub line_hints_ready_reader: ManualEventReader<LineHintsReadyEvent>,
+    pub choice_timer_tick_reader: ManualEventReader<ChoiceTimerTickEvent>,
 }
 
 impl EventAsserter {
@@ -24,6 +28,8 @@ impl EventAsserter {
             .clear(app.world().resource::<Events<PresentLineEvent>>());
         self.present_options_reader
             .clear(app.world().resource::<Events<PresentOptionsEvent>>());
+        self.option_selected_reader
+            .clear(app.world().resource::<Events<OptionSelectedEvent>>());
         self.dialogue_start_reader
             .clear(app.world().resource::<Events<DialogueStartEvent>>());
         self.dialogue_complete_reader
@@ -36,6 +42,12 @@ impl EventAsserter {
             .clear(app.world().resource::<Events<LineHintsEvent>>());
         self.execute_command_reader
             .clear(app.world().resource::<Events<ExecuteCommandEvent>>());
+        self.variable_changed_reader
+            .clear(app.world().resource::<Events<VariableChangedEvent>>());
+        self.line_hints_ready_reader
+            .clear(app.world().resource::<Events<LineHintsReadyEvent>>());
+        self.choice_timer_tick_reader
+            .clear(app.world().resource::<Events<ChoiceTimerTickEvent>>());
     }
 }
 
@@ -47,6 +59,9 @@ macro_rules! get_reader {
     ($asserter:ident, PresentOptionsEvent) => {
         &mut $asserter.present_options_reader
     };
+    ($asserter:ident, OptionSelectedEvent) => {
+        &mut $asserter.option_selected_reader
+    };
     ($asserter:ident, DialogueStartEvent) => {
         &mut $asserter.dialogue_start_reader
     };
@@ -65,6 +80,15 @@ macro_rules! get_reader {
     ($asserter:ident, ExecuteCommandEvent) => {
         &mut $asserter.execute_command_reader
     };
+    ($asserter:ident, LineHintsReadyEvent) => {
+        &mut $asserter.line_hints_ready_reader
+    };
+    ($asserter:ident, VariableChangedEvent) => {
+        &mut $asserter.variable_changed_reader
+    };
+    ($asserter:ident, ChoiceTimerTickEvent) => {
+        &mut $asserter.choice_timer_tick_reader
+    };
 }
 
 #[macro_export]
@@ -36,7 +36,7 @@ pub trait AppExt {
     fn setup_default_plugins_for_path(&mut self, asset_folder: impl AsRef<Path>) -> &mut App;
 
     #[must_use]
-    fn clone_loaded_untyped_assets(&self) -> Assets<LoadedUntypedAsset>;
+    fn update_line_availability(&mut self) -> bool;
 }
 
 impl AppExt for App {
@@ -57,8 +57,7 @@ impl AppExt for App {
     fn load_lines(&mut self) -> &mut App {
         self.load_project();
         loop {
-            let assets = self.clone_loaded_untyped_assets();
-            if self.dialogue_runner_mut().update_line_availability(&assets) {
+            if self.update_line_availability() {
                 break;
             }
             self.update();
@@ -120,17 +119,17 @@ impl AppExt for App {
         self
     }
 
-    fn clone_loaded_untyped_assets(&self) -> Assets<LoadedUntypedAsset> {
-        self.world()
-            .resource::<Assets<LoadedUntypedAsset>>()
-            .iter()
-            .map(|(_handle, asset)| LoadedUntypedAsset {
-                handle: asset.handle.clone(),
-            })
-            .fold(Assets::default(), |mut assets, asset| {
-                assets.add(asset);
-                assets
-            })
+    fn update_line_availability(&mut self) -> bool {
+        // Borrow the real assets instead of copying them, since copies get new asset IDs
+        let entity = self.dialogue_runner_entity();
+        self.world_mut().resource_scope(
+            |world, loaded_untyped_assets: Mut<Assets<LoadedUntypedAsset>>| {
+                world
+                    .get_mut::<DialogueRunner>(entity)
+                    .unwrap()
+                    .update_line_availability(&loaded_untyped_assets)
+            },
+        )
     }
 }
 
@@ -0,0 +1,68 @@
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+
+/// The arguments of a subcommand, split into positional arguments, flags like `--no-color` and options with a value like `--output <path>`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Args {
+    positional: Vec<String>,
+    flags: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    /// Parses the arguments, failing on flags and options that the subcommand does not know.
+    /// Options accept both `--name value` and `--name=value`.
+    pub(crate) fn parse(
+        arguments: impl IntoIterator<Item = String>,
+        known_flags: &[&str],
+        known_options: &[&str],
+    ) -> Result<Self> {
+        let mut args = Self::default();
+        let mut arguments = arguments.into_iter();
+        while let Some(argument) = arguments.next() {
+            if !argument.starts_with('-') || argument == "-" {
+                args.positional.push(argument);
+                continue;
+            }
+            let (name, inline_value) = match argument.split_once('=') {
+                Some((name, value)) => (name.to_owned(), Some(value.to_owned())),
+                None => (argument, None),
+            };
+            if known_flags.contains(&name.as_str()) {
+                if inline_value.is_some() {
+                    bail!("The flag {name} does not take a value");
+                }
+                args.flags.push(name);
+            } else if known_options.contains(&name.as_str()) {
+                let Some(value) = inline_value.or_else(|| arguments.next()) else {
+                    bail!("The option {name} needs a value");
+                };
+                args.options.insert(name, value);
+            } else {
+                bail!("Unknown argument {name}");
+            }
+        }
+        Ok(args)
+    }
+
+    pub(crate) fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|flag| flag == name)
+    }
+
+    pub(crate) fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    pub(crate) fn positional(&self) -> &[String] {
+        &self.positional
+    }
+
+    /// Returns the only positional argument, failing if there is none or more than one.
+    pub(crate) fn single_positional(&self, description: &str) -> Result<&str> {
+        match self.positional.as_slice() {
+            [argument] => Ok(argument),
+            [] => bail!("Missing {description}"),
+            [_, extra, ..] => bail!("Unexpected argument {extra}"),
+        }
+    }
+}
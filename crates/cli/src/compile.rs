@@ -0,0 +1,73 @@
+use crate::args::Args;
+use crate::diagnostics::{summary, Reporter};
+use crate::project::YarnSources;
+use crate::strings_file;
+use crate::EXIT_YARN_ERRORS;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use yarnspinner::prelude::*;
+
+pub(crate) const USAGE: &str = "\
+Usage: yarn-slinger compile <PATH> [OPTIONS]
+
+Compiles a .yarn file, a directory of .yarn files or a .yarnproject into a .yarnc program
+and a .strings.csv file with its lines, as loaded by `YarnSpinnerPlugin::with_precompiled_program`.
+
+Options:
+  -o, --output <FILE>    Where to write the program [default: <NAME>.yarnc]
+      --language <LANG>  The language of the lines [default: base language of the project or en-US]
+      --no-color         Print diagnostics without colors";
+
+pub(crate) fn run(arguments: Vec<String>) -> Result<ExitCode> {
+    let args = Args::parse(
+        arguments,
+        &["--no-color"],
+        &["-o", "--output", "--language"],
+    )?;
+    let reporter = Reporter::new(args.flag("--no-color"));
+    let sources = YarnSources::collect(args.single_positional("the path to compile")?)?;
+    let Some(compilation) = sources.compile(&reporter)? else {
+        return Ok(ExitCode::from(EXIT_YARN_ERRORS));
+    };
+
+    let output = args
+        .option("--output")
+        .or(args.option("-o"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(format!("{}.yarnc", sources.name)));
+    if let Some(parent_dir) = output.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(parent_dir)
+            .with_context(|| format!("Failed to create \"{}\"", parent_dir.display()))?;
+    }
+    let program = compilation
+        .program
+        .as_ref()
+        .context("The compiler did not produce a program")?;
+    fs::write(&output, program.to_bytes())
+        .with_context(|| format!("Failed to write \"{}\"", output.display()))?;
+
+    let language = args
+        .option("--language")
+        .map(Language::from)
+        .or(sources.base_language)
+        .unwrap_or_default();
+    let strings_file_path = output.with_extension("strings.csv");
+    let records = strings_file::records_from_string_table(&language, &compilation.string_table);
+    strings_file::write(&strings_file_path, &records)?;
+
+    reporter.success(format!(
+        "Compiled {} Yarn {} to \"{}\" and \"{}\" ({})",
+        sources.files.len(),
+        if sources.files.len() == 1 {
+            "file"
+        } else {
+            "files"
+        },
+        output.display(),
+        strings_file_path.display(),
+        summary(&compilation.warnings)
+    ));
+    Ok(ExitCode::SUCCESS)
+}
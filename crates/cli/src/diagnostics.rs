@@ -0,0 +1,98 @@
+use std::io::IsTerminal;
+use yarnspinner::compiler::{Diagnostic, DiagnosticSeverity};
+
+/// Prints diagnostics and messages to stderr, with colors unless they are turned off.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Reporter {
+    color: bool,
+}
+
+impl Reporter {
+    /// Uses colors if stderr is a terminal, the `NO_COLOR` environment variable is not set and `--no-color` was not passed.
+    pub(crate) fn new(no_color: bool) -> Self {
+        let color =
+            !no_color && std::env::var_os("NO_COLOR").is_none() && std::io::stderr().is_terminal();
+        Self { color }
+    }
+
+    pub(crate) fn diagnostics(&self, diagnostics: &[Diagnostic]) {
+        for diagnostic in diagnostics {
+            let rendered = diagnostic.to_string();
+            if self.color {
+                eprint!("{rendered}");
+            } else {
+                eprint!("{}", strip_ansi_codes(&rendered));
+            }
+        }
+    }
+
+    pub(crate) fn error(&self, message: impl AsRef<str>) {
+        eprintln!("{}: {}", self.paint("error", "1;31"), message.as_ref());
+    }
+
+    pub(crate) fn warning(&self, message: impl AsRef<str>) {
+        eprintln!("{}: {}", self.paint("warning", "1;33"), message.as_ref());
+    }
+
+    pub(crate) fn success(&self, message: impl AsRef<str>) {
+        eprintln!("{} {}", self.paint("✓", "1;32"), message.as_ref());
+    }
+
+    /// Prints a dimmed message that is not part of the dialogue, e.g. an executed command.
+    pub(crate) fn note(&self, message: impl AsRef<str>) {
+        eprintln!("{}", self.paint(message.as_ref(), "2"));
+    }
+
+    /// Wraps the text in the given ANSI style, e.g. `1;31` for bold red, if colors are used.
+    pub(crate) fn paint(&self, text: &str, style: &str) -> String {
+        if self.color {
+            format!("\x1b[{style}m{text}\x1b[0m")
+        } else {
+            text.to_owned()
+        }
+    }
+}
+
+/// Summarizes the diagnostics like "2 errors, 1 warning".
+pub(crate) fn summary(diagnostics: &[Diagnostic]) -> String {
+    let errors = count(diagnostics, DiagnosticSeverity::Error);
+    let warnings = count(diagnostics, DiagnosticSeverity::Warning);
+    format!(
+        "{errors} {}, {warnings} {}",
+        plural(errors, "error"),
+        plural(warnings, "warning")
+    )
+}
+
+fn count(diagnostics: &[Diagnostic], severity: DiagnosticSeverity) -> usize {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == severity)
+        .count()
+}
+
+pub(crate) fn plural(count: usize, word: &str) -> String {
+    if count == 1 {
+        word.to_owned()
+    } else {
+        format!("{word}s")
+    }
+}
+
+fn strip_ansi_codes(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(char) = chars.next() {
+        if char == '\x1b' {
+            // Skips control sequences like `ESC [ 1 ; 31 m` up to their final letter
+            for char in chars.by_ref() {
+                if char.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            output.push(char);
+        }
+    }
+    output
+}
@@ -0,0 +1,140 @@
+use crate::args::Args;
+use crate::diagnostics::{plural, Reporter};
+use crate::project::YarnSources;
+use crate::EXIT_YARN_ERRORS;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+use yarnspinner::explorer::{DialogueExplorer, ExplorationReport, ExploredPath};
+use yarnspinner::prelude::*;
+
+pub(crate) const USAGE: &str = "\
+Usage: yarn-slinger explore <PATH> [OPTIONS]
+
+Plays through every combination of options in the dialogue of a .yarn file, a directory of .yarn files or a .yarnproject.
+Reports paths that end in a runtime error or on options that are all unavailable, as well as lines and nodes that no path reaches.
+Paths that reach options with the same variables as an earlier path are not explored any further.
+
+Options:
+      --node <NAME>        The node to start at [default: Start]
+      --max-choices <N>    How many choices a path may make before it is cut off [default: 32]
+      --max-paths <N>      How many paths are explored at most [default: 1000]
+      --transcripts <DIR>  Write the transcript of every path to a file in DIR
+      --no-color           Print without colors";
+
+pub(crate) fn run(arguments: Vec<String>) -> Result<ExitCode> {
+    let args = Args::parse(
+        arguments,
+        &["--no-color"],
+        &["--node", "--max-choices", "--max-paths", "--transcripts"],
+    )?;
+    let reporter = Reporter::new(args.flag("--no-color"));
+    let sources = YarnSources::collect(args.single_positional("the path to explore")?)?;
+    let Some(compilation) = sources.compile(&reporter)? else {
+        return Ok(ExitCode::from(EXIT_YARN_ERRORS));
+    };
+
+    let mut explorer = DialogueExplorer::new(&compilation);
+    if let Some(max_choices) = number_option(&args, "--max-choices")? {
+        explorer = explorer.with_max_choices(max_choices);
+    }
+    if let Some(max_paths) = number_option(&args, "--max-paths")? {
+        explorer = explorer.with_max_paths(max_paths);
+    }
+    let start_node = args.option("--node").unwrap_or("Start");
+    let report = explorer.explore(start_node);
+
+    if let Some(dir) = args.option("--transcripts") {
+        write_transcripts(Path::new(dir), &report)?;
+    }
+    for (number, path) in numbered_paths(&report) {
+        if path.outcome.is_problem() {
+            reporter.error(format!("Path {number}: {}", path.outcome));
+            for line in path.to_string().lines() {
+                eprintln!("  {line}");
+            }
+        }
+    }
+    report_unreachable_content(&report, &compilation, &reporter);
+    if report.truncated {
+        reporter.warning(format!(
+            "Stopped after {} paths, pass a higher --max-paths to explore the rest",
+            report.paths.len()
+        ));
+    }
+
+    let problems = report.problems().count();
+    let summary = format!(
+        "{} {}, {problems} {}",
+        report.paths.len(),
+        plural(report.paths.len(), "path"),
+        plural(problems, "problem")
+    );
+    if problems > 0 {
+        reporter.error(format!("Exploring {} failed: {summary}", sources.name));
+        Ok(ExitCode::from(EXIT_YARN_ERRORS))
+    } else {
+        reporter.success(format!("Explored {}: {summary}", sources.name));
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+fn number_option(args: &Args, name: &str) -> Result<Option<usize>> {
+    args.option(name)
+        .map(|value| {
+            value
+                .parse()
+                .with_context(|| format!("The option {name} needs a number, got \"{value}\""))
+        })
+        .transpose()
+}
+
+/// Numbers the paths starting at 1, like the transcript files.
+fn numbered_paths(report: &ExplorationReport) -> impl Iterator<Item = (usize, &ExploredPath)> {
+    report
+        .paths
+        .iter()
+        .enumerate()
+        .map(|(index, path)| (index + 1, path))
+}
+
+fn write_transcripts(dir: &Path, report: &ExplorationReport) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create \"{}\"", dir.display()))?;
+    for (number, path) in numbered_paths(report) {
+        let file = dir.join(format!("path-{number:04}.txt"));
+        fs::write(&file, format!("{path}\n"))
+            .with_context(|| format!("Failed to write \"{}\"", file.display()))?;
+    }
+    Ok(())
+}
+
+fn report_unreachable_content(
+    report: &ExplorationReport,
+    compilation: &Compilation,
+    reporter: &Reporter,
+) {
+    for node in report.unreachable_nodes() {
+        reporter.warning(format!("No path reaches the node {node}"));
+    }
+    // Lines of unreachable nodes were already reported along with their node
+    let reached_nodes: Vec<_> = report
+        .coverage_report
+        .nodes
+        .iter()
+        .filter(|node| node.visited)
+        .collect();
+    for node in reached_nodes {
+        for line_id in &node.unseen_lines {
+            let text = compilation
+                .string_table
+                .get(line_id)
+                .map(|string_info| string_info.text.as_str())
+                .unwrap_or_default();
+            reporter.warning(format!(
+                "No path reaches the line \"{text}\" ({line_id}) in node {}",
+                node.node_name
+            ));
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::args::Args;
+use crate::diagnostics::Reporter;
+use crate::project::YarnSources;
+use crate::strings_file;
+use crate::EXIT_YARN_ERRORS;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use yarnspinner::prelude::*;
+
+pub(crate) const USAGE: &str = "\
+Usage: yarn-slinger extract-strings <PATH> [OPTIONS]
+
+Exports the lines of a .yarn file, a directory of .yarn files or a .yarnproject in the base language to a .strings.csv file
+that can be handed to translators. Bring the translations back with `yarn-slinger import-strings`.
+
+Options:
+  -o, --output <FILE>    Where to write the strings file [default: <NAME>.strings.csv]
+      --language <LANG>  The base language of the lines [default: base language of the project or en-US]
+      --no-color         Print diagnostics without colors";
+
+pub(crate) fn run(arguments: Vec<String>) -> Result<ExitCode> {
+    let args = Args::parse(
+        arguments,
+        &["--no-color"],
+        &["-o", "--output", "--language"],
+    )?;
+    let reporter = Reporter::new(args.flag("--no-color"));
+    let sources =
+        YarnSources::collect(args.single_positional("the path to extract strings from")?)?;
+    let Some(compilation) = sources.compile_as(CompilationType::StringsOnly, &reporter)? else {
+        return Ok(ExitCode::from(EXIT_YARN_ERRORS));
+    };
+
+    let untagged_lines = compilation
+        .string_table
+        .values()
+        .filter(|string_info| string_info.is_implicit_tag)
+        .count();
+    if untagged_lines > 0 {
+        reporter.warning(format!(
+            "{untagged_lines} of {} lines have no #line: tag, so their IDs change whenever the lines move. \
+            Run `yarn-slinger tag` before handing the strings to translators",
+            compilation.string_table.len()
+        ));
+    }
+
+    let output = args
+        .option("--output")
+        .or(args.option("-o"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(format!("{}.strings.csv", sources.name)));
+    if let Some(parent_dir) = output.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(parent_dir)
+            .with_context(|| format!("Failed to create \"{}\"", parent_dir.display()))?;
+    }
+    let language = args
+        .option("--language")
+        .map(Language::from)
+        .or(sources.base_language)
+        .unwrap_or_default();
+    let records = strings_file::records_from_string_table(&language, &compilation.string_table);
+    strings_file::write(&output, &records)?;
+
+    reporter.success(format!(
+        "Extracted {} lines in {language} to \"{}\"",
+        records.len(),
+        output.display()
+    ));
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,176 @@
+use crate::args::Args;
+use crate::diagnostics::Reporter;
+use crate::project::YarnSources;
+use crate::strings_file::{self, StringsFileRecord};
+use crate::EXIT_YARN_ERRORS;
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use yarnspinner::prelude::*;
+
+pub(crate) const USAGE: &str = "\
+Usage: yarn-slinger import-strings <PATH> <TRANSLATION>... [OPTIONS]
+
+Merges translated .strings.csv files, e.g. created with `yarn-slinger extract-strings`, with the current lines of
+a .yarn file, a directory of .yarn files or a .yarnproject and writes them as <LANG>.strings.csv, ready to be loaded by the game.
+- Translations whose lock does not match the current base text are stale and get marked with \"(NEEDS UPDATE)\"
+- Lines without a translation fall back to the base text
+- Translations of lines that no longer exist are dropped
+
+Options:
+      --output-dir <DIR>  Where to write the merged strings files [default: the directory of the Yarn files]
+      --check             Only verify the translations and exit with 1 if any are stale or unknown, without writing
+      --no-color          Print diagnostics without colors";
+
+/// Marks translations whose base text changed, the same way as `bevy_yarnspinner` does when it updates strings files.
+const UPDATE_PREFIX: &str = "(NEEDS UPDATE) ";
+
+pub(crate) fn run(arguments: Vec<String>) -> Result<ExitCode> {
+    let args = Args::parse(arguments, &["--check", "--no-color"], &["--output-dir"])?;
+    let reporter = Reporter::new(args.flag("--no-color"));
+    let (path, translations) = match args.positional() {
+        [path, translations @ ..] if !translations.is_empty() => (path, translations),
+        [_] => bail!("Missing the translated strings files to import"),
+        _ => bail!("Missing the path to import strings for"),
+    };
+    let sources = YarnSources::collect(path)?;
+    let Some(compilation) = sources.compile_as(CompilationType::StringsOnly, &reporter)? else {
+        return Ok(ExitCode::from(EXIT_YARN_ERRORS));
+    };
+    let base_language = sources.base_language.clone().unwrap_or_default();
+    let base_records =
+        strings_file::records_from_string_table(&base_language, &compilation.string_table);
+    let output_dir = args
+        .option("--output-dir")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| sources.root.clone());
+
+    let mut has_problems = false;
+    for translation in translations {
+        let translation = Path::new(translation);
+        let import = Import::new(strings_file::read(translation)?, &base_records)
+            .with_context(|| format!("Failed to import \"{}\"", translation.display()))?;
+        for (record, base) in &import.stale {
+            reporter.warning(format!(
+                "The {} translation of {} is stale: \"{}\" was translated, but the line is now \"{}\"",
+                import.language, record.id, record.text, base.text
+            ));
+        }
+        for record in &import.unknown {
+            reporter.warning(format!(
+                "The {} translation of {} does not belong to any line and is dropped: \"{}\"",
+                import.language, record.id, record.text
+            ));
+        }
+        has_problems |= !import.stale.is_empty() || !import.unknown.is_empty();
+
+        let statistics = format!(
+            "{} translated, {} stale, {} untranslated, {} unknown",
+            import.translated,
+            import.stale.len(),
+            import.untranslated,
+            import.unknown.len()
+        );
+        if args.flag("--check") {
+            reporter.note(format!("Checked {}: {statistics}", import.language));
+            continue;
+        }
+        fs::create_dir_all(&output_dir)
+            .with_context(|| format!("Failed to create \"{}\"", output_dir.display()))?;
+        let output = output_dir.join(format!("{}.strings.csv", import.language));
+        strings_file::write(&output, &import.records)?;
+        reporter.success(format!(
+            "Imported {} to \"{}\": {statistics}",
+            import.language,
+            output.display()
+        ));
+    }
+
+    if args.flag("--check") && has_problems {
+        Ok(ExitCode::from(EXIT_YARN_ERRORS))
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+/// The translations of one language merged with the current base records.
+#[derive(Debug, Clone)]
+struct Import {
+    language: String,
+    /// One record per current line, in the order of the base records.
+    records: Vec<StringsFileRecord>,
+    translated: usize,
+    untranslated: usize,
+    /// The translations whose base text changed since they were translated, paired with the current base record.
+    stale: Vec<(StringsFileRecord, StringsFileRecord)>,
+    /// The translations of lines that no longer exist.
+    unknown: Vec<StringsFileRecord>,
+}
+
+impl Import {
+    fn new(
+        translations: Vec<StringsFileRecord>,
+        base_records: &[StringsFileRecord],
+    ) -> Result<Self> {
+        let Some(language) = translations.first().map(|record| record.language.clone()) else {
+            bail!("The strings file contains no lines");
+        };
+        if let Some(record) = translations
+            .iter()
+            .find(|record| record.language != language)
+        {
+            bail!(
+                "The strings file mixes the languages {language} and {}",
+                record.language
+            );
+        }
+        let mut translations: HashMap<_, _> = translations
+            .into_iter()
+            .map(|record| (record.id.clone(), record))
+            .collect();
+
+        let mut import = Self {
+            language: language.clone(),
+            records: Vec::with_capacity(base_records.len()),
+            translated: 0,
+            untranslated: 0,
+            stale: Vec::new(),
+            unknown: Vec::new(),
+        };
+        for base in base_records {
+            // Keeps the position and comment of the line up to date in case it moved
+            let mut record = StringsFileRecord {
+                language: language.clone(),
+                ..base.clone()
+            };
+            match translations.remove(&base.id) {
+                Some(translation) if !is_untranslated(&translation) => {
+                    if translation.lock != base.lock || translation.text.starts_with(UPDATE_PREFIX)
+                    {
+                        record.text = if translation.text.starts_with(UPDATE_PREFIX) {
+                            translation.text.clone()
+                        } else {
+                            format!("{UPDATE_PREFIX}{}", translation.text)
+                        };
+                        import.stale.push((translation, base.clone()));
+                    } else {
+                        record.text = translation.text;
+                        import.translated += 1;
+                    }
+                }
+                _ => import.untranslated += 1,
+            }
+            import.records.push(record);
+        }
+        import.unknown = translations.into_values().collect();
+        import.unknown.sort_by(|lhs, rhs| lhs.id.cmp(&rhs.id));
+        Ok(import)
+    }
+}
+
+/// Whether the text was left empty or copied from the base language, which is recognizable by its lock matching the text.
+fn is_untranslated(record: &StringsFileRecord) -> bool {
+    record.text.is_empty() || strings_file::lock(&record.text) == record.lock
+}
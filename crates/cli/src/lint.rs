@@ -0,0 +1,479 @@
+use crate::args::Args;
+use crate::diagnostics::{summary, Reporter};
+use crate::project::YarnSources;
+use crate::EXIT_YARN_ERRORS;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::process::ExitCode;
+use yarnspinner::compiler::{DeclarationSource, Diagnostic, DiagnosticSeverity};
+use yarnspinner::core::{OpCode, Position};
+use yarnspinner::prelude::*;
+
+pub(crate) const USAGE: &str = "\
+Usage: yarn-slinger lint <PATH> [OPTIONS]
+       yarn-slinger check <PATH> [OPTIONS]
+
+Type-checks a .yarn file, a directory of .yarn files or a .yarnproject and runs lint rules on it without writing any output files.
+Exits with 1 if there are errors, which makes it usable as a CI gate.
+
+Rules:
+  compiler-warnings  Warnings reported by the compiler, e.g. calls to deprecated functions [default: warn]
+  unused-variables   Variables that are declared but never read [default: warn]
+  empty-nodes        Nodes without any content [default: warn]
+  untagged-lines     Lines and options without a #line: tag, which cannot be localized [default: allow]
+
+Options:
+      --rules <FILE>       A JSON file mapping rule names to a level of allow, warn or deny, e.g. {\"untagged-lines\": \"deny\"}
+      --allow <RULES>      Comma-separated rules to turn off
+      --warn <RULES>       Comma-separated rules to report as warnings
+      --deny <RULES>       Comma-separated rules to report as errors
+      --deny-warnings      Treat all warnings as errors
+      --format <FORMAT>    Either human or json [default: human]
+      --no-color           Print without colors";
+
+pub(crate) fn run(arguments: Vec<String>) -> Result<ExitCode> {
+    let args = Args::parse(
+        arguments,
+        &["--deny-warnings", "--no-color"],
+        &["--rules", "--allow", "--warn", "--deny", "--format"],
+    )?;
+    let reporter = Reporter::new(args.flag("--no-color"));
+    let format = match args.option("--format").unwrap_or("human") {
+        "human" => Format::Human,
+        "json" => Format::Json,
+        format => bail!("Unknown format {format}, expected human or json"),
+    };
+    let mut rule_set = match args.option("--rules") {
+        Some(path) => RuleSet::from_file(path)?,
+        None => RuleSet::default(),
+    };
+    for (option, level) in [
+        ("--allow", Level::Allow),
+        ("--warn", Level::Warn),
+        ("--deny", Level::Deny),
+    ] {
+        for rule in args
+            .option(option)
+            .into_iter()
+            .flat_map(|rules| rules.split(','))
+        {
+            rule_set.set(rule.trim().parse()?, level);
+        }
+    }
+    let sources = YarnSources::collect(args.single_positional("the path to lint")?)?;
+
+    let mut findings = lint(&sources, &rule_set)?;
+    if args.flag("--deny-warnings") {
+        for finding in &mut findings {
+            finding.diagnostic.severity = DiagnosticSeverity::Error;
+        }
+    }
+    let diagnostics: Vec<_> = findings.iter().map(Finding::labelled_diagnostic).collect();
+    let has_errors = diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error);
+
+    match format {
+        Format::Human => {
+            reporter.diagnostics(&diagnostics);
+            if has_errors {
+                reporter.error(format!(
+                    "Linting {} failed: {}",
+                    sources.name,
+                    summary(&diagnostics)
+                ));
+            } else {
+                reporter.success(format!(
+                    "Linted {}: {}",
+                    sources.name,
+                    summary(&diagnostics)
+                ));
+            }
+        }
+        Format::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&JsonReport::new(&findings))?
+        ),
+    }
+    if has_errors {
+        Ok(ExitCode::from(EXIT_YARN_ERRORS))
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Human,
+    Json,
+}
+
+/// A check that can be turned off or escalated to an error with a [`RuleSet`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum Rule {
+    CompilerWarnings,
+    UnusedVariables,
+    EmptyNodes,
+    UntaggedLines,
+}
+
+impl Rule {
+    const ALL: [Rule; 4] = [
+        Rule::CompilerWarnings,
+        Rule::UnusedVariables,
+        Rule::EmptyNodes,
+        Rule::UntaggedLines,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Rule::CompilerWarnings => "compiler-warnings",
+            Rule::UnusedVariables => "unused-variables",
+            Rule::EmptyNodes => "empty-nodes",
+            Rule::UntaggedLines => "untagged-lines",
+        }
+    }
+
+    fn default_level(self) -> Level {
+        match self {
+            Rule::UntaggedLines => Level::Allow,
+            _ => Level::Warn,
+        }
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Rule {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        Rule::ALL
+            .into_iter()
+            .find(|rule| rule.name() == name)
+            .with_context(|| {
+                let known: Vec<_> = Rule::ALL.iter().map(|rule| rule.name()).collect();
+                format!("Unknown rule {name}, expected one of {}", known.join(", "))
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Level {
+    Allow,
+    Warn,
+    Deny,
+}
+
+/// The level of every rule, falling back to [`Rule::default_level`].
+#[derive(Debug, Clone, Default)]
+struct RuleSet(HashMap<Rule, Level>);
+
+impl RuleSet {
+    fn from_file(path: &str) -> Result<Self> {
+        let json =
+            fs::read_to_string(path).with_context(|| format!("Failed to read \"{path}\""))?;
+        let levels: HashMap<String, Level> =
+            serde_json::from_str(&json).with_context(|| format!("Failed to parse \"{path}\""))?;
+        let mut rule_set = Self::default();
+        for (rule, level) in levels {
+            rule_set.set(rule.parse()?, level);
+        }
+        Ok(rule_set)
+    }
+
+    fn set(&mut self, rule: Rule, level: Level) {
+        self.0.insert(rule, level);
+    }
+
+    fn severity(&self, rule: Rule) -> Option<DiagnosticSeverity> {
+        match self.0.get(&rule).copied().unwrap_or(rule.default_level()) {
+            Level::Allow => None,
+            Level::Warn => Some(DiagnosticSeverity::Warning),
+            Level::Deny => Some(DiagnosticSeverity::Error),
+        }
+    }
+}
+
+/// A diagnostic together with the rule that produced it. Compiler errors have no rule because they cannot be turned off.
+#[derive(Debug, Clone)]
+struct Finding {
+    rule: Option<Rule>,
+    diagnostic: Diagnostic,
+}
+
+impl Finding {
+    /// The diagnostic with the rule appended to its message, so that readers know which rule to configure.
+    fn labelled_diagnostic(&self) -> Diagnostic {
+        let mut diagnostic = self.diagnostic.clone();
+        if let Some(rule) = self.rule {
+            diagnostic.message = format!("{} [{rule}]", diagnostic.message);
+        }
+        diagnostic
+    }
+}
+
+/// Compiles the sources and runs all rules that are not allowed. The lint rules only run if the sources compile.
+fn lint(sources: &YarnSources, rule_set: &RuleSet) -> Result<Vec<Finding>> {
+    let files = sources.read()?;
+    let compilation = match YarnCompiler::new().add_files(files.clone()).compile() {
+        Ok(compilation) => compilation,
+        Err(CompilerError(diagnostics)) => {
+            return Ok(diagnostics
+                .into_iter()
+                .filter_map(|diagnostic| compiler_finding(diagnostic, rule_set))
+                .collect())
+        }
+    };
+    let sources: HashMap<_, _> = files
+        .iter()
+        .map(|file| (file.file_name.as_str(), file.source.as_str()))
+        .collect();
+
+    let mut findings: Vec<_> = compilation
+        .warnings
+        .iter()
+        .cloned()
+        .filter_map(|diagnostic| compiler_finding(diagnostic, rule_set))
+        .collect();
+    let mut report = |rule: Rule, file_name: &str, range: Range, message: String| {
+        let Some(severity) = rule_set.severity(rule) else {
+            return;
+        };
+        let source = sources.get(file_name).copied().unwrap_or_default();
+        findings.push(Finding {
+            rule: Some(rule),
+            diagnostic: diagnostic_at(file_name, source, range, message, severity),
+        });
+    };
+
+    let read_variables: HashSet<String> = compilation
+        .program
+        .iter()
+        .flat_map(|program| program.nodes.values())
+        .flat_map(|node| &node.instructions)
+        .filter(|instruction| {
+            OpCode::try_from(instruction.opcode).ok() == Some(OpCode::PushVariable)
+        })
+        .filter_map(|instruction| instruction.operands.first())
+        .filter_map(|operand| String::try_from(operand.clone()).ok())
+        .collect();
+    for declaration in &compilation.declarations {
+        let DeclarationSource::File(file_name) = &declaration.source_file_name else {
+            continue;
+        };
+        if declaration.is_implicit || read_variables.contains(&declaration.name) {
+            continue;
+        }
+        let range = declaration
+            .range
+            .clone()
+            .map(Range::Exact)
+            .unwrap_or(Range::Line(0));
+        report(
+            Rule::UnusedVariables,
+            file_name,
+            range,
+            format!("Variable {} is declared but never read", declaration.name),
+        );
+    }
+
+    let mut debug_infos: Vec<_> = compilation.debug_info.values().collect();
+    debug_infos.sort_by(|a, b| (&a.file_name, &a.node_name).cmp(&(&b.file_name, &b.node_name)));
+    for debug_info in debug_infos {
+        let node = compilation
+            .program
+            .as_ref()
+            .and_then(|program| program.nodes.get(&debug_info.node_name));
+        let is_empty = node.is_none_or(|node| {
+            node.instructions
+                .iter()
+                .all(|instruction| OpCode::try_from(instruction.opcode).ok() == Some(OpCode::Stop))
+        });
+        if !is_empty {
+            continue;
+        }
+        let source = sources
+            .get(debug_info.file_name.as_str())
+            .copied()
+            .unwrap_or_default();
+        report(
+            Rule::EmptyNodes,
+            &debug_info.file_name,
+            Range::Line(title_line(source, &debug_info.node_name)),
+            format!("Node {} is empty", debug_info.node_name),
+        );
+    }
+
+    let mut untagged_lines: Vec<_> = compilation
+        .string_table
+        .values()
+        .filter(|string_info| string_info.is_implicit_tag)
+        .collect();
+    untagged_lines
+        .sort_by(|a, b| (&a.file_name, a.line_number).cmp(&(&b.file_name, b.line_number)));
+    for string_info in untagged_lines {
+        report(
+            Rule::UntaggedLines,
+            &string_info.file_name,
+            Range::Line(string_info.line_number.saturating_sub(1)),
+            format!("Line \"{}\" has no #line: tag", string_info.text),
+        );
+    }
+
+    findings.sort_by(|a, b| {
+        let key = |finding: &Finding| {
+            let diagnostic = &finding.diagnostic;
+            let start = diagnostic.range.as_ref().map(|range| range.start);
+            (
+                diagnostic.file_name.clone(),
+                start.map(|start| (start.line, start.character)),
+            )
+        };
+        key(a).cmp(&key(b))
+    });
+    Ok(findings)
+}
+
+fn compiler_finding(diagnostic: Diagnostic, rule_set: &RuleSet) -> Option<Finding> {
+    if diagnostic.severity == DiagnosticSeverity::Error {
+        return Some(Finding {
+            rule: None,
+            diagnostic,
+        });
+    }
+    let severity = rule_set.severity(Rule::CompilerWarnings)?;
+    Some(Finding {
+        rule: Some(Rule::CompilerWarnings),
+        diagnostic: Diagnostic {
+            severity,
+            ..diagnostic
+        },
+    })
+}
+
+/// Where a lint finding is located, either exactly or as a whole zero-indexed line.
+#[derive(Debug, Clone)]
+enum Range {
+    Exact(std::ops::Range<Position>),
+    Line(usize),
+}
+
+/// Builds a [`Diagnostic`] that renders like the ones produced by the compiler.
+fn diagnostic_at(
+    file_name: &str,
+    source: &str,
+    range: Range,
+    message: String,
+    severity: DiagnosticSeverity,
+) -> Diagnostic {
+    let range = match range {
+        Range::Exact(range) => range,
+        Range::Line(line) => {
+            let length = source
+                .lines()
+                .nth(line)
+                .map_or(0, |text| text.chars().count());
+            Position { line, character: 0 }..Position {
+                line,
+                character: length.max(1),
+            }
+        }
+    };
+    let start_line = range.start.line;
+    let mut context = source
+        .lines()
+        .skip(start_line)
+        .take(range.end.line - start_line + 1)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if context.is_empty() {
+        // The renderer needs a character to point at
+        context.push(' ');
+    }
+    Diagnostic {
+        file_name: Some(file_name.to_owned()),
+        range: Some(range),
+        message,
+        context: Some(context),
+        severity,
+        start_line,
+    }
+}
+
+/// Finds the zero-indexed line of the `title:` header of a node, falling back to the first line.
+fn title_line(source: &str, node_name: &str) -> usize {
+    source
+        .lines()
+        .position(|line| {
+            line.trim()
+                .strip_prefix("title:")
+                .is_some_and(|title| title.trim() == node_name)
+        })
+        .unwrap_or_default()
+}
+
+/// The machine-readable output of `--format json`. Lines and columns are one-indexed.
+#[derive(Debug, Serialize)]
+struct JsonReport {
+    diagnostics: Vec<JsonDiagnostic>,
+    errors: usize,
+    warnings: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonDiagnostic {
+    rule: Option<Rule>,
+    severity: &'static str,
+    message: String,
+    file: Option<String>,
+    line: Option<usize>,
+    column: Option<usize>,
+    end_line: Option<usize>,
+    end_column: Option<usize>,
+}
+
+impl JsonReport {
+    fn new(findings: &[Finding]) -> Self {
+        let diagnostics: Vec<_> = findings
+            .iter()
+            .map(|finding| {
+                let diagnostic = &finding.diagnostic;
+                let range = diagnostic.range.as_ref();
+                JsonDiagnostic {
+                    rule: finding.rule,
+                    severity: match diagnostic.severity {
+                        DiagnosticSeverity::Error => "error",
+                        DiagnosticSeverity::Warning => "warning",
+                    },
+                    message: diagnostic.message.clone(),
+                    file: diagnostic.file_name.clone(),
+                    line: range.map(|range| range.start.line + 1),
+                    column: range.map(|range| range.start.character + 1),
+                    end_line: range.map(|range| range.end.line + 1),
+                    end_column: range.map(|range| range.end.character + 1),
+                }
+            })
+            .collect();
+        let count = |severity| {
+            diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.severity == severity)
+                .count()
+        };
+        Self {
+            errors: count("error"),
+            warnings: count("warning"),
+            diagnostics,
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! The `yarn-slinger` command line tool for working with Yarn files outside of a game,
+//! e.g. to compile, lint or explore them in a build pipeline, to play through them while writing or to prepare them for localization.
+//!
+//! Exit codes:
+//! - `0`: success
+//! - `1`: the Yarn files contain errors
+//! - `2`: invalid arguments or an I/O failure
+
+use std::process::ExitCode;
+
+mod args;
+mod compile;
+mod diagnostics;
+mod explore;
+mod extract_strings;
+mod import_strings;
+mod lint;
+mod project;
+mod run;
+mod strings_file;
+mod tag;
+
+pub(crate) const EXIT_YARN_ERRORS: u8 = 1;
+const EXIT_USAGE: u8 = 2;
+
+const USAGE: &str = "\
+Usage: yarn-slinger <COMMAND> [ARGS]
+
+Commands:
+  compile          Compile Yarn files into a .yarnc program and a strings file
+  lint             Check Yarn files for errors and lint them without writing output, also available as `check`
+  run              Play the dialogue of Yarn files in the terminal
+  explore          Play through every combination of options to find runtime errors, dead ends and unreachable content
+  tag              Add line tags to the lines of Yarn files that do not have one
+  extract-strings  Export the lines of Yarn files to a strings file for translators
+  import-strings   Merge translated strings files with the current lines of Yarn files
+  help             Print the help of a command
+
+Run `yarn-slinger help <COMMAND>` for more information on a command.";
+
+fn main() -> ExitCode {
+    let mut arguments = std::env::args().skip(1);
+    let command = arguments.next();
+    let arguments: Vec<_> = arguments.collect();
+    let result = match command.as_deref() {
+        Some("compile") => compile::run(arguments),
+        Some("lint" | "check") => lint::run(arguments),
+        Some("run") => run::run(arguments),
+        Some("explore") => explore::run(arguments),
+        Some("tag") => tag::run(arguments),
+        Some("extract-strings") => extract_strings::run(arguments),
+        Some("import-strings") => import_strings::run(arguments),
+        Some("help" | "-h" | "--help") | None => {
+            println!("{}", usage(arguments.first().map(String::as_str)));
+            return ExitCode::SUCCESS;
+        }
+        Some("-V" | "--version") => {
+            println!("yarn-slinger {}", env!("CARGO_PKG_VERSION"));
+            return ExitCode::SUCCESS;
+        }
+        Some(command) => Err(anyhow::anyhow!("Unknown command {command}\n\n{USAGE}")),
+    };
+    result.unwrap_or_else(|error| {
+        diagnostics::Reporter::new(false).error(format!("{error:#}"));
+        ExitCode::from(EXIT_USAGE)
+    })
+}
+
+fn usage(command: Option<&str>) -> &'static str {
+    match command {
+        Some("compile") => compile::USAGE,
+        Some("lint" | "check") => lint::USAGE,
+        Some("run") => run::USAGE,
+        Some("explore") => explore::USAGE,
+        Some("tag") => tag::USAGE,
+        Some("extract-strings") => extract_strings::USAGE,
+        Some("import-strings") => import_strings::USAGE,
+        _ => USAGE,
+    }
+}
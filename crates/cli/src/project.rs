@@ -0,0 +1,185 @@
+use crate::diagnostics::{summary, Reporter};
+use anyhow::{bail, Context, Result};
+use glob::Pattern;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use yarnspinner::prelude::*;
+
+/// The Yarn files to work on, collected from a single `.yarn` file, a directory containing `.yarn` files or a `.yarnproject` file.
+#[derive(Debug, Clone)]
+pub(crate) struct YarnSources {
+    /// Used to name the output files, i.e. the name of the file, directory or project without extension.
+    pub(crate) name: String,
+    /// The directory that the [`YarnSources::files`] are relative to.
+    pub(crate) root: PathBuf,
+    /// The paths of the Yarn files relative to [`YarnSources::root`], sorted.
+    pub(crate) files: Vec<PathBuf>,
+    /// The base language declared in the `.yarnproject`, if any.
+    pub(crate) base_language: Option<Language>,
+}
+
+impl YarnSources {
+    pub(crate) fn collect(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if path.is_dir() {
+            return Self::from_directory(path);
+        }
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("yarnproject") => Self::from_project_file(path),
+            Some("yarn") => Ok(Self {
+                name: file_stem(path),
+                root: parent_dir(path),
+                files: vec![path.file_name().unwrap().into()],
+                base_language: None,
+            }),
+            _ if !path.exists() => bail!("\"{}\" does not exist", path.display()),
+            _ => bail!(
+                "\"{}\" is neither a directory, a .yarn file nor a .yarnproject file",
+                path.display()
+            ),
+        }
+    }
+
+    fn from_directory(dir: &Path) -> Result<Self> {
+        let files = find_files(dir, &["**/*.yarn".to_owned()], &[])?;
+        if files.is_empty() {
+            bail!("Found no .yarn files in \"{}\"", dir.display());
+        }
+        let name = dir
+            .canonicalize()
+            .ok()
+            .as_deref()
+            .map(file_stem)
+            .unwrap_or_else(|| "dialogue".to_owned());
+        Ok(Self {
+            name,
+            root: dir.to_owned(),
+            files,
+            base_language: None,
+        })
+    }
+
+    fn from_project_file(path: &Path) -> Result<Self> {
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read \"{}\"", path.display()))?;
+        let project: YarnProjectFile = serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse \"{}\"", path.display()))?;
+        let root = parent_dir(path);
+        let files = find_files(&root, &project.source_files, &project.exclude_files)?;
+        if files.is_empty() {
+            bail!(
+                "The source files of \"{}\" do not match any .yarn file",
+                path.display()
+            );
+        }
+        Ok(Self {
+            name: file_stem(path),
+            root,
+            files,
+            base_language: project.base_language.map(Language::new),
+        })
+    }
+
+    /// Reads all files, naming them by their path relative to [`YarnSources::root`] with forward slashes.
+    pub(crate) fn read(&self) -> Result<Vec<YarnFile>> {
+        self.files
+            .iter()
+            .map(|file| {
+                let path = self.root.join(file);
+                let source = fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read \"{}\"", path.display()))?;
+                Ok(YarnFile {
+                    file_name: file.to_string_lossy().replace('\\', "/"),
+                    source,
+                })
+            })
+            .collect()
+    }
+
+    /// Compiles the files and prints the diagnostics. Returns [`None`] if there were errors.
+    pub(crate) fn compile(&self, reporter: &Reporter) -> Result<Option<Compilation>> {
+        self.compile_as(CompilationType::FullCompilation, reporter)
+    }
+
+    /// Like [`YarnSources::compile`], but lets the caller skip generating the program when only the string table is needed.
+    pub(crate) fn compile_as(
+        &self,
+        compilation_type: CompilationType,
+        reporter: &Reporter,
+    ) -> Result<Option<Compilation>> {
+        let result = YarnCompiler::new()
+            .with_compilation_type(compilation_type)
+            .add_files(self.read()?)
+            .compile();
+        match result {
+            Ok(compilation) => {
+                reporter.diagnostics(&compilation.warnings);
+                Ok(Some(compilation))
+            }
+            Err(CompilerError(diagnostics)) => {
+                reporter.diagnostics(&diagnostics);
+                reporter.error(format!(
+                    "Failed to compile {}: {}",
+                    self.name,
+                    summary(&diagnostics)
+                ));
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// The subset of the `.yarnproject` format of the original Yarn Spinner that is needed to find the Yarn files.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct YarnProjectFile {
+    #[serde(default = "default_source_files")]
+    source_files: Vec<String>,
+    #[serde(default)]
+    exclude_files: Vec<String>,
+    #[serde(default)]
+    base_language: Option<String>,
+}
+
+fn default_source_files() -> Vec<String> {
+    vec!["**/*.yarn".to_owned()]
+}
+
+fn find_files(root: &Path, include: &[String], exclude: &[String]) -> Result<Vec<PathBuf>> {
+    let exclude = exclude
+        .iter()
+        .map(|pattern| Pattern::new(pattern).with_context(|| format!("Invalid pattern {pattern}")))
+        .collect::<Result<Vec<_>>>()?;
+    let mut files = Vec::new();
+    for pattern in include {
+        let full_pattern = root.join(pattern);
+        let paths = glob::glob(&full_pattern.to_string_lossy())
+            .with_context(|| format!("Invalid pattern {pattern}"))?;
+        for path in paths {
+            let path = path?;
+            let relative = path.strip_prefix(root).unwrap_or(&path).to_owned();
+            let is_excluded = exclude
+                .iter()
+                .any(|pattern| pattern.matches_path(&relative));
+            if path.is_file() && !is_excluded && !files.contains(&relative) {
+                files.push(relative);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "dialogue".to_owned())
+}
+
+fn parent_dir(path: &Path) -> PathBuf {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_owned(),
+        _ => PathBuf::from("."),
+    }
+}
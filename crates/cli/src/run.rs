@@ -0,0 +1,234 @@
+use crate::args::Args;
+use crate::diagnostics::Reporter;
+use crate::project::YarnSources;
+use crate::EXIT_YARN_ERRORS;
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::Path;
+use std::process::{Command, ExitCode};
+use yarnspinner::prelude::*;
+use yarnspinner::runtime::{
+    CoverageReport, DialogueCoverage, MemoryVariableStorage, StringTableTextProvider,
+};
+
+pub(crate) const USAGE: &str = "\
+Usage: yarn-slinger run <PATH> [OPTIONS]
+
+Plays the dialogue of a .yarn file, a directory of .yarn files or a .yarnproject in the terminal.
+Press enter to continue after a line, type the number of an option to select it and type `q` to quit.
+Commands do nothing unless a command hook is given.
+
+Options:
+      --node <NAME>             The node to start at [default: Start]
+      --command-hook <PROGRAM>  A program that is run for each command, with the command name and its parameters as arguments
+      --coverage <FILE>         Adds the lines, options and branches reached in this playthrough to the coverage recorded in FILE
+                                and prints which parts of the dialogue have never been reached
+      --no-color                Print without colors";
+
+pub(crate) fn run(arguments: Vec<String>) -> Result<ExitCode> {
+    let args = Args::parse(
+        arguments,
+        &["--no-color"],
+        &["--node", "--command-hook", "--coverage"],
+    )?;
+    let reporter = Reporter::new(args.flag("--no-color"));
+    let sources = YarnSources::collect(args.single_positional("the path to run")?)?;
+    let Some(compilation) = sources.compile(&reporter)? else {
+        return Ok(ExitCode::from(EXIT_YARN_ERRORS));
+    };
+    let program = compilation.program.clone();
+    let start_node = args.option("--node").unwrap_or("Start");
+    let mut playthrough = Playthrough::new(compilation, start_node, reporter)?;
+    playthrough.command_hook = args.option("--command-hook").map(ToOwned::to_owned);
+    let stdin = std::io::stdin();
+    playthrough.play(&mut stdin.lock())?;
+
+    if let (Some(coverage_path), Some(program)) = (args.option("--coverage"), program) {
+        let coverage = playthrough
+            .dialogue
+            .stop_recording_coverage()
+            .unwrap_or_default();
+        let coverage = update_coverage(Path::new(coverage_path), coverage)?;
+        eprint!("{}", CoverageReport::new(&program, &coverage));
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Adds the coverage to the coverage stored at the path, if any, and stores the result there.
+fn update_coverage(path: &Path, coverage: DialogueCoverage) -> Result<DialogueCoverage> {
+    let mut recorded_coverage = if path.exists() {
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read coverage from {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse coverage from {}", path.display()))?
+    } else {
+        DialogueCoverage::default()
+    };
+    recorded_coverage.merge(&coverage);
+    let json = serde_json::to_string_pretty(&recorded_coverage)?;
+    fs::write(path, json + "\n")
+        .with_context(|| format!("Failed to write coverage to {}", path.display()))?;
+    Ok(recorded_coverage)
+}
+
+/// Drives a [`Dialogue`] with input read line by line, printing the dialogue to stdout and prompts to stderr.
+struct Playthrough {
+    dialogue: Dialogue,
+    metadata: HashMap<LineId, Vec<String>>,
+    command_hook: Option<String>,
+    reporter: Reporter,
+    show_prompts: bool,
+}
+
+/// What the player typed.
+enum Input {
+    Text(String),
+    Quit,
+}
+
+impl Playthrough {
+    fn new(compilation: Compilation, start_node: &str, reporter: Reporter) -> Result<Self> {
+        let mut text_provider = StringTableTextProvider::new();
+        text_provider.extend_base_language(
+            compilation
+                .string_table
+                .iter()
+                .map(|(id, string_info)| (id.clone(), string_info.text.clone()))
+                .collect(),
+        );
+        let metadata = compilation
+            .string_table
+            .into_iter()
+            .map(|(id, string_info)| (id, string_info.metadata))
+            .collect();
+        let mut dialogue = Dialogue::new(
+            Box::new(MemoryVariableStorage::new()),
+            Box::new(text_provider),
+        );
+        dialogue
+            .add_program(
+                compilation
+                    .program
+                    .context("The compiler did not produce a program")?,
+            )
+            .add_debug_info(compilation.debug_info)
+            .start_recording_coverage()
+            .set_node(start_node)
+            .with_context(|| format!("Failed to start at node {start_node}"))?;
+        Ok(Self {
+            dialogue,
+            metadata,
+            command_hook: None,
+            reporter,
+            show_prompts: std::io::stdin().is_terminal(),
+        })
+    }
+
+    fn play(&mut self, input: &mut impl BufRead) -> Result<()> {
+        loop {
+            for event in self.dialogue.continue_()? {
+                match event {
+                    DialogueEvent::Line(line) => {
+                        println!("{}", line.text);
+                        if !self.is_last_line_before_options(&line) {
+                            if let Input::Quit = self.read_input(input, "")? {
+                                return Ok(());
+                            }
+                        }
+                    }
+                    DialogueEvent::Options(options) => {
+                        let Some(option) = self.select_option(input, &options)? else {
+                            return Ok(());
+                        };
+                        self.dialogue.set_selected_option(option)?;
+                    }
+                    DialogueEvent::Command(command) => self.execute_command(&command)?,
+                    DialogueEvent::DialogueComplete => {
+                        self.reporter.note("Dialogue complete");
+                        return Ok(());
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn is_last_line_before_options(&self, line: &YarnLine) -> bool {
+        self.metadata
+            .get(&line.id)
+            .is_some_and(|metadata| metadata.iter().any(|tag| tag == "lastline"))
+    }
+
+    fn select_option(
+        &self,
+        input: &mut impl BufRead,
+        options: &[DialogueOption],
+    ) -> Result<Option<OptionId>> {
+        for (index, option) in options.iter().enumerate() {
+            let number = self.reporter.paint(&format!("{}.", index + 1), "1;36");
+            if option.is_available {
+                println!("  {number} {}", option.line.text);
+            } else {
+                let text = format!("{} (unavailable)", option.line.text);
+                println!("  {number} {}", self.reporter.paint(&text, "2"));
+            }
+        }
+        loop {
+            let Input::Text(text) = self.read_input(input, "> ")? else {
+                return Ok(None);
+            };
+            let option = text
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .and_then(|number| options.get(number.wrapping_sub(1)))
+                .filter(|option| option.is_available);
+            match option {
+                Some(option) => return Ok(Some(option.id)),
+                None => self.reporter.note(format!(
+                    "Type the number of an available option between 1 and {}",
+                    options.len()
+                )),
+            }
+        }
+    }
+
+    fn execute_command(&self, command: &YarnCommand) -> Result<()> {
+        self.reporter.note(format!("<<{}>>", command.raw.trim()));
+        let Some(command_hook) = &self.command_hook else {
+            return Ok(());
+        };
+        std::io::stdout().flush()?;
+        let status = Command::new(command_hook)
+            .arg(&command.name)
+            .args(command.parameters.iter().map(ToString::to_string))
+            .status()
+            .with_context(|| format!("Failed to run the command hook {command_hook}"))?;
+        if !status.success() {
+            bail!(
+                "The command hook {command_hook} failed for the command <<{}>> with {status}",
+                command.raw.trim()
+            );
+        }
+        Ok(())
+    }
+
+    fn read_input(&self, input: &mut impl BufRead, prompt: &str) -> Result<Input> {
+        if self.show_prompts {
+            eprint!("{prompt}");
+            std::io::stderr().flush()?;
+        }
+        std::io::stdout().flush()?;
+        let mut text = String::new();
+        if input.read_line(&mut text)? == 0 {
+            self.reporter.note("Input ended");
+            return Ok(Input::Quit);
+        }
+        match text.trim() {
+            "q" | "quit" => Ok(Input::Quit),
+            _ => Ok(Input::Text(text)),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use yarnspinner::prelude::*;
+
+/// A row of a strings file, in the same CSV format that `bevy_yarnspinner` reads and writes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct StringsFileRecord {
+    pub(crate) language: String,
+    pub(crate) id: String,
+    pub(crate) text: String,
+    pub(crate) file: String,
+    pub(crate) node: String,
+    #[serde(rename = "lineNumber", alias = "line_number")]
+    pub(crate) line_number: usize,
+    pub(crate) lock: String,
+    pub(crate) comment: String,
+}
+
+impl StringsFileRecord {
+    pub(crate) fn from_string_info(
+        language: &Language,
+        id: &LineId,
+        string_info: &StringInfo,
+    ) -> Self {
+        Self {
+            language: language.to_string(),
+            id: id.to_string(),
+            text: string_info.text.clone(),
+            file: string_info.file_name.clone(),
+            node: string_info.node_name.clone(),
+            line_number: string_info.line_number,
+            lock: lock(&string_info.text),
+            comment: comment(&string_info.metadata),
+        }
+    }
+}
+
+/// Converts a string table into records sorted by file and line number.
+pub(crate) fn records_from_string_table<'a>(
+    language: &Language,
+    string_table: impl IntoIterator<Item = (&'a LineId, &'a StringInfo)>,
+) -> Vec<StringsFileRecord> {
+    let mut records: Vec<_> = string_table
+        .into_iter()
+        .map(|(id, string_info)| StringsFileRecord::from_string_info(language, id, string_info))
+        .collect();
+    records.sort_by(|lhs, rhs| {
+        lhs.file
+            .cmp(&rhs.file)
+            .then(lhs.line_number.cmp(&rhs.line_number))
+    });
+    records
+}
+
+pub(crate) fn read(path: &Path) -> Result<Vec<StringsFileRecord>> {
+    let mut reader = csv::Reader::from_path(path)
+        .with_context(|| format!("Failed to open strings file \"{}\"", path.display()))?;
+    reader
+        .deserialize()
+        .collect::<Result<_, _>>()
+        .with_context(|| format!("Failed to parse strings file \"{}\"", path.display()))
+}
+
+pub(crate) fn write(path: &Path, records: &[StringsFileRecord]) -> Result<()> {
+    let mut writer = csv::Writer::from_path(path)
+        .with_context(|| format!("Failed to create strings file \"{}\"", path.display()))?;
+    for record in records {
+        writer.serialize(record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// The first 8 characters of the SHA-256 hash of the text, used to detect outdated translations.
+pub(crate) fn lock(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text))
+        .chars()
+        .take(8)
+        .collect()
+}
+
+fn comment(metadata: &[String]) -> String {
+    let metadata: Vec<_> = metadata
+        .iter()
+        .filter(|metadata| !metadata.starts_with("line:"))
+        .map(String::as_str)
+        .collect();
+    if metadata.is_empty() {
+        String::new()
+    } else {
+        format!("Line metadata: {}", metadata.join(" "))
+    }
+}
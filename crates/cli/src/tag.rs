@@ -0,0 +1,168 @@
+use crate::args::Args;
+use crate::diagnostics::{plural, Reporter};
+use crate::project::YarnSources;
+use crate::EXIT_YARN_ERRORS;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::process::ExitCode;
+use yarnspinner::compiler::StringInfo;
+use yarnspinner::prelude::*;
+
+pub(crate) const USAGE: &str = "\
+Usage: yarn-slinger tag <PATH> [OPTIONS]
+
+Adds a `#line:` tag to every line and option of a .yarn file, a directory of .yarn files or a .yarnproject that does not have one yet,
+which is needed before the dialogue can be localized. The new tags are unique across all files.
+
+Options:
+      --dry-run   Only report which files would be changed
+      --diff      Print the changes as a unified diff instead of writing them
+      --no-color  Print without colors";
+
+/// The number of unchanged lines shown around each change in a diff.
+const DIFF_CONTEXT: usize = 3;
+
+pub(crate) fn run(arguments: Vec<String>) -> Result<ExitCode> {
+    let args = Args::parse(arguments, &["--dry-run", "--diff", "--no-color"], &[])?;
+    let reporter = Reporter::new(args.flag("--no-color"));
+    let sources = YarnSources::collect(args.single_positional("the path to tag")?)?;
+    let Some(compilation) = sources.compile_as(CompilationType::StringsOnly, &reporter)? else {
+        return Ok(ExitCode::from(EXIT_YARN_ERRORS));
+    };
+    let write = !args.flag("--dry-run") && !args.flag("--diff");
+
+    let mut existing_tags = explicit_tags(&compilation.string_table);
+    let mut tagged_lines = 0;
+    let mut tagged_files = 0;
+    for file in sources.read()? {
+        let Some(tagged_source) =
+            YarnCompiler::add_tags_to_lines(file.source.clone(), existing_tags.clone())
+                .with_context(|| format!("Failed to add line tags to {}", file.file_name))?
+        else {
+            continue;
+        };
+        let new_tags = new_tags(&file.file_name, &tagged_source, &existing_tags)?;
+        tagged_lines += new_tags.len();
+        tagged_files += 1;
+        existing_tags.extend(new_tags.iter().cloned());
+
+        if args.flag("--diff") {
+            print!(
+                "{}",
+                unified_diff(&file.file_name, &file.source, &tagged_source)
+            );
+        }
+        if write {
+            let path = sources.root.join(&file.file_name);
+            fs::write(&path, tagged_source)
+                .with_context(|| format!("Failed to write \"{}\"", path.display()))?;
+            reporter.success(format!(
+                "Tagged {} in {}",
+                count(new_tags.len(), "line"),
+                file.file_name
+            ));
+        } else if args.flag("--dry-run") {
+            reporter.note(format!(
+                "Would tag {} in {}",
+                count(new_tags.len(), "line"),
+                file.file_name
+            ));
+        }
+    }
+
+    if tagged_files == 0 {
+        reporter.success("All lines are already tagged");
+    } else if write {
+        reporter.success(format!(
+            "Tagged {} in {}",
+            count(tagged_lines, "line"),
+            count(tagged_files, "file")
+        ));
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// The tags that were written in the source, as opposed to the implicit ones the compiler generates for untagged lines.
+fn explicit_tags(string_table: &HashMap<LineId, StringInfo>) -> Vec<LineId> {
+    let mut tags: Vec<_> = string_table
+        .iter()
+        .filter(|(_, string_info)| !string_info.is_implicit_tag)
+        .map(|(id, _)| id.clone())
+        .collect();
+    tags.sort_by(|a, b| a.0.cmp(&b.0));
+    tags
+}
+
+/// Finds the tags that [`YarnCompiler::add_tags_to_lines`] added to a file by compiling its new source.
+fn new_tags(file_name: &str, tagged_source: &str, existing_tags: &[LineId]) -> Result<Vec<LineId>> {
+    let compilation = YarnCompiler::new()
+        .with_compilation_type(CompilationType::StringsOnly)
+        .add_file(YarnFile {
+            file_name: file_name.to_owned(),
+            source: tagged_source.to_owned(),
+        })
+        .compile()
+        .with_context(|| format!("Failed to compile {file_name} after adding line tags"))?;
+    Ok(explicit_tags(&compilation.string_table)
+        .into_iter()
+        .filter(|tag| !existing_tags.contains(tag))
+        .collect())
+}
+
+/// Renders the changes between two versions of a file in the unified diff format understood by `patch` and `git apply`.
+/// Lines are compared by position, which is enough because adding tags never inserts or removes lines.
+fn unified_diff(file_name: &str, old: &str, new: &str) -> String {
+    let old_lines: Vec<_> = old.lines().collect();
+    let new_lines: Vec<_> = new.lines().collect();
+    let line_count = old_lines.len().max(new_lines.len());
+    let changed: Vec<_> = (0..line_count)
+        .filter(|&index| old_lines.get(index) != new_lines.get(index))
+        .collect();
+
+    // Merges changes whose context would overlap into the same hunk
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &index in &changed {
+        let start = index.saturating_sub(DIFF_CONTEXT);
+        let end = (index + DIFF_CONTEXT + 1).min(line_count);
+        match hunks.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut diff = format!("--- a/{file_name}\n+++ b/{file_name}\n");
+    for (start, end) in hunks {
+        let old_count = end.min(old_lines.len()).saturating_sub(start);
+        let new_count = end.min(new_lines.len()).saturating_sub(start);
+        diff += &format!(
+            "@@ -{},{old_count} +{},{new_count} @@\n",
+            start + 1,
+            start + 1
+        );
+        let mut index = start;
+        while index < end {
+            if old_lines.get(index) == new_lines.get(index) {
+                diff += &format!(" {}\n", old_lines[index]);
+                index += 1;
+                continue;
+            }
+            // Groups consecutive changes so that all removals come before the additions
+            let run_end = (index..end)
+                .find(|&i| old_lines.get(i) == new_lines.get(i))
+                .unwrap_or(end);
+            for line in old_lines.iter().take(run_end).skip(index) {
+                diff += &format!("-{line}\n");
+            }
+            for line in new_lines.iter().take(run_end).skip(index) {
+                diff += &format!("+{line}\n");
+            }
+            index = run_end;
+        }
+    }
+    diff
+}
+
+fn count(count: usize, word: &str) -> String {
+    format!("{count} {}", plural(count, word))
+}
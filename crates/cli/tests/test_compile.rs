@@ -0,0 +1,129 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::tempdir;
+use yarnspinner::core::Program;
+
+const GREETING: &str = r#"title: Start
+---
+Hello there! #line:hello
+<<jump Farewell>>
+===
+"#;
+
+const FAREWELL: &str = r#"title: Farewell
+---
+Goodbye! #line:bye #sad
+===
+"#;
+
+#[test]
+fn compiles_directory_to_program_and_strings_file() -> Result<()> {
+    let dir = tempdir()?;
+    fs::create_dir(dir.path().join("chapter_1"))?;
+    fs::write(dir.path().join("greeting.yarn"), GREETING)?;
+    fs::write(dir.path().join("chapter_1/farewell.yarn"), FAREWELL)?;
+    let output = dir.path().join("out/game.yarnc");
+
+    let result = yarn_slinger(&[
+        "compile",
+        &dir.path().to_string_lossy(),
+        "--output",
+        &output.to_string_lossy(),
+    ]);
+
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    let program = Program::from_bytes(&fs::read(&output)?)?;
+    assert!(program.nodes.contains_key("Start"));
+    assert!(program.nodes.contains_key("Farewell"));
+    assert_eq!(
+        "language,id,text,file,node,lineNumber,lock,comment\n\
+        en-US,line:bye,Goodbye!,chapter_1/farewell.yarn,Farewell,3,1cb7b221,Line metadata: sad\n\
+        en-US,line:hello,Hello there!,greeting.yarn,Start,3,89b8b8e4,\n",
+        fs::read_to_string(dir.path().join("out/game.strings.csv"))?
+    );
+    Ok(())
+}
+
+#[test]
+fn compiles_yarn_project_with_excluded_files_and_base_language() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join("greeting.yarn"), GREETING)?;
+    fs::write(dir.path().join("farewell.yarn"), FAREWELL)?;
+    fs::write(
+        dir.path().join("broken.yarn"),
+        "title: Broken\n---\n<<if>>\n===\n",
+    )?;
+    fs::write(
+        dir.path().join("game.yarnproject"),
+        r#"{
+            "projectFileVersion": 2,
+            "sourceFiles": ["*.yarn"],
+            "excludeFiles": ["broken.yarn"],
+            "baseLanguage": "de-CH"
+        }"#,
+    )?;
+
+    let result = yarn_slinger_in(dir.path(), &["compile", "game.yarnproject", "--no-color"]);
+
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    assert!(dir.path().join("game.yarnc").exists());
+    let strings_file = fs::read_to_string(dir.path().join("game.strings.csv"))?;
+    assert!(strings_file.contains("de-CH,line:hello,Hello there!"));
+    assert!(stderr(&result).contains("Compiled 2 Yarn files"));
+    Ok(())
+}
+
+#[test]
+fn reports_errors_with_exit_code() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(
+        dir.path().join("broken.yarn"),
+        "title: Start\n---\n<<declare $x = 1>>\n<<set $x to \"text\">>\n===\n",
+    )?;
+
+    let result = yarn_slinger_in(dir.path(), &["compile", "broken.yarn", "--no-color"]);
+
+    assert_eq!(Some(1), result.status.code());
+    let stderr = stderr(&result);
+    assert!(stderr.contains("broken.yarn"), "{stderr}");
+    assert!(
+        stderr.contains("error: Failed to compile broken: 1 error"),
+        "{stderr}"
+    );
+    assert!(!stderr.contains('\x1b'), "{stderr}");
+    assert!(!dir.path().join("broken.yarnc").exists());
+    Ok(())
+}
+
+#[test]
+fn rejects_invalid_arguments_with_exit_code() {
+    for arguments in [
+        vec!["frobnicate"],
+        vec!["compile"],
+        vec!["compile", "does_not_exist.yarn"],
+        vec!["compile", ".", "--unknown"],
+    ] {
+        let result = yarn_slinger(&arguments);
+        assert_eq!(Some(2), result.status.code(), "{arguments:?}");
+        assert!(stderr(&result).starts_with("error: "), "{arguments:?}");
+    }
+}
+
+fn yarn_slinger(arguments: &[&str]) -> Output {
+    yarn_slinger_in(Path::new("."), arguments)
+}
+
+fn yarn_slinger_in(dir: &Path, arguments: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_yarn-slinger"))
+        .args(arguments)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
@@ -0,0 +1,88 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::tempdir;
+
+const QUEST: &str = r#"title: Start
+---
+<<declare $gold = 0>>
+Guard: Halt!
+-> Pay
+    <<set $gold to 5>>
+    <<jump Gate>>
+-> Leave
+    You walk away.
+===
+title: Gate
+---
+Guard: Go on.
+-> Enter <<if $gold > 10>>
+===
+title: Secret
+---
+Nobody finds this.
+===
+"#;
+
+#[test]
+fn reports_dead_ends_and_unreachable_content() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join("quest.yarn"), QUEST)?;
+
+    let result = yarn_slinger_in(dir.path(), &["explore", ".", "--transcripts", "paths"]);
+
+    assert_eq!(Some(1), result.status.code(), "{}", stderr(&result));
+    let messages = stderr(&result);
+    assert!(
+        messages.contains("error: Path 1: dead end: no option is available\n  Guard: Halt!\n"),
+        "{messages}"
+    );
+    assert!(messages.contains("warning: No path reaches the node Secret"));
+    assert!(
+        messages.contains("failed: 2 paths, 1 problem"),
+        "{messages}"
+    );
+    assert_eq!(
+        "Guard: Halt!\n-> Pay\n-> Leave\n[selected Leave]\nYou walk away.\n[dialogue complete]\n",
+        fs::read_to_string(dir.path().join("paths/path-0002.txt"))?
+    );
+    Ok(())
+}
+
+#[test]
+fn succeeds_without_problems_and_respects_limits() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(
+        dir.path().join("loop.yarn"),
+        "title: Start\n---\n<<declare $count = 0>>\n<<set $count to $count + 1>>\n-> Again\n    <<jump Start>>\n-> Stop\n===\n",
+    )?;
+
+    let result = yarn_slinger_in(dir.path(), &["explore", "loop.yarn", "--max-paths", "2"]);
+
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    let messages = stderr(&result);
+    assert!(
+        messages.contains("warning: Stopped after 2 paths, pass a higher --max-paths"),
+        "{messages}"
+    );
+    assert!(messages.contains("Explored loop: 2 paths, 0 problems"));
+
+    let result = yarn_slinger_in(dir.path(), &["explore", "loop.yarn", "--max-choices", "x"]);
+    assert_eq!(Some(2), result.status.code());
+    assert!(stderr(&result).contains("The option --max-choices needs a number, got \"x\""));
+    Ok(())
+}
+
+fn yarn_slinger_in(dir: &Path, arguments: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_yarn-slinger"))
+        .args(arguments)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
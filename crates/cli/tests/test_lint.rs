@@ -0,0 +1,167 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::tempdir;
+
+const QUEST: &str = r#"title: Start
+---
+<<declare $gold = 0>>
+<<declare $name = "Ann">>
+Hello {$name}!
+-> Hi #line:hi
+===
+title: Empty
+---
+// TODO
+===
+"#;
+
+#[test]
+fn reports_default_rules_as_warnings() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join("quest.yarn"), QUEST)?;
+
+    let result = yarn_slinger_in(dir.path(), &["lint", "quest.yarn"]);
+
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    let stderr = stderr(&result);
+    assert!(
+        stderr.contains("Variable $gold is declared but never read [unused-variables]"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("Node Empty is empty [empty-nodes]"),
+        "{stderr}"
+    );
+    assert!(!stderr.contains("untagged-lines"), "{stderr}");
+    assert!(
+        stderr.contains("Linted quest: 0 errors, 2 warnings"),
+        "{stderr}"
+    );
+    Ok(())
+}
+
+#[test]
+fn configures_rules_and_prints_json() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join("quest.yarn"), QUEST)?;
+    fs::write(
+        dir.path().join("rules.json"),
+        r#"{"untagged-lines": "deny", "unused-variables": "allow"}"#,
+    )?;
+
+    let result = yarn_slinger_in(
+        dir.path(),
+        &[
+            "check",
+            "quest.yarn",
+            "--rules",
+            "rules.json",
+            "--allow=empty-nodes",
+            "--format",
+            "json",
+        ],
+    );
+
+    assert_eq!(Some(1), result.status.code(), "{}", stderr(&result));
+    let report: Value = serde_json::from_slice(&result.stdout)?;
+    assert_eq!(
+        json!({
+            "diagnostics": [{
+                "rule": "untagged-lines",
+                "severity": "error",
+                "message": "Line \"Hello {0}!\" has no #line: tag",
+                "file": "quest.yarn",
+                "line": 5,
+                "column": 1,
+                "end_line": 5,
+                "end_column": 15,
+            }],
+            "errors": 1,
+            "warnings": 0,
+        }),
+        report
+    );
+    Ok(())
+}
+
+#[test]
+fn deny_warnings_fails_on_warnings() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join("quest.yarn"), QUEST)?;
+
+    let result = yarn_slinger_in(
+        dir.path(),
+        &[
+            "lint",
+            "quest.yarn",
+            "--deny-warnings",
+            "--warn",
+            "untagged-lines",
+        ],
+    );
+
+    assert_eq!(Some(1), result.status.code());
+    assert!(stderr(&result).contains("Linting quest failed: 3 errors, 0 warnings"));
+
+    let result = yarn_slinger_in(
+        dir.path(),
+        &[
+            "lint",
+            "quest.yarn",
+            "--deny-warnings",
+            "--allow",
+            "unused-variables,empty-nodes",
+        ],
+    );
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    Ok(())
+}
+
+#[test]
+fn reports_compiler_errors_without_rule() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(
+        dir.path().join("broken.yarn"),
+        "title: Start\n---\n<<declare $x = 1>>\n<<set $x to \"text\">>\n===\n",
+    )?;
+
+    let result = yarn_slinger_in(dir.path(), &["lint", ".", "--format", "json"]);
+
+    assert_eq!(Some(1), result.status.code());
+    let report: Value = serde_json::from_slice(&result.stdout)?;
+    assert_eq!(1, report["errors"]);
+    assert_eq!(Value::Null, report["diagnostics"][0]["rule"]);
+    assert_eq!("broken.yarn", report["diagnostics"][0]["file"]);
+    Ok(())
+}
+
+#[test]
+fn rejects_unknown_rules_and_formats() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join("quest.yarn"), QUEST)?;
+
+    for arguments in [
+        ["lint", "quest.yarn", "--deny", "no-such-rule"],
+        ["lint", "quest.yarn", "--format", "xml"],
+    ] {
+        let result = yarn_slinger_in(dir.path(), &arguments);
+        assert_eq!(Some(2), result.status.code(), "{arguments:?}");
+    }
+    Ok(())
+}
+
+fn yarn_slinger_in(dir: &Path, arguments: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_yarn-slinger"))
+        .args(arguments)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
@@ -0,0 +1,142 @@
+use anyhow::Result;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use tempfile::tempdir;
+
+const QUEST: &str = r#"title: Start
+---
+<<declare $gold = 0>>
+Guard: Halt!
+<<play_sound "horn" 2>>
+Guard: Pay the toll.
+-> Pay
+    <<set $gold to 10>>
+    Guard: Thank you.
+-> Bribe <<if $gold > 100>>
+-> Leave
+    <<jump Leave>>
+===
+title: Leave
+---
+You walk away.
+===
+"#;
+
+#[test]
+fn plays_lines_and_selected_options() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join("quest.yarn"), QUEST)?;
+
+    let result = yarn_slinger_run(dir.path(), &["quest.yarn"], "\n3\n\n")?;
+
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    assert_eq!(
+        "Guard: Halt!\n\
+        Guard: Pay the toll.\n  \
+        1. Pay\n  \
+        2. Bribe (unavailable)\n  \
+        3. Leave\n\
+        You walk away.\n",
+        stdout(&result)
+    );
+    assert!(stderr(&result).contains("<<play_sound \"horn\" 2>>"));
+    assert!(stderr(&result).contains("Dialogue complete"));
+    Ok(())
+}
+
+#[test]
+fn asks_again_for_unavailable_options_and_quits_on_request() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join("quest.yarn"), QUEST)?;
+
+    let result = yarn_slinger_run(dir.path(), &["quest.yarn"], "\n2\n7\n1\nq\n")?;
+
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    assert!(stdout(&result).ends_with("Guard: Thank you.\n"));
+    let stderr = stderr(&result);
+    assert_eq!(
+        2,
+        stderr
+            .matches("Type the number of an available option between 1 and 3")
+            .count()
+    );
+    assert!(!stderr.contains("Dialogue complete"));
+    Ok(())
+}
+
+#[test]
+fn starts_at_given_node_and_runs_command_hook() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(
+        dir.path().join("hook.yarn"),
+        "title: Other\n---\n<<play_sound \"horn\" 2>>\nDone.\n===\n",
+    )?;
+
+    let result = yarn_slinger_run(
+        dir.path(),
+        &["hook.yarn", "--node", "Other", "--command-hook", "echo"],
+        "\n",
+    )?;
+
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    assert_eq!("play_sound horn 2\nDone.\n", stdout(&result));
+
+    let result = yarn_slinger_run(dir.path(), &["hook.yarn", "--node", "Missing"], "")?;
+    assert_eq!(Some(2), result.status.code());
+    assert!(stderr(&result).contains("Failed to start at node Missing"));
+    Ok(())
+}
+
+#[test]
+fn accumulates_coverage_across_playthroughs() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join("quest.yarn"), QUEST)?;
+
+    let arguments = ["quest.yarn", "--coverage", "coverage.json"];
+    let result = yarn_slinger_run(dir.path(), &arguments, "\n3\n\n")?;
+
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    let messages = stderr(&result);
+    assert!(
+        messages.contains("Start        2/3        1/3        0/0     50.0%"),
+        "{messages}"
+    );
+    assert!(messages.contains("Leave        1/1        0/0        0/0    100.0%"));
+    assert!(messages.contains("Never seen lines:\n  Start: line:"));
+    assert!(dir.path().join("coverage.json").exists());
+
+    let result = yarn_slinger_run(dir.path(), &arguments, "\n1\n\n")?;
+
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    let messages = stderr(&result);
+    assert!(
+        messages.contains("Start        3/3        2/3        0/0     83.3%"),
+        "{messages}"
+    );
+    assert!(!messages.contains("Never seen lines"));
+    Ok(())
+}
+
+fn yarn_slinger_run(dir: &Path, arguments: &[&str], input: &str) -> Result<Output> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_yarn-slinger"))
+        .arg("run")
+        .args(arguments)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    child.stdin.take().unwrap().write_all(input.as_bytes())?;
+    Ok(child.wait_with_output()?)
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
@@ -0,0 +1,170 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::tempdir;
+
+const DIALOGUE: &str = r#"title: Start
+---
+Hello! #line:hello
+Goodbye! #line:bye #sad
+===
+"#;
+
+#[test]
+fn extracts_base_language_strings() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join("dialogue.yarn"), DIALOGUE)?;
+
+    let result = yarn_slinger_in(
+        dir.path(),
+        &[
+            "extract-strings",
+            ".",
+            "-o",
+            "loc/base.csv",
+            "--language",
+            "en-GB",
+        ],
+    );
+
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    assert_eq!(
+        "language,id,text,file,node,lineNumber,lock,comment\n\
+        en-GB,line:hello,Hello!,dialogue.yarn,Start,3,334d016f,\n\
+        en-GB,line:bye,Goodbye!,dialogue.yarn,Start,4,1cb7b221,Line metadata: sad\n",
+        fs::read_to_string(dir.path().join("loc/base.csv"))?
+    );
+    assert!(stderr(&result).contains("Extracted 2 lines in en-GB"));
+    assert!(!stderr(&result).contains("warning"));
+    Ok(())
+}
+
+#[test]
+fn warns_about_untagged_lines_when_extracting() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(
+        dir.path().join("dialogue.yarn"),
+        "title: Start\n---\nHello!\nGoodbye! #line:bye\n===\n",
+    )?;
+
+    let result = yarn_slinger_in(dir.path(), &["extract-strings", "dialogue.yarn"]);
+
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    assert!(dir.path().join("dialogue.strings.csv").exists());
+    assert!(stderr(&result).contains("warning: 1 of 2 lines have no #line: tag"));
+    Ok(())
+}
+
+#[test]
+fn imports_translations_and_flags_stale_lines() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join("dialogue.yarn"), DIALOGUE)?;
+    fs::write(
+        dir.path().join("translated.csv"),
+        "language,id,text,file,node,lineNumber,lock,comment\n\
+        de-CH,line:hello,Hallo!,dialogue.yarn,Start,3,334d016f,\n\
+        de-CH,line:bye,Goodbye!,dialogue.yarn,Start,4,1cb7b221,Line metadata: sad\n\
+        de-CH,line:gone,Weg,dialogue.yarn,Start,5,00000000,\n",
+    )?;
+    // The writer changes and moves a line after the strings were sent to translation
+    fs::write(
+        dir.path().join("dialogue.yarn"),
+        "title: Start\n---\nHi there! #line:new\nHello, friend! #line:hello\nGoodbye! #line:bye #sad\n===\n",
+    )?;
+
+    let result = yarn_slinger_in(
+        dir.path(),
+        &["import-strings", ".", "translated.csv", "--check"],
+    );
+
+    assert_eq!(Some(1), result.status.code(), "{}", stderr(&result));
+    assert!(!dir.path().join("de-CH.strings.csv").exists());
+    let messages = stderr(&result);
+    assert!(
+        messages.contains(
+            "warning: The de-CH translation of line:hello is stale: \"Hallo!\" was translated, but the line is now \"Hello, friend!\""
+        ),
+        "{messages}"
+    );
+    assert!(
+        messages
+            .contains("warning: The de-CH translation of line:gone does not belong to any line"),
+        "{messages}"
+    );
+    assert!(
+        messages.contains("Checked de-CH: 0 translated, 1 stale, 2 untranslated, 1 unknown"),
+        "{messages}"
+    );
+
+    let result = yarn_slinger_in(
+        dir.path(),
+        &[
+            "import-strings",
+            ".",
+            "translated.csv",
+            "--output-dir",
+            "loc",
+        ],
+    );
+
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    assert_eq!(
+        "language,id,text,file,node,lineNumber,lock,comment\n\
+        de-CH,line:new,Hi there!,dialogue.yarn,Start,3,d451d2a7,\n\
+        de-CH,line:hello,(NEEDS UPDATE) Hallo!,dialogue.yarn,Start,4,3d662cce,\n\
+        de-CH,line:bye,Goodbye!,dialogue.yarn,Start,5,1cb7b221,Line metadata: sad\n",
+        fs::read_to_string(dir.path().join("loc/de-CH.strings.csv"))?
+    );
+
+    // Importing the merged file again only flags the line that still needs an update
+    let result = yarn_slinger_in(
+        dir.path(),
+        &["import-strings", ".", "loc/de-CH.strings.csv", "--check"],
+    );
+    assert_eq!(Some(1), result.status.code());
+    assert!(
+        stderr(&result).contains("Checked de-CH: 0 translated, 1 stale, 2 untranslated, 0 unknown")
+    );
+    Ok(())
+}
+
+#[test]
+fn accepts_up_to_date_translations() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join("dialogue.yarn"), DIALOGUE)?;
+    fs::write(
+        dir.path().join("fr.csv"),
+        "language,id,text,file,node,lineNumber,lock,comment\n\
+        fr-FR,line:hello,Bonjour !,dialogue.yarn,Start,3,334d016f,\n\
+        fr-FR,line:bye,Au revoir !,dialogue.yarn,Start,4,1cb7b221,Line metadata: sad\n",
+    )?;
+
+    let result = yarn_slinger_in(dir.path(), &["import-strings", ".", "fr.csv", "--check"]);
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+
+    let result = yarn_slinger_in(dir.path(), &["import-strings", ".", "fr.csv"]);
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    assert!(stderr(&result).contains("2 translated, 0 stale, 0 untranslated, 0 unknown"));
+    assert_eq!(
+        fs::read_to_string(dir.path().join("fr.csv"))?,
+        fs::read_to_string(dir.path().join("fr-FR.strings.csv"))?
+    );
+
+    let result = yarn_slinger_in(dir.path(), &["import-strings", "."]);
+    assert_eq!(Some(2), result.status.code());
+    Ok(())
+}
+
+fn yarn_slinger_in(dir: &Path, arguments: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_yarn-slinger"))
+        .args(arguments)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
@@ -0,0 +1,143 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::tempdir;
+
+const GREETING: &str = r#"title: Start
+---
+Hello there! #line:hello
+How are you?
+-> Fine
+-> Tired #line:tired
+===
+"#;
+
+const FAREWELL: &str = r#"title: Farewell
+---
+Goodbye!
+===
+"#;
+
+#[test]
+fn tags_untagged_lines_of_all_files() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join("greeting.yarn"), GREETING)?;
+    fs::write(dir.path().join("farewell.yarn"), FAREWELL)?;
+
+    let result = yarn_slinger_in(dir.path(), &["tag", "."]);
+
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    let greeting = fs::read_to_string(dir.path().join("greeting.yarn"))?;
+    let farewell = fs::read_to_string(dir.path().join("farewell.yarn"))?;
+    let greeting_lines: Vec<_> = greeting.lines().collect();
+    assert_eq!("Hello there! #line:hello", greeting_lines[2]);
+    assert!(greeting_lines[3].starts_with("How are you? #line:"));
+    assert!(greeting_lines[4].starts_with("-> Fine #line:"));
+    assert_eq!("-> Tired #line:tired", greeting_lines[5]);
+    assert!(farewell
+        .lines()
+        .nth(2)
+        .unwrap()
+        .starts_with("Goodbye! #line:"));
+
+    let mut tags: Vec<_> = [&greeting, &farewell]
+        .iter()
+        .flat_map(|source| source.split_whitespace())
+        .filter(|word| word.starts_with("#line:"))
+        .collect();
+    assert_eq!(5, tags.len());
+    tags.sort();
+    tags.dedup();
+    assert_eq!(5, tags.len(), "Tags are not unique: {tags:?}");
+
+    let messages = stderr(&result);
+    assert!(
+        messages.contains("Tagged 1 line in farewell.yarn"),
+        "{messages}"
+    );
+    assert!(
+        messages.contains("Tagged 2 lines in greeting.yarn"),
+        "{messages}"
+    );
+    assert!(messages.contains("Tagged 3 lines in 2 files"), "{messages}");
+
+    let result = yarn_slinger_in(dir.path(), &["tag", "."]);
+    assert_eq!(Some(0), result.status.code());
+    assert!(stderr(&result).contains("All lines are already tagged"));
+    assert_eq!(
+        greeting,
+        fs::read_to_string(dir.path().join("greeting.yarn"))?
+    );
+    Ok(())
+}
+
+#[test]
+fn dry_run_and_diff_do_not_write() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join("greeting.yarn"), GREETING)?;
+
+    let result = yarn_slinger_in(dir.path(), &["tag", "greeting.yarn", "--dry-run"]);
+
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    assert!(stderr(&result).contains("Would tag 2 lines in greeting.yarn"));
+    assert!(result.stdout.is_empty());
+    assert_eq!(
+        GREETING,
+        fs::read_to_string(dir.path().join("greeting.yarn"))?
+    );
+
+    let result = yarn_slinger_in(dir.path(), &["tag", "greeting.yarn", "--diff"]);
+
+    assert_eq!(Some(0), result.status.code(), "{}", stderr(&result));
+    assert_eq!(
+        GREETING,
+        fs::read_to_string(dir.path().join("greeting.yarn"))?
+    );
+    let diff = String::from_utf8(result.stdout)?;
+    let diff_lines: Vec<_> = diff.lines().collect();
+    assert_eq!(
+        [
+            "--- a/greeting.yarn",
+            "+++ b/greeting.yarn",
+            "@@ -1,7 +1,7 @@",
+            " title: Start",
+            " ---",
+            " Hello there! #line:hello",
+            "-How are you?",
+            "--> Fine",
+        ],
+        diff_lines[..8]
+    );
+    assert!(diff_lines[8].starts_with("+How are you? #line:"));
+    assert!(diff_lines[9].starts_with("+-> Fine #line:"));
+    assert_eq!([" -> Tired #line:tired", " ==="], diff_lines[10..]);
+    Ok(())
+}
+
+#[test]
+fn refuses_to_tag_files_with_errors() -> Result<()> {
+    let dir = tempdir()?;
+    let broken = "title: Start\n---\nHello\n<<if $x>>\n===\n";
+    fs::write(dir.path().join("broken.yarn"), broken)?;
+
+    let result = yarn_slinger_in(dir.path(), &["tag", "broken.yarn"]);
+
+    assert_eq!(Some(1), result.status.code());
+    assert!(stderr(&result).contains("error: Failed to compile broken"));
+    assert_eq!(broken, fs::read_to_string(dir.path().join("broken.yarn"))?);
+    Ok(())
+}
+
+fn yarn_slinger_in(dir: &Path, arguments: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_yarn-slinger"))
+        .args(arguments)
+        .current_dir(dir)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
@@ -1,12 +1,14 @@
 use std::env;
-use std::io::Result;
+use std::fs;
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Path, PathBuf};
 use yarnspinner_codegen::*;
 
 fn main() -> Result<()> {
     let include_dir = path(ProjectPath::ThirdPersonYarnSpinner).join("YarnSpinner");
-    let proto_file = include_dir.join("yarn_spinner.proto");
+    let proto_file = extend_proto(&include_dir.join("yarn_spinner.proto"))?;
     let output_dir = path(ProjectPath::Core).join("src/generated");
-    env::set_var("OUT_DIR", output_dir);
+    env::set_var("OUT_DIR", &output_dir);
 
     prost_build::Config::new()
         .type_attribute(
@@ -20,6 +22,83 @@ fn main() -> Result<()> {
                  reflect(Serialize, Deserialize)\n\
              )]",
         )
-        .compile_protos(&[proto_file], &[include_dir])?;
-    Ok(())
+        // `OperandList` contains `Operand`s, so deriving `Reflect` must not require its fields to be reflectable first
+        .type_attribute(
+            ".Yarn.OperandList",
+            "#[cfg_attr(feature = \"bevy\", reflect(no_field_bounds))]",
+        )
+        .compile_protos(
+            &[&proto_file],
+            &[proto_file.parent().unwrap(), &include_dir],
+        )?;
+    make_maps_no_std_compatible(&output_dir.join("yarn.rs"))
+}
+
+/// Fields added to the `value` oneof of `Operand` that are not part of the original protocol.
+/// They use tags the original does not, so programs compiled by the original Yarn Spinner stay readable.
+const OPERAND_VALUE_EXTENSIONS: &str = "
+\t\t// A list of operands.
+\t\tOperandList list_value = 4;
+
+\t\t// A floating point number that loses precision as a float_value.
+\t\tdouble double_value = 5;
+";
+
+/// Messages that are not part of the original protocol.
+const MESSAGE_EXTENSIONS: &str = "
+// A list of values used by an Operand.
+message OperandList {
+\t// The operands contained in this list.
+\trepeated Operand values = 1;
+}
+";
+
+/// Writes a copy of the original protocol that includes [`OPERAND_VALUE_EXTENSIONS`] and [`MESSAGE_EXTENSIONS`]
+/// and returns its path.
+fn extend_proto(original_proto_file: &Path) -> Result<PathBuf> {
+    let source = fs::read_to_string(original_proto_file)?;
+    let last_operand_value = "float float_value = 3;";
+    let Some(index) = source.find(last_operand_value) else {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Could not find \"{last_operand_value}\" in the oneof of `Operand` to extend it"
+            ),
+        ));
+    };
+    let index = index + last_operand_value.len();
+    let extended_source = format!(
+        "{}{OPERAND_VALUE_EXTENSIONS}{}{MESSAGE_EXTENSIONS}",
+        &source[..index],
+        &source[index..]
+    );
+    let extended_dir = env::temp_dir().join("yarnspinner_codegen");
+    fs::create_dir_all(&extended_dir)?;
+    let extended_proto_file = extended_dir.join(original_proto_file.file_name().unwrap());
+    fs::write(&extended_proto_file, extended_source)?;
+    Ok(extended_proto_file)
+}
+
+/// `prost` can only encode `HashMap`s with `std`, so map fields use `ProtoMap` instead,
+/// which is a `HashMap` when the `std` feature of `yarnspinner_core` is enabled and a `BTreeMap` otherwise.
+fn make_maps_no_std_compatible(generated_file: &Path) -> Result<()> {
+    let source = fs::read_to_string(generated_file)?;
+    let mut output = String::with_capacity(source.len());
+    for line in source.lines() {
+        let trimmed = line.trim_start();
+        let indentation = &line[..line.len() - trimmed.len()];
+        if let Some(arguments) = trimmed
+            .strip_prefix("#[prost(map = ")
+            .and_then(|rest| rest.strip_suffix(")]"))
+        {
+            output.push_str(&format!(
+                "{indentation}#[cfg_attr(feature = \"std\", prost(map = {arguments}))]\n\
+                 {indentation}#[cfg_attr(not(feature = \"std\"), prost(btree_map = {arguments}))]\n"
+            ));
+        } else {
+            output.push_str(&line.replace("::std::collections::HashMap<", "ProtoMap<"));
+            output.push('\n');
+        }
+    }
+    fs::write(generated_file, output)
 }
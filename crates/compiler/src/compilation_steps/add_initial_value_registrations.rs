@@ -15,7 +15,10 @@ pub(crate) fn add_initial_value_registrations(
     let declarations = state
         .known_variable_declarations
         .iter()
-        .filter(|decl| !matches!(decl.r#type, Type::Function(_)));
+        // Objects of custom types only exist at runtime and need to be put into the variable storage by the host.
+        .filter(|decl| !matches!(decl.r#type, Type::Function(_) | Type::Custom(_)))
+        // Constants are replaced by their values, so they never need to be stored.
+        .filter(|decl| !state.job.constants.contains_key(&decl.name));
 
     for declaration in declarations {
         let Some(default_value) = declaration.default_value.clone() else {
@@ -29,8 +32,9 @@ pub(crate) fn add_initial_value_registrations(
         if let Some(ref mut program) = compilation.program {
             let value = match &declaration.r#type {
                     Type::String => Operand::from(String::from(default_value)),
-                    Type::Number => Operand::from(f32::try_from(default_value).unwrap()),
+                    Type::Number => Operand::from(f64::try_from(default_value).unwrap()),
                     Type::Boolean => Operand::from(bool::try_from(default_value).unwrap()),
+                    Type::List => Operand::from(YarnList::try_from(default_value).unwrap()),
                     _ => panic!("Cannot create initial value registration for type {}. This is a bug. Please report it at https://github.com/YarnSpinnerTool/YarnSpinner-Rust/issues/new", declaration.r#type.format()),
                 };
             program
@@ -5,7 +5,8 @@ use antlr_rust::tree::ParseTreeVisitorCompat;
 pub(crate) fn check_types(mut state: CompilationIntermediate) -> CompilationIntermediate {
     for (file, known_types) in &mut state.parsed_files {
         let mut visitor =
-            TypeCheckVisitor::new(state.known_variable_declarations.clone(), file.clone());
+            TypeCheckVisitor::new(state.known_variable_declarations.clone(), file.clone())
+                .with_constants(state.job.constants.keys().cloned());
         visitor.visit(file.tree.as_ref());
         state
             .known_variable_declarations
@@ -16,6 +17,9 @@ pub(crate) fn check_types(mut state: CompilationIntermediate) -> CompilationInte
         state.diagnostics.extend(visitor.diagnostics);
         state.potential_issues.extend(visitor.deferred_types);
         known_types.extend(visitor.known_types);
+        state
+            .resolved_overloads
+            .insert(file.name.clone(), visitor.resolved_overloads);
     }
     state
 }
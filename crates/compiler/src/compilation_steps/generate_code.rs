@@ -1,7 +1,7 @@
 use crate::listeners::{CompilerListener, DiagnosticVec};
 use crate::prelude::generated::yarnspinnerparser::YarnSpinnerParserTreeWalker;
 use crate::prelude::*;
-use crate::visitors::KnownTypes;
+use crate::visitors::{KnownTypes, ResolvedOverloads};
 use crate::Result;
 use std::collections::{HashMap, HashSet};
 
@@ -24,7 +24,13 @@ pub(crate) fn generate_code(mut state: CompilationIntermediate) -> CompilationIn
             .map(|(file, known_types)| {
                 generate_code_for_file(
                     &mut state.tracking_nodes,
+                    &state.job.constants,
                     known_types.clone(),
+                    state
+                        .resolved_overloads
+                        .get(&file.name)
+                        .cloned()
+                        .unwrap_or_default(),
                     template.clone(),
                     file,
                 )
@@ -55,15 +61,21 @@ pub(crate) fn generate_code(mut state: CompilationIntermediate) -> CompilationIn
 
 fn generate_code_for_file<'a, 'b: 'a, 'input: 'a + 'b>(
     tracking_nodes: &mut HashSet<String>,
+    constants: &HashMap<String, YarnValue>,
     known_types: KnownTypes,
+    resolved_overloads: ResolvedOverloads,
     result_template: Compilation,
     file: &'a FileParseResult<'input>,
 ) -> Result<Compilation> {
-    let compiler_listener = Box::new(CompilerListener::new(
-        tracking_nodes.clone(),
-        known_types,
-        file.clone(),
-    ));
+    let compiler_listener = Box::new(
+        CompilerListener::new(
+            tracking_nodes.clone(),
+            known_types,
+            resolved_overloads,
+            file.clone(),
+        )
+        .with_constants(constants.clone()),
+    );
     let compiler_tracking_nodes = compiler_listener.tracking_nodes.clone();
     let compiler_diagnostics = compiler_listener.diagnostics.clone();
     let compiler_program = compiler_listener.program.clone();
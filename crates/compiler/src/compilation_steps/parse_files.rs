@@ -1,7 +1,7 @@
 use crate::prelude::*;
 
 pub(crate) fn parse_files(mut state: CompilationIntermediate) -> CompilationIntermediate {
-    for (file, chars) in state.job.files.iter().zip(state.file_chars.iter()) {
+    for (file, chars) in state.files.iter().zip(state.file_chars.iter()) {
         let parse_result = parse_syntax_tree(file, chars, &mut state.diagnostics);
         state.parsed_files.push((parse_result, Default::default()));
     }
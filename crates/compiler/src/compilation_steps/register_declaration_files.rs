@@ -0,0 +1,15 @@
+use crate::prelude::*;
+
+pub(crate) fn register_declaration_files(
+    mut state: CompilationIntermediate,
+) -> CompilationIntermediate {
+    for file in &state.declaration_files {
+        match SignatureManifest::from_declaration_file(file) {
+            Ok(manifest) => state
+                .known_variable_declarations
+                .extend(manifest.declarations()),
+            Err(CompilerError(diagnostics)) => state.diagnostics.extend(diagnostics),
+        }
+    }
+    state
+}
@@ -1,5 +1,6 @@
 use crate::prelude::*;
 use yarnspinner_core::prelude::*;
+use yarnspinner_core::types::{Type, TypeFormat, TypedValue};
 
 pub(crate) fn register_initial_variables(
     mut state: CompilationIntermediate,
@@ -7,10 +8,27 @@ pub(crate) fn register_initial_variables(
     let variables = &mut state.known_variable_declarations;
     let job_variable_declarations = state.job.variable_declarations.clone();
     variables.extend(job_variable_declarations);
-    let standard_library_declarations = get_declarations_from_library(&Library::standard_library());
+    let standard_library_declarations =
+        get_declarations_from_library(&Library::standard_library(), &mut state.diagnostics);
     variables.extend(standard_library_declarations);
-    let job_library_declarations = get_declarations_from_library(&state.job.library);
+    let job_library_declarations =
+        get_declarations_from_library(&state.job.library, &mut state.diagnostics);
     variables.extend(job_library_declarations);
 
+    for (name, value) in &state.job.constants {
+        let r#type = value.r#type();
+        if !matches!(r#type, Type::Number | Type::String | Type::Boolean) {
+            state.diagnostics.push(Diagnostic::from_message(format!(
+                "Constant {name} has a value of type {}, but only numbers, strings and booleans can be constants",
+                r#type.format()
+            )));
+            continue;
+        }
+        let declaration = Declaration::new(name, r#type)
+            .with_default_value(value.clone())
+            .with_description("Constant defined by the host");
+        state.known_variable_declarations.push(declaration);
+    }
+
     state
 }
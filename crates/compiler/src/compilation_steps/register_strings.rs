@@ -1,15 +1,17 @@
+use crate::prelude::generated::yarnspinnerparser::*;
 use crate::prelude::*;
 use crate::visitors::{LastLineBeforeOptionsVisitor, StringTableGeneratorVisitor};
+use antlr_rust::token::Token;
 use antlr_rust::tree::ParseTreeVisitorCompat;
 
 pub(crate) fn register_strings(mut state: CompilationIntermediate) -> CompilationIntermediate {
     // First pass: parse all files, generate their syntax trees,
     // and figure out what variables they've declared
     for (file, _) in &state.parsed_files {
-        // ok now we will add in our lastline tags
+        // ok now we will add in our lastline tags, if the job wants them
         // we do this BEFORE we build our strings table otherwise the tags will get missed
-        // this should probably be a flag instead of every time though
-        let mut last_line_tagger = LastLineBeforeOptionsVisitor::default();
+        let mut last_line_tagger =
+            LastLineBeforeOptionsVisitor::new(state.job.last_line_tag.as_deref());
         last_line_tagger.visit(file.tree.as_ref());
 
         let mut visitor =
@@ -17,7 +19,25 @@ pub(crate) fn register_strings(mut state: CompilationIntermediate) -> Compilatio
         visitor.visit(file.tree.as_ref());
         state.diagnostics.extend(visitor.diagnostics);
         state.string_table.extend(visitor.string_table_manager);
+
+        // Now that every line has an ID, link the options to the line before them
+        for (last_line, options) in last_line_tagger.options_by_last_line {
+            let Some(last_line_id) = line_id_of(&last_line) else {
+                continue;
+            };
+            for option_id in options.iter().filter_map(|option| line_id_of(option)) {
+                if let Some(string_info) = state.string_table.get_mut(&option_id) {
+                    string_info.parent_line_id = Some(last_line_id.clone());
+                }
+            }
+        }
     }
 
     state
 }
+
+fn line_id_of(line: &Line_statementContextAll) -> Option<LineId> {
+    let line_id_tag = get_line_id_tag(&line.hashtag_all())?;
+    let line_id = line_id_tag.text.as_ref()?.get_text();
+    Some(line_id.into())
+}
@@ -22,15 +22,15 @@ pub(crate) fn validate_unique_node_names(
     let nodes_with_names = all_nodes.filter_map(|(node, file)| {
         node.header_all()
             .iter()
-            .find(|header| header.header_key.as_ref().unwrap().get_text() == "title")
-            .map(|title_header| {
-                let title = title_header
-                    .header_value
+            .find(|header| {
+                header
+                    .header_key
                     .as_ref()
-                    .unwrap()
-                    .get_text()
-                    .to_owned();
-                (title, title_header.clone(), file)
+                    .is_some_and(|key| key.get_text() == "title")
+            })
+            .and_then(|title_header| {
+                let title = title_header.header_value.as_ref()?.get_text().to_owned();
+                Some((title, title_header.clone(), file))
             })
     });
 
@@ -2,14 +2,18 @@
 //! and <https://github.com/YarnSpinnerTool/YarnSpinner/blob/da39c7195107d8211f21c263e4084f773b84eaff/YarnSpinner.Compiler/CompilationJob.cs>
 
 use crate::prelude::*;
+use std::collections::HashMap;
 use std::path::Path;
 use yarnspinner_core::prelude::*;
 
 mod add_tags_to_lines;
 pub(crate) mod antlr_rust_ext;
+mod references;
 pub(crate) mod run_compilation;
 pub(crate) mod utils;
 
+pub use references::*;
+
 #[allow(missing_docs)]
 pub type Result<T> = std::result::Result<T, CompilerError>;
 
@@ -21,7 +25,7 @@ pub type Result<T> = std::result::Result<T, CompilerError>;
 /// ## Implementation note
 ///
 /// This type is a combination of the original `CompilationStep` and `Compiler` types, optimized for easier, fluent calling.
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "bevy", derive(Reflect))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "bevy", reflect(Debug, PartialEq))]
@@ -43,6 +47,29 @@ pub struct Compiler {
 
     /// The declarations for variables.
     pub variable_declarations: Vec<Declaration>,
+
+    /// Named constants, keyed by their variable name, see [`Compiler::define_constant`].
+    pub constants: HashMap<String, YarnValue>,
+
+    /// The hashtag added to the metadata of lines that come right before options, see [`Compiler::with_last_line_tag`].
+    /// [`None`] if such lines are not tagged. Defaults to [`DEFAULT_LAST_LINE_TAG`].
+    pub last_line_tag: Option<String>,
+}
+
+/// The default value of [`Compiler::last_line_tag`]. The runtime's `LocalizedLine::is_last_line_before_options` relies on this tag.
+pub const DEFAULT_LAST_LINE_TAG: &str = "lastline";
+
+impl Default for Compiler {
+    fn default() -> Self {
+        Self {
+            files: Default::default(),
+            library: Default::default(),
+            compilation_type: Default::default(),
+            variable_declarations: Default::default(),
+            constants: Default::default(),
+            last_line_tag: Some(DEFAULT_LAST_LINE_TAG.to_owned()),
+        }
+    }
 }
 
 impl Compiler {
@@ -97,6 +124,59 @@ impl Compiler {
         self
     }
 
+    /// Declares the functions of a [`SignatureManifest`], so that calls to them are type checked without registering
+    /// their implementations in the [`Library`].
+    pub fn import_manifest(&mut self, manifest: SignatureManifest) -> &mut Self {
+        self.variable_declarations.extend(manifest.declarations());
+        self
+    }
+
+    /// Defines a named constant, which is used in Yarn like a variable, e.g. `$MAX_REPUTATION` for the name `MAX_REPUTATION`.
+    /// A leading `$` in the name is optional.
+    ///
+    /// Uses of the constant are type checked and replaced by its value at compile time, so the constant is never stored in the
+    /// [`VariableStorage`](https://docs.rs/yarnspinner/latest/yarnspinner/runtime/trait.VariableStorage.html).
+    /// Assigning to a constant is a compile error. Only numbers, strings and booleans can be constants.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use yarnspinner_compiler::prelude::*;
+    /// let compilation = Compiler::new()
+    ///     .add_file(File {
+    ///         file_name: "reputation.yarn".to_owned(),
+    ///         source: "title: Start\n---\n<<declare $reputation = 0>>\n<<set $reputation to $MAX_REPUTATION>>\n===\n".to_owned(),
+    ///     })
+    ///     .define_constant("MAX_REPUTATION", 100)
+    ///     .compile()
+    ///     .unwrap();
+    /// assert!(!compilation.program.unwrap().initial_values.contains_key("$MAX_REPUTATION"));
+    /// ```
+    pub fn define_constant(
+        &mut self,
+        name: impl AsRef<str>,
+        value: impl Into<YarnValue>,
+    ) -> &mut Self {
+        let name = format!("${}", name.as_ref().trim_start_matches('$'));
+        self.constants.insert(name, value.into());
+        self
+    }
+
+    /// Sets the hashtag that is added to lines that come right before options, so that a dialogue view can show them
+    /// together with the options. By default, this is [`DEFAULT_LAST_LINE_TAG`].
+    ///
+    /// Options are linked to such lines via [`StringInfo::parent_line_id`] regardless of this setting.
+    pub fn with_last_line_tag(&mut self, tag: impl Into<String>) -> &mut Self {
+        self.last_line_tag = Some(tag.into());
+        self
+    }
+
+    /// Disables the tagging of lines that come right before options, see [`Compiler::with_last_line_tag`].
+    pub fn without_last_line_tag(&mut self) -> &mut Self {
+        self.last_line_tag = None;
+        self
+    }
+
     /// Compiles the Yarn files previously added into a [`Compilation`].
     pub fn compile(&self) -> Result<Compilation> {
         run_compilation::compile(self)
@@ -33,6 +33,9 @@ impl Compiler {
         existing_line_tags: Vec<LineId>,
     ) -> crate::Result<Option<String>> {
         let contents = contents.into();
+        if is_declaration_file(&contents) {
+            return Ok(None);
+        }
         let chars: Vec<_> = contents.chars().map(|c| c as u32).collect();
         // First, get the parse tree for this source code.
         let file = File {
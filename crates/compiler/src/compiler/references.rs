@@ -0,0 +1,180 @@
+//! Not part of the original Yarn Spinner. Finding and renaming variables, nodes and functions across all files of a
+//! [`Compiler`], e.g. as the backend of an editor's "Find All References" and "Rename Symbol" commands.
+
+use crate::prelude::*;
+use crate::visitors::ReferenceVisitor;
+use antlr_rust::tree::ParseTreeVisitorCompat;
+use std::ops::Range;
+use yarnspinner_core::prelude::*;
+
+/// Something in Yarn source code that can be referenced by name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy", derive(Reflect))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bevy", reflect(Debug, PartialEq, Hash))]
+#[cfg_attr(
+    all(feature = "bevy", feature = "serde"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum Symbol {
+    /// A variable, including its leading `$`, e.g. `$gold`.
+    Variable(String),
+    /// A node, e.g. `Start`. Referenced by its `title` header, `<<jump>>` statements and the string literals passed to
+    /// `visited` and `visited_count`.
+    Node(String),
+    /// A function called in an expression, e.g. `dice`. Functions are defined in the [`Library`], so they have no
+    /// [`ReferenceKind::Definition`] in the source code.
+    Function(String),
+}
+
+/// Whether a [`SymbolReference`] defines the symbol or uses it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy", derive(Reflect))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bevy", reflect(Debug, PartialEq, Hash))]
+#[cfg_attr(
+    all(feature = "bevy", feature = "serde"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum ReferenceKind {
+    /// The `<<declare>>` statement of a variable or the `title` header of a node.
+    Definition,
+    /// Any other mention of the symbol.
+    Usage,
+}
+
+/// A place in the source code where a [`Symbol`] is mentioned, as returned by [`Compiler::find_references`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy", derive(Reflect))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bevy", reflect(Debug, PartialEq, Hash))]
+#[cfg_attr(
+    all(feature = "bevy", feature = "serde"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct SymbolReference {
+    /// The name of the file the reference is in, as given by [`File::file_name`].
+    pub file_name: String,
+    /// The range of the name itself, e.g. `$gold` in `<<set $gold to 1>>` or `Start` in `visited("Start")`.
+    pub range: Range<Position>,
+    /// Whether this reference defines the symbol.
+    pub kind: ReferenceKind,
+}
+
+/// A replacement of text in a file, as returned by [`Compiler::rename`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy", derive(Reflect))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bevy", reflect(Debug, PartialEq, Hash))]
+#[cfg_attr(
+    all(feature = "bevy", feature = "serde"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct TextEdit {
+    /// The name of the file to edit, as given by [`File::file_name`].
+    pub file_name: String,
+    /// The range of text to replace.
+    pub range: Range<Position>,
+    /// The text to replace the range with.
+    pub new_text: String,
+}
+
+impl Compiler {
+    /// Finds all places in the added files where the given symbol is mentioned, in the order of the files and then of
+    /// their appearance. Files with syntax errors are searched as far as they could be parsed.
+    ///
+    /// Positions are relative to the source with a leading byte order mark removed, just like those of [`Diagnostic`]s.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use yarnspinner_compiler::prelude::*;
+    /// let mut compiler = Compiler::new();
+    /// compiler.add_file(File {
+    ///     file_name: "shop.yarn".to_owned(),
+    ///     source: "title: Shop\n---\n<<declare $gold = 10>>\nYou have {$gold} coins.\n===\n".to_owned(),
+    /// });
+    /// let references = compiler.find_references(&Symbol::Variable("$gold".to_owned()));
+    /// assert_eq!(references.len(), 2);
+    /// assert_eq!(references[0].kind, ReferenceKind::Definition);
+    /// assert_eq!(references[1].range.start.line, 3);
+    /// ```
+    pub fn find_references(&self, symbol: &Symbol) -> Vec<SymbolReference> {
+        let mut references = Vec::new();
+        for file in &self.files {
+            if is_declaration_file(&file.source) {
+                continue;
+            }
+            let source = file.source.strip_prefix('\u{feff}').unwrap_or(&file.source);
+            let chars: Vec<u32> = source.chars().map(|c| c as u32).collect();
+            // Syntax errors are reported by `compile`
+            let parse_result = parse_syntax_tree(file, &chars, &mut Vec::new());
+            let mut visitor = ReferenceVisitor::new(symbol, file.file_name.clone());
+            visitor.visit(parse_result.tree.as_ref());
+            references.extend(visitor.references);
+        }
+        references
+    }
+
+    /// Creates the edits that rename the given symbol to `new_name` in all added files. Apply them with [`File::apply_text_edits`].
+    ///
+    /// For variables, the leading `$` of `new_name` is optional. The new name is not validated,
+    /// so make sure that it is a valid identifier and not used by another symbol yet.
+    /// Renaming a function only changes its calls, so it must be registered in the [`Library`] under the new name as well.
+    pub fn rename(&self, symbol: &Symbol, new_name: impl AsRef<str>) -> Vec<TextEdit> {
+        let new_name = match symbol {
+            Symbol::Variable(_) => format!("${}", new_name.as_ref().trim_start_matches('$')),
+            Symbol::Node(_) | Symbol::Function(_) => new_name.as_ref().to_owned(),
+        };
+        self.find_references(symbol)
+            .into_iter()
+            .map(|reference| TextEdit {
+                file_name: reference.file_name,
+                range: reference.range,
+                new_text: new_name.clone(),
+            })
+            .collect()
+    }
+}
+
+impl File {
+    /// Applies the edits made for this file, i.e. the ones whose [`TextEdit::file_name`] matches [`File::file_name`].
+    /// The edits must not overlap.
+    pub fn apply_text_edits<'a>(&mut self, edits: impl IntoIterator<Item = &'a TextEdit>) {
+        let bom_len = if self.source.starts_with('\u{feff}') {
+            '\u{feff}'.len_utf8()
+        } else {
+            0
+        };
+        let mut edits: Vec<_> = edits
+            .into_iter()
+            .filter(|edit| edit.file_name == self.file_name)
+            .filter_map(|edit| {
+                let start = byte_index(&self.source[bom_len..], edit.range.start)? + bom_len;
+                let end = byte_index(&self.source[bom_len..], edit.range.end)? + bom_len;
+                Some((start..end.max(start), edit.new_text.as_str()))
+            })
+            .collect();
+        // Replace from back to front so that the earlier byte indices stay valid
+        edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+        for (range, new_text) in edits {
+            self.source.replace_range(range, new_text);
+        }
+    }
+}
+
+/// The byte index of a position in the source, or [`None`] if the position is out of bounds.
+fn byte_index(source: &str, position: Position) -> Option<usize> {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        line_start += source[line_start..].find('\n')? + 1;
+    }
+    let line = &source[line_start..];
+    let line = &line[..line.find('\n').unwrap_or(line.len())];
+    let character = line
+        .char_indices()
+        .map(|(index, _)| index)
+        .chain(std::iter::once(line.len()))
+        .nth(position.character)?;
+    Some(line_start + character)
+}
@@ -10,6 +10,7 @@ use std::collections::{HashMap, HashSet};
 pub(crate) fn compile(compiler: &Compiler) -> Result<Compilation> {
     let compiler_steps: Vec<&CompilationStep> = vec![
         &register_initial_variables,
+        &register_declaration_files,
         &parse_files,
         &register_strings,
         &validate_unique_node_names,
@@ -25,8 +26,12 @@ pub(crate) fn compile(compiler: &Compiler) -> Result<Compilation> {
         &add_initial_value_registrations,
     ];
 
-    let chars: Vec<Vec<u32>> = compiler
+    // Declaration files contain no nodes, so they are only used for their declarations instead of being parsed.
+    let (declaration_files, files): (Vec<_>, Vec<_>) = compiler
         .files
+        .iter()
+        .partition(|file| is_declaration_file(&file.source));
+    let chars: Vec<Vec<u32>> = files
         .iter()
         .map(|file| {
             // Strip the BOM from the source string if it is present before compiling.
@@ -40,7 +45,7 @@ pub(crate) fn compile(compiler: &Compiler) -> Result<Compilation> {
         })
         .collect();
     let chars: Vec<_> = chars.iter().map(|c| c.as_slice()).collect();
-    let initial = CompilationIntermediate::from_job(compiler, chars);
+    let initial = CompilationIntermediate::from_job(compiler, files, declaration_files, chars);
     let intermediate = compiler_steps.into_iter().fold(initial, |state, step| {
         if state.early_break {
             state
@@ -59,6 +64,10 @@ type CompilationStep = dyn Fn(CompilationIntermediate) -> CompilationIntermediat
 
 pub(crate) struct CompilationIntermediate<'input> {
     pub(crate) job: &'input Compiler,
+    /// The files of the job that contain nodes
+    pub(crate) files: Vec<&'input File>,
+    /// The files of the job that only contain declarations, see [`SignatureManifest::from_declaration_file`]
+    pub(crate) declaration_files: Vec<&'input File>,
     pub(crate) file_chars: Vec<&'input [u32]>,
     pub(crate) result: Option<Result<Compilation>>,
     /// All variable declarations that we've encountered, PLUS the ones we knew about before
@@ -71,13 +80,22 @@ pub(crate) struct CompilationIntermediate<'input> {
     pub(crate) string_table: StringTableManager,
     pub(crate) diagnostics: Vec<Diagnostic>,
     pub(crate) file_tags: HashMap<String, Vec<String>>,
+    /// The overloads that function calls were resolved to, by file name
+    pub(crate) resolved_overloads: HashMap<String, ResolvedOverloads>,
     pub(crate) early_break: bool,
 }
 
 impl<'input> CompilationIntermediate<'input> {
-    pub(crate) fn from_job(compiler: &'input Compiler, chars: Vec<&'input [u32]>) -> Self {
+    pub(crate) fn from_job(
+        compiler: &'input Compiler,
+        files: Vec<&'input File>,
+        declaration_files: Vec<&'input File>,
+        chars: Vec<&'input [u32]>,
+    ) -> Self {
         Self {
             job: compiler,
+            files,
+            declaration_files,
             file_chars: chars,
             result: Default::default(),
             known_variable_declarations: Default::default(),
@@ -88,6 +106,7 @@ impl<'input> CompilationIntermediate<'input> {
             string_table: Default::default(),
             diagnostics: Default::default(),
             file_tags: Default::default(),
+            resolved_overloads: Default::default(),
             early_break: Default::default(),
         }
     }
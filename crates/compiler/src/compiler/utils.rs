@@ -8,8 +8,9 @@ use crate::prelude::generated::{yarnspinnerlexer, yarnspinnerparser};
 use crate::prelude::*;
 use antlr_rust::common_token_stream::CommonTokenStream;
 use antlr_rust::input_stream::CodePoint32BitCharStream;
-use antlr_rust::token::{Token, TOKEN_DEFAULT_CHANNEL};
-use antlr_rust::Parser;
+use antlr_rust::token::{Token, TOKEN_DEFAULT_CHANNEL, TOKEN_EOF};
+use antlr_rust::{Parser, TokenSource};
+use std::borrow::Cow;
 use std::collections::HashSet;
 use std::rc::Rc;
 use yarnspinner_core::prelude::*;
@@ -21,12 +22,11 @@ pub(crate) fn get_line_id_tag<'a>(
     hashtag_contexts
         .iter()
         .find(|hashtag| {
-            let hashtag_text = hashtag
+            // A lone `#` has no text. The parser reports it as an error.
+            hashtag
                 .text
                 .as_ref()
-                .expect("Hashtag held no text")
-                .get_text();
-            hashtag_text.starts_with("line:")
+                .is_some_and(|text| text.get_text().starts_with("line:"))
         })
         .cloned()
 }
@@ -36,6 +36,14 @@ pub(crate) fn parse_syntax_tree<'a, 'b: 'a>(
     file_chars: &'a [u32],
     diagnostics: &mut Vec<Diagnostic>,
 ) -> FileParseResult<'a> {
+    // antlr-rust indexes out of bounds when the parser gives up on the root rule without having consumed a token
+    // after skipping hidden ones, i.e. for a file that contains nothing but whitespace and comments.
+    // Such a file has no dialogue in it anyway, so parse it as an empty file, which reports the same error.
+    let file_chars = if contains_only_hidden_tokens(file, file_chars) {
+        &[]
+    } else {
+        file_chars
+    };
     // Using 32 bit codepoints because that's how big a Rust `char` is: 4 bytes.
     let input = CodePoint32BitCharStream::new(file_chars);
     let mut lexer = YarnSpinnerLexer::new(input, file.file_name.clone());
@@ -73,6 +81,23 @@ pub(crate) fn parse_syntax_tree<'a, 'b: 'a>(
     FileParseResult::new(file_name, tree, Rc::new(parser))
 }
 
+fn contains_only_hidden_tokens(file: &File, file_chars: &[u32]) -> bool {
+    let input = CodePoint32BitCharStream::new(file_chars);
+    let mut lexer = YarnSpinnerLexer::new(input, file.file_name.clone());
+    lexer.remove_error_listeners();
+    let mut found_hidden_token = false;
+    loop {
+        let token = lexer.next_token();
+        if token.get_token_type() == TOKEN_EOF {
+            return found_hidden_token;
+        }
+        if token.get_channel() == TOKEN_DEFAULT_CHANNEL {
+            return false;
+        }
+        found_hidden_token = true;
+    }
+}
+
 pub(crate) fn get_line_id_for_node_name(name: &str) -> LineId {
     format!("line:{name}").into()
 }
@@ -194,10 +219,14 @@ where
 ///
 /// ## Implementation note
 ///
-/// In contrast to the original implementation, we don't return any diagnostics
-/// because Rust's type system already guarantees at compile-time that all registered
-/// functions are valid and compatible with Yarn.
-pub(crate) fn get_declarations_from_library(library: &Library) -> Vec<Declaration> {
+/// Rust's type system already guarantees at compile-time that registered functions only use types
+/// that can be passed to and from Yarn. The exception are references to [`YarnObjectType`]s, which
+/// only have a Yarn type if the function was registered through a [`CustomType`]. Other functions using them
+/// are reported in `diagnostics` and left out.
+pub(crate) fn get_declarations_from_library(
+    library: &Library,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Vec<Declaration> {
     let operators: HashSet<_> = Type::EXPLICITLY_CONSTRUCTABLE
         .iter()
         .flat_map(|r#type| {
@@ -212,18 +241,23 @@ pub(crate) fn get_declarations_from_library(library: &Library) -> Vec<Declaratio
         .iter()
         // Operators are type checked by visitors instead
         .filter(|(name, _function)| !operators.contains(*name))
-        .map(|(name, function)| {
-            let mut function_type = FunctionType::default();
-            let parameters = function
-                .parameter_types()
-                .into_iter()
-                .map(|t| Type::try_from(t).unwrap())
-                .map(Some)
-                .collect();
-            function_type.parameters = parameters;
-            let return_type = Type::try_from(function.return_type()).unwrap();
-            function_type.set_return_type(return_type);
-            Declaration::new(name, function_type).with_source_file_name(DeclarationSource::External)
+        .filter_map(|(name, function)| {
+            let function_type = match FunctionType::try_from(function) {
+                Ok(function_type) => function_type,
+                Err(error) => {
+                    diagnostics.push(Diagnostic::from_message(format!(
+                        "Function {name} has a parameter or return type that Yarn does not know ({error}). \
+                        Functions taking custom objects must be registered as members of a `CustomType` with `Library::add_custom_type`"
+                    )));
+                    return None;
+                }
+            };
+            let metadata = function.metadata().cloned().unwrap_or_default();
+            let declaration = Declaration::new(name, function_type)
+                .with_source_file_name(DeclarationSource::External)
+                .with_description_optional(metadata.docs.map(Cow::into_owned))
+                .with_deprecation_optional(metadata.deprecation.map(Cow::into_owned));
+            Some(declaration)
         })
         .collect()
 }
@@ -232,6 +266,59 @@ pub(crate) fn get_declarations_from_library(library: &Library) -> Vec<Declaratio
 mod tests {
     use super::*;
 
+    #[test]
+    fn declarations_from_library_carry_function_metadata() {
+        let mut library = Library::new();
+        library.add_function_with_metadata(
+            "old_double",
+            |value: f32| value * 2.0,
+            YarnFnMetadata::new()
+                .with_docs("Doubles the value")
+                .with_deprecation("Use `value * 2` instead"),
+        );
+        let declarations = get_declarations_from_library(&library, &mut Vec::new());
+        let declaration = declarations
+            .iter()
+            .find(|declaration| declaration.name == "old_double")
+            .unwrap();
+        assert_eq!(
+            Some("Doubles the value"),
+            declaration.description.as_deref()
+        );
+        assert_eq!(
+            Some("Use `value * 2` instead"),
+            declaration.deprecation.as_deref()
+        );
+    }
+
+    #[test]
+    fn reports_functions_with_unknown_types() {
+        struct Player;
+
+        impl YarnObjectType for Player {
+            const NAME: &'static str = "Player";
+        }
+
+        let mut library = Library::new();
+        library
+            .add_function("greet", |_player: &Player| "Hi".to_owned())
+            .add_function("double", |value: f32| value * 2.0);
+        let mut diagnostics = Vec::new();
+        let declarations = get_declarations_from_library(&library, &mut diagnostics);
+
+        assert_eq!(
+            vec!["double"],
+            declarations
+                .iter()
+                .map(|declaration| declaration.name.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(1, diagnostics.len());
+        assert!(diagnostics[0]
+            .message
+            .starts_with("Function greet has a parameter or return type that Yarn does not know"));
+    }
+
     #[test]
     fn warns_about_mixed_indentation() {
         let mut diagnostics = Vec::new();
@@ -0,0 +1,272 @@
+//! Not part of the original implementation. Declaration files are Yarn files without any nodes that only declare the
+//! functions and commands a game provides, so that writers get their scripts type checked without compiling the game.
+
+use crate::prelude::*;
+use crate::visitors::keyword_to_type;
+use yarnspinner_core::types::{FunctionType, Type};
+
+impl SignatureManifest {
+    /// Parses a declaration file, which declares the functions and commands a game makes available to Yarn without defining any nodes.
+    ///
+    /// Declaration files added to a [`Compiler`] like any other Yarn file are detected automatically and used only for
+    /// type checking, so they can live next to the scripts they describe. Every statement is on its own line:
+    /// - `<<declare function name(type, ...) -> type>>` declares a function. Parameters may be named, like `amount: number`,
+    ///   and the last one may be variadic, like `...number`.
+    /// - `<<declare command name(type, ...)>>` declares a command. The parameter list is optional and only documents the command,
+    ///   as the compiler does not check commands.
+    ///
+    /// The types are `number`, `string`, `bool`, `list` and `any`. Lines starting with `///` document the declaration that follows them,
+    /// other lines starting with `//` are comments.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use yarnspinner_compiler::prelude::*;
+    /// let manifest = SignatureManifest::from_declaration_file(&File {
+    ///     file_name: "engine.yarn".to_owned(),
+    ///     source: "/// Gives the player gold\n<<declare function add_gold(amount: number) -> number>>\n<<declare command shake_camera(number)>>\n"
+    ///         .to_owned(),
+    /// })
+    /// .unwrap();
+    /// assert_eq!(manifest.functions[0].name, "add_gold");
+    /// assert_eq!(manifest.functions[0].docs.as_deref(), Some("Gives the player gold"));
+    /// assert_eq!(manifest.commands, vec!["shake_camera".to_owned()]);
+    /// ```
+    pub fn from_declaration_file(file: &File) -> crate::Result<Self> {
+        let mut manifest = SignatureManifest::new();
+        let mut diagnostics = Vec::new();
+        let mut docs: Vec<&str> = Vec::new();
+        for (line_index, line) in strip_bom(&file.source).lines().enumerate() {
+            let trimmed = line.trim();
+            if let Some(doc) = trimmed.strip_prefix("///") {
+                docs.push(doc.trim());
+                continue;
+            }
+            if trimmed.is_empty() || trimmed.starts_with("//") {
+                continue;
+            }
+            let docs = (!docs.is_empty()).then(|| std::mem::take(&mut docs).join("\n"));
+            let result = match split_declaration(trimmed) {
+                Some(("function", signature)) => {
+                    parse_function(signature).map(|(name, function_type)| {
+                        manifest.functions.push(FunctionSignature {
+                            name,
+                            function_type,
+                            docs,
+                            deprecation: None,
+                        });
+                    })
+                }
+                Some(("command", signature)) => parse_command(signature).map(|name| {
+                    manifest.commands.push(name);
+                }),
+                _ => Err(format!(
+                    "Expected a function or command declaration like `<<declare function name(number) -> bool>>` in declaration file, but found `{trimmed}`"
+                )),
+            };
+            if let Err(message) = result {
+                let line_length = line.chars().count();
+                diagnostics.push(
+                    Diagnostic::from_message(message)
+                        .with_file_name(file.file_name.clone())
+                        .with_range(
+                            Position {
+                                line: line_index,
+                                character: 0,
+                            }..Position {
+                                line: line_index,
+                                character: line_length,
+                            },
+                        )
+                        .with_context(line)
+                        .with_start_line(line_index),
+                );
+            }
+        }
+        if !diagnostics.is_empty() {
+            return Err(CompilerError(diagnostics));
+        }
+        manifest
+            .functions
+            .sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+        manifest.commands.sort();
+        manifest.commands.dedup();
+        Ok(manifest)
+    }
+}
+
+/// Returns whether the source is that of a declaration file, i.e. contains no nodes but at least one `<<declare ...>>` statement.
+/// Whether the statements are valid is checked by [`SignatureManifest::from_declaration_file`].
+pub(crate) fn is_declaration_file(source: &str) -> bool {
+    let mut statements = strip_bom(source)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .peekable();
+    statements.peek().is_some() && statements.all(|line| split_declaration(line).is_some())
+}
+
+fn strip_bom(source: &str) -> &str {
+    source.strip_prefix('\u{feff}').unwrap_or(source)
+}
+
+/// Splits `<<declare function foo() -> bool>>` into `("function", "foo() -> bool")`.
+fn split_declaration(line: &str) -> Option<(&str, &str)> {
+    let statement = line.strip_prefix("<<")?.strip_suffix(">>")?.trim();
+    let rest = statement.strip_prefix("declare")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let rest = rest.trim_start();
+    let keyword_end = rest
+        .find(|c: char| !c.is_alphabetic())
+        .unwrap_or(rest.len());
+    let (keyword, signature) = rest.split_at(keyword_end);
+    Some((keyword, signature.trim()))
+}
+
+fn parse_function(signature: &str) -> Result<(String, FunctionType), String> {
+    let Some((head, return_type)) = signature.rsplit_once("->") else {
+        return Err(format!(
+            "Function declaration `{signature}` is missing its return type, e.g. `-> number`"
+        ));
+    };
+    let (name, parameters) = parse_name_and_parameters(head.trim(), true)?;
+    let mut function_type = FunctionType::default();
+    let parameter_count = parameters.len();
+    for (index, parameter) in parameters.into_iter().enumerate() {
+        if let Some(variadic_type) = parameter.strip_prefix("...") {
+            if index + 1 != parameter_count {
+                return Err(format!(
+                    "Only the last parameter of function {name} can be variadic"
+                ));
+            }
+            function_type.set_variadic_parameter_type(parse_type(variadic_type.trim())?);
+        } else {
+            function_type.add_parameter(parse_type(parameter)?);
+        }
+    }
+    function_type.set_return_type(parse_type(return_type.trim())?);
+    Ok((name, function_type))
+}
+
+fn parse_command(signature: &str) -> Result<String, String> {
+    let (name, parameters) = parse_name_and_parameters(signature, false)?;
+    for parameter in parameters {
+        parse_type(parameter.trim_start_matches("...").trim())?;
+    }
+    Ok(name)
+}
+
+/// Parses `name(a: number, string)` into the name and the types of the parameters, dropping parameter names.
+fn parse_name_and_parameters(
+    signature: &str,
+    requires_parameter_list: bool,
+) -> Result<(String, Vec<&str>), String> {
+    let (name, parameters) = match signature.split_once('(') {
+        Some((name, rest)) => {
+            let Some(parameters) = rest.trim_end().strip_suffix(')') else {
+                return Err(format!(
+                    "Expected `)` at the end of the parameters of `{signature}`"
+                ));
+            };
+            (name.trim(), Some(parameters))
+        }
+        None => (signature.trim(), None),
+    };
+    let is_valid_name = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '.');
+    if !is_valid_name {
+        return Err(format!("`{name}` is not a valid name"));
+    }
+    let Some(parameters) = parameters else {
+        if requires_parameter_list {
+            return Err(format!(
+                "Function declaration `{signature}` is missing its parameter list, e.g. `()`"
+            ));
+        }
+        return Ok((name.to_owned(), Vec::new()));
+    };
+    let parameters = parameters
+        .split(',')
+        .map(str::trim)
+        .filter(|parameter| !parameter.is_empty())
+        .map(|parameter| match parameter.split_once(':') {
+            Some((_name, r#type)) => r#type.trim(),
+            None => parameter,
+        })
+        .collect();
+    Ok((name.to_owned(), parameters))
+}
+
+fn parse_type(name: &str) -> Result<Type, String> {
+    match name {
+        "any" => Ok(Type::Any),
+        _ => keyword_to_type(name).ok_or_else(|| {
+            format!("Unknown type `{name}`, expected one of number, string, bool, list or any")
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_functions_and_commands() {
+        let file = File {
+            file_name: "engine.yarn".to_owned(),
+            source: "// Provided by the engine\n\
+                     <<declare function sum(first: number, ...number) -> number>>\n\
+                     <<declare function is_night() -> bool>>\n\
+                     <<declare command wait>>\n"
+                .to_owned(),
+        };
+        let manifest = SignatureManifest::from_declaration_file(&file).unwrap();
+
+        let names: Vec<_> = manifest.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["is_night", "sum"]);
+        let sum = &manifest.functions[1].function_type;
+        assert_eq!(sum.parameters, vec![Some(Type::Number)]);
+        assert_eq!(*sum.variadic_parameter_type, Some(Type::Number));
+        assert_eq!(*sum.return_type, Some(Type::Number));
+        assert_eq!(manifest.commands, vec!["wait".to_owned()]);
+    }
+
+    #[test]
+    fn reports_invalid_declarations_with_their_line() {
+        let file = File {
+            file_name: "engine.yarn".to_owned(),
+            source: "<<declare function broken(number)>>\n<<declare command fine>>\n<<declare function typo(nubmer) -> bool>>\n"
+                .to_owned(),
+        };
+        let error = SignatureManifest::from_declaration_file(&file).unwrap_err();
+
+        let lines: Vec<_> = error
+            .0
+            .iter()
+            .map(|diagnostic| diagnostic.range.as_ref().unwrap().start.line)
+            .collect();
+        assert_eq!(lines, vec![0, 2]);
+    }
+
+    #[test]
+    fn only_recognizes_files_without_nodes_as_declaration_files() {
+        assert!(is_declaration_file(
+            "<<declare function f() -> bool>>\n\n// comment\n<<declare command c>>"
+        ));
+        assert!(!is_declaration_file(""));
+        assert!(!is_declaration_file(
+            "title: Start\n---\n<<declare $gold = 0>>\n==="
+        ));
+        assert!(!is_declaration_file(
+            "<<declare function f() -> bool>>\ntitle: Start\n---\nHi\n==="
+        ));
+        assert!(is_declaration_file("<<declare functoin f() -> bool>>"));
+    }
+}
@@ -0,0 +1,320 @@
+//! Not part of the original Yarn Spinner. [`Arbitrary`] implementations that turn fuzzer input into Yarn-like source code,
+//! so that fuzzing reaches past the lexer into the parser, type checker and code generation.
+
+use crate::prelude::*;
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+/// The source code of a Yarn file made from arbitrary data.
+///
+/// The source is built from nodes, headers, lines, options, commands and expressions, most of them well-formed.
+/// Now and then a piece is left out, replaced by a random token or cut off, like in a file that is still being written.
+///
+/// ## Implementation notes
+///
+/// Not part of the original Yarn Spinner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct YarnSource(pub String);
+
+impl<'a> Arbitrary<'a> for YarnSource {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut writer = SourceWriter {
+            u,
+            source: String::new(),
+        };
+        writer.file()?;
+        let mut source = writer.source;
+        if u.ratio(1, 4)? {
+            let cut = u.choose_index(source.len() + 1)?;
+            let cut = (0..=cut)
+                .rev()
+                .find(|&index| source.is_char_boundary(index))
+                .unwrap_or_default();
+            source.truncate(cut);
+        }
+        Ok(Self(source))
+    }
+}
+
+impl<'a> Arbitrary<'a> for File {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let file_name = format!("{}.yarn", u.choose(IDENTIFIERS)?);
+        let YarnSource(source) = u.arbitrary()?;
+        Ok(Self { file_name, source })
+    }
+}
+
+const IDENTIFIERS: &[&str] = &["Start", "Node", "Other", "a", "b_2", "Ünïcödé"];
+const VARIABLES: &[&str] = &["$x", "$gold", "$name", "$flag", "$undeclared"];
+const FUNCTIONS: &[&str] = &[
+    "visited",
+    "visited_count",
+    "random",
+    "random_range",
+    "dice",
+    "round",
+    "string",
+    "number",
+    "bool",
+    "unknown_function",
+];
+const BINARY_OPERATORS: &[&str] = &[
+    "+", "-", "*", "/", "%", "==", "!=", "<", "<=", ">", ">=", "and", "or", "xor", "is", "eq",
+    "&&", "||", "^",
+];
+const TEXT: &[&str] = &[
+    "Hello",
+    "there",
+    "it's",
+    "me",
+    "!",
+    "...",
+    "[b]bold[/b]",
+    "[wave]",
+    "[/wave]",
+    "\\{",
+    "\\#",
+    "[select value={$x} a=1 other=2 /]",
+    "[plural value={$gold} one=coin other=coins /]",
+    "ü",
+];
+/// Pieces of syntax that are likely to be misplaced when inserted somewhere random.
+const TOKENS: &[&str] = &[
+    "<<", ">>", "{", "}", "[", "]", "(", ")", "->", "#", "#line:", "===", "---", ":", "$", "\"",
+    ",", "//", "\n", "    ", "\t", "to", "=", "if", "endif", "else", "elseif", "set", "declare",
+    "as", "jump", "call", "enum", "case", "once", "when", "\u{feff}",
+];
+
+struct SourceWriter<'a, 'b> {
+    u: &'b mut Unstructured<'a>,
+    source: String,
+}
+
+impl SourceWriter<'_, '_> {
+    fn file(&mut self) -> Result<()> {
+        if self.u.ratio(1, 4)? {
+            self.source.push_str("# file_tag\n");
+        }
+        let node_count = self.u.int_in_range(0..=3)?;
+        for _ in 0..node_count {
+            self.node()?;
+        }
+        Ok(())
+    }
+
+    fn node(&mut self) -> Result<()> {
+        let title = self.u.choose(IDENTIFIERS)?;
+        self.source.push_str(&format!("title: {title}\n"));
+        if self.u.ratio(1, 3)? {
+            let tags = self.u.choose(&["rawText", "a b", ""])?;
+            self.source.push_str(&format!("tags: {tags}\n"));
+        }
+        if self.u.ratio(1, 5)? {
+            let value = self.u.choose(&["", "always", "never", "x: y"])?;
+            self.source.push_str(&format!("tracking: {value}\n"));
+        }
+        self.source.push_str("---\n");
+        self.statements(0)?;
+        if self.u.ratio(9, 10)? {
+            self.source.push_str("===\n");
+        }
+        Ok(())
+    }
+
+    fn statements(&mut self, depth: usize) -> Result<()> {
+        let count = self.u.int_in_range(0..=6)?;
+        for _ in 0..count {
+            self.statement(depth)?;
+        }
+        Ok(())
+    }
+
+    fn statement(&mut self, depth: usize) -> Result<()> {
+        let indent = "    ".repeat(depth);
+        self.source.push_str(&indent);
+        // Don't nest any deeper once the indentation gets silly
+        let max_kind = if depth < 3 { 12 } else { 8 };
+        match self.u.int_in_range(0..=max_kind)? {
+            0 | 1 => self.line()?,
+            2 => {
+                let variable = self.u.choose(VARIABLES)?;
+                let operator = self.u.choose(&["to", "=", "+=", "-=", "*=", "/=", "%="])?;
+                self.source
+                    .push_str(&format!("<<set {variable} {operator} "));
+                self.expression(0)?;
+                self.source.push_str(">>");
+            }
+            3 => {
+                let variable = self.u.choose(VARIABLES)?;
+                self.source.push_str(&format!("<<declare {variable} = "));
+                self.expression(0)?;
+                if self.u.ratio(1, 3)? {
+                    let r#type = self.u.choose(&["number", "string", "bool", "any"])?;
+                    self.source.push_str(&format!(" as {type}"));
+                }
+                self.source.push_str(">>");
+            }
+            4 => {
+                let node = self.u.choose(IDENTIFIERS)?;
+                if self.u.ratio(1, 4)? {
+                    self.source.push_str("<<jump {");
+                    self.expression(0)?;
+                    self.source.push_str("}>>");
+                } else {
+                    self.source.push_str(&format!("<<jump {node}>>"));
+                }
+            }
+            5 => {
+                let command = self
+                    .u
+                    .choose(&["wait 1", "stop", "fade_in", "give \"sword\""])?;
+                self.source.push_str(&format!("<<{command}"));
+                if self.u.ratio(1, 3)? {
+                    self.source.push_str(" {");
+                    self.expression(0)?;
+                    self.source.push('}');
+                }
+                self.source.push_str(">>");
+            }
+            6 => {
+                let comment = self.u.choose(&["// comment", "", "    "])?;
+                self.source.push_str(comment);
+            }
+            7 => {
+                let count = self.u.int_in_range(1..=4)?;
+                for _ in 0..count {
+                    let token = self.u.choose(TOKENS)?;
+                    self.source.push_str(token);
+                }
+            }
+            8 => {
+                let text: String = self.u.arbitrary()?;
+                self.source.push_str(&text);
+            }
+            9 | 10 => {
+                let count = self.u.int_in_range(1..=3)?;
+                for index in 0..count {
+                    if index > 0 {
+                        self.source.push_str(&indent);
+                    }
+                    self.source.push_str("-> ");
+                    self.line()?;
+                    self.source.push('\n');
+                    self.statements(depth + 1)?;
+                }
+                return Ok(());
+            }
+            _ => {
+                self.source.push_str("<<if ");
+                self.expression(0)?;
+                self.source.push_str(">>\n");
+                self.statements(depth + 1)?;
+                if self.u.ratio(1, 3)? {
+                    self.source.push_str(&format!("{indent}<<elseif "));
+                    self.expression(0)?;
+                    self.source.push_str(">>\n");
+                    self.statements(depth + 1)?;
+                }
+                if self.u.ratio(1, 2)? {
+                    self.source.push_str(&format!("{indent}<<else>>\n"));
+                    self.statements(depth + 1)?;
+                }
+                if self.u.ratio(9, 10)? {
+                    self.source.push_str(&format!("{indent}<<endif>>"));
+                }
+            }
+        }
+        self.source.push('\n');
+        Ok(())
+    }
+
+    /// A line of text, which may also be the text of an option.
+    fn line(&mut self) -> Result<()> {
+        if self.u.ratio(1, 3)? {
+            let character = self.u.choose(IDENTIFIERS)?;
+            self.source.push_str(&format!("{character}: "));
+        }
+        let word_count = self.u.int_in_range(1..=5)?;
+        for index in 0..word_count {
+            if index > 0 {
+                self.source.push(' ');
+            }
+            if self.u.ratio(1, 5)? {
+                self.source.push('{');
+                self.expression(0)?;
+                self.source.push('}');
+            } else {
+                let text = self.u.choose(TEXT)?;
+                self.source.push_str(text);
+            }
+        }
+        if self.u.ratio(1, 4)? {
+            self.source.push_str(" <<if ");
+            self.expression(0)?;
+            self.source.push_str(">>");
+        }
+        if self.u.ratio(1, 3)? {
+            let tag = self
+                .u
+                .choose(&["#line:abc", "#line:abc2", "#tag", "#", "#lastline"])?;
+            self.source.push_str(&format!(" {tag}"));
+        }
+        Ok(())
+    }
+
+    fn expression(&mut self, depth: usize) -> Result<()> {
+        let max_kind = if depth < 4 { 9 } else { 4 };
+        match self.u.int_in_range(0..=max_kind)? {
+            0 => {
+                let number: f32 = self.u.arbitrary()?;
+                self.source.push_str(&number.abs().to_string());
+            }
+            1 => {
+                let string = self
+                    .u
+                    .choose(&["\"text\"", "\"\"", "\"Start\"", "\"\\\"\""])?;
+                self.source.push_str(string);
+            }
+            2 => {
+                let boolean = self.u.choose(&["true", "false", "null"])?;
+                self.source.push_str(boolean);
+            }
+            3 => {
+                let variable = self.u.choose(VARIABLES)?;
+                self.source.push_str(variable);
+            }
+            // Left out, e.g. `<<set $x to>>`
+            4 => {}
+            5 => {
+                let operator = self.u.choose(&["-", "!", "not "])?;
+                self.source.push_str(operator);
+                self.expression(depth + 1)?;
+            }
+            6 => {
+                self.source.push('(');
+                self.expression(depth + 1)?;
+                if self.u.ratio(9, 10)? {
+                    self.source.push(')');
+                }
+            }
+            7 => {
+                let function = self.u.choose(FUNCTIONS)?;
+                self.source.push_str(&format!("{function}("));
+                let argument_count = self.u.int_in_range(0..=3)?;
+                for index in 0..argument_count {
+                    if index > 0 {
+                        self.source.push_str(", ");
+                    }
+                    self.expression(depth + 1)?;
+                }
+                self.source.push(')');
+            }
+            _ => {
+                self.expression(depth + 1)?;
+                let operator = self.u.choose(BINARY_OPERATORS)?;
+                self.source.push_str(&format!(" {operator} "));
+                self.expression(depth + 1)?;
+            }
+        }
+        Ok(())
+    }
+}
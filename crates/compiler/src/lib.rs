@@ -11,12 +11,16 @@
 mod collections;
 pub(crate) mod compilation_steps;
 pub(crate) mod compiler;
+mod declaration_file;
 pub(crate) mod error_strategy;
 mod file_parse_result;
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
 pub(crate) mod listeners;
 mod output;
 mod parser;
 pub(crate) mod parser_rule_context_ext;
+mod signature_manifest;
 mod string_table_manager;
 pub(crate) mod token_ext;
 pub(crate) mod visitors;
@@ -25,15 +29,21 @@ pub use crate::compiler::Result;
 
 pub mod prelude {
     //! Everything you need to get started with the Yarn Spinner compiler.
+    #[cfg(feature = "arbitrary")]
+    pub use crate::fuzzing::*;
     pub(crate) use crate::{
         compiler::antlr_rust_ext::*, compiler::run_compilation::*, compiler::utils::*,
-        file_parse_result::*, parser::*, parser_rule_context_ext::*, string_table_manager::*,
-        token_ext::*,
+        declaration_file::*, file_parse_result::*, parser::*, parser_rule_context_ext::*,
+        string_table_manager::*, token_ext::*,
     };
     pub use crate::{
-        compiler::{CompilationType, Compiler, File},
+        compiler::{
+            CompilationType, Compiler, File, ReferenceKind, Symbol, SymbolReference, TextEdit,
+            DEFAULT_LAST_LINE_TAG,
+        },
         listeners::{Diagnostic, DiagnosticSeverity, DiagnosticVec},
         output::*,
+        signature_manifest::*,
     };
     pub(crate) use yarnspinner_core::prelude::*;
 }
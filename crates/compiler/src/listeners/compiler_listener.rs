@@ -5,7 +5,7 @@ use antlr_rust::parser_rule_context::ParserRuleContext;
 use antlr_rust::token::Token;
 use antlr_rust::tree::{ParseTreeListener, ParseTreeVisitorCompat};
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 use yarnspinner_core::prelude::*;
 
@@ -15,7 +15,7 @@ use crate::parser::generated::yarnspinnerparser::{
 };
 use crate::prelude::generated::yarnspinnerparser::BodyContextAttrs;
 use crate::prelude::generated::yarnspinnerparserlistener::YarnSpinnerParserListener;
-use crate::visitors::{CodeGenerationVisitor, KnownTypes};
+use crate::visitors::{CodeGenerationVisitor, KnownTypes, ResolvedOverloads};
 pub(crate) use emit::*;
 use yarnspinner_core::prelude::OpCode;
 
@@ -27,6 +27,10 @@ pub(crate) struct CompilerListener<'input> {
     pub(crate) tracking_nodes: Rc<RefCell<HashSet<String>>>,
     pub(crate) diagnostics: Rc<RefCell<Vec<Diagnostic>>>,
     pub(crate) types: KnownTypes,
+    /// The overloads that function calls were resolved to by the type checker.
+    pub(crate) resolved_overloads: ResolvedOverloads,
+    /// The constants defined with [`Compiler::define_constant`], which are replaced by their values.
+    pub(crate) constants: HashMap<String, YarnValue>,
     /// The current node to which instructions are being added.
     pub(crate) current_node: Option<Node>,
     /// The current debug information that describes [`current_node`].
@@ -42,11 +46,14 @@ impl<'input> CompilerListener<'input> {
     pub(crate) fn new(
         tracking_nodes: HashSet<String>,
         types: KnownTypes,
+        resolved_overloads: ResolvedOverloads,
         file: FileParseResult<'input>,
     ) -> Self {
         Self {
             file,
             types,
+            resolved_overloads,
+            constants: Default::default(),
             tracking_nodes: Rc::new(RefCell::new(tracking_nodes)),
             current_node: Default::default(),
             current_debug_info: Default::default(),
@@ -58,6 +65,11 @@ impl<'input> CompilerListener<'input> {
         }
     }
 
+    pub(crate) fn with_constants(mut self, constants: HashMap<String, YarnValue>) -> Self {
+        self.constants = constants;
+        self
+    }
+
     /// Generates a unique label name to use in the program.
     ///
     /// ## Params
@@ -1,14 +1,14 @@
 //! Adapted from <https://github.com/YarnSpinnerTool/YarnSpinner/blob/da39c7195107d8211f21c263e4084f773b84eaff/YarnSpinner.Compiler/CompilationResult.cs>
 
 use crate::listeners::*;
-pub use crate::output::{debug_info::*, declaration::*, string_info::*};
+pub use crate::output::{declaration::*, string_info::*};
 use crate::prelude::*;
 use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::{Debug, Display};
 use yarnspinner_core::prelude::*;
+pub use yarnspinner_core::prelude::{DebugInfo, LineInfo};
 
-mod debug_info;
 mod declaration;
 mod string_info;
 
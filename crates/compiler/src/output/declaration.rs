@@ -60,6 +60,10 @@ pub struct Declaration {
     /// in a variant of [`Type`].
     pub r#type: Type,
 
+    /// If set, this declaration is a deprecated function and calling it makes the compiler emit a warning with this message.
+    /// Taken from [`YarnFnMetadata::deprecation`](yarnspinner_core::prelude::YarnFnMetadata::deprecation).
+    pub deprecation: Option<String>,
+
     /// The range of text at which this declaration occurs.
     ///
     /// This range refers to the declaration of the symbol itself, and
@@ -89,6 +93,7 @@ impl Declaration {
             source_file_name: Default::default(),
             source_node_name: Default::default(),
             is_implicit: Default::default(),
+            deprecation: Default::default(),
             range: Default::default(),
         }
     }
@@ -150,6 +155,12 @@ impl Declaration {
         self
     }
 
+    #[doc(hidden)]
+    pub fn with_deprecation_optional(mut self, deprecation: impl Into<Option<String>>) -> Self {
+        self.deprecation = deprecation.into();
+        self
+    }
+
     #[doc(hidden)]
     pub fn with_range(mut self, range: impl Into<Range<Position>>) -> Self {
         self.range = Some(range.into());
@@ -157,12 +168,13 @@ impl Declaration {
     }
 
     #[doc(hidden)]
-    pub fn eq(&self, other: &Self, epsilon: f32) -> bool {
+    pub fn eq(&self, other: &Self, epsilon: f64) -> bool {
         self.name == other.name
             && self.description == other.description
             && self.source_file_name == other.source_file_name
             && self.source_node_name == other.source_node_name
             && self.is_implicit == other.is_implicit
+            && self.deprecation == other.deprecation
             && self.r#type == other.r#type
             && self.range == other.range
             && match (&self.default_value, &other.default_value) {
@@ -2,6 +2,7 @@
 
 #[cfg(any(feature = "bevy", feature = "serde"))]
 use crate::prelude::*;
+use yarnspinner_core::prelude::LineId;
 
 /// Information about a string. Stored inside a string table, which is
 /// produced from the Compiler.
@@ -43,4 +44,9 @@ pub struct StringInfo {
     /// This array will contain any hashtags associated with this
     /// string besides the `#line:` hashtag.
     pub metadata: Vec<String>,
+
+    /// For options, the ID of the line that immediately precedes their option block,
+    /// i.e. the line tagged with `#lastline`. Views can use this to show the line the options respond to.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub parent_line_id: Option<LineId>,
 }
@@ -2288,6 +2288,7 @@ impl<'input, 'a> Listenable<dyn YarnSpinnerParserListener<'input> + 'a>
 impl<'input, 'a> Visitable<dyn YarnSpinnerParserVisitor<'input> + 'a>
     for ExpressionContext<'input>
 {
+    fn accept(&self, _visitor: &mut (dyn YarnSpinnerParserVisitor<'input> + 'a)) {}
 }
 
 impl<'input> CustomRuleContext<'input> for ExpressionContextExt<'input> {
@@ -3765,7 +3766,9 @@ impl<'input> YarnSpinnerParserContext<'input> for ValueContext<'input> {}
 
 impl<'input, 'a> Listenable<dyn YarnSpinnerParserListener<'input> + 'a> for ValueContext<'input> {}
 
-impl<'input, 'a> Visitable<dyn YarnSpinnerParserVisitor<'input> + 'a> for ValueContext<'input> {}
+impl<'input, 'a> Visitable<dyn YarnSpinnerParserVisitor<'input> + 'a> for ValueContext<'input> {
+    fn accept(&self, _visitor: &mut (dyn YarnSpinnerParserVisitor<'input> + 'a)) {}
+}
 
 impl<'input> CustomRuleContext<'input> for ValueContextExt<'input> {
     type TF = LocalTokenFactory<'input>;
@@ -6876,6 +6879,7 @@ impl<'input, 'a> Listenable<dyn YarnSpinnerParserListener<'input> + 'a>
 impl<'input, 'a> Visitable<dyn YarnSpinnerParserVisitor<'input> + 'a>
     for Jump_statementContext<'input>
 {
+    fn accept(&self, _visitor: &mut (dyn YarnSpinnerParserVisitor<'input> + 'a)) {}
 }
 
 impl<'input> CustomRuleContext<'input> for Jump_statementContextExt<'input> {
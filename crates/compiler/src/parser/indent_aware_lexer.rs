@@ -50,6 +50,11 @@ pub(crate) struct IndentAwareYarnSpinnerLexer<
     /// only returns a single [`Token`] at a time, which
     /// means we use this list to buffer it.
     pending_tokens: Queue<TF::Tok>,
+    /// Tokens read from the generated lexer while looking ahead for member access,
+    /// which are processed before reading any new ones.
+    ///
+    /// Not part of the original implementation, which has no member access syntax.
+    lookahead_tokens: Queue<TF::Tok>,
     /// A flag to say the last line observed was a shortcut or not.
     /// Used to determine if tracking indents needs to occur.
     line_contains_shortcut: bool,
@@ -132,6 +137,7 @@ where
             hit_eof: false,
             last_token: Default::default(),
             pending_tokens: Default::default(),
+            lookahead_tokens: Default::default(),
             line_contains_shortcut: false,
             last_indent: Default::default(),
             unbalanced_indents: Default::default(),
@@ -141,7 +147,7 @@ where
     }
 
     fn check_next_token(&mut self) {
-        let current = self.base.next_token();
+        let current = self.next_base_token();
 
         match current.token_type {
             // Insert indents or dedents depending on the next token's
@@ -169,6 +175,7 @@ where
                 // [sic from the original!] TODO: this should be empty by now actually...
                 self.pending_tokens.enqueue(current.clone());
             }
+            yarnspinnerlexer::VAR_ID => self.handle_variable_token(current.clone()),
             _ => self.pending_tokens.enqueue(current.clone()),
         }
 
@@ -176,6 +183,87 @@ where
         self.last_token = Some(current);
     }
 
+    fn next_base_token(
+        &mut self,
+    ) -> Box<antlr_rust::token::GenericToken<std::borrow::Cow<'input, str>>> {
+        self.lookahead_tokens
+            .dequeue()
+            .unwrap_or_else(|| self.base.next_token())
+    }
+
+    /// Puts tokens that were read while looking ahead back in front of the stream, keeping their order.
+    fn push_back_base_tokens(
+        &mut self,
+        tokens: impl DoubleEndedIterator<
+            Item = Box<antlr_rust::token::GenericToken<std::borrow::Cow<'input, str>>>,
+        >,
+    ) {
+        for token in tokens.rev() {
+            self.lookahead_tokens.0.push_front(token);
+        }
+    }
+
+    /// Not part of the original implementation.
+    ///
+    /// The grammar is shared with the original Yarn Spinner and has no member access syntax,
+    /// so member accesses on variables are rewritten to function calls with the variable as the first argument:
+    /// `$player.name` becomes `.name($player)` and `$item.price(2)` becomes `.price($item, 2)`.
+    /// The type checker resolves the leading dot to the member of the variable's [`Type::Custom`].
+    fn handle_variable_token(
+        &mut self,
+        current_token: Box<antlr_rust::token::GenericToken<std::borrow::Cow<'input, str>>>,
+    ) {
+        let dot = self.next_base_token();
+        if dot.token_type != yarnspinnerlexer::DOT {
+            self.pending_tokens.enqueue(current_token);
+            self.push_back_base_tokens([dot].into_iter());
+            return;
+        }
+        let member = self.next_base_token();
+        if member.token_type != yarnspinnerlexer::FUNC_ID {
+            // Not a member access, so the parser reports the unexpected dot
+            self.pending_tokens.enqueue(current_token);
+            self.push_back_base_tokens([dot, member].into_iter());
+            return;
+        }
+
+        let mut function_name = synthesize_token(&current_token, yarnspinnerlexer::FUNC_ID, "");
+        function_name.stop = member.stop;
+        function_name.text = format!(".{}", member.get_text()).into();
+        self.pending_tokens.enqueue(function_name);
+        self.pending_tokens.enqueue(synthesize_token(
+            &current_token,
+            yarnspinnerlexer::LPAREN,
+            "(",
+        ));
+        self.pending_tokens.enqueue(current_token);
+
+        let next = self.next_base_token();
+        if next.token_type != yarnspinnerlexer::LPAREN {
+            // A property, which takes no arguments besides the object
+            self.pending_tokens
+                .enqueue(synthesize_token(&member, yarnspinnerlexer::RPAREN, ")"));
+            self.push_back_base_tokens([next].into_iter());
+            return;
+        }
+
+        // A method call, whose arguments follow the object
+        let mut skipped = Vec::new();
+        let mut argument = self.next_base_token();
+        while argument.channel != TOKEN_DEFAULT_CHANNEL {
+            skipped.push(argument);
+            argument = self.next_base_token();
+        }
+        if argument.token_type == yarnspinnerlexer::RPAREN {
+            self.pending_tokens.0.extend(skipped);
+            self.pending_tokens.enqueue(argument);
+        } else {
+            self.pending_tokens
+                .enqueue(synthesize_token(&next, yarnspinnerlexer::COMMA, ","));
+            self.push_back_base_tokens(skipped.into_iter().chain([argument]));
+        }
+    }
+
     fn handle_newline_token(
         &mut self,
         current_token: Box<antlr_rust::token::GenericToken<std::borrow::Cow<'input, str>>>,
@@ -375,6 +463,19 @@ where
     }
 }
 
+/// Creates a token of the given type and text at the position of the given token.
+fn synthesize_token<'input>(
+    template: &antlr_rust::token::GenericToken<std::borrow::Cow<'input, str>>,
+    token_type: isize,
+    text: &'static str,
+) -> Box<antlr_rust::token::GenericToken<std::borrow::Cow<'input, str>>> {
+    let mut token = Box::new(template.clone());
+    token.token_type = token_type;
+    token.channel = TOKEN_DEFAULT_CHANNEL;
+    token.text = text.into();
+    token
+}
+
 fn get_newline_indentation_range(token: &CommonToken<'_>) -> Range<Position> {
     // +1 compared to similar code because we don't want to start at the newline
     let line = token.get_line_as_usize();
@@ -567,6 +668,62 @@ This is the one and only line
         assert_eq!(expected, symbols);
     }
 
+    #[test]
+    fn rewrites_member_access_to_function_calls() {
+        let input =
+            "title: Start\n---\n{$player.name} {$item.price(2, $player.gold)} {$item.id()}\n===\n";
+
+        let indent_aware_lexer =
+            IndentAwareYarnSpinnerLexer::new(InputStream::new(input), "input.yarn".to_owned());
+        let mut indent_aware_token_stream = CommonTokenStream::new(indent_aware_lexer);
+
+        let mut tokens = vec![indent_aware_token_stream.iter().next().unwrap()];
+        while indent_aware_token_stream.la(1) != TOKEN_EOF {
+            tokens.push(indent_aware_token_stream.iter().next().unwrap());
+        }
+
+        let symbols: Vec<_> = tokens
+            .into_iter()
+            .map(|t| yarnspinnerlexer::_SYMBOLIC_NAMES[t as usize].unwrap())
+            .skip_while(|&symbol| symbol != "EXPRESSION_START")
+            .take_while(|&symbol| symbol != "NEWLINE")
+            .filter(|&symbol| symbol != "TEXT")
+            .collect();
+
+        let expected = vec![
+            // {$player.name}
+            "EXPRESSION_START",
+            "FUNC_ID",
+            "LPAREN",
+            "VAR_ID",
+            "RPAREN",
+            "EXPRESSION_END",
+            // {$item.price(2, $player.gold)}
+            "EXPRESSION_START",
+            "FUNC_ID",
+            "LPAREN",
+            "VAR_ID",
+            "COMMA",
+            "NUMBER",
+            "COMMA",
+            "FUNC_ID",
+            "LPAREN",
+            "VAR_ID",
+            "RPAREN",
+            "RPAREN",
+            "EXPRESSION_END",
+            // {$item.id()}
+            "EXPRESSION_START",
+            "FUNC_ID",
+            "LPAREN",
+            "VAR_ID",
+            "RPAREN",
+            "EXPRESSION_END",
+        ];
+
+        assert_eq!(expected, symbols);
+    }
+
     #[test]
     fn generated_lexer_output_is_same_as_reference() {
         let option_indentation_relevant_input: &str = include_str!("significant_whitespace.yarn");
@@ -44,18 +44,18 @@ pub(crate) trait ParserRuleContextExt<'input>: ParserRuleContext<'input> {
     ) -> LinesAroundResult {
         // This seems expensive, but it's only used for error reporting.
         let whole_file = token_stream.get_all_text();
-        let char_start = self.start().get_start() as usize;
-        let char_stop = self.stop().get_stop() as usize + 1;
-        let byte_start = whole_file
-            .char_indices()
-            .map(|(byte_start, _)| byte_start)
-            .nth(char_start)
-            .unwrap();
-        let byte_stop = whole_file
-            .char_indices()
-            .map(|(byte_start, _)| byte_start)
-            .nth(char_stop)
-            .unwrap();
+        let char_start = self.start().get_start().max(0) as usize;
+        let char_stop = (self.stop().get_stop() + 1).max(0) as usize;
+        // Contexts of incomplete statements can stop at the end of the file or before they start
+        let byte_index = |char_index| {
+            whole_file
+                .char_indices()
+                .map(|(byte_index, _)| byte_index)
+                .nth(char_index)
+                .unwrap_or(whole_file.len())
+        };
+        let byte_start = byte_index(char_start);
+        let byte_stop = byte_index(char_stop).max(byte_start);
         let first_line = self.start().get_line_as_usize().saturating_sub(1);
 
         let head = &whole_file[..byte_start];
@@ -0,0 +1,188 @@
+//! Not part of the original implementation. Allows type checking Yarn scripts on machines that do not have access to the
+//! Rust code defining the functions, such as a build server or a writer's editor.
+
+use crate::prelude::*;
+use yarnspinner_core::prelude::*;
+use yarnspinner_core::types::{FunctionType, Type};
+
+/// The signatures of the functions and the names of the commands a game makes available to Yarn.
+///
+/// Create one from the game's [`Library`] with [`SignatureManifest::from_library`], write it to disk with
+/// [`SignatureManifest::to_json`] and load it on the other side with [`SignatureManifest::from_json`].
+/// Passing it to [`Compiler::import_manifest`] then lets the compiler type check calls to the described functions
+/// just like if the [`Library`] itself was registered.
+///
+/// A [`Compilation`] produced this way is only meant for checking scripts. Its [`Program`] calls functions that
+/// are not part of the compiler's [`Library`], so run it with a dialogue that registers the actual functions.
+///
+/// ## Example
+///
+/// ```
+/// # use yarnspinner_compiler::prelude::*;
+/// # use yarnspinner_core::prelude::*;
+/// let mut library = Library::new();
+/// library.add_function("add_gold", |amount: f32| amount);
+/// let manifest = SignatureManifest::from_library(&library).with_commands(["shake_camera"]);
+///
+/// let compilation = Compiler::new()
+///     .add_file(File {
+///         file_name: "shop.yarn".to_owned(),
+///         source: "title: Start\n---\n{add_gold(10)}\n<<shake_camera>>\n===\n".to_owned(),
+///     })
+///     .import_manifest(manifest)
+///     .compile();
+/// assert!(compilation.is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "bevy", derive(Reflect))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bevy", reflect(Debug, PartialEq, Default))]
+#[cfg_attr(
+    all(feature = "bevy", feature = "serde"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct SignatureManifest {
+    /// The signatures of the available functions.
+    pub functions: Vec<FunctionSignature>,
+
+    /// The names of the available commands.
+    ///
+    /// The compiler does not check commands, so this is only informational for tools that want to validate them.
+    pub commands: Vec<String>,
+}
+
+/// The signature of a single function in a [`SignatureManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "bevy", derive(Reflect))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bevy", reflect(Debug, PartialEq, Default))]
+#[cfg_attr(
+    all(feature = "bevy", feature = "serde"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct FunctionSignature {
+    /// The name under which the function is called from Yarn.
+    pub name: String,
+
+    /// The parameter and return types of the function.
+    #[cfg_attr(feature = "bevy", reflect(ignore))]
+    pub function_type: FunctionType,
+
+    /// The documentation of the function, see [`YarnFnMetadata::docs`].
+    pub docs: Option<String>,
+
+    /// The deprecation message of the function, see [`YarnFnMetadata::deprecation`].
+    pub deprecation: Option<String>,
+}
+
+impl SignatureManifest {
+    /// Creates an empty manifest.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a manifest containing the signatures of all functions in the given [`Library`].
+    /// Operators are left out, as they are checked by the compiler itself.
+    pub fn from_library(library: &Library) -> Self {
+        // Functions the compiler cannot declare cannot be called from Yarn either, so they are left out without a diagnostic.
+        let mut functions: Vec<_> = get_declarations_from_library(library, &mut Vec::new())
+            .into_iter()
+            .filter_map(|declaration| {
+                let Type::Function(function_type) = declaration.r#type else {
+                    return None;
+                };
+                Some(FunctionSignature {
+                    name: declaration.name,
+                    function_type,
+                    docs: declaration.description,
+                    deprecation: declaration.deprecation,
+                })
+            })
+            .collect();
+        // The library is unordered, but the manifest should be stable to be diffable.
+        functions.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+        Self {
+            functions,
+            commands: Vec::new(),
+        }
+    }
+
+    /// Adds the given command names to the manifest.
+    pub fn with_commands(mut self, commands: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.commands.extend(commands.into_iter().map(Into::into));
+        self.commands.sort();
+        self.commands.dedup();
+        self
+    }
+
+    /// Adds all functions and commands of another manifest to this one.
+    pub fn extend(&mut self, other: SignatureManifest) -> &mut Self {
+        self.functions.extend(other.functions);
+        self.functions.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+        self.commands.extend(other.commands);
+        self.commands.sort();
+        self.commands.dedup();
+        self
+    }
+
+    /// Returns the declarations the compiler uses to type check calls to the functions in this manifest.
+    pub fn declarations(&self) -> impl Iterator<Item = Declaration> + '_ {
+        self.functions.iter().map(|function| {
+            Declaration::new(&function.name, function.function_type.clone())
+                .with_source_file_name(DeclarationSource::External)
+                .with_description_optional(function.docs.clone())
+                .with_deprecation_optional(function.deprecation.clone())
+        })
+    }
+
+    /// Serializes the manifest to pretty-printed JSON.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Deserializes a manifest previously written with [`SignatureManifest::to_json`].
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_contains_functions_but_no_operators() {
+        let mut library = Library::standard_library();
+        library.add_function_with_metadata(
+            "add_gold",
+            |amount: f32| amount,
+            YarnFnMetadata::new().with_docs("Gives the player gold"),
+        );
+        let manifest = SignatureManifest::from_library(&library);
+
+        let add_gold = manifest
+            .functions
+            .iter()
+            .find(|function| function.name == "add_gold")
+            .unwrap();
+        assert_eq!(add_gold.docs.as_deref(), Some("Gives the player gold"));
+        assert_eq!(add_gold.function_type.parameters, vec![Some(Type::Number)]);
+        assert!(manifest
+            .functions
+            .iter()
+            .all(|function| !function.name.starts_with("Number.")));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn manifest_survives_json_round_trip() {
+        let mut library = Library::new();
+        library.add_function("greet", |name: &str| format!("Hello, {name}!"));
+        let manifest = SignatureManifest::from_library(&library).with_commands(["wave"]);
+
+        let json = manifest.to_json().unwrap();
+        assert_eq!(SignatureManifest::from_json(&json).unwrap(), manifest);
+    }
+}
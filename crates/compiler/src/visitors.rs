@@ -4,11 +4,12 @@ mod declaration_visitor;
 mod hashable_interval;
 mod last_line_before_options_visitor;
 mod node_tracking_visitor;
+mod reference_visitor;
 mod string_table_generator_visitor;
 mod type_check_visitor;
 
 pub(crate) use self::{
     code_generation_visitor::*, declaration_visitor::*, hashable_interval::*,
-    last_line_before_options_visitor::*, node_tracking_visitor::*,
+    last_line_before_options_visitor::*, node_tracking_visitor::*, reference_visitor::*,
     string_table_generator_visitor::*, type_check_visitor::*,
 };
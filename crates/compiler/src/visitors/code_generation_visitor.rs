@@ -208,7 +208,7 @@ impl<'a, 'input: 'a> YarnSpinnerParserVisitorCompat<'input> for CodeGenerationVi
     }
 
     fn visit_valueNumber(&mut self, ctx: &ValueNumberContext<'input>) -> Self::Return {
-        let number: f32 = ctx.NUMBER().unwrap().get_text().parse().unwrap();
+        let number: f64 = ctx.NUMBER().unwrap().get_text().parse().unwrap();
         self.compiler_listener.emit(
             Emit::from_op_code(OpCode::PushFloat)
                 .with_token(ctx.start().deref())
@@ -264,6 +264,22 @@ impl<'a, 'input: 'a> YarnSpinnerParserVisitorCompat<'input> for CodeGenerationVi
 
     fn visit_variable(&mut self, ctx: &VariableContext<'input>) -> Self::Return {
         let variable_name = ctx.VAR_ID().unwrap().get_text();
+        if let Some(value) = self.compiler_listener.constants.get(&variable_name) {
+            // Constants are substituted by their value
+            let op_code = match value {
+                YarnValue::Number(_) => OpCode::PushFloat,
+                YarnValue::String(_) => OpCode::PushString,
+                YarnValue::Boolean(_) => OpCode::PushBool,
+                _ => unreachable!("Internal error: constant {variable_name} has a type that was rejected when registering it. This is a bug. Please report it at https://github.com/YarnSpinnerTool/YarnSpinner-Rust/issues/new"),
+            };
+            let value = value.clone();
+            self.compiler_listener.emit(
+                Emit::from_op_code(op_code)
+                    .with_token(ctx.start().deref())
+                    .with_operand(value),
+            );
+            return;
+        }
         self.compiler_listener.emit(
             Emit::from_op_code(OpCode::PushVariable)
                 .with_token(ctx.start().deref())
@@ -287,8 +303,13 @@ impl<'a, 'input: 'a> YarnSpinnerParserVisitorCompat<'input> for CodeGenerationVi
                 .with_operand(expressions.len()),
         );
 
-        // then call the function itself
-        let function_name = ctx.FUNC_ID().unwrap().get_text();
+        // then call the function itself, or the overload the type checker picked for it
+        let function_name = self
+            .compiler_listener
+            .resolved_overloads
+            .get(ctx)
+            .cloned()
+            .unwrap_or_else(|| ctx.FUNC_ID().unwrap().get_text());
         self.compiler_listener.emit(
             Emit::from_op_code(OpCode::CallFunc)
                 .with_token(token.deref())
@@ -49,7 +49,7 @@ impl<'input> ParseTreeVisitorCompat<'input> for ConstantValueVisitor<'input> {
 impl<'input> YarnSpinnerParserVisitorCompat<'input> for ConstantValueVisitor<'input> {
     fn visit_valueNumber(&mut self, ctx: &ValueNumberContext<'input>) -> Self::Return {
         let text = ctx.get_text();
-        if let Ok(number) = text.parse::<f32>() {
+        if let Ok(number) = text.parse::<f64>() {
             InternalValue::from(number).into()
         } else {
             let message = format!("Failed to parse {text} as a float",);
@@ -102,6 +102,36 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for ConstantValueVisitor<'in
     }
 
     fn visit_valueFunc(&mut self, ctx: &ValueFuncContext<'input>) -> Self::Return {
+        let function_call = ctx.function_call().unwrap();
+        let function_name = function_call.FUNC_ID().unwrap().get_text();
+        // Not part of the original implementation.
+        // Since there is no syntax for list literals, a call to `list` with constant arguments is treated as one.
+        if function_name == "list" {
+            let values: Option<Vec<_>> = function_call
+                .expression_all()
+                .iter()
+                .map(|expression| {
+                    let ExpressionContextAll::ExpValueContext(value_context) = expression.as_ref()
+                    else {
+                        let text = expression.get_text();
+                        let message = format!(
+                            "Variable declarations must be constant values, but `{text}` is an expression",
+                        );
+                        self.diagnostics.push(
+                            Diagnostic::from_message(message)
+                                .with_file_name(&self.file.name)
+                                .with_parser_context(expression.as_ref(), self.file.tokens()),
+                        );
+                        return None;
+                    };
+                    let value = value_context.value().unwrap();
+                    self.visit(value.as_ref()).0.map(|value| value.raw_value)
+                })
+                .collect();
+            return values
+                .map(|values| InternalValue::from(YarnValue::List(values)).into())
+                .unwrap_or_else(ConstantValue::non_panicking_default);
+        }
         let text = ctx.get_text();
         let message =
             format!("Variable declarations must be constant values, but `{text}` is a function",);
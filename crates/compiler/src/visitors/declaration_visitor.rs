@@ -82,18 +82,20 @@ impl<'input> ParseTreeVisitorCompat<'input> for DeclarationVisitor<'input> {
 
 impl<'input> YarnSpinnerParserVisitorCompat<'input> for DeclarationVisitor<'input> {
     fn visit_file_hashtag(&mut self, ctx: &File_hashtagContext<'input>) -> Self::Return {
-        let hashtag_text = ctx.text.as_ref().unwrap();
-        self.file_tags.push(hashtag_text.get_text().to_owned());
+        if let Some(hashtag_text) = ctx.text.as_ref() {
+            self.file_tags.push(hashtag_text.get_text().to_owned());
+        }
     }
 
     fn visit_node(&mut self, ctx: &NodeContext<'input>) -> Self::Return {
         for header in ctx.header_all() {
-            let header_key = header.header_key.as_ref().unwrap();
-            if header_key.get_text() != "title" {
+            let is_title = header
+                .header_key
+                .as_ref()
+                .is_some_and(|key| key.get_text() == "title");
+            let Some(header_value) = header.header_value.as_ref().filter(|_| is_title) else {
                 continue;
-            }
-
-            let header_value = header.header_value.as_ref().unwrap();
+            };
             let current_node_name = header_value.get_text();
             self.current_node_name = Some(current_node_name.to_owned());
             if self.regex.is_match(current_node_name) {
@@ -112,8 +114,11 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for DeclarationVisitor<'inpu
     }
 
     fn visit_declare_statement(&mut self, ctx: &Declare_statementContext<'input>) -> Self::Return {
+        // Incomplete declarations were already reported by the parser
+        let (Some(variable_context), Some(value_context)) = (ctx.variable(), ctx.value()) else {
+            return;
+        };
         // Get the name of the variable we're declaring
-        let variable_context = ctx.variable().unwrap();
         let variable_name = variable_context.get_text();
 
         // Does this variable name already exist in our declarations?
@@ -142,7 +147,6 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for DeclarationVisitor<'inpu
         // Figure out the value and its type
         let mut constant_value_visitor =
             ConstantValueVisitor::new(self.diagnostics.clone(), self.file.clone());
-        let value_context = ctx.value().unwrap();
         let value = constant_value_visitor.visit(value_context.as_ref());
         self.diagnostics
             .extend_from_slice(&constant_value_visitor.diagnostics);
@@ -207,11 +211,12 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for DeclarationVisitor<'inpu
     }
 }
 
-fn keyword_to_type(keyword: &str) -> Option<Type> {
+pub(crate) fn keyword_to_type(keyword: &str) -> Option<Type> {
     match keyword {
         "string" => Some(Type::String),
         "number" => Some(Type::Number),
         "bool" => Some(Type::Boolean),
+        "list" => Some(Type::List),
         _ => None,
     }
 }
@@ -238,6 +243,8 @@ mod tests {
             library: Default::default(),
             compilation_type: CompilationType::FullCompilation,
             variable_declarations: vec![],
+            constants: Default::default(),
+            ..Default::default()
         }
         .compile()
         .unwrap();
@@ -328,6 +335,8 @@ mod tests {
             library: Default::default(),
             compilation_type: CompilationType::FullCompilation,
             variable_declarations: vec![],
+            constants: Default::default(),
+            ..Default::default()
         }
         .compile();
 
@@ -42,6 +42,30 @@ impl KnownTypes {
     }
 }
 
+/// The names of the overloads or custom type members that the [`TypeCheckVisitor`](crate::visitors::TypeCheckVisitor) resolved function calls to,
+/// see [`Library::add_overload`] and [`Library::add_custom_type`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct ResolvedOverloads(pub(crate) HashMap<HashableInterval, String>);
+
+impl ResolvedOverloads {
+    pub(crate) fn get<'input>(
+        &self,
+        ctx: &impl YarnSpinnerParserContext<'input>,
+    ) -> Option<&String> {
+        let hashable_interval = ctx.get_hashable_interval();
+        self.0.get(&hashable_interval)
+    }
+
+    pub(crate) fn insert<'input>(
+        &mut self,
+        ctx: &impl YarnSpinnerParserContext<'input>,
+        overload_name: String,
+    ) -> Option<String> {
+        let hashable_interval = ctx.get_hashable_interval();
+        self.0.insert(hashable_interval, overload_name)
+    }
+}
+
 impl From<Interval> for HashableInterval {
     fn from(interval: Interval) -> Self {
         Self(interval)
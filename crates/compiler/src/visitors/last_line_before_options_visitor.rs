@@ -6,12 +6,30 @@ use crate::prelude::*;
 use antlr_rust::tree::ParseTreeVisitorCompat;
 use std::rc::Rc;
 
-#[derive(Debug, Clone, Default)]
-pub(crate) struct LastLineBeforeOptionsVisitor {
+#[derive(Debug, Clone)]
+pub(crate) struct LastLineBeforeOptionsVisitor<'input> {
+    /// The hashtag to add to the lines, if any.
+    last_line_tag: Option<&'input str>,
+    /// Every tagged line along with the lines of the options that follow it,
+    /// so that the options can be linked to it once all lines have their IDs.
+    pub(crate) options_by_last_line: Vec<(
+        Rc<Line_statementContextAll<'input>>,
+        Vec<Rc<Line_statementContextAll<'input>>>,
+    )>,
     _dummy: (),
 }
 
-impl<'input> ParseTreeVisitorCompat<'input> for LastLineBeforeOptionsVisitor {
+impl<'input> LastLineBeforeOptionsVisitor<'input> {
+    pub(crate) fn new(last_line_tag: Option<&'input str>) -> Self {
+        Self {
+            last_line_tag,
+            options_by_last_line: Default::default(),
+            _dummy: (),
+        }
+    }
+}
+
+impl<'input> ParseTreeVisitorCompat<'input> for LastLineBeforeOptionsVisitor<'input> {
     type Node = YarnSpinnerParserContextType;
     type Return = ();
 
@@ -20,7 +38,7 @@ impl<'input> ParseTreeVisitorCompat<'input> for LastLineBeforeOptionsVisitor {
     }
 }
 
-impl<'input> YarnSpinnerParserVisitorCompat<'input> for LastLineBeforeOptionsVisitor {
+impl<'input> YarnSpinnerParserVisitorCompat<'input> for LastLineBeforeOptionsVisitor<'input> {
     // entry point for everything
     // if there are no ifs or options with embedded statements this will be all that is visited
     fn visit_body(&mut self, ctx: &BodyContext<'input>) -> Self::Return {
@@ -32,7 +50,9 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for LastLineBeforeOptionsVis
     // The line is tagged regardless of if there is a #lastline there already
     // technically unnecessary in that case but this feels uncommon enough to not bother edgecasing
     fn visit_line_statement(&mut self, ctx: &Line_statementContext<'input>) -> Self::Return {
-        add_hashtag_child(ctx, "lastline")
+        if let Some(last_line_tag) = self.last_line_tag {
+            add_hashtag_child(ctx, last_line_tag)
+        }
     }
 
     // handles the statements inside of an if statement
@@ -64,7 +84,7 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for LastLineBeforeOptionsVis
     }
 }
 
-impl LastLineBeforeOptionsVisitor {
+impl<'input> LastLineBeforeOptionsVisitor<'input> {
     // in the current block of statements finds any lines that immediately follow an option block and visits them for tagging
     // this works by making our way through each and every statement inside of a block performing the following:
     // 1. assume the current statement is an option block
@@ -72,7 +92,7 @@ impl LastLineBeforeOptionsVisitor {
     // 3. if both of these hold true we have found a line we need to flag as being before options
     // 4. repeat this process until we run out of statements to check
     // this has the potential to have VERY deep call stacks
-    fn run_through_statement(&mut self, statements: &[Rc<StatementContextAll>]) {
+    fn run_through_statement(&mut self, statements: &[Rc<StatementContextAll<'input>>]) {
         for (i, statement) in statements.iter().enumerate() {
             // if we are an if-block we have to visit it in case there are options and lines inside of that
             // once that is done we can move onto the next statement
@@ -98,6 +118,12 @@ impl LastLineBeforeOptionsVisitor {
                 // ok now at this point we know the line that needs to be tagged as the last line
                 // we do that inside the line visitation
                 self.visit(previous.as_ref());
+                let options = shortcut_option_statement
+                    .shortcut_option_all()
+                    .iter()
+                    .filter_map(|option| option.line_statement())
+                    .collect();
+                self.options_by_last_line.push((previous, options));
             }
         }
     }
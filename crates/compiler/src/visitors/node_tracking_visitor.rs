@@ -32,7 +32,9 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for NodeTrackingVisitor {
         let mut title = None;
         let mut tracking = None;
         for header in ctx.header_all() {
-            let key = header.header_key.as_ref().unwrap().get_text();
+            let Some(key) = header.header_key.as_ref().map(|key| key.get_text()) else {
+                continue;
+            };
             let value = header
                 .header_value
                 .as_ref()
@@ -67,8 +69,7 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for NodeTrackingVisitor {
     }
 
     fn visit_valueString(&mut self, ctx: &ValueStringContext<'input>) -> Self::Return {
-        ctx.get_token(yarnspinnerparser::STRING, 0)
-            .unwrap()
+        ctx.get_token(yarnspinnerparser::STRING, 0)?
             .get_text()
             .trim_matches('"')
             .to_owned()
@@ -76,10 +77,7 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for NodeTrackingVisitor {
     }
 
     fn visit_function_call(&mut self, ctx: &Function_callContext<'input>) -> Self::Return {
-        let function_name = ctx
-            .get_token(yarnspinnerparser::FUNC_ID, 0)
-            .unwrap()
-            .get_text();
+        let function_name = ctx.get_token(yarnspinnerparser::FUNC_ID, 0)?.get_text();
 
         if !["visited", "visited_count"].contains(&function_name.as_str()) {
             return None;
@@ -87,7 +85,10 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for NodeTrackingVisitor {
         // we aren't bothering to test anything about the value itself
         // if it isn't a static string we'll get back null so can ignore it
         // if the func has more than one parameter later on it will cause an error so again can ignore
-        let expression = ctx.expression(0).unwrap();
+        let Some(expression) = ctx.expression(0) else {
+            // The type checker reports the missing argument
+            return None;
+        };
         let result = self.visit(expression.as_ref());
         if let Some(result) = result {
             self.tracking_nodes.insert(result);
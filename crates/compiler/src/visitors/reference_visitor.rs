@@ -0,0 +1,153 @@
+//! Not part of the original Yarn Spinner. Finds the places in a parse tree where a [`Symbol`] is used,
+//! see [`Compiler::find_references`].
+
+use crate::parser::generated::yarnspinnerparser::*;
+use crate::prelude::generated::yarnspinnerparservisitor::YarnSpinnerParserVisitorCompat;
+use crate::prelude::*;
+use antlr_rust::rule_context::RuleContext;
+use antlr_rust::token::{CommonToken, Token};
+use antlr_rust::tree::{ParseTree, ParseTreeVisitorCompat};
+use std::ops::Range;
+use yarnspinner_core::prelude::*;
+
+/// The functions whose first argument is the name of a node.
+const NODE_NAME_FUNCTIONS: &[&str] = &["visited", "visited_count"];
+
+pub(crate) struct ReferenceVisitor<'a> {
+    symbol: &'a Symbol,
+    file_name: String,
+    pub(crate) references: Vec<SymbolReference>,
+    _dummy: (),
+}
+
+impl<'a> ReferenceVisitor<'a> {
+    pub(crate) fn new(symbol: &'a Symbol, file_name: String) -> Self {
+        Self {
+            symbol,
+            file_name,
+            references: Default::default(),
+            _dummy: (),
+        }
+    }
+
+    fn add(&mut self, token: &CommonToken, kind: ReferenceKind) {
+        self.references.push(SymbolReference {
+            file_name: self.file_name.clone(),
+            range: token_range(token, 0),
+            kind,
+        });
+    }
+
+    /// Adds the node name in a string literal like `"Start"`, excluding the quotes.
+    fn add_node_name_in_string(&mut self, expression: &ExpressionContextAll) {
+        let ExpressionContextAll::ExpValueContext(expression) = expression else {
+            return;
+        };
+        let Some(value) = expression.value() else {
+            return;
+        };
+        let ValueContextAll::ValueStringContext(value) = value.as_ref() else {
+            return;
+        };
+        let Some(string) = value.STRING() else {
+            return;
+        };
+        let text = string.get_text();
+        let name = text.trim_matches('"');
+        if self.symbol == &Symbol::Node(name.to_owned()) {
+            let mut range = token_range(&string.symbol, 1);
+            range.end.character = range.start.character + name.chars().count();
+            self.references.push(SymbolReference {
+                file_name: self.file_name.clone(),
+                range,
+                kind: ReferenceKind::Usage,
+            });
+        }
+    }
+}
+
+/// The range of a token, skipping the given number of leading characters.
+fn token_range(token: &CommonToken, skip: usize) -> Range<Position> {
+    let start = Position {
+        line: token.get_line_as_usize().saturating_sub(1),
+        character: token.get_column_as_usize() + skip,
+    };
+    let end = Position {
+        line: start.line,
+        character: token.get_column_as_usize() + token.get_text().chars().count(),
+    };
+    start..end
+}
+
+impl<'input> ParseTreeVisitorCompat<'input> for ReferenceVisitor<'_> {
+    type Node = YarnSpinnerParserContextType;
+    type Return = ();
+
+    fn temp_result(&mut self) -> &mut Self::Return {
+        &mut self._dummy
+    }
+}
+
+impl<'input> YarnSpinnerParserVisitorCompat<'input> for ReferenceVisitor<'_> {
+    fn visit_header(&mut self, ctx: &HeaderContext<'input>) -> Self::Return {
+        let is_title = ctx
+            .header_key
+            .as_ref()
+            .is_some_and(|key| key.get_text() == "title");
+        let Some(value) = ctx.header_value.as_ref().filter(|_| is_title) else {
+            return;
+        };
+        if self.symbol == &Symbol::Node(value.get_text().to_owned()) {
+            self.add(value, ReferenceKind::Definition);
+        }
+    }
+
+    fn visit_variable(&mut self, ctx: &VariableContext<'input>) -> Self::Return {
+        let Some(variable) = ctx.VAR_ID() else {
+            return;
+        };
+        if self.symbol != &Symbol::Variable(variable.get_text()) {
+            return;
+        }
+        let is_declaration = ctx
+            .get_parent_ctx()
+            .is_some_and(|parent| parent.get_rule_index() == RULE_declare_statement);
+        let kind = if is_declaration {
+            ReferenceKind::Definition
+        } else {
+            ReferenceKind::Usage
+        };
+        self.add(&variable.symbol, kind);
+    }
+
+    fn visit_function_call(&mut self, ctx: &Function_callContext<'input>) -> Self::Return {
+        if let Some(function) = ctx.FUNC_ID() {
+            let name = function.get_text();
+            if self.symbol == &Symbol::Function(name.clone()) {
+                self.add(&function.symbol, ReferenceKind::Usage);
+            }
+            if NODE_NAME_FUNCTIONS.contains(&name.as_str()) {
+                if let Some(expression) = ctx.expression(0) {
+                    self.add_node_name_in_string(&expression);
+                }
+            }
+        }
+        ParseTreeVisitorCompat::visit_children(self, ctx);
+    }
+
+    fn visit_jumpToNodeName(&mut self, ctx: &JumpToNodeNameContext<'input>) -> Self::Return {
+        let Some(destination) = ctx.destination.as_ref() else {
+            return;
+        };
+        if self.symbol == &Symbol::Node(destination.get_text().to_owned()) {
+            self.add(destination, ReferenceKind::Usage);
+        }
+    }
+
+    fn visit_jumpToExpression(&mut self, ctx: &JumpToExpressionContext<'input>) -> Self::Return {
+        if let Some(expression) = ctx.expression() {
+            self.add_node_name_in_string(&expression);
+        }
+        ParseTreeVisitorCompat::visit_children(self, ctx);
+    }
+}
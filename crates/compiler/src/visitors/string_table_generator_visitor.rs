@@ -49,14 +49,16 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for StringTableGeneratorVisi
     fn visit_node(&mut self, ctx: &NodeContext<'input>) -> Self::Return {
         let mut tags = Vec::new();
         for header in ctx.header_all() {
-            let header_key = header.header_key.as_ref().unwrap().get_text();
+            // Headers that failed to parse were already reported by the parser
+            let Some(header_key) = header.header_key.as_ref().map(|key| key.get_text()) else {
+                continue;
+            };
             if header_key == "title" {
-                header
+                self.current_node_name = header
                     .header_value
                     .as_ref()
-                    .unwrap()
-                    .get_text()
-                    .clone_into(&mut self.current_node_name)
+                    .map(|header| header.get_text().to_owned())
+                    .unwrap_or_default();
             } else if header_key == "tags" {
                 let header_value = header
                     .header_value
@@ -70,6 +72,10 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for StringTableGeneratorVisi
                     .collect();
             }
         }
+        // String table generator: don't crash if a node has no body
+        let Some(body) = ctx.body() else {
+            return;
+        };
         if !self.current_node_name.is_empty() && tags.contains(&"rawText".to_owned()) {
             // This is a raw text node. Use its entire contents as a
             // string and don't use its contents.
@@ -77,19 +83,16 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for StringTableGeneratorVisi
             self.string_table_manager.insert(
                 line_id,
                 StringInfo {
-                    text: ctx.body().unwrap().get_text(),
+                    text: body.get_text(),
                     node_name: self.current_node_name.clone(),
-                    line_number: ctx.body().unwrap().start().line as usize,
+                    line_number: body.start().line as usize,
                     file_name: self.file.name.clone(),
                     ..Default::default()
                 },
             );
         } else {
             // This is a regular node
-            // String table generator: don't crash if a node has no body
-            if let Some(body) = ctx.body() {
-                self.visit(body.as_ref());
-            }
+            self.visit(body.as_ref());
         }
     }
 
@@ -170,14 +173,7 @@ fn generate_formatted_text(ctx: &Line_formatted_textContext) -> String {
 pub(crate) fn get_hashtag_texts(hashtags: &[Rc<HashtagContext>]) -> Vec<String> {
     hashtags
         .iter()
-        .map(|t| {
-            t.text
-                .as_ref()
-                .expect("No text in hashtag")
-                .get_text()
-                .trim()
-                .to_owned()
-        })
+        .filter_map(|t| Some(t.text.as_ref()?.get_text().trim().to_owned()))
         .collect()
 }
 
@@ -260,6 +256,8 @@ a {1 + 3} cool expression
             library: Default::default(),
             compilation_type: CompilationType::FullCompilation,
             variable_declarations: vec![],
+            constants: Default::default(),
+            ..Default::default()
         }
         .compile()
         .unwrap();
@@ -275,6 +273,7 @@ a {1 + 3} cool expression
                 file_name: "test.yarn".to_string(),
                 is_implicit_tag: true,
                 metadata: vec![],
+                parent_line_id: None,
             }
         );
         assert_eq!(
@@ -286,6 +285,7 @@ a {1 + 3} cool expression
                 file_name: "test.yarn".to_string(),
                 is_implicit_tag: true,
                 metadata: vec![],
+                parent_line_id: None,
             }
         );
         assert_eq!(
@@ -297,6 +297,7 @@ a {1 + 3} cool expression
                 file_name: "test.yarn".to_string(),
                 is_implicit_tag: true,
                 metadata: vec![],
+                parent_line_id: None,
             }
         );
     }
@@ -318,6 +319,8 @@ a {very} cool expression
             library: Default::default(),
             compilation_type: CompilationType::FullCompilation,
             variable_declarations: vec![],
+            constants: Default::default(),
+            ..Default::default()
         }
         .compile();
 
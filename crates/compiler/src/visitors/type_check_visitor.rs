@@ -5,16 +5,19 @@ use crate::prelude::generated::yarnspinnerlexer;
 use crate::prelude::generated::yarnspinnerparser::*;
 use crate::prelude::generated::yarnspinnerparservisitor::YarnSpinnerParserVisitorCompat;
 use crate::prelude::*;
-use crate::visitors::{CodeGenerationVisitor, KnownTypes};
+use crate::visitors::{CodeGenerationVisitor, KnownTypes, ResolvedOverloads};
 use antlr_rust::parser_rule_context::ParserRuleContext;
 use antlr_rust::token::Token;
 use antlr_rust::tree::{ParseTree, ParseTreeVisitorCompat};
 use check_operation::*;
+use std::collections::HashSet;
 use std::path::Path;
 use yarnspinner_core::prelude::*;
 use yarnspinner_core::types::*;
 
 mod check_operation;
+mod resolve_member;
+mod resolve_overload;
 
 /// A visitor that walks the parse tree, checking for type consistency
 /// in expressions. Existing type information is provided via the
@@ -68,6 +71,12 @@ pub(crate) struct TypeCheckVisitor<'input> {
     /// on the [`ValueContext`] directly using a `partial`
     hints: KnownTypes,
 
+    /// The functions that function calls were resolved to, if the called function is overloaded or a member of a custom type.
+    pub(crate) resolved_overloads: ResolvedOverloads,
+
+    /// The names of the constants defined with [`Compiler::define_constant`], which cannot be assigned to.
+    constants: HashSet<String>,
+
     file: FileParseResult<'input>,
     _dummy: Option<Type>,
 }
@@ -86,10 +95,17 @@ impl<'input> TypeCheckVisitor<'input> {
             current_node_name: Default::default(),
             known_types: Default::default(),
             hints: Default::default(),
+            resolved_overloads: Default::default(),
+            constants: Default::default(),
             _dummy: Default::default(),
         }
     }
 
+    pub(crate) fn with_constants(mut self, constants: impl IntoIterator<Item = String>) -> Self {
+        self.constants.extend(constants);
+        self
+    }
+
     /// Gets the collection of all declarations - both the ones we received
     /// at the start, and the new ones we've derived ourselves.
     pub(crate) fn declarations(&self) -> impl Iterator<Item = &Declaration> + '_ {
@@ -105,6 +121,21 @@ impl<'input> TypeCheckVisitor<'input> {
             .iter_mut()
             .chain(self.new_declarations.iter_mut())
     }
+
+    /// Emits a warning if the called function was registered as deprecated, see [`YarnFnMetadata::deprecation`].
+    fn warn_if_deprecated(&mut self, ctx: &ValueFuncContext<'input>, declaration: &Declaration) {
+        let Some(deprecation) = &declaration.deprecation else {
+            return;
+        };
+        let diagnostic = Diagnostic::from_message(format!(
+            "Function \"{}\" is deprecated: {deprecation}",
+            declaration.name
+        ))
+        .with_file_name(&self.file.name)
+        .with_parser_context(ctx, self.file.tokens())
+        .with_severity(DiagnosticSeverity::Warning);
+        self.diagnostics.push(diagnostic);
+    }
 }
 
 impl<'input> ParseTreeVisitorCompat<'input> for TypeCheckVisitor<'input> {
@@ -120,10 +151,12 @@ impl<'input> ParseTreeVisitorCompat<'input> for TypeCheckVisitor<'input> {
 impl<'input> YarnSpinnerParserVisitorCompat<'input> for TypeCheckVisitor<'input> {
     fn visit_node(&mut self, ctx: &NodeContext<'input>) -> Self::Return {
         for header in ctx.header_all() {
-            let key = header.header_key.as_ref().unwrap().get_text();
-            if key == "title" {
-                let value = header.header_value.as_ref().unwrap().get_text();
-                self.current_node_name = Some(value.to_owned());
+            let key = header.header_key.as_ref().map(|key| key.get_text());
+            if key == Some("title") {
+                self.current_node_name = header
+                    .header_value
+                    .as_ref()
+                    .map(|value| value.get_text().to_owned());
             }
         }
         if let Some(body) = ctx.body() {
@@ -265,13 +298,22 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for TypeCheckVisitor<'input>
             .get_token(yarnspinnerlexer::FUNC_ID, 0)
             .unwrap()
             .get_text();
+        // Not part of the original implementation, see `IndentAwareYarnSpinnerLexer::handle_variable_token`
+        let function_name = match function_name.strip_prefix('.') {
+            Some(member_name) => self.resolve_member(ctx, member_name)?,
+            None => function_name,
+        };
 
         let function_declaration = self
             .declarations()
             .find(|decl| decl.name == function_name)
             .cloned(); // Cloning to avoid borrow checker issues
+        if function_declaration.is_none() && self.is_overloaded(&function_name) {
+            return self.visit_overloaded_function_call(ctx, &function_name);
+        }
         let hint = self.hints.get(ctx).cloned();
         let function_type = if let Some(function_declaration) = function_declaration {
+            self.warn_if_deprecated(ctx, &function_declaration);
             let Type::Function(mut function_type) = function_declaration.r#type.clone() else {
                 unreachable!("Internal error: function declaration is not of type Function. This is a bug. Please report it at https://github.com/YarnSpinnerTool/YarnSpinner-Rust/issues/new")
             };
@@ -322,17 +364,26 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for TypeCheckVisitor<'input>
         // Check each parameter of the function
         let supplied_parameters = ctx.function_call().unwrap().expression_all();
         let expected_parameter_types = function_type.parameters;
+        let variadic_parameter_type = *function_type.variadic_parameter_type;
+        let is_variadic = variadic_parameter_type.is_some();
 
-        if supplied_parameters.len() != expected_parameter_types.len() {
+        let has_wrong_parameter_count = if is_variadic {
+            supplied_parameters.len() < expected_parameter_types.len()
+        } else {
+            supplied_parameters.len() != expected_parameter_types.len()
+        };
+        if has_wrong_parameter_count {
             // Wrong number of parameters supplied
             let parameters = if expected_parameter_types.len() == 1 {
                 "parameter"
             } else {
                 "parameters"
             };
+            let at_least = if is_variadic { "at least " } else { "" };
             let diagnostic = Diagnostic::from_message(format!(
-                "Function \"{}\" expects {} {}, but received {}",
+                "Function \"{}\" expects {}{} {}, but received {}",
                 function_name,
+                at_least,
                 expected_parameter_types.len(),
                 parameters,
                 supplied_parameters.len()
@@ -343,10 +394,14 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for TypeCheckVisitor<'input>
             return *function_type.return_type;
         }
 
+        // Any arguments beyond the declared parameters are checked against the variadic parameter type
+        let expected_parameter_types = expected_parameter_types
+            .iter()
+            .chain(core::iter::repeat(&variadic_parameter_type));
         for (i, (supplied_parameter, mut expected_type)) in supplied_parameters
             .iter()
             .cloned()
-            .zip(expected_parameter_types.iter())
+            .zip(expected_parameter_types)
             .enumerate()
         {
             let supplied_type = self.visit(supplied_parameter.as_ref());
@@ -448,6 +503,15 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for TypeCheckVisitor<'input>
         }
         let mut expression_type = self.visit(expression_context.as_ref());
         let variable_name = variable_context.get_text();
+        if self.constants.contains(&variable_name) {
+            let diagnostic = Diagnostic::from_message(format!(
+                "{variable_name} is a constant and cannot be set"
+            ))
+            .with_file_name(&self.file.name)
+            .with_parser_context(ctx, self.file.tokens());
+            self.diagnostics.push(diagnostic);
+            return None;
+        }
         let terms: &[Term] = &[
             variable_context.clone().into(),
             expression_context.clone().into(),
@@ -480,11 +544,11 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for TypeCheckVisitor<'input>
                         // we can't get one, we can't create the definition.
                         if let Some(default_value) = expression_type.default_value() {
                             // Generate a declaration for this variable here.
-                            let decl = Declaration::new(variable_name, expression_type.clone())
+                            let decl = Declaration::new(variable_name.clone(), expression_type.clone())
                                 .with_description(format!(
                                     "Implicitly declared in {}, node {}",
                                     get_filename(&self.file.name),
-                                    self.current_node_name.as_ref().unwrap()
+                                    self.current_node_name.as_deref().unwrap_or_default()
                                 ))
                                 .with_default_value(default_value)
                                 .with_source_file_name(self.file.name.clone())
@@ -492,6 +556,15 @@ impl<'input> YarnSpinnerParserVisitorCompat<'input> for TypeCheckVisitor<'input>
                                 .with_range(variable_context.range())
                                 .with_implicit();
                             self.new_declarations.push(decl);
+                            self.diagnostics.push(
+                                Diagnostic::from_message(format_inferred_variable_type_warning(
+                                    &variable_name,
+                                    expression_type,
+                                ))
+                                .with_file_name(&self.file.name)
+                                .with_parser_context(variable_context.as_ref(), self.file.tokens())
+                                .with_severity(DiagnosticSeverity::Warning),
+                            );
                         } else {
                             self.diagnostics.push(
                                 Diagnostic::from_message(
@@ -576,6 +649,16 @@ fn format_cannot_determine_variable_type_error(name: &str) -> String {
     format!("Can't figure out the type of variable {name} given its context. Specify its type with a <<declare>> statement.")
 }
 
+/// {0} = variable name, {1} = inferred type
+///
+/// Not part of the original implementation.
+fn format_inferred_variable_type_warning(name: &str, r#type: &Type) -> String {
+    format!(
+        "Variable {name} is not declared, so its type was implicitly inferred as {}",
+        r#type.format()
+    )
+}
+
 fn get_filename(path: &str) -> &str {
     if let Some(os_str) = Path::new(path).file_name() {
         if let Some(file_name) = os_str.to_str() {
@@ -594,6 +677,7 @@ impl DefaultValue for Type {
             Type::String => Some(YarnValue::String(Default::default())),
             Type::Number => Some(YarnValue::Number(Default::default())),
             Type::Boolean => Some(YarnValue::Boolean(Default::default())),
+            Type::List => Some(YarnValue::List(Default::default())),
             _ => None,
         }
     }
@@ -629,6 +713,8 @@ mod tests {
             library: Default::default(),
             compilation_type: CompilationType::FullCompilation,
             variable_declarations: vec![],
+            constants: Default::default(),
+            ..Default::default()
         }
         .compile()
         .unwrap();
@@ -654,6 +740,8 @@ mod tests {
             library: Default::default(),
             compilation_type: CompilationType::FullCompilation,
             variable_declarations: vec![],
+            constants: Default::default(),
+            ..Default::default()
         }
         .compile();
 
@@ -741,6 +829,8 @@ mod tests {
             library: Default::default(),
             compilation_type: CompilationType::FullCompilation,
             variable_declarations: vec![],
+            constants: Default::default(),
+            ..Default::default()
         }
         .compile()
         .unwrap();
@@ -764,6 +854,8 @@ mod tests {
             library: Default::default(),
             compilation_type: CompilationType::FullCompilation,
             variable_declarations: vec![],
+            constants: Default::default(),
+            ..Default::default()
         }
         .compile();
 
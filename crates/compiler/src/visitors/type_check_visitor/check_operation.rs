@@ -2,7 +2,8 @@ use crate::parser_rule_context_ext::ParserRuleContextExt;
 use crate::prelude::generated::yarnspinnerparser::*;
 use crate::prelude::*;
 use crate::visitors::type_check_visitor::{
-    format_cannot_determine_variable_type_error, get_filename, DefaultValue,
+    format_cannot_determine_variable_type_error, format_inferred_variable_type_warning,
+    get_filename, DefaultValue,
 };
 use crate::visitors::*;
 use antlr_rust::rule_context::CustomRuleContext;
@@ -158,6 +159,18 @@ impl<'input> TypeCheckVisitor<'input> {
                     continue;
                 }
                 func.return_type = Box::new(expression_type.clone());
+                // Not part of the original implementation.
+                if let Some(expression_type) = &expression_type {
+                    let message = format!(
+                        "Function \"{id}\" is not declared, so its return type was implicitly inferred as {}",
+                        expression_type.format()
+                    );
+                    let diagnostic = Diagnostic::from_message(message)
+                        .with_file_name(&self.file.name)
+                        .with_parser_context(func_context, self.file.tokens())
+                        .with_severity(DiagnosticSeverity::Warning);
+                    self.diagnostics.push(diagnostic);
+                }
             } else {
                 self.visit(term.deref());
             }
@@ -213,10 +226,10 @@ impl<'input> TypeCheckVisitor<'input> {
         // Build the list of variable contexts that we don't have a
         // declaration for. We'll check for explicit declarations first.
         let mut undefined_variable_contexts: Vec<_> = variable_contexts
+            // Variables without a name were already reported by the parser
             .filter(|v| {
-                !self
-                    .declarations()
-                    .any(|d| d.name == v.VAR_ID().unwrap().get_text())
+                v.VAR_ID()
+                    .is_some_and(|var_id| !self.declarations().any(|d| d.name == var_id.get_text()))
             })
             .collect();
         // Implementation note: The original compares by reference here. The interval should be unique for each context, so let's use that instead.
@@ -242,7 +255,7 @@ impl<'input> TypeCheckVisitor<'input> {
                     .map(|name| format!(", node {name}"))
                     .unwrap_or_default();
                 let r#type = expression_type.clone().unwrap(); // Guaranteed to be Some
-                let decl = Declaration::new(var_name.clone(), r#type)
+                let decl = Declaration::new(var_name.clone(), r#type.clone())
                     .with_description(format!("Implicitly declared in {file_name}{node}"))
                     .with_default_value(default_value)
                     .with_source_file_name(self.file.name.clone())
@@ -250,6 +263,13 @@ impl<'input> TypeCheckVisitor<'input> {
                     .with_range(undefined_variable_context.range())
                     .with_implicit();
                 self.new_declarations.push(decl);
+                // Not part of the original implementation.
+                let message = format_inferred_variable_type_warning(&var_name, &r#type);
+                let diagnostic = Diagnostic::from_message(message)
+                    .with_file_name(&self.file.name)
+                    .with_parser_context(undefined_variable_context.as_ref(), self.file.tokens())
+                    .with_severity(DiagnosticSeverity::Warning);
+                self.diagnostics.push(diagnostic);
             } else {
                 // If we can't produce this, then we can't generate the
                 // declaration.
@@ -0,0 +1,52 @@
+//! Not part of the original implementation, which does not support custom types.
+
+use crate::prelude::generated::yarnspinnerparser::*;
+use crate::prelude::*;
+use crate::visitors::*;
+use antlr_rust::tree::ParseTree;
+use yarnspinner_core::prelude::*;
+use yarnspinner_core::types::{Type, TypeFormat};
+
+impl<'input> TypeCheckVisitor<'input> {
+    /// Resolves a member access like `$player.name`, which the lexer turned into a call of the function `.name` with the object as its first argument,
+    /// to the function registered for the member of the object's type by [`Library::add_custom_type`], e.g. `Player_name`.
+    /// The resolved function is remembered in [`TypeCheckVisitor::resolved_overloads`] so that the code generation calls it directly.
+    pub(super) fn resolve_member(
+        &mut self,
+        ctx: &ValueFuncContext<'input>,
+        member_name: &str,
+    ) -> Option<String> {
+        let function_call = ctx.function_call().unwrap();
+        // The lexer only rewrites member accesses on variables, so the object is always present
+        let variable_name = function_call.expression(0).unwrap().get_text();
+        let variable_type = self
+            .declarations()
+            .find(|decl| decl.name == variable_name)
+            .map(|decl| decl.r#type.clone());
+
+        let message = match variable_type {
+            Some(Type::Custom(type_name)) => {
+                let function_name =
+                    format!("{type_name}{}{member_name}", Library::NAMESPACE_SEPARATOR);
+                if self.declarations().any(|decl| decl.name == function_name) {
+                    self.resolved_overloads
+                        .insert(function_call.as_ref(), function_name.clone());
+                    return Some(function_name);
+                }
+                format!("Type {type_name} has no member \"{member_name}\"")
+            }
+            Some(variable_type) => format!(
+                "Cannot access member \"{member_name}\" of {variable_name}, because its type {} is not a custom type",
+                variable_type.format()
+            ),
+            None => format!(
+                "Cannot access member \"{member_name}\" of {variable_name}, because it has not been declared"
+            ),
+        };
+        let diagnostic = Diagnostic::from_message(message)
+            .with_file_name(&self.file.name)
+            .with_parser_context(ctx, self.file.tokens());
+        self.diagnostics.push(diagnostic);
+        None
+    }
+}
@@ -0,0 +1,78 @@
+//! Not part of the original implementation, which does not support function overloading.
+
+use crate::prelude::generated::yarnspinnerparser::*;
+use crate::prelude::*;
+use crate::visitors::*;
+use antlr_rust::tree::ParseTreeVisitorCompat;
+use yarnspinner_core::prelude::*;
+use yarnspinner_core::types::{Type, TypeFormat};
+
+impl<'input> TypeCheckVisitor<'input> {
+    /// Returns `true` if overloads of the given function were registered with [`Library::add_overload`].
+    pub(super) fn is_overloaded(&self, function_name: &str) -> bool {
+        self.declarations()
+            .any(|decl| Library::get_overload_base_name(&decl.name) == Some(function_name))
+    }
+
+    /// Type checks a call to an overloaded function by picking the overload that accepts the supplied arguments.
+    /// The chosen overload is remembered in [`TypeCheckVisitor::resolved_overloads`] so that the code generation calls it directly.
+    pub(super) fn visit_overloaded_function_call(
+        &mut self,
+        ctx: &ValueFuncContext<'input>,
+        function_name: &str,
+    ) -> Option<Type> {
+        let function_call = ctx.function_call().unwrap();
+        let argument_types: Vec<_> = function_call
+            .expression_all()
+            .iter()
+            .map(|expression| self.visit(expression.as_ref()))
+            .collect();
+
+        let overloads: Vec<_> = self
+            .declarations()
+            .filter(|decl| Library::get_overload_base_name(&decl.name) == Some(function_name))
+            .filter_map(|decl| match &decl.r#type {
+                Type::Function(function_type) => Some((decl.name.as_str(), function_type)),
+                _ => None,
+            })
+            .collect();
+        let Some(overload_name) =
+            Library::select_overload(overloads.iter().copied(), &argument_types)
+        else {
+            let argument_types = argument_types
+                .iter()
+                .map(TypeFormat::format)
+                .collect::<Vec<_>>()
+                .join(", ");
+            let candidates = overloads
+                .iter()
+                .map(|(overload_name, _)| *overload_name)
+                .collect::<Vec<_>>()
+                .join(", ");
+            let diagnostic = Diagnostic::from_message(format!(
+                "No overload of function \"{function_name}\" accepts arguments of types ({argument_types}). Available overloads: {candidates}"
+            ))
+            .with_file_name(&self.file.name)
+            .with_parser_context(ctx, self.file.tokens());
+            self.diagnostics.push(diagnostic);
+            return None;
+        };
+        let (overload_name, function_type) = overloads
+            .iter()
+            .find(|(name, _)| *name == overload_name)
+            .unwrap();
+        let return_type = *function_type.return_type.clone();
+        let overload_name = overload_name.to_string();
+        let declaration = self
+            .declarations()
+            .find(|decl| decl.name == overload_name)
+            .cloned();
+        if let Some(declaration) = declaration {
+            self.warn_if_deprecated(ctx, &declaration);
+        }
+
+        self.resolved_overloads
+            .insert(function_call.as_ref(), overload_name);
+        return_type
+    }
+}
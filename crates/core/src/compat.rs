@@ -0,0 +1,12 @@
+//! Stand-ins for the items of the `std` prelude and collections, sourced from `alloc` so that the crate also builds without the `std` feature.
+
+pub(crate) use alloc::borrow::ToOwned;
+pub(crate) use alloc::boxed::Box;
+pub(crate) use alloc::string::{String, ToString};
+pub(crate) use alloc::vec::Vec;
+pub(crate) use alloc::{format, vec};
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{hash_map, HashMap};
+
+#[cfg(not(feature = "std"))]
+pub(crate) use hashbrown::{hash_map, HashMap};
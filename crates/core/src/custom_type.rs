@@ -0,0 +1,282 @@
+//! Not part of the original Yarn Spinner, which only supports the built-in types.
+
+use crate::prelude::*;
+use crate::types::{FunctionType, InvalidDowncastError};
+use alloc::borrow::Cow;
+use alloc::sync::Arc;
+use core::any::{Any, TypeId};
+use core::fmt::{self, Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+
+/// A Rust type that can be passed around Yarn scripts as an opaque value, i.e. a [`YarnObject`].
+/// Its properties and methods are made available to Yarn by registering a [`CustomType`] in a [`Library`].
+///
+/// References to types implementing this trait can be used as parameters of [`YarnFn`]s, and the types themselves as return values.
+pub trait YarnObjectType: Any + Send + Sync {
+    /// The name of this type in Yarn scripts.
+    const NAME: &'static str;
+}
+
+/// An opaque Rust value of a type implementing [`YarnObjectType`], corresponding to [`YarnValue::Object`].
+///
+/// Objects cannot be written in Yarn scripts, so they need to be put into the [`VariableStorage`](https://docs.rs/yarnspinner/latest/yarnspinner/runtime/trait.VariableStorage.html) or returned by a [`YarnFn`].
+/// Cloning an object is cheap, as all clones share the same underlying value.
+#[derive(Clone)]
+pub struct YarnObject {
+    type_name: &'static str,
+    value: Arc<dyn Any + Send + Sync>,
+}
+
+impl YarnObject {
+    /// Wraps the given value.
+    pub fn new<T: YarnObjectType>(value: T) -> Self {
+        Self {
+            type_name: T::NAME,
+            value: Arc::new(value),
+        }
+    }
+
+    /// The name of the [`YarnObjectType`] of the wrapped value.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns a reference to the wrapped value if it is of type `T`.
+    pub fn downcast_ref<T: YarnObjectType>(&self) -> Option<&T> {
+        self.value.downcast_ref()
+    }
+
+    /// Stands in for an object when a [`YarnValue`] is created through reflection, which cannot carry the opaque value.
+    #[cfg(feature = "bevy")]
+    pub(crate) fn unreflected() -> Self {
+        Self {
+            type_name: "()",
+            value: Arc::new(()),
+        }
+    }
+}
+
+impl Debug for YarnObject {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("YarnObject")
+            .field("type_name", &self.type_name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for YarnObject {
+    /// Objects are equal if they share the same underlying value.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.value, &other.value)
+    }
+}
+
+impl Eq for YarnObject {}
+
+impl Hash for YarnObject {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.value).cast::<()>().hash(state);
+    }
+}
+
+impl Display for YarnObject {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}>", self.type_name)
+    }
+}
+
+impl From<YarnObject> for YarnValue {
+    fn from(value: YarnObject) -> Self {
+        Self::Object(value)
+    }
+}
+
+impl TryFrom<YarnValue> for YarnObject {
+    type Error = YarnValueCastError;
+
+    fn try_from(value: YarnValue) -> Result<Self, Self::Error> {
+        match value {
+            YarnValue::Object(object) => Ok(object),
+            _ => Err(YarnValueCastError::NotAnObject),
+        }
+    }
+}
+
+impl<T: YarnObjectType> IntoYarnValueFromNonYarnValue for T {
+    fn into_yarn_value(self) -> YarnValue {
+        YarnObject::new(self).into()
+    }
+}
+
+/// The properties and methods of a [`YarnObjectType`] that can be used in Yarn scripts.
+/// Register it with [`Library::add_custom_type`].
+///
+/// Members are accessed on variables holding an object, e.g. `{$player.name}` or `{$item.discounted_price(0.2)}`.
+/// A member `name` of a type `Player` is registered as the function `Player_name`, which takes the object as its first argument,
+/// so `$player.name` is the same as calling `Player_name($player)`. The compiler type checks these calls like any other function call.
+///
+/// ## Example
+///
+/// ```rust
+/// # use yarnspinner_core::prelude::*;
+/// struct Item {
+///     price: f64,
+/// }
+///
+/// impl YarnObjectType for Item {
+///     const NAME: &'static str = "Item";
+/// }
+///
+/// let item_type = CustomType::<Item>::new()
+///     .with_property("price", |item: &Item| item.price)
+///     .with_method("discounted_price", |item: &Item, discount: f64| item.price * (1.0 - discount));
+///
+/// let mut library = Library::new();
+/// library.add_custom_type(item_type);
+/// assert!(library.contains_function("Item_price"));
+/// assert!(library.contains_function("Item_discounted_price"));
+/// ```
+pub struct CustomType<T: YarnObjectType> {
+    members: Vec<(Cow<'static, str>, Box<dyn UntypedYarnFn>)>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: YarnObjectType> Debug for CustomType<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomType")
+            .field("name", &T::NAME)
+            .field("members", &self.members)
+            .finish()
+    }
+}
+
+impl<T: YarnObjectType> Default for CustomType<T> {
+    fn default() -> Self {
+        Self {
+            members: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: YarnObjectType> CustomType<T> {
+    /// Creates a custom type without any members.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The Yarn [`Type`] of objects of this custom type, e.g. for passing to [`Declaration::new`](https://docs.rs/yarnspinner/latest/yarnspinner/compiler/struct.Declaration.html#method.new).
+    pub fn r#type() -> Type {
+        Type::Custom(Cow::Borrowed(T::NAME))
+    }
+
+    /// Adds a property, i.e. a function that takes a reference to the object as its only parameter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function takes any other parameters.
+    pub fn with_property<Marker, F>(self, name: impl Into<Cow<'static, str>>, function: F) -> Self
+    where
+        Marker: 'static,
+        F: YarnFn<Marker> + 'static + Clone,
+        F::Out: YarnFnOutput + 'static,
+    {
+        let name = name.into();
+        assert_eq!(
+            vec![TypeId::of::<&T>()],
+            function.parameter_types(),
+            "Property {name} of custom type {} must take a reference to the object as its only parameter",
+            T::NAME,
+        );
+        self.with_method(name, function)
+    }
+
+    /// Adds a method, i.e. a function that takes a reference to the object as its first parameter.
+    /// Other parameters of the object's type are type checked as well.
+    pub fn with_method<Marker, F>(mut self, name: impl Into<Cow<'static, str>>, function: F) -> Self
+    where
+        Marker: 'static,
+        F: YarnFn<Marker> + 'static + Clone,
+        F::Out: YarnFnOutput + 'static,
+    {
+        let member = CustomTypeMember {
+            function: Box::new(YarnFnWrapper::from(function)),
+            object_type_ids: [TypeId::of::<T>(), TypeId::of::<&T>()],
+            object_type: Self::r#type(),
+        };
+        self.members.push((name.into(), Box::new(member)));
+        self
+    }
+
+    pub(crate) fn into_functions(
+        self,
+    ) -> impl Iterator<Item = (Cow<'static, str>, Box<dyn UntypedYarnFn>)> {
+        self.members.into_iter().map(|(member_name, function)| {
+            let name = format!("{}{}{member_name}", T::NAME, Library::NAMESPACE_SEPARATOR);
+            (Cow::Owned(name), function)
+        })
+    }
+}
+
+/// A function registered through a [`CustomType`], which knows the Yarn [`Type`] of the object.
+#[derive(Debug, Clone)]
+struct CustomTypeMember {
+    function: Box<dyn UntypedYarnFn>,
+    object_type_ids: [TypeId; 2],
+    object_type: Type,
+}
+
+impl Display for CustomTypeMember {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.function, f)
+    }
+}
+
+impl UntypedYarnFn for CustomTypeMember {
+    fn call(
+        &self,
+        input: Vec<YarnValue>,
+        context: &YarnFnContext,
+    ) -> Result<YarnValue, YarnFnError> {
+        self.function.call(input, context)
+    }
+
+    fn clone_box(&self) -> Box<dyn UntypedYarnFn> {
+        Box::new(self.clone())
+    }
+
+    fn parameter_types(&self) -> Vec<TypeId> {
+        self.function.parameter_types()
+    }
+
+    fn variadic_parameter_type(&self) -> Option<TypeId> {
+        self.function.variadic_parameter_type()
+    }
+
+    fn return_type(&self) -> TypeId {
+        self.function.return_type()
+    }
+
+    fn function_type(&self) -> Result<FunctionType, InvalidDowncastError> {
+        FunctionType::from_type_ids(self, |type_id| {
+            if self.object_type_ids.contains(&type_id) {
+                Ok(self.object_type.clone())
+            } else {
+                Type::try_from(type_id)
+            }
+        })
+    }
+
+    fn is_async(&self) -> bool {
+        self.function.is_async()
+    }
+
+    fn call_async(
+        &self,
+        input: Vec<YarnValue>,
+        context: &YarnFnContext,
+    ) -> Result<YarnValueFuture, YarnFnError> {
+        self.function.call_async(input, context)
+    }
+}
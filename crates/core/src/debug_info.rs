@@ -1,7 +1,6 @@
 //! Adapted from <https://github.com/YarnSpinnerTool/YarnSpinner/blob/da39c7195107d8211f21c263e4084f773b84eaff/YarnSpinner.Compiler/DebugInfo.cs>
 
 use crate::prelude::*;
-use std::collections::HashMap;
 
 /// Contains debug information for a node in a Yarn file.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
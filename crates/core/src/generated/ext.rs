@@ -1,8 +1,10 @@
 //! Contains extensions to generated types that in the original implementation are sprinkled around the repo via partial classes
 
 use crate::prelude::*;
-use std::error::Error;
-use std::fmt::{Debug, Display};
+use core::error::Error;
+use core::fmt::{Debug, Display, Write};
+use core::hash::{Hash, Hasher};
+use prost::Message;
 
 impl From<String> for Operand {
     fn from(s: String) -> Self {
@@ -12,6 +14,19 @@ impl From<String> for Operand {
     }
 }
 
+impl From<f64> for Operand {
+    fn from(f: f64) -> Self {
+        // The original protocol only has 32-bit floats, so only fall back to doubles when a float would change the number.
+        let float = f as f32;
+        let value = if widen_float_operand(float) == f {
+            OperandValue::FloatValue(float)
+        } else {
+            OperandValue::DoubleValue(f)
+        };
+        Self { value: Some(value) }
+    }
+}
+
 impl From<f32> for Operand {
     fn from(f: f32) -> Self {
         Self {
@@ -22,7 +37,7 @@ impl From<f32> for Operand {
 
 impl From<usize> for Operand {
     fn from(f: usize) -> Self {
-        Self::from(f as f32)
+        Self::from(f as f64)
     }
 }
 
@@ -34,6 +49,28 @@ impl From<bool> for Operand {
     }
 }
 
+impl From<YarnList> for Operand {
+    fn from(list: YarnList) -> Self {
+        Self {
+            value: Some(OperandValue::ListValue(OperandList {
+                values: list.into_iter().map(Operand::from).collect(),
+            })),
+        }
+    }
+}
+
+impl From<YarnValue> for Operand {
+    fn from(value: YarnValue) -> Self {
+        match value {
+            YarnValue::Number(f) => f.into(),
+            YarnValue::String(s) => s.into(),
+            YarnValue::Boolean(b) => b.into(),
+            YarnValue::List(values) => YarnList(values).into(),
+            YarnValue::Object(object) => panic!("Cannot store object {object} in a program, as objects only exist at runtime. This is a bug. Please report it at https://github.com/YarnSpinnerTool/YarnSpinner-Rust/issues/new"),
+        }
+    }
+}
+
 impl TryFrom<Operand> for String {
     type Error = ();
 
@@ -45,17 +82,26 @@ impl TryFrom<Operand> for String {
     }
 }
 
-impl TryFrom<Operand> for f32 {
+impl TryFrom<Operand> for f64 {
     type Error = ();
 
     fn try_from(value: Operand) -> Result<Self, Self::Error> {
         match value.value {
-            Some(OperandValue::FloatValue(f)) => Ok(f),
+            Some(OperandValue::FloatValue(f)) => Ok(widen_float_operand(f)),
+            Some(OperandValue::DoubleValue(f)) => Ok(f),
             _ => Err(()),
         }
     }
 }
 
+impl TryFrom<Operand> for f32 {
+    type Error = ();
+
+    fn try_from(value: Operand) -> Result<Self, Self::Error> {
+        f64::try_from(value).map(|f| f as f32)
+    }
+}
+
 impl TryFrom<Operand> for usize {
     type Error = ();
 
@@ -67,6 +113,7 @@ impl TryFrom<Operand> for usize {
             // language differentiates between floats and
             // ints, which it doesn't.
             Some(OperandValue::FloatValue(f)) => Ok(f as usize),
+            Some(OperandValue::DoubleValue(f)) => Ok(f as usize),
             _ => Err(()),
         }
     }
@@ -88,12 +135,49 @@ impl From<Operand> for YarnValue {
         let value = value.value.unwrap();
         match value {
             OperandValue::StringValue(s) => s.into(),
-            OperandValue::FloatValue(f) => f.into(),
+            OperandValue::FloatValue(f) => widen_float_operand(f).into(),
+            OperandValue::DoubleValue(f) => f.into(),
             OperandValue::BoolValue(b) => b.into(),
+            OperandValue::ListValue(list) => {
+                YarnValue::List(list.values.into_iter().map(YarnValue::from).collect())
+            }
         }
     }
 }
 
+/// Widens a 32-bit float operand to the number it was written as, e.g. `0.1` instead of `0.10000000149011612`.
+fn widen_float_operand(f: f32) -> f64 {
+    // The shortest digits that round-trip to the float, formatted on the stack since operands are widened on every read
+    let mut digits = FloatDigits::default();
+    match write!(digits, "{f:e}") {
+        Ok(()) => digits.as_str().parse().unwrap_or(f64::from(f)),
+        Err(_) => f64::from(f),
+    }
+}
+
+/// Holds an `f32` formatted in scientific notation, which takes at most 15 bytes, e.g. `-1.1754942e-38`.
+#[derive(Default)]
+struct FloatDigits {
+    bytes: [u8; 16],
+    len: usize,
+}
+
+impl FloatDigits {
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
+
+impl Write for FloatDigits {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        let bytes = self.bytes.get_mut(self.len..end).ok_or(core::fmt::Error)?;
+        bytes.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "bevy", derive(Reflect))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -107,32 +191,259 @@ pub struct InvalidOpCodeError(pub i32);
 impl Error for InvalidOpCodeError {}
 
 impl Display for InvalidOpCodeError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:?} is not a valid OpCode", self.0)
     }
 }
 
 impl Program {
+    /// The separator between the namespace and the name of a node, see [`Program::with_namespace`].
+    pub const NAMESPACE_SEPARATOR: &'static str = "_";
+
     /// Creates a new Program by merging multiple Programs together.
     ///
     /// The new program will contain every node from every input program.
     /// Returns [`None`] if the input is empty.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the programs conflict, see [`Program::try_combine`] for a non-panicking version.
     pub fn combine(programs: Vec<Program>) -> Option<Self> {
         if programs.is_empty() {
             return None;
         }
+        Some(Self::try_combine(programs).unwrap_or_else(|error| panic!("{error}")))
+    }
+
+    /// Creates a new Program by merging multiple Programs together, failing if they contain nodes with the same name
+    /// or different initial values for the same variable. The error lists all such conflicts at once.
+    ///
+    /// Use [`Program::with_namespace`] to merge programs that reuse node names, e.g. from mods.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use yarnspinner_core::prelude::*;
+    /// let base = ProgramBuilder::new().node(NodeBuilder::new("Start")).build().unwrap();
+    /// let expansion = base.clone();
+    ///
+    /// let error = Program::try_combine([base.clone(), expansion.clone()]).unwrap_err();
+    /// assert_eq!(error.duplicate_nodes, vec!["Start"]);
+    ///
+    /// let combined = Program::try_combine([base, expansion.with_namespace("dlc")]).unwrap();
+    /// assert!(combined.nodes.contains_key("dlc_Start"));
+    /// ```
+    pub fn try_combine(
+        programs: impl IntoIterator<Item = Program>,
+    ) -> Result<Self, ProgramCombineError> {
         let mut output = Program::default();
+        let mut error = ProgramCombineError::default();
         for program in programs {
             for (node_name, node) in program.nodes {
-                assert!(
-                    !output.nodes.contains_key(&node_name),
-                    "This program already contains a node named {node_name}",
-                );
-                output.nodes.insert(node_name, node);
+                if output.nodes.contains_key(&node_name) {
+                    error.duplicate_nodes.push(node_name);
+                } else {
+                    output.nodes.insert(node_name, node);
+                }
+            }
+            for (variable, value) in program.initial_values {
+                match output.initial_values.get(&variable) {
+                    Some(existing_value) if existing_value != &value => {
+                        error.conflicting_initial_values.push(variable);
+                    }
+                    Some(_) => {}
+                    None => {
+                        output.initial_values.insert(variable, value);
+                    }
+                }
             }
-            output.initial_values.extend(program.initial_values);
         }
-        Some(output)
+        if error.duplicate_nodes.is_empty() && error.conflicting_initial_values.is_empty() {
+            Ok(output)
+        } else {
+            error.duplicate_nodes.sort_unstable();
+            error.duplicate_nodes.dedup();
+            error.conflicting_initial_values.sort_unstable();
+            error.conflicting_initial_values.dedup();
+            Err(error)
+        }
+    }
+
+    /// Encodes this program in the binary format of the `.yarnc` files written by the original Yarn Spinner compiler.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.encode_to_vec()
+    }
+
+    /// Decodes a program from the binary format of `.yarnc` files, see [`Program::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProgramDecodeError> {
+        Self::decode(bytes).map_err(|error| ProgramDecodeError(error.to_string()))
+    }
+
+    /// Returns this program with all of its nodes renamed to `<namespace>_<name>`, see [`Program::NAMESPACE_SEPARATOR`].
+    /// This allows loading content that reuses node names alongside each other, e.g. mods or DLC.
+    ///
+    /// Jumps, `visited` and `visited_count` calls with a literal node name and the variables tracking visits are
+    /// renamed as well. Jumps to node names computed at runtime are left as-is.
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        let rename = |name: &str| format!("{namespace}{}{name}", Self::NAMESPACE_SEPARATOR);
+        let node_names: Vec<String> = self.nodes.keys().cloned().collect();
+        let visit_variables: HashMap<String, String> = node_names
+            .iter()
+            .map(|name| {
+                (
+                    Library::generate_unique_visited_variable_for_node(name),
+                    Library::generate_unique_visited_variable_for_node(&rename(name)),
+                )
+            })
+            .collect();
+
+        let nodes = core::mem::take(&mut self.nodes);
+        for (name, mut node) in nodes {
+            node.name = rename(&name);
+            let instruction_count = node.instructions.len();
+            for index in 0..instruction_count {
+                let references_node = references_node_by_name(&node.instructions, index);
+                let instruction = &mut node.instructions[index];
+                let Some(OperandValue::StringValue(operand)) = instruction
+                    .operands
+                    .first_mut()
+                    .and_then(|operand| operand.value.as_mut())
+                else {
+                    continue;
+                };
+                if references_node && node_names.contains(operand) {
+                    *operand = rename(operand);
+                } else if let Some(variable) = visit_variables.get(operand.as_str()) {
+                    *operand = variable.clone();
+                }
+            }
+            self.nodes.insert(node.name.clone(), node);
+        }
+
+        let initial_values = core::mem::take(&mut self.initial_values);
+        self.initial_values = initial_values
+            .into_iter()
+            .map(|(variable, value)| {
+                let variable = visit_variables.get(&variable).cloned().unwrap_or(variable);
+                (variable, value)
+            })
+            .collect();
+        self
+    }
+}
+
+/// Whether the instruction at `index` pushes a node name that is consumed by a jump or a call to `visited` or `visited_count`.
+fn references_node_by_name(instructions: &[Instruction], index: usize) -> bool {
+    let op_code = |offset: usize| {
+        instructions
+            .get(index + offset)
+            .and_then(|instruction| OpCode::try_from(instruction.opcode).ok())
+    };
+    if op_code(0) != Some(OpCode::PushString) {
+        return false;
+    }
+    if op_code(1) == Some(OpCode::RunNode) {
+        return true;
+    }
+    let is_visit_query = instructions.get(index + 2).is_some_and(|instruction| {
+        OpCode::try_from(instruction.opcode).ok() == Some(OpCode::CallFunc)
+            && matches!(
+                instruction.operands.first().and_then(|operand| operand.value.as_ref()),
+                Some(OperandValue::StringValue(function)) if function == "visited" || function == "visited_count"
+            )
+    });
+    op_code(1) == Some(OpCode::PushFloat) && is_visit_query
+}
+
+/// The error returned by [`Program::from_bytes`] when the bytes are not a valid encoded program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramDecodeError(pub String);
+
+impl Error for ProgramDecodeError {}
+
+impl Display for ProgramDecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Failed to decode Yarn program: {}", self.0)
+    }
+}
+
+/// The conflicts found by [`Program::try_combine`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ProgramCombineError {
+    /// The names of nodes that are contained in more than one program.
+    pub duplicate_nodes: Vec<String>,
+    /// The names of variables that have different initial values in different programs.
+    pub conflicting_initial_values: Vec<String>,
+}
+
+impl Error for ProgramCombineError {}
+
+impl Display for ProgramCombineError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Cannot combine programs")?;
+        if !self.duplicate_nodes.is_empty() {
+            write!(
+                f,
+                ", as more than one of them contains the nodes {}",
+                self.duplicate_nodes.join(", ")
+            )?;
+        }
+        if !self.conflicting_initial_values.is_empty() {
+            let conjunction = if self.duplicate_nodes.is_empty() {
+                ","
+            } else {
+                " and"
+            };
+            write!(
+                f,
+                "{conjunction} as they disagree on the initial values of {}",
+                self.conflicting_initial_values.join(", ")
+            )?;
+        }
+        f.write_str(
+            ". Consider using `Program::with_namespace` to load programs that reuse node names.",
+        )
+    }
+}
+
+impl Node {
+    /// Computes a hash over everything that influences how this node executes, i.e. its name, instructions and labels.
+    /// Two nodes with the same content hash can be swapped for one another without invalidating an execution position inside them.
+    ///
+    /// Metadata like tags and headers is not part of the hash.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = Fnv1aHasher::default();
+        self.name.hash(&mut hasher);
+        for instruction in &self.instructions {
+            instruction.encode_to_vec().hash(&mut hasher);
+        }
+        let mut labels: Vec<_> = self.labels.iter().collect();
+        labels.sort_unstable();
+        labels.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// A minimal FNV-1a hasher. Unlike [`std::collections::hash_map::DefaultHasher`], it is available without `std` and
+/// produces the same results across runs and platforms.
+struct Fnv1aHasher(u64);
+
+impl Default for Fnv1aHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1aHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
     }
 }
 
@@ -148,3 +459,50 @@ impl Instruction {
             .unwrap_or_else(|e| panic!("Failed to convert operand {index}: {e:?}",))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_are_float_operands_when_exact() {
+        for number in [3.0, -2.5, 0.1, 1e30] {
+            let operand = Operand::from(number);
+            assert!(matches!(operand.value, Some(OperandValue::FloatValue(_))));
+            assert_eq!(number, f64::try_from(operand).unwrap());
+        }
+    }
+
+    #[test]
+    fn numbers_are_double_operands_when_a_float_loses_precision() {
+        for number in [1_700_000_000.25, 0.1 + 0.2, 1e300] {
+            let operand = Operand::from(number);
+            assert!(matches!(operand.value, Some(OperandValue::DoubleValue(_))));
+            assert_eq!(number, f64::try_from(operand).unwrap());
+        }
+    }
+
+    #[test]
+    fn float_operands_are_widened_to_their_shortest_digits() {
+        for (float, expected) in [
+            (0.1_f32, 0.1),
+            (-1.1754942e-38, -1.1754942e-38),
+            (f32::MAX, 3.4028235e38),
+            (1e-45, 1e-45),
+            (f32::INFINITY, f64::INFINITY),
+        ] {
+            assert_eq!(expected, widen_float_operand(float));
+        }
+        assert!(widen_float_operand(f32::NAN).is_nan());
+    }
+
+    #[test]
+    fn float_operands_are_encoded_like_the_original() {
+        let operand = Operand::from(0.5);
+        // Field 3 with the fixed 32-bit wire type, followed by 0.5 as a little endian float
+        let mut expected = vec![(3 << 3) | 5];
+        expected.extend(0.5_f32.to_le_bytes());
+        assert_eq!(expected, operand.encode_to_vec());
+        assert_eq!(operand, Operand::decode(expected.as_slice()).unwrap());
+    }
+}
@@ -6,4 +6,11 @@ use crate::prelude::*;
 mod ext;
 pub use self::ext::*;
 
+/// The map type used by the generated types. `prost` can only encode [`HashMap`](std::collections::HashMap)s when `std` is available.
+#[cfg(feature = "std")]
+pub type ProtoMap<K, V> = std::collections::HashMap<K, V>;
+/// The map type used by the generated types. `prost` can only encode [`HashMap`](std::collections::HashMap)s when `std` is available.
+#[cfg(not(feature = "std"))]
+pub type ProtoMap<K, V> = alloc::collections::BTreeMap<K, V>;
+
 include!("yarn.rs");
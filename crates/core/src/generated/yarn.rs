@@ -11,13 +11,15 @@ pub struct Program {
     #[prost(string, tag = "1")]
     pub name: ::prost::alloc::string::String,
     /// The collection of nodes in this program.
-    #[prost(map = "string, message", tag = "2")]
-    pub nodes: ::std::collections::HashMap<::prost::alloc::string::String, Node>,
+    #[cfg_attr(feature = "std", prost(map = "string, message", tag = "2"))]
+    #[cfg_attr(not(feature = "std"), prost(btree_map = "string, message", tag = "2"))]
+    pub nodes: ProtoMap<::prost::alloc::string::String, Node>,
     /// The collection of initial values for variables; if a PUSH_VARIABLE
     /// instruction is run, and the value is not found in the storage, this
     /// value will be used
-    #[prost(map = "string, message", tag = "3")]
-    pub initial_values: ::std::collections::HashMap<
+    #[cfg_attr(feature = "std", prost(map = "string, message", tag = "3"))]
+    #[cfg_attr(not(feature = "std"), prost(btree_map = "string, message", tag = "3"))]
+    pub initial_values: ProtoMap<
         ::prost::alloc::string::String,
         Operand,
     >,
@@ -39,8 +41,9 @@ pub struct Node {
     pub instructions: ::prost::alloc::vec::Vec<Instruction>,
     /// A jump table, mapping the names of labels to positions in the
     /// instructions list.
-    #[prost(map = "string, int32", tag = "3")]
-    pub labels: ::std::collections::HashMap<::prost::alloc::string::String, i32>,
+    #[cfg_attr(feature = "std", prost(map = "string, int32", tag = "3"))]
+    #[cfg_attr(not(feature = "std"), prost(btree_map = "string, int32", tag = "3"))]
+    pub labels: ProtoMap<::prost::alloc::string::String, i32>,
     /// The tags associated with this node.
     #[prost(string, repeated, tag = "4")]
     pub tags: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
@@ -231,7 +234,7 @@ use crate::prelude::*;
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Operand {
     /// The type of operand this is.
-    #[prost(oneof = "operand::Value", tags = "1, 2, 3")]
+    #[prost(oneof = "operand::Value", tags = "1, 2, 3, 4, 5")]
     pub value: ::core::option::Option<operand::Value>,
 }
 /// Nested message and enum types in `Operand`.
@@ -257,5 +260,25 @@ pub mod operand {
         /// A floating point number.
         #[prost(float, tag = "3")]
         FloatValue(f32),
+        /// A list of operands.
+        #[prost(message, tag = "4")]
+        ListValue(super::OperandList),
+        /// A floating point number that loses precision as a float_value.
+        #[prost(double, tag = "5")]
+        DoubleValue(f64),
     }
 }
+/// A list of values used by an Operand.
+use crate::prelude::*;
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bevy", derive(Reflect))]
+#[cfg_attr(feature = "bevy", reflect(Debug, PartialEq))]
+#[cfg_attr(all(feature = "bevy", feature = "serde"), reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "bevy", reflect(no_field_bounds))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OperandList {
+    /// The operands contained in this list.
+    #[prost(message, repeated, tag = "1")]
+    pub values: ::prost::alloc::vec::Vec<Operand>,
+}
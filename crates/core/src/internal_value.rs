@@ -11,7 +11,7 @@ use crate::types::{Type, TypedValue as _};
 /// ## Implementation Notes
 ///
 /// Corresponds to the internal `Value` class in the original C# implementation.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "bevy", derive(Reflect))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "bevy", reflect(Debug, PartialEq))]
@@ -26,6 +26,16 @@ pub struct InternalValue {
     pub raw_value: YarnValue,
 }
 
+impl PartialOrd for InternalValue {
+    /// Values are only ordered if they have the same [`Type`], see [`YarnValue`] for the rules.
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        if self.r#type != other.r#type {
+            return None;
+        }
+        self.raw_value.partial_cmp(&other.raw_value)
+    }
+}
+
 macro_rules! impl_from {
     ($($from_type:ty,)*) => {
         $(
@@ -4,8 +4,16 @@
 //! - If you're a game developer, you'll want to use a crate that is already designed for your game engine of choice,
 //!     such as [`bevy_yarnspinner`](https://crates.io/crates/bevy_yarnspinner) for the [Bevy engine](https://bevyengine.org/).
 //! - If you wish to write an adapter crate for an engine yourself, use the [`yarnspinner`](https://crates.io/crates/yarnspinner) crate.
+//!
+//! Disabling the default `std` feature makes this crate `no_std`, in which case it only requires `alloc`.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs, missing_debug_implementations)]
+extern crate alloc;
+
+mod compat;
+mod custom_type;
+mod debug_info;
 mod feature_gates;
 mod generated;
 mod internal_value;
@@ -13,6 +21,7 @@ mod library;
 mod line_id;
 mod operator;
 mod position;
+mod program_builder;
 pub mod types;
 mod yarn_fn;
 mod yarn_value;
@@ -22,16 +31,21 @@ pub mod prelude {
     #[cfg(any(feature = "bevy", feature = "serde"))]
     pub use crate::feature_gates::*;
 
+    pub(crate) use crate::compat::*;
     pub use crate::{
+        custom_type::*,
+        debug_info::*,
         generated::{
             instruction::OpCode, operand::Value as OperandValue, Header, Instruction,
-            InvalidOpCodeError, Node, Operand, Program,
+            InvalidOpCodeError, Node, Operand, OperandList, Program, ProgramCombineError,
+            ProgramDecodeError,
         },
         internal_value::*,
         library::*,
         line_id::*,
         operator::*,
         position::*,
+        program_builder::*,
         types::Type,
         yarn_fn::*,
         yarn_value::*,
@@ -1,9 +1,10 @@
 //! Adapted from <https://github.com/YarnSpinnerTool/YarnSpinner/blob/da39c7195107d8211f21c263e4084f773b84eaff/YarnSpinner/Library.cs>
 
 use crate::prelude::*;
-use std::borrow::Cow;
-use std::collections::hash_map;
-use std::fmt::Display;
+use crate::types::{FunctionType, TypeFormat, TypedValue};
+use alloc::borrow::Cow;
+use core::error::Error;
+use core::fmt::{self, Display};
 
 /// A collection of functions that can be called from Yarn scripts.
 ///
@@ -30,6 +31,13 @@ impl IntoIterator for Library {
 }
 
 impl Library {
+    /// The separator placed between the namespace and the name of a function by [`Library::with_namespace`].
+    ///
+    /// ## Implementation Notes
+    ///
+    /// An underscore is used instead of a dot because Yarn function names cannot contain dots.
+    pub const NAMESPACE_SEPARATOR: &'static str = "_";
+
     /// Creates a new empty library. Does not include the functions of [`Library::standard_library`].
     pub fn new() -> Self {
         Self::default()
@@ -37,7 +45,7 @@ impl Library {
 
     /// Loads functions from another [`Library`].
     ///
-    /// Will overwrite any functions that have the same name.
+    /// Will overwrite any functions that have the same name. Use [`Library::merge`] to handle such conflicts differently.
     ///
     /// ## Implementation Notes
     ///
@@ -46,6 +54,94 @@ impl Library {
         self.0.extend(other.0 .0);
     }
 
+    /// Merges the functions of another [`Library`] into this one,
+    /// resolving functions that exist in both libraries according to the given [`ConflictPolicy`].
+    ///
+    /// With [`ConflictPolicy::Error`], this library is left unchanged if there are any conflicts.
+    pub fn merge(
+        &mut self,
+        other: Self,
+        policy: ConflictPolicy,
+    ) -> Result<&mut Self, LibraryConflictError> {
+        match policy {
+            ConflictPolicy::Error => {
+                let mut function_names: Vec<_> = other
+                    .names()
+                    .filter(|name| self.0.contains_function(name))
+                    .map(ToOwned::to_owned)
+                    .collect();
+                if !function_names.is_empty() {
+                    function_names.sort();
+                    return Err(LibraryConflictError { function_names });
+                }
+                self.0.extend(other.0);
+            }
+            ConflictPolicy::Overwrite => self.0.extend(other.0),
+            ConflictPolicy::Skip => {
+                for (name, function) in other.0 {
+                    if !self.0.contains_function(&name) {
+                        self.0.add_boxed(name, function);
+                    }
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Returns this library with the functions of another [`Library`] merged into it. See [`Library::merge`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use yarnspinner_core::prelude::*;
+    /// let combat = yarn_library! { "damage" => |strength: f32| strength * 2.0, };
+    /// let inventory = yarn_library! { "item_count" => |item: &str| item.len() as f32, };
+    /// let library = Library::new()
+    ///     .with_library(combat, ConflictPolicy::Error)?
+    ///     .with_library(inventory, ConflictPolicy::Error)?;
+    ///
+    /// let duplicate = yarn_library! { "damage" => |strength: f32| strength, };
+    /// assert!(library.with_library(duplicate, ConflictPolicy::Error).is_err());
+    /// # Ok::<(), LibraryConflictError>(())
+    /// ```
+    pub fn with_library(
+        mut self,
+        other: Self,
+        policy: ConflictPolicy,
+    ) -> Result<Self, LibraryConflictError> {
+        self.merge(other, policy)?;
+        Ok(self)
+    }
+
+    /// Returns this library with all of its functions renamed to `<namespace>_<name>`, see [`Library::NAMESPACE_SEPARATOR`].
+    /// This allows multiple plugins to contribute functions with the same name without clobbering each other.
+    ///
+    /// Don't use this on a library containing the [`Library::standard_library`], as the compiler relies on the names of its functions.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use yarnspinner_core::prelude::*;
+    /// let library = yarn_library! { "floor" => f32::floor, }.with_namespace("math");
+    /// assert!(library.contains_function("math_floor"));
+    /// assert!(!library.contains_function("floor"));
+    /// ```
+    pub fn with_namespace(self, namespace: &str) -> Self {
+        let mut library = Self::new();
+        library.0.extend(self.0.into_iter().map(|(name, function)| {
+            let name = format!("{namespace}{}{name}", Self::NAMESPACE_SEPARATOR);
+            (Cow::Owned(name), function)
+        }));
+        library
+    }
+
+    /// Removes the function with the given name from the library, returning it if it was present.
+    ///
+    /// To remove an overload added with [`Library::add_overload`], pass its overload name as returned by [`Library::overloads`].
+    pub fn remove(&mut self, name: &str) -> Option<Box<dyn UntypedYarnFn>> {
+        self.0.remove(name)
+    }
+
     /// Iterates over the names and functions in the library.
     pub fn iter(&self) -> impl Iterator<Item = (&str, &(dyn UntypedYarnFn))> {
         self.0.iter()
@@ -69,14 +165,32 @@ impl Library {
     /// - `string`: Converts a value to a string.
     /// - `number`: Converts a value to a number.
     /// - `bool`: Converts a value to a boolean.
+    /// - `list`: Creates a list out of all of its arguments, e.g. `list("sword", "shield")`.
+    /// - `count`: Returns the number of items in a list.
+    /// - `contains`: Returns whether a list contains a value.
+    /// - `item_at`: Returns the item of a list at a zero-based index. Fails if the index is out of bounds.
+    /// - `append`: Returns a copy of a list with a value added to its end.
+    /// - `join`: Concatenates the items of a list into a string, separated by a given separator.
     /// - Comparison operators for numbers, strings, and booleans. (`==`, `!=`, `<`, `<=`, `>`, `>=`)
+    /// - Equality and concatenation (`+`) operators for lists.
+    ///
+    /// Since Yarn has no syntax for list literals or indexing, `list(a, b)` and `item_at($list, i)` take the place of `[a, b]` and `$list[i]`.
     pub fn standard_library() -> Self {
         let mut library = yarn_library!(
             "string" => <String as From<YarnValue >>::from,
-            "number" => |value: YarnValue| f32::try_from(value).expect("Failed to convert a Yarn value to a number"),
+            "number" => |value: YarnValue| f64::try_from(value).expect("Failed to convert a Yarn value to a number"),
             "bool" => |value: YarnValue| bool::try_from(value).expect("Failed to convert a Yarn value to a bool"),
+            "list" => YarnList,
+            "count" => |list: &YarnList| list.len(),
+            "contains" => |list: &YarnList, value: &YarnValue| list.contains(value),
+            "item_at" => |list: &YarnList, index: usize| list.get(index).cloned().ok_or_else(|| format!("Index {index} is out of bounds for a list of {} items", list.len())),
+            "append" => |mut list: YarnList, value: YarnValue| {
+                list.push(value);
+                list
+            },
+            "join" => |list: &YarnList, separator: &str| list.iter().map(String::from).collect::<Vec<_>>().join(separator),
         );
-        for r#type in [Type::Number, Type::String, Type::Boolean] {
+        for r#type in [Type::Number, Type::String, Type::Boolean, Type::List] {
             library.add_methods(r#type);
         }
         library
@@ -116,15 +230,183 @@ impl Library {
     where
         Marker: 'static,
         F: YarnFn<Marker> + 'static + Clone,
-        F::Out: IntoYarnValueFromNonYarnValue + 'static + Clone,
+        F::Out: YarnFnOutput + 'static,
     {
         self.0.register_function(name, function);
         self
     }
 
-    /// Returns `true` if the library contains a function with the given name.
+    /// Adds a new function along with its documentation and deprecation, see [`YarnFnMetadata`].
+    /// Otherwise the same as [`Library::add_function`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use yarnspinner_core::prelude::*;
+    /// # let mut library = Library::default();
+    /// library.add_function_with_metadata(
+    ///     "old_damage",
+    ///     |strength: f32| strength * 2.0,
+    ///     YarnFnMetadata::new()
+    ///         .with_docs("Calculates the damage dealt by an attack")
+    ///         .with_deprecation("Use `damage` instead"),
+    /// );
+    ///
+    /// let metadata = library.metadata("old_damage").unwrap();
+    /// assert_eq!(Some("Use `damage` instead"), metadata.deprecation.as_deref());
+    /// ```
+    pub fn add_function_with_metadata<Marker, F>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        function: F,
+        metadata: YarnFnMetadata,
+    ) -> &mut Self
+    where
+        Marker: 'static,
+        F: YarnFn<Marker> + 'static + Clone,
+        F::Out: YarnFnOutput + 'static,
+    {
+        let function = Box::new(YarnFnWrapper::from(function));
+        let function = YarnFnWithMetadata::new(function, metadata);
+        self.0.add_boxed(name, Box::new(function));
+        self
+    }
+
+    /// Returns the documentation and deprecation of the function with the given name,
+    /// if it was registered with [`Library::add_function_with_metadata`].
+    pub fn metadata(&self, name: &str) -> Option<&YarnFnMetadata> {
+        self.get(name)?.metadata()
+    }
+
+    /// Adds a new function as an overload of all other functions registered under the same name with [`Library::add_overload`].
+    /// This allows e.g. `random()`, `random(max)` and `random(min, max)` to be distinct functions.
+    ///
+    /// Overloads are registered under a name that includes their parameter types, see [`Library::get_overload_name`].
+    /// Registering an overload with the same Yarn parameter types as an existing one replaces it.
+    ///
+    /// When compiling, the overload to call is chosen by the type checker based on the types of the supplied arguments.
+    /// Calls to functions that could not be resolved at compile time, e.g. in programs compiled without the overloads,
+    /// are dispatched at runtime with [`Library::resolve_overload`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use yarnspinner_core::prelude::*;
+    /// # let mut library = Library::default();
+    /// library
+    ///     .add_overload("clamp", |value: f32| value.clamp(0.0, 1.0))
+    ///     .add_overload("clamp", |value: f32, max: f32| value.clamp(0.0, max))
+    ///     .add_overload("clamp", |value: f32, min: f32, max: f32| value.clamp(min, max));
+    ///
+    /// assert_eq!(3, library.overloads("clamp").count());
+    /// ```
+    pub fn add_overload<Marker, F>(&mut self, name: impl AsRef<str>, function: F) -> &mut Self
+    where
+        Marker: 'static,
+        F: YarnFn<Marker> + 'static + Clone,
+        F::Out: YarnFnOutput + 'static,
+    {
+        let function: Box<dyn UntypedYarnFn> = Box::new(YarnFnWrapper::from(function));
+        let function_type = FunctionType::try_from(function.as_ref()).unwrap_or_else(|e| {
+            panic!(
+                "Failed to get Yarn types for overload of function {}: {e:?}",
+                name.as_ref()
+            )
+        });
+        let overload_name = Self::get_overload_name(name.as_ref(), &function_type);
+        self.0.add_boxed(overload_name, function);
+        self
+    }
+
+    /// Iterates over the names and functions of all overloads registered for the given name with [`Library::add_overload`],
+    /// ordered by their overload name.
+    pub fn overloads<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a dyn UntypedYarnFn)> + 'a {
+        let mut overloads: Vec<_> = self
+            .0
+            .iter()
+            .filter(|(overload_name, _)| Self::get_overload_base_name(overload_name) == Some(name))
+            .collect();
+        overloads.sort_by_key(|(overload_name, _)| *overload_name);
+        overloads.into_iter()
+    }
+
+    /// Picks the overload of the function with the given name that should be called with the given arguments.
+    /// Returns the name and the function of the overload, or [`None`] if no overload accepts the arguments.
+    ///
+    /// See [`Library::select_overload`] for how the overload is chosen.
+    pub fn resolve_overload(
+        &self,
+        name: &str,
+        arguments: &[YarnValue],
+    ) -> Option<(&str, &dyn UntypedYarnFn)> {
+        let argument_types: Vec<_> = arguments.iter().map(|value| Some(value.r#type())).collect();
+        let candidates: Vec<_> = self
+            .overloads(name)
+            .filter_map(|(overload_name, function)| {
+                let function_type = FunctionType::try_from(function).ok()?;
+                Some((overload_name, function_type))
+            })
+            .collect();
+        let overload_name = Self::select_overload(
+            candidates
+                .iter()
+                .map(|(overload_name, function_type)| (*overload_name, function_type)),
+            &argument_types,
+        )?
+        .to_owned();
+        self.iter().find(|(name, _)| *name == overload_name)
+    }
+
+    /// Picks the overload that should be called with arguments of the given types out of the given overload names and signatures.
+    ///
+    /// The choice is deterministic: the overload with the lowest [`FunctionType::get_overload_match_cost`] wins,
+    /// i.e. parameters that exactly match the argument types are preferred over parameters of type [`Type::Any`].
+    /// Ties are broken by picking the overload whose name comes first alphabetically.
+    pub fn select_overload<'a>(
+        candidates: impl IntoIterator<Item = (&'a str, &'a FunctionType)>,
+        argument_types: &[Option<Type>],
+    ) -> Option<&'a str> {
+        candidates
+            .into_iter()
+            .filter_map(|(overload_name, function_type)| {
+                let cost = function_type.get_overload_match_cost(argument_types)?;
+                Some((cost, overload_name))
+            })
+            .min()
+            .map(|(_cost, overload_name)| overload_name)
+    }
+
+    /// Returns the name an overload of the function `name` with the given signature is registered under,
+    /// e.g. `random(Number, Number)`.
+    pub fn get_overload_name(name: &str, function_type: &FunctionType) -> String {
+        let variadic_parameter = function_type
+            .variadic_parameter_type
+            .as_ref()
+            .as_ref()
+            .map(|r#type| format!("...{}", r#type.format()));
+        let parameters = function_type
+            .parameters
+            .iter()
+            .map(TypeFormat::format)
+            .chain(variadic_parameter)
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{name}({parameters})")
+    }
+
+    /// Returns the name of the function that the given overload name (as created by [`Library::get_overload_name`]) belongs to,
+    /// or [`None`] if the name is not an overload name.
+    pub fn get_overload_base_name(overload_name: &str) -> Option<&str> {
+        let (base_name, _parameters) = overload_name.strip_suffix(')')?.split_once('(')?;
+        Some(base_name)
+    }
+
+    /// Returns `true` if the library contains a function with the given name, including overloads added with [`Library::add_overload`].
     pub fn contains_function(&self, name: &str) -> bool {
-        self.0.contains_function(name)
+        self.0.contains_function(name) || self.overloads(name).next().is_some()
     }
 
     /// Iterates over the names of all functions in the library.
@@ -137,6 +419,19 @@ impl Library {
         self.0.functions()
     }
 
+    /// Adds the properties and methods of a [`CustomType`], named `<type>_<member>`. See [`CustomType`] for an example.
+    pub fn add_custom_type<T: YarnObjectType>(&mut self, custom_type: CustomType<T>) -> &mut Self {
+        self.0.extend(custom_type.into_functions());
+        self
+    }
+
+    /// Adds a function annotated with [`#[yarn_fn]`](yarn_fn) under its [`YarnFnDefinition::NAME`].
+    /// Use [`add_module!`] to add several of them at once.
+    pub fn add_yarn_fn<T: YarnFnDefinition>(&mut self) -> &mut Self {
+        T::register(self);
+        self
+    }
+
     /// Registers the methods found inside a type.
     fn add_methods(&mut self, r#type: Type) {
         for (name, function) in r#type.methods().into_iter() {
@@ -147,7 +442,7 @@ impl Library {
 }
 
 impl Display for Library {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut functions: Vec<_> = self.0.iter().collect();
         functions.sort_by_key(|(name, _)| name.to_string());
         writeln!(f, "{{")?;
@@ -159,6 +454,37 @@ impl Display for Library {
     }
 }
 
+/// Determines what [`Library::merge`] does with functions that are present in both libraries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ConflictPolicy {
+    /// Fail with a [`LibraryConflictError`] listing the conflicting functions.
+    #[default]
+    Error,
+    /// Replace the existing function with the new one.
+    Overwrite,
+    /// Keep the existing function and ignore the new one.
+    Skip,
+}
+
+/// The error returned by [`Library::merge`] with [`ConflictPolicy::Error`] when both libraries contain functions with the same name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibraryConflictError {
+    /// The names of the functions present in both libraries, sorted alphabetically.
+    pub function_names: Vec<String>,
+}
+
+impl Error for LibraryConflictError {}
+
+impl Display for LibraryConflictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Cannot merge libraries because both contain the functions {}",
+            self.function_names.join(", ")
+        )
+    }
+}
+
 /// Create a [`Library`] from a list of named functions.
 ///
 /// ## Example
@@ -188,3 +514,46 @@ macro_rules! yarn_library {
     };
 }
 pub use yarn_library;
+
+/// Adds functions annotated with [`#[yarn_fn]`](yarn_fn) to a [`Library`], optionally under a namespace as in [`Library::with_namespace`].
+///
+/// ## Example
+///
+/// ```rust
+/// # use yarnspinner_core::prelude::*;
+/// mod math {
+/// #   use yarnspinner_core::prelude::*;
+///     #[yarn_fn(crate = yarnspinner_core::prelude)]
+///     pub fn square(x: f64) -> f64 {
+///         x * x
+///     }
+///
+///     #[yarn_fn(crate = yarnspinner_core::prelude, name = "half")]
+///     pub fn halve(x: f64) -> f64 {
+///         x / 2.0
+///     }
+/// }
+///
+/// let mut library = Library::new();
+/// add_module!(library, [math::square, math::halve]);
+/// add_module!(library, "math", [math::square]);
+/// assert!(library.contains_function("square"));
+/// assert!(library.contains_function("half"));
+/// assert!(library.contains_function("math_square"));
+/// ```
+#[macro_export]
+macro_rules! add_module {
+    ($library:expr, [$($function:path),* $(,)?]) => {
+        $(
+            $library.add_yarn_fn::<$function>();
+        )*
+    };
+    ($library:expr, $namespace:expr, [$($function:path),* $(,)?]) => {
+        {
+            let mut module = $crate::prelude::Library::new();
+            $crate::add_module!(module, [$($function),*]);
+            $library.import(module.with_namespace($namespace));
+        }
+    };
+}
+pub use add_module;
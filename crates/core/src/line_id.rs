@@ -1,13 +1,12 @@
-#[cfg(any(feature = "bevy", feature = "serde"))]
 use crate::prelude::*;
-use std::fmt::Display;
+use core::fmt::Display;
 
 /// The unique ID of a line in a Yarn script. In a Yarn script, line IDs look like this:
 /// ```text
 /// Darth Vader: I am your father! #line:123
 /// Luke: Noooooo #line:nooooo
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "bevy", derive(Reflect))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "bevy", reflect(Debug, PartialEq, Hash))]
@@ -33,7 +32,7 @@ impl AsRef<str> for LineId {
 }
 
 impl Display for LineId {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.0.fmt(f)
     }
 }
@@ -0,0 +1,466 @@
+//! Not part of the original implementation, where [`Program`]s are only ever produced by the compiler.
+
+use crate::prelude::*;
+use crate::types::TypedValue;
+use core::error::Error;
+use core::fmt::{self, Display, Formatter};
+
+/// Builds a [`Program`] directly from Rust code instead of compiling it from Yarn source,
+/// e.g. for procedurally generated dialogue or for testing dialogue views.
+///
+/// The builder makes sure that node names are unique, that every jump targets an existing label and
+/// that only values that can be stored in a [`Program`] are used.
+///
+/// Note that a [`Program`] does not contain the text of its lines, only their [`LineId`]s. Provide the text
+/// through the dialogue's `TextProvider` as usual.
+///
+/// ## Example
+///
+/// ```rust
+/// # use yarnspinner_core::prelude::*;
+/// let program = ProgramBuilder::new()
+///     .with_name("generated")
+///     .initial_value("$gold", 0)
+///     .node(
+///         NodeBuilder::new("Start")
+///             .line("line:greeting")
+///             .options(|options| {
+///                 options
+///                     .option("line:buy", |branch| branch.set_variable("$gold", 10).line("line:thanks"))
+///                     .option("line:leave", |branch| branch.jump_to_node("Farewell"))
+///             }),
+///     )
+///     .node(NodeBuilder::new("Farewell").line("line:bye"))
+///     .build()
+///     .unwrap();
+/// assert_eq!(program.nodes.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ProgramBuilder {
+    name: String,
+    nodes: Vec<NodeBuilder>,
+    initial_values: Vec<(String, YarnValue)>,
+}
+
+impl ProgramBuilder {
+    /// Creates a builder for an empty, unnamed [`Program`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name of the program.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Adds a node to the program.
+    pub fn node(mut self, node: NodeBuilder) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    /// Sets the value a variable has if the variable storage does not contain it yet, like `<<declare>>` does in Yarn.
+    pub fn initial_value(
+        mut self,
+        variable: impl Into<String>,
+        value: impl Into<YarnValue>,
+    ) -> Self {
+        self.initial_values.push((variable.into(), value.into()));
+        self
+    }
+
+    /// Builds the [`Program`], failing on the first node that is invalid.
+    pub fn build(self) -> Result<Program, ProgramBuildError> {
+        let mut program = Program {
+            name: self.name,
+            ..Default::default()
+        };
+        for node in self.nodes {
+            let node = node.build()?;
+            if program.nodes.contains_key(&node.name) {
+                return Err(ProgramBuildError::DuplicateNode(node.name));
+            }
+            program.nodes.insert(node.name.clone(), node);
+        }
+        for (variable, value) in self.initial_values {
+            if let YarnValue::Object(_) = value {
+                return Err(ProgramBuildError::UnsupportedValue {
+                    location: variable,
+                    value,
+                });
+            }
+            program.initial_values.insert(variable, value.into());
+        }
+        Ok(program)
+    }
+}
+
+/// Builds a single [`Node`] for a [`ProgramBuilder`] by appending instructions to it.
+///
+/// A `<<stop>>` is appended when building unless the node already ends by stopping or jumping to another node.
+#[derive(Debug, Clone, Default)]
+pub struct NodeBuilder {
+    node: Node,
+    jump_targets: Vec<String>,
+    next_generated_label: usize,
+    error: Option<ProgramBuildError>,
+}
+
+impl NodeBuilder {
+    /// Creates a builder for an empty node with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            node: Node {
+                name: name.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Adds a tag to the node, like the `tags` header does in Yarn.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.node.tags.push(tag.into());
+        self
+    }
+
+    /// Adds a header to the node.
+    pub fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.node.headers.push(Header {
+            key: key.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Appends a raw instruction. Prefer the specific methods, which also keep track of the labels the instruction uses.
+    pub fn instruction(mut self, instruction: Instruction) -> Self {
+        self.node.instructions.push(instruction);
+        self
+    }
+
+    /// Marks the position of the next instruction with a label, which can then be jumped to with [`NodeBuilder::jump_to`].
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        let label = label.into();
+        let position = self.node.instructions.len() as i32;
+        if self.node.labels.insert(label.clone(), position).is_some() {
+            self.fail(ProgramBuildError::DuplicateLabel {
+                node: self.node.name.clone(),
+                label,
+            });
+        }
+        self
+    }
+
+    /// Continues execution at the given label.
+    pub fn jump_to(mut self, label: impl Into<String>) -> Self {
+        let label = label.into();
+        self.jump_targets.push(label.clone());
+        self.emit(OpCode::JumpTo, [label.into()])
+    }
+
+    /// Continues execution at the given label if the value on top of the stack is `false`.
+    /// The value is left on the stack, so follow up with [`NodeBuilder::pop`] on both paths.
+    pub fn jump_if_false(mut self, label: impl Into<String>) -> Self {
+        let label = label.into();
+        self.jump_targets.push(label.clone());
+        self.emit(OpCode::JumpIfFalse, [label.into()])
+    }
+
+    /// Runs the line with the given ID.
+    pub fn line(self, line_id: impl Into<LineId>) -> Self {
+        self.line_with_substitutions(line_id, 0)
+    }
+
+    /// Runs the line with the given ID, substituting its `{0}`, `{1}`, etc. with the given number of values popped from the stack.
+    pub fn line_with_substitutions(self, line_id: impl Into<LineId>, substitutions: usize) -> Self {
+        let line_id: LineId = line_id.into();
+        self.emit(OpCode::RunLine, [line_id.0.into(), substitutions.into()])
+    }
+
+    /// Runs the given command, e.g. `"wait 2"`.
+    pub fn command(self, command: impl Into<String>) -> Self {
+        self.command_with_substitutions(command, 0)
+    }
+
+    /// Runs the given command, substituting its `{0}`, `{1}`, etc. with the given number of values popped from the stack.
+    pub fn command_with_substitutions(
+        self,
+        command: impl Into<String>,
+        substitutions: usize,
+    ) -> Self {
+        self.emit(
+            OpCode::RunCommand,
+            [command.into().into(), substitutions.into()],
+        )
+    }
+
+    /// Pushes a number, string or boolean onto the stack.
+    pub fn push(mut self, value: impl Into<YarnValue>) -> Self {
+        let value = value.into();
+        match value {
+            YarnValue::Number(number) => self.emit(OpCode::PushFloat, [number.into()]),
+            YarnValue::String(string) => self.emit(OpCode::PushString, [string.into()]),
+            YarnValue::Boolean(boolean) => self.emit(OpCode::PushBool, [boolean.into()]),
+            YarnValue::List(_) | YarnValue::Object(_) => {
+                let location = self.node.name.clone();
+                self.fail(ProgramBuildError::UnsupportedValue { location, value });
+                self
+            }
+        }
+    }
+
+    /// Pushes the value of the given variable onto the stack.
+    pub fn push_variable(self, variable: impl Into<String>) -> Self {
+        self.emit(OpCode::PushVariable, [variable.into().into()])
+    }
+
+    /// Stores the value on top of the stack in the given variable. The value is left on the stack.
+    pub fn store_variable(self, variable: impl Into<String>) -> Self {
+        self.emit(OpCode::StoreVariable, [variable.into().into()])
+    }
+
+    /// Sets the given variable to a number, string or boolean, like `<<set>>` does in Yarn.
+    pub fn set_variable(self, variable: impl Into<String>, value: impl Into<YarnValue>) -> Self {
+        self.push(value).store_variable(variable).pop()
+    }
+
+    /// Calls a function of the [`Library`] with the given number of arguments popped from the stack and pushes its result.
+    pub fn call_function(self, name: impl Into<String>, argument_count: usize) -> Self {
+        self.emit(OpCode::PushFloat, [argument_count.into()])
+            .emit(OpCode::CallFunc, [name.into().into()])
+    }
+
+    /// Discards the value on top of the stack.
+    pub fn pop(self) -> Self {
+        self.emit(OpCode::Pop, [])
+    }
+
+    /// Runs the given node, like `<<jump>>` does in Yarn.
+    pub fn jump_to_node(self, node: impl Into<String>) -> Self {
+        self.emit(OpCode::PushString, [node.into().into()])
+            .emit(OpCode::RunNode, [])
+    }
+
+    /// Stops the dialogue, like `<<stop>>` does in Yarn.
+    pub fn stop(self) -> Self {
+        self.emit(OpCode::Stop, [])
+    }
+
+    /// Presents options to the player and continues with the branch of the selected one.
+    /// After the branch, execution continues after the options.
+    pub fn options(mut self, options: impl FnOnce(OptionsBuilder) -> OptionsBuilder) -> Self {
+        let options = options(OptionsBuilder {
+            options: Vec::new(),
+            node_name: self.node.name.clone(),
+            next_generated_label: self.next_generated_label,
+        });
+        self.next_generated_label = options.next_generated_label;
+
+        let mut destinations = Vec::new();
+        for option in &options.options {
+            let destination = self.generate_label("option");
+            if let Some(condition) = &option.condition {
+                self = self.push_variable(condition.clone());
+            }
+            let line_id = option.line_id.0.clone();
+            let has_condition = option.condition.is_some();
+            self = self.emit(
+                OpCode::AddOption,
+                [
+                    line_id.into(),
+                    destination.clone().into(),
+                    0_usize.into(),
+                    has_condition.into(),
+                ],
+            );
+            destinations.push(destination);
+        }
+        self = self.emit(OpCode::ShowOptions, []).emit(OpCode::Jump, []);
+
+        let end = self.generate_label("options_end");
+        for (option, destination) in options.options.into_iter().zip(destinations) {
+            self = self.label(destination).append(option.branch);
+            self = self.jump_to(end.clone());
+        }
+        self.label(end).pop()
+    }
+
+    /// Builds the [`Node`].
+    pub fn build(mut self) -> Result<Node, ProgramBuildError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        if let Some(label) = self
+            .jump_targets
+            .into_iter()
+            .find(|label| !self.node.labels.contains_key(label))
+        {
+            return Err(ProgramBuildError::UnknownLabel {
+                node: self.node.name,
+                label,
+            });
+        }
+        let ends_node = self.node.instructions.last().is_some_and(|instruction| {
+            [OpCode::Stop as i32, OpCode::RunNode as i32].contains(&instruction.opcode)
+        });
+        if !ends_node {
+            self.node.instructions.push(Instruction {
+                opcode: OpCode::Stop.into(),
+                operands: Vec::new(),
+            });
+        }
+        Ok(self.node)
+    }
+
+    fn emit(mut self, op_code: OpCode, operands: impl IntoIterator<Item = Operand>) -> Self {
+        self.node.instructions.push(Instruction {
+            opcode: op_code.into(),
+            operands: operands.into_iter().collect(),
+        });
+        self
+    }
+
+    fn fail(&mut self, error: ProgramBuildError) {
+        self.error.get_or_insert(error);
+    }
+
+    fn generate_label(&mut self, purpose: &str) -> String {
+        let label = format!("L{}{purpose}", self.next_generated_label);
+        self.next_generated_label += 1;
+        label
+    }
+
+    /// Appends the instructions of a branch built for this node, moving its labels along.
+    fn append(mut self, branch: NodeBuilder) -> Self {
+        if let Some(error) = branch.error {
+            self.fail(error);
+        }
+        let offset = self.node.instructions.len() as i32;
+        for (label, position) in branch.node.labels {
+            if self
+                .node
+                .labels
+                .insert(label.clone(), position + offset)
+                .is_some()
+            {
+                self.fail(ProgramBuildError::DuplicateLabel {
+                    node: self.node.name.clone(),
+                    label,
+                });
+            }
+        }
+        self.node.instructions.extend(branch.node.instructions);
+        self.jump_targets.extend(branch.jump_targets);
+        self
+    }
+}
+
+/// Collects the options presented by [`NodeBuilder::options`].
+#[derive(Debug, Clone)]
+pub struct OptionsBuilder {
+    options: Vec<OptionBranch>,
+    node_name: String,
+    next_generated_label: usize,
+}
+
+#[derive(Debug, Clone)]
+struct OptionBranch {
+    line_id: LineId,
+    condition: Option<String>,
+    branch: NodeBuilder,
+}
+
+impl OptionsBuilder {
+    /// Adds an option showing the line with the given ID. The `branch` appends the instructions that run when the option is selected.
+    pub fn option(
+        self,
+        line_id: impl Into<LineId>,
+        branch: impl FnOnce(NodeBuilder) -> NodeBuilder,
+    ) -> Self {
+        self.add(line_id.into(), None, branch)
+    }
+
+    /// Adds an option that is only available if the given boolean variable is `true`, like `-> Option <<if $condition>>` in Yarn.
+    pub fn conditional_option(
+        self,
+        line_id: impl Into<LineId>,
+        condition_variable: impl Into<String>,
+        branch: impl FnOnce(NodeBuilder) -> NodeBuilder,
+    ) -> Self {
+        self.add(line_id.into(), Some(condition_variable.into()), branch)
+    }
+
+    fn add(
+        mut self,
+        line_id: LineId,
+        condition: Option<String>,
+        branch: impl FnOnce(NodeBuilder) -> NodeBuilder,
+    ) -> Self {
+        let mut builder = NodeBuilder::new(self.node_name.clone());
+        builder.next_generated_label = self.next_generated_label;
+        let branch = branch(builder);
+        self.next_generated_label = branch.next_generated_label;
+        self.options.push(OptionBranch {
+            line_id,
+            condition,
+            branch,
+        });
+        self
+    }
+}
+
+/// An error returned by [`ProgramBuilder::build`] and [`NodeBuilder::build`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgramBuildError {
+    /// Two nodes have the same name.
+    DuplicateNode(String),
+    /// A label was defined twice in the same node.
+    DuplicateLabel {
+        /// The name of the node containing the label.
+        node: String,
+        /// The duplicated label.
+        label: String,
+    },
+    /// A jump targets a label that is not defined in the same node.
+    UnknownLabel {
+        /// The name of the node containing the jump.
+        node: String,
+        /// The missing label.
+        label: String,
+    },
+    /// The value cannot be stored in a [`Program`] at the given location, e.g. because it is a [`YarnValue::Object`].
+    UnsupportedValue {
+        /// The name of the node or variable the value was used for.
+        location: String,
+        /// The rejected value.
+        value: YarnValue,
+    },
+}
+
+impl Error for ProgramBuildError {}
+
+impl Display for ProgramBuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateNode(node) => {
+                write!(f, "The program already contains a node named {node}")
+            }
+            Self::DuplicateLabel { node, label } => {
+                write!(f, "Node {node} defines the label {label} more than once")
+            }
+            Self::UnknownLabel { node, label } => {
+                write!(
+                    f,
+                    "Node {node} jumps to the label {label}, which it does not define"
+                )
+            }
+            Self::UnsupportedValue { location, value } => write!(
+                f,
+                "{value} of type {} cannot be stored in a program, but was used in {location}",
+                value.r#type()
+            ),
+        }
+    }
+}
@@ -6,7 +6,9 @@ pub use {function::*, r#type::*, type_util::*};
 
 mod any;
 mod boolean;
+mod custom;
 mod function;
+mod list;
 mod number;
 mod string;
 mod r#type;
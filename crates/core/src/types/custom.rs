@@ -0,0 +1,9 @@
+//! Not part of the original Yarn Spinner, which has no custom types.
+
+use crate::prelude::*;
+use crate::types::TypeProperties;
+
+/// A type that bridges to a [`YarnObjectType`](crate::prelude::YarnObjectType)
+pub(crate) fn custom_type_properties(name: &str) -> TypeProperties {
+    TypeProperties::from_name("Custom").with_description(format!("The custom type {name}."))
+}
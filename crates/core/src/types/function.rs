@@ -1,9 +1,9 @@
 //! Adapted from <https://github.com/YarnSpinnerTool/YarnSpinner/blob/da39c7195107d8211f21c263e4084f773b84eaff/YarnSpinner/Types/FunctionType.cs>
-#[cfg(any(feature = "bevy", feature = "serde"))]
 use crate::prelude::*;
 use crate::types::TypeProperties;
-use crate::types::{Type, TypeFormat};
-use std::fmt::Display;
+use crate::types::{InvalidDowncastError, SubTypeOf, Type, TypeFormat};
+use core::any::TypeId;
+use core::fmt::Display;
 
 pub(crate) fn function_type_properties(function_type: &FunctionType) -> TypeProperties {
     TypeProperties::from_name("Function").with_description(function_type.to_string())
@@ -30,6 +30,11 @@ pub struct FunctionType {
     /// (also known as the function's *arity*).
     pub parameters: Vec<Option<Type>>,
 
+    #[cfg_attr(feature = "bevy", reflect(ignore))]
+    /// The type of the arguments that may follow the [`FunctionType::parameters`] in any number, if the function is variadic.
+    // Needs to be on the heap because of type recursion
+    pub variadic_parameter_type: Box<Option<Type>>,
+
     #[cfg_attr(feature = "bevy", reflect(ignore))]
     ///The type of value that this function returns.
     // Needs to be on the heap because of type recursion
@@ -54,14 +59,87 @@ impl FunctionType {
         self.parameters.push(parameter.into());
         self
     }
+
+    /// Sets the type of the variadic parameter of this function signature
+    pub fn set_variadic_parameter_type(
+        &mut self,
+        variadic_parameter_type: impl Into<Option<Type>>,
+    ) -> &mut Self {
+        *self.variadic_parameter_type = variadic_parameter_type.into();
+        self
+    }
+
+    /// Returns how well a call with arguments of the given types matches this function signature,
+    /// or [`None`] if the arguments are not accepted at all.
+    ///
+    /// Lower costs are better matches. The cost is the number of arguments that are only accepted
+    /// because the corresponding parameter is of type [`Type::Any`].
+    pub fn get_overload_match_cost(&self, argument_types: &[Option<Type>]) -> Option<usize> {
+        let has_valid_arity = if self.variadic_parameter_type.is_some() {
+            argument_types.len() >= self.parameters.len()
+        } else {
+            argument_types.len() == self.parameters.len()
+        };
+        if !has_valid_arity {
+            return None;
+        }
+        let parameter_types = self
+            .parameters
+            .iter()
+            .chain(core::iter::repeat(self.variadic_parameter_type.as_ref()));
+        let mut cost = 0;
+        for (argument_type, parameter_type) in argument_types.iter().zip(parameter_types) {
+            if !argument_type.is_sub_type_of(parameter_type) {
+                return None;
+            }
+            if argument_type != parameter_type {
+                cost += 1;
+            }
+        }
+        Some(cost)
+    }
+}
+
+impl FunctionType {
+    /// Builds the signature of a function by converting the [`TypeId`]s of its parameters and return type with the given function.
+    pub(crate) fn from_type_ids(
+        function: &(impl UntypedYarnFn + ?Sized),
+        to_type: impl Fn(TypeId) -> Result<Type, InvalidDowncastError>,
+    ) -> Result<Self, InvalidDowncastError> {
+        let mut function_type = FunctionType::default();
+        for parameter_type in function.parameter_types() {
+            function_type.add_parameter(to_type(parameter_type)?);
+        }
+        let variadic_parameter_type = function
+            .variadic_parameter_type()
+            .map(&to_type)
+            .transpose()?;
+        function_type.set_variadic_parameter_type(variadic_parameter_type);
+        function_type.set_return_type(to_type(function.return_type())?);
+        Ok(function_type)
+    }
+}
+
+impl TryFrom<&dyn UntypedYarnFn> for FunctionType {
+    type Error = InvalidDowncastError;
+
+    fn try_from(function: &dyn UntypedYarnFn) -> Result<Self, Self::Error> {
+        function.function_type()
+    }
 }
 
 impl Display for FunctionType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let variadic_parameter = self
+            .variadic_parameter_type
+            .as_ref()
+            .as_ref()
+            .map(|r#type| format!("...{}", r#type.format()));
         let parameters = self
             .parameters
             .iter()
             .map(TypeFormat::format)
+            .chain(variadic_parameter)
             .collect::<Vec<_>>()
             .join(", ");
         let return_type = self.return_type.as_ref().format();
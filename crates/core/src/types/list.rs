@@ -0,0 +1,24 @@
+//! Not part of the original Yarn Spinner, which has no list type.
+//!
+//! Yarn scripts create lists with the `list` function and read their items with `item_at`, see [`Library::standard_library`].
+//! There is no literal syntax like `[a, b]` and no indexing syntax like `$list[i]`, since both would require changing
+//! the grammar the parser is generated from, which is taken as-is from the original Yarn Spinner.
+
+use crate::prelude::*;
+use crate::types::TypeProperties;
+
+/// A type that bridges to [`YarnList`]
+pub(crate) fn list_type_properties() -> TypeProperties {
+    TypeProperties::from_name("List")
+        .with_description("A list of values of any type.")
+        .with_methods(yarn_library! {
+            Operator::EqualTo => <RustType as PartialEq>::eq,
+            Operator::NotEqualTo => <RustType as PartialEq>::ne,
+            Operator::Add => |mut a: RustType, b: RustType| {
+                a.extend(b);
+                a
+            },
+        })
+}
+
+type RustType = YarnList;
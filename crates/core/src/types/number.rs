@@ -2,9 +2,9 @@
 
 use crate::prelude::*;
 use crate::types::TypeProperties;
-use std::ops::*;
+use core::ops::*;
 
-/// A type that bridges to [`f32`]
+/// A type that bridges to [`f64`]
 pub(crate) fn number_type_properties() -> TypeProperties {
     TypeProperties::from_name("Number").with_methods(yarn_library! {
         Operator::EqualTo => <RustType as PartialEq>::eq,
@@ -22,4 +22,4 @@ pub(crate) fn number_type_properties() -> TypeProperties {
     })
 }
 
-type RustType = f32;
+type RustType = f64;
@@ -1,12 +1,15 @@
 use crate::prelude::*;
 use crate::types::any::any_type_properties;
 use crate::types::boolean::boolean_type_properties;
+use crate::types::custom::custom_type_properties;
+use crate::types::list::list_type_properties;
 use crate::types::number::number_type_properties;
 use crate::types::string::string_type_properties;
 use crate::types::*;
-use std::any::TypeId;
-use std::error::Error;
-use std::fmt::{Debug, Display};
+use alloc::borrow::Cow;
+use core::any::TypeId;
+use core::error::Error;
+use core::fmt::{Debug, Display};
 
 /// All types in the virtual machine, both built-in, i.e. usable in Yarn scripts, and internal.
 ///
@@ -36,8 +39,12 @@ pub enum Type {
     Any,
     /// The type representing booleans
     Boolean,
+    /// The type representing objects of a [`CustomType`] with the given name
+    Custom(Cow<'static, str>),
     /// The type representing functions
     Function(FunctionType),
+    /// The type representing lists of values of any type
+    List,
     /// The type representing numbers
     Number,
     /// The type representing strings
@@ -45,7 +52,7 @@ pub enum Type {
 }
 
 impl Display for Type {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let name = self.name();
         match self {
             Type::Function(function) => Display::fmt(function, f),
@@ -78,8 +85,12 @@ impl TypeFormat for Type {
 
 impl Type {
     /// Returns the name of this type.
-    pub fn name(&self) -> &'static str {
-        self.properties().name
+    pub fn name(&self) -> &str {
+        match self {
+            // Custom types are named by the user, so their name is not known statically.
+            Type::Custom(name) => name,
+            _ => self.properties().name,
+        }
     }
 
     /// Returns a more verbose description of this type.
@@ -96,7 +107,9 @@ impl Type {
         match self {
             Type::Any => any_type_properties(),
             Type::Boolean => boolean_type_properties(),
+            Type::Custom(name) => custom_type_properties(name),
             Type::Function(function_type) => function_type_properties(function_type),
+            Type::List => list_type_properties(),
             Type::Number => number_type_properties(),
             Type::String => string_type_properties(),
         }
@@ -122,6 +135,7 @@ impl Type {
         Type::Number,
         Type::String,
         Type::Boolean,
+        Type::List,
         // Functions are not explicitly constructable
     ];
 }
@@ -191,6 +205,7 @@ impl_typed_value! {
     [f32, f64, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, usize, isize] => Type::Number,
     [String, str] => Type::String,
     [bool] => Type::Boolean,
+    [YarnList] => Type::List,
 }
 
 macro_rules! type_ids {
@@ -209,6 +224,7 @@ impl TryFrom<TypeId> for Type {
         let string_types = type_ids![String, &str];
         let bool_types = type_ids![bool];
         let value_types = type_ids![YarnValue];
+        let list_types = type_ids![YarnList];
         let number_types =
             type_ids![f32, f64, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, usize, isize,];
 
@@ -216,6 +232,7 @@ impl TryFrom<TypeId> for Type {
             (string_types, Type::String),
             (bool_types, Type::Boolean),
             (number_types, Type::Number),
+            (list_types, Type::List),
             (value_types, Type::Any),
         ]
         .into_iter()
@@ -230,6 +247,8 @@ impl TypedValue for YarnValue {
             YarnValue::Number(_) => Type::Number,
             YarnValue::String(_) => Type::String,
             YarnValue::Boolean(_) => Type::Boolean,
+            YarnValue::List(_) => Type::List,
+            YarnValue::Object(object) => Type::Custom(Cow::Borrowed(object.type_name())),
         }
     }
 }
@@ -244,7 +263,7 @@ pub enum InvalidDowncastError {
 impl Error for InvalidDowncastError {}
 
 impl Display for InvalidDowncastError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             InvalidDowncastError::InvalidTypeId(id) => {
                 write!(f, "Cannot convert TypeId {id:?} to a Yarn Spinner `Type`")
@@ -2,10 +2,19 @@
 //! Inspired by how Bevy stores [`FnSystem`](https://docs.rs/bevy_ecs/0.10.1/bevy_ecs/system/struct.FnSystem.html)s.
 //! This is all here just to emulate the `Dictionary<string, Delegate>` used in Yarn Spinner's `Library` class.
 
+mod context;
+mod definition;
 mod function_registry;
 mod function_wrapping;
+mod metadata;
 pub mod optionality;
+mod output;
 mod parameter_wrapping;
 
 pub(crate) use function_registry::*;
-pub use {function_wrapping::*, parameter_wrapping::*};
+pub(crate) use metadata::YarnFnWithMetadata;
+pub use yarnspinner_macros::yarn_fn;
+pub use {
+    context::*, definition::*, function_wrapping::*, metadata::YarnFnMetadata, output::*,
+    parameter_wrapping::*,
+};
@@ -0,0 +1,99 @@
+//! Not part of the original implementation, where functions that need to know about the dialogue capture it when they are registered.
+//! Inspired by how Bevy systems request access to the world through their parameters.
+
+use super::optionality::Required;
+use crate::prelude::*;
+use core::fmt::Debug;
+
+/// Information about the dialogue that is calling a [`YarnFn`].
+///
+/// A [`YarnFn`] receives it by taking a `&YarnFnContext` parameter. This parameter does not correspond to an argument
+/// in Yarn scripts and is thus not part of the function's Yarn signature. By convention, it is the first parameter.
+///
+/// When a function is called outside of a running dialogue, all of the information is absent.
+///
+/// ## Example
+///
+/// ```rust
+/// # use yarnspinner_core::prelude::*;
+/// fn greet(context: &YarnFnContext, name: &str) -> String {
+///     match context.language_code() {
+///         Some("de-CH") => format!("Grüezi, {name}!"),
+///         _ => format!("Hello, {name}!"),
+///     }
+/// }
+///
+/// let mut library = Library::new();
+/// library.add_function("greet", greet);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct YarnFnContext<'a> {
+    node_name: Option<&'a str>,
+    language_code: Option<&'a str>,
+    variables: Option<&'a dyn VariableAccess>,
+}
+
+impl<'a> YarnFnContext<'a> {
+    /// Creates a context without any information about a dialogue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name of the node that is currently running.
+    pub fn with_node_name(mut self, node_name: impl Into<Option<&'a str>>) -> Self {
+        self.node_name = node_name.into();
+        self
+    }
+
+    /// Sets the IETF BCP 47 code of the language the dialogue is currently running in.
+    pub fn with_language_code(mut self, language_code: impl Into<Option<&'a str>>) -> Self {
+        self.language_code = language_code.into();
+        self
+    }
+
+    /// Sets the variables of the dialogue.
+    pub fn with_variables(mut self, variables: &'a dyn VariableAccess) -> Self {
+        self.variables = Some(variables);
+        self
+    }
+
+    /// The name of the node that is currently running, if any.
+    pub fn node_name(&self) -> Option<&'a str> {
+        self.node_name
+    }
+
+    /// The IETF BCP 47 code of the language the dialogue is currently running in, if one was set.
+    pub fn language_code(&self) -> Option<&'a str> {
+        self.language_code
+    }
+
+    /// Returns the current value of the variable with the given name, e.g. `"$gold"`.
+    /// Returns [`None`] if the variable is not set or the context has no access to variables.
+    pub fn variable(&self, name: &str) -> Option<YarnValue> {
+        self.variables
+            .and_then(|variables| variables.variable(name))
+    }
+}
+
+/// Read access to the variables of a dialogue, which is handed to [`YarnFn`]s through a [`YarnFnContext`].
+/// Implemented by the runtime's `VariableStorage`.
+pub trait VariableAccess: Debug {
+    /// Returns the current value of the variable with the given name, if it is set.
+    fn variable(&self, name: &str) -> Option<YarnValue>;
+}
+
+impl YarnFnParam for &YarnFnContext<'_> {
+    type Item<'new> = &'new YarnFnContext<'new>;
+    type Optionality = Required;
+
+    fn retrieve<'a>(
+        _iter: &mut YarnValueWrapperIter<'a>,
+        context: &'a YarnFnContext<'a>,
+    ) -> Result<Self::Item<'a>, YarnFnError> {
+        Ok(context)
+    }
+
+    fn is_context() -> bool {
+        true
+    }
+}
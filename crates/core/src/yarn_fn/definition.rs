@@ -0,0 +1,38 @@
+//! Not part of the original implementation. Backs the `#[yarn_fn]` attribute, which saves writing out the registration of every function by hand.
+
+use crate::prelude::*;
+
+/// A function annotated with [`#[yarn_fn]`](yarn_fn), which implements this trait for a struct of the same name.
+/// Register it with [`Library::add_yarn_fn`] or, together with other functions, with [`add_module!`].
+///
+/// ## Example
+///
+/// ```rust
+/// # use yarnspinner_core::prelude::*;
+/// /// Raises `base` to the power of `exponent`.
+/// #[yarn_fn(crate = yarnspinner_core::prelude)]
+/// fn pow(base: f64, exponent: i32) -> f64 {
+///     base.powi(exponent)
+/// }
+///
+/// assert_eq!("pow", pow::NAME);
+/// assert_eq!(&["base", "exponent"], pow::PARAMETER_NAMES);
+/// assert_eq!(Some("Raises `base` to the power of `exponent`."), pow::DOCS);
+///
+/// let mut library = Library::new();
+/// library.add_yarn_fn::<pow>();
+/// assert!(library.contains_function("pow"));
+/// assert_eq!(Some("Raises `base` to the power of `exponent`."), library.metadata("pow").unwrap().docs.as_deref());
+/// ```
+pub trait YarnFnDefinition {
+    /// The name under which the function is called from Yarn.
+    const NAME: &'static str;
+    /// The names of the function's parameters, excluding [`YarnFnContext`] parameters.
+    const PARAMETER_NAMES: &'static [&'static str];
+    /// The doc comment of the function, if it has one.
+    const DOCS: Option<&'static str>;
+    /// The deprecation message given by `#[yarn_fn(deprecated = "...")]`, if any.
+    const DEPRECATION: Option<&'static str>;
+    /// Adds the function to the library under [`YarnFnDefinition::NAME`], along with its [`YarnFnMetadata`].
+    fn register(library: &mut Library);
+}
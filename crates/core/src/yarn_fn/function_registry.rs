@@ -1,6 +1,5 @@
 use crate::prelude::*;
-use std::borrow::Cow;
-use std::collections::HashMap;
+use alloc::borrow::Cow;
 
 /// A registry of functions that can be called from Yarn after they have been added via [`YarnFnRegistry::register_function`].
 ///
@@ -39,7 +38,7 @@ impl YarnFnRegistry {
     where
         Marker: 'static,
         F: YarnFn<Marker> + 'static + Clone,
-        F::Out: IntoYarnValueFromNonYarnValue + 'static + Clone,
+        F::Out: YarnFnOutput + 'static,
     {
         let name = name.into();
         let wrapped = YarnFnWrapper::from(function);
@@ -64,6 +63,11 @@ impl YarnFnRegistry {
         self
     }
 
+    /// Removes the function with the given name from the registry, returning it if it was present.
+    pub(crate) fn remove(&mut self, name: &str) -> Option<Box<dyn UntypedYarnFn>> {
+        self.0.remove(name)
+    }
+
     /// Returns `true` if the registry contains a function with the given name.
     pub(crate) fn contains_function(&self, name: &str) -> bool {
         self.get(name).is_some()
@@ -104,7 +108,11 @@ mod tests {
 
         functions.register_function("test", || true);
         let function = functions.get("test").unwrap();
-        let result: bool = function.call(vec![]).try_into().unwrap();
+        let result: bool = function
+            .call(vec![], &YarnFnContext::default())
+            .unwrap()
+            .try_into()
+            .unwrap();
 
         assert!(result);
     }
@@ -115,7 +123,11 @@ mod tests {
 
         functions.register_function("test", |a: f32| a);
         let function = functions.get("test").unwrap();
-        let result: f32 = function.call(to_function_params([1.0])).try_into().unwrap();
+        let result: f32 = function
+            .call(to_function_params([1.0]), &YarnFnContext::default())
+            .unwrap()
+            .try_into()
+            .unwrap();
 
         assert_eq!(result, 1.0);
     }
@@ -137,9 +149,14 @@ mod tests {
         let function1 = functions.get("test1").unwrap();
         let function2 = functions.get("test2").unwrap();
 
-        let result1: bool = function1.call(vec![]).try_into().unwrap();
+        let result1: bool = function1
+            .call(vec![], &YarnFnContext::default())
+            .unwrap()
+            .try_into()
+            .unwrap();
         let result2: f32 = function2
-            .call(to_function_params([1.0]))
+            .call(to_function_params([1.0]), &YarnFnContext::default())
+            .unwrap()
             .try_into()
             .unwrap();
 
@@ -164,23 +181,36 @@ mod tests {
         let function3 = functions.get("test3").unwrap();
         let function4 = functions.get("test4").unwrap();
 
-        let result1: bool = function1.call(vec![]).try_into().unwrap();
+        let result1: bool = function1
+            .call(vec![], &YarnFnContext::default())
+            .unwrap()
+            .try_into()
+            .unwrap();
         let result2: f32 = function2
-            .call(to_function_params([1.0, 2.0]))
+            .call(to_function_params([1.0, 2.0]), &YarnFnContext::default())
+            .unwrap()
             .try_into()
             .unwrap();
         let result3: f32 = function3
-            .call(to_function_params([1.0, 2.0, 3.0]))
+            .call(
+                to_function_params([1.0, 2.0, 3.0]),
+                &YarnFnContext::default(),
+            )
+            .unwrap()
             .try_into()
             .unwrap();
         let result4: String = function4
-            .call(to_function_params([
-                YarnValue::from("a"),
-                "b".into(),
-                "c".into(),
-                true.into(),
-                1.0.into(),
-            ]))
+            .call(
+                to_function_params([
+                    YarnValue::from("a"),
+                    "b".into(),
+                    "c".into(),
+                    true.into(),
+                    1.0.into(),
+                ]),
+                &YarnFnContext::default(),
+            )
+            .unwrap()
             .into();
 
         assert!(result1);
@@ -189,6 +219,18 @@ mod tests {
         assert_eq!(result4, "abctrue1".to_string());
     }
 
+    #[test]
+    fn can_remove_fn() {
+        let mut functions = YarnFnRegistry::default();
+        functions.register_function("test1", || true);
+        functions.register_function("test2", |a: f32| a);
+
+        assert!(functions.remove("test1").is_some());
+        assert!(functions.remove("test1").is_none());
+        assert!(!functions.contains_function("test1"));
+        assert!(functions.contains_function("test2"));
+    }
+
     fn to_function_params(
         params: impl IntoIterator<Item = impl Into<YarnValue>>,
     ) -> Vec<YarnValue> {
@@ -1,8 +1,10 @@
 use super::optionality::AllowedOptionalityChain;
 use crate::prelude::*;
-use std::any::TypeId;
-use std::fmt::{Debug, Display, Formatter};
-use std::marker::PhantomData;
+use crate::types::{FunctionType, InvalidDowncastError};
+use core::any::TypeId;
+use core::fmt::{Debug, Display, Formatter};
+use core::future::Future;
+use core::marker::PhantomData;
 use yarnspinner_macros::all_tuples;
 
 /// A function that can be registered into and called from Yarn.
@@ -12,13 +14,27 @@ use yarnspinner_macros::all_tuples;
 ///   - [`bool`]
 ///   - A numeric type, i.e. one of [`f32`], [`f64`], [`i8`], [`i16`], [`i32`], [`i64`], [`i128`], [`u8`], [`u16`], [`u32`], [`u64`], [`u128`], [`usize`], [`isize`]
 ///   - [`String`] (for a reference, [`&str`] may be used instead of `&String`)
+///   - [`YarnList`], i.e. a list of [`YarnValue`]s
 ///   - [`YarnValue`], which means that a parameter may be any of the above types
 ///   - Tuples of the above types.
+///   - A reference to a [`YarnObjectType`]. Such functions need to be registered as members of a [`CustomType`],
+///     otherwise compiling a program with them fails with a diagnostic.
+/// - It may additionally take a reference to a [`YarnFnContext`], which gives access to the calling dialogue
+///   and is not passed from Yarn.
+/// - Its last parameter may be a [`Vec`] of the above types except tuples, which makes the function variadic,
+///   i.e. callable from Yarn with any number of trailing arguments of that type.
 /// - It must return a value.
 /// - Its return type must be one of the following types:
 ///   - [`bool`]
 ///   - A numeric type, i.e. one of [`f32`], [`f64`], [`i8`], [`i16`], [`i32`], [`i64`], [`i128`], [`u8`], [`u16`], [`u32`], [`u64`], [`u128`], [`usize`], [`isize`]
 ///   - [`String`]
+///   - [`YarnList`]
+///   - A [`YarnObjectType`]
+///   - [`YarnValue`], in which case the return type is only known at runtime
+///   - A [`Result`] of the above types with an error type that implements [`Display`].
+///     Returning an [`Err`] makes the dialogue fail with a runtime error naming the function instead of panicking.
+///   - A [`Future`] resolving to one of the above types, e.g. when the function is `async`.
+///     Calling such a function makes the dialogue wait until the host has driven the future to completion.
 ///
 /// Note that in particular, no references can be returned.
 /// ## Examples
@@ -36,14 +52,22 @@ use yarnspinner_macros::all_tuples;
 /// ```
 pub trait YarnFn<Marker>: Clone + Send + Sync {
     /// The type of the value returned by this function. See [`YarnFn`] for more information about what is allowed.
-    type Out: IntoYarnValueFromNonYarnValue + 'static;
+    type Out: YarnFnOutput + 'static;
     #[doc(hidden)]
-    fn call(&self, input: Vec<YarnValue>) -> Self::Out;
-    /// The [`TypeId`]s of the parameters of this function.
+    fn call(
+        &self,
+        input: Vec<YarnValue>,
+        context: &YarnFnContext,
+    ) -> Result<Self::Out, YarnFnError>;
+    /// The [`TypeId`]s of the parameters of this function, excluding the variadic parameter and [`YarnFnContext`] parameters.
     fn parameter_types(&self) -> Vec<TypeId>;
+    /// The [`TypeId`] of the elements of the variadic parameter of this function, if it has one.
+    fn variadic_parameter_type(&self) -> Option<TypeId> {
+        None
+    }
     /// The [`TypeId`] of the return type of this function.
     fn return_type(&self) -> TypeId {
-        TypeId::of::<Self::Out>()
+        TypeId::of::<<Self::Out as YarnFnOutput>::Value>()
     }
 }
 
@@ -51,13 +75,43 @@ pub trait YarnFn<Marker>: Clone + Send + Sync {
 /// See its documentation for more information about what kind of functions are allowed.
 pub trait UntypedYarnFn: Debug + Display + Send + Sync {
     #[doc(hidden)]
-    fn call(&self, input: Vec<YarnValue>) -> YarnValue;
+    fn call(
+        &self,
+        input: Vec<YarnValue>,
+        context: &YarnFnContext,
+    ) -> Result<YarnValue, YarnFnError>;
     #[doc(hidden)]
     fn clone_box(&self) -> Box<dyn UntypedYarnFn>;
-    /// The [`TypeId`]s of the parameters of this function.
+    /// The [`TypeId`]s of the parameters of this function, excluding the variadic parameter and [`YarnFnContext`] parameters.
     fn parameter_types(&self) -> Vec<TypeId>;
+    /// The [`TypeId`] of the elements of the variadic parameter of this function, if it has one.
+    fn variadic_parameter_type(&self) -> Option<TypeId> {
+        None
+    }
     /// The [`TypeId`] of the return type of this function.
     fn return_type(&self) -> TypeId;
+    /// The Yarn signature of this function, derived from its [`TypeId`]s by default.
+    /// Overridden by functions whose [`TypeId`]s do not map to a [`Type`] on their own, such as the members of a [`CustomType`].
+    fn function_type(&self) -> Result<FunctionType, InvalidDowncastError> {
+        FunctionType::from_type_ids(self, Type::try_from)
+    }
+    /// The documentation and deprecation of this function, if it was registered with [`Library::add_function_with_metadata`].
+    fn metadata(&self) -> Option<&YarnFnMetadata> {
+        None
+    }
+    /// Whether this function returns a future that needs to be awaited, see [`YarnFn`].
+    fn is_async(&self) -> bool {
+        false
+    }
+    #[doc(hidden)]
+    fn call_async(
+        &self,
+        input: Vec<YarnValue>,
+        context: &YarnFnContext,
+    ) -> Result<YarnValueFuture, YarnFnError> {
+        let value = self.call(input, context)?;
+        Ok(Box::pin(core::future::ready(Ok(value))))
+    }
 }
 
 impl Clone for Box<dyn UntypedYarnFn> {
@@ -70,11 +124,14 @@ impl<Marker, F> UntypedYarnFn for YarnFnWrapper<Marker, F>
 where
     Marker: 'static,
     F: YarnFn<Marker> + 'static + Clone,
-    F::Out: IntoYarnValueFromNonYarnValue + 'static + Clone,
+    F::Out: YarnFnOutput + 'static,
 {
-    fn call(&self, input: Vec<YarnValue>) -> YarnValue {
-        let output = self.function.call(input);
-        output.into_yarn_value()
+    fn call(
+        &self,
+        input: Vec<YarnValue>,
+        context: &YarnFnContext,
+    ) -> Result<YarnValue, YarnFnError> {
+        self.function.call(input, context)?.into_yarn_fn_result()
     }
 
     fn clone_box(&self) -> Box<dyn UntypedYarnFn> {
@@ -85,9 +142,25 @@ where
         self.function.parameter_types()
     }
 
+    fn variadic_parameter_type(&self) -> Option<TypeId> {
+        self.function.variadic_parameter_type()
+    }
+
     fn return_type(&self) -> TypeId {
         self.function.return_type()
     }
+
+    fn is_async(&self) -> bool {
+        <F::Out as YarnFnOutput>::IS_ASYNC
+    }
+
+    fn call_async(
+        &self,
+        input: Vec<YarnValue>,
+        context: &YarnFnContext,
+    ) -> Result<YarnValueFuture, YarnFnError> {
+        Ok(self.function.call(input, context)?.into_yarn_fn_future())
+    }
 }
 
 pub(crate) struct YarnFnWrapper<Marker, F>
@@ -128,9 +201,9 @@ impl<Marker, F> Debug for YarnFnWrapper<Marker, F>
 where
     F: YarnFn<Marker>,
 {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let signature = std::any::type_name::<Marker>();
-        let function_path = std::any::type_name::<F>();
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let signature = core::any::type_name::<Marker>();
+        let function_path = core::any::type_name::<F>();
         let debug_message = format!("{signature} {{{function_path}}}");
         f.debug_struct(&debug_message).finish()
     }
@@ -140,8 +213,8 @@ impl<Marker, F> Display for YarnFnWrapper<Marker, F>
 where
     F: YarnFn<Marker>,
 {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let signature = std::any::type_name::<Marker>();
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let signature = core::any::type_name::<Marker>();
         f.write_str(signature)
     }
 }
@@ -169,23 +242,38 @@ macro_rules! yarn_fn_type {
 }
 pub use yarn_fn_type;
 
+/// The `Marker` of async [`YarnFn`]s, which distinguishes them from functions returning a value directly.
+#[derive(Debug)]
+pub struct AsyncYarnFnMarker<T>(PhantomData<T>);
+
 /// Adapted from <https://github.com/bevyengine/bevy/blob/fe852fd0adbce6856f5886d66d20d62cfc936287/crates/bevy_ecs/src/system/system_param.rs#L1370>
 macro_rules! impl_yarn_fn_tuple {
     ($($param: ident),*) => {
+        impl_yarn_fn_tuple!(@impl
+            [fn($($param,)*) -> O] [O] [O: YarnFnOutput + 'static,] [core::convert::identity]
+            $($param),*
+        );
+        impl_yarn_fn_tuple!(@impl
+            [AsyncYarnFnMarker<fn($($param,)*) -> O>] [YarnFnFuture<O>]
+            [O: Future + Send + 'static, O::Output: YarnFnOutput,] [YarnFnFuture]
+            $($param),*
+        );
+    };
+    (@impl [$marker:ty] [$out:ty] [$($output_bounds:tt)*] [$wrap_output:expr] $($param: ident),*) => {
         #[allow(non_snake_case)]
-        impl<F, O, $($param,)*> YarnFn<fn($($param,)*) -> O> for F
+        impl<F, O, $($param,)*> YarnFn<$marker> for F
             where
             for<'a> F:
                 Send + Sync + Clone +
                 Fn($($param,)*) -> O +
                 Fn($(<$param as YarnFnParam>::Item<'a>,)*) -> O,
-            O: IntoYarnValueFromNonYarnValue + 'static,
+            $($output_bounds)*
             $($param: YarnFnParam + 'static,)*
             ($(<$param as YarnFnParam>::Optionality,)*): AllowedOptionalityChain,
             {
-                type Out = O;
-                #[allow(non_snake_case)]
-                fn call(&self, input: Vec<YarnValue>) -> Self::Out {
+                type Out = $out;
+                #[allow(non_snake_case, unused_variables)] // for n = 0 tuples
+                fn call(&self, input: Vec<YarnValue>, context: &YarnFnContext) -> Result<Self::Out, YarnFnError> {
                     let mut params: Vec<_> = input.into_iter().map(YarnValueWrapper::from).collect();
 
                     #[allow(unused_variables, unused_mut)] // for n = 0 tuples
@@ -193,16 +281,32 @@ macro_rules! impl_yarn_fn_tuple {
 
                     // $param is the type implementing YarnFnParam
                     let input = (
-                        $($param::retrieve(&mut iter),)*
+                        $($param::retrieve(&mut iter, context)?,)*
                     );
-                    assert!(iter.next().is_none(), "Passed too many arguments to YarnFn");
+                    if iter.next().is_some() {
+                        return Err(YarnFnError::InvalidArguments {
+                            message: "Passed too many arguments to YarnFn".to_owned(),
+                        });
+                    }
 
                     let ($($param,)*) = input;
-                    self($($param,)*)
+                    Ok($wrap_output(self($($param,)*)))
                 }
 
                 fn parameter_types(&self) -> Vec<TypeId> {
-                    vec![$(TypeId::of::<$param>()),*]
+                    let parameter_types: Vec<(TypeId, bool)> = vec![$((
+                        TypeId::of::<$param>(),
+                        $param::variadic_type().is_none() && !$param::is_context(),
+                    )),*];
+                    parameter_types
+                        .into_iter()
+                        .filter_map(|(type_id, is_argument)| is_argument.then_some(type_id))
+                        .collect()
+                }
+
+                fn variadic_parameter_type(&self) -> Option<TypeId> {
+                    let variadic_types: Vec<Option<TypeId>> = vec![$($param::variadic_type()),*];
+                    variadic_types.into_iter().flatten().last()
                 }
             }
     };
@@ -357,13 +461,99 @@ mod tests {
         accept_yarn_fn(f);
     }
 
+    #[test]
+    fn accepts_result() {
+        fn f(divisor: f32) -> Result<f32, String> {
+            if divisor == 0.0 {
+                Err("Division by zero".to_owned())
+            } else {
+                Ok(1.0 / divisor)
+            }
+        }
+        accept_yarn_fn(f);
+        assert_eq!(TypeId::of::<f32>(), f.return_type());
+        assert_eq!(Ok(0.5), apply_yarn_fn(f, vec![2.0.into()]));
+        assert_eq!(
+            Err(YarnFnError::Failed {
+                message: "Division by zero".to_owned()
+            }),
+            YarnFnWrapper::from(f).call(vec![0.0.into()], &YarnFnContext::default())
+        );
+    }
+
+    #[test]
+    fn returns_error_on_invalid_arguments() {
+        fn f(_: bool) -> bool {
+            true
+        }
+        assert!(matches!(
+            YarnFnWrapper::from(f).call(vec!["not a bool".into()], &YarnFnContext::default()),
+            Err(YarnFnError::InvalidArguments { .. })
+        ));
+        assert!(matches!(
+            YarnFnWrapper::from(f).call(vec![], &YarnFnContext::default()),
+            Err(YarnFnError::InvalidArguments { .. })
+        ));
+        assert!(matches!(
+            YarnFnWrapper::from(f).call(vec![true.into(), true.into()], &YarnFnContext::default()),
+            Err(YarnFnError::InvalidArguments { .. })
+        ));
+    }
+
+    #[test]
+    fn accepts_variadic() {
+        fn f(first: f32, rest: Vec<f32>) -> f32 {
+            rest.into_iter().fold(first, f32::max)
+        }
+        accept_yarn_fn(f);
+        assert_eq!(vec![TypeId::of::<f32>()], f.parameter_types());
+        assert_eq!(Some(TypeId::of::<f32>()), f.variadic_parameter_type());
+        assert_eq!(1.0, apply_yarn_fn(f, vec![1.0.into()]));
+        assert_eq!(
+            3.0,
+            apply_yarn_fn(f, vec![1.0.into(), 3.0.into(), 2.0.into()])
+        );
+    }
+
+    #[test]
+    fn accepts_async() {
+        async fn f(a: f32) -> Result<f32, String> {
+            Ok(a * 2.0)
+        }
+        accept_yarn_fn(f);
+        let function = YarnFnWrapper::from(f);
+        assert!(function.is_async());
+        assert_eq!(TypeId::of::<f32>(), UntypedYarnFn::return_type(&function));
+        assert!(!YarnFnWrapper::from(|a: f32| a).is_async());
+    }
+
+    #[test]
+    fn accepts_context() {
+        fn f(context: &YarnFnContext, greeting: &str) -> String {
+            format!(
+                "{greeting} from {}",
+                context.node_name().unwrap_or("nowhere")
+            )
+        }
+        accept_yarn_fn(f);
+        assert_eq!(vec![TypeId::of::<&str>()], f.parameter_types());
+        assert_eq!(
+            Ok(YarnValue::from("Hi from Start")),
+            YarnFnWrapper::from(f).call(
+                vec!["Hi".into()],
+                &YarnFnContext::new().with_node_name("Start")
+            )
+        );
+        assert_eq!("Hi from nowhere", apply_yarn_fn(f, vec!["Hi".into()]));
+    }
+
     fn accept_yarn_fn<Marker>(_: impl YarnFn<Marker>) {}
 
     fn apply_yarn_fn<T, Marker>(f: T, input: Vec<YarnValue>) -> T::Out
     where
         T: YarnFn<Marker>,
     {
-        f.call(input)
+        f.call(input, &YarnFnContext::default()).unwrap()
     }
 
     mod optionality {
@@ -395,5 +585,10 @@ mod tests {
         assert_is_yarn_fn! { (((), (), ()), ((), Option<()>), (Option<()>, Option<()>)) -> bool }
         assert_is_yarn_fn! { ((), ((), ((), ((), Option<()>)))) -> bool }
         assert_is_not_yarn_fn! { ((), ((), ((), ((), Option<()>))), ()) -> bool }
+
+        assert_is_yarn_fn! { (Vec<()>) -> bool }
+        assert_is_yarn_fn! { ((), Option<()>, Vec<()>) -> bool }
+        assert_is_not_yarn_fn! { (Vec<()>, ()) -> bool }
+        assert_is_not_yarn_fn! { (Vec<()>, Option<()>) -> bool }
     }
 }
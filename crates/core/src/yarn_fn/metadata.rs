@@ -0,0 +1,101 @@
+//! Not part of the original implementation, where functions cannot be documented or deprecated.
+
+use crate::prelude::*;
+use crate::types::{FunctionType, InvalidDowncastError};
+use alloc::borrow::Cow;
+use core::any::TypeId;
+use core::fmt::{self, Display, Formatter};
+
+/// Documentation and deprecation of a function in a [`Library`], see [`Library::add_function_with_metadata`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct YarnFnMetadata {
+    /// Describes what the function does, e.g. for showing in the completions of an editor.
+    /// Passed on to the compiler as the description of the function's declaration.
+    pub docs: Option<Cow<'static, str>>,
+    /// If set, the function is deprecated and the compiler emits a warning with this message for every call to it.
+    pub deprecation: Option<Cow<'static, str>>,
+}
+
+impl YarnFnMetadata {
+    /// Creates metadata without docs that does not deprecate the function.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets [`YarnFnMetadata::docs`].
+    pub fn with_docs(mut self, docs: impl Into<Cow<'static, str>>) -> Self {
+        self.docs = Some(docs.into());
+        self
+    }
+
+    /// Sets [`YarnFnMetadata::deprecation`], e.g. to a message naming the function to use instead.
+    pub fn with_deprecation(mut self, message: impl Into<Cow<'static, str>>) -> Self {
+        self.deprecation = Some(message.into());
+        self
+    }
+}
+
+/// A function that was registered along with its [`YarnFnMetadata`].
+#[derive(Debug, Clone)]
+pub(crate) struct YarnFnWithMetadata {
+    function: Box<dyn UntypedYarnFn>,
+    metadata: YarnFnMetadata,
+}
+
+impl YarnFnWithMetadata {
+    pub(crate) fn new(function: Box<dyn UntypedYarnFn>, metadata: YarnFnMetadata) -> Self {
+        Self { function, metadata }
+    }
+}
+
+impl Display for YarnFnWithMetadata {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.function, f)
+    }
+}
+
+impl UntypedYarnFn for YarnFnWithMetadata {
+    fn call(
+        &self,
+        input: Vec<YarnValue>,
+        context: &YarnFnContext,
+    ) -> Result<YarnValue, YarnFnError> {
+        self.function.call(input, context)
+    }
+
+    fn clone_box(&self) -> Box<dyn UntypedYarnFn> {
+        Box::new(self.clone())
+    }
+
+    fn parameter_types(&self) -> Vec<TypeId> {
+        self.function.parameter_types()
+    }
+
+    fn variadic_parameter_type(&self) -> Option<TypeId> {
+        self.function.variadic_parameter_type()
+    }
+
+    fn return_type(&self) -> TypeId {
+        self.function.return_type()
+    }
+
+    fn function_type(&self) -> Result<FunctionType, InvalidDowncastError> {
+        self.function.function_type()
+    }
+
+    fn metadata(&self) -> Option<&YarnFnMetadata> {
+        Some(&self.metadata)
+    }
+
+    fn is_async(&self) -> bool {
+        self.function.is_async()
+    }
+
+    fn call_async(
+        &self,
+        input: Vec<YarnValue>,
+        context: &YarnFnContext,
+    ) -> Result<YarnValueFuture, YarnFnError> {
+        self.function.call_async(input, context)
+    }
+}
@@ -1,5 +1,5 @@
-//! Marker traits for [`super::YarnFnParam`] to determine if the type is [`Required`] or
-//! [`Optional`].
+//! Marker traits for [`super::YarnFnParam`] to determine if the type is [`Required`],
+//! [`Optional`] or [`Variadic`].
 #![allow(missing_debug_implementations)]
 
 use yarnspinner_macros::all_tuples;
@@ -20,6 +20,13 @@ pub struct Required;
 impl private::Sealed for Required {}
 impl Optionality for Required {}
 
+/// A parameter that takes all remaining arguments, i.e. a [`Vec`],
+/// or a tuple where the last element is variadic.
+pub struct Variadic;
+
+impl private::Sealed for Variadic {}
+impl Optionality for Variadic {}
+
 mod private {
     /// Used to seal [`AllowedOptionalityChain`] so the type can be exported,
     /// but not implemented.
@@ -27,8 +34,8 @@ mod private {
 }
 
 /// A valid chain of optionality hints
-/// i.e. a chain where no optional element follows
-/// a required element.
+/// i.e. a chain where no required element follows
+/// an optional element and nothing follows a variadic element.
 pub trait AllowedOptionalityChain: private::Sealed {
     /// The optionality hint of the last element in the chain.
     type Last: Optionality;
@@ -54,8 +61,14 @@ impl AllowedOptionalityChain for (Optional, Optional) {
     type Last = Optional;
 }
 
+impl private::Sealed for (Optional, Variadic) {}
+impl AllowedOptionalityChain for (Optional, Variadic) {
+    type Last = Variadic;
+}
+
 // `impl AllowedOptionalityChain for (Optional, Required) {}`
-// is intentionally missing (that's the whole point of this trait).
+// and `impl<O: Optionality> AllowedOptionalityChain for (Variadic, O) {}`
+// are intentionally missing (that's the whole point of this trait).
 
 macro_rules! impl_chain {
     // Implementations for zero, one and two-element tuples covered manually.
@@ -0,0 +1,101 @@
+//! Not part of the original implementation. Allows [`YarnFn`]s to fail gracefully instead of panicking.
+
+use crate::prelude::*;
+use core::error::Error;
+use core::fmt::{self, Display};
+use core::future::Future;
+use core::pin::Pin;
+
+/// The future returned by calling an async [`UntypedYarnFn`], resolving to the value returned to Yarn.
+pub type YarnValueFuture = Pin<Box<dyn Future<Output = Result<YarnValue, YarnFnError>> + Send>>;
+
+/// Implemented by the types a [`YarnFn`] may return, i.e. all types implementing [`IntoYarnValueFromNonYarnValue`]
+/// and [`Result`]s of them with an error type implementing [`Display`].
+pub trait YarnFnOutput {
+    /// The type of the value that is passed to Yarn when the function succeeds.
+    type Value: IntoYarnValueFromNonYarnValue + 'static;
+    #[doc(hidden)]
+    const IS_ASYNC: bool = false;
+    #[doc(hidden)]
+    fn into_yarn_fn_result(self) -> Result<YarnValue, YarnFnError>;
+    #[doc(hidden)]
+    fn into_yarn_fn_future(self) -> YarnValueFuture
+    where
+        Self: Sized,
+    {
+        Box::pin(core::future::ready(self.into_yarn_fn_result()))
+    }
+}
+
+impl<T> YarnFnOutput for T
+where
+    T: IntoYarnValueFromNonYarnValue + 'static,
+{
+    type Value = T;
+
+    fn into_yarn_fn_result(self) -> Result<YarnValue, YarnFnError> {
+        Ok(self.into_yarn_value())
+    }
+}
+
+impl<T, E> YarnFnOutput for Result<T, E>
+where
+    T: IntoYarnValueFromNonYarnValue + 'static,
+    E: Display,
+{
+    type Value = T;
+
+    fn into_yarn_fn_result(self) -> Result<YarnValue, YarnFnError> {
+        self.map(IntoYarnValueFromNonYarnValue::into_yarn_value)
+            .map_err(|error| YarnFnError::Failed {
+                message: error.to_string(),
+            })
+    }
+}
+
+/// The output of an async [`YarnFn`], i.e. a function returning a [`Future`].
+/// Its [`YarnFnOutput::Value`] is the one of the future's output.
+///
+/// You don't need to construct this yourself: any function returning a future that resolves to a valid [`YarnFnOutput`] is an async [`YarnFn`].
+#[derive(Debug, Clone)]
+pub struct YarnFnFuture<Fut>(pub Fut);
+
+impl<Fut> YarnFnOutput for YarnFnFuture<Fut>
+where
+    Fut: Future + Send + 'static,
+    Fut::Output: YarnFnOutput,
+{
+    type Value = <Fut::Output as YarnFnOutput>::Value;
+    const IS_ASYNC: bool = true;
+
+    fn into_yarn_fn_result(self) -> Result<YarnValue, YarnFnError> {
+        Err(YarnFnError::Failed {
+            message: "Async functions cannot be called synchronously".to_owned(),
+        })
+    }
+
+    fn into_yarn_fn_future(self) -> YarnValueFuture {
+        Box::pin(async move { self.0.await.into_yarn_fn_result() })
+    }
+}
+
+/// An error that occurred while calling a [`YarnFn`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum YarnFnError {
+    /// The arguments passed to the function did not match its parameters.
+    InvalidArguments { message: String },
+    /// The function returned an [`Err`].
+    Failed { message: String },
+}
+
+impl Error for YarnFnError {}
+
+impl Display for YarnFnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            YarnFnError::InvalidArguments { message } => write!(f, "Invalid arguments: {message}"),
+            YarnFnError::Failed { message } => f.write_str(message),
+        }
+    }
+}
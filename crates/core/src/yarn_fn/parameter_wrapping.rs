@@ -2,14 +2,14 @@
 //!
 //! Inspired by <https://promethia-27.github.io/dependency_injection_like_bevy_from_scratch/chapter2/passing_references.html>
 
-use super::optionality::{AllowedOptionalityChain, Optional, Optionality, Required};
+use super::optionality::{AllowedOptionalityChain, Optional, Optionality, Required, Variadic};
 use crate::prelude::*;
-use std::any::Any;
-use std::borrow::Borrow;
-use std::fmt::{Debug, Display};
-use std::iter::Peekable;
-use std::marker::PhantomData;
-use std::slice::IterMut;
+use core::any::{Any, TypeId};
+use core::borrow::Borrow;
+use core::fmt::{Debug, Display};
+use core::iter::Peekable;
+use core::marker::PhantomData;
+use core::slice::IterMut;
 use yarnspinner_macros::all_tuples;
 
 /// Helper class for implementing something like [`YarnFn`] yourself.
@@ -33,16 +33,17 @@ impl From<YarnValue> for YarnValueWrapper {
 }
 
 impl YarnValueWrapper {
-    fn convert<T>(&mut self)
+    fn convert<T>(&mut self) -> Result<(), YarnFnError>
     where
         T: TryFrom<YarnValue> + 'static,
         <T as TryFrom<YarnValue>>::Error: Display,
     {
-        let raw = std::mem::take(&mut self.raw).unwrap();
-        let converted: T = raw
-            .try_into()
-            .unwrap_or_else(|e| panic!("Parameter passed to Yarn has invalid type: {e}"));
+        let raw = core::mem::take(&mut self.raw).unwrap();
+        let converted: T = raw.try_into().map_err(|e| YarnFnError::InvalidArguments {
+            message: format!("Parameter passed to Yarn has invalid type: {e}"),
+        })?;
         self.converted.replace(Box::new(converted));
+        Ok(())
     }
 }
 
@@ -50,8 +51,12 @@ impl YarnValueWrapper {
 /// - [`bool`]
 /// - Numeric type, i.e. one of [`f32`], [`f64`], [`i8`], [`i16`], [`i32`], [`i64`], [`i128`], [`u8`], [`u16`], [`u32`], [`u64`], [`u128`], [`usize`], [`isize`]
 /// - [`String`] (for a reference, [`&str`] may be used instead of `&String`)
+/// - [`YarnList`], i.e. a list of [`YarnValue`]s
 /// - [`YarnValue`], which means that a parameter may be any of the above types
 /// - Tuples of the above types.
+/// - References to [`YarnObjectType`]s, see [`CustomType`].
+/// - [`Vec`]s of the above types except tuples. These take all remaining arguments and can thus only be used as the last parameter.
+/// - A reference to a [`YarnFnContext`], which is provided by the dialogue instead of being passed as an argument.
 pub trait YarnFnParam {
     /// The item type returned when constructing this [`YarnFn`] param. The value of this associated type should be `Self`, instantiated with a new lifetime.
     /// You could think of `YarnFnParam::Item<'new>` as being an operation that changes the lifetime bound to `Self`.
@@ -64,7 +69,36 @@ pub trait YarnFnParam {
     type Optionality: Optionality;
 
     #[doc(hidden)]
-    fn retrieve<'a>(iter: &mut YarnValueWrapperIter<'a>) -> Self::Item<'a>;
+    fn retrieve<'a>(
+        iter: &mut YarnValueWrapperIter<'a>,
+        context: &'a YarnFnContext<'a>,
+    ) -> Result<Self::Item<'a>, YarnFnError>;
+
+    /// The [`TypeId`] of the elements taken by this parameter if it is variadic, i.e. takes all remaining arguments.
+    #[doc(hidden)]
+    fn variadic_type() -> Option<TypeId> {
+        None
+    }
+
+    /// Whether this parameter is a [`YarnFnContext`], which is not passed as an argument from Yarn.
+    #[doc(hidden)]
+    fn is_context() -> bool {
+        false
+    }
+
+    /// The [`TypeId`]s of the arguments this parameter takes from Yarn, excluding variadic and [`YarnFnContext`] parameters.
+    /// Tuples list the types of their elements.
+    #[doc(hidden)]
+    fn parameter_types() -> Vec<TypeId>
+    where
+        Self: Sized + 'static,
+    {
+        if Self::variadic_type().is_some() || Self::is_context() {
+            Vec::new()
+        } else {
+            vec![TypeId::of::<Self>()]
+        }
+    }
 }
 
 /// Shorthand way of accessing the associated type [`YarnFnParam::Item`] for a given [`YarnFnParam`].
@@ -74,12 +108,38 @@ impl<T: YarnFnParam> YarnFnParam for Option<T> {
     type Item<'new> = Option<T::Item<'new>>;
     type Optionality = Optional;
 
-    fn retrieve<'a>(iter: &mut YarnValueWrapperIter<'a>) -> Self::Item<'a> {
+    fn retrieve<'a>(
+        iter: &mut YarnValueWrapperIter<'a>,
+        context: &'a YarnFnContext<'a>,
+    ) -> Result<Self::Item<'a>, YarnFnError> {
         if iter.peek().is_some() {
-            Some(T::retrieve(iter))
+            T::retrieve(iter, context).map(Some)
         } else {
-            None
+            Ok(None)
+        }
+    }
+}
+
+impl<T> YarnFnParam for Vec<T>
+where
+    T: YarnFnParam<Optionality = Required> + 'static,
+{
+    type Item<'new> = Vec<T::Item<'new>>;
+    type Optionality = Variadic;
+
+    fn retrieve<'a>(
+        iter: &mut YarnValueWrapperIter<'a>,
+        context: &'a YarnFnContext<'a>,
+    ) -> Result<Self::Item<'a>, YarnFnError> {
+        let mut items = Vec::new();
+        while iter.peek().is_some() {
+            items.push(T::retrieve(iter, context)?);
         }
+        Ok(items)
+    }
+
+    fn variadic_type() -> Option<TypeId> {
+        Some(TypeId::of::<T>())
     }
 }
 
@@ -94,8 +154,26 @@ macro_rules! impl_yarn_fn_param_tuple {
             type Optionality = <($(<$param as YarnFnParam>::Optionality,)*) as AllowedOptionalityChain>::Last;
 
             #[allow(unused_variables, clippy::unused_unit)] // for n = 0 tuples
-            fn retrieve<'a>(iter: &mut YarnValueWrapperIter<'a>) -> Self::Item<'a> {
-               ($($param::retrieve(iter),)*)
+            fn retrieve<'a>(
+        iter: &mut YarnValueWrapperIter<'a>,
+        context: &'a YarnFnContext<'a>,
+    ) -> Result<Self::Item<'a>, YarnFnError> {
+               Ok(($($param::retrieve(iter, context)?,)*))
+            }
+
+            fn variadic_type() -> Option<TypeId> {
+                let variadic_types: Vec<Option<TypeId>> = vec![$($param::variadic_type()),*];
+                variadic_types.into_iter().flatten().last()
+            }
+
+            fn parameter_types() -> Vec<TypeId>
+            where
+                Self: Sized + 'static,
+            {
+                #[allow(unused_mut)] // for n = 0 tuples
+                let mut parameter_types = Vec::new();
+                $(parameter_types.extend($param::parameter_types());)*
+                parameter_types
             }
         }
     };
@@ -103,6 +181,14 @@ macro_rules! impl_yarn_fn_param_tuple {
 
 all_tuples!(impl_yarn_fn_param_tuple, 0, 16, P);
 
+fn next_argument<'a>(
+    iter: &mut YarnValueWrapperIter<'a>,
+) -> Result<&'a mut YarnValueWrapper, YarnFnError> {
+    iter.next().ok_or_else(|| YarnFnError::InvalidArguments {
+        message: "Passed too few arguments to YarnFn".to_owned(),
+    })
+}
+
 struct ResRef<'a, T>
 where
     T: TryFrom<YarnValue> + 'static,
@@ -120,15 +206,18 @@ where
     type Item<'new> = ResRef<'new, T>;
     type Optionality = Required;
 
-    fn retrieve<'a>(iter: &mut YarnValueWrapperIter<'a>) -> Self::Item<'a> {
-        let value = iter.next().expect("Passed too few arguments to YarnFn");
-        value.convert::<T>();
+    fn retrieve<'a>(
+        iter: &mut YarnValueWrapperIter<'a>,
+        _context: &'a YarnFnContext<'a>,
+    ) -> Result<Self::Item<'a>, YarnFnError> {
+        let value = next_argument(iter)?;
+        value.convert::<T>()?;
         let converted = value.converted.as_ref().unwrap();
         let value = converted.downcast_ref::<T>().unwrap();
-        ResRef {
+        Ok(ResRef {
             value,
             phantom_data: PhantomData,
-        }
+        })
     }
 }
 
@@ -155,15 +244,18 @@ where
     type Item<'new> = ResRefBorrow<'new, T, U>;
     type Optionality = Required;
 
-    fn retrieve<'a>(iter: &mut YarnValueWrapperIter<'a>) -> Self::Item<'a> {
-        let value = iter.next().expect("Passed too few arguments to YarnFn");
-        value.convert::<T>();
+    fn retrieve<'a>(
+        iter: &mut YarnValueWrapperIter<'a>,
+        _context: &'a YarnFnContext<'a>,
+    ) -> Result<Self::Item<'a>, YarnFnError> {
+        let value = next_argument(iter)?;
+        value.convert::<T>()?;
         let converted = value.converted.as_ref().unwrap();
         let value = converted.downcast_ref::<T>().unwrap();
-        ResRefBorrow {
+        Ok(ResRefBorrow {
             value: value.borrow(),
             phantom_data: PhantomData,
-        }
+        })
     }
 }
 
@@ -183,12 +275,36 @@ where
     type Item<'new> = ResOwned<T>;
     type Optionality = Required;
 
-    fn retrieve<'a>(iter: &mut YarnValueWrapperIter<'a>) -> Self::Item<'a> {
-        let value = iter.next().expect("Passed too few arguments to YarnFn");
-        value.convert::<T>();
+    fn retrieve<'a>(
+        iter: &mut YarnValueWrapperIter<'a>,
+        _context: &'a YarnFnContext<'a>,
+    ) -> Result<Self::Item<'a>, YarnFnError> {
+        let value = next_argument(iter)?;
+        value.convert::<T>()?;
         let converted = value.converted.take().unwrap();
         let value = *converted.downcast::<T>().unwrap();
-        ResOwned { value }
+        Ok(ResOwned { value })
+    }
+}
+
+impl<T: YarnObjectType> YarnFnParam for &T {
+    type Item<'new> = &'new T;
+    type Optionality = Required;
+
+    fn retrieve<'a>(
+        iter: &mut YarnValueWrapperIter<'a>,
+        context: &'a YarnFnContext<'a>,
+    ) -> Result<Self::Item<'a>, YarnFnError> {
+        let object = ResRef::<YarnObject>::retrieve(iter, context)?.value;
+        object
+            .downcast_ref::<T>()
+            .ok_or_else(|| YarnFnError::InvalidArguments {
+                message: format!(
+                    "Parameter passed to Yarn has invalid type: expected {} but got {}",
+                    T::NAME,
+                    object.type_name()
+                ),
+            })
     }
 }
 
@@ -208,8 +324,11 @@ macro_rules! impl_yarn_fn_param_inner {
             type Item<'new> = &'new $referenced;
             type Optionality = Required;
 
-            fn retrieve<'a>(iter: &mut YarnValueWrapperIter<'a>) -> Self::Item<'a> {
-                ResRef::<$referenced>::retrieve(iter).value
+            fn retrieve<'a>(
+                iter: &mut YarnValueWrapperIter<'a>,
+                context: &'a YarnFnContext<'a>,
+            ) -> Result<Self::Item<'a>, YarnFnError> {
+                ResRef::<$referenced>::retrieve(iter, context).map(|res| res.value)
             }
         }
 
@@ -217,8 +336,11 @@ macro_rules! impl_yarn_fn_param_inner {
             type Item<'new> = $referenced;
             type Optionality = Required;
 
-            fn retrieve<'a>(iter: &mut YarnValueWrapperIter<'a>) -> Self::Item<'a> {
-                ResOwned::<$referenced>::retrieve(iter).value
+            fn retrieve<'a>(
+                iter: &mut YarnValueWrapperIter<'a>,
+                context: &'a YarnFnContext<'a>,
+            ) -> Result<Self::Item<'a>, YarnFnError> {
+                ResOwned::<$referenced>::retrieve(iter, context).map(|res| res.value)
             }
         }
     };
@@ -227,8 +349,11 @@ macro_rules! impl_yarn_fn_param_inner {
             type Item<'new> = &'new $referenced;
             type Optionality = Required;
 
-            fn retrieve<'a>(iter: &mut YarnValueWrapperIter<'a>) -> Self::Item<'a> {
-                ResRefBorrow::<$owned, $referenced>::retrieve(iter).value
+            fn retrieve<'a>(
+                iter: &mut YarnValueWrapperIter<'a>,
+                context: &'a YarnFnContext<'a>,
+            ) -> Result<Self::Item<'a>, YarnFnError> {
+                ResRefBorrow::<$owned, $referenced>::retrieve(iter, context).map(|res| res.value)
             }
         }
 
@@ -236,8 +361,11 @@ macro_rules! impl_yarn_fn_param_inner {
             type Item<'new> = &'new $owned;
             type Optionality = Required;
 
-            fn retrieve<'a>(iter: &mut YarnValueWrapperIter<'a>) -> Self::Item<'a> {
-                ResRef::<$owned>::retrieve(iter).value
+            fn retrieve<'a>(
+                iter: &mut YarnValueWrapperIter<'a>,
+                context: &'a YarnFnContext<'a>,
+            ) -> Result<Self::Item<'a>, YarnFnError> {
+                ResRef::<$owned>::retrieve(iter, context).map(|res| res.value)
             }
         }
 
@@ -245,13 +373,16 @@ macro_rules! impl_yarn_fn_param_inner {
             type Item<'new> = $owned;
             type Optionality = Required;
 
-            fn retrieve<'a>(iter: &mut YarnValueWrapperIter<'a>) -> Self::Item<'a> {
-                ResOwned::<$owned>::retrieve(iter).value
+            fn retrieve<'a>(
+                iter: &mut YarnValueWrapperIter<'a>,
+                context: &'a YarnFnContext<'a>,
+            ) -> Result<Self::Item<'a>, YarnFnError> {
+                ResOwned::<$owned>::retrieve(iter, context).map(|res| res.value)
             }
         }
     };
 }
 
 impl_yarn_fn_param! {
-    [str => String, YarnValue, bool, f32, f64, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, usize, isize]: YarnFnParam
+    [str => String, YarnValue, YarnList, bool, f32, f64, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, usize, isize]: YarnFnParam
 }
@@ -1,8 +1,13 @@
 //! Implements a subset of dotnet's [`Convert`](https://learn.microsoft.com/en-us/dotnet/api/system.convert?view=net-8.0) type.
-#[cfg(any(feature = "bevy", feature = "serde"))]
 use crate::prelude::*;
-use std::error::Error;
-use std::fmt::{Display, Formatter};
+use core::cmp::Ordering;
+use core::error::Error;
+use core::fmt::{Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut};
+
+#[cfg(feature = "serde")]
+mod json;
 
 /// Represents a Yarn value. The chosen variant corresponds to the last assignment of the value,
 /// with the type being inferred from the type checker.
@@ -10,10 +15,28 @@ use std::fmt::{Display, Formatter};
 /// The type implements meaningful conversions between types through [`TryFrom`] and [`From`].
 /// A failure to convert one variant to another will result in an [`YarnValueCastError`].
 ///
+/// ## Comparison and hashing
+///
+/// Values follow the comparison rules of Yarn scripts, so they can be sorted and used as keys in maps and sets:
+/// - Numbers compare numerically. Unlike for [`f64`], `NaN` is equal to itself and `-0.0` is equal to `0.0`.
+/// - Strings compare lexicographically by their Unicode code points.
+/// - Booleans compare with `false` being less than `true`.
+/// - Lists compare lexicographically by their elements.
+/// - Objects are only equal if they share the same underlying value and are otherwise unordered.
+/// - Values of different variants are never equal and unordered, just like Yarn refuses to compare values of different types.
+///
+/// ```rust
+/// # use yarnspinner_core::prelude::*;
+/// let mut options = vec![YarnValue::from(3), YarnValue::from(1.5), YarnValue::from(2)];
+/// options.sort_by(|a, b| a.partial_cmp(b).unwrap());
+/// assert_eq!(options, vec![YarnValue::from(1.5), YarnValue::from(2), YarnValue::from(3)]);
+/// assert_eq!(YarnValue::from(1).partial_cmp(&YarnValue::from("1")), None);
+/// ```
+///
 /// ## Implementation Notes
 ///
 /// Corresponds to C#'s [`Convert`](https://docs.microsoft.com/en-us/dotnet/api/system.convert?view=net-5.0) class.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "bevy", derive(Reflect))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "bevy", reflect(Debug, PartialEq))]
@@ -21,20 +44,31 @@ use std::fmt::{Display, Formatter};
     all(feature = "bevy", feature = "serde"),
     reflect(Serialize, Deserialize)
 )]
+#[cfg_attr(feature = "bevy", reflect(no_field_bounds))]
 pub enum YarnValue {
     /// Any kind of Rust number, i.e. one of `f32`, `f64`, `i8`, `i16`, `i32`, `i64`, `i128`, `u8`, `u16`, `u32`, `u64`, `u128`, `usize`, `isize`.
-    /// They are internally stored as `f32` through simple type casts.
-    Number(f32),
+    /// They are internally stored as `f64` through simple type casts, so whole numbers up to 2^53 are represented exactly.
+    /// Converting a number back to an integer type fails instead of truncating if it is not a whole number that fits the target type.
+    /// Division and modulo follow floating point semantics, e.g. `7 / 2` is `3.5`.
+    Number(f64),
     /// An owned Rust string.
     String(String),
     /// A Rust boolean.
     Boolean(bool),
+    /// A list of values, which may be of different types. Use [`YarnList`] to pass lists to and from [`YarnFn`]s.
+    List(Vec<YarnValue>),
+    /// An opaque Rust value registered as a [`CustomType`]. Objects only exist at runtime and are thus neither serialized nor reflected.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Object(
+        #[cfg_attr(feature = "bevy", reflect(ignore, default = "YarnObject::unreflected"))]
+        YarnObject,
+    ),
 }
 
 /// The return value of a [`YarnFn`]. See [`YarnFn`] for more information on the kinds of signatures that can be registered.
 ///
 /// Needed to ensure that the return type of a registered function is
-/// able to be turned into a [`YarnValue`], but not a [`YarnValue`] itself.
+/// able to be turned into a [`YarnValue`]. Returning a [`YarnValue`] itself means that the function's return type is only known at runtime.
 pub trait IntoYarnValueFromNonYarnValue {
     #[doc(hidden)]
     fn into_yarn_value(self) -> YarnValue;
@@ -43,9 +77,12 @@ pub trait IntoYarnValueFromNonYarnValue {
 impl YarnValue {
     /// Checks if two [`YarnValue`]s are equal, with a given epsilon for two [`YarnValue::Number`]s.
     /// Note that all equality operations are type-safe, i.e. comparing a [`YarnValue::Number`] to a [`YarnValue::String`] will always return `false`.
-    pub fn eq(&self, other: &Self, epsilon: f32) -> bool {
+    pub fn eq(&self, other: &Self, epsilon: f64) -> bool {
         match (self, other) {
             (Self::Number(a), Self::Number(b)) => (a - b).abs() < epsilon,
+            (Self::List(a), Self::List(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.eq(b, epsilon))
+            }
             (a, b) => a == b,
         }
     }
@@ -66,7 +103,7 @@ macro_rules! impl_floating_point {
         $(
             impl From<$from_type> for YarnValue {
                 fn from(value: $from_type) -> Self {
-                    Self::Number(value as f32)
+                    Self::Number(value as f64)
                 }
             }
 
@@ -86,6 +123,8 @@ macro_rules! impl_floating_point {
                         YarnValue::Number(value) => Ok(*value as $from_type),
                         YarnValue::String(value) => value.parse().map_err(Into::into),
                         YarnValue::Boolean(value) => Ok(if *value { 1.0 as $from_type } else { 0.0 }),
+                        YarnValue::List(_) => Err(YarnValueCastError::ListCastError("number")),
+                        YarnValue::Object(_) => Err(YarnValueCastError::ObjectCastError("number")),
                     }
                 }
             }
@@ -107,7 +146,7 @@ macro_rules! impl_whole_number {
         $(
             impl From<$from_type> for YarnValue {
                 fn from(value: $from_type) -> Self {
-                    Self::Number(value as f32)
+                    Self::Number(value as f64)
                 }
             }
 
@@ -123,7 +162,16 @@ macro_rules! impl_whole_number {
                 type Error = YarnValueCastError;
 
                 fn try_from(value: &YarnValue) -> Result<Self, Self::Error> {
-                    f32::try_from(value).map(|value| value as $from_type)
+                    let number = f64::try_from(value)?;
+                    // `as` saturates, and the maximum of 64-bit and wider types rounds up to the next power of two as a float,
+                    // so casting back alone would accept that power of two. The range excludes NaN and infinities as well.
+                    let range = <$from_type>::MIN as f64..<$from_type>::MAX as f64 + 1.0;
+                    let converted = number as $from_type;
+                    if range.contains(&number) && converted as f64 == number {
+                        Ok(converted)
+                    } else {
+                        Err(YarnValueCastError::NotAnInteger(number))
+                    }
                 }
             }
 
@@ -144,6 +192,7 @@ impl From<YarnValue> for String {
             YarnValue::Number(value) => value.to_string(),
             YarnValue::String(value) => value,
             YarnValue::Boolean(value) => value.to_string(),
+            YarnValue::List(_) | YarnValue::Object(_) => value.to_string(),
         }
     }
 }
@@ -188,6 +237,8 @@ impl TryFrom<&YarnValue> for bool {
             YarnValue::Number(value) => Ok(*value != 0.0),
             YarnValue::String(value) => value.parse().map_err(Into::into),
             YarnValue::Boolean(value) => Ok(*value),
+            YarnValue::List(_) => Err(YarnValueCastError::ListCastError("bool")),
+            YarnValue::Object(_) => Err(YarnValueCastError::ObjectCastError("bool")),
         }
     }
 }
@@ -204,13 +255,144 @@ impl IntoYarnValueFromNonYarnValue for bool {
     }
 }
 
+impl IntoYarnValueFromNonYarnValue for YarnValue {
+    fn into_yarn_value(self) -> YarnValue {
+        self
+    }
+}
+
+/// A list of [`YarnValue`]s, corresponding to [`YarnValue::List`].
+/// Use this type for parameters and return values of [`YarnFn`]s that work with lists,
+/// since a [`Vec`] parameter makes a [`YarnFn`] variadic instead.
+///
+/// ## Example
+///
+/// ```rust
+/// # use yarnspinner_core::prelude::*;
+/// let list = YarnList::from(vec!["sword", "shield"]);
+/// assert_eq!(YarnValue::from(list.clone()), YarnValue::List(vec!["sword".into(), "shield".into()]));
+/// assert_eq!(list.to_string(), "[sword, shield]");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash, Default)]
+#[cfg_attr(feature = "bevy", derive(Reflect))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bevy", reflect(Debug, PartialEq, Default))]
+#[cfg_attr(
+    all(feature = "bevy", feature = "serde"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct YarnList(pub Vec<YarnValue>);
+
+impl Deref for YarnList {
+    type Target = Vec<YarnValue>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for YarnList {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<Vec<T>> for YarnList
+where
+    YarnValue: From<T>,
+{
+    fn from(values: Vec<T>) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+impl<T> FromIterator<T> for YarnList
+where
+    YarnValue: From<T>,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(iter.into_iter().map(YarnValue::from).collect())
+    }
+}
+
+impl IntoIterator for YarnList {
+    type Item = YarnValue;
+    type IntoIter = <Vec<YarnValue> as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl From<YarnList> for YarnValue {
+    fn from(value: YarnList) -> Self {
+        Self::List(value.0)
+    }
+}
+
+impl TryFrom<YarnValue> for YarnList {
+    type Error = YarnValueCastError;
+
+    fn try_from(value: YarnValue) -> Result<Self, Self::Error> {
+        match value {
+            YarnValue::List(values) => Ok(Self(values)),
+            _ => Err(YarnValueCastError::NotAList),
+        }
+    }
+}
+
+impl TryFrom<&YarnValue> for YarnList {
+    type Error = YarnValueCastError;
+
+    fn try_from(value: &YarnValue) -> Result<Self, Self::Error> {
+        Self::try_from(value.clone())
+    }
+}
+
+impl IntoYarnValueFromNonYarnValue for YarnList {
+    fn into_yarn_value(self) -> YarnValue {
+        self.into()
+    }
+}
+
+impl Display for YarnList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        fmt_list(&self.0, f)
+    }
+}
+
+fn fmt_list(values: &[YarnValue], f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.write_str("[")?;
+    for (index, value) in values.iter().enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+        Display::fmt(value, f)?;
+    }
+    f.write_str("]")
+}
+
 /// Represents a failure to convert one variant of [`YarnValue`] to a base type.
 #[derive(Debug)]
 #[allow(missing_docs)]
 pub enum YarnValueCastError {
-    ParseFloatError(std::num::ParseFloatError),
-    ParseIntError(std::num::ParseIntError),
-    ParseBoolError(std::str::ParseBoolError),
+    ParseFloatError(core::num::ParseFloatError),
+    ParseIntError(core::num::ParseIntError),
+    ParseBoolError(core::str::ParseBoolError),
+    /// A [`YarnValue::List`] cannot be converted to the named type.
+    ListCastError(&'static str),
+    /// Only a [`YarnValue::List`] can be converted to a [`YarnList`].
+    NotAList,
+    /// A [`YarnValue::Object`] cannot be converted to the named type.
+    ObjectCastError(&'static str),
+    /// Only a [`YarnValue::Object`] can be converted to a [`YarnObject`].
+    NotAnObject,
+    /// The number is not a whole number or does not fit into the target integer type.
+    NotAnInteger(f64),
+    /// `NaN` and infinite numbers cannot be converted to JSON.
+    NonFiniteNumber(f64),
+    /// The named kind of JSON value, e.g. `null`, has no corresponding [`YarnValue`].
+    UnsupportedJson(&'static str),
 }
 
 impl Error for YarnValueCastError {
@@ -219,44 +401,152 @@ impl Error for YarnValueCastError {
             YarnValueCastError::ParseFloatError(e) => Some(e),
             YarnValueCastError::ParseIntError(e) => Some(e),
             YarnValueCastError::ParseBoolError(e) => Some(e),
+            YarnValueCastError::ListCastError(_)
+            | YarnValueCastError::NotAList
+            | YarnValueCastError::ObjectCastError(_)
+            | YarnValueCastError::NotAnObject
+            | YarnValueCastError::NotAnInteger(_)
+            | YarnValueCastError::NonFiniteNumber(_)
+            | YarnValueCastError::UnsupportedJson(_) => None,
         }
     }
 }
 
 impl Display for YarnValueCastError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             YarnValueCastError::ParseFloatError(e) => Display::fmt(e, f),
             YarnValueCastError::ParseIntError(e) => Display::fmt(e, f),
             YarnValueCastError::ParseBoolError(e) => Display::fmt(e, f),
+            YarnValueCastError::ListCastError(target_type) => {
+                write!(f, "Cannot convert a list to a {target_type}")
+            }
+            YarnValueCastError::NotAList => f.write_str("Value is not a list"),
+            YarnValueCastError::ObjectCastError(target_type) => {
+                write!(f, "Cannot convert an object to a {target_type}")
+            }
+            YarnValueCastError::NotAnObject => f.write_str("Value is not an object"),
+            YarnValueCastError::NotAnInteger(number) => {
+                write!(
+                    f,
+                    "Cannot convert {number} to an integer without losing precision"
+                )
+            }
+            YarnValueCastError::NonFiniteNumber(number) => {
+                write!(f, "Cannot convert {number} to JSON")
+            }
+            YarnValueCastError::UnsupportedJson(kind) => {
+                write!(f, "Cannot convert JSON {kind} to a Yarn value")
+            }
         }
     }
 }
 
-impl From<std::num::ParseFloatError> for YarnValueCastError {
-    fn from(value: std::num::ParseFloatError) -> Self {
+impl From<core::num::ParseFloatError> for YarnValueCastError {
+    fn from(value: core::num::ParseFloatError) -> Self {
         Self::ParseFloatError(value)
     }
 }
 
-impl From<std::num::ParseIntError> for YarnValueCastError {
-    fn from(value: std::num::ParseIntError) -> Self {
+impl From<core::num::ParseIntError> for YarnValueCastError {
+    fn from(value: core::num::ParseIntError) -> Self {
         Self::ParseIntError(value)
     }
 }
 
-impl From<std::str::ParseBoolError> for YarnValueCastError {
-    fn from(value: std::str::ParseBoolError) -> Self {
+impl From<core::str::ParseBoolError> for YarnValueCastError {
+    fn from(value: core::str::ParseBoolError) -> Self {
         Self::ParseBoolError(value)
     }
 }
 
 impl Display for YarnValue {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Number(value) => write!(f, "{value}"),
             Self::String(value) => write!(f, "{value}"),
             Self::Boolean(value) => write!(f, "{value}"),
+            Self::List(values) => fmt_list(values, f),
+            Self::Object(object) => Display::fmt(object, f),
+        }
+    }
+}
+
+impl PartialEq for YarnValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Number(a), Self::Number(b)) => a == b || (a.is_nan() && b.is_nan()),
+            (Self::String(a), Self::String(b)) => a == b,
+            (Self::Boolean(a), Self::Boolean(b)) => a == b,
+            (Self::List(a), Self::List(b)) => a == b,
+            (Self::Object(a), Self::Object(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for YarnValue {}
+
+impl PartialOrd for YarnValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Number(a), Self::Number(b)) if a.is_nan() && b.is_nan() => Some(Ordering::Equal),
+            (Self::Number(a), Self::Number(b)) => a.partial_cmp(b),
+            (Self::String(a), Self::String(b)) => Some(a.cmp(b)),
+            (Self::Boolean(a), Self::Boolean(b)) => Some(a.cmp(b)),
+            (Self::List(a), Self::List(b)) => a.partial_cmp(b),
+            (Self::Object(a), Self::Object(b)) => (a == b).then_some(Ordering::Equal),
+            _ => None,
+        }
+    }
+}
+
+impl Hash for YarnValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match self {
+            Self::Number(value) => {
+                // Values that are equal must hash the same, so normalize the bit patterns `==` considers equal.
+                let value = if value.is_nan() {
+                    f64::NAN
+                } else if *value == 0.0 {
+                    0.0
+                } else {
+                    *value
+                };
+                value.to_bits().hash(state);
+            }
+            Self::String(value) => value.hash(state),
+            Self::Boolean(value) => value.hash(state),
+            Self::List(values) => values.hash(state),
+            Self::Object(object) => object.hash(state),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whole_numbers_convert_to_integers() {
+        assert_eq!(i64::try_from(YarnValue::from(-3.0)).unwrap(), -3);
+        assert_eq!(u8::try_from(YarnValue::from(255.0)).unwrap(), u8::MAX);
+        assert_eq!(i8::try_from(YarnValue::from(-128.0)).unwrap(), i8::MIN);
+        assert_eq!(
+            i64::try_from(YarnValue::from(-(2.0_f64.powi(63)))).unwrap(),
+            i64::MIN
+        );
+    }
+
+    #[test]
+    fn numbers_out_of_range_fail_to_convert_to_integers() {
+        assert!(u8::try_from(YarnValue::from(256.0)).is_err());
+        assert!(u8::try_from(YarnValue::from(-1.0)).is_err());
+        assert!(i64::try_from(YarnValue::from(2.0_f64.powi(63))).is_err());
+        assert!(u64::try_from(YarnValue::from(2.0_f64.powi(64))).is_err());
+        assert!(i64::try_from(YarnValue::from(2.5)).is_err());
+        assert!(i64::try_from(YarnValue::from(f64::NAN)).is_err());
+        assert!(i64::try_from(YarnValue::from(f64::INFINITY)).is_err());
+    }
+}
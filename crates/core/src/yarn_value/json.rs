@@ -0,0 +1,115 @@
+//! Not part of the original implementation. Bridges [`YarnValue`]s to JSON, e.g. for config files, network payloads and save files.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # use yarnspinner_core::prelude::*;
+//! let json = serde_json::json!(["sword", 3, true]);
+//! let value = YarnValue::try_from(json.clone()).unwrap();
+//! assert_eq!(value, YarnList::from(vec![YarnValue::from("sword"), 3.into(), true.into()]).into());
+//! assert_eq!(serde_json::Value::try_from(value).unwrap(), json);
+//! ```
+
+use crate::prelude::*;
+use serde_json::{Number, Value};
+
+impl TryFrom<Value> for YarnValue {
+    type Error = YarnValueCastError;
+
+    /// Converts JSON numbers, strings, booleans and arrays. `null` and objects have no corresponding [`YarnValue`].
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Null => Err(YarnValueCastError::UnsupportedJson("null")),
+            Value::Bool(value) => Ok(Self::Boolean(value)),
+            Value::Number(number) => number
+                .as_f64()
+                .map(Self::Number)
+                .ok_or(YarnValueCastError::UnsupportedJson("number")),
+            Value::String(value) => Ok(Self::String(value)),
+            Value::Array(values) => values
+                .into_iter()
+                .map(Self::try_from)
+                .collect::<Result<_, _>>()
+                .map(Self::List),
+            Value::Object(_) => Err(YarnValueCastError::UnsupportedJson("object")),
+        }
+    }
+}
+
+impl TryFrom<&Value> for YarnValue {
+    type Error = YarnValueCastError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value.clone().try_into()
+    }
+}
+
+impl TryFrom<YarnValue> for Value {
+    type Error = YarnValueCastError;
+
+    /// Converts whole numbers to JSON integers and all other numbers to JSON floats.
+    /// Fails for `NaN`, infinite numbers and [`YarnValue::Object`]s.
+    fn try_from(value: YarnValue) -> Result<Self, Self::Error> {
+        (&value).try_into()
+    }
+}
+
+impl TryFrom<&YarnValue> for Value {
+    type Error = YarnValueCastError;
+
+    fn try_from(value: &YarnValue) -> Result<Self, Self::Error> {
+        match value {
+            YarnValue::Number(number) => {
+                let number = *number;
+                if !number.is_finite() {
+                    return Err(YarnValueCastError::NonFiniteNumber(number));
+                }
+                let is_integer = number.fract() == 0.0 && number.abs() < i64::MAX as f64;
+                let json_number = if is_integer {
+                    Number::from(number as i64)
+                } else {
+                    Number::from_f64(number).ok_or(YarnValueCastError::NonFiniteNumber(number))?
+                };
+                Ok(Value::Number(json_number))
+            }
+            YarnValue::String(value) => Ok(Value::String(value.clone())),
+            YarnValue::Boolean(value) => Ok(Value::Bool(*value)),
+            YarnValue::List(values) => values
+                .iter()
+                .map(Value::try_from)
+                .collect::<Result<_, _>>()
+                .map(Value::Array),
+            YarnValue::Object(_) => Err(YarnValueCastError::ObjectCastError("JSON value")),
+        }
+    }
+}
+
+impl TryFrom<YarnList> for Value {
+    type Error = YarnValueCastError;
+
+    fn try_from(list: YarnList) -> Result<Self, Self::Error> {
+        YarnValue::from(list).try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn numbers_keep_their_json_representation() {
+        assert_eq!(Value::try_from(YarnValue::from(3)).unwrap(), json!(3));
+        assert_eq!(Value::try_from(YarnValue::from(0.5)).unwrap(), json!(0.5));
+        assert_eq!(YarnValue::try_from(json!(3)).unwrap(), YarnValue::from(3));
+    }
+
+    #[test]
+    fn unsupported_values_fail_to_convert() {
+        assert!(YarnValue::try_from(json!(null)).is_err());
+        assert!(YarnValue::try_from(json!({ "gold": 3 })).is_err());
+        assert!(YarnValue::try_from(json!([1, null])).is_err());
+        assert!(Value::try_from(YarnValue::from(f64::NAN)).is_err());
+        assert!(Value::try_from(YarnValue::from(f64::INFINITY)).is_err());
+    }
+}
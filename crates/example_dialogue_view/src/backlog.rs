@@ -0,0 +1,247 @@
+use crate::assets::font_handle;
+use crate::ExampleYarnSpinnerDialogueViewSystemSet;
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy_yarnspinner::prelude::*;
+
+/// An optional panel that lets players scroll back through the conversation so far, including the options they chose.
+/// Press [`BacklogSettings::toggle_key`] to show or hide it and use the mouse wheel to scroll while it is shown.
+///
+/// The entries are taken from the [`DialogueTranscript`], which this plugin inserts if it was not inserted yet.
+/// Like [`ExampleYarnSpinnerDialogueViewPlugin`](crate::ExampleYarnSpinnerDialogueViewPlugin), the panel only shows the transcript of a single [`DialogueRunner`].
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_yarnspinner::prelude::*;
+/// use bevy_yarnspinner_example_dialogue_view::prelude::*;
+///
+/// App::new()
+///     .add_plugins(DefaultPlugins)
+///     .add_plugins(YarnSpinnerPlugin::new())
+///     .add_plugins(ExampleYarnSpinnerDialogueViewPlugin::new())
+///     .add_plugins(BacklogDialogueViewPlugin::new());
+/// ```
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct BacklogDialogueViewPlugin;
+
+impl BacklogDialogueViewPlugin {
+    /// Creates a new backlog dialogue view
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Plugin for BacklogDialogueViewPlugin {
+    fn build(&self, app: &mut App) {
+        assert!(
+            app.is_plugin_added::<YarnSpinnerPlugin>(),
+            "YarnSpinnerPlugin must be added before BacklogDialogueViewPlugin"
+        );
+        // Loads the font without registering the assets plugin, so the view can be combined with `ExampleYarnSpinnerDialogueViewPlugin` in any order
+        crate::assets::ui_assets_plugin(app);
+        if !app.world().contains_resource::<DialogueTranscript>() {
+            app.init_resource::<DialogueTranscript>();
+        }
+        app.init_resource::<BacklogSettings>()
+            .add_systems(Startup, spawn_backlog)
+            .add_systems(
+                Update,
+                (toggle_backlog, show_backlog, update_backlog, scroll_backlog)
+                    .chain()
+                    .after(YarnSpinnerSystemSet)
+                    .in_set(ExampleYarnSpinnerDialogueViewSystemSet),
+            )
+            .configure_sets(
+                Update,
+                ExampleYarnSpinnerDialogueViewSystemSet.before(LanguageFontSystemSet),
+            );
+    }
+}
+
+/// Configures the panel of the [`BacklogDialogueViewPlugin`].
+#[derive(Debug, Clone, Resource)]
+pub struct BacklogSettings {
+    /// Whether the panel is shown. Defaults to `false`.
+    pub visible: bool,
+    /// The key that shows or hides the panel. Set to [`None`] to only toggle it through [`BacklogSettings::visible`]. Defaults to [`KeyCode::KeyL`].
+    pub toggle_key: Option<KeyCode>,
+    /// The style of lines that were presented.
+    pub line_style: TextStyle,
+    /// The style of options that were chosen.
+    pub selected_option_style: TextStyle,
+    /// The background color of the panel.
+    pub background_color: Color,
+}
+
+impl Default for BacklogSettings {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            toggle_key: Some(KeyCode::KeyL),
+            line_style: TextStyle {
+                font: font_handle::MEDIUM,
+                font_size: 18.0,
+                color: Color::WHITE,
+            },
+            selected_option_style: TextStyle {
+                font: font_handle::MEDIUM,
+                font_size: 18.0,
+                color: Color::srgb(1.0, 0.85, 0.4),
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.85),
+        }
+    }
+}
+
+#[derive(Debug, Default, Component)]
+struct BacklogPanel;
+
+/// How far the list is scrolled up from its newest entry, in logical pixels.
+#[derive(Debug, Default, Component)]
+struct BacklogList {
+    scroll: f32,
+}
+
+const SCROLL_LINE_HEIGHT: f32 = 20.0;
+
+fn spawn_backlog(mut commands: Commands, settings: Res<BacklogSettings>) {
+    commands
+        .spawn((
+            Name::new("Yarn Spinner backlog"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(15.0),
+                    top: Val::Percent(10.0),
+                    width: Val::Percent(70.0),
+                    height: Val::Percent(70.0),
+                    overflow: Overflow::clip_y(),
+                    ..default()
+                },
+                background_color: settings.background_color.into(),
+                visibility: Visibility::Hidden,
+                // Drawn above the dialogue box
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            BacklogPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(16.0),
+                        right: Val::Px(16.0),
+                        padding: UiRect::vertical(Val::Px(16.0)),
+                        bottom: Val::Px(0.0),
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(8.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+                BacklogList::default(),
+            ));
+        });
+}
+
+fn toggle_backlog(keys: Res<ButtonInput<KeyCode>>, mut settings: ResMut<BacklogSettings>) {
+    if settings
+        .toggle_key
+        .is_some_and(|key| keys.just_pressed(key))
+    {
+        settings.visible = !settings.visible;
+    }
+}
+
+fn show_backlog(
+    settings: Res<BacklogSettings>,
+    mut panels: Query<&mut Visibility, With<BacklogPanel>>,
+    mut lists: Query<(&mut BacklogList, &mut Style)>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    for mut visibility in panels.iter_mut() {
+        *visibility = if settings.visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+    // Always open at the newest entry
+    for (mut list, mut style) in lists.iter_mut() {
+        list.scroll = 0.0;
+        style.bottom = Val::Px(0.0);
+    }
+}
+
+fn update_backlog(
+    mut commands: Commands,
+    transcript: Res<DialogueTranscript>,
+    settings: Res<BacklogSettings>,
+    dialogue_runners: Query<Entity, With<DialogueRunner>>,
+    lists: Query<Entity, With<BacklogList>>,
+) {
+    if !(transcript.is_changed() || settings.is_changed()) {
+        return;
+    }
+    let Some(dialogue_runner) = dialogue_runners.iter().next() else {
+        return;
+    };
+    for list in lists.iter() {
+        commands
+            .entity(list)
+            .despawn_descendants()
+            .with_children(|parent| {
+                for entry in transcript.entries(dialogue_runner) {
+                    let (text, style) = match entry {
+                        TranscriptEntry::Line(line) => {
+                            (line.text.clone(), settings.line_style.clone())
+                        }
+                        TranscriptEntry::SelectedOption(line) => (
+                            format!("> {}", line.text_without_character_name()),
+                            settings.selected_option_style.clone(),
+                        ),
+                    };
+                    let font = style.font.clone();
+                    parent.spawn((
+                        TextBundle::from_section(text, style),
+                        Label,
+                        LanguageFontText::new(font),
+                    ));
+                }
+            });
+    }
+}
+
+fn scroll_backlog(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    settings: Res<BacklogSettings>,
+    mut lists: Query<(&mut BacklogList, &mut Style, &Parent, &Node)>,
+    panels: Query<&Node, With<BacklogPanel>>,
+) {
+    if !settings.visible {
+        mouse_wheel_events.clear();
+        return;
+    }
+    for event in mouse_wheel_events.read() {
+        let delta = match event.unit {
+            MouseScrollUnit::Line => event.y * SCROLL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => event.y,
+        };
+        for (mut list, mut style, parent, node) in lists.iter_mut() {
+            let Ok(panel) = panels.get(parent.get()) else {
+                continue;
+            };
+            let max_scroll = (node.size().y - panel.size().y).max(0.0);
+            list.scroll = (list.scroll + delta).clamp(0.0, max_scroll);
+            // The list is anchored to the bottom of the panel, so moving it down reveals older entries
+            style.bottom = Val::Px(-list.scroll);
+        }
+    }
+}
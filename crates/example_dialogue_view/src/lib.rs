@@ -24,13 +24,42 @@
 //! ```
 //!
 //! This crate also exposes the [`SpeakerChangeEvent`] which you can use to animate characters while they are speaking,
-//! as the text is written out over a few seconds.
+//! as the text is written out over a few seconds. Once a line is fully shown, a [`TypewriterFinishedEvent`] is sent.
+//!
+//! ## Typewriter
+//!
+//! The speed at which the text is written out can be changed through the [`TypewriterSettings`] resource.
+//! Lines can pause the typewriter for a given amount of milliseconds with the `pause` markup:
+//! ```yarn
+//! Ferris: Well...[pause=500/] I guess so.
+//! ```
+//!
+//! ## Fonts
+//!
+//! All text is rendered in the font registered for the current text language in the
+//! [`LanguageFonts`](bevy_yarnspinner::prelude::LanguageFonts) resource, falling back to the builtin font.
+//! Register a font for languages whose glyphs the builtin font lacks, e.g. Chinese, Japanese or Korean.
+//!
+//! ## Speech bubbles
+//!
+//! For overhead barks and NPC chatter, the optional [`SpeechBubbleDialogueViewPlugin`] shows lines in speech bubbles that follow the entity
+//! of their speaker. Register speakers by adding a [`DialogueSpeaker`] with the character name used in the Yarn files to their entity:
+//! ```yarn
+//! Guard: Halt! Who goes there?
+//! ```
+//! The bubbles are configured through the [`SpeechBubbleSettings`] resource.
+//!
+//! ## Backlog
+//!
+//! The optional [`BacklogDialogueViewPlugin`] adds a panel that lists the lines of the conversation so far and the options the player chose,
+//! based on the [`DialogueTranscript`](bevy_yarnspinner::prelude::DialogueTranscript). It is configured through the [`BacklogSettings`] resource.
 //!
 //! ## Inputs
 //!
 //! - Advance the dialogue: press the space bar, enter key, left click or tap the screen after the text is done typing.
 //! - Type out the text faster: Same as above, but hold press before the text is done typing.
 //! - Select an option: press the number key corresponding to the option you want to select or click/tap the option.
+//! - Show or hide the backlog of [`BacklogDialogueViewPlugin`]: press L, then scroll through it with the mouse wheel.
 //!
 //! ## Limitations
 //!
@@ -40,16 +69,23 @@
 #![allow(clippy::too_many_arguments, clippy::type_complexity)]
 #![warn(missing_docs, missing_debug_implementations)]
 
+pub use backlog::{BacklogDialogueViewPlugin, BacklogSettings};
 use bevy::prelude::*;
-use bevy_yarnspinner::prelude::YarnSpinnerPlugin;
+use bevy_yarnspinner::prelude::{LanguageFontSystemSet, YarnSpinnerPlugin};
 pub use setup::UiRootNode;
+pub use speech_bubble::{
+    DialogueSpeaker, SpeechBubble, SpeechBubbleDialogueViewPlugin, SpeechBubbleSettings,
+};
+pub use typewriter::{TypewriterFinishedEvent, TypewriterSettings};
 pub use updating::SpeakerChangeEvent;
 
 pub mod prelude {
     //! Everything you need to get starting using this example Yarn Spinner dialogue view.
     pub use crate::{
+        BacklogDialogueViewPlugin, BacklogSettings, DialogueSpeaker,
         ExampleYarnSpinnerDialogueViewPlugin, ExampleYarnSpinnerDialogueViewSystemSet,
-        SpeakerChangeEvent,
+        SpeakerChangeEvent, SpeechBubbleDialogueViewPlugin, SpeechBubbleSettings,
+        TypewriterFinishedEvent, TypewriterSettings,
     };
 }
 
@@ -71,8 +107,10 @@ impl ExampleYarnSpinnerDialogueViewPlugin {
 }
 
 mod assets;
+mod backlog;
 mod option_selection;
 mod setup;
+mod speech_bubble;
 mod typewriter;
 mod updating;
 
@@ -86,7 +124,11 @@ impl Plugin for ExampleYarnSpinnerDialogueViewPlugin {
             .add_plugins(setup::ui_setup_plugin)
             .add_plugins(updating::ui_updating_plugin)
             .add_plugins(typewriter::typewriter_plugin)
-            .add_plugins(option_selection::option_selection_plugin);
+            .add_plugins(option_selection::option_selection_plugin)
+            .configure_sets(
+                Update,
+                ExampleYarnSpinnerDialogueViewSystemSet.before(LanguageFontSystemSet),
+            );
     }
 }
 
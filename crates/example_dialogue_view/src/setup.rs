@@ -73,6 +73,7 @@ fn setup(mut commands: Commands) {
                 },
                 DialogueNameNode,
                 Label,
+                LanguageFontText::new(font_handle::MEDIUM),
             ));
 
             parent
@@ -104,6 +105,7 @@ fn setup(mut commands: Commands) {
                             .with_style(style::standard()),
                         DialogueNode,
                         Label,
+                        LanguageFontText::new(font_handle::MEDIUM),
                     ));
                 })
                 .with_children(|parent| {
@@ -209,6 +211,7 @@ where
                         fmt_name("option text"),
                         TextBundle::from_sections(sections).with_style(style::options()),
                         Label,
+                        LanguageFontText::new(font_handle::MEDIUM),
                     ));
                 });
         }
@@ -238,7 +241,7 @@ mod style {
     }
 }
 
-mod text_style {
+pub(crate) mod text_style {
     use super::*;
     use bevy::color::palettes::css;
     pub(crate) fn standard() -> TextStyle {
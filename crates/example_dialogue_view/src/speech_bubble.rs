@@ -0,0 +1,227 @@
+use crate::assets::font_handle;
+use crate::ExampleYarnSpinnerDialogueViewSystemSet;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_yarnspinner::{events::*, prelude::*};
+
+/// An optional dialogue view that shows lines in speech bubbles floating above the entity of their speaker instead of in a dialogue box.
+/// This is common for overhead barks and NPC chatter. Mark the speaking entities with a [`DialogueSpeaker`] carrying the character name used in the Yarn files.
+///
+/// The bubbles are drawn as UI nodes placed at the on-screen position of the speaker, so they always face the camera and follow the speaker as it moves.
+/// If no entity has a matching [`DialogueSpeaker`], the entity of the character in the [`CharacterRegistry`] is used instead, if that resource exists.
+/// Lines whose character has neither are ignored by this view.
+/// This view does not advance the dialogue on its own, so either combine it with [`DialogueRunner::set_auto_advance`] for barks
+/// or with another view like [`ExampleYarnSpinnerDialogueViewPlugin`](crate::ExampleYarnSpinnerDialogueViewPlugin).
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_yarnspinner::prelude::*;
+/// use bevy_yarnspinner_example_dialogue_view::prelude::*;
+///
+/// App::new()
+///     .add_plugins(DefaultPlugins)
+///     .add_plugins(YarnSpinnerPlugin::new())
+///     .add_plugins(SpeechBubbleDialogueViewPlugin::new())
+///     .add_systems(Startup, spawn_guard);
+///
+/// fn spawn_guard(mut commands: Commands) {
+///     commands.spawn((SpatialBundle::default(), DialogueSpeaker::new("Guard")));
+/// }
+/// ```
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct SpeechBubbleDialogueViewPlugin;
+
+impl SpeechBubbleDialogueViewPlugin {
+    /// Creates a new speech bubble dialogue view
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Plugin for SpeechBubbleDialogueViewPlugin {
+    fn build(&self, app: &mut App) {
+        assert!(
+            app.is_plugin_added::<YarnSpinnerPlugin>(),
+            "YarnSpinnerPlugin must be added before SpeechBubbleDialogueViewPlugin"
+        );
+        // Loads the font without registering the assets plugin, so the view can be combined with `ExampleYarnSpinnerDialogueViewPlugin` in any order
+        crate::assets::ui_assets_plugin(app);
+        app.init_resource::<SpeechBubbleSettings>()
+            .add_systems(
+                Update,
+                (present_line_in_bubble, hide_bubbles, follow_speakers)
+                    .chain()
+                    .after(YarnSpinnerSystemSet)
+                    .in_set(ExampleYarnSpinnerDialogueViewSystemSet),
+            )
+            .configure_sets(
+                Update,
+                ExampleYarnSpinnerDialogueViewSystemSet.before(LanguageFontSystemSet),
+            );
+    }
+}
+
+/// Marks an entity as the speaker of all lines whose [`LocalizedLine::character_name`] is the given name.
+/// The entity needs a [`GlobalTransform`] for [`SpeechBubbleDialogueViewPlugin`] to place its speech bubble.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Component, Reflect)]
+#[reflect(Debug, Component, PartialEq, Hash)]
+pub struct DialogueSpeaker(pub String);
+
+impl DialogueSpeaker {
+    /// Creates a speaker for the character with the given name.
+    pub fn new(character_name: impl Into<String>) -> Self {
+        Self(character_name.into())
+    }
+}
+
+/// Configures the speech bubbles of the [`SpeechBubbleDialogueViewPlugin`].
+#[derive(Debug, Clone, Resource)]
+pub struct SpeechBubbleSettings {
+    /// The offset from the speaker's [`GlobalTransform`] to the point the bubble is anchored at, in world space. Defaults to 2 units up.
+    pub offset: Vec3,
+    /// The camera that the bubbles are projected with. If [`None`], the first active camera is used.
+    pub camera: Option<Entity>,
+    /// The maximum width of a bubble in logical pixels. Defaults to 300.
+    pub max_width: f32,
+    /// The style of the text inside a bubble.
+    pub text_style: TextStyle,
+    /// The background color of a bubble.
+    pub background_color: Color,
+}
+
+impl Default for SpeechBubbleSettings {
+    fn default() -> Self {
+        Self {
+            offset: Vec3::Y * 2.0,
+            camera: None,
+            max_width: 300.0,
+            text_style: TextStyle {
+                font: font_handle::MEDIUM,
+                font_size: 18.0,
+                color: Color::WHITE,
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.8),
+        }
+    }
+}
+
+/// The UI node of a speech bubble shown by the [`SpeechBubbleDialogueViewPlugin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct SpeechBubble {
+    /// The entity of the speaker that the bubble follows, see [`SpeechBubbleDialogueViewPlugin`].
+    pub speaker: Entity,
+    /// The [`DialogueRunner`] that presented the line.
+    pub source: Entity,
+}
+
+fn present_line_in_bubble(
+    mut commands: Commands,
+    mut line_events: EventReader<PresentLineEvent>,
+    speakers: Query<(Entity, &DialogueSpeaker)>,
+    bubbles: Query<(Entity, &SpeechBubble)>,
+    settings: Res<SpeechBubbleSettings>,
+    character_registry: Option<Res<CharacterRegistry>>,
+) {
+    let speakers_by_name: HashMap<_, _> = speakers
+        .iter()
+        .map(|(entity, speaker)| (speaker.0.as_str(), entity))
+        .collect();
+    for event in line_events.read() {
+        // Only one bubble per dialogue runner is visible at a time
+        for (bubble_entity, bubble) in bubbles.iter() {
+            if bubble.source == event.source {
+                commands.entity(bubble_entity).despawn_recursive();
+            }
+        }
+        let Some(speaker) = event.line.character_name().and_then(|name| {
+            speakers_by_name.get(name).copied().or_else(|| {
+                character_registry
+                    .as_ref()
+                    .and_then(|registry| registry.entity(name))
+            })
+        }) else {
+            continue;
+        };
+        commands
+            .spawn((
+                Name::new("Yarn Spinner speech bubble"),
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        max_width: Val::Px(settings.max_width),
+                        padding: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    },
+                    background_color: settings.background_color.into(),
+                    // Placed by `follow_speakers` before it is shown
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                SpeechBubble {
+                    speaker,
+                    source: event.source,
+                },
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    TextBundle::from_section(
+                        event.line.text_without_character_name(),
+                        settings.text_style.clone(),
+                    ),
+                    Label,
+                    LanguageFontText::new(settings.text_style.font.clone()),
+                ));
+            });
+    }
+}
+
+fn hide_bubbles(
+    mut commands: Commands,
+    mut dialogue_complete_events: EventReader<DialogueCompleteEvent>,
+    mut present_options_events: EventReader<PresentOptionsEvent>,
+    bubbles: Query<(Entity, &SpeechBubble)>,
+    speakers: Query<(), With<GlobalTransform>>,
+) {
+    let finished_sources: Vec<_> = dialogue_complete_events
+        .read()
+        .map(|event| event.source)
+        .chain(present_options_events.read().map(|event| event.source))
+        .collect();
+    for (bubble_entity, bubble) in bubbles.iter() {
+        if finished_sources.contains(&bubble.source) || !speakers.contains(bubble.speaker) {
+            commands.entity(bubble_entity).despawn_recursive();
+        }
+    }
+}
+
+fn follow_speakers(
+    mut bubbles: Query<(&SpeechBubble, &mut Style, &mut Visibility, &Node)>,
+    speakers: Query<&GlobalTransform>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform)>,
+    settings: Res<SpeechBubbleSettings>,
+) {
+    let camera = match settings.camera {
+        Some(entity) => cameras.get(entity).ok(),
+        None => cameras.iter().find(|(_, camera, _)| camera.is_active),
+    };
+    for (bubble, mut style, mut visibility, node) in bubbles.iter_mut() {
+        let viewport_position = camera.zip(speakers.get(bubble.speaker).ok()).and_then(
+            |((_, camera, camera_transform), speaker_transform)| {
+                let anchor = speaker_transform.translation() + settings.offset;
+                camera.world_to_viewport(camera_transform, anchor)
+            },
+        );
+        let Some(viewport_position) = viewport_position else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        // Centered horizontally above the anchor
+        let size = node.size();
+        style.left = Val::Px(viewport_position.x - size.x / 2.0);
+        style.top = Val::Px(viewport_position.y - size.y);
+        *visibility = Visibility::Inherited;
+    }
+}
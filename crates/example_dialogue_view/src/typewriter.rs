@@ -5,6 +5,7 @@ use crate::ExampleYarnSpinnerDialogueViewSystemSet;
 use bevy::prelude::*;
 use bevy::utils::Instant;
 use bevy_yarnspinner::{events::*, prelude::*};
+use std::collections::VecDeque;
 use unicode_segmentation::UnicodeSegmentation;
 
 pub(crate) fn typewriter_plugin(app: &mut App) {
@@ -22,18 +23,56 @@ pub(crate) fn typewriter_plugin(app: &mut App) {
             .after(YarnSpinnerSystemSet)
             .in_set(ExampleYarnSpinnerDialogueViewSystemSet),
     )
-    .add_event::<TypewriterFinishedEvent>();
+    .init_resource::<TypewriterSettings>()
+    .register_type::<TypewriterSettings>()
+    .add_event::<TypewriterFinishedEvent>()
+    .register_type::<TypewriterFinishedEvent>();
 }
 
-#[derive(Debug, Eq, PartialEq, Hash, Reflect, Event)]
-pub(crate) struct TypewriterFinishedEvent;
+/// Signals that the text of a line has been fully revealed by the typewriter, either because it finished typing
+/// or because the user fast-forwarded it.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Reflect, Event)]
+#[reflect(Debug, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct TypewriterFinishedEvent {
+    /// The ID of the line that was revealed.
+    pub line_id: LineId,
+}
+
+/// Controls how fast the typewriter reveals the text of a line. Change this resource to adjust the speed at runtime.
+///
+/// The typewriter also understands the `pause` markup, which stops the reveal for the given amount of milliseconds
+/// at its position, e.g. `Well[pause=500/] I guess so.`
+/// Pauses are skipped when the user fast-forwards the text.
+#[derive(Debug, Clone, Copy, PartialEq, Resource, Reflect)]
+#[reflect(Debug, PartialEq, Resource, Default)]
+pub struct TypewriterSettings {
+    /// The number of characters revealed per second. Defaults to `40`.
+    pub graphemes_per_second: f32,
+    /// The number of characters revealed per second after the user pressed continue before the line was fully shown.
+    /// Defaults to `120`. Set this to [`f32::INFINITY`] to instantly reveal the rest of the line instead.
+    pub fast_forward_graphemes_per_second: f32,
+}
+
+impl Default for TypewriterSettings {
+    fn default() -> Self {
+        Self {
+            graphemes_per_second: 40.0,
+            fast_forward_graphemes_per_second: 120.0,
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Resource)]
 pub(crate) struct Typewriter {
+    pub(crate) line_id: Option<LineId>,
     pub(crate) character_name: Option<String>,
     pub(crate) current_text: String,
     pub(crate) graphemes_left: Vec<String>,
     pub(crate) last_before_options: bool,
+    /// Pauses in seconds, keyed by the number of graphemes that are revealed before them. Sorted by position.
+    pauses: VecDeque<(usize, f32)>,
+    revealed_graphemes: usize,
     elapsed: f32,
     start: Instant,
     fast_typing: bool,
@@ -42,10 +81,13 @@ pub(crate) struct Typewriter {
 impl Default for Typewriter {
     fn default() -> Self {
         Self {
+            line_id: default(),
             character_name: default(),
             current_text: default(),
             graphemes_left: default(),
             last_before_options: default(),
+            pauses: default(),
+            revealed_graphemes: default(),
             elapsed: default(),
             start: Instant::now(),
             fast_typing: default(),
@@ -55,15 +97,36 @@ impl Default for Typewriter {
 
 impl Typewriter {
     pub(crate) fn set_line(&mut self, line: &LocalizedLine) {
+        // Removing the character name via `delete_range` keeps the positions of the pauses in sync with the shown text.
+        let shown_line = match line.attribute("character") {
+            Some(character) => line.delete_range(character),
+            None => line.clone(),
+        };
+        let mut pauses: Vec<_> = shown_line
+            .attributes
+            .iter()
+            .filter(|attribute| attribute.name == PAUSE_ATTRIBUTE)
+            .filter_map(|attribute| {
+                let milliseconds = match attribute.property(PAUSE_ATTRIBUTE)? {
+                    MarkupValue::Integer(milliseconds) => *milliseconds as f32,
+                    MarkupValue::Float(milliseconds) => *milliseconds,
+                    _ => return None,
+                };
+                Some((attribute.position, milliseconds / 1000.0))
+            })
+            .collect();
+        pauses.sort_by_key(|(position, _)| *position);
         *self = Self {
+            line_id: Some(line.id.clone()),
             character_name: line.character_name().map(|s| s.to_string()),
             current_text: String::new(),
-            graphemes_left: line
-                .text_without_character_name()
+            graphemes_left: shown_line
+                .text
                 .graphemes(true)
                 .map(|s| s.to_string())
                 .collect(),
             last_before_options: line.is_last_line_before_options(),
+            pauses: pauses.into(),
             ..default()
         };
     }
@@ -76,32 +139,58 @@ impl Typewriter {
         self.fast_typing = true;
     }
 
-    fn update_current_text(&mut self) {
+    fn update_current_text(&mut self, settings: &TypewriterSettings) {
         if self.is_finished() {
             return;
         }
         self.elapsed += self.start.elapsed().as_secs_f32();
         self.start = Instant::now();
-        let calculated_graphemes = (self.graphemes_per_second() * self.elapsed).floor() as usize;
-        let graphemes_left = self.graphemes_left.len();
-        let grapheme_length_to_take = (calculated_graphemes).min(graphemes_left);
-        self.elapsed -= grapheme_length_to_take as f32 / self.graphemes_per_second();
-        let graphemes_to_take = self.graphemes_left.drain(..grapheme_length_to_take);
+        let seconds_per_grapheme = 1.0 / self.graphemes_per_second(settings);
+        let mut graphemes_to_take = 0;
+        while graphemes_to_take < self.graphemes_left.len() {
+            let position = self.revealed_graphemes + graphemes_to_take;
+            if let Some(&(pause_position, pause)) = self.pauses.front() {
+                if pause_position <= position {
+                    if !self.fast_typing {
+                        if self.elapsed < pause {
+                            break;
+                        }
+                        self.elapsed -= pause;
+                    }
+                    self.pauses.pop_front();
+                    continue;
+                }
+            }
+            if self.elapsed < seconds_per_grapheme {
+                break;
+            }
+            self.elapsed -= seconds_per_grapheme;
+            graphemes_to_take += 1;
+        }
+        self.revealed_graphemes += graphemes_to_take;
+        let graphemes_to_take = self.graphemes_left.drain(..graphemes_to_take);
         self.current_text.extend(graphemes_to_take);
+        if self.graphemes_left.is_empty() {
+            self.elapsed = 0.0;
+        }
     }
 
-    fn graphemes_per_second(&self) -> f32 {
+    fn graphemes_per_second(&self, settings: &TypewriterSettings) -> f32 {
         if self.fast_typing {
-            120.0
+            settings.fast_forward_graphemes_per_second
         } else {
-            40.0
+            settings.graphemes_per_second
         }
     }
 }
 
+/// The name of the self-closing markup that pauses the typewriter, as well as of its property holding the duration in milliseconds.
+const PAUSE_ATTRIBUTE: &str = "pause";
+
 fn write_text(
     mut text: Query<&mut Text, With<DialogueNode>>,
     mut typewriter: ResMut<Typewriter>,
+    typewriter_settings: Res<TypewriterSettings>,
     option_selection: Option<Res<OptionSelection>>,
     mut speaker_change_events: EventWriter<SpeakerChangeEvent>,
     mut root_visibility: Query<&mut Visibility, With<UiRootNode>>,
@@ -118,7 +207,7 @@ fn write_text(
         *root_visibility.single_mut() = Visibility::Inherited;
         // If this is last before options, the `OptionSelection` will make the visibility inherited as soon as it's ready instead
     }
-    typewriter.update_current_text();
+    typewriter.update_current_text(&typewriter_settings);
     if typewriter.is_finished() {
         if let Some(name) = typewriter.character_name.as_deref() {
             speaker_change_events.send(SpeakerChangeEvent {
@@ -137,8 +226,12 @@ fn show_continue(
     typewriter: Res<Typewriter>,
     mut visibility: Query<&mut Visibility, With<DialogueContinueNode>>,
     mut typewriter_finished_event: EventReader<TypewriterFinishedEvent>,
+    mut dialogue_runners: Query<&mut DialogueRunner>,
 ) {
     for _event in typewriter_finished_event.read() {
+        for mut dialogue_runner in dialogue_runners.iter_mut() {
+            dialogue_runner.release_auto_advance();
+        }
         if !typewriter.last_before_options {
             let mut visibility = visibility.single_mut();
             *visibility = Visibility::Inherited;
@@ -176,7 +269,9 @@ fn send_finished_event(
     if !typewriter.is_finished() {
         *last_finished = false;
     } else if !*last_finished {
-        events.send(TypewriterFinishedEvent);
+        if let Some(line_id) = typewriter.line_id.clone() {
+            events.send(TypewriterFinishedEvent { line_id });
+        }
         *last_finished = true;
     }
 }
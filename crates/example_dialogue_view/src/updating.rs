@@ -1,5 +1,5 @@
 use crate::option_selection::OptionSelection;
-use crate::setup::{DialogueContinueNode, DialogueNameNode, UiRootNode};
+use crate::setup::{text_style, DialogueContinueNode, DialogueNameNode, UiRootNode};
 use crate::typewriter::{self, Typewriter};
 use crate::ExampleYarnSpinnerDialogueViewSystemSet;
 use bevy::prelude::*;
@@ -57,19 +57,39 @@ fn present_line(
     mut speaker_change_events: EventWriter<SpeakerChangeEvent>,
     mut typewriter: ResMut<Typewriter>,
     mut name_node: Query<&mut Text, With<DialogueNameNode>>,
+    mut dialogue_runners: Query<&mut DialogueRunner>,
+    character_registry: Option<Res<CharacterRegistry>>,
 ) {
     for event in line_events.read() {
-        let name = if let Some(name) = event.line.character_name() {
+        let mut name_text = name_node.single_mut();
+        let name_section = &mut name_text.sections[0];
+        name_section.style.color = text_style::name().color;
+        name_section.value = if let Some(name) = event.line.character_name() {
             speaker_change_events.send(SpeakerChangeEvent {
                 character_name: name.to_string(),
                 speaking: true,
             });
-            name.to_string()
+            match character_registry.as_ref() {
+                Some(registry) => {
+                    if let Some(color) = registry.get(name).and_then(|character| character.color) {
+                        name_section.style.color = color;
+                    }
+                    let language = dialogue_runners
+                        .get(event.source)
+                        .ok()
+                        .and_then(|dialogue_runner| dialogue_runner.text_language());
+                    registry.display_name(name, language.as_ref()).to_string()
+                }
+                None => name.to_string(),
+            }
         } else {
             String::new()
         };
-        name_node.single_mut().sections[0].value = name;
         typewriter.set_line(&event.line);
+        if let Ok(mut dialogue_runner) = dialogue_runners.get_mut(event.source) {
+            // Auto-advance only starts counting once the typewriter has revealed the whole line
+            dialogue_runner.hold_auto_advance();
+        }
     }
 }
 
@@ -6,9 +6,11 @@ use syn::{
     parse::{Parse, ParseStream},
     parse_macro_input,
     token::Comma,
-    Ident, LitInt, Result,
+    Ident, ItemFn, LitInt, Result,
 };
 
+mod yarn_fn;
+
 struct AllTuples {
     macro_ident: Ident,
     start: usize,
@@ -72,3 +74,24 @@ pub fn all_tuples(input: TokenStream) -> TokenStream {
         )*
     })
 }
+
+/// Generates the glue for registering a function in a `Library`.
+///
+/// Next to the function, this creates a struct of the same name implementing `YarnFnDefinition`,
+/// which can be passed to `Library::add_yarn_fn` or the `add_module!` macro.
+/// The function is registered under its own name, or under the one given by `#[yarn_fn(name = "...")]`.
+/// Its doc comment is kept as documentation for Yarn, and `#[yarn_fn(deprecated = "...")]` marks it as deprecated.
+/// Both are registered as the function's `YarnFnMetadata`.
+///
+/// The generated code refers to `::yarnspinner::core`. When depending on Yarn Spinner through another crate,
+/// pass the path to the module exporting `YarnFnDefinition` and `Library` with `#[yarn_fn(crate = ...)]`.
+#[proc_macro_attribute]
+pub fn yarn_fn(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    let mut args = yarn_fn::YarnFnArgs::default();
+    let parser = syn::meta::parser(|meta| args.parse(meta));
+    parse_macro_input!(attribute with parser);
+    let function = parse_macro_input!(item as ItemFn);
+    yarn_fn::expand(args, function)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
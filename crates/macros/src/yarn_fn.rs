@@ -0,0 +1,146 @@
+//! Not part of the original implementation. Generates the glue needed to register a function with `Library::add_yarn_fn`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    meta::ParseNestedMeta, spanned::Spanned, Error, Expr, ExprLit, FnArg, ItemFn, Lit, LitStr,
+    Meta, Pat, Path, Result, Type,
+};
+
+#[derive(Default)]
+pub(crate) struct YarnFnArgs {
+    name: Option<LitStr>,
+    deprecated: Option<LitStr>,
+    krate: Option<Path>,
+}
+
+impl YarnFnArgs {
+    pub(crate) fn parse(&mut self, meta: ParseNestedMeta) -> Result<()> {
+        if meta.path.is_ident("name") {
+            self.name = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("deprecated") {
+            self.deprecated = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("crate") {
+            self.krate = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta
+                .error("unsupported yarn_fn argument, expected `name`, `deprecated` or `crate`"))
+        }
+    }
+}
+
+pub(crate) fn expand(args: YarnFnArgs, function: ItemFn) -> Result<TokenStream> {
+    let signature = &function.sig;
+    if !signature.generics.params.is_empty() {
+        return Err(Error::new(
+            signature.generics.span(),
+            "yarn_fn cannot be used on generic functions",
+        ));
+    }
+    if let Some(receiver) = signature.receiver() {
+        return Err(Error::new(
+            receiver.span(),
+            "yarn_fn cannot be used on methods",
+        ));
+    }
+
+    let ident = &signature.ident;
+    let name = args
+        .name
+        .map(|name| name.value())
+        .unwrap_or_else(|| ident.to_string());
+    let krate = args
+        .krate
+        .unwrap_or_else(|| syn::parse_quote!(::yarnspinner::core));
+    let parameter_names = signature
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            FnArg::Typed(pattern) if !is_context(&pattern.ty) => Some(&pattern.pat),
+            _ => None,
+        })
+        .map(|pattern| match pattern.as_ref() {
+            Pat::Ident(pattern) => pattern.ident.to_string(),
+            _ => "_".to_owned(),
+        });
+    let docs = optional(docs(&function));
+    let deprecation = optional(args.deprecated.map(|message| message.value()));
+    let vis = &function.vis;
+    let struct_doc = format!("Registers the Yarn function [`{ident}`] as `{name}`.");
+
+    Ok(quote! {
+        #function
+
+        #[doc = #struct_doc]
+        #[allow(non_camel_case_types)]
+        #vis struct #ident {}
+
+        impl #krate::YarnFnDefinition for #ident {
+            const NAME: &'static str = #name;
+            const PARAMETER_NAMES: &'static [&'static str] = &[#(#parameter_names),*];
+            const DOCS: ::core::option::Option<&'static str> = #docs;
+            const DEPRECATION: ::core::option::Option<&'static str> = #deprecation;
+
+            fn register(library: &mut #krate::Library) {
+                let metadata = #krate::YarnFnMetadata {
+                    docs: <Self as #krate::YarnFnDefinition>::DOCS.map(::core::convert::Into::into),
+                    deprecation: <Self as #krate::YarnFnDefinition>::DEPRECATION
+                        .map(::core::convert::Into::into),
+                };
+                library.add_function_with_metadata(
+                    <Self as #krate::YarnFnDefinition>::NAME,
+                    #ident,
+                    metadata,
+                );
+            }
+        }
+    })
+}
+
+fn optional(value: Option<String>) -> TokenStream {
+    match value {
+        Some(value) => quote! { ::core::option::Option::Some(#value) },
+        None => quote! { ::core::option::Option::None },
+    }
+}
+
+/// Whether the parameter is a `&YarnFnContext`, which is not passed from Yarn.
+fn is_context(ty: &Type) -> bool {
+    let Type::Reference(reference) = ty else {
+        return false;
+    };
+    let Type::Path(path) = reference.elem.as_ref() else {
+        return false;
+    };
+    path.path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "YarnFnContext")
+}
+
+/// Joins the lines of the function's doc comment, if it has one.
+fn docs(function: &ItemFn) -> Option<String> {
+    let lines: Vec<_> = function
+        .attrs
+        .iter()
+        .filter_map(|attribute| match &attribute.meta {
+            Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(line),
+                    ..
+                }) => Some(line.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .map(|line| {
+            line.strip_prefix(' ')
+                .map(ToOwned::to_owned)
+                .unwrap_or(line)
+        })
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n").trim().to_owned())
+}
@@ -2,7 +2,8 @@
 
 pub(crate) use self::default_analysers::*;
 pub use self::{context::*, diagnosis::*};
-use std::fmt::Debug;
+use crate::prelude::*;
+use core::fmt::Debug;
 use yarnspinner_core::prelude::*;
 
 mod context;
@@ -2,7 +2,6 @@
 //! which was split into multiple files.
 
 use crate::prelude::*;
-use std::collections::HashSet;
 use yarnspinner_core::prelude::*;
 
 #[derive(Debug, Default)]
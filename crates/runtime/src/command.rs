@@ -4,7 +4,6 @@
 //! The original delegates command parsing to the Unity plugin, but we think it's foundational enough to do it directly in the runtime.
 
 use crate::markup::normalize;
-#[cfg(any(feature = "bevy", feature = "serde"))]
 use crate::prelude::*;
 use yarnspinner_core::prelude::YarnValue;
 
@@ -87,7 +86,7 @@ fn split_command_text(input: &str) -> Vec<String> {
                     // We've reached the end of a run of visible
                     // characters. Add this run to the result list and
                     // prepare for the next one.
-                    results.push(std::mem::take(&mut current_component));
+                    results.push(core::mem::take(&mut current_component));
                 } else {
                     // We encountered a whitespace character, but
                     // didn't have any characters queued up. Skip this
@@ -133,7 +132,7 @@ fn split_command_text(input: &str) -> Vec<String> {
                         }
                     }
                 }
-                results.push(std::mem::take(&mut current_component));
+                results.push(core::mem::take(&mut current_component));
             }
             _ => {
                 current_component.push(char);
@@ -0,0 +1,82 @@
+//! Stand-ins for the items of the `std` prelude, collections and synchronization primitives, sourced from `alloc` and `spin`
+//! so that the crate also builds without the `std` feature.
+
+pub(crate) use alloc::borrow::ToOwned;
+pub(crate) use alloc::boxed::Box;
+pub(crate) use alloc::string::{String, ToString};
+pub(crate) use alloc::sync::Arc;
+pub(crate) use alloc::vec::Vec;
+pub(crate) use alloc::{format, vec};
+use core::ops::{Deref, DerefMut};
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{HashMap, HashSet};
+
+#[cfg(not(feature = "std"))]
+pub(crate) use hashbrown::{HashMap, HashSet};
+
+/// A reader-writer lock backed by [`std::sync::RwLock`] when `std` is available and by a spinlock otherwise.
+///
+/// A poisoned lock is treated as a bug and panics, just like the unwrapping of [`std::sync::LockResult`]s would.
+#[derive(Debug, Default)]
+pub(crate) struct RwLock<T>(
+    #[cfg(feature = "std")] std::sync::RwLock<T>,
+    #[cfg(not(feature = "std"))] spin::RwLock<T>,
+);
+
+impl<T> RwLock<T> {
+    pub(crate) fn read(&self) -> impl Deref<Target = T> + '_ {
+        #[cfg(feature = "std")]
+        {
+            self.0.read().unwrap()
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            self.0.read()
+        }
+    }
+
+    pub(crate) fn write(&self) -> impl DerefMut<Target = T> + '_ {
+        #[cfg(feature = "std")]
+        {
+            self.0.write().unwrap()
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            self.0.write()
+        }
+    }
+}
+
+/// A mutual exclusion lock backed by [`std::sync::Mutex`] when `std` is available and by a spinlock otherwise.
+///
+/// Unlike [`RwLock`], it is [`Sync`] even if `T` is only [`Send`].
+/// A poisoned lock is treated as a bug and panics, just like the unwrapping of [`std::sync::LockResult`]s would.
+#[derive(Debug, Default)]
+pub(crate) struct Mutex<T>(
+    #[cfg(feature = "std")] std::sync::Mutex<T>,
+    #[cfg(not(feature = "std"))] spin::Mutex<T>,
+);
+
+impl<T> Mutex<T> {
+    pub(crate) fn new(value: T) -> Self {
+        #[cfg(feature = "std")]
+        {
+            Self(std::sync::Mutex::new(value))
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            Self(spin::Mutex::new(value))
+        }
+    }
+
+    pub(crate) fn lock(&self) -> impl DerefMut<Target = T> + '_ {
+        #[cfg(feature = "std")]
+        {
+            self.0.lock().unwrap()
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            self.0.lock()
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! Not part of the original Yarn Spinner. Records which parts of the dialogue were exercised, e.g. by test runs or playtests.
+
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+use core::fmt::{self, Display};
+use yarnspinner_core::prelude::*;
+
+/// Which lines, options and branches a [`Dialogue`] ran through, created by [`Dialogue::start_recording_coverage`] and [`Dialogue::stop_recording_coverage`].
+///
+/// Coverage of several runs can be combined with [`DialogueCoverage::merge`] and evaluated against a [`Program`] with [`CoverageReport::new`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DialogueCoverage {
+    /// The coverage of every node that was entered, by node name.
+    pub nodes: BTreeMap<String, NodeCoverage>,
+}
+
+/// The part of a [`DialogueCoverage`] recorded in a single node.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NodeCoverage {
+    /// How often the node was entered.
+    pub visits: usize,
+    /// How often each line was delivered.
+    pub lines: BTreeMap<LineId, usize>,
+    /// How often each option was presented, regardless of whether it was available.
+    pub options_presented: BTreeMap<LineId, usize>,
+    /// How often each option was selected.
+    pub options_selected: BTreeMap<LineId, usize>,
+    /// The outcomes of every condition that was evaluated, by the index of its [`OpCode::JumpIfFalse`] instruction.
+    pub branches: BTreeMap<usize, BranchCoverage>,
+}
+
+/// How often a condition of a [`NodeCoverage`] evaluated to `true` and to `false`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BranchCoverage {
+    /// How often the condition was `true`.
+    pub times_true: usize,
+    /// How often the condition was `false`.
+    pub times_false: usize,
+}
+
+impl DialogueCoverage {
+    /// Adds the coverage recorded in another run to this one.
+    pub fn merge(&mut self, other: &DialogueCoverage) -> &mut Self {
+        for (node_name, other) in &other.nodes {
+            let node = self.nodes.entry(node_name.clone()).or_default();
+            node.visits += other.visits;
+            merge_counts(&mut node.lines, &other.lines);
+            merge_counts(&mut node.options_presented, &other.options_presented);
+            merge_counts(&mut node.options_selected, &other.options_selected);
+            for (index, branch) in &other.branches {
+                let node_branch = node.branches.entry(*index).or_default();
+                node_branch.times_true += branch.times_true;
+                node_branch.times_false += branch.times_false;
+            }
+        }
+        self
+    }
+}
+
+fn merge_counts(counts: &mut BTreeMap<LineId, usize>, other: &BTreeMap<LineId, usize>) {
+    for (line_id, count) in other {
+        *counts.entry(line_id.clone()).or_default() += count;
+    }
+}
+
+/// The coverage of every node of a [`Program`] by a [`DialogueCoverage`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CoverageReport {
+    /// The coverage of each node, sorted by node name.
+    pub nodes: Vec<NodeCoverageReport>,
+}
+
+/// The coverage of a single node in a [`CoverageReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NodeCoverageReport {
+    /// The name of the node.
+    pub node_name: String,
+    /// Whether the node was entered at all.
+    pub visited: bool,
+    /// How many of the node's lines were delivered.
+    pub lines: CoverageCount,
+    /// How many of the node's options were selected.
+    pub options: CoverageCount,
+    /// How many outcomes of the node's conditions occurred. Every condition has two outcomes: `true` and `false`.
+    pub branches: CoverageCount,
+    /// The lines that were never delivered, in the order they appear in the node.
+    pub unseen_lines: Vec<LineId>,
+    /// The options that were never selected, in the order they appear in the node.
+    pub unselected_options: Vec<LineId>,
+}
+
+/// How many of a number of things were covered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CoverageCount {
+    /// How many things were covered.
+    pub covered: usize,
+    /// How many things there are.
+    pub total: usize,
+}
+
+impl CoverageReport {
+    /// Compares the lines, options and conditions of every node in the program with the recorded coverage.
+    /// Coverage of nodes that are not part of the program is ignored.
+    pub fn new(program: &Program, coverage: &DialogueCoverage) -> Self {
+        let empty_coverage = NodeCoverage::default();
+        let mut nodes: Vec<_> = program
+            .nodes
+            .values()
+            .map(|node| {
+                let node_coverage = coverage.nodes.get(&node.name).unwrap_or(&empty_coverage);
+                NodeCoverageReport::new(node, node_coverage)
+            })
+            .collect();
+        nodes.sort_by(|a, b| a.node_name.cmp(&b.node_name));
+        Self { nodes }
+    }
+
+    /// The combined coverage of all nodes.
+    pub fn total(&self) -> NodeCoverageReport {
+        self.nodes.iter().fold(
+            NodeCoverageReport {
+                node_name: "Total".to_owned(),
+                visited: self.nodes.iter().any(|node| node.visited),
+                ..Default::default()
+            },
+            |mut total, node| {
+                total.lines += node.lines;
+                total.options += node.options;
+                total.branches += node.branches;
+                total.unseen_lines.extend(node.unseen_lines.iter().cloned());
+                total
+                    .unselected_options
+                    .extend(node.unselected_options.iter().cloned());
+                total
+            },
+        )
+    }
+}
+
+impl NodeCoverageReport {
+    fn new(node: &Node, coverage: &NodeCoverage) -> Self {
+        let mut report = Self {
+            node_name: node.name.clone(),
+            visited: coverage.visits > 0,
+            ..Default::default()
+        };
+        let mut lines = Vec::new();
+        let mut options = Vec::new();
+        for (index, instruction) in node.instructions.iter().enumerate() {
+            match instruction.opcode.try_into() {
+                Ok(OpCode::RunLine) => push_unique(&mut lines, instruction),
+                Ok(OpCode::AddOption) => push_unique(&mut options, instruction),
+                Ok(OpCode::JumpIfFalse) => {
+                    let branch = coverage.branches.get(&index).copied().unwrap_or_default();
+                    report.branches.total += 2;
+                    report.branches.covered +=
+                        usize::from(branch.times_true > 0) + usize::from(branch.times_false > 0);
+                }
+                _ => {}
+            }
+        }
+
+        report.lines.total = lines.len();
+        report.unseen_lines = lines
+            .into_iter()
+            .filter(|line_id| !coverage.lines.contains_key(line_id))
+            .collect();
+        report.lines.covered = report.lines.total - report.unseen_lines.len();
+
+        report.options.total = options.len();
+        report.unselected_options = options
+            .into_iter()
+            .filter(|line_id| !coverage.options_selected.contains_key(line_id))
+            .collect();
+        report.options.covered = report.options.total - report.unselected_options.len();
+        report
+    }
+
+    /// The share of lines, options and branch outcomes that were covered, in percent.
+    /// A node without any of them counts as fully covered if it was visited.
+    pub fn percentage(&self) -> f32 {
+        let covered = self.lines.covered + self.options.covered + self.branches.covered;
+        let total = self.lines.total + self.options.total + self.branches.total;
+        if total == 0 {
+            if self.visited {
+                100.0
+            } else {
+                0.0
+            }
+        } else {
+            covered as f32 / total as f32 * 100.0
+        }
+    }
+}
+
+fn push_unique(line_ids: &mut Vec<LineId>, instruction: &Instruction) {
+    let line_id: String = instruction.read_operand(0);
+    let line_id = LineId(line_id);
+    if !line_ids.contains(&line_id) {
+        line_ids.push(line_id);
+    }
+}
+
+impl core::ops::AddAssign for CoverageCount {
+    fn add_assign(&mut self, other: Self) {
+        self.covered += other.covered;
+        self.total += other.total;
+    }
+}
+
+impl Display for CoverageCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.covered, self.total)
+    }
+}
+
+impl Display for CoverageReport {
+    /// Prints a table of the coverage of every node, followed by the lines that were never delivered.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.total();
+        let name_width = self
+            .nodes
+            .iter()
+            .map(|node| node.node_name.chars().count())
+            .chain([total.node_name.len(), "Node".len()])
+            .max()
+            .unwrap_or_default();
+        writeln!(
+            f,
+            "{:name_width$}  {:>9}  {:>9}  {:>9}  {:>8}",
+            "Node", "Lines", "Options", "Branches", "Coverage"
+        )?;
+        for node in self.nodes.iter().chain([&total]) {
+            writeln!(
+                f,
+                "{:name_width$}  {:>9}  {:>9}  {:>9}  {:>7.1}%",
+                node.node_name,
+                node.lines.to_string(),
+                node.options.to_string(),
+                node.branches.to_string(),
+                node.percentage()
+            )?;
+        }
+
+        let unseen_lines: Vec<_> = self
+            .nodes
+            .iter()
+            .flat_map(|node| {
+                node.unseen_lines
+                    .iter()
+                    .map(move |line_id| (&node.node_name, line_id))
+            })
+            .collect();
+        if !unseen_lines.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Never seen lines:")?;
+            for (node_name, line_id) in unseen_lines {
+                writeln!(f, "  {node_name}: {line_id}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -2,10 +2,10 @@
 
 use crate::markup::{DialogueTextProcessor, LineParser, MarkupParseError};
 use crate::prelude::*;
+use core::error::Error;
+use core::fmt::{self, Debug, Display};
+use core::task::{self, Poll};
 use log::error;
-use std::collections::HashMap;
-use std::error::Error;
-use std::fmt::{self, Debug, Display};
 use yarnspinner_core::prelude::*;
 
 /// Co-ordinates the execution of Yarn programs.
@@ -15,10 +15,11 @@ use yarnspinner_core::prelude::*;
 pub struct Dialogue {
     vm: VirtualMachine,
     language_code: Option<Language>,
+    line_metadata: HashMap<LineId, Vec<String>>,
 }
 
 #[allow(missing_docs)]
-pub type Result<T> = std::result::Result<T, DialogueError>;
+pub type Result<T> = core::result::Result<T, DialogueError>;
 
 #[allow(missing_docs)]
 #[derive(Debug)]
@@ -34,6 +35,8 @@ pub enum DialogueError {
     },
     UnexpectedOptionSelectionError,
     ContinueOnOptionSelectionError,
+    ContinueOnPendingFunctionCallError,
+    NoPendingFunctionCallError,
     NoNodeSelectedOnContinue,
     NoProgramLoaded,
     InvalidNode {
@@ -44,6 +47,18 @@ pub enum DialogueError {
         function_name: String,
         library: Library,
     },
+    FunctionCallError {
+        function_name: String,
+        node_name: String,
+        instruction_index: usize,
+        line_info: Option<Box<LineInfo>>,
+        error: YarnFnError,
+    },
+    TraceMismatch {
+        expected: String,
+        found: Option<TraceStep>,
+    },
+    ProgramCombineError(ProgramCombineError),
 }
 
 impl Error for DialogueError {
@@ -52,6 +67,8 @@ impl Error for DialogueError {
         match self {
             MarkupParseError(e) => e.source(),
             VariableStorageError(e) => e.source(),
+            FunctionCallError { error, .. } => Some(error),
+            ProgramCombineError(e) => Some(e),
             _ => None,
         }
     }
@@ -66,11 +83,20 @@ impl Display for DialogueError {
             InvalidOptionIdError { selected_option_id, max_id } => write!(f, "{selected_option_id:?} is not a valid option ID (expected a number between 0 and {max_id}."),
             UnexpectedOptionSelectionError => f.write_str("An option was selected, but the dialogue wasn't waiting for a selection. This method should only be called after the Dialogue is waiting for the user to select an option."),
             ContinueOnOptionSelectionError => f.write_str("Dialogue was asked to continue running, but it is waiting for the user to select an option first."),
+            ContinueOnPendingFunctionCallError => f.write_str("Dialogue was asked to continue running, but it is waiting for an async function to finish first."),
+            NoPendingFunctionCallError => f.write_str("Dialogue was asked to drive an async function, but it is not waiting for one."),
             NoNodeSelectedOnContinue => f.write_str("Cannot continue running dialogue. No node has been selected."),
             NoProgramLoaded => f.write_str("No program has been loaded. Cannot continue running dialogue."),
             InvalidNode { node_name } => write!(f, "No node named \"{node_name}\" has been loaded."),
             VariableStorageError(e) => Display::fmt(e, f),
             FunctionNotFound { function_name, library } => write!(f, "Function \"{function_name}\" not found in library: {library}"),
+            FunctionCallError { function_name, node_name, instruction_index, line_info, error } => match line_info.as_deref() {
+                Some(LineInfo { file_name, position: Some(position), .. }) => write!(f, "Function \"{function_name}\" failed in node \"{node_name}\" at {file_name}:{}:{}: {error}", position.line + 1, position.character + 1),
+                _ => write!(f, "Function \"{function_name}\" failed in node \"{node_name}\" at instruction {instruction_index}: {error}"),
+            },
+            TraceMismatch { expected, found: Some(found) } => write!(f, "The replayed dialogue diverged from its trace: expected a {expected}, but the trace contains a {found}."),
+            TraceMismatch { expected, found: None } => write!(f, "The replayed dialogue diverged from its trace: expected a {expected}, but the trace has already ended."),
+            ProgramCombineError(e) => Display::fmt(e, f),
         }
     }
 }
@@ -81,6 +107,12 @@ impl From<MarkupParseError> for DialogueError {
     }
 }
 
+impl From<ProgramCombineError> for DialogueError {
+    fn from(source: ProgramCombineError) -> Self {
+        DialogueError::ProgramCombineError(source)
+    }
+}
+
 impl From<VariableStorageError> for DialogueError {
     fn from(source: VariableStorageError) -> Self {
         DialogueError::VariableStorageError(source)
@@ -100,8 +132,8 @@ impl Dialogue {
     ) -> Self {
         let mut library = Library::standard_library();
         library
-            .add_function("visited", visited(variable_storage.clone()))
-            .add_function("visited_count", visited_count(variable_storage.clone()));
+            .add_function("visited", visited)
+            .add_function("visited_count", visited_count);
 
         let dialogue_text_processor = Box::new(DialogueTextProcessor::new());
         let line_parser = LineParser::new()
@@ -112,29 +144,22 @@ impl Dialogue {
         Self {
             vm: VirtualMachine::new(library, variable_storage, line_parser, text_provider),
             language_code: Default::default(),
+            line_metadata: Default::default(),
         }
     }
 }
 
-fn visited(storage: Box<dyn VariableStorage>) -> yarn_fn_type! { impl Fn(String) -> bool } {
-    move |node: String| -> bool {
-        let name = Library::generate_unique_visited_variable_for_node(&node);
-        if let Ok(YarnValue::Number(count)) = storage.get(&name) {
-            count > 0.0
-        } else {
-            false
-        }
-    }
+fn visited(context: &YarnFnContext, node: &str) -> bool {
+    visited_count(context, node) > 0.0
 }
 
-fn visited_count(storage: Box<dyn VariableStorage>) -> yarn_fn_type! { impl Fn(String) -> f32 } {
-    move |node: String| {
-        let name = Library::generate_unique_visited_variable_for_node(&node);
-        if let Ok(YarnValue::Number(count)) = storage.get(&name) {
-            count
-        } else {
-            0.0
-        }
+/// Reads the generated tracking variable of a node. Nodes that were never visited or are not tracked count as `0.0`.
+fn visited_count(context: &YarnFnContext, node: &str) -> f64 {
+    let name = Library::generate_unique_visited_variable_for_node(node);
+    if let Some(YarnValue::Number(count)) = context.variable(&name) {
+        count
+    } else {
+        0.0
     }
 }
 
@@ -174,7 +199,54 @@ impl Dialogue {
     ) -> Option<Language> {
         let language_code = language_code.into();
         self.vm.set_language_code(language_code.clone());
-        std::mem::replace(&mut self.language_code, language_code)
+        core::mem::replace(&mut self.language_code, language_code)
+    }
+
+    /// Prepares the line the [`Dialogue`] is currently paused on once more, using the same substitutions as when it was delivered by [`Dialogue::continue_`].
+    /// Useful to present the line again after the language was changed with [`Dialogue::set_language_code`].
+    ///
+    /// Returns [`None`] if the last call to [`Dialogue::continue_`] did not stop on a line.
+    pub fn relocalize_current_line(&mut self) -> Option<Result<Line>> {
+        self.vm.prepare_current_line()
+    }
+
+    /// Registers the metadata of lines, i.e. their hashtags, so that [`Dialogue::localize_line`] can attach it.
+    /// Typically taken from the `metadata` of the compilation's string table. Replaces previously registered metadata of the same lines.
+    pub fn add_line_metadata(
+        &mut self,
+        line_metadata: impl IntoIterator<Item = (LineId, Vec<String>)>,
+    ) -> &mut Self {
+        self.line_metadata.extend(line_metadata);
+        self
+    }
+
+    /// Gets the metadata registered for a line with [`Dialogue::add_line_metadata`], if any.
+    #[must_use]
+    pub fn line_metadata(&self, line_id: &LineId) -> Option<&[String]> {
+        self.line_metadata
+            .get(line_id)
+            .map(|metadata| metadata.as_slice())
+    }
+
+    /// Registers the [`DebugInfo`] of nodes, so that errors raised while running them can point to their source, e.g. `intro.yarn:12:5`.
+    /// Typically taken from the `debug_info` of the compilation. Replaces previously registered debug info of the same nodes.
+    pub fn add_debug_info(
+        &mut self,
+        debug_info: impl IntoIterator<Item = (String, DebugInfo)>,
+    ) -> &mut Self {
+        self.vm.debug_info.extend(debug_info);
+        self
+    }
+
+    /// Turns a [`Line`] delivered by [`Dialogue::continue_`] into a [`LocalizedLine`] with its registered metadata and no assets.
+    /// Attach the host's assets with [`LocalizedLine::with_assets`].
+    #[must_use]
+    pub fn localize_line(&self, line: Line) -> LocalizedLine {
+        let metadata = self
+            .line_metadata(&line.id)
+            .map(<[String]>::to_vec)
+            .unwrap_or_default();
+        LocalizedLine::from_line(line, metadata, ())
     }
 
     /// Gets the [`Library`] that this Dialogue uses to locate functions.
@@ -192,20 +264,62 @@ impl Dialogue {
         &mut self.vm.library
     }
 
-    /// Gets whether [`Dialogue::next`] is able able to return [`DialogueEvent::LineHints`] events.
+    /// Gets whether [`Dialogue::next`] is able to return [`DialogueEvent::LineHints`] events.
     /// The default is `false`.
     #[must_use]
     pub fn line_hints_enabled(&self) -> bool {
         self.vm.line_hints_enabled
     }
 
-    /// Mutable gets whether [`Dialogue::next`] is able able to return [`DialogueEvent::LineHints`] events.
+    /// Mutable gets whether [`Dialogue::next`] is able to return [`DialogueEvent::LineHints`] events.
     /// The default is `false`.
     pub fn set_line_hints_enabled(&mut self, enabled: bool) -> &mut Self {
         self.vm.line_hints_enabled = enabled;
         self
     }
 
+    /// Gets how many lines a [`DialogueEvent::LineHints`] covers.
+    /// The default is [`LineHintsGranularity::Node`].
+    #[must_use]
+    pub fn line_hints_granularity(&self) -> LineHintsGranularity {
+        self.vm.line_hints_granularity
+    }
+
+    /// Sets how many lines a [`DialogueEvent::LineHints`] covers, see [`LineHintsGranularity`].
+    /// Has no effect unless [`Dialogue::line_hints_enabled`] is set.
+    pub fn set_line_hints_granularity(&mut self, granularity: LineHintsGranularity) -> &mut Self {
+        self.vm.line_hints_granularity = granularity;
+        self
+    }
+
+    /// Gets whether [`Dialogue::next`] is able to return [`DialogueEvent::ExecutionStateChanged`] events.
+    /// The default is `false`.
+    #[must_use]
+    pub fn execution_state_events_enabled(&self) -> bool {
+        self.vm.execution_state_events_enabled
+    }
+
+    /// Mutable gets whether [`Dialogue::next`] is able to return [`DialogueEvent::ExecutionStateChanged`] events.
+    /// The default is `false`.
+    pub fn set_execution_state_events_enabled(&mut self, enabled: bool) -> &mut Self {
+        self.vm.execution_state_events_enabled = enabled;
+        self
+    }
+
+    /// Gets whether [`Dialogue::next`] is able to return [`DialogueEvent::VariableChanged`] events.
+    /// The default is `false`.
+    #[must_use]
+    pub fn variable_changed_events_enabled(&self) -> bool {
+        self.vm.variable_changed_events_enabled
+    }
+
+    /// Mutable gets whether [`Dialogue::next`] is able to return [`DialogueEvent::VariableChanged`] events.
+    /// The default is `false`.
+    pub fn set_variable_changed_events_enabled(&mut self, enabled: bool) -> &mut Self {
+        self.vm.variable_changed_events_enabled = enabled;
+        self
+    }
+
     /// Gets the currently registered [`TextProvider`].
     pub fn text_provider(&self) -> &dyn TextProvider {
         self.vm.text_provider()
@@ -216,6 +330,36 @@ impl Dialogue {
         self.vm.text_provider_mut()
     }
 
+    /// Registers an [`OptionsProcessor`] that is allowed to reorder and remove options before they are delivered through [`DialogueEvent::Options`].
+    /// Replaces any previously registered processor.
+    pub fn set_options_processor(
+        &mut self,
+        options_processor: impl OptionsProcessor + 'static,
+    ) -> &mut Self {
+        self.vm.options_processor = Some(Box::new(options_processor));
+        self
+    }
+
+    /// Removes the currently registered [`OptionsProcessor`], if any, so that options are delivered in the order they were written in again.
+    pub fn remove_options_processor(&mut self) -> Option<Box<dyn OptionsProcessor>> {
+        self.vm.options_processor.take()
+    }
+
+    /// Sets what happens when a Yarn script calls a function that is not in the [`Library`]. Defaults to [`MissingFunctionBehavior::Error`].
+    pub fn set_missing_function_behavior(
+        &mut self,
+        missing_function_behavior: MissingFunctionBehavior,
+    ) -> &mut Self {
+        self.vm.missing_function_behavior = missing_function_behavior;
+        self
+    }
+
+    /// Gets what happens when a Yarn script calls a function that is not in the [`Library`], see [`Dialogue::set_missing_function_behavior`].
+    #[must_use]
+    pub fn missing_function_behavior(&self) -> &MissingFunctionBehavior {
+        &self.vm.missing_function_behavior
+    }
+
     /// Gets the currently registered [`VariableStorage`].
     pub fn variable_storage(&self) -> &dyn VariableStorage {
         self.vm.variable_storage()
@@ -225,6 +369,48 @@ impl Dialogue {
     pub fn variable_storage_mut(&mut self) -> &mut dyn VariableStorage {
         self.vm.variable_storage_mut()
     }
+
+    /// Returns how much content this dialogue delivered since it was created or since [`Dialogue::reset_metrics`] was last called.
+    #[must_use]
+    pub fn metrics(&self) -> DialogueMetrics {
+        self.vm.metrics
+    }
+
+    /// Resets all counters returned by [`Dialogue::metrics`] to zero.
+    pub fn reset_metrics(&mut self) -> &mut Self {
+        self.vm.metrics = DialogueMetrics::default();
+        self
+    }
+
+    /// Returns typed access to the variables of this dialogue, which shares the registered [`VariableStorage`].
+    #[must_use]
+    pub fn story_state(&self) -> StoryState {
+        StoryState::new(self.variable_storage().clone_shallow())
+    }
+
+    /// Gets the number of times the node `node_name` has been visited, i.e. the value the Yarn function `visited_count` would return for it.
+    ///
+    /// Nodes that were never visited, don't exist or are not tracked report `0`.
+    /// This reads the generated tracking variable from the [`VariableStorage`], so save systems and debugging tools
+    /// don't need to know how that variable is named.
+    #[must_use]
+    pub fn visit_count(&self, node_name: &str) -> usize {
+        let context = YarnFnContext::new().with_variables(&self.vm.variable_storage);
+        visited_count(&context, node_name) as usize
+    }
+
+    /// Sets the number of times the node `node_name` has been visited, which will be reported by the Yarn functions `visited` and `visited_count`.
+    /// See [`Dialogue::visit_count`].
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the [`VariableStorage`] refuses to store the generated tracking variable.
+    pub fn set_visit_count(&mut self, node_name: &str, visit_count: usize) -> Result<&mut Self> {
+        let name = Library::generate_unique_visited_variable_for_node(node_name);
+        self.variable_storage_mut()
+            .set(name, (visit_count as f64).into())?;
+        Ok(self)
+    }
 }
 
 // VM proxy
@@ -252,9 +438,11 @@ impl Dialogue {
     }
 
     fn extend_variable_storage_from(&mut self, program: &Program) {
+        let variable_storage = self.variable_storage();
         let initial: HashMap<String, YarnValue> = program
             .initial_values
             .iter()
+            .filter(|(k, _)| !variable_storage.contains(k))
             .map(|(k, v)| (k.clone(), v.clone().into()))
             .collect();
 
@@ -267,26 +455,48 @@ impl Dialogue {
         }
     }
 
-    /// Sets or replaces the [`Dialogue`]'s current [`Program`]. The program is replaced, all current state is reset.
+    /// Sets or replaces the [`Dialogue`]'s current [`Program`].
+    ///
+    /// The [`VariableStorage`] is preserved, only variables it does not contain yet are populated with the initial values of the new program.
+    /// If the node that is currently running still exists in the new program with the same [`Node::content_hash`],
+    /// the current execution position is preserved as well, so that live-edited programs can be swapped in mid-dialogue.
+    /// Otherwise, the execution state is reset.
     pub fn replace_program(&mut self, program: Program) -> &mut Self {
-        self.vm.program.replace(program.clone());
-        self.vm.reset_state();
         self.extend_variable_storage_from(&program);
+        self.vm.replace_program(program);
         self
     }
 
     /// Merges the currently set [`Program`] with the given one. If there is no program set, the given one is set.
+    ///
+    /// Only variables the [`VariableStorage`] does not contain yet are populated with the initial values of the given program,
+    /// so that [`Dialogue`]s sharing a storage, e.g. through a [`ScopedVariableStorage`], don't reset each other's variables.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the programs conflict, see [`Dialogue::try_add_program`] for a non-panicking version.
     pub fn add_program(&mut self, program: Program) -> &mut Self {
+        self.try_add_program(program)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Merges the currently set [`Program`] with the given one like [`Dialogue::add_program`].
+    ///
+    /// ## Errors
+    ///
+    /// Returns a [`DialogueError::ProgramCombineError`] listing all conflicts if both programs contain nodes with the same name
+    /// or different initial values for the same variable. In that case, the current program is left untouched.
+    /// Use [`Program::with_namespace`] to load programs that reuse node names, e.g. mods.
+    pub fn try_add_program(&mut self, program: Program) -> Result<&mut Self> {
         if let Some(existing_program) = self.vm.program.as_mut() {
-            *existing_program =
-                Program::combine(vec![existing_program.clone(), program.clone()]).unwrap();
+            *existing_program = Program::try_combine([existing_program.clone(), program.clone()])?;
         } else {
             self.vm.program.replace(program.clone());
             self.vm.reset_state();
         }
         self.extend_variable_storage_from(&program);
 
-        self
+        Ok(self)
     }
 
     /// Prepares the [`Dialogue`] that the user intends to start running a node.
@@ -300,7 +510,10 @@ impl Dialogue {
     ///
     /// Returns an error if no node with the value of `node_name` has been loaded.
     pub fn set_node(&mut self, node_name: impl Into<String>) -> Result<&mut Self> {
-        self.vm.set_node(node_name)?;
+        let node_name = node_name.into();
+        self.vm.set_node(&node_name)?;
+        self.vm
+            .record_trace_step(TraceStep::NodeStarted { node_name });
         Ok(self)
     }
 
@@ -322,6 +535,54 @@ impl Dialogue {
         self.vm.stop()
     }
 
+    /// Starts recording every decision point of this [`Dialogue`] into a [`DialogueTrace`]:
+    /// started nodes, continuations, selected options, variable reads and function calls, including the ones drawing random numbers.
+    /// Discards any trace that was being recorded before.
+    pub fn start_recording_trace(&mut self) -> &mut Self {
+        self.vm.start_recording_trace();
+        self
+    }
+
+    /// Stops recording and returns the [`DialogueTrace`] recorded since the last call to [`Dialogue::start_recording_trace`].
+    /// Returns `None` if no trace was being recorded.
+    pub fn stop_recording_trace(&mut self) -> Option<DialogueTrace> {
+        self.vm.stop_recording_trace()
+    }
+
+    /// Starts recording which lines, options and conditions of each node this [`Dialogue`] runs through into a [`DialogueCoverage`].
+    /// Discards any coverage that was being recorded before.
+    pub fn start_recording_coverage(&mut self) -> &mut Self {
+        self.vm.start_recording_coverage();
+        self
+    }
+
+    /// Returns the [`DialogueCoverage`] recorded so far, if [`Dialogue::start_recording_coverage`] was called.
+    #[must_use]
+    pub fn coverage(&self) -> Option<&DialogueCoverage> {
+        self.vm.coverage()
+    }
+
+    /// Stops recording and returns the [`DialogueCoverage`] recorded since the last call to [`Dialogue::start_recording_coverage`].
+    /// Returns `None` if no coverage was being recorded.
+    ///
+    /// Pass it to [`CoverageReport::new`] along with the [`Program`] to see what was never reached.
+    pub fn stop_recording_coverage(&mut self) -> Option<DialogueCoverage> {
+        self.vm.stop_recording_coverage()
+    }
+
+    /// Re-drives this [`Dialogue`] from a [`DialogueTrace`] recorded by [`Dialogue::start_recording_trace`] and returns all events emitted along the way.
+    ///
+    /// Variable reads and function calls are answered by the trace instead of the [`VariableStorage`] and the [`Library`],
+    /// so the replay is deterministic even if the dialogue depends on randomness or state that has changed since.
+    /// Variables written by the dialogue are still stored in the [`VariableStorage`].
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`DialogueError::TraceMismatch`] if the dialogue diverges from the trace, e.g. because the program was edited since it was recorded.
+    pub fn replay_trace(&mut self, trace: DialogueTrace) -> Result<Vec<DialogueEvent>> {
+        self.vm.replay_trace(trace)
+    }
+
     /// Unloads all nodes from the Dialogue.
     pub fn unload_all(&mut self) {
         self.vm.unload_programs()
@@ -446,6 +707,12 @@ impl Dialogue {
         Ok(self)
     }
 
+    /// Gets the [`ExecutionState`] the dialogue is currently in.
+    #[must_use]
+    pub fn execution_state(&self) -> ExecutionState {
+        self.vm.execution_state()
+    }
+
     /// Gets a value indicating whether the Dialogue is currently executing Yarn instructions.
     #[must_use]
     pub fn is_active(&self) -> bool {
@@ -458,6 +725,44 @@ impl Dialogue {
     pub fn is_waiting_for_option_selection(&self) -> bool {
         self.vm.is_waiting_for_option_selection()
     }
+
+    /// Returns `true` if the dialogue called an async function (see [`YarnFn`]) and is waiting for the future it returned to resolve.
+    /// If this is `true`, calling [`Dialogue::continue_`] will error.
+    /// Drive the future with [`Dialogue::poll_pending_function_call`] or [`Dialogue::wait_for_pending_function_call`] first.
+    pub fn is_waiting_for_function_call(&self) -> bool {
+        self.vm.is_waiting_for_function_call()
+    }
+
+    /// Polls the future of the async function the dialogue is waiting on, see [`Dialogue::is_waiting_for_function_call`].
+    /// This is meant for hosts that drive the dialogue from a game loop, e.g. by polling once per frame.
+    ///
+    /// Once this returns [`Poll::Ready`] with [`Ok`], the function's value is available to the Yarn script and [`Dialogue::continue_`] can be called again.
+    /// If the function failed, the dialogue is stopped and a [`DialogueError::FunctionCallError`] is returned.
+    pub fn poll_pending_function_call(&mut self, cx: &mut task::Context<'_>) -> Poll<Result<()>> {
+        self.vm.poll_pending_function_call(cx)
+    }
+
+    /// Waits for the async function the dialogue is waiting on to resolve. See [`Dialogue::poll_pending_function_call`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use yarnspinner_runtime::prelude::*;
+    /// async fn run_to_completion(dialogue: &mut Dialogue) -> yarnspinner_runtime::Result<Vec<DialogueEvent>> {
+    ///     let mut events = Vec::new();
+    ///     while dialogue.is_active() {
+    ///         if dialogue.is_waiting_for_function_call() {
+    ///             dialogue.wait_for_pending_function_call().await?;
+    ///         }
+    ///         events.extend(dialogue.continue_()?);
+    ///         // Option selection omitted for brevity
+    ///     }
+    ///     Ok(events)
+    /// }
+    /// ```
+    pub async fn wait_for_pending_function_call(&mut self) -> Result<()> {
+        core::future::poll_fn(|cx| self.poll_pending_function_call(cx)).await
+    }
 }
 
 #[cfg(test)]
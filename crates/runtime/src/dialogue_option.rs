@@ -1,7 +1,7 @@
 //! Adapted from <https://github.com/YarnSpinnerTool/YarnSpinner/blob/da39c7195107d8211f21c263e4084f773b84eaff/YarnSpinner/Dialogue.cs>, which we split off into multiple files
 
 use crate::prelude::*;
-use std::fmt::Display;
+use core::fmt::Display;
 
 /// An option to be presented to the user.
 #[derive(Debug, Clone, PartialEq)]
@@ -41,6 +41,7 @@ pub struct DialogueOption {
 ///
 /// Since the IDs are just zero-based indices, you can also derive them yourself. Note that the index numeration includes options which
 /// have [`DialogueOption::is_available`] set to `false`, so the index of an option may not be as it appears in the list of options presented to the user.11
+/// The same is true if an [`OptionsProcessor`] reordered or removed options before they were delivered.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "bevy", derive(Reflect))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -52,7 +53,7 @@ pub struct DialogueOption {
 pub struct OptionId(pub usize);
 
 impl Display for OptionId {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
@@ -40,11 +40,43 @@ pub enum DialogueEvent {
     ///
     /// A hint that the contained line IDs might be encountered while progressing the dialogue.
     /// These are not guaranteed to run, but give a caller the chance to pre-load resources for them if they want.
+    /// Which lines are hinted, and when, is configured via [`Dialogue::set_line_hints_granularity`].
     ///
     /// ## Implementation note
     ///
     /// Corresponds to Yarn Spinner's `PrepareForLinesHandler`
     LineHints(Vec<LineId>),
+    /// Only emitted if [`Dialogue::variable_changed_events_enabled`] is enabled.
+    ///
+    /// A variable was set by the dialogue, e.g. via `<<set $gold to 10>>`.
+    /// Not emitted when the [`VariableStorage`] is changed from outside the dialogue.
+    ///
+    /// ## Implementation note
+    ///
+    /// Not part of the original Yarn Spinner.
+    VariableChanged {
+        /// The name of the variable, including the leading `$`.
+        name: String,
+        /// The value of the variable before it was set, falling back to its declared initial value. [`None`] if neither exists.
+        old_value: Option<YarnValue>,
+        /// The value the variable was set to.
+        new_value: YarnValue,
+    },
+    /// Only emitted if [`Dialogue::execution_state_events_enabled`] is enabled.
+    ///
+    /// The [`ExecutionState`] of the dialogue changed, e.g. from [`ExecutionState::Running`] to [`ExecutionState::WaitingOnOptionSelection`].
+    /// Lets a caller drive its UI from the state of the dialogue, e.g. by locking player input while it is not [`ExecutionState::Stopped`].
+    /// Changes caused outside of [`Dialogue::continue_`], e.g. by [`Dialogue::set_selected_option`], are returned by the next call to [`Dialogue::continue_`].
+    ///
+    /// ## Implementation note
+    ///
+    /// Not part of the original Yarn Spinner.
+    ExecutionStateChanged {
+        /// The state the dialogue was in before.
+        previous: ExecutionState,
+        /// The state the dialogue is in now.
+        current: ExecutionState,
+    },
     /// The dialogue was completed. Set it to a new node via [`Dialogue::set_node`] before calling [`Dialogue::continue_`] again.
     DialogueComplete,
 }
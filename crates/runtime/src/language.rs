@@ -1,12 +1,18 @@
-#[cfg(any(feature = "bevy", feature = "serde"))]
 use crate::prelude::*;
 use core::fmt::Display;
-use icu_locid::LanguageIdentifier;
+use core::str::FromStr;
+use icu_locid::{LanguageIdentifier, ParserError};
 
 /// IETF BCP 47 code.
 /// The default is "en-US".
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy", derive(Reflect))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bevy", reflect_value(Debug, PartialEq, Hash))]
+#[cfg_attr(
+    all(feature = "bevy", feature = "serde"),
+    reflect_value(Serialize, Deserialize)
+)]
 #[non_exhaustive]
 pub struct Language(pub(crate) LanguageIdentifier);
 impl Language {
@@ -17,8 +23,17 @@ impl Language {
     }
 }
 
+impl FromStr for Language {
+    type Err = ParserError;
+
+    /// Parses a `Language` from a string, failing instead of panicking if it is not a valid IETF BCP 47 code.
+    fn from_str(language: &str) -> core::result::Result<Self, Self::Err> {
+        language.parse().map(Self)
+    }
+}
+
 impl Display for Language {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.0.fmt(f)
     }
 }
@@ -4,18 +4,32 @@
 //! - If you're a game developer, you'll want to use a crate that is already designed for your game engine of choice,
 //!     such as [`bevy_yarnspinner`](https://crates.io/crates/bevy_yarnspinner) for the [Bevy engine](https://bevyengine.org/).
 //! - If you wish to write an adapter crate for an engine yourself, use the [`yarnspinner`](https://crates.io/crates/yarnspinner) crate.
+//!
+//! Disabling the default `std` feature makes this crate `no_std`, in which case it only requires `alloc`.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs, missing_debug_implementations)]
+extern crate alloc;
+
 mod analyser;
 mod command;
+mod compat;
+mod coverage;
 mod dialogue;
 mod dialogue_option;
 mod events;
 mod language;
 mod line;
+mod line_hints;
+mod localized_line;
 pub mod markup;
+mod metrics;
+mod missing_functions;
+mod options_processor;
 mod pluralization;
+mod story_state;
 mod text_provider;
+mod trace;
 mod variable_storage;
 mod virtual_machine;
 
@@ -26,15 +40,24 @@ pub mod prelude {
     pub use crate::{
         analyser::*,
         command::*,
+        coverage::*,
         dialogue::{Dialogue, DialogueError},
         dialogue_option::*,
         events::*,
         language::*,
         line::*,
+        line_hints::LineHintsGranularity,
+        localized_line::*,
         markup::MarkupParseError,
+        metrics::*,
+        missing_functions::*,
+        options_processor::*,
+        story_state::*,
         text_provider::*,
+        trace::*,
         variable_storage::*,
+        virtual_machine::ExecutionState,
     };
-    pub(crate) use crate::{pluralization::*, virtual_machine::*};
+    pub(crate) use crate::{compat::*, line_hints::*, pluralization::*, virtual_machine::*};
     pub(crate) use yarnspinner_core::prelude::*;
 }
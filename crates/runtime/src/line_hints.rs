@@ -0,0 +1,125 @@
+//! Not part of the original Yarn Spinner. Collects the lines that are sent as [`DialogueEvent::LineHints`] at different granularities.
+
+use crate::prelude::*;
+use alloc::collections::VecDeque;
+use yarnspinner_core::prelude::*;
+
+/// How many lines a [`DialogueEvent::LineHints`] covers, set via [`Dialogue::set_line_hints_granularity`].
+/// Covering fewer lines keeps fewer preloaded assets in memory, while covering more lines avoids hitches caused by loading assets too late.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LineHintsGranularity {
+    /// Hints all lines and options of a node when it is entered.
+    #[default]
+    Node,
+    /// Hints the lines and options up to and including the next set of options when a node is entered,
+    /// and the ones up to the set after that whenever an option is selected.
+    /// Lines behind a jump to another node are hinted when that node is entered.
+    Block,
+    /// Hints all lines and options of a node when it is entered, as well as those of the nodes it jumps to,
+    /// up to the given depth. A depth of `0` is the same as [`LineHintsGranularity::Node`].
+    Reachable {
+        /// How many jumps to other nodes to follow.
+        depth: usize,
+    },
+}
+
+/// Returns the IDs of all lines and options in the node.
+pub(crate) fn node_line_hints(node: &Node) -> Vec<LineId> {
+    node.instructions
+        .iter()
+        .filter_map(line_id_of_instruction)
+        .collect()
+}
+
+/// Returns the IDs of the lines and options that can run from the instruction at `start` until options are shown,
+/// following jumps within the node.
+pub(crate) fn block_line_hints(node: &Node, start: usize) -> Vec<LineId> {
+    let mut line_ids = Vec::new();
+    let mut seen_line_ids = HashSet::new();
+    let mut visited = HashSet::new();
+    let mut pending_starts = vec![start];
+    while let Some(mut index) = pending_starts.pop() {
+        while let Some(instruction) = node.instructions.get(index) {
+            if !visited.insert(index) {
+                break;
+            }
+            if let Some(line_id) = line_id_of_instruction(instruction) {
+                if seen_line_ids.insert(line_id.clone()) {
+                    line_ids.push(line_id);
+                }
+            }
+            match instruction.opcode.try_into().unwrap() {
+                // The jump to the destination of the selected option, to another node or out of the dialogue
+                OpCode::ShowOptions | OpCode::Jump | OpCode::RunNode | OpCode::Stop => break,
+                OpCode::JumpTo => match label_index(node, &instruction.read_operand::<String>(0)) {
+                    Some(label_index) => index = label_index,
+                    None => break,
+                },
+                OpCode::JumpIfFalse => {
+                    pending_starts
+                        .extend(label_index(node, &instruction.read_operand::<String>(0)));
+                    index += 1;
+                }
+                _ => index += 1,
+            }
+        }
+    }
+    line_ids
+}
+
+/// Returns the IDs of all lines and options in the node and in the nodes it jumps to, following up to `depth` jumps.
+pub(crate) fn reachable_line_hints(program: &Program, node: &Node, depth: usize) -> Vec<LineId> {
+    let mut line_ids = Vec::new();
+    let mut seen_line_ids = HashSet::new();
+    let mut visited_nodes: HashSet<&str> = HashSet::from([node.name.as_str()]);
+    let mut pending_nodes = VecDeque::from([(node, 0)]);
+    while let Some((node, node_depth)) = pending_nodes.pop_front() {
+        for line_id in node_line_hints(node) {
+            if seen_line_ids.insert(line_id.clone()) {
+                line_ids.push(line_id);
+            }
+        }
+        if node_depth == depth {
+            continue;
+        }
+        for destination in jump_destinations(node) {
+            if let Some(destination) = program.nodes.get(destination) {
+                if visited_nodes.insert(destination.name.as_str()) {
+                    pending_nodes.push_back((destination, node_depth + 1));
+                }
+            }
+        }
+    }
+    line_ids
+}
+
+fn line_id_of_instruction(instruction: &Instruction) -> Option<LineId> {
+    let opcode: OpCode = instruction.opcode.try_into().unwrap();
+    // Both RunLine and AddOption have the string ID they want to show as their first operand
+    [OpCode::RunLine, OpCode::AddOption]
+        .contains(&opcode)
+        .then(|| LineId(instruction.read_operand(0)))
+}
+
+fn label_index(node: &Node, label_name: &str) -> Option<usize> {
+    node.labels
+        .get(label_name)
+        .and_then(|&index| usize::try_from(index).ok())
+}
+
+/// The names of the nodes the node runs via `<<jump>>`, as far as they are known at compile time.
+fn jump_destinations(node: &Node) -> impl Iterator<Item = &str> {
+    node.instructions.windows(2).filter_map(|instructions| {
+        let [push, run] = instructions else {
+            return None;
+        };
+        if push.opcode != OpCode::PushString as i32 || run.opcode != OpCode::RunNode as i32 {
+            return None;
+        }
+        match push.operands.first()?.value.as_ref()? {
+            OperandValue::StringValue(node_name) => Some(node_name.as_str()),
+            _ => None,
+        }
+    })
+}
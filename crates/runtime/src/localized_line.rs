@@ -0,0 +1,204 @@
+//! Not part of the original Yarn Spinner. A [`Line`] together with everything a game needs to present it,
+//! so that every host gets the same fully resolved line. Used to be part of the Bevy plugin.
+
+use crate::markup::{
+    MarkupAttribute, MarkupValue, CHARACTER_ATTRIBUTE, CHARACTER_ATTRIBUTE_NAME_PROPERTY,
+};
+use crate::prelude::*;
+
+/// A line from the Yarn file, with all metadata and markup parsed and assets attached.
+/// The text is localized according to the localization logic used by the [`TextProvider`].
+///
+/// Created with [`Dialogue::localize_line`] or [`LocalizedLine::from_line`].
+/// The assets `A` are whatever the host associates with a line, e.g. voice-over handles in the Bevy plugin.
+/// Hosts that don't load any assets use the default of `()`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(Reflect))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "bevy",
+    reflect(Debug, PartialEq, where A: core::fmt::Debug + PartialEq + Default)
+)]
+pub struct LocalizedLine<A = ()> {
+    /// The ID of the line in the string table.
+    pub id: LineId,
+    /// The original text, with all parsed markers removed.
+    pub text: String,
+    /// The [`MarkupAttribute`]s in this line. An example of markup is `Hello, [b]world[/b]!`.
+    pub attributes: Vec<MarkupAttribute>,
+    /// The list of metadata associated with this line.
+    /// Metadata is defined by the hashtags at the end of the line, e.g. `Hello, world! #greeting #friendly`.
+    /// This data is also provided in the `comment` field of a generated strings file.
+    pub metadata: Vec<String>,
+    /// The assets associated with this line.
+    #[cfg_attr(feature = "bevy", reflect(ignore))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub assets: A,
+}
+
+impl<A> LocalizedLine<A> {
+    /// Creates a [`LocalizedLine`] from a [`Line`] delivered by the [`Dialogue`], its metadata and its assets.
+    pub fn from_line(line: Line, metadata: Vec<String>, assets: A) -> Self {
+        Self {
+            id: line.id,
+            text: line.text,
+            attributes: line.attributes,
+            metadata,
+            assets,
+        }
+    }
+
+    /// Replaces the assets of this line, e.g. to attach the assets a host loaded for a line created by [`Dialogue::localize_line`].
+    pub fn with_assets<B>(self, assets: B) -> LocalizedLine<B> {
+        LocalizedLine {
+            id: self.id,
+            text: self.text,
+            attributes: self.attributes,
+            metadata: self.metadata,
+            assets,
+        }
+    }
+
+    /// Gets the first attribute with the specified name, if present.
+    pub fn attribute(&self, name: &str) -> Option<&MarkupAttribute> {
+        self.attributes.iter().find(|attr| attr.name == name)
+    }
+
+    /// The name of the character, if present.
+    /// ## Examples
+    /// When there is a name:
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use yarnspinner_runtime::markup::*;
+    /// # use yarnspinner_runtime::prelude::*;
+    /// # let line = LocalizedLine {
+    /// #    id: "line".into(),
+    /// #    text: "Alice: Hello! How are you today?".to_owned(),
+    /// #    attributes: vec![MarkupAttribute {
+    /// #        name: "character".to_owned(),
+    /// #        position: 0,
+    /// #        length: 7,
+    /// #        properties: HashMap::from([("name".to_owned(), "Alice".into())]),
+    /// #        source_position: 0,
+    /// #    }],
+    /// #    metadata: vec![],
+    /// #    assets: (),
+    /// # };
+    /// assert_eq!("Alice: Hello! How are you today?", line.text);
+    /// assert_eq!(Some("Alice"), line.character_name());
+    /// ```
+    ///
+    /// When there is no name:
+    /// ```rust
+    /// # use yarnspinner_runtime::prelude::*;
+    /// # let line = LocalizedLine {
+    /// #    id: "line".into(),
+    /// #    text: "Great, thanks".to_owned(),
+    /// #    attributes: vec![],
+    /// #    metadata: vec![],
+    /// #    assets: (),
+    /// # };
+    /// assert_eq!("Great, thanks", line.text);
+    /// assert!(line.character_name().is_none());
+    pub fn character_name(&self) -> Option<&str> {
+        if let Some(attribute) = self.attribute(CHARACTER_ATTRIBUTE) {
+            if let Some(name) = attribute.property(CHARACTER_ATTRIBUTE_NAME_PROPERTY) {
+                let MarkupValue::String(name) = name else {
+                    panic!(
+                        "Attribute \"character\" has a \"name\" property, but it is not a string. \
+                         This is a bug. Please report it at https://github.com/YarnSpinnerTool/YarnSpinner-Rust/issues/new"
+                    );
+                };
+                return Some(name.as_str());
+            }
+        }
+        None
+    }
+
+    /// Returns the substring of [`LocalizedLine::text`] covered by the passed `attribute`s [`MarkupAttribute::position`] and [`MarkupAttribute::length`] fields.
+    pub fn text_for_attribute(&self, attribute: &MarkupAttribute) -> &str {
+        assert!(
+            self.text.len() >= attribute.position + attribute.length,
+            "Attribute \"{attribute}\" represents a range not representable by this text: \"{}\". \
+        Does this MarkupAttribute belong to this MarkupParseResult?",
+            self.text
+        );
+        &self.text[attribute.position..attribute.position + attribute.length]
+    }
+
+    /// Returns `true` if this line comes right before an options block.
+    ///
+    /// "right before" means that no commands are called in between them, no variables are set, etc., in which case this returns `false`.
+    pub fn is_last_line_before_options(&self) -> bool {
+        self.metadata.iter().any(|m| m == "lastline")
+    }
+}
+
+impl<A: Clone> LocalizedLine<A> {
+    /// The underlying text for this line, with any `character` attribute removed.
+    ///
+    /// ## Examples
+    /// When there is a name:
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use yarnspinner_runtime::markup::*;
+    /// # use yarnspinner_runtime::prelude::*;
+    /// # let line = LocalizedLine {
+    /// #    id: "line".into(),
+    /// #    text: "Alice: Hello! How are you today?".to_owned(),
+    /// #    attributes: vec![MarkupAttribute {
+    /// #        name: "character".to_owned(),
+    /// #        position: 0,
+    /// #        length: 7,
+    /// #        properties: HashMap::from([("name".to_owned(), "Alice".into())]),
+    /// #        source_position: 0,
+    /// #    }],
+    /// #    metadata: vec![],
+    /// #    assets: (),
+    /// # };
+    /// assert_eq!("Alice: Hello! How are you today?", line.text);
+    /// assert_eq!("Hello! How are you today?", &line.text_without_character_name());
+    /// ```
+    ///
+    /// When there is no name:
+    /// ```rust
+    /// # use yarnspinner_runtime::prelude::*;
+    /// # let line = LocalizedLine {
+    /// #    id: "line".into(),
+    /// #    text: "Great, thanks".to_owned(),
+    /// #    attributes: vec![],
+    /// #    metadata: vec![],
+    /// #    assets: (),
+    /// # };
+    /// assert_eq!("Great, thanks", line.text);
+    /// assert_eq!("Great, thanks", &line.text_without_character_name());
+    pub fn text_without_character_name(&self) -> String {
+        if let Some(attribute) = self.attribute(CHARACTER_ATTRIBUTE) {
+            self.delete_range(attribute).text
+        } else {
+            self.text.to_owned()
+        }
+    }
+
+    /// Deletes an attribute from this markup, see [`Line::delete_range`].
+    ///
+    /// This method does not modify the current object. A new  [`LocalizedLine`] with the same metadata and assets is returned.
+    ///
+    /// ## Panics
+    /// Panics if `attribute_to_delete` is not an attribute of this [`LocalizedLine::attribute`].
+    pub fn delete_range(&self, attribute_to_delete: &MarkupAttribute) -> Self {
+        let line: Line = self.clone().into();
+        let deleted_range = line.delete_range(attribute_to_delete);
+        Self::from_line(deleted_range, self.metadata.clone(), self.assets.clone())
+    }
+}
+
+impl<A> From<LocalizedLine<A>> for Line {
+    fn from(line: LocalizedLine<A>) -> Self {
+        Self {
+            id: line.id,
+            text: line.text,
+            attributes: line.attributes,
+        }
+    }
+}
@@ -3,7 +3,6 @@
 use crate::markup::AttributeMarkerProcessor;
 use crate::prelude::*;
 use icu_plurals::PluralCategory;
-use std::collections::HashSet;
 
 #[derive(Default, Debug, Clone)]
 pub(crate) struct DialogueTextProcessor {
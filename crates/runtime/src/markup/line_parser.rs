@@ -6,13 +6,11 @@ use crate::markup::{
     MarkupValue, NoMarkupTextProcessor, TagType,
 };
 use crate::prelude::*;
-use once_cell::sync::Lazy;
-use regex::Regex;
-use std::collections::{HashMap, VecDeque};
+use alloc::collections::VecDeque;
 use unicode_normalization::UnicodeNormalization;
 use unicode_segmentation::UnicodeSegmentation;
 
-pub type Result<T> = std::result::Result<T, MarkupParseError>;
+pub type Result<T> = core::result::Result<T, MarkupParseError>;
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "bevy", derive(Reflect))]
@@ -189,16 +187,16 @@ impl LineParser {
 
         // Attempt to generate a character attribute from the start
         // of the string to the first colon
-        let Some(match_) = END_OF_CHARACTER_MARKER.find(&self.input) else {
+        let Some((marker_start, marker_end)) = find_end_of_character_marker(&self.input) else {
             return Ok(ParsedMarkup { text, attributes });
         };
 
-        let character_name = self.input[..match_.start()].to_string();
+        let character_name = self.input[..marker_start].to_string();
 
         let character_attribute = MarkupAttribute {
             name: CHARACTER_ATTRIBUTE.to_string(),
             position: 0,
-            length: match_.end(),
+            length: marker_end,
             properties: HashMap::from([(
                 CHARACTER_ATTRIBUTE_NAME_PROPERTY.to_string(),
                 character_name.into(),
@@ -608,19 +606,17 @@ impl LineParser {
         let remainder_of_line = self.read_to_end();
 
         // Parse up to either [/name] or [/], allowing whitespace between any elements.
-        let regex = Regex::new(&format!(r"\[\s*\/\s*({name})?\s*\]")).unwrap();
-        let match_ =
-            regex
-                .find(&remainder_of_line)
-                .ok_or_else(|| MarkupParseError::UnterminatedMarker {
+        let close_marker_position =
+            find_close_marker(&remainder_of_line, name).ok_or_else(|| {
+                MarkupParseError::UnterminatedMarker {
                     input: self.input.clone(),
                     name: name.to_string(),
                     position: self.position,
-                })?;
+                }
+            })?;
 
         // Split the line into the part up to the closing tag, and the
         // part afterwards
-        let close_marker_position = match_.start();
         let raw_text_substring = &remainder_of_line[..close_marker_position];
 
         // We've consumed all of this text in the string reader, so to
@@ -738,5 +734,26 @@ pub const CHARACTER_ATTRIBUTE_NAME_PROPERTY: &str = "name";
 /// if a tag had preceding whitespace or begins the line. This property must be a bool value.
 pub const TRIM_WHITESPACE_PROPERTY: &str = "trimwhitespace";
 
-/// A regular expression that matches a colon followed by optional whitespace.
-static END_OF_CHARACTER_MARKER: Lazy<Regex> = Lazy::new(|| Regex::new(r":\s*").unwrap());
+/// Finds the first colon and the optional whitespace following it, which mark the end of a character name.
+/// Returns the start and end position of the marker.
+fn find_end_of_character_marker(text: &str) -> Option<(usize, usize)> {
+    let start = text.find(':')?;
+    let after_colon = &text[start + 1..];
+    let end = text.len() - after_colon.trim_start().len();
+    Some((start, end))
+}
+
+/// Finds the first `[/name]` or `[/]` marker, allowing whitespace between any elements.
+/// Returns the start position of the marker.
+fn find_close_marker(text: &str, name: &str) -> Option<usize> {
+    text.match_indices('[')
+        .map(|(start, _)| start)
+        .find(|start| {
+            let Some(rest) = text[start + 1..].trim_start().strip_prefix('/') else {
+                return false;
+            };
+            let rest = rest.trim_start();
+            let rest = rest.strip_prefix(name).unwrap_or(rest);
+            rest.trim_start().starts_with(']')
+        })
+}
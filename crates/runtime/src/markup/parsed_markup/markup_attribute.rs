@@ -2,10 +2,8 @@
 //! which was split into multiple files.
 
 use crate::markup::{MarkupAttributeMarker, MarkupValue};
-#[cfg(any(feature = "bevy", feature = "serde"))]
 use crate::prelude::*;
 use core::fmt::Display;
-use std::collections::HashMap;
 
 /// Represents a range of text in a marked-up string.
 ///
@@ -54,7 +52,7 @@ impl MarkupAttribute {
 }
 
 impl Display for MarkupAttribute {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let properties = (!self.properties.is_empty())
             .then(|| format!(", {} properties", self.properties.len()))
             .unwrap_or_default();
@@ -2,7 +2,7 @@
 //! which was split into multiple files.
 
 use crate::markup::{MarkupValue, TagType};
-use std::collections::HashMap;
+use crate::prelude::*;
 
 /// Represents a marker (e.g. `[a]`) in line of marked up text.
 ///
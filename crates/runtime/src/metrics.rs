@@ -0,0 +1,29 @@
+//! Not part of the original Yarn Spinner. Counts what a [`Dialogue`] delivered, e.g. for analytics or achievements.
+
+#[cfg(any(feature = "bevy", feature = "serde"))]
+use crate::prelude::*;
+
+/// Counters of the content a [`Dialogue`] delivered since it was created or since [`Dialogue::reset_metrics`] was last called.
+/// Read them with [`Dialogue::metrics`].
+///
+/// Replacing or adding programs does not reset the counters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "bevy", derive(Reflect))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bevy", reflect(Debug, PartialEq, Default))]
+#[cfg_attr(
+    all(feature = "bevy", feature = "serde"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct DialogueMetrics {
+    /// The number of [`DialogueEvent::Line`]s delivered.
+    pub lines_delivered: usize,
+    /// The number of [`DialogueEvent::Options`] delivered, i.e. how often the player was asked to choose, regardless of the number of options.
+    pub options_presented: usize,
+    /// The number of options selected through [`Dialogue::set_selected_option`].
+    pub options_chosen: usize,
+    /// The number of [`DialogueEvent::Command`]s delivered.
+    pub commands_executed: usize,
+    /// The number of nodes entered, counting repeated visits of the same node.
+    pub nodes_visited: usize,
+}
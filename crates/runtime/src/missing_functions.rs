@@ -0,0 +1,86 @@
+//! Not part of the original Yarn Spinner, which always fails when a function is missing from the [`Library`].
+
+use crate::prelude::*;
+use core::fmt::{self, Debug};
+use yarnspinner_core::prelude::*;
+
+/// What a [`Dialogue`] does when a Yarn script calls a function that is not in its [`Library`]. Set with [`Dialogue::set_missing_function_behavior`].
+///
+/// Falling back instead of failing is useful for modded content and staged rollouts, where scripts may call functions that the running game does not provide (yet).
+/// The return type of a missing function is not known at runtime, so the value returned in its place should have the type the script expects.
+///
+/// ## Example
+///
+/// ```rust
+/// # use yarnspinner_runtime::prelude::*;
+/// # use yarnspinner_core::prelude::*;
+/// # let mut dialogue = Dialogue::new(Box::new(MemoryVariableStorage::new()), Box::new(StringTableTextProvider::new()));
+/// // Functions from mods are called `mod_<name>` and return `false` when the mod is not installed
+/// dialogue.set_missing_function_behavior(MissingFunctionBehavior::handler(
+///     |function_name: &str, _parameters: &[YarnValue]| {
+///         function_name.starts_with("mod_").then(|| false.into())
+///     },
+/// ));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub enum MissingFunctionBehavior {
+    /// Fails with a [`DialogueError::FunctionNotFound`].
+    #[default]
+    Error,
+    /// Logs a warning and returns the given value in place of the missing function.
+    WarnAndReturn(YarnValue),
+    /// Asks the given [`MissingFunctionHandler`] for the value to return in place of the missing function.
+    Handler(Box<dyn MissingFunctionHandler>),
+}
+
+impl MissingFunctionBehavior {
+    /// Convenience constructor for [`MissingFunctionBehavior::Handler`].
+    pub fn handler(handler: impl MissingFunctionHandler + 'static) -> Self {
+        Self::Handler(Box::new(handler))
+    }
+}
+
+/// A catch-all for calls to functions that are not in the [`Library`] of a [`Dialogue`], see [`MissingFunctionBehavior::Handler`].
+///
+/// This trait is implemented for all cloneable closures with the right signature.
+pub trait MissingFunctionHandler: Send + Sync {
+    /// Returns the value of the call to the missing function with the given name and arguments,
+    /// or [`None`] to fail with a [`DialogueError::FunctionNotFound`] after all.
+    fn handle_missing_function(
+        &mut self,
+        function_name: &str,
+        parameters: &[YarnValue],
+    ) -> Option<YarnValue>;
+    /// Clones this handler into a new box.
+    fn clone_box(&self) -> Box<dyn MissingFunctionHandler>;
+}
+
+impl<F> MissingFunctionHandler for F
+where
+    F: FnMut(&str, &[YarnValue]) -> Option<YarnValue> + Clone + Send + Sync + 'static,
+{
+    fn handle_missing_function(
+        &mut self,
+        function_name: &str,
+        parameters: &[YarnValue],
+    ) -> Option<YarnValue> {
+        self(function_name, parameters)
+    }
+
+    fn clone_box(&self) -> Box<dyn MissingFunctionHandler> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn MissingFunctionHandler> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl Debug for dyn MissingFunctionHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MissingFunctionHandler")
+            .finish_non_exhaustive()
+    }
+}
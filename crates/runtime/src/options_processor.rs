@@ -0,0 +1,58 @@
+//! Not part of the original Yarn Spinner, which always delivers options in the order they were written in.
+
+use crate::prelude::*;
+use core::fmt::{self, Debug};
+
+/// A hook that post-processes the options of a [`Dialogue`] right before they are delivered through [`DialogueEvent::Options`].
+/// Register one with [`Dialogue::set_options_processor`] to e.g. shuffle, sort or cap the options that are presented.
+///
+/// The options are passed in the order they appear in the Yarn file. The processor may reorder them and remove some of them,
+/// but should not change their [`DialogueOption::id`]s: these keep referring to the original options,
+/// so [`Dialogue::set_selected_option`] continues to work with the IDs of the delivered options.
+/// Selecting an option that was removed by the processor results in a [`DialogueError::InvalidOptionIdError`].
+/// If the processor removes all options, the dialogue completes as if there had been no options to begin with.
+///
+/// This trait is implemented for all cloneable closures with the right signature.
+///
+/// ## Example
+///
+/// ```rust
+/// # use yarnspinner_runtime::prelude::*;
+/// # let mut dialogue = Dialogue::new(Box::new(MemoryVariableStorage::new()), Box::new(StringTableTextProvider::new()));
+/// // Present the available options first and at most three options in total
+/// dialogue.set_options_processor(|options: &mut Vec<DialogueOption>| {
+///     options.sort_by_key(|option| !option.is_available);
+///     options.truncate(3);
+/// });
+/// ```
+pub trait OptionsProcessor: Send + Sync {
+    /// Reorders or removes the options that are about to be delivered.
+    fn process_options(&mut self, options: &mut Vec<DialogueOption>);
+    /// Clones this processor into a new box.
+    fn clone_box(&self) -> Box<dyn OptionsProcessor>;
+}
+
+impl<F> OptionsProcessor for F
+where
+    F: FnMut(&mut Vec<DialogueOption>) + Clone + Send + Sync + 'static,
+{
+    fn process_options(&mut self, options: &mut Vec<DialogueOption>) {
+        self(options)
+    }
+
+    fn clone_box(&self) -> Box<dyn OptionsProcessor> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn OptionsProcessor> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl Debug for dyn OptionsProcessor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OptionsProcessor").finish_non_exhaustive()
+    }
+}
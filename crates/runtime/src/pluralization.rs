@@ -1,4 +1,4 @@
-use crate::prelude::Language;
+use crate::prelude::*;
 use fixed_decimal::{DoublePrecision, FixedDecimal};
 use icu_plurals::{PluralCategory, PluralRuleType};
 use icu_plurals::{PluralOperands, PluralRules};
@@ -33,9 +33,9 @@ impl Pluralization {
 }
 
 fn get_into_plural_operand(value: f32) -> PluralOperands {
-    let rounded = value.round();
-    let floating_point = (rounded - value).abs();
-    if floating_point < 1e-5 {
+    // Checks the distance to the nearest integer without `f32::round`, which is not available without `std`.
+    let fractional_part = (value - value as isize as f32).abs();
+    if !(1e-5..=1.0 - 1e-5).contains(&fractional_part) {
         (value as isize).into()
     } else {
         (&FixedDecimal::try_from_f64(value as f64, DoublePrecision::Floating).unwrap()).into()
@@ -0,0 +1,141 @@
+//! Not part of the original Yarn Spinner. Gives game code typed access to the variables of a [`Dialogue`].
+
+use crate::prelude::*;
+use yarnspinner_core::prelude::*;
+
+/// Typed access to the story progress stored in a [`VariableStorage`], so that gameplay can branch on the same state as Yarn scripts.
+///
+/// Get one from [`Dialogue::story_state`] or wrap any [`VariableStorage`] with [`StoryState::new`]. Since it holds a shallow clone
+/// of the storage, changes made through it are visible to the dialogue and vice versa.
+///
+/// Variable names can be passed with or without the leading `$`, so `"met_sally"` and `"$met_sally"` refer to the same variable.
+///
+/// ## Example
+///
+/// ```rust
+/// # use yarnspinner_runtime::prelude::*;
+/// let mut state = StoryState::new(Box::new(MemoryVariableStorage::new()));
+/// state.set_flag("met_sally", true).unwrap();
+/// state.increment("reputation", 5.0).unwrap();
+///
+/// assert!(state.flag("$met_sally"));
+/// assert_eq!(state.counter("reputation"), 5.0);
+/// assert!(!state.visited("Sally"));
+/// ```
+#[derive(Debug)]
+pub struct StoryState(Box<dyn VariableStorage>);
+
+impl Clone for StoryState {
+    fn clone(&self) -> Self {
+        Self(self.0.clone_shallow())
+    }
+}
+
+impl StoryState {
+    /// Wraps the given [`VariableStorage`].
+    pub fn new(variable_storage: Box<dyn VariableStorage>) -> Self {
+        Self(variable_storage)
+    }
+
+    /// Returns whether the boolean variable is `true`. Variables that are not set or are not booleans count as `false`.
+    #[must_use]
+    pub fn flag(&self, name: &str) -> bool {
+        self.value(name)
+            .and_then(|value| bool::try_from(value).ok())
+            .unwrap_or_default()
+    }
+
+    /// Sets the boolean variable.
+    pub fn set_flag(&mut self, name: &str, value: bool) -> Result<&mut Self> {
+        self.set(name, value)
+    }
+
+    /// Returns the value of the number variable. Variables that are not set or are not numbers count as `0`.
+    #[must_use]
+    pub fn counter(&self, name: &str) -> f64 {
+        match self.value(name) {
+            Some(YarnValue::Number(number)) => number,
+            _ => 0.0,
+        }
+    }
+
+    /// Sets the number variable.
+    pub fn set_counter(&mut self, name: &str, value: f64) -> Result<&mut Self> {
+        self.set(name, value)
+    }
+
+    /// Adds `amount` to the number variable, treating it as `0` if it is not set yet, and returns the new value.
+    pub fn increment(&mut self, name: &str, amount: f64) -> Result<f64> {
+        let value = self.counter(name) + amount;
+        self.set(name, value)?;
+        Ok(value)
+    }
+
+    /// Returns whether the node has been visited at least once, like the Yarn function `visited`.
+    #[must_use]
+    pub fn visited(&self, node_name: &str) -> bool {
+        self.visit_count(node_name) > 0
+    }
+
+    /// Returns how often the node has been visited, like the Yarn function `visited_count`.
+    /// Nodes that were never visited, don't exist or are not tracked report `0`.
+    #[must_use]
+    pub fn visit_count(&self, node_name: &str) -> usize {
+        let name = Library::generate_unique_visited_variable_for_node(node_name);
+        match self.0.get(&name) {
+            Ok(YarnValue::Number(count)) => count as usize,
+            _ => 0,
+        }
+    }
+
+    /// Returns the value of any variable, if it is set.
+    #[must_use]
+    pub fn value(&self, name: &str) -> Option<YarnValue> {
+        self.0.get(&variable_name(name)).ok()
+    }
+
+    /// Sets any variable.
+    pub fn set(&mut self, name: &str, value: impl Into<YarnValue>) -> Result<&mut Self> {
+        self.0.set(variable_name(name), value.into())?;
+        Ok(self)
+    }
+
+    /// Captures all variables, e.g. to write them to a save file.
+    #[must_use]
+    pub fn snapshot(&self) -> StorySnapshot {
+        StorySnapshot {
+            variables: self.0.variables(),
+        }
+    }
+
+    /// Replaces all variables with the ones of a previously taken [`StorySnapshot`].
+    pub fn restore(&mut self, snapshot: StorySnapshot) -> Result<&mut Self> {
+        self.0.clear();
+        VariableStorage::extend(self.0.as_mut(), snapshot.variables)?;
+        Ok(self)
+    }
+
+    /// Returns the wrapped [`VariableStorage`].
+    #[must_use]
+    pub fn variable_storage(&self) -> &dyn VariableStorage {
+        self.0.as_ref()
+    }
+}
+
+/// The variables of a [`StoryState`] at one point in time, see [`StoryState::snapshot`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "bevy", derive(Reflect))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bevy", reflect(Debug, PartialEq, Default))]
+#[cfg_attr(
+    all(feature = "bevy", feature = "serde"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct StorySnapshot {
+    /// The values of all variables, keyed by their name including the leading `$`.
+    pub variables: HashMap<String, YarnValue>,
+}
+
+fn variable_name(name: &str) -> String {
+    format!("${}", name.trim_start_matches('$'))
+}
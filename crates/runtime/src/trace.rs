@@ -0,0 +1,74 @@
+//! Not part of the original Yarn Spinner. Allows recording the decisions made while running a [`Dialogue`] and replaying them later.
+
+use crate::prelude::*;
+use core::fmt::{self, Display};
+use yarnspinner_core::prelude::*;
+
+/// A recording of every decision point encountered while running a [`Dialogue`], created by [`Dialogue::start_recording_trace`] and [`Dialogue::stop_recording_trace`].
+///
+/// A trace can be fed to [`Dialogue::replay_trace`] to deterministically re-run the same dialogue, which is useful for
+/// attaching reproductions to bug reports or for regression tests of narrative content.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "bevy", derive(Reflect))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bevy", reflect(Debug, PartialEq, Default))]
+#[cfg_attr(
+    all(feature = "bevy", feature = "serde"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct DialogueTrace {
+    /// The recorded decision points, in the order they were encountered.
+    pub steps: Vec<TraceStep>,
+}
+
+/// A single decision point of a [`DialogueTrace`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(Reflect))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "bevy", reflect(Debug, PartialEq))]
+#[cfg_attr(
+    all(feature = "bevy", feature = "serde"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum TraceStep {
+    /// A node was started through [`Dialogue::set_node`].
+    NodeStarted {
+        /// The name of the started node.
+        node_name: String,
+    },
+    /// The dialogue was continued through [`Dialogue::continue_`] or [`Dialogue::next`].
+    Continued,
+    /// An option was selected through [`Dialogue::set_selected_option`].
+    OptionSelected {
+        /// The ID of the selected option.
+        option_id: OptionId,
+    },
+    /// The value of a variable was read from the [`VariableStorage`].
+    VariableRead {
+        /// The name of the variable, including the leading `$`.
+        name: String,
+        /// The value that was read.
+        value: YarnValue,
+    },
+    /// A function of the [`Library`] was called. This includes functions that draw random numbers.
+    FunctionCalled {
+        /// The name of the called function.
+        name: String,
+        /// The value the function returned.
+        return_value: YarnValue,
+    },
+}
+
+impl Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceStep::NodeStarted { node_name } => write!(f, "start of node \"{node_name}\""),
+            TraceStep::Continued => f.write_str("continuation of the dialogue"),
+            TraceStep::OptionSelected { option_id } => write!(f, "selection of option {option_id}"),
+            TraceStep::VariableRead { name, value } => write!(f, "read of {name} = {value}"),
+            TraceStep::FunctionCalled { name, return_value } => {
+                write!(f, "call of {name}() returning {return_value}")
+            }
+        }
+    }
+}
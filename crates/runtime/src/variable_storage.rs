@@ -1,13 +1,15 @@
 //! Adapted from <https://github.com/YarnSpinnerTool/YarnSpinner/blob/da39c7195107d8211f21c263e4084f773b84eaff/YarnSpinner/Dialogue.cs>, which we split off into multiple files
-use std::any::Any;
-use std::collections::HashMap;
-use std::error::Error;
-use std::fmt::{self, Debug, Display};
-use std::sync::{Arc, RwLock};
+pub use self::scoped_variable_storage::*;
+use crate::prelude::*;
+use core::any::Any;
+use core::error::Error;
+use core::fmt::{self, Debug, Display};
 use yarnspinner_core::prelude::*;
 
+mod scoped_variable_storage;
+
 #[allow(missing_docs)]
-pub type Result<T> = std::result::Result<T, VariableStorageError>;
+pub type Result<T> = core::result::Result<T, VariableStorageError>;
 
 /// Provides a mechanism for storing and retrieving instances
 /// of the [`YarnValue`] type.
@@ -75,6 +77,12 @@ impl Display for VariableStorageError {
     }
 }
 
+impl VariableAccess for Box<dyn VariableStorage> {
+    fn variable(&self, name: &str) -> Option<YarnValue> {
+        self.get(name).ok()
+    }
+}
+
 impl Clone for Box<dyn VariableStorage> {
     fn clone(&self) -> Self {
         self.clone_shallow()
@@ -99,33 +107,35 @@ impl VariableStorage for MemoryVariableStorage {
 
     fn set(&mut self, name: String, value: YarnValue) -> Result<()> {
         Self::validate_name(&name)?;
-        self.0.write().unwrap().insert(name, value);
+        self.0.write().insert(name, value);
         Ok(())
     }
 
     fn get(&self, name: &str) -> Result<YarnValue> {
         Self::validate_name(name)?;
-        self.0.read().unwrap().get(name).cloned().ok_or_else(|| {
-            VariableStorageError::VariableNotFound {
+        self.0
+            .read()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| VariableStorageError::VariableNotFound {
                 name: name.to_string(),
-            }
-        })
+            })
     }
 
     fn extend(&mut self, values: HashMap<String, YarnValue>) -> Result<()> {
         for name in values.keys() {
             Self::validate_name(name)?;
         }
-        self.0.write().unwrap().extend(values);
+        self.0.write().extend(values);
         Ok(())
     }
 
     fn variables(&self) -> HashMap<String, YarnValue> {
-        self.0.read().unwrap().clone()
+        self.0.read().clone()
     }
 
     fn clear(&mut self) {
-        self.0.write().unwrap().clear();
+        self.0.write().clear();
     }
 
     fn as_any(&self) -> &dyn Any {
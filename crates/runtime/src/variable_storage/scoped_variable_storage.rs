@@ -0,0 +1,131 @@
+//! Not part of the original Yarn Spinner, which only supports one [`VariableStorage`] per [`Dialogue`].
+
+use crate::prelude::*;
+use core::any::Any;
+use yarnspinner_core::prelude::*;
+
+/// The prefix that marks a variable as local to a single [`ScopedVariableStorage`], e.g. `$local_greeted_player`.
+/// An underscore is used as separator because Yarn variable names cannot contain dots.
+pub const LOCAL_VARIABLE_PREFIX: &str = "$local_";
+
+/// A [`VariableStorage`] that lets multiple [`Dialogue`]s share one global storage while keeping some variables to themselves.
+///
+/// Variables are resolved as follows:
+/// - Variables whose name starts with [`LOCAL_VARIABLE_PREFIX`], e.g. `$local_times_talked_to`, live in a local scope owned by this storage.
+///   They are never visible to other [`Dialogue`]s, even if these share the same global storage.
+/// - All other variables, including the ones used to track node visits, are read from and written to the shared global storage.
+///
+/// Since the local scope is separate from the global one, [`VariableStorage::clear`] only clears the local variables.
+/// Use [`ScopedVariableStorage::global_mut`] if you really want to clear the global variables of every [`Dialogue`] sharing them.
+///
+/// ## Example
+///
+/// ```rust
+/// # use yarnspinner_runtime::prelude::*;
+/// # use yarnspinner_core::prelude::*;
+/// let global = MemoryVariableStorage::new();
+/// let mut quest_storage = ScopedVariableStorage::new(global.clone_shallow());
+/// let mut bark_storage = ScopedVariableStorage::new(global.clone_shallow());
+///
+/// quest_storage.set("$gold".to_owned(), 10.0.into()).unwrap();
+/// bark_storage.set("$local_line_index".to_owned(), 1.0.into()).unwrap();
+///
+/// assert_eq!(bark_storage.get("$gold").unwrap(), YarnValue::from(10.0));
+/// assert!(!quest_storage.contains("$local_line_index"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScopedVariableStorage {
+    global: Box<dyn VariableStorage>,
+    local: MemoryVariableStorage,
+}
+
+impl ScopedVariableStorage {
+    /// Creates a new [`ScopedVariableStorage`] with an empty local scope on top of the given global storage.
+    /// Pass a [`VariableStorage::clone_shallow`] of the same storage to every [`Dialogue`] that should share its global variables.
+    pub fn new(global: Box<dyn VariableStorage>) -> Self {
+        Self {
+            global,
+            local: MemoryVariableStorage::new(),
+        }
+    }
+
+    /// Returns the shared global storage.
+    pub fn global(&self) -> &dyn VariableStorage {
+        self.global.as_ref()
+    }
+
+    /// Returns the shared global storage mutably. Changes made through it are visible to all storages sharing it.
+    pub fn global_mut(&mut self) -> &mut dyn VariableStorage {
+        self.global.as_mut()
+    }
+
+    /// Returns the storage holding the variables local to this [`ScopedVariableStorage`].
+    pub fn local(&self) -> &dyn VariableStorage {
+        &self.local
+    }
+
+    /// Returns whether the given variable name refers to the local scope, i.e. starts with [`LOCAL_VARIABLE_PREFIX`].
+    pub fn is_local(name: &str) -> bool {
+        name.starts_with(LOCAL_VARIABLE_PREFIX)
+    }
+
+    fn scope(&self, name: &str) -> &dyn VariableStorage {
+        if Self::is_local(name) {
+            &self.local
+        } else {
+            self.global.as_ref()
+        }
+    }
+
+    fn scope_mut(&mut self, name: &str) -> &mut dyn VariableStorage {
+        if Self::is_local(name) {
+            &mut self.local
+        } else {
+            self.global.as_mut()
+        }
+    }
+}
+
+impl VariableStorage for ScopedVariableStorage {
+    fn clone_shallow(&self) -> Box<dyn VariableStorage> {
+        Box::new(self.clone())
+    }
+
+    fn set(&mut self, name: String, value: YarnValue) -> Result<()> {
+        self.scope_mut(&name).set(name, value)
+    }
+
+    fn get(&self, name: &str) -> Result<YarnValue> {
+        self.scope(name).get(name)
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.scope(name).contains(name)
+    }
+
+    fn extend(&mut self, values: HashMap<String, YarnValue>) -> Result<()> {
+        let (local, global): (HashMap<_, _>, HashMap<_, _>) = values
+            .into_iter()
+            .partition(|(name, _)| Self::is_local(name));
+        VariableStorage::extend(&mut self.local, local)?;
+        VariableStorage::extend(self.global.as_mut(), global)
+    }
+
+    fn variables(&self) -> HashMap<String, YarnValue> {
+        let mut variables = self.global.variables();
+        variables.extend(self.local.variables());
+        variables
+    }
+
+    fn clear(&mut self) {
+        self.local.clear();
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
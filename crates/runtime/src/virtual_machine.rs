@@ -3,17 +3,22 @@
 //! ## Implementation Notes
 //! The `Operand` extensions and the `Operator` enum were moved into upstream crates to make them not depend on the runtime.
 
-pub(crate) use self::{execution_state::*, state::*};
+pub use self::execution_state::*;
+pub(crate) use self::{pending_function_call::*, state::*, trace_state::*};
 use crate::markup::{LineParser, ParsedMarkup};
 use crate::prelude::*;
 use crate::Result;
+use core::fmt::Debug;
+use core::task::{ready, Context, Poll};
 use log::*;
-use std::fmt::Debug;
 use yarnspinner_core::prelude::OpCode;
 use yarnspinner_core::prelude::*;
+use yarnspinner_core::types::TypedValue as _;
 
 mod execution_state;
+mod pending_function_call;
 mod state;
+mod trace_state;
 
 #[derive(Debug, Clone)]
 pub(crate) struct VirtualMachine {
@@ -21,6 +26,9 @@ pub(crate) struct VirtualMachine {
     pub(crate) program: Option<Program>,
     pub(crate) variable_storage: Box<dyn VariableStorage>,
     pub(crate) line_hints_enabled: bool,
+    pub(crate) line_hints_granularity: LineHintsGranularity,
+    pub(crate) execution_state_events_enabled: bool,
+    pub(crate) variable_changed_events_enabled: bool,
     current_node_name: Option<String>,
     state: State,
     execution_state: ExecutionState,
@@ -28,7 +36,16 @@ pub(crate) struct VirtualMachine {
     batched_events: Vec<DialogueEvent>,
     line_parser: LineParser,
     text_provider: Box<dyn TextProvider>,
+    pub(crate) options_processor: Option<Box<dyn OptionsProcessor>>,
+    pub(crate) missing_function_behavior: MissingFunctionBehavior,
+    pub(crate) debug_info: HashMap<String, DebugInfo>,
+    trace: Option<TraceState>,
+    coverage: Option<DialogueCoverage>,
+    pending_function_call: Option<PendingFunctionCall>,
+    /// The line the dialogue paused on, along with its substitutions, so that it can be prepared again in another language.
+    current_line: Option<(LineId, Vec<String>)>,
     language_code: Option<Language>,
+    pub(crate) metrics: DialogueMetrics,
 }
 
 impl Iterator for VirtualMachine {
@@ -53,6 +70,13 @@ impl VirtualMachine {
             variable_storage,
             line_parser,
             text_provider,
+            options_processor: Default::default(),
+            missing_function_behavior: Default::default(),
+            debug_info: Default::default(),
+            trace: Default::default(),
+            coverage: Default::default(),
+            pending_function_call: Default::default(),
+            current_line: Default::default(),
             language_code: Default::default(),
             program: Default::default(),
             current_node_name: Default::default(),
@@ -61,6 +85,10 @@ impl VirtualMachine {
             current_node: Default::default(),
             batched_events: Default::default(),
             line_hints_enabled: Default::default(),
+            line_hints_granularity: Default::default(),
+            execution_state_events_enabled: Default::default(),
+            variable_changed_events_enabled: Default::default(),
+            metrics: Default::default(),
         }
     }
 
@@ -90,10 +118,19 @@ impl VirtualMachine {
     pub(crate) fn reset_state(&mut self) {
         self.state = State::default();
         self.current_node_name = None;
+        self.pending_function_call = None;
+        self.current_line = None;
     }
 
     pub(crate) fn set_execution_state(&mut self, execution_state: ExecutionState) -> &mut Self {
-        self.execution_state = execution_state;
+        let previous = core::mem::replace(&mut self.execution_state, execution_state);
+        if self.execution_state_events_enabled && previous != execution_state {
+            self.batched_events
+                .push(DialogueEvent::ExecutionStateChanged {
+                    previous,
+                    current: execution_state,
+                });
+        }
         if execution_state == ExecutionState::Stopped {
             self.reset_state()
         }
@@ -105,7 +142,7 @@ impl VirtualMachine {
     pub(crate) fn stop(&mut self) -> Vec<DialogueEvent> {
         self.set_execution_state(ExecutionState::Stopped);
         self.batched_events.push(DialogueEvent::DialogueComplete);
-        std::mem::take(&mut self.batched_events)
+        core::mem::take(&mut self.batched_events)
     }
 
     pub(crate) fn set_node(&mut self, node_name: impl Into<String>) -> Result<()> {
@@ -120,6 +157,8 @@ impl VirtualMachine {
 
         self.batched_events
             .push(DialogueEvent::NodeStart(node_name));
+        self.metrics.nodes_visited += 1;
+        self.record_coverage(|coverage| coverage.visits += 1);
 
         if self.line_hints_enabled {
             self.send_line_hints();
@@ -128,33 +167,18 @@ impl VirtualMachine {
     }
 
     fn send_line_hints(&mut self) {
-        // Create a list; we will never have more lines and options
-        // than total instructions, so that's a decent capacity for
-        // the list
-        // [sic] TODO: maybe this list could be reused to save on allocations?
+        let current_node = self.current_node.as_ref().unwrap();
+        let string_ids = match self.line_hints_granularity {
+            LineHintsGranularity::Node => node_line_hints(current_node),
+            LineHintsGranularity::Block => block_line_hints(current_node, 0),
+            LineHintsGranularity::Reachable { depth } => {
+                reachable_line_hints(self.program.as_ref().unwrap(), current_node, depth)
+            }
+        };
+        self.push_line_hints(string_ids);
+    }
 
-        let string_ids: Vec<_> = self
-            .current_node
-            .as_ref()
-            .unwrap()
-            .instructions
-            .iter()
-            // Loop over every instruction and find the ones that run a
-            // line or add an option; these are the two instructions
-            // that will signal a line can appear to the player
-            .filter_map(|instruction| {
-                let opcode: OpCode = instruction.opcode.try_into().unwrap();
-                [OpCode::RunLine, OpCode::AddOption]
-                    .contains(&opcode)
-                    .then(|| {
-                        // Both RunLine and AddOption have the string ID
-                        // they want to show as their first operand, so
-                        // store that
-                        let id: String = instruction.operands[0].clone().try_into().unwrap();
-                        LineId(id)
-                    })
-            })
-            .collect();
+    fn push_line_hints(&mut self, string_ids: Vec<LineId>) {
         self.text_provider.accept_line_hints(&string_ids);
         self.batched_events
             .push(DialogueEvent::LineHints(string_ids));
@@ -196,7 +220,9 @@ impl VirtualMachine {
     ///
     pub(crate) fn continue_(&mut self) -> crate::Result<Vec<DialogueEvent>> {
         self.assert_can_continue()?;
+        self.record_trace_step(TraceStep::Continued);
         self.set_execution_state(ExecutionState::Running);
+        self.current_line = None;
 
         while self.execution_state == ExecutionState::Running {
             let current_node = self.current_node.clone().unwrap();
@@ -216,7 +242,12 @@ impl VirtualMachine {
             self.batched_events.push(DialogueEvent::DialogueComplete);
             debug!("Run complete.");
         }
-        Ok(std::mem::take(&mut self.batched_events))
+        Ok(core::mem::take(&mut self.batched_events))
+    }
+
+    pub(crate) fn prepare_current_line(&mut self) -> Option<Result<Line>> {
+        let (string_id, substitutions) = self.current_line.clone()?;
+        Some(self.prepare_line(string_id, &substitutions))
     }
 
     pub(crate) fn parse_markup(&mut self, line: &str) -> crate::markup::Result<ParsedMarkup> {
@@ -229,6 +260,8 @@ impl VirtualMachine {
             Err(DialogueError::NoNodeSelectedOnContinue)
         } else if self.execution_state == ExecutionState::WaitingOnOptionSelection {
             Err(DialogueError::ContinueOnOptionSelectionError)
+        } else if self.execution_state == ExecutionState::WaitingForFunctionCall {
+            Err(DialogueError::ContinueOnPendingFunctionCallError)
         } else {
             // ## Implementation note:
             // The other checks the original did are not needed because our relevant handlers cannot be `None` per our API.
@@ -236,6 +269,27 @@ impl VirtualMachine {
         }
     }
 
+    /// Replaces the loaded program. The execution position is kept if the running node is still present with the same content.
+    pub(crate) fn replace_program(&mut self, program: Program) {
+        let compatible_node = self
+            .current_node
+            .as_ref()
+            .filter(|_| self.current_node_name.is_some())
+            .and_then(|current_node| {
+                program
+                    .nodes
+                    .get(&current_node.name)
+                    .filter(|node| node.content_hash() == current_node.content_hash())
+                    .cloned()
+            });
+        self.program.replace(program);
+        if compatible_node.is_some() {
+            self.current_node = compatible_node;
+        } else {
+            self.reset_state();
+        }
+    }
+
     pub(crate) fn unload_programs(&mut self) {
         self.program = None
     }
@@ -244,19 +298,44 @@ impl VirtualMachine {
         if self.execution_state != ExecutionState::WaitingOnOptionSelection {
             return Err(DialogueError::UnexpectedOptionSelectionError);
         }
-        if selected_option_id.0 >= self.state.current_options.len() {
+        // ## Implementation note:
+        // The delivered options may have been reordered or filtered by the options processor,
+        // so we look the option up by its ID instead of using the ID as an index.
+        let Some(selected_option) = self
+            .state
+            .current_options
+            .iter()
+            .find(|option| option.id == selected_option_id)
+        else {
             return Err(DialogueError::InvalidOptionIdError {
                 selected_option_id,
-                max_id: self.state.current_options.len().saturating_sub(1),
+                max_id: self
+                    .state
+                    .current_options
+                    .iter()
+                    .map(|option| option.id.0)
+                    .max()
+                    .unwrap_or_default(),
             });
-        }
+        };
 
         // We now know what number option was selected; push the
         // corresponding node name to the stack.
-        let destination_node = self.state.current_options[selected_option_id.0]
-            .destination_node
-            .clone();
+        let destination_node = selected_option.destination_node.clone();
+        let line_id = selected_option.line.id.clone();
+        if self.line_hints_enabled && self.line_hints_granularity == LineHintsGranularity::Block {
+            let start = self.find_instruction_point_for_label(&destination_node);
+            let string_ids = block_line_hints(self.current_node.as_ref().unwrap(), start);
+            self.push_line_hints(string_ids);
+        }
         self.state.push(destination_node);
+        self.record_trace_step(TraceStep::OptionSelected {
+            option_id: selected_option_id,
+        });
+        self.metrics.options_chosen += 1;
+        self.record_coverage(|coverage| {
+            *coverage.options_selected.entry(line_id).or_default() += 1
+        });
 
         // We no longer need the accumulated list of options; clear it
         // so that it's ready for the next one
@@ -267,6 +346,10 @@ impl VirtualMachine {
         Ok(())
     }
 
+    pub(crate) fn execution_state(&self) -> ExecutionState {
+        self.execution_state
+    }
+
     pub(crate) fn is_active(&self) -> bool {
         self.execution_state != ExecutionState::Stopped
     }
@@ -275,6 +358,86 @@ impl VirtualMachine {
         self.execution_state == ExecutionState::WaitingOnOptionSelection
     }
 
+    pub(crate) fn is_waiting_for_function_call(&self) -> bool {
+        self.execution_state == ExecutionState::WaitingForFunctionCall
+    }
+
+    /// Drives the future of the async function the [`VirtualMachine`] is waiting on.
+    /// Once it resolves, its value is pushed onto the stack and the [`VirtualMachine`] waits for [`VirtualMachine::continue_`] to be called.
+    pub(crate) fn poll_pending_function_call(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let Some(pending_function_call) = self.pending_function_call.as_ref() else {
+            return Poll::Ready(Err(DialogueError::NoPendingFunctionCallError));
+        };
+        let result = ready!(pending_function_call.poll(cx));
+        let pending_function_call = self.pending_function_call.take().unwrap();
+        match result {
+            Ok(return_value) => {
+                self.record_trace_step(TraceStep::FunctionCalled {
+                    name: pending_function_call.function_name,
+                    return_value: return_value.clone(),
+                });
+                self.state.push(InternalValue {
+                    raw_value: return_value,
+                    r#type: pending_function_call.return_type,
+                });
+                self.set_execution_state(ExecutionState::WaitingForContinue);
+                Poll::Ready(Ok(()))
+            }
+            Err(error) => {
+                let error = self.function_call_error(
+                    pending_function_call.function_name,
+                    pending_function_call.instruction_index,
+                    error,
+                );
+                self.set_execution_state(ExecutionState::Stopped);
+                Poll::Ready(Err(error))
+            }
+        }
+    }
+
+    fn function_call_error(
+        &self,
+        function_name: String,
+        instruction_index: usize,
+        error: YarnFnError,
+    ) -> DialogueError {
+        let node_name = self.current_node_name.clone().unwrap_or_default();
+        let line_info = self
+            .debug_info
+            .get(&node_name)
+            .and_then(|debug_info| debug_info.try_get_line_info(instruction_index))
+            .map(Box::new);
+        DialogueError::FunctionCallError {
+            function_name,
+            node_name,
+            instruction_index,
+            line_info,
+            error,
+        }
+    }
+
+    /// Produces the return value of a function that is not in the [`Library`] according to the [`MissingFunctionBehavior`].
+    fn call_missing_function(
+        &mut self,
+        function_name: &str,
+        parameters: Vec<YarnValue>,
+    ) -> Result<YarnValue> {
+        let return_value = match &mut self.missing_function_behavior {
+            MissingFunctionBehavior::Error => None,
+            MissingFunctionBehavior::WarnAndReturn(value) => {
+                warn!("Function \"{function_name}\" not found in library. Returning {value} in its place.");
+                Some(value.clone())
+            }
+            MissingFunctionBehavior::Handler(handler) => {
+                handler.handle_missing_function(function_name, &parameters)
+            }
+        };
+        return_value.ok_or_else(|| DialogueError::FunctionNotFound {
+            function_name: function_name.to_owned(),
+            library: self.library.clone(),
+        })
+    }
+
     pub(crate) fn current_node(&self) -> Option<String> {
         self.current_node_name.clone()
     }
@@ -310,9 +473,14 @@ impl VirtualMachine {
                 assert_up_to_date_compiler(instruction.operands.len() >= 2);
 
                 let substitutions = self.pop_substitutions_with_count_at_operand(instruction, 1);
-                let line = self.prepare_line(string_id, &substitutions)?;
+                let line = self.prepare_line(string_id.clone(), &substitutions)?;
+                self.record_coverage(|coverage| {
+                    *coverage.lines.entry(string_id.clone()).or_default() += 1
+                });
+                self.current_line = Some((string_id, substitutions));
 
                 self.batched_events.push(DialogueEvent::Line(line));
+                self.metrics.lines_delivered += 1;
 
                 // Implementation note:
                 // In the original, this is only done if `execution_state` is still `DeliveringContent`,
@@ -336,6 +504,7 @@ impl VirtualMachine {
                 let command = Command::parse(command_text);
 
                 self.batched_events.push(DialogueEvent::Command(command));
+                self.metrics.commands_executed += 1;
 
                 // Implementation note:
                 // In the original, this is only done if `execution_state` is still `DeliveringContent`,
@@ -351,7 +520,10 @@ impl VirtualMachine {
                 let string_id: LineId = string_id.into();
                 assert_up_to_date_compiler(instruction.operands.len() >= 4);
                 let substitutions = self.pop_substitutions_with_count_at_operand(instruction, 2);
-                let line = self.prepare_line(string_id, &substitutions)?;
+                let line = self.prepare_line(string_id.clone(), &substitutions)?;
+                self.record_coverage(|coverage| {
+                    *coverage.options_presented.entry(string_id).or_default() += 1
+                });
 
                 // Indicates whether the VM believes that the
                 // option should be shown to the user, based on any
@@ -382,6 +554,10 @@ impl VirtualMachine {
                 self.state.program_counter += 1;
             }
             OpCode::ShowOptions => {
+                if let Some(options_processor) = self.options_processor.as_mut() {
+                    options_processor.process_options(&mut self.state.current_options);
+                }
+
                 // If we have no options to show, immediately stop.
                 if self.state.current_options.is_empty() {
                     self.batched_events.push(DialogueEvent::DialogueComplete);
@@ -399,6 +575,7 @@ impl VirtualMachine {
                 let current_options = self.state.current_options.clone();
                 self.batched_events
                     .push(DialogueEvent::Options(current_options));
+                self.metrics.options_presented += 1;
 
                 // Implementation note:
                 // Not checking the execution state now since we have no line handler to call `continue_` from.
@@ -412,7 +589,7 @@ impl VirtualMachine {
             }
             OpCode::PushFloat => {
                 // Pushes a floating point onto the stack.
-                let float: f32 = instruction.read_operand(0);
+                let float: f64 = instruction.read_operand(0);
                 self.state.push(float);
                 self.state.program_counter += 1;
             }
@@ -429,6 +606,15 @@ impl VirtualMachine {
             OpCode::JumpIfFalse => {
                 // Jumps to a named label if the value on the top of the stack evaluates to the boolean value 'false'.
                 let is_top_value_true: bool = self.state.peek();
+                let instruction_index = self.state.program_counter;
+                self.record_coverage(|coverage| {
+                    let branch = coverage.branches.entry(instruction_index).or_default();
+                    if is_top_value_true {
+                        branch.times_true += 1;
+                    } else {
+                        branch.times_false += 1;
+                    }
+                });
                 if !is_top_value_true {
                     let label_name: String = instruction.read_operand(0);
                     let instruction_point = self.find_instruction_point_for_label(&label_name);
@@ -456,29 +642,89 @@ impl VirtualMachine {
 
                 // Call a function, whose parameters are expected to be on the stack. Pushes the function's return value, if it returns one.
                 let function_name: String = instruction.read_operand(0);
-                let function =
+                // Calls to overloaded functions are usually resolved by the compiler already.
+                // Otherwise, pick the overload based on the arguments.
+                let function = self.library.get(&function_name).or_else(|| {
                     self.library
-                        .get(&function_name)
-                        .ok_or(DialogueError::FunctionNotFound {
-                            function_name: function_name.to_string(),
-                            library: self.library.clone(),
-                        })?;
+                        .resolve_overload(&function_name, &parameters)
+                        .map(|(_overload_name, function)| function)
+                });
+                let Some(function) = function else {
+                    let return_value = if self.is_replaying_trace() {
+                        self.replay_function_call(&function_name)?
+                    } else {
+                        let return_value =
+                            self.call_missing_function(&function_name, parameters)?;
+                        self.record_trace_step(TraceStep::FunctionCalled {
+                            name: function_name,
+                            return_value: return_value.clone(),
+                        });
+                        return_value
+                    };
+                    // The return type of a missing function is unknown, so the best guess is the type of the value returned in its place
+                    self.state.push(InternalValue {
+                        r#type: return_value.r#type(),
+                        raw_value: return_value,
+                    });
+                    self.state.program_counter += 1;
+                    return Ok(());
+                };
 
                 // Expect the compiler to have placed the number of parameters
                 // actually passed at the top of the stack.
                 let expected_parameter_count = function.parameter_types().len();
 
-                assert_eq!(
-                    expected_parameter_count, actual_parameter_count,
-                    "Function {function_name} expected {expected_parameter_count} parameters, but received {actual_parameter_count}",
-                );
+                if function.variadic_parameter_type().is_some() {
+                    assert!(
+                        actual_parameter_count >= expected_parameter_count,
+                        "Function {function_name} expected at least {expected_parameter_count} parameters, but received {actual_parameter_count}",
+                    );
+                } else {
+                    assert_eq!(
+                        expected_parameter_count, actual_parameter_count,
+                        "Function {function_name} expected {expected_parameter_count} parameters, but received {actual_parameter_count}",
+                    );
+                }
 
-                // Invoke the function
-                let return_value = function.call(parameters);
                 let return_type = function
                     .return_type()
                     .try_into()
                     .unwrap_or_else(|e| panic!("Failed to get Yarn type for return type id of function {function_name}: {e:?}"));
+
+                // Invoke the function, unless we are replaying a trace that already knows what it returned
+                let instruction_index = self.state.program_counter;
+                let is_async = function.is_async();
+                let language_code = self.language_code.as_ref().map(ToString::to_string);
+                let context = YarnFnContext::new()
+                    .with_node_name(self.current_node_name.as_deref())
+                    .with_language_code(language_code.as_deref())
+                    .with_variables(&self.variable_storage);
+                let return_value = if self.is_replaying_trace() {
+                    self.replay_function_call(&function_name)?
+                } else if is_async {
+                    // Wait for the host to drive the future to completion, which pushes its value
+                    let future = function.call_async(parameters, &context).map_err(|error| {
+                        self.function_call_error(function_name.clone(), instruction_index, error)
+                    })?;
+                    self.pending_function_call = Some(PendingFunctionCall::new(
+                        function_name,
+                        instruction_index,
+                        return_type,
+                        future,
+                    ));
+                    self.set_execution_state(ExecutionState::WaitingForFunctionCall);
+                    self.state.program_counter += 1;
+                    return Ok(());
+                } else {
+                    let return_value = function.call(parameters, &context).map_err(|error| {
+                        self.function_call_error(function_name.clone(), instruction_index, error)
+                    })?;
+                    self.record_trace_step(TraceStep::FunctionCalled {
+                        name: function_name.clone(),
+                        return_value: return_value.clone(),
+                    });
+                    return_value
+                };
                 let typed_return_value = InternalValue {
                     raw_value: return_value,
                     r#type: return_type,
@@ -488,44 +734,44 @@ impl VirtualMachine {
                 // In current Yarn, every function MUST return a valid typed value, so we skip that check.
                 self.state.push(typed_return_value);
                 self.state.program_counter += 1;
+                if self.is_replaying_trace() && is_async {
+                    // When recording, the dialogue waited for the future and was continued afterwards,
+                    // so the replay pauses here as well to stay in step with the trace
+                    self.set_execution_state(ExecutionState::WaitingForContinue);
+                }
             }
             OpCode::PushVariable => {
                 // Get the contents of a variable, push that onto the stack.
                 let variable_name: String = instruction.read_operand(0);
-                let loaded_value = self
-                    .variable_storage
-                    .get(&variable_name)
-                    .or_else(|e| {
-                        if let VariableStorageError::VariableNotFound { .. } = e {
-                            // We don't have a value for this. The initial
-                            // value may be found in the program. (If it's
-                            // not, then the variable's value is undefined,
-                            // which isn't allowed.)
-                            let initial_value = self
-                                .program
-                                .as_ref()
-                                .unwrap()
-                                .initial_values
-                                .get(&variable_name)
-                                .unwrap_or_else(|| panic!("The loaded program does not contain an initial value for the variable {variable_name}"))
-                                .clone();
-
-                            // Store the initial value in the variable_storage
-                            self.variable_storage.set(variable_name.clone(), initial_value.clone().into())?;
-
-                            Ok(initial_value.into())
-                        } else {
-                            Err(e)
-                        }
-                    })?;
+                let loaded_value = if self.is_replaying_trace() {
+                    self.replay_variable_read(&variable_name)?
+                } else {
+                    let loaded_value = self.read_variable(&variable_name)?;
+                    self.record_trace_step(TraceStep::VariableRead {
+                        name: variable_name.clone(),
+                        value: loaded_value.clone(),
+                    });
+                    loaded_value
+                };
                 self.state.push(loaded_value);
                 self.state.program_counter += 1;
             }
             OpCode::StoreVariable => {
                 // Store the top value on the stack in a variable.
-                let top_value = self.state.peek_value().clone();
+                let new_value: YarnValue = self.state.peek_value().clone().into();
                 let variable_name: String = instruction.read_operand(0);
-                self.variable_storage.set(variable_name, top_value.into())?;
+                if self.variable_changed_events_enabled {
+                    let old_value = self.current_variable_value(&variable_name);
+                    self.variable_storage
+                        .set(variable_name.clone(), new_value.clone())?;
+                    self.batched_events.push(DialogueEvent::VariableChanged {
+                        name: variable_name,
+                        old_value,
+                        new_value,
+                    });
+                } else {
+                    self.variable_storage.set(variable_name, new_value)?;
+                }
                 self.state.program_counter += 1;
             }
             OpCode::Stop => {
@@ -554,6 +800,151 @@ impl VirtualMachine {
         Ok(())
     }
 
+    /// Like [`VirtualMachine::read_variable`], but without storing the initial value or failing on undefined variables.
+    fn current_variable_value(&self, variable_name: &str) -> Option<YarnValue> {
+        self.variable_storage.get(variable_name).ok().or_else(|| {
+            self.program
+                .as_ref()?
+                .initial_values
+                .get(variable_name)
+                .cloned()
+                .map(Into::into)
+        })
+    }
+
+    fn read_variable(&mut self, variable_name: &str) -> Result<YarnValue> {
+        let loaded_value = self
+            .variable_storage
+            .get(variable_name)
+            .or_else(|e| {
+                if let VariableStorageError::VariableNotFound { .. } = e {
+                    // We don't have a value for this. The initial
+                    // value may be found in the program. (If it's
+                    // not, then the variable's value is undefined,
+                    // which isn't allowed.)
+                    let initial_value = self
+                        .program
+                        .as_ref()
+                        .unwrap()
+                        .initial_values
+                        .get(variable_name)
+                        .unwrap_or_else(|| panic!("The loaded program does not contain an initial value for the variable {variable_name}"))
+                        .clone();
+
+                    // Store the initial value in the variable_storage
+                    self.variable_storage.set(variable_name.to_owned(), initial_value.clone().into())?;
+
+                    Ok(initial_value.into())
+                } else {
+                    Err(e)
+                }
+            })?;
+        Ok(loaded_value)
+    }
+
+    pub(crate) fn start_recording_trace(&mut self) {
+        self.trace = Some(TraceState::Recording(DialogueTrace::default()));
+    }
+
+    pub(crate) fn stop_recording_trace(&mut self) -> Option<DialogueTrace> {
+        match self.trace.take() {
+            Some(TraceState::Recording(trace)) => Some(trace),
+            trace => {
+                self.trace = trace;
+                None
+            }
+        }
+    }
+
+    pub(crate) fn record_trace_step(&mut self, step: TraceStep) {
+        if let Some(TraceState::Recording(trace)) = self.trace.as_mut() {
+            trace.steps.push(step);
+        }
+    }
+
+    pub(crate) fn start_recording_coverage(&mut self) {
+        self.coverage = Some(DialogueCoverage::default());
+    }
+
+    pub(crate) fn stop_recording_coverage(&mut self) -> Option<DialogueCoverage> {
+        self.coverage.take()
+    }
+
+    pub(crate) fn coverage(&self) -> Option<&DialogueCoverage> {
+        self.coverage.as_ref()
+    }
+
+    /// Records coverage for the node that is currently running, if coverage is being recorded.
+    fn record_coverage(&mut self, record: impl FnOnce(&mut NodeCoverage)) {
+        let (Some(coverage), Some(node_name)) = (self.coverage.as_mut(), &self.current_node_name)
+        else {
+            return;
+        };
+        record(coverage.nodes.entry(node_name.clone()).or_default());
+    }
+
+    fn is_replaying_trace(&self) -> bool {
+        matches!(self.trace, Some(TraceState::Replaying(_)))
+    }
+
+    fn next_replayed_step(&mut self) -> Option<TraceStep> {
+        match self.trace.as_mut() {
+            Some(TraceState::Replaying(steps)) => steps.pop_front(),
+            _ => None,
+        }
+    }
+
+    /// Re-drives the dialogue from the given trace, taking every decision from it instead of from the game,
+    /// the [`VariableStorage`] or the [`Library`]. Returns all events that were emitted along the way.
+    pub(crate) fn replay_trace(&mut self, trace: DialogueTrace) -> Result<Vec<DialogueEvent>> {
+        let previous_trace = self
+            .trace
+            .replace(TraceState::Replaying(trace.steps.into()));
+        let events = self.replay_remaining_steps();
+        self.trace = previous_trace;
+        events
+    }
+
+    fn replay_remaining_steps(&mut self) -> Result<Vec<DialogueEvent>> {
+        let mut events = Vec::new();
+        while let Some(step) = self.next_replayed_step() {
+            match step {
+                TraceStep::NodeStarted { node_name } => self.set_node(node_name)?,
+                TraceStep::Continued => events.extend(self.continue_()?),
+                TraceStep::OptionSelected { option_id } => self.set_selected_option(option_id)?,
+                step => {
+                    return Err(DialogueError::TraceMismatch {
+                        expected: "start of a node, continuation or option selection".to_owned(),
+                        found: Some(step),
+                    })
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    fn replay_variable_read(&mut self, variable_name: &str) -> Result<YarnValue> {
+        match self.next_replayed_step() {
+            Some(TraceStep::VariableRead { name, value }) if name == variable_name => Ok(value),
+            found => Err(DialogueError::TraceMismatch {
+                expected: format!("read of {variable_name}"),
+                found,
+            }),
+        }
+    }
+
+    fn replay_function_call(&mut self, function_name: &str) -> Result<YarnValue> {
+        match self.next_replayed_step() {
+            Some(TraceStep::FunctionCalled { name, return_value }) if name == function_name => {
+                Ok(return_value)
+            }
+            found => Err(DialogueError::TraceMismatch {
+                expected: format!("call of {function_name}()"),
+                found,
+            }),
+        }
+    }
+
     fn prepare_line(&mut self, string_id: LineId, substitutions: &[String]) -> Result<Line> {
         let line_text = self.text_provider.get_text(&string_id).ok_or_else(|| {
             DialogueError::LineProviderError {
@@ -2,10 +2,16 @@
 
 #[cfg(any(feature = "bevy", feature = "serde"))]
 use crate::prelude::*;
+#[cfg(doc)]
+use crate::prelude::{Dialogue, DialogueEvent};
 
+/// The state of execution a [`Dialogue`] is in, see [`Dialogue::execution_state`].
+/// Changes are reported as [`DialogueEvent::ExecutionStateChanged`] if [`Dialogue::execution_state_events_enabled`] is set.
+///
 /// ## Implementation notes
 /// Does not contain `DeliveringContent` since that that state would be used to indicate
-/// that a handler is currently running, which we don't have.
+/// that a handler is currently running, which we don't have. Instead, the dialogue is [`ExecutionState::WaitingForContinue`]
+/// while the host delivers the content returned by [`Dialogue::continue_`].
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
 #[cfg_attr(feature = "bevy", derive(Reflect))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -14,21 +20,25 @@ use crate::prelude::*;
     all(feature = "bevy", feature = "serde"),
     reflect(Serialize, Deserialize)
 )]
-pub(crate) enum ExecutionState {
-    /// The VirtualMachine is not running a node.
+pub enum ExecutionState {
+    /// The dialogue is not running a node.
     #[default]
     Stopped,
 
-    /// The VirtualMachine is waiting on option selection. Call
-    /// [`VirtualMachine::set_selected_option`] before calling
-    /// [`VirtualMachine::next`].
+    /// The dialogue is waiting on option selection. Call
+    /// [`Dialogue::set_selected_option`] before calling
+    /// [`Dialogue::continue_`].
     WaitingOnOptionSelection,
 
-    /// The VirtualMachine has finished delivering content to the
-    /// client game, and is waiting for [`VirtualMachine::next`]
+    /// The dialogue has finished delivering content to the
+    /// client game, and is waiting for [`Dialogue::continue_`]
     /// to be called.
     WaitingForContinue,
 
-    /// The VirtualMachine is in the middle of executing code.
+    /// The dialogue is waiting for the future returned by an async function to resolve.
+    /// Call [`Dialogue::poll_pending_function_call`] until it does before calling [`Dialogue::continue_`].
+    WaitingForFunctionCall,
+
+    /// The dialogue is in the middle of executing code.
     Running,
 }
@@ -0,0 +1,51 @@
+//! Not part of the original Yarn Spinner, which only supports functions that return immediately.
+
+use crate::prelude::*;
+use core::fmt::{self, Debug};
+use core::task::{Context, Poll};
+use yarnspinner_core::prelude::*;
+use yarnspinner_core::types::Type;
+
+/// A call to an async [`YarnFn`] that the [`VirtualMachine`] is waiting on.
+///
+/// Clones of the [`VirtualMachine`] share the same pending future, since futures cannot be cloned.
+#[derive(Clone)]
+pub(crate) struct PendingFunctionCall {
+    pub(crate) function_name: String,
+    pub(crate) instruction_index: usize,
+    pub(crate) return_type: Type,
+    future: Arc<Mutex<YarnValueFuture>>,
+}
+
+impl PendingFunctionCall {
+    pub(crate) fn new(
+        function_name: String,
+        instruction_index: usize,
+        return_type: Type,
+        future: YarnValueFuture,
+    ) -> Self {
+        Self {
+            function_name,
+            instruction_index,
+            return_type,
+            future: Arc::new(Mutex::new(future)),
+        }
+    }
+
+    pub(crate) fn poll(
+        &self,
+        cx: &mut Context<'_>,
+    ) -> Poll<core::result::Result<YarnValue, YarnFnError>> {
+        self.future.lock().as_mut().poll(cx)
+    }
+}
+
+impl Debug for PendingFunctionCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingFunctionCall")
+            .field("function_name", &self.function_name)
+            .field("instruction_index", &self.instruction_index)
+            .field("return_type", &self.return_type)
+            .finish_non_exhaustive()
+    }
+}
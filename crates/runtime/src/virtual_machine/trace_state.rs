@@ -0,0 +1,12 @@
+//! Not part of the original Yarn Spinner. Tracks whether the [`VirtualMachine`] is recording or replaying a [`DialogueTrace`].
+
+use crate::prelude::*;
+use alloc::collections::VecDeque;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TraceState {
+    /// Decision points are appended to the trace as they happen.
+    Recording(DialogueTrace),
+    /// Decision points are taken from the front of the queue instead of being made by the game, the [`VariableStorage`] or the [`Library`].
+    Replaying(VecDeque<TraceStep>),
+}
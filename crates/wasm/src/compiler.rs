@@ -0,0 +1,150 @@
+use crate::YarnDialogue;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use yarnspinner::compiler::{Compilation, Compiler, Diagnostic, DiagnosticSeverity, File};
+
+/// Collects Yarn files and compiles them, like [`yarnspinner::compiler::Compiler`].
+#[wasm_bindgen]
+#[derive(Debug, Default)]
+pub struct YarnCompiler {
+    files: Vec<File>,
+}
+
+#[wasm_bindgen]
+impl YarnCompiler {
+    /// Creates a compiler without any files.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the source of a Yarn file. The name is used in diagnostics and to derive implicit line IDs.
+    #[wasm_bindgen(js_name = addFile)]
+    pub fn add_file(&mut self, file_name: String, source: String) {
+        self.files.push(File { file_name, source });
+    }
+
+    /// Compiles all added files. Never throws, check [`CompileResult::succeeded`] and [`CompileResult::diagnostics`] instead.
+    pub fn compile(&self) -> CompileResult {
+        let mut compiler = Compiler::new();
+        for file in &self.files {
+            compiler.add_file(file.clone());
+        }
+        match compiler.compile() {
+            Ok(compilation) => CompileResult {
+                diagnostics: compilation.warnings.iter().map(Into::into).collect(),
+                compilation: Some(compilation),
+            },
+            Err(error) => CompileResult {
+                diagnostics: error.0.iter().map(Into::into).collect(),
+                compilation: None,
+            },
+        }
+    }
+}
+
+/// The outcome of [`YarnCompiler::compile`]: the compiled program, if there were no errors, and all diagnostics.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct CompileResult {
+    compilation: Option<Compilation>,
+    diagnostics: Vec<WasmDiagnostic>,
+}
+
+#[wasm_bindgen]
+impl CompileResult {
+    /// Whether the files compiled without errors. Warnings may still have been reported.
+    #[wasm_bindgen(getter)]
+    pub fn succeeded(&self) -> bool {
+        self.compilation.is_some()
+    }
+
+    /// The errors and warnings of the compilation as an array of [`WasmDiagnostic`] objects.
+    #[wasm_bindgen(getter, js_name = diagnostics)]
+    pub fn diagnostics_js(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.diagnostics)?)
+    }
+
+    /// The names of all nodes in the program, sorted alphabetically. Empty if the compilation failed.
+    #[wasm_bindgen(getter, js_name = nodeNames)]
+    pub fn node_names(&self) -> Vec<String> {
+        let mut node_names: Vec<_> = self
+            .compilation
+            .iter()
+            .flat_map(|compilation| &compilation.program)
+            .flat_map(|program| program.nodes.keys().cloned())
+            .collect();
+        node_names.sort();
+        node_names
+    }
+
+    /// Creates a dialogue that starts at the given node. Throws if the compilation failed or the node does not exist.
+    #[wasm_bindgen(js_name = startDialogue)]
+    pub fn start_dialogue_js(&self, node_name: &str) -> Result<YarnDialogue, JsError> {
+        self.start_dialogue(node_name)
+            .map_err(|message| JsError::new(&message))
+    }
+}
+
+impl CompileResult {
+    /// The errors and warnings of the compilation.
+    pub fn diagnostics(&self) -> &[WasmDiagnostic] {
+        &self.diagnostics
+    }
+
+    /// The compilation, if it succeeded.
+    pub fn compilation(&self) -> Option<&Compilation> {
+        self.compilation.as_ref()
+    }
+
+    /// Rust counterpart of `startDialogue`.
+    pub fn start_dialogue(&self, node_name: &str) -> Result<YarnDialogue, String> {
+        let compilation = self
+            .compilation
+            .as_ref()
+            .ok_or_else(|| "Cannot start a dialogue because the compilation failed".to_owned())?;
+        let mut dialogue = YarnDialogue::new(compilation)?;
+        dialogue
+            .set_node(node_name)
+            .map_err(|error| error.to_string())?;
+        Ok(dialogue)
+    }
+}
+
+/// A compiler error or warning as passed to JavaScript. Lines and columns are one-indexed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WasmDiagnostic {
+    /// Either `"error"` or `"warning"`.
+    pub severity: &'static str,
+    /// The description of the issue.
+    pub message: String,
+    /// The name of the file the issue occurred in.
+    pub file: Option<String>,
+    /// The line the issue starts on.
+    pub line: Option<usize>,
+    /// The column the issue starts at.
+    pub column: Option<usize>,
+    /// The line the issue ends on.
+    pub end_line: Option<usize>,
+    /// The column the issue ends at.
+    pub end_column: Option<usize>,
+}
+
+impl From<&Diagnostic> for WasmDiagnostic {
+    fn from(diagnostic: &Diagnostic) -> Self {
+        let range = diagnostic.range.as_ref();
+        Self {
+            severity: match diagnostic.severity {
+                DiagnosticSeverity::Error => "error",
+                DiagnosticSeverity::Warning => "warning",
+            },
+            message: diagnostic.message.clone(),
+            file: diagnostic.file_name.clone(),
+            line: range.map(|range| range.start.line + 1),
+            column: range.map(|range| range.start.character + 1),
+            end_line: range.map(|range| range.end.line + 1),
+            end_column: range.map(|range| range.end.character + 1),
+        }
+    }
+}
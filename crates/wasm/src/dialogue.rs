@@ -0,0 +1,135 @@
+use crate::{WasmEvent, WasmValue};
+use wasm_bindgen::prelude::*;
+use yarnspinner::compiler::Compilation;
+use yarnspinner::prelude::*;
+use yarnspinner::runtime::{MemoryVariableStorage, StringTableTextProvider, VariableStorageError};
+
+/// Runs a compiled program, like [`Dialogue`]. Created by `CompileResult.startDialogue`.
+///
+/// Lines are delivered in the base language of the compiled files. Variables are kept in memory.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct YarnDialogue {
+    dialogue: Dialogue,
+}
+
+#[wasm_bindgen]
+impl YarnDialogue {
+    /// Runs the dialogue until the next options, the next line or its end and returns an array of the events that occurred.
+    /// See [`WasmEvent`] for their shape. Throws on runtime errors, e.g. a missing function.
+    #[wasm_bindgen(js_name = "continue")]
+    pub fn continue_js(&mut self) -> Result<JsValue, JsError> {
+        let events = self.continue_()?;
+        Ok(serde_wasm_bindgen::to_value(&events)?)
+    }
+
+    /// Selects the option with the given `id` from the last `options` event. Call `continue` afterwards.
+    #[wasm_bindgen(js_name = selectOption)]
+    pub fn select_option_js(&mut self, option_id: usize) -> Result<(), JsError> {
+        Ok(self.select_option(option_id)?)
+    }
+
+    /// Jumps to the start of the given node. Call `continue` afterwards.
+    #[wasm_bindgen(js_name = setNode)]
+    pub fn set_node_js(&mut self, node_name: &str) -> Result<(), JsError> {
+        Ok(self.set_node(node_name)?)
+    }
+
+    /// Gets the value of a variable, e.g. `"$gold"`, or `undefined` if it was neither set nor declared.
+    #[wasm_bindgen(js_name = getVariable)]
+    pub fn variable_js(&self, name: &str) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.variable(name))?)
+    }
+
+    /// Sets a variable to a number, string, boolean or array of those.
+    #[wasm_bindgen(js_name = setVariable)]
+    pub fn set_variable_js(&mut self, name: &str, value: JsValue) -> Result<(), JsError> {
+        let value: WasmValue = serde_wasm_bindgen::from_value(value)?;
+        Ok(self.set_variable(name, value)?)
+    }
+
+    /// Whether the dialogue is running, i.e. `continue` was called and the dialogue has not completed yet.
+    #[wasm_bindgen(getter, js_name = isActive)]
+    pub fn is_active(&self) -> bool {
+        self.dialogue.is_active()
+    }
+
+    /// The name of the node that is currently running.
+    #[wasm_bindgen(getter, js_name = currentNode)]
+    pub fn current_node(&self) -> Option<String> {
+        self.dialogue.current_node()
+    }
+}
+
+impl YarnDialogue {
+    pub(crate) fn new(compilation: &Compilation) -> Result<Self, String> {
+        let program = compilation
+            .program
+            .clone()
+            .ok_or_else(|| "The compiler did not produce a program".to_owned())?;
+        let mut text_provider = StringTableTextProvider::new();
+        text_provider.extend_base_language(
+            compilation
+                .string_table
+                .iter()
+                .map(|(id, string_info)| (id.clone(), string_info.text.clone()))
+                .collect(),
+        );
+        let mut dialogue = Dialogue::new(
+            Box::new(MemoryVariableStorage::new()),
+            Box::new(text_provider),
+        );
+        dialogue
+            .set_variable_changed_events_enabled(true)
+            .add_program(program)
+            .add_debug_info(compilation.debug_info.clone())
+            .add_line_metadata(
+                compilation
+                    .string_table
+                    .iter()
+                    .map(|(id, string_info)| (id.clone(), string_info.metadata.clone())),
+            );
+        Ok(Self { dialogue })
+    }
+
+    /// Rust counterpart of `continue`.
+    pub fn continue_(&mut self) -> Result<Vec<WasmEvent>, DialogueError> {
+        let events = self.dialogue.continue_()?;
+        Ok(events
+            .into_iter()
+            .map(|event| WasmEvent::new(event, &self.dialogue))
+            .collect())
+    }
+
+    /// Rust counterpart of `selectOption`.
+    pub fn select_option(&mut self, option_id: usize) -> Result<(), DialogueError> {
+        self.dialogue.set_selected_option(OptionId(option_id))?;
+        Ok(())
+    }
+
+    /// Rust counterpart of `setNode`.
+    pub fn set_node(&mut self, node_name: &str) -> Result<(), DialogueError> {
+        self.dialogue.set_node(node_name)?;
+        Ok(())
+    }
+
+    /// Rust counterpart of `getVariable`.
+    pub fn variable(&self, name: &str) -> Option<WasmValue> {
+        self.dialogue
+            .variable_storage()
+            .get(name)
+            .ok()
+            .map(|value| (&value).into())
+    }
+
+    /// Rust counterpart of `setVariable`.
+    pub fn set_variable(
+        &mut self,
+        name: &str,
+        value: WasmValue,
+    ) -> Result<(), VariableStorageError> {
+        self.dialogue
+            .variable_storage_mut()
+            .set(name.to_owned(), value.into())
+    }
+}
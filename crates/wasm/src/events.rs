@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+use yarnspinner::prelude::*;
+
+/// A [`DialogueEvent`] as passed to JavaScript, e.g. `{ type: "line", id: "line:intro", text: "Hi!", ... }`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum WasmEvent {
+    /// See [`DialogueEvent::Line`].
+    Line(WasmLine),
+    /// See [`DialogueEvent::Options`].
+    Options {
+        /// The options to choose from, including unavailable ones.
+        options: Vec<WasmOption>,
+    },
+    /// See [`DialogueEvent::Command`].
+    Command {
+        /// The name of the command, e.g. `"fade_in"`.
+        name: String,
+        /// The evaluated parameters of the command.
+        parameters: Vec<WasmValue>,
+        /// The full text of the command.
+        raw: String,
+    },
+    /// See [`DialogueEvent::NodeStart`].
+    NodeStart {
+        /// The name of the entered node.
+        node_name: String,
+    },
+    /// See [`DialogueEvent::NodeComplete`].
+    NodeComplete {
+        /// The name of the completed node.
+        node_name: String,
+    },
+    /// See [`DialogueEvent::LineHints`].
+    LineHints {
+        /// The IDs of the lines that might be run.
+        line_ids: Vec<String>,
+    },
+    /// See [`DialogueEvent::VariableChanged`].
+    VariableChanged {
+        /// The name of the variable, including the leading `$`.
+        name: String,
+        /// The value before it was set, if there was one.
+        old_value: Option<WasmValue>,
+        /// The value the variable was set to.
+        new_value: WasmValue,
+    },
+    /// See [`DialogueEvent::ExecutionStateChanged`].
+    ExecutionStateChanged {
+        /// The state before, e.g. `"running"`.
+        previous: &'static str,
+        /// The state now, e.g. `"waitingOnOptionSelection"`.
+        current: &'static str,
+    },
+    /// See [`DialogueEvent::DialogueComplete`].
+    DialogueComplete,
+}
+
+/// A [`YarnLine`] as passed to JavaScript.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WasmLine {
+    /// The ID of the line in the string table.
+    pub id: String,
+    /// The text of the line, including the character name.
+    pub text: String,
+    /// The name of the speaking character, if the line starts with one.
+    pub character_name: Option<String>,
+    /// The text of the line without the character name.
+    pub text_without_character_name: String,
+    /// The hashtags of the line without the `#`, including its line ID, e.g. `["line:intro", "sad"]`.
+    pub metadata: Vec<String>,
+}
+
+/// A [`DialogueOption`] as passed to JavaScript.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WasmOption {
+    /// The ID to pass to `selectOption`.
+    pub id: usize,
+    /// The line of the option.
+    pub line: WasmLine,
+    /// The node the option leads to.
+    pub destination_node: String,
+    /// Whether the condition of the option passed.
+    pub is_available: bool,
+}
+
+/// A [`YarnValue`] as passed to and from JavaScript, i.e. a plain number, string, boolean or array.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum WasmValue {
+    /// A JavaScript number.
+    Number(f64),
+    /// A JavaScript string. Objects registered from Rust are passed as their string representation.
+    String(String),
+    /// A JavaScript boolean.
+    Boolean(bool),
+    /// A JavaScript array.
+    List(Vec<WasmValue>),
+}
+
+impl WasmEvent {
+    pub(crate) fn new(event: DialogueEvent, dialogue: &Dialogue) -> Self {
+        match event {
+            DialogueEvent::Line(line) => Self::Line(WasmLine::new(line, dialogue)),
+            DialogueEvent::Options(options) => Self::Options {
+                options: options
+                    .into_iter()
+                    .map(|option| WasmOption {
+                        id: option.id.0,
+                        line: WasmLine::new(option.line, dialogue),
+                        destination_node: option.destination_node,
+                        is_available: option.is_available,
+                    })
+                    .collect(),
+            },
+            DialogueEvent::Command(command) => Self::Command {
+                name: command.name,
+                parameters: command.parameters.iter().map(Into::into).collect(),
+                raw: command.raw,
+            },
+            DialogueEvent::NodeStart(node_name) => Self::NodeStart { node_name },
+            DialogueEvent::NodeComplete(node_name) => Self::NodeComplete { node_name },
+            DialogueEvent::LineHints(line_ids) => Self::LineHints {
+                line_ids: line_ids.into_iter().map(|line_id| line_id.0).collect(),
+            },
+            DialogueEvent::VariableChanged {
+                name,
+                old_value,
+                new_value,
+            } => Self::VariableChanged {
+                name,
+                old_value: old_value.as_ref().map(Into::into),
+                new_value: (&new_value).into(),
+            },
+            DialogueEvent::ExecutionStateChanged { previous, current } => {
+                Self::ExecutionStateChanged {
+                    previous: execution_state_name(previous),
+                    current: execution_state_name(current),
+                }
+            }
+            DialogueEvent::DialogueComplete => Self::DialogueComplete,
+        }
+    }
+}
+
+fn execution_state_name(execution_state: ExecutionState) -> &'static str {
+    match execution_state {
+        ExecutionState::Stopped => "stopped",
+        ExecutionState::WaitingOnOptionSelection => "waitingOnOptionSelection",
+        ExecutionState::WaitingForContinue => "waitingForContinue",
+        ExecutionState::WaitingForFunctionCall => "waitingForFunctionCall",
+        ExecutionState::Running => "running",
+    }
+}
+
+impl WasmLine {
+    fn new(line: YarnLine, dialogue: &Dialogue) -> Self {
+        let line = dialogue.localize_line(line);
+        Self {
+            character_name: line.character_name().map(ToOwned::to_owned),
+            text_without_character_name: line.text_without_character_name(),
+            id: line.id.0,
+            text: line.text,
+            metadata: line.metadata,
+        }
+    }
+}
+
+impl From<&YarnValue> for WasmValue {
+    fn from(value: &YarnValue) -> Self {
+        match value {
+            YarnValue::Number(number) => Self::Number(*number),
+            YarnValue::String(string) => Self::String(string.clone()),
+            YarnValue::Boolean(boolean) => Self::Boolean(*boolean),
+            YarnValue::List(values) => Self::List(values.iter().map(Into::into).collect()),
+            YarnValue::Object(_) => Self::String(value.to_string()),
+        }
+    }
+}
+
+impl From<WasmValue> for YarnValue {
+    fn from(value: WasmValue) -> Self {
+        match value {
+            WasmValue::Number(number) => number.into(),
+            WasmValue::String(string) => string.into(),
+            WasmValue::Boolean(boolean) => boolean.into(),
+            WasmValue::List(values) => {
+                YarnValue::List(values.into_iter().map(Into::into).collect())
+            }
+        }
+    }
+}
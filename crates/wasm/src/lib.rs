@@ -0,0 +1,46 @@
+//! JavaScript bindings for compiling and running Yarn files in the browser,
+//! e.g. for preview tools, web builds of editors or documentation playgrounds.
+//!
+//! Build the bindings with [wasm-pack](https://rustwasm.github.io/wasm-pack/), e.g. `wasm-pack build crates/wasm --target web`.
+//!
+//! ```js
+//! import init, { YarnCompiler } from "./pkg/yarnspinner_wasm.js";
+//!
+//! await init();
+//! const compiler = new YarnCompiler();
+//! compiler.addFile("intro.yarn", source);
+//! const result = compiler.compile();
+//! for (const diagnostic of result.diagnostics) {
+//!     console.warn(`${diagnostic.file}:${diagnostic.line}: ${diagnostic.message}`);
+//! }
+//!
+//! const dialogue = result.startDialogue("Start");
+//! let complete = false;
+//! while (!complete) {
+//!     for (const event of dialogue.continue()) {
+//!         switch (event.type) {
+//!             case "line":
+//!                 console.log(event.text);
+//!                 break;
+//!             case "options":
+//!                 dialogue.selectOption(event.options.find((option) => option.isAvailable).id);
+//!                 break;
+//!             case "dialogueComplete":
+//!                 complete = true;
+//!                 break;
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Values passed to and from JavaScript only exist in a WebAssembly runtime, so every exported method that takes or returns them
+//! has a counterpart for use from Rust, e.g. [`YarnDialogue::continue_`] for `continue`. These are also what the tests exercise.
+#![warn(missing_docs, missing_debug_implementations)]
+
+mod compiler;
+mod dialogue;
+mod events;
+
+pub use compiler::*;
+pub use dialogue::*;
+pub use events::*;
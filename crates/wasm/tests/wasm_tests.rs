@@ -0,0 +1,116 @@
+//! Exercises the Rust counterparts of the JavaScript bindings, since `JsValue`s only exist in a WebAssembly runtime.
+
+use yarnspinner_wasm::*;
+
+const SOURCE: &str = "\
+title: Start
+---
+<<declare $gold = 0>>
+Guard: Halt! #line:halt #angry
+<<give_item \"sword\" 2>>
+-> Pay <<if $gold >= 5>>
+    <<set $gold to $gold - 5>>
+-> Leave
+===
+";
+
+fn compile(source: &str) -> CompileResult {
+    let mut compiler = YarnCompiler::new();
+    compiler.add_file("test.yarn".to_owned(), source.to_owned());
+    compiler.compile()
+}
+
+fn line(event: &WasmEvent) -> &WasmLine {
+    let WasmEvent::Line(line) = event else {
+        panic!("Expected a line, got {event:?}");
+    };
+    line
+}
+
+#[test]
+fn compiles_and_runs_dialogue() {
+    let result = compile(SOURCE);
+    assert!(result.succeeded());
+    assert_eq!(vec!["Start"], result.node_names());
+
+    let mut dialogue = result.start_dialogue("Start").unwrap();
+    dialogue
+        .set_variable("$gold", WasmValue::Number(7.0))
+        .unwrap();
+    let events = dialogue.continue_().unwrap();
+    assert_eq!(
+        WasmEvent::NodeStart {
+            node_name: "Start".to_owned()
+        },
+        events[0]
+    );
+    assert_eq!(
+        &WasmLine {
+            id: "line:halt".to_owned(),
+            text: "Guard: Halt!".to_owned(),
+            character_name: Some("Guard".to_owned()),
+            text_without_character_name: "Halt!".to_owned(),
+            metadata: vec!["line:halt".to_owned(), "angry".to_owned()],
+        },
+        line(&events[1])
+    );
+
+    let events = dialogue.continue_().unwrap();
+    assert_eq!(
+        WasmEvent::Command {
+            name: "give_item".to_owned(),
+            parameters: vec![
+                WasmValue::String("sword".to_owned()),
+                WasmValue::String("2".to_owned())
+            ],
+            raw: "give_item \"sword\" 2".to_owned(),
+        },
+        events[0]
+    );
+
+    let events = dialogue.continue_().unwrap();
+    let WasmEvent::Options { options } = &events[0] else {
+        panic!("Expected options, got {events:?}");
+    };
+    assert_eq!(
+        vec![true, true],
+        options
+            .iter()
+            .map(|option| option.is_available)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!("Pay", options[0].line.text);
+
+    dialogue.select_option(options[0].id).unwrap();
+    let events = dialogue.continue_().unwrap();
+    assert!(events.contains(&WasmEvent::VariableChanged {
+        name: "$gold".to_owned(),
+        old_value: Some(WasmValue::Number(7.0)),
+        new_value: WasmValue::Number(2.0),
+    }));
+    assert_eq!(Some(&WasmEvent::DialogueComplete), events.last());
+    assert_eq!(Some(WasmValue::Number(2.0)), dialogue.variable("$gold"));
+    assert_eq!(None, dialogue.variable("$unknown"));
+}
+
+#[test]
+fn reports_diagnostics_instead_of_failing() {
+    let result = compile("title: Start\n---\n<<set $gold to \"many\" + 1>>\n===\n");
+
+    assert!(!result.succeeded());
+    assert!(result.node_names().is_empty());
+    let diagnostic = &result.diagnostics()[0];
+    assert_eq!("error", diagnostic.severity);
+    assert_eq!(Some("test.yarn"), diagnostic.file.as_deref());
+    assert_eq!(Some(3), diagnostic.line);
+    assert_eq!(
+        "Cannot start a dialogue because the compilation failed",
+        result.start_dialogue("Start").unwrap_err()
+    );
+}
+
+#[test]
+fn cannot_start_at_unknown_node() {
+    let error = compile(SOURCE).start_dialogue("Nowhere").unwrap_err();
+    assert!(error.contains("Nowhere"), "{error}");
+}
@@ -0,0 +1,424 @@
+//! Not part of the original Yarn Spinner. Automated smoke testing of written dialogue.
+//!
+//! A [`DialogueExplorer`] plays through a compiled program over and over, selecting every combination of available options,
+//! and reports each path it took along with its transcript. Paths that fail with runtime errors or get stuck on options that are all
+//! unavailable are flagged as problems, and lines and options that no path reached are listed as unreachable.
+//!
+//! ```rust
+//! # use yarnspinner::prelude::*;
+//! # use yarnspinner::explorer::{DialogueExplorer, PathOutcome};
+//! let compilation = YarnCompiler::new()
+//!     .add_file(YarnFile {
+//!         file_name: "guard.yarn".to_owned(),
+//!         source: "title: Start\n---\nGuard: Halt!\n-> Pay\n-> Run\n===\n".to_owned(),
+//!     })
+//!     .compile()
+//!     .unwrap();
+//! let report = DialogueExplorer::new(&compilation).explore("Start");
+//! assert_eq!(2, report.paths.len());
+//! assert!(report.paths.iter().all(|path| matches!(path.outcome, PathOutcome::Completed)));
+//! assert!(report.problems().next().is_none());
+//! ```
+//!
+//! Every path is played on a new [`Dialogue`], so functions and variables the Yarn files need have to be registered with
+//! [`DialogueExplorer::with_setup`]. Functions that return different values on every call, like `dice`, make exploration unreliable.
+
+use crate::compiler::Compilation;
+use crate::core::{LineId, Program};
+use crate::runtime::{
+    CoverageReport, Dialogue, DialogueCoverage, DialogueError, DialogueEvent, DialogueOption,
+    MemoryVariableStorage, OptionId, StringTableTextProvider,
+};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{self, Debug, Display, Formatter};
+
+/// Explores every path through a compiled program. See the [module documentation](self) for an example.
+pub struct DialogueExplorer {
+    program: Option<Program>,
+    text_provider: StringTableTextProvider,
+    setup: Option<DialogueSetup>,
+    max_choices: usize,
+    max_paths: usize,
+    max_steps: usize,
+}
+
+type DialogueSetup = Box<dyn Fn(&mut Dialogue)>;
+
+/// The result of [`DialogueExplorer::explore`].
+#[derive(Debug, Default)]
+pub struct ExplorationReport {
+    /// Every path that was explored, in the order they were explored.
+    pub paths: Vec<ExploredPath>,
+    /// Whether exploration stopped early because [`DialogueExplorer::with_max_paths`] paths were explored.
+    pub truncated: bool,
+    /// The combined coverage of all paths.
+    pub coverage: DialogueCoverage,
+    /// The coverage of every node of the program by all paths together, which lists the content no path reached.
+    pub coverage_report: CoverageReport,
+}
+
+/// A single way through the dialogue, from the start node to its [`PathOutcome`].
+#[derive(Debug)]
+pub struct ExploredPath {
+    /// The ID of the option selected at each choice along the path.
+    pub selections: Vec<OptionId>,
+    /// Everything that happened along the path.
+    pub transcript: Vec<TranscriptEntry>,
+    /// How the path ended.
+    pub outcome: PathOutcome,
+}
+
+/// A single event in the transcript of an [`ExploredPath`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptEntry {
+    /// A line was delivered.
+    Line(String),
+    /// A command was delivered.
+    Command(String),
+    /// Options were presented, along with whether each was available.
+    Options(Vec<(String, bool)>),
+    /// An option was selected.
+    Selected(String),
+}
+
+/// How an [`ExploredPath`] ended.
+#[derive(Debug)]
+pub enum PathOutcome {
+    /// The dialogue completed.
+    Completed,
+    /// The dialogue presented options, none of which were available, so the player could never continue.
+    DeadEnd,
+    /// The dialogue failed.
+    Error(DialogueError),
+    /// The dialogue presented the same options with the same variables as before on this path, so continuing would loop forever.
+    Cycle,
+    /// The dialogue presented options with variables that another path already explored, so the rest of this path is the same as that one's.
+    Merged,
+    /// The path made [`DialogueExplorer::with_max_choices`] choices without ending.
+    ChoiceLimitReached,
+    /// The dialogue continued [`DialogueExplorer::with_max_steps`] times without ending or presenting options.
+    StepLimitReached,
+}
+
+impl DialogueExplorer {
+    /// Creates an explorer for the program and base language lines of the compilation.
+    pub fn new(compilation: &Compilation) -> Self {
+        let mut text_provider = StringTableTextProvider::new();
+        text_provider.extend_base_language(
+            compilation
+                .string_table
+                .iter()
+                .map(|(id, string_info)| (id.clone(), string_info.text.clone()))
+                .collect(),
+        );
+        Self {
+            program: compilation.program.clone(),
+            text_provider,
+            setup: None,
+            max_choices: 32,
+            max_paths: 1000,
+            max_steps: 10_000,
+        }
+    }
+
+    /// Runs the given function on the [`Dialogue`] of every path before it starts, e.g. to register functions or set variables.
+    #[must_use]
+    pub fn with_setup(mut self, setup: impl Fn(&mut Dialogue) + 'static) -> Self {
+        self.setup = Some(Box::new(setup));
+        self
+    }
+
+    /// Sets how many choices a path may make before it is cut off. Defaults to 32.
+    #[must_use]
+    pub fn with_max_choices(mut self, max_choices: usize) -> Self {
+        self.max_choices = max_choices;
+        self
+    }
+
+    /// Sets how many paths are explored at most. Defaults to 1000.
+    #[must_use]
+    pub fn with_max_paths(mut self, max_paths: usize) -> Self {
+        self.max_paths = max_paths;
+        self
+    }
+
+    /// Sets how often the dialogue may continue between two choices before the path is cut off. Defaults to 10 000.
+    #[must_use]
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Explores every path starting at the given node, depth first, with the first option of every choice explored first.
+    pub fn explore(&self, start_node: &str) -> ExplorationReport {
+        let mut report = ExplorationReport::default();
+        let mut explored_states = HashSet::new();
+        let mut pending_selections = vec![Vec::new()];
+        while let Some(selections) = pending_selections.pop() {
+            if report.paths.len() >= self.max_paths {
+                report.truncated = true;
+                break;
+            }
+            let mut exploration = PathExploration {
+                path: ExploredPath {
+                    selections,
+                    transcript: Vec::new(),
+                    outcome: PathOutcome::Completed,
+                },
+                explored_states: &mut explored_states,
+                pending_selections: &mut pending_selections,
+            };
+            let mut dialogue = self.new_dialogue();
+            let branched = exploration.play(self, &mut dialogue, start_node);
+            if let Some(coverage) = dialogue.stop_recording_coverage() {
+                report.coverage.merge(&coverage);
+            }
+            if !branched {
+                report.paths.push(exploration.path);
+            }
+        }
+        if let Some(program) = &self.program {
+            report.coverage_report = CoverageReport::new(program, &report.coverage);
+        }
+        report
+    }
+
+    fn new_dialogue(&self) -> Dialogue {
+        let mut dialogue = Dialogue::new(
+            Box::new(MemoryVariableStorage::new()),
+            Box::new(self.text_provider.clone()),
+        );
+        if let Some(program) = self.program.clone() {
+            dialogue.add_program(program);
+        }
+        if let Some(setup) = &self.setup {
+            setup(&mut dialogue);
+        }
+        dialogue.start_recording_coverage();
+        dialogue
+    }
+}
+
+/// The state of exploring a single path.
+struct PathExploration<'a> {
+    path: ExploredPath,
+    explored_states: &'a mut HashSet<String>,
+    pending_selections: &'a mut Vec<Vec<OptionId>>,
+}
+
+impl PathExploration<'_> {
+    /// Plays the path and sets its outcome.
+    /// Returns `true` if the path reached a choice that it did not make yet and was thus split into one path per available option.
+    fn play(
+        &mut self,
+        explorer: &DialogueExplorer,
+        dialogue: &mut Dialogue,
+        start_node: &str,
+    ) -> bool {
+        match self.try_play(explorer, dialogue, start_node) {
+            Ok(Some(outcome)) => {
+                self.path.outcome = outcome;
+                false
+            }
+            Ok(None) => true,
+            Err(error) => {
+                self.path.outcome = PathOutcome::Error(error);
+                false
+            }
+        }
+    }
+
+    fn try_play(
+        &mut self,
+        explorer: &DialogueExplorer,
+        dialogue: &mut Dialogue,
+        start_node: &str,
+    ) -> Result<Option<PathOutcome>, DialogueError> {
+        let mut states_on_path = HashSet::new();
+        let mut choices = 0;
+        let mut steps = 0;
+        dialogue.set_node(start_node)?;
+        loop {
+            if steps >= explorer.max_steps {
+                return Ok(Some(PathOutcome::StepLimitReached));
+            }
+            steps += 1;
+            for event in dialogue.continue_()? {
+                match event {
+                    DialogueEvent::Line(line) => {
+                        self.path.transcript.push(TranscriptEntry::Line(line.text));
+                    }
+                    DialogueEvent::Command(command) => {
+                        self.path
+                            .transcript
+                            .push(TranscriptEntry::Command(command.raw));
+                    }
+                    DialogueEvent::Options(options) => {
+                        self.path.transcript.push(TranscriptEntry::Options(
+                            options
+                                .iter()
+                                .map(|option| (option.line.text.clone(), option.is_available))
+                                .collect(),
+                        ));
+                        let state = state_key(dialogue, &options);
+                        if let Some(&selection) = self.path.selections.get(choices) {
+                            states_on_path.insert(state);
+                            // An unknown ID is reported by `set_selected_option`
+                            if let Some(selected) =
+                                options.iter().find(|option| option.id == selection)
+                            {
+                                self.path
+                                    .transcript
+                                    .push(TranscriptEntry::Selected(selected.line.text.clone()));
+                            }
+                            dialogue.set_selected_option(selection)?;
+                            choices += 1;
+                            steps = 0;
+                            continue;
+                        }
+                        if states_on_path.contains(&state) {
+                            return Ok(Some(PathOutcome::Cycle));
+                        }
+                        if !self.explored_states.insert(state) {
+                            return Ok(Some(PathOutcome::Merged));
+                        }
+                        if choices >= explorer.max_choices {
+                            return Ok(Some(PathOutcome::ChoiceLimitReached));
+                        }
+                        let available_options: Vec<_> = options
+                            .iter()
+                            .filter(|option| option.is_available)
+                            .collect();
+                        if available_options.is_empty() {
+                            return Ok(Some(PathOutcome::DeadEnd));
+                        }
+                        // Pushed in reverse so that the first option is explored first
+                        for option in available_options.into_iter().rev() {
+                            let mut selections = self.path.selections.clone();
+                            selections.push(option.id);
+                            self.pending_selections.push(selections);
+                        }
+                        return Ok(None);
+                    }
+                    DialogueEvent::DialogueComplete => return Ok(Some(PathOutcome::Completed)),
+                    _ => {}
+                }
+            }
+            if !dialogue.is_active() {
+                return Ok(Some(PathOutcome::Completed));
+            }
+        }
+    }
+}
+
+/// Identifies the situation the dialogue is in when presenting options: two paths presenting the same options
+/// in the same node with the same variables, including the visit counts of nodes, will continue the same way.
+fn state_key(dialogue: &Dialogue, options: &[DialogueOption]) -> String {
+    let variables: BTreeMap<_, _> = dialogue
+        .variable_storage()
+        .variables()
+        .into_iter()
+        .map(|(name, value)| (name, value.to_string()))
+        .collect();
+    let options: Vec<_> = options
+        .iter()
+        .map(|option| (&option.line.id, option.is_available))
+        .collect();
+    format!("{:?} {options:?} {variables:?}", dialogue.current_node())
+}
+
+impl ExplorationReport {
+    /// The paths that ended in a runtime error or a dead end.
+    pub fn problems(&self) -> impl Iterator<Item = &ExploredPath> {
+        self.paths.iter().filter(|path| path.outcome.is_problem())
+    }
+
+    /// The lines that no path delivered, along with the node they are in.
+    pub fn unreachable_lines(&self) -> impl Iterator<Item = (&str, &LineId)> {
+        self.coverage_report.nodes.iter().flat_map(|node| {
+            node.unseen_lines
+                .iter()
+                .map(move |line_id| (node.node_name.as_str(), line_id))
+        })
+    }
+
+    /// The nodes that no path entered.
+    pub fn unreachable_nodes(&self) -> impl Iterator<Item = &str> {
+        self.coverage_report
+            .nodes
+            .iter()
+            .filter(|node| !node.visited)
+            .map(|node| node.node_name.as_str())
+    }
+}
+
+impl PathOutcome {
+    /// Whether this outcome points to a bug in the dialogue, i.e. a runtime error or a dead end.
+    pub fn is_problem(&self) -> bool {
+        matches!(self, Self::Error(_) | Self::DeadEnd)
+    }
+}
+
+impl Debug for DialogueExplorer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DialogueExplorer")
+            .field("program", &self.program)
+            .field("text_provider", &self.text_provider)
+            .field("setup", &self.setup.as_ref().map(|_| "<function>"))
+            .field("max_choices", &self.max_choices)
+            .field("max_paths", &self.max_paths)
+            .field("max_steps", &self.max_steps)
+            .finish()
+    }
+}
+
+impl Display for ExploredPath {
+    /// Prints the transcript followed by the outcome.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for entry in &self.transcript {
+            writeln!(f, "{entry}")?;
+        }
+        write!(f, "[{}]", self.outcome)
+    }
+}
+
+impl Display for TranscriptEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Line(text) => f.write_str(text),
+            Self::Command(text) => write!(f, "<<{text}>>"),
+            Self::Options(options) => {
+                let options: Vec<_> = options
+                    .iter()
+                    .map(|(text, available)| {
+                        if *available {
+                            format!("-> {text}")
+                        } else {
+                            format!("-> {text} (unavailable)")
+                        }
+                    })
+                    .collect();
+                f.write_str(&options.join("\n"))
+            }
+            Self::Selected(text) => write!(f, "[selected {text}]"),
+        }
+    }
+}
+
+impl Display for PathOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Completed => f.write_str("dialogue complete"),
+            Self::DeadEnd => f.write_str("dead end: no option is available"),
+            Self::Error(error) => write!(f, "error: {error}"),
+            Self::Cycle => f.write_str(
+                "cycle: these options were already presented in the same state on this path",
+            ),
+            Self::Merged => {
+                f.write_str("merged: another path already explored these options in the same state")
+            }
+            Self::ChoiceLimitReached => f.write_str("stopped: too many choices"),
+            Self::StepLimitReached => f.write_str("stopped: too many steps without a choice"),
+        }
+    }
+}
@@ -14,24 +14,30 @@ pub mod prelude {
         LineInfo, Result as YarnCompilerResult, StringInfo,
     };
     pub use crate::core::{
-        yarn_library, IntoYarnValueFromNonYarnValue, Library as YarnLibrary, LineId,
-        Program as YarnProgram, YarnFn, YarnValue,
+        add_module, yarn_fn, yarn_library, CustomType, IntoYarnValueFromNonYarnValue,
+        Library as YarnLibrary, LineId, Program as YarnProgram, YarnFn, YarnFnContext, YarnList,
+        YarnObject, YarnObjectType, YarnValue,
     };
     pub use crate::runtime::{
         Command as YarnCommand, CompiledProgramAnalyser as YarnAnalyser,
-        Context as YarnAnalysisContext, Dialogue, DialogueError, DialogueEvent, DialogueOption,
-        Language, Line as YarnLine, MarkupAttribute, MarkupValue, OptionId,
-        Result as YarnRuntimeResult, StringTable, TextProvider, VariableStorage,
+        Context as YarnAnalysisContext, Dialogue, DialogueError, DialogueEvent, DialogueMetrics,
+        DialogueOption, ExecutionState, Language, Line as YarnLine, MarkupAttribute, MarkupValue,
+        OptionId, Result as YarnRuntimeResult, StoryState, StringTable, TextProvider,
+        VariableStorage,
     };
 }
 
 pub mod core {
     //! Core types and traits that are used by both the compiler and runtime.
     pub use yarnspinner_core::prelude::{
-        optionality, yarn_fn_type, yarn_library, Header, Instruction,
-        IntoYarnValueFromNonYarnValue, InvalidOpCodeError, Library, LineId, Node, Position,
-        Program, Type, UntypedYarnFn, YarnFn, YarnFnParam, YarnFnParamItem, YarnValue,
-        YarnValueCastError, YarnValueWrapper, YarnValueWrapperIter,
+        add_module, optionality, yarn_fn, yarn_fn_type, yarn_library, ConflictPolicy, CustomType,
+        Header, Instruction, IntoYarnValueFromNonYarnValue, InvalidOpCodeError, Library,
+        LibraryConflictError, LineId, Node, NodeBuilder, OpCode, OptionsBuilder, Position, Program,
+        ProgramBuildError, ProgramBuilder, ProgramCombineError, ProgramDecodeError, Type,
+        UntypedYarnFn, VariableAccess, YarnFn, YarnFnContext, YarnFnDefinition, YarnFnError,
+        YarnFnFuture, YarnFnMetadata, YarnFnOutput, YarnFnParam, YarnFnParamItem, YarnList,
+        YarnObject, YarnObjectType, YarnValue, YarnValueCastError, YarnValueFuture,
+        YarnValueWrapper, YarnValueWrapperIter,
     };
 }
 pub mod compiler {
@@ -49,3 +55,6 @@ pub mod runtime {
     pub use yarnspinner_runtime::prelude::*;
     pub use yarnspinner_runtime::Result;
 }
+
+pub mod explorer;
+pub mod yarnspinner_test;
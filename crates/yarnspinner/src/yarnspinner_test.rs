@@ -0,0 +1,46 @@
+//! Regression tests for written dialogue, adapted from the test plans of the original Yarn Spinner's test suite.
+//!
+//! A [`TestPlan`] lists the lines, options and commands a conversation is expected to produce and which options to select,
+//! either built in code or read from a `.testplan` file:
+//!
+//! ```text
+//! # Comments start with a hash
+//! line: Guard: Halt!
+//! command: play_sound horn
+//! option: Pay the toll
+//! option: Bribe the guard [disabled]
+//! select: 1
+//! line: Guard: Thank you.
+//! stop
+//! ```
+//!
+//! - `line: <text>` and `command: <text>` expect a line or command with exactly that text.
+//!   `*` matches any text if [`TestPlan::with_wildcards_enabled`] is set, which also applies to `option: *`.
+//! - `option: <text>` expects an option in the next set of options, which is unavailable if the text ends with ` [disabled]`.
+//! - `select: <number>` expects the options listed before it and selects the one with the given one-based number.
+//! - `stop` expects the dialogue to end. It is implied at the end of the plan.
+//!
+//! [`TestPlan::run`] then plays through a [`Dialogue`](crate::runtime::Dialogue) and reports the first step that did not match,
+//! which makes it easy to check conversations in CI:
+//!
+//! ```rust
+//! # use yarnspinner::prelude::*;
+//! # use yarnspinner::yarnspinner_test::TestPlan;
+//! let compilation = YarnCompiler::new()
+//!     .add_file(YarnFile {
+//!         file_name: "guard.yarn".to_owned(),
+//!         source: "title: Start\n---\nGuard: Halt!\n-> Pay\n-> Run\n===\n".to_owned(),
+//!     })
+//!     .compile()
+//!     .unwrap();
+//! let plan: TestPlan = "line: Guard: Halt!\noption: Pay\noption: Run\nselect: 2".parse().unwrap();
+//! plan.run_compilation(&compilation, "Start").unwrap();
+//! ```
+
+mod runner;
+mod step;
+mod test_plan;
+
+pub use runner::{TestPlanFailure, TestPlanMismatch};
+pub use step::{ExpectedStepType, Step, StepValue};
+pub use test_plan::{ProcessedOption, TestPlan, TestPlanError};
@@ -0,0 +1,242 @@
+//! Not part of the original Yarn Spinner, which checks test plans with assertions inside its test suite.
+//! This runner reports the first mismatch instead, so that it can be used outside of tests.
+
+use super::step::{ExpectedStepType, StepValue};
+use super::test_plan::{ProcessedOption, TestPlan};
+use crate::compiler::Compilation;
+use crate::runtime::{
+    Dialogue, DialogueError, DialogueEvent, DialogueOption, MemoryVariableStorage,
+    StringTableTextProvider,
+};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// Why a [`TestPlan`] did not pass.
+#[derive(Debug)]
+pub enum TestPlanFailure {
+    /// The dialogue did something else than the plan expected.
+    Mismatch(TestPlanMismatch),
+    /// The dialogue failed while running.
+    Dialogue(DialogueError),
+    /// The compilation passed to [`TestPlan::run_compilation`] contains no program.
+    NoProgram,
+}
+
+/// The first step of a [`TestPlan`] that did not match the dialogue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestPlanMismatch {
+    /// The one-based line of the test plan file containing the step, if the plan was read from a file.
+    pub line_number: Option<usize>,
+    /// A description of what the plan expected, e.g. `line "Hello"`.
+    pub expected: String,
+    /// A description of what the dialogue did instead.
+    pub actual: String,
+}
+
+impl TestPlan {
+    /// Runs the dialogue from the given node and checks every line, set of options, command and the end of the dialogue against this plan.
+    /// Every set of options is expected to be followed by a `select` step, which decides the option to continue with.
+    ///
+    /// The dialogue is used as-is, so register the functions and variables the Yarn files need beforehand.
+    /// See [`TestPlan::run_compilation`] for dialogue that does not need any.
+    pub fn run(&self, dialogue: &mut Dialogue, start_node: &str) -> Result<(), TestPlanFailure> {
+        let mut plan = self.restarted();
+        dialogue.set_node(start_node)?;
+        loop {
+            let events = dialogue.continue_()?;
+            for event in events {
+                match event {
+                    DialogueEvent::Line(line) => {
+                        plan.next();
+                        plan.check_text(ExpectedStepType::Line, &line.text)?;
+                    }
+                    DialogueEvent::Command(command) => {
+                        plan.next();
+                        plan.check_text(ExpectedStepType::Command, &command.raw)?;
+                    }
+                    DialogueEvent::Options(options) => {
+                        plan.next();
+                        let option = plan.check_options(&options)?;
+                        dialogue.set_selected_option(option)?;
+                    }
+                    DialogueEvent::DialogueComplete => {
+                        plan.next();
+                        return plan.check(ExpectedStepType::Stop, "the end of the dialogue");
+                    }
+                    _ => {}
+                }
+            }
+            if !dialogue.is_active() {
+                plan.next();
+                return plan.check(ExpectedStepType::Stop, "the end of the dialogue");
+            }
+        }
+    }
+
+    /// Runs [`TestPlan::run`] on a new [`Dialogue`] with the program and base language lines of the compilation.
+    pub fn run_compilation(
+        &self,
+        compilation: &Compilation,
+        start_node: &str,
+    ) -> Result<(), TestPlanFailure> {
+        let program = compilation
+            .program
+            .clone()
+            .ok_or(TestPlanFailure::NoProgram)?;
+        let mut text_provider = StringTableTextProvider::new();
+        text_provider.extend_base_language(
+            compilation
+                .string_table
+                .iter()
+                .map(|(id, string_info)| (id.clone(), string_info.text.clone()))
+                .collect(),
+        );
+        let mut dialogue = Dialogue::new(
+            Box::new(MemoryVariableStorage::new()),
+            Box::new(text_provider),
+        );
+        dialogue
+            .add_program(program)
+            .add_debug_info(compilation.debug_info.clone());
+        self.run(&mut dialogue, start_node)
+    }
+
+    fn check(&self, actual_type: ExpectedStepType, actual: &str) -> Result<(), TestPlanFailure> {
+        if self.next_expected_step == actual_type {
+            Ok(())
+        } else {
+            Err(self.mismatch(actual))
+        }
+    }
+
+    fn check_text(&self, actual_type: ExpectedStepType, text: &str) -> Result<(), TestPlanFailure> {
+        let actual = format!("{} \"{text}\"", step_name(actual_type));
+        self.check(actual_type, &actual)?;
+        let matches = match &self.next_step_value {
+            Some(StepValue::String(expected)) => expected == text,
+            // Steps written as `*`
+            None => self.wildcards_enabled() || text == "*",
+            Some(StepValue::Number(_)) => false,
+        };
+        if matches {
+            Ok(())
+        } else {
+            Err(self.mismatch(&actual))
+        }
+    }
+
+    /// Returns the ID of the option to select.
+    fn check_options(
+        &self,
+        options: &[DialogueOption],
+    ) -> Result<crate::runtime::OptionId, TestPlanFailure> {
+        let actual_options: Vec<_> = options
+            .iter()
+            .map(|option| ProcessedOption {
+                line: option.line.text.clone(),
+                enabled: option.is_available,
+            })
+            .collect();
+        let actual = format!("options {}", list(&actual_options));
+        self.check(ExpectedStepType::Select, &actual)?;
+        let options_match =
+            self.next_expected_options.len() == actual_options.len()
+                && self.next_expected_options.iter().zip(&actual_options).all(
+                    |(expected, actual)| {
+                        ((self.wildcards_enabled() && expected.line == "*")
+                            || expected.line == actual.line)
+                            && expected.enabled == actual.enabled
+                    },
+                );
+        if !options_match {
+            return Err(self.mismatch(&actual));
+        }
+        let selection = match self.next_step_value {
+            Some(StepValue::Number(selection)) if selection > 0 => selection - 1,
+            _ => 0,
+        };
+        options
+            .get(selection)
+            .map(|option| option.id)
+            .ok_or_else(|| self.mismatch(&actual))
+    }
+
+    fn mismatch(&self, actual: &str) -> TestPlanFailure {
+        let expected = match (self.next_expected_step, &self.next_step_value) {
+            (ExpectedStepType::Stop, _) => "the end of the dialogue".to_owned(),
+            (ExpectedStepType::Select, value) => {
+                let selection = match value {
+                    Some(StepValue::Number(selection)) if *selection > 0 => *selection,
+                    _ => 1,
+                };
+                format!(
+                    "options {} and selecting option {selection}",
+                    list(&self.next_expected_options)
+                )
+            }
+            (step_type, Some(value)) => format!("{} {value}", step_name(step_type)),
+            (step_type, None) if self.wildcards_enabled() => {
+                format!("any {}", step_name(step_type))
+            }
+            (step_type, None) => format!("{} \"*\"", step_name(step_type)),
+        };
+        TestPlanFailure::Mismatch(TestPlanMismatch {
+            line_number: self
+                .current_step()
+                // Not the case when the plan ran out of steps and implicitly expects the end of the dialogue
+                .filter(|step| step.expected_step_type == self.next_expected_step)
+                .and_then(|step| step.line_number),
+            expected,
+            actual: actual.to_owned(),
+        })
+    }
+}
+
+fn step_name(step_type: ExpectedStepType) -> &'static str {
+    match step_type {
+        ExpectedStepType::Line => "line",
+        ExpectedStepType::Option => "option",
+        ExpectedStepType::Select => "selection",
+        ExpectedStepType::Command => "command",
+        ExpectedStepType::Stop => "stop",
+    }
+}
+
+fn list(options: &[ProcessedOption]) -> String {
+    let options: Vec<_> = options.iter().map(ToString::to_string).collect();
+    format!("[{}]", options.join(", "))
+}
+
+impl From<DialogueError> for TestPlanFailure {
+    fn from(error: DialogueError) -> Self {
+        Self::Dialogue(error)
+    }
+}
+
+impl Error for TestPlanFailure {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Dialogue(error) => Some(error),
+            Self::Mismatch(_) | Self::NoProgram => None,
+        }
+    }
+}
+
+impl Display for TestPlanFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mismatch(mismatch) => Display::fmt(mismatch, f),
+            Self::Dialogue(error) => write!(f, "The dialogue failed: {error}"),
+            Self::NoProgram => f.write_str("The compilation contains no program to test"),
+        }
+    }
+}
+
+impl Display for TestPlanMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(line_number) = self.line_number {
+            write!(f, "Test plan line {line_number}: ")?;
+        }
+        write!(f, "Expected {}, but got {}", self.expected, self.actual)
+    }
+}
@@ -0,0 +1,200 @@
+//! Adapted from <https://github.com/YarnSpinnerTool/YarnSpinner/blob/da39c7195107d8211f21c263e4084f773b84eaff/YarnSpinner.Tests/TestPlan.cs>
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// A single expectation of a [`TestPlan`](super::TestPlan).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    /// What kind of dialogue event is expected.
+    pub expected_step_type: ExpectedStepType,
+    /// The expected text or selection. [`None`] if the step was written as `*`, see [`TestPlan::wildcards_enabled`](super::TestPlan::wildcards_enabled).
+    pub value: Option<StepValue>,
+    /// Whether an expected option should be available.
+    pub expect_option_enabled: bool,
+    /// The one-based line of the test plan file this step was read from, used to report mismatches.
+    pub line_number: Option<usize>,
+}
+
+/// The value of a [`Step`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepValue {
+    /// The text of a line, option or command.
+    String(String),
+    /// The one-based number of the option to select.
+    Number(usize),
+}
+
+/// The kinds of [`Step`]s.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum ExpectedStepType {
+    /// Expecting to see this specific line.
+    #[default]
+    Line,
+
+    /// Expecting to see this specific option (if '*' is given,
+    /// means 'see an option, don't care about text').
+    Option,
+
+    /// Expecting options to have been presented; value = the
+    /// index to select.
+    Select,
+
+    /// Expecting to see this specific command.
+    Command,
+
+    /// Expecting to stop the test here (this is optional - a
+    /// 'stop' at the end of a test plan is assumed).
+    Stop,
+}
+
+impl Step {
+    /// Parses a line of a test plan file like `line: Hello` or `select: 1`.
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim();
+        let (step_type, value) = line.split_once(':').unwrap_or((line, ""));
+        let expected_step_type: ExpectedStepType = step_type.trim().parse()?;
+        let value = value.trim();
+
+        match expected_step_type {
+            ExpectedStepType::Line | ExpectedStepType::Option | ExpectedStepType::Command => {
+                // Options whose text ends with " [disabled]"
+                // are expected to be present, but have their
+                // 'allowed' flag set to false
+                if value == "*" && expected_step_type != ExpectedStepType::Option {
+                    Ok(Self::with_expected_step_type(expected_step_type))
+                } else if expected_step_type == ExpectedStepType::Option
+                    && value.ends_with(" [disabled]")
+                {
+                    Ok(Self {
+                        value: Some(value.trim_end_matches(" [disabled]").into()),
+                        expect_option_enabled: false,
+                        ..Self::with_expected_step_type(expected_step_type)
+                    })
+                } else {
+                    Ok(Self::with_value_and_type(value, expected_step_type))
+                }
+            }
+            ExpectedStepType::Select => {
+                let selection: usize = value.parse().map_err(|_| {
+                    format!("Expected the number of an option to select, got \"{value}\"")
+                })?;
+                if selection == 0 {
+                    return Err("Options to select are numbered starting at 1".to_owned());
+                }
+                Ok(Self::with_value_and_type(selection, expected_step_type))
+            }
+            ExpectedStepType::Stop => Ok(Self::with_expected_step_type(expected_step_type)),
+        }
+    }
+
+    /// Expects a line with the given text.
+    pub fn from_line(line: impl Into<String>) -> Self {
+        Self::with_value_and_type(line.into(), ExpectedStepType::Line)
+    }
+
+    /// Expects an option with the given text.
+    pub fn from_option(line: impl Into<String>) -> Self {
+        Self::with_value_and_type(line.into(), ExpectedStepType::Option)
+    }
+
+    /// Expects a command with the given text.
+    pub fn from_command(line: impl Into<String>) -> Self {
+        Self::with_value_and_type(line.into(), ExpectedStepType::Command)
+    }
+
+    /// Expects the previously listed options and selects the one with the given one-based number.
+    pub fn from_select(selection: impl Into<usize>) -> Self {
+        Self::with_value_and_type(selection.into(), ExpectedStepType::Select)
+    }
+
+    /// Expects the dialogue to end.
+    pub fn from_stop() -> Self {
+        Self::with_expected_step_type(ExpectedStepType::Stop)
+    }
+
+    fn with_value_and_type(
+        value: impl Into<StepValue>,
+        expected_step_type: ExpectedStepType,
+    ) -> Self {
+        Self {
+            value: Some(value.into()),
+            ..Self::with_expected_step_type(expected_step_type)
+        }
+    }
+
+    fn with_expected_step_type(expected_step_type: ExpectedStepType) -> Self {
+        Self {
+            expected_step_type,
+            value: None,
+            expect_option_enabled: true,
+            line_number: None,
+        }
+    }
+}
+
+impl From<String> for StepValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for StepValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<usize> for StepValue {
+    fn from(value: usize) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl TryFrom<StepValue> for String {
+    type Error = StepValue;
+
+    fn try_from(value: StepValue) -> Result<Self, Self::Error> {
+        match value {
+            StepValue::String(value) => Ok(value),
+            value => Err(value),
+        }
+    }
+}
+
+impl TryFrom<StepValue> for usize {
+    type Error = StepValue;
+
+    fn try_from(value: StepValue) -> Result<Self, Self::Error> {
+        match value {
+            StepValue::Number(value) => Ok(value),
+            value => Err(value),
+        }
+    }
+}
+
+impl Display for StepValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String(value) => write!(f, "\"{value}\""),
+            Self::Number(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl FromStr for ExpectedStepType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "line" => Ok(Self::Line),
+            "option" => Ok(Self::Option),
+            "select" => Ok(Self::Select),
+            "command" => Ok(Self::Command),
+            "stop" => Ok(Self::Stop),
+            _ => Err(format!(
+                "Unknown step \"{s}\", expected line, option, select, command or stop"
+            )),
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Adapted from <https://github.com/YarnSpinnerTool/YarnSpinner/blob/da39c7195107d8211f21c263e4084f773b84eaff/YarnSpinner.Tests/TestPlan.cs>
+
+use super::step::{ExpectedStepType, Step, StepValue};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The expected course of a conversation. See the [module documentation](super) for the file format.
+///
+/// Besides [`TestPlan::run`], the plan can be stepped through manually with [`TestPlan::next`],
+/// which advances to the next expected line, command, selection or stop.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TestPlan {
+    /// The kind of event expected after the last call to [`TestPlan::next`].
+    pub next_expected_step: ExpectedStepType,
+    /// The options expected before the next [`ExpectedStepType::Select`].
+    pub next_expected_options: Vec<ProcessedOption>,
+    /// The value of the step expected after the last call to [`TestPlan::next`].
+    pub next_step_value: Option<StepValue>,
+    steps: Vec<Step>,
+    current_test_plan_step: usize,
+    wildcards_enabled: bool,
+}
+
+/// An option as presented by the dialogue or as expected by a [`TestPlan`].
+/// An expected option with the text `*` matches any option if [`TestPlan::wildcards_enabled`] is set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessedOption {
+    /// The text of the option.
+    pub line: String,
+    /// Whether the option is available.
+    pub enabled: bool,
+}
+
+/// The error returned when reading a [`TestPlan`] fails.
+#[derive(Debug)]
+pub enum TestPlanError {
+    /// The test plan file could not be read.
+    Io(std::io::Error),
+    /// A line of the test plan is not a valid step.
+    Parse {
+        /// The one-based line number of the invalid step.
+        line_number: usize,
+        /// What is wrong with the step.
+        message: String,
+    },
+}
+
+impl TestPlan {
+    /// Creates an empty test plan, which expects the dialogue to stop right away.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Reads a test plan from a `.testplan` file.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, TestPlanError> {
+        fs::read_to_string(path).map_err(TestPlanError::Io)?.parse()
+    }
+
+    /// The steps of this plan.
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// The step that was reached by the last call to [`TestPlan::next`], if any.
+    pub fn current_step(&self) -> Option<&Step> {
+        self.current_test_plan_step
+            .checked_sub(1)
+            .and_then(|index| self.steps.get(index))
+    }
+
+    /// Gets whether steps with the text `*` match any line, option or command.
+    /// Otherwise, `*` only matches the text `*` itself. The default is `false`.
+    pub fn wildcards_enabled(&self) -> bool {
+        self.wildcards_enabled
+    }
+
+    /// Sets whether steps with the text `*` match any line, option or command, see [`TestPlan::wildcards_enabled`].
+    #[must_use]
+    pub fn with_wildcards_enabled(mut self, enabled: bool) -> Self {
+        self.wildcards_enabled = enabled;
+        self
+    }
+
+    /// The same plan without any progress made by [`TestPlan::next`].
+    pub(super) fn restarted(&self) -> Self {
+        Self {
+            steps: self.steps.clone(),
+            wildcards_enabled: self.wildcards_enabled,
+            ..Default::default()
+        }
+    }
+
+    /// Advances to the next expected line, command, selection or stop and updates
+    /// [`TestPlan::next_expected_step`], [`TestPlan::next_step_value`] and [`TestPlan::next_expected_options`].
+    pub fn next(&mut self) {
+        // step through the test plan until we hit an expectation to
+        // see a line, option, or command. specifically, we're waiting
+        // to see if we got a Line, Select, Command or Assert step
+        // type.
+        if self.next_expected_step == ExpectedStepType::Select {
+            // our previously-notified task was to select an option.
+            // we've now moved past that, so clear the list of expected
+            // options.
+            self.next_expected_options.clear();
+            self.next_step_value = Some(StepValue::Number(0));
+        }
+
+        for current_step in self.steps.iter().skip(self.current_test_plan_step) {
+            self.current_test_plan_step += 1;
+            if current_step.expected_step_type == ExpectedStepType::Option {
+                let line = match current_step.value.clone() {
+                    Some(StepValue::String(line)) => line,
+                    _ => "*".to_owned(),
+                };
+                self.next_expected_options.push(ProcessedOption {
+                    line,
+                    enabled: current_step.expect_option_enabled,
+                });
+            } else {
+                self.next_expected_step = current_step.expected_step_type;
+                self.next_step_value.clone_from(&current_step.value);
+                return;
+            }
+        }
+
+        // We've fallen off the end of the test plan step list. We
+        // expect a stop here.
+        self.next_expected_step = ExpectedStepType::Stop;
+    }
+
+    /// Expects a line with the given text.
+    #[must_use]
+    pub fn expect_line(mut self, line: impl Into<String>) -> Self {
+        self.steps.push(Step::from_line(line));
+        self
+    }
+
+    /// Expects an available option with the given text.
+    #[must_use]
+    pub fn expect_option(mut self, line: impl Into<String>) -> Self {
+        self.steps.push(Step::from_option(line));
+        self
+    }
+
+    /// Expects an unavailable option with the given text.
+    #[must_use]
+    pub fn expect_disabled_option(mut self, line: impl Into<String>) -> Self {
+        self.steps.push(Step {
+            expect_option_enabled: false,
+            ..Step::from_option(line)
+        });
+        self
+    }
+
+    /// Expects a command with the given text.
+    #[must_use]
+    pub fn expect_command(mut self, line: impl Into<String>) -> Self {
+        self.steps.push(Step::from_command(line));
+        self
+    }
+
+    /// Expects the options listed before and selects the one with the given one-based number.
+    #[must_use]
+    pub fn then_select(mut self, selection: usize) -> Self {
+        self.steps.push(Step::from_select(selection));
+        self
+    }
+
+    /// Expects the dialogue to end.
+    #[must_use]
+    pub fn expect_stop(mut self) -> Self {
+        self.steps.push(Step::from_stop());
+        self
+    }
+}
+
+impl FromStr for TestPlan {
+    type Err = TestPlanError;
+
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        let steps = contents
+            .lines()
+            .enumerate()
+            // Skip commented lines
+            .filter(|(_, line)| !line.trim_start().starts_with('#'))
+            // Skip empty or blank lines
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                let line_number = index + 1;
+                let step = Step::parse(line).map_err(|message| TestPlanError::Parse {
+                    line_number,
+                    message,
+                })?;
+                Ok(Step {
+                    line_number: Some(line_number),
+                    ..step
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            steps,
+            ..Default::default()
+        })
+    }
+}
+
+impl Display for ProcessedOption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\"", self.line)?;
+        if !self.enabled {
+            f.write_str(" [disabled]")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for TestPlanError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Parse { .. } => None,
+        }
+    }
+}
+
+impl Display for TestPlanError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Failed to read test plan: {error}"),
+            Self::Parse {
+                line_number,
+                message,
+            } => write!(f, "Invalid test plan step on line {line_number}: {message}"),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Not part of the original Yarn Spinner. Tests for constants defined with [`Compiler::define_constant`].
+
+use test_base::prelude::*;
+use yarnspinner::compiler::*;
+use yarnspinner::core::YarnValue;
+
+mod test_base;
+
+#[test]
+fn test_constants_are_substituted_at_compile_time() {
+    let source = "\
+    <<declare $reputation = 0>>
+    <<declare $faction = \"\">>
+    <<set $reputation = $MAX_REPUTATION - 1>>
+    <<set $faction = $FACTION_REBELS>>
+    ";
+    let result = Compiler::from_test_source(source)
+        .define_constant("MAX_REPUTATION", 100)
+        .define_constant("$FACTION_REBELS", "rebels")
+        .compile()
+        .unwrap();
+    let mut dialogue = TestBase::new().with_compilation(result).dialogue;
+    dialogue.set_node("Start").unwrap();
+    dialogue.run_to_completion().unwrap();
+
+    let storage = dialogue.variable_storage();
+    assert_eq!(storage.get("$reputation").unwrap(), YarnValue::from(99));
+    assert_eq!(storage.get("$faction").unwrap(), YarnValue::from("rebels"));
+    assert!(!storage.contains("$MAX_REPUTATION"));
+}
+
+#[test]
+fn test_constants_are_type_checked_and_cannot_be_set() {
+    let mismatched_type =
+        Compiler::from_test_source("<<declare $name = \"\">>\n<<set $name = $MAX_REPUTATION>>")
+            .define_constant("MAX_REPUTATION", 100)
+            .compile();
+    assert!(mismatched_type
+        .unwrap_err()
+        .0
+        .iter()
+        .any(|diagnostic| diagnostic.message.contains("cannot be assigned a Number")));
+
+    let assignment = Compiler::from_test_source("<<set $MAX_REPUTATION = 5>>")
+        .define_constant("MAX_REPUTATION", 100)
+        .compile();
+    assert!(assignment
+        .unwrap_err()
+        .0
+        .iter()
+        .any(|diagnostic| diagnostic.message == "$MAX_REPUTATION is a constant and cannot be set"));
+}
@@ -0,0 +1,111 @@
+//! Not part of the original Yarn Spinner. Tests for [`DialogueCoverage`].
+
+use test_base::prelude::*;
+use yarnspinner::compiler::*;
+use yarnspinner::core::LineId;
+use yarnspinner::runtime::*;
+
+mod test_base;
+
+#[test]
+fn test_coverage_reports_unexercised_content() {
+    let source = "\
+    <<declare $gold = 0>>
+    Hello #line:hello
+    <<if $gold > 10>>
+    Rich #line:rich
+    <<endif>>
+    -> Buy #line:buy
+        Bought #line:bought
+    -> Leave #line:leave
+        Left #line:left
+    ";
+    let result = Compiler::from_test_source(source).compile().unwrap();
+    let program = result.program.clone().unwrap();
+    let mut dialogue = TestBase::new().with_compilation(result).dialogue;
+    let mut run = |selection| {
+        dialogue.start_recording_coverage();
+        dialogue.set_node("Start").unwrap();
+        dialogue.continue_().unwrap();
+        while dialogue.is_active() {
+            if dialogue.is_waiting_for_option_selection() {
+                dialogue.set_selected_option(OptionId(selection)).unwrap();
+            }
+            dialogue.continue_().unwrap();
+        }
+        dialogue.stop_recording_coverage().unwrap()
+    };
+
+    let coverage = run(1);
+    let start = &coverage.nodes["Start"];
+    assert_eq!(1, start.visits);
+    assert_eq!(
+        Some(&1),
+        start.options_presented.get(&LineId::from("line:buy"))
+    );
+    assert!(!start
+        .options_selected
+        .contains_key(&LineId::from("line:buy")));
+    assert_eq!(
+        vec![BranchCoverage {
+            times_true: 0,
+            times_false: 1
+        }],
+        start.branches.values().copied().collect::<Vec<_>>()
+    );
+
+    let report = CoverageReport::new(&program, &coverage);
+    let start = &report.nodes[0];
+    assert_eq!("Start", start.node_name);
+    assert_eq!(
+        CoverageCount {
+            covered: 2,
+            total: 4
+        },
+        start.lines
+    );
+    assert_eq!(
+        CoverageCount {
+            covered: 1,
+            total: 2
+        },
+        start.options
+    );
+    assert_eq!(
+        CoverageCount {
+            covered: 1,
+            total: 2
+        },
+        start.branches
+    );
+    assert_eq!(50.0, start.percentage());
+    assert_eq!(
+        vec![LineId::from("line:rich"), LineId::from("line:bought")],
+        start.unseen_lines
+    );
+    assert_eq!(vec![LineId::from("line:buy")], start.unselected_options);
+    assert!(report
+        .to_string()
+        .contains("Never seen lines:\n  Start: line:rich\n  Start: line:bought\n"));
+
+    let mut merged = coverage;
+    merged.merge(&run(0));
+    assert_eq!(2, merged.nodes["Start"].visits);
+    let report = CoverageReport::new(&program, &merged);
+    let total = report.total();
+    assert_eq!(
+        CoverageCount {
+            covered: 3,
+            total: 4
+        },
+        total.lines
+    );
+    assert_eq!(
+        CoverageCount {
+            covered: 2,
+            total: 2
+        },
+        total.options
+    );
+    assert_eq!(vec![LineId::from("line:rich")], total.unseen_lines);
+}
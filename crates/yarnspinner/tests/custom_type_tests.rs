@@ -0,0 +1,105 @@
+//! Not part of the original Yarn Spinner, which only supports the built-in types. Tests for [`CustomType`] and [`YarnObject`].
+
+use test_base::prelude::*;
+use yarnspinner::compiler::*;
+use yarnspinner::core::{CustomType, Type, YarnObject, YarnObjectType, YarnValue};
+
+mod test_base;
+
+#[derive(Debug)]
+struct Player {
+    name: String,
+    gold: f64,
+}
+
+impl YarnObjectType for Player {
+    const NAME: &'static str = "Player";
+}
+
+fn player_type() -> CustomType<Player> {
+    CustomType::<Player>::new()
+        .with_property("name", |player: &Player| player.name.clone())
+        .with_method("can_afford", |player: &Player, price: f64| {
+            player.gold >= price
+        })
+}
+
+#[test]
+fn test_custom_type_members() {
+    let test_base = TestBase::new().extend_library(|library| {
+        library.add_custom_type(player_type());
+    });
+    let source = "\
+    <<declare $name = \"\">>
+    <<declare $can_afford = false>>
+    <<set $name = $player.name>>
+    <<set $can_afford = $player.can_afford(30)>>
+    ";
+    let result = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .declare_variable(Declaration::new("$player", CustomType::<Player>::r#type()))
+        .compile()
+        .unwrap();
+    let mut dialogue = test_base.with_compilation(result).dialogue;
+    let player = YarnObject::new(Player {
+        name: "Alice".to_owned(),
+        gold: 42.0,
+    });
+    dialogue
+        .variable_storage_mut()
+        .set("$player".to_owned(), player.into())
+        .unwrap();
+    dialogue.set_node("Start").unwrap();
+    dialogue.run_to_completion().unwrap();
+
+    let storage = dialogue.variable_storage();
+    assert_eq!(storage.get("$name").unwrap(), YarnValue::from("Alice"));
+    assert_eq!(storage.get("$can_afford").unwrap(), YarnValue::from(true));
+}
+
+#[test]
+fn test_custom_type_members_are_type_checked() {
+    let test_base = TestBase::new().extend_library(|library| {
+        library.add_custom_type(player_type());
+    });
+    let source = "\
+    <<declare $name = \"\">>
+    <<set $name = Player_name(\"Alice\")>>
+    ";
+    let result = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .compile();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_custom_type_member_access_is_type_checked() {
+    let test_base = TestBase::new().extend_library(|library| {
+        library.add_custom_type(player_type());
+    });
+    let compile = |source: &str| {
+        Compiler::from_test_source(source)
+            .extend_library(test_base.dialogue.library().clone())
+            .declare_variable(Declaration::new("$player", CustomType::<Player>::r#type()))
+            .declare_variable(Declaration::new("$gold", Type::Number))
+            .compile()
+            .unwrap_err()
+            .0
+    };
+
+    let unknown_member = compile("<<declare $name = \"\">>\n<<set $name = $player.title>>");
+    assert!(unknown_member
+        .iter()
+        .any(|diagnostic| diagnostic.message == "Type Player has no member \"title\""));
+
+    let wrong_argument = compile(
+        "<<declare $can_afford = false>>\n<<set $can_afford = $player.can_afford(\"a lot\")>>",
+    );
+    assert!(wrong_argument.iter().any(|diagnostic| diagnostic
+        .message
+        .contains("Player_can_afford parameter 2 expects a Number")));
+
+    let not_an_object = compile("<<declare $name = \"\">>\n<<set $name = $gold.name>>");
+    assert!(not_an_object.iter().any(|diagnostic| diagnostic.message
+        == "Cannot access member \"name\" of $gold, because its type Number is not a custom type"));
+}
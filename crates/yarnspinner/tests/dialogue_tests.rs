@@ -6,6 +6,7 @@
 use std::collections::HashMap;
 use test_base::prelude::*;
 use yarnspinner::compiler::*;
+use yarnspinner::core::{LineId, YarnValue};
 use yarnspinner::runtime::*;
 
 mod test_base;
@@ -218,6 +219,114 @@ fn test_line_hints() {
     assert!(line_hints_were_sent);
 }
 
+const LINE_HINTS_GRANULARITY_SOURCE: &str = "\
+title: Start
+---
+Hello #line:hello
+-> One #line:one
+    After one #line:after_one
+-> Two #line:two
+    <<jump Other>>
+End #line:end
+===
+title: Other
+---
+Other #line:other
+<<jump Third>>
+===
+title: Third
+---
+Third #line:third
+===
+";
+
+fn dialogue_with_line_hints_granularity(granularity: LineHintsGranularity) -> Dialogue {
+    let compilation = Compiler::new()
+        .add_file(File {
+            file_name: "<input>".to_string(),
+            source: LINE_HINTS_GRANULARITY_SOURCE.to_string(),
+        })
+        .compile()
+        .unwrap();
+    let mut dialogue = TestBase::new().with_compilation(compilation).dialogue;
+    dialogue
+        .set_line_hints_enabled(true)
+        .set_line_hints_granularity(granularity)
+        .set_node("Start")
+        .unwrap();
+    dialogue
+}
+
+fn sorted_line_ids(line_ids: Vec<LineId>) -> Vec<String> {
+    let mut line_ids: Vec<_> = line_ids.into_iter().map(|line_id| line_id.0).collect();
+    line_ids.sort();
+    line_ids
+}
+
+#[test]
+fn test_line_hints_per_node() {
+    let mut dialogue = dialogue_with_line_hints_granularity(LineHintsGranularity::Node);
+
+    let line_ids = dialogue.pop_line_hints().unwrap();
+    assert_eq!(
+        vec![
+            "line:after_one",
+            "line:end",
+            "line:hello",
+            "line:one",
+            "line:two"
+        ],
+        sorted_line_ids(line_ids)
+    );
+}
+
+#[test]
+fn test_line_hints_per_block() {
+    let mut dialogue = dialogue_with_line_hints_granularity(LineHintsGranularity::Block);
+
+    let line_ids = dialogue.pop_line_hints().unwrap();
+    assert_eq!(
+        vec!["line:hello", "line:one", "line:two"],
+        sorted_line_ids(line_ids)
+    );
+
+    dialogue.continue_().unwrap();
+    let events = dialogue.continue_().unwrap();
+    assert!(matches!(events.last(), Some(DialogueEvent::Options(_))));
+    dialogue.set_selected_option(OptionId(0)).unwrap();
+    let events = dialogue.continue_().unwrap();
+    let Some(DialogueEvent::LineHints(line_ids)) = events.first().cloned() else {
+        panic!("Expected line hints for the next block, but got {events:?}");
+    };
+    assert_eq!(
+        vec!["line:after_one", "line:end"],
+        sorted_line_ids(line_ids)
+    );
+}
+
+#[test]
+fn test_line_hints_for_reachable_nodes() {
+    let mut dialogue =
+        dialogue_with_line_hints_granularity(LineHintsGranularity::Reachable { depth: 1 });
+    let line_ids = dialogue.pop_line_hints().unwrap();
+    assert_eq!(
+        vec![
+            "line:after_one",
+            "line:end",
+            "line:hello",
+            "line:one",
+            "line:other",
+            "line:two"
+        ],
+        sorted_line_ids(line_ids)
+    );
+
+    let mut dialogue =
+        dialogue_with_line_hints_granularity(LineHintsGranularity::Reachable { depth: 2 });
+    let line_ids = dialogue.pop_line_hints().unwrap();
+    assert!(line_ids.contains(&LineId::from("line:third")));
+}
+
 #[test]
 fn test_function_argument_type_inference() {
     let test_base = TestBase::new().extend_library(|library| {
@@ -322,8 +431,294 @@ fn test_selecting_option_from_inside_option_callback() {
                 DialogueEvent::Command(_)
                 | DialogueEvent::NodeComplete(_)
                 | DialogueEvent::NodeStart(_)
-                | DialogueEvent::LineHints(_) => {}
+                | DialogueEvent::LineHints(_)
+                | DialogueEvent::VariableChanged { .. }
+                | DialogueEvent::ExecutionStateChanged { .. } => {}
             }
         }
     }
 }
+
+#[test]
+fn test_visit_count() {
+    let source = "title: Start\ntracking: always\n---\nA line\n===\n";
+    let result = Compiler::new()
+        .add_file(File {
+            file_name: "<input>".to_string(),
+            source: source.to_string(),
+        })
+        .compile()
+        .unwrap();
+
+    let mut test_base = TestBase::new().with_compilation(result);
+    assert_eq!(0, test_base.dialogue.visit_count("Start"));
+
+    test_base.run_standard_testcase();
+    assert_eq!(1, test_base.dialogue.visit_count("Start"));
+
+    test_base.dialogue.set_visit_count("Start", 5).unwrap();
+    assert_eq!(5, test_base.dialogue.visit_count("Start"));
+    assert_eq!(0, test_base.dialogue.visit_count("NotANode"));
+}
+
+#[test]
+fn test_set_emits_variable_changed_event_when_enabled() {
+    let compilation = Compiler::new()
+        .add_file(File {
+            file_name: "<input>".to_string(),
+            source:
+                "title: Start\n---\n<<declare $gold = 1>>\n<<set $gold to $gold + 2>>\nDone\n===\n"
+                    .to_string(),
+        })
+        .compile()
+        .unwrap();
+    let mut test_base = TestBase::new().with_compilation(compilation);
+    test_base.dialogue.set_node("Start").unwrap();
+    let events = test_base.dialogue.continue_().unwrap();
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event, DialogueEvent::VariableChanged { .. })));
+
+    test_base.dialogue.set_variable_changed_events_enabled(true);
+    test_base.dialogue.set_node("Start").unwrap();
+    let events = test_base.dialogue.continue_().unwrap();
+    let variable_changes: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            DialogueEvent::VariableChanged {
+                name,
+                old_value,
+                new_value,
+            } => Some((name.as_str(), old_value.clone(), new_value.clone())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        vec![("$gold", Some(YarnValue::from(3)), YarnValue::from(5))],
+        variable_changes
+    );
+}
+
+#[test]
+fn test_execution_state_changes_are_only_emitted_when_enabled() {
+    let compilation = Compiler::new()
+        .add_file(File {
+            file_name: "<input>".to_string(),
+            source: "title: Start\n---\nHello\n-> A\n-> B\n===\n".to_string(),
+        })
+        .compile()
+        .unwrap();
+    let mut test_base = TestBase::new().with_compilation(compilation);
+    test_base.dialogue.set_node("Start").unwrap();
+    let events = test_base.dialogue.continue_().unwrap();
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event, DialogueEvent::ExecutionStateChanged { .. })));
+
+    test_base.dialogue.set_execution_state_events_enabled(true);
+    let state_changes = |events: Vec<DialogueEvent>| -> Vec<_> {
+        events
+            .into_iter()
+            .filter_map(|event| match event {
+                DialogueEvent::ExecutionStateChanged { previous, current } => {
+                    Some((previous, current))
+                }
+                _ => None,
+            })
+            .collect()
+    };
+    let events = test_base.dialogue.continue_().unwrap();
+    assert!(matches!(events.last(), Some(DialogueEvent::Options(_))));
+    assert_eq!(
+        vec![
+            (ExecutionState::WaitingForContinue, ExecutionState::Running),
+            (
+                ExecutionState::Running,
+                ExecutionState::WaitingOnOptionSelection
+            ),
+        ],
+        state_changes(events)
+    );
+    assert_eq!(
+        ExecutionState::WaitingOnOptionSelection,
+        test_base.dialogue.execution_state()
+    );
+
+    test_base.dialogue.set_selected_option(OptionId(0)).unwrap();
+    let events = test_base.dialogue.continue_().unwrap();
+    assert_eq!(
+        vec![
+            (
+                ExecutionState::WaitingOnOptionSelection,
+                ExecutionState::WaitingForContinue
+            ),
+            (ExecutionState::WaitingForContinue, ExecutionState::Running),
+            (ExecutionState::Running, ExecutionState::Stopped),
+        ],
+        state_changes(events)
+    );
+    assert_eq!(
+        ExecutionState::Stopped,
+        test_base.dialogue.execution_state()
+    );
+}
+
+#[test]
+fn test_replacing_program_preserves_compatible_state() {
+    let compile = |source: &str| {
+        Compiler::new()
+            .add_file(File {
+                file_name: "<input>".to_string(),
+                source: source.to_string(),
+            })
+            .compile()
+            .unwrap()
+    };
+    let start = "title: Start\n---\n<<declare $x = 1>>\nFirst\nSecond\n===\n";
+    let original = compile(start);
+    let extended = compile(&format!("{start}title: Other\n---\nOther line\n===\n"));
+    let edited = compile("title: Start\n---\n<<declare $x = 2>>\nChanged\n===\n");
+
+    let mut test_base = TestBase::new().with_compilation(original);
+    test_base.dialogue.set_node("Start").unwrap();
+    let events = test_base.dialogue.continue_().unwrap();
+    assert!(matches!(events.last(), Some(DialogueEvent::Line(line)) if line.text == "First"));
+    test_base
+        .dialogue
+        .variable_storage_mut()
+        .set("$x".to_string(), 5.into())
+        .unwrap();
+
+    test_base
+        .dialogue
+        .replace_program(extended.program.unwrap());
+    assert_eq!(Some("Start".to_string()), test_base.dialogue.current_node());
+    assert_eq!(
+        YarnValue::Number(5.0),
+        test_base.dialogue.variable_storage().get("$x").unwrap()
+    );
+    let events = test_base.dialogue.continue_().unwrap();
+    assert!(matches!(events.last(), Some(DialogueEvent::Line(line)) if line.text == "Second"));
+
+    test_base.dialogue.replace_program(edited.program.unwrap());
+    assert_eq!(None, test_base.dialogue.current_node());
+    assert_eq!(
+        YarnValue::Number(5.0),
+        test_base.dialogue.variable_storage().get("$x").unwrap()
+    );
+}
+
+#[test]
+fn test_dialogues_sharing_storage_keep_local_variables_apart() {
+    let result = Compiler::new()
+        .add_file(File {
+            file_name: "<input>".to_string(),
+            source: "title: Start\n---\n<<declare $gold = 0>>\n<<declare $local_talks = 0>>\n\
+                     <<set $gold to $gold + 1>>\n<<set $local_talks to $local_talks + 1>>\n===\n"
+                .to_string(),
+        })
+        .compile()
+        .unwrap();
+    let program = result.program.unwrap();
+
+    let global = MemoryVariableStorage::new();
+    let mut quest_storage = ScopedVariableStorage::new(global.clone_shallow());
+    quest_storage
+        .set("$local_talks".to_string(), 10.into())
+        .unwrap();
+    let mut quest = Dialogue::new(
+        Box::new(quest_storage),
+        Box::new(StringTableTextProvider::new()),
+    );
+    quest.add_program(program.clone());
+    let mut bark = Dialogue::new(
+        Box::new(ScopedVariableStorage::new(global.clone_shallow())),
+        Box::new(StringTableTextProvider::new()),
+    );
+    bark.add_program(program);
+
+    for dialogue in [&mut quest, &mut bark] {
+        dialogue.set_node("Start").unwrap();
+        dialogue.run_to_completion().unwrap();
+    }
+
+    assert_eq!(YarnValue::Number(2.0), global.get("$gold").unwrap());
+    assert!(!global.contains("$local_talks"));
+    assert_eq!(
+        YarnValue::Number(11.0),
+        quest.variable_storage().get("$local_talks").unwrap()
+    );
+    assert_eq!(
+        YarnValue::Number(1.0),
+        bark.variable_storage().get("$local_talks").unwrap()
+    );
+}
+
+#[test]
+fn test_options_processor_reorders_and_caps_options() {
+    let result =
+        Compiler::from_test_source("-> A\n    Chose A\n-> B\n    Chose B\n-> C\n    Chose C\n")
+            .compile()
+            .unwrap();
+    let mut test_base = TestBase::new().with_compilation(result);
+    test_base
+        .dialogue
+        .set_options_processor(|options: &mut Vec<DialogueOption>| {
+            options.reverse();
+            options.truncate(2);
+        });
+    test_base.dialogue.set_node("Start").unwrap();
+
+    let events = test_base.dialogue.continue_().unwrap();
+    let Some(DialogueEvent::Options(options)) = events.last() else {
+        panic!("Expected options, got {events:?}");
+    };
+    let delivered: Vec<_> = options
+        .iter()
+        .map(|option| (option.id, option.line.text.as_str()))
+        .collect();
+    assert_eq!(vec![(OptionId(2), "C"), (OptionId(1), "B")], delivered);
+
+    assert!(matches!(
+        test_base.dialogue.set_selected_option(OptionId(0)),
+        Err(DialogueError::InvalidOptionIdError { .. })
+    ));
+    test_base.dialogue.set_selected_option(OptionId(2)).unwrap();
+    let events = test_base.dialogue.continue_().unwrap();
+    assert!(matches!(events.last(), Some(DialogueEvent::Line(line)) if line.text == "Chose C"));
+}
+
+#[test]
+fn test_current_line_can_be_relocalized() {
+    let test_base = TestBase::new();
+    let source = "\
+    <<declare $name = \"Sam\">>
+    Hello, {$name}!
+    ";
+    let result = Compiler::from_test_source(source).compile().unwrap();
+    let line_id = result.string_table.keys().next().unwrap().clone();
+    let mut text_provider = StringTableTextProvider::new();
+    text_provider
+        .extend_base_language(HashMap::from([(line_id.clone(), "Hello, {0}!".to_owned())]));
+    text_provider.extend_translation(
+        "de-CH",
+        HashMap::from([(line_id, "Hallo, {0}!".to_owned())]),
+    );
+    let mut string_table = test_base.string_table.clone();
+    string_table.replace(text_provider);
+    let mut dialogue = test_base.with_program(result.program.unwrap()).dialogue;
+    dialogue.set_node("Start").unwrap();
+
+    let events = dialogue.continue_().unwrap();
+    let Some(DialogueEvent::Line(line)) = events.last() else {
+        panic!("Expected a line, but got {events:?}");
+    };
+    assert_eq!("Hello, Sam!", line.text);
+
+    dialogue.set_language_code(Language::from("de-CH"));
+    let line = dialogue.relocalize_current_line().unwrap().unwrap();
+    assert_eq!("Hallo, Sam!", line.text);
+
+    dialogue.continue_().unwrap();
+    assert!(dialogue.relocalize_current_line().is_none());
+}
@@ -0,0 +1,217 @@
+//! Not part of the original Yarn Spinner, which has no tooling to explore dialogue automatically.
+
+use test_base::prelude::*;
+use yarnspinner::compiler::*;
+use yarnspinner::core::{Library, LineId};
+use yarnspinner::explorer::*;
+use yarnspinner::runtime::*;
+
+mod test_base;
+
+fn compile(source: &str) -> Compilation {
+    let mut library = Library::new();
+    library.add_function("roll", || 4);
+    Compiler::new()
+        .add_file(File {
+            file_name: "test.yarn".to_owned(),
+            source: source.to_owned(),
+        })
+        .extend_library(library)
+        .compile()
+        .unwrap()
+}
+
+fn outcomes(report: &ExplorationReport) -> Vec<String> {
+    report
+        .paths
+        .iter()
+        .map(|path| path.outcome.to_string())
+        .collect()
+}
+
+#[test]
+fn test_exploring_flags_problems_and_unreachable_content() {
+    let compilation = compile(
+        "\
+title: Start
+---
+<<declare $gold = 0>>
+Guard: Halt!
+-> Pay
+    <<set $gold to 5>>
+    <<jump Gate>>
+-> Fight
+    <<jump Fight>>
+-> Leave
+===
+title: Gate
+---
+Guard: Go on.
+-> Enter <<if $gold > 10>>
+===
+title: Fight
+---
+<<if roll() > 3>>
+    You win.
+<<endif>>
+===
+title: Unused
+---
+Nobody hears this. #line:unused
+===
+",
+    );
+
+    let report = DialogueExplorer::new(&compilation).explore("Start");
+
+    let outcomes = outcomes(&report);
+    assert_eq!(3, outcomes.len());
+    assert_eq!("dead end: no option is available", outcomes[0]);
+    assert!(outcomes[1].starts_with("error: Function \"roll\" not found in library"));
+    assert_eq!("dialogue complete", outcomes[2]);
+    assert_eq!(vec![OptionId(0)], report.paths[0].selections);
+    assert_eq!(
+        "Guard: Halt!\n\
+        -> Pay\n\
+        -> Fight\n\
+        -> Leave\n\
+        [selected Pay]\n\
+        Guard: Go on.\n\
+        -> Enter (unavailable)\n\
+        [dead end: no option is available]",
+        report.paths[0].to_string()
+    );
+    assert_eq!(2, report.problems().count());
+    assert!(!report.truncated);
+    assert_eq!(
+        vec!["Unused"],
+        report.unreachable_nodes().collect::<Vec<_>>()
+    );
+    assert!(report
+        .unreachable_lines()
+        .any(|(node, line_id)| node == "Unused" && line_id == &LineId::from("line:unused")));
+}
+
+#[test]
+fn test_exploring_with_setup_registers_functions() {
+    let compilation =
+        compile("title: Start\n---\n<<if roll() > 3>>\n    You win.\n<<endif>>\n===\n");
+
+    let report = DialogueExplorer::new(&compilation)
+        .with_setup(|dialogue| {
+            dialogue.library_mut().add_function("roll", || 4);
+        })
+        .explore("Start");
+
+    assert_eq!(vec!["dialogue complete"], outcomes(&report));
+    assert_eq!(
+        vec![TranscriptEntry::Line("You win.".to_owned())],
+        report.paths[0].transcript
+    );
+    assert_eq!(0, report.unreachable_lines().count());
+}
+
+#[test]
+fn test_exploring_detects_cycles_and_merged_paths() {
+    let compilation = compile(
+        "\
+title: Start
+---
+-> Again
+    <<jump Start>>
+-> Onwards
+    <<jump Onwards>>
+===
+title: Onwards
+---
+-> Left
+-> Right
+Both ways meet here.
+-> Stop
+===
+",
+    );
+
+    let report = DialogueExplorer::new(&compilation).explore("Start");
+
+    assert_eq!(
+        vec![
+            "cycle: these options were already presented in the same state on this path",
+            "dialogue complete",
+            "merged: another path already explored these options in the same state",
+        ],
+        outcomes(&report)
+    );
+    assert_eq!(
+        vec![OptionId(1), OptionId(0), OptionId(0)],
+        report.paths[1].selections
+    );
+    assert_eq!(0, report.problems().count());
+}
+
+#[test]
+fn test_exploring_respects_limits() {
+    let compilation = compile(
+        "\
+title: Start
+---
+<<declare $count = 0>>
+<<set $count to $count + 1>>
+-> Again
+    <<jump Start>>
+-> Stop
+===
+",
+    );
+
+    let report = DialogueExplorer::new(&compilation)
+        .with_max_choices(2)
+        .explore("Start");
+    assert_eq!(
+        vec![
+            "stopped: too many choices",
+            "dialogue complete",
+            "dialogue complete",
+        ],
+        outcomes(&report)
+    );
+
+    let report = DialogueExplorer::new(&compilation)
+        .with_max_paths(1)
+        .explore("Start");
+    assert_eq!(1, report.paths.len());
+    assert!(report.truncated);
+
+    let compilation = compile("title: Start\n---\nAgain and again\n<<jump Start>>\n===\n");
+    let report = DialogueExplorer::new(&compilation)
+        .with_max_steps(5)
+        .explore("Start");
+    assert_eq!(
+        vec!["stopped: too many steps without a choice"],
+        outcomes(&report)
+    );
+    assert_eq!(5, report.paths[0].transcript.len());
+}
+
+#[test]
+fn test_exploring_selects_options_by_id() {
+    let compilation = compile("title: Start\n---\n-> First\n-> Second\n-> Third\n===\n");
+
+    let report = DialogueExplorer::new(&compilation)
+        .with_setup(|dialogue| {
+            dialogue.set_options_processor(|options: &mut Vec<DialogueOption>| options.reverse());
+        })
+        .explore("Start");
+
+    assert_eq!(3, report.paths.len());
+    assert_eq!(vec![OptionId(2)], report.paths[0].selections);
+    assert_eq!(
+        Some(&TranscriptEntry::Selected("Third".to_owned())),
+        report.paths[0].transcript.get(1)
+    );
+    assert_eq!(vec![OptionId(0)], report.paths[2].selections);
+    assert_eq!(
+        Some(&TranscriptEntry::Selected("First".to_owned())),
+        report.paths[2].transcript.get(1)
+    );
+}
@@ -0,0 +1,445 @@
+//! Not part of the original Yarn Spinner. Tests for Rust-specific features of the functions in a [`Library`].
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{self, Poll, Waker};
+use test_base::prelude::*;
+use yarnspinner::compiler::*;
+use yarnspinner::core::{
+    add_module, yarn_fn, yarn_library, ConflictPolicy, Library, YarnFnContext, YarnFnDefinition,
+    YarnFnMetadata, YarnValue,
+};
+use yarnspinner::runtime::*;
+
+mod test_base;
+
+#[test]
+fn test_functions_can_request_context() {
+    let test_base = TestBase::new().extend_library(|library| {
+        library.add_function("describe", |context: &YarnFnContext, variable: &str| {
+            let value = context.variable(variable).map(|value| value.to_string());
+            format!(
+                "{} in {}: {}",
+                context.node_name().unwrap_or_default(),
+                context.language_code().unwrap_or_default(),
+                value.unwrap_or_default(),
+            )
+        });
+    });
+    let source = "\
+    <<declare $gold = 42>>
+    <<declare $description = \"\">>
+    <<set $description = describe(\"$gold\")>>
+    ";
+    let result = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .compile()
+        .unwrap();
+    let mut dialogue = test_base.with_compilation(result).dialogue;
+    dialogue.set_language_code(Language::from("de-CH"));
+    dialogue.set_node("Start").unwrap();
+    dialogue.run_to_completion().unwrap();
+
+    assert_eq!(
+        dialogue.variable_storage().get("$description").unwrap(),
+        YarnValue::from("Start in de-CH: 42")
+    );
+}
+
+#[test]
+fn test_annotated_functions_can_be_added_as_module() {
+    let test_base = TestBase::new().extend_library(|library| {
+        add_module!(library, [shout]);
+        add_module!(library, "math", [double]);
+    });
+    let source = "\
+    <<declare $greeting = \"\">>
+    <<declare $doubled = 0>>
+    <<set $greeting = loud(\"hi\")>>
+    <<set $doubled = math_double(21)>>
+    ";
+    let result = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .compile()
+        .unwrap();
+    let mut dialogue = test_base.with_compilation(result).dialogue;
+    dialogue.set_node("Start").unwrap();
+    dialogue.run_to_completion().unwrap();
+
+    let storage = dialogue.variable_storage();
+    assert_eq!(storage.get("$greeting").unwrap(), YarnValue::from("HI!"));
+    assert_eq!(storage.get("$doubled").unwrap(), YarnValue::from(42));
+    assert_eq!(&["text"], shout::PARAMETER_NAMES);
+    assert_eq!(Some("Turns the text into a shout."), shout::DOCS);
+}
+
+/// Turns the text into a shout.
+#[yarn_fn(name = "loud")]
+fn shout(_context: &YarnFnContext, text: &str) -> String {
+    format!("{}!", text.to_uppercase())
+}
+
+#[yarn_fn]
+fn double(x: f64) -> f64 {
+    x * 2.0
+}
+
+const MISSING_FUNCTION_SOURCE: &str = "\
+    <<declare $bonus = 0>>
+    <<set $bonus = mod_bonus(3)>>
+    ";
+
+fn dialogue_calling_missing_function(
+    behavior: MissingFunctionBehavior,
+) -> std::result::Result<Dialogue, DialogueError> {
+    let result = Compiler::from_test_source(MISSING_FUNCTION_SOURCE)
+        .compile()
+        .unwrap();
+    let mut dialogue = TestBase::new().with_compilation(result).dialogue;
+    dialogue.set_missing_function_behavior(behavior);
+    dialogue.set_node("Start").unwrap();
+    dialogue.run_to_completion()?;
+    Ok(dialogue)
+}
+
+#[test]
+fn test_missing_function_fails_by_default() {
+    let error = dialogue_calling_missing_function(MissingFunctionBehavior::default()).unwrap_err();
+    assert!(
+        matches!(&error, DialogueError::FunctionNotFound { function_name, .. } if function_name == "mod_bonus"),
+        "Unexpected error: {error}"
+    );
+}
+
+#[test]
+fn test_missing_function_can_be_replaced_by_value() {
+    let dialogue =
+        dialogue_calling_missing_function(MissingFunctionBehavior::WarnAndReturn(7.into()))
+            .unwrap();
+    assert_eq!(
+        dialogue.variable_storage().get("$bonus").unwrap(),
+        YarnValue::from(7.0)
+    );
+}
+
+#[test]
+fn test_missing_function_can_be_handled() {
+    let dialogue = dialogue_calling_missing_function(MissingFunctionBehavior::handler(
+        |function_name: &str, parameters: &[YarnValue]| {
+            assert_eq!("mod_bonus", function_name);
+            let base = f64::try_from(&parameters[0]).unwrap();
+            Some((base * 2.0).into())
+        },
+    ))
+    .unwrap();
+    assert_eq!(
+        dialogue.variable_storage().get("$bonus").unwrap(),
+        YarnValue::from(6.0)
+    );
+
+    let error = dialogue_calling_missing_function(MissingFunctionBehavior::handler(
+        |_function_name: &str, _parameters: &[YarnValue]| None,
+    ))
+    .unwrap_err();
+    assert!(matches!(error, DialogueError::FunctionNotFound { .. }));
+}
+
+#[test]
+fn test_failing_function_returns_error() {
+    let test_base = TestBase::new().extend_library(|library| {
+        library.add_function("parse_number", |text: &str| text.parse::<f32>());
+    });
+    let source = "\
+    <<declare $number = 0>>
+    <<set $number = parse_number(\"not a number\")>>
+    ";
+    let result = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .compile()
+        .unwrap();
+    let mut dialogue = test_base.with_compilation(result).dialogue;
+    dialogue.set_node("Start").unwrap();
+
+    let error = dialogue.continue_().unwrap_err();
+    assert!(
+        matches!(&error, DialogueError::FunctionCallError { function_name, node_name, .. } if function_name == "parse_number" && node_name == "Start"),
+        "Unexpected error: {error}"
+    );
+}
+
+#[test]
+fn test_failing_function_reports_its_source_location() {
+    let test_base = TestBase::new().extend_library(|library| {
+        library.add_function("parse_number", |text: &str| text.parse::<f32>());
+    });
+    let source = "\
+title: Start
+---
+<<declare $number = 0>>
+<<set $number = parse_number(\"not a number\")>>
+===
+";
+    let result = Compiler::new()
+        .add_file(File {
+            file_name: "numbers.yarn".to_owned(),
+            source: source.to_owned(),
+        })
+        .extend_library(test_base.dialogue.library().clone())
+        .compile()
+        .unwrap();
+    let debug_info = result.debug_info.clone();
+    let mut dialogue = test_base.with_compilation(result).dialogue;
+    dialogue
+        .add_debug_info(debug_info)
+        .set_node("Start")
+        .unwrap();
+
+    let error = dialogue.continue_().unwrap_err();
+    assert_eq!(
+        "Function \"parse_number\" failed in node \"Start\" at numbers.yarn:4:17: invalid float literal",
+        error.to_string()
+    );
+}
+
+#[test]
+fn test_variadic_function_accepts_any_number_of_arguments() {
+    let test_base = TestBase::new().extend_library(|library| {
+        library.add_function("max_of", |first: f32, rest: Vec<f32>| {
+            rest.into_iter().fold(first, f32::max)
+        });
+    });
+    let source = "\
+    <<declare $single = 0>>
+    <<declare $many = 0>>
+    <<set $single = max_of(1)>>
+    <<set $many = max_of(1, 5, 3, 4)>>
+    ";
+    let result = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .compile()
+        .unwrap();
+    let mut dialogue = test_base.with_compilation(result).dialogue;
+    dialogue.set_node("Start").unwrap();
+    dialogue.run_to_completion().unwrap();
+
+    let storage = dialogue.variable_storage();
+    assert_eq!(storage.get("$single").unwrap(), YarnValue::from(1.0));
+    assert_eq!(storage.get("$many").unwrap(), YarnValue::from(5.0));
+}
+
+#[test]
+fn test_variadic_function_rejects_arguments_of_wrong_type() {
+    let test_base = TestBase::new().extend_library(|library| {
+        library.add_function("max_of", |first: f32, rest: Vec<f32>| {
+            rest.into_iter().fold(first, f32::max)
+        });
+    });
+    let source = "\
+    <<declare $many = 0>>
+    <<set $many = max_of(1, \"two\")>>
+    ";
+    let result = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .compile();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_overloaded_functions_are_resolved_by_argument_types() {
+    let test_base = TestBase::new().extend_library(|library| {
+        library
+            .add_overload("clamp", |value: f32| value.clamp(0.0, 1.0))
+            .add_overload("clamp", |value: f32, max: f32| value.clamp(0.0, max))
+            .add_overload("clamp", |value: f32, min: f32, max: f32| {
+                value.clamp(min, max)
+            })
+            .add_overload("describe", |value: f32| format!("number {value}"))
+            .add_overload("describe", |value: String| format!("string {value}"));
+    });
+    let source = "\
+    <<declare $one = 0>>
+    <<declare $two = 0>>
+    <<declare $three = 0>>
+    <<declare $number = \"\">>
+    <<declare $string = \"\">>
+    <<set $one = clamp(5)>>
+    <<set $two = clamp(5, 2)>>
+    <<set $three = clamp(5, 1, 3)>>
+    <<set $number = describe(1)>>
+    <<set $string = describe(\"a\")>>
+    ";
+    let result = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .compile()
+        .unwrap();
+    let mut dialogue = test_base.with_compilation(result).dialogue;
+    dialogue.set_node("Start").unwrap();
+    dialogue.run_to_completion().unwrap();
+
+    let storage = dialogue.variable_storage();
+    assert_eq!(storage.get("$one").unwrap(), YarnValue::from(1.0));
+    assert_eq!(storage.get("$two").unwrap(), YarnValue::from(2.0));
+    assert_eq!(storage.get("$three").unwrap(), YarnValue::from(3.0));
+    assert_eq!(storage.get("$number").unwrap(), YarnValue::from("number 1"));
+    assert_eq!(storage.get("$string").unwrap(), YarnValue::from("string a"));
+}
+
+#[test]
+fn test_overloaded_function_without_matching_overload_fails_to_compile() {
+    let test_base = TestBase::new().extend_library(|library| {
+        library
+            .add_overload("clamp", |value: f32| value.clamp(0.0, 1.0))
+            .add_overload("clamp", |value: f32, max: f32| value.clamp(0.0, max));
+    });
+    let source = "\
+    <<declare $value = 0>>
+    <<set $value = clamp(true)>>
+    ";
+    let result = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .compile();
+    let error = result.unwrap_err();
+    assert!(error.0.iter().any(|diagnostic| diagnostic
+        .message
+        .contains("No overload of function \"clamp\"")));
+}
+
+#[test]
+fn test_overloaded_functions_are_resolved_at_runtime_if_unknown_to_compiler() {
+    let test_base = TestBase::new().extend_library(|library| {
+        library
+            .add_overload("clamp", |value: f32| value.clamp(0.0, 1.0))
+            .add_overload("clamp", |value: f32, max: f32| value.clamp(0.0, max));
+    });
+    let source = "\
+    <<declare $value = 0>>
+    <<set $value = clamp(5, 2)>>
+    ";
+    let result = Compiler::from_test_source(source).compile().unwrap();
+    let mut dialogue = test_base.with_compilation(result).dialogue;
+    dialogue.set_node("Start").unwrap();
+    dialogue.run_to_completion().unwrap();
+
+    assert_eq!(
+        dialogue.variable_storage().get("$value").unwrap(),
+        YarnValue::from(2.0)
+    );
+}
+
+#[test]
+fn test_calling_deprecated_function_emits_warning() {
+    let test_base = TestBase::new().extend_library(|library| {
+        library.add_function_with_metadata(
+            "old_double",
+            |value: f32| value * 2.0,
+            YarnFnMetadata::new().with_deprecation("Use `value * 2` instead"),
+        );
+    });
+    let source = "\
+    <<declare $value = 0>>
+    <<set $value = old_double(2)>>
+    ";
+    let compilation = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .compile()
+        .unwrap();
+
+    let warning = compilation
+        .warnings
+        .iter()
+        .find(|diagnostic| diagnostic.message.contains("old_double"))
+        .unwrap();
+    assert_eq!(
+        "Function \"old_double\" is deprecated: Use `value * 2` instead",
+        warning.message
+    );
+    assert_eq!(DiagnosticSeverity::Warning, warning.severity);
+}
+
+#[test]
+fn test_namespaced_libraries_do_not_clobber_each_other() {
+    let combat = yarn_library! { "roll" => || 6.0, }.with_namespace("combat");
+    let loot = yarn_library! { "roll" => || 1.0, }.with_namespace("loot");
+    let plugins = Library::new()
+        .with_library(combat, ConflictPolicy::Error)
+        .unwrap()
+        .with_library(loot, ConflictPolicy::Error)
+        .unwrap();
+    let test_base = TestBase::new().extend_library(|library| {
+        library
+            .merge(plugins.clone(), ConflictPolicy::Error)
+            .unwrap();
+    });
+    let source = "\
+    <<declare $damage = 0>>
+    <<declare $gold = 0>>
+    <<set $damage = combat_roll()>>
+    <<set $gold = loot_roll()>>
+    ";
+    let result = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .compile()
+        .unwrap();
+    let mut dialogue = test_base.with_compilation(result).dialogue;
+    dialogue.set_node("Start").unwrap();
+    dialogue.run_to_completion().unwrap();
+
+    let storage = dialogue.variable_storage();
+    assert_eq!(storage.get("$damage").unwrap(), YarnValue::from(6.0));
+    assert_eq!(storage.get("$gold").unwrap(), YarnValue::from(1.0));
+}
+
+#[test]
+fn test_async_function_makes_dialogue_wait_until_resolved() {
+    let is_loaded = Arc::new(AtomicBool::new(false));
+    let is_loaded_clone = is_loaded.clone();
+    let test_base = TestBase::new().extend_library(move |library| {
+        let is_loaded = is_loaded_clone.clone();
+        library.add_function("load_gold", move || {
+            let is_loaded = is_loaded.clone();
+            std::future::poll_fn(move |_| {
+                if is_loaded.load(Ordering::SeqCst) {
+                    Poll::Ready(42.0_f32)
+                } else {
+                    Poll::Pending
+                }
+            })
+        });
+    });
+    let source = "\
+    <<declare $gold = 0>>
+    <<set $gold = load_gold()>>
+    ";
+    let result = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .compile()
+        .unwrap();
+    let mut dialogue = test_base.with_compilation(result).dialogue;
+    let mut context = task::Context::from_waker(Waker::noop());
+    dialogue.set_node("Start").unwrap();
+    dialogue.continue_().unwrap();
+
+    assert!(dialogue.is_waiting_for_function_call());
+    assert!(matches!(
+        dialogue.continue_(),
+        Err(DialogueError::ContinueOnPendingFunctionCallError)
+    ));
+    assert!(dialogue
+        .poll_pending_function_call(&mut context)
+        .is_pending());
+
+    is_loaded.store(true, Ordering::SeqCst);
+    assert!(matches!(
+        dialogue.poll_pending_function_call(&mut context),
+        Poll::Ready(Ok(()))
+    ));
+    assert!(!dialogue.is_waiting_for_function_call());
+    while dialogue.is_active() {
+        dialogue.continue_().unwrap();
+    }
+
+    assert_eq!(
+        dialogue.variable_storage().get("$gold").unwrap(),
+        YarnValue::from(42.0)
+    );
+}
@@ -0,0 +1,118 @@
+//! Not part of the original Yarn Spinner. Tests for [`LocalizedLine`] and [`Dialogue::localize_line`].
+
+use yarnspinner::compiler::*;
+use yarnspinner::core::LineId;
+use yarnspinner::runtime::*;
+
+fn dialogue(source: &str) -> Dialogue {
+    let compilation = Compiler::new()
+        .add_file(File {
+            file_name: "test.yarn".to_owned(),
+            source: source.to_owned(),
+        })
+        .compile()
+        .unwrap();
+    let mut text_provider = StringTableTextProvider::new();
+    text_provider.extend_base_language(
+        compilation
+            .string_table
+            .iter()
+            .map(|(id, string_info)| (id.clone(), string_info.text.clone()))
+            .collect(),
+    );
+    let mut dialogue = Dialogue::new(
+        Box::new(MemoryVariableStorage::new()),
+        Box::new(text_provider),
+    );
+    dialogue
+        .add_program(compilation.program.unwrap())
+        .add_line_metadata(
+            compilation
+                .string_table
+                .into_iter()
+                .map(|(id, string_info)| (id, string_info.metadata)),
+        );
+    dialogue.set_node("Start").unwrap();
+    dialogue
+}
+
+fn next_line(dialogue: &mut Dialogue) -> Line {
+    dialogue
+        .continue_()
+        .unwrap()
+        .into_iter()
+        .find_map(|event| match event {
+            DialogueEvent::Line(line) => Some(line),
+            _ => None,
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_localized_line_has_metadata_of_the_line() {
+    let mut dialogue =
+        dialogue("title: Start\n---\nAlice: Hi [b]there[/b]! #line:hi #happy\nBob: Hello.\n===\n");
+
+    let line = next_line(&mut dialogue);
+    let localized = dialogue.localize_line(line.clone());
+
+    assert_eq!(localized.id, LineId::from("line:hi"));
+    assert_eq!(localized.text, line.text);
+    assert_eq!(localized.attributes, line.attributes);
+    assert_eq!(localized.metadata, vec!["line:hi", "happy"]);
+    assert_eq!(localized.character_name(), Some("Alice"));
+    assert_eq!(localized.text_without_character_name(), "Hi there!");
+    let bold = localized.attribute("b").unwrap();
+    assert_eq!(localized.text_for_attribute(bold), "there");
+    assert_eq!(Line::from(localized), line);
+
+    // Implicit line IDs are not part of the metadata
+    let line = next_line(&mut dialogue);
+    assert_eq!(dialogue.line_metadata(&line.id), Some([].as_slice()));
+    assert!(dialogue.localize_line(line).metadata.is_empty());
+}
+
+#[test]
+fn test_localized_line_knows_if_it_comes_before_options() {
+    let mut dialogue =
+        dialogue("title: Start\n---\nWhere to?\n-> North\n-> South\nNo hashtags here.\n===\n");
+
+    let line = next_line(&mut dialogue);
+
+    assert!(dialogue.localize_line(line).is_last_line_before_options());
+}
+
+#[test]
+fn test_assets_can_be_attached_to_a_localized_line() {
+    let mut dialogue = dialogue("title: Start\n---\nAlice: Hi! #voice\n===\n");
+    let line = next_line(&mut dialogue);
+
+    let localized = dialogue
+        .localize_line(line.clone())
+        .with_assets(vec!["alice_hi.ogg"]);
+    let without_name = localized.delete_range(localized.attribute("character").unwrap());
+
+    assert_eq!(localized.assets, vec!["alice_hi.ogg"]);
+    assert_eq!(without_name.assets, localized.assets);
+    assert_eq!(without_name.metadata, localized.metadata);
+    assert_eq!(without_name.text, "Hi!");
+    assert_eq!(
+        LocalizedLine::from_line(line, vec!["voice".to_owned()], 42).assets,
+        42
+    );
+}
+
+#[test]
+fn test_lines_without_registered_metadata_have_none() {
+    let mut dialogue = dialogue("title: Start\n---\nHi! #tag\n===\n");
+    let line = next_line(&mut dialogue);
+    let mut unregistered = Dialogue::new(
+        Box::new(MemoryVariableStorage::new()),
+        Box::new(StringTableTextProvider::new()),
+    );
+    unregistered.add_line_metadata([]);
+
+    let localized = unregistered.localize_line(line);
+
+    assert!(localized.metadata.is_empty());
+}
@@ -0,0 +1,93 @@
+//! Not part of the original Yarn Spinner. Makes sure that malformed input, like a file that is still being written,
+//! results in error diagnostics instead of a panic. Most of these inputs were found by the fuzz targets in `fuzz/`.
+
+use yarnspinner::compiler::*;
+
+fn compile(source: &str) -> Result<Compilation> {
+    Compiler::new()
+        .add_file(File {
+            file_name: "test.yarn".to_owned(),
+            source: source.to_owned(),
+        })
+        .compile()
+}
+
+fn assert_fails_with_errors(source: &str) {
+    let error = compile(source).expect_err(source);
+    assert!(error.0.has_errors(), "No error diagnostics for {source:?}");
+}
+
+#[test]
+fn test_files_without_dialogue_are_errors() {
+    for source in ["", "\n", "   ", "// just a comment\n", "\n===\n", "title"] {
+        assert_fails_with_errors(source);
+    }
+}
+
+#[test]
+fn test_incomplete_statements_are_errors() {
+    for statement in [
+        "<<set",
+        "<<set $x to>>",
+        "<<set $x +=>>",
+        "<<declare $gold",
+        "<<declare $gold = >>",
+        "<<jump {}>>",
+        "<<if>>\n<<endif>>",
+        "<<if $x ==>>\n<<endif>>",
+        "{visited(}",
+        "-> Option <<if>>",
+        "Line #",
+    ] {
+        assert_fails_with_errors(&format!("title: Start\n---\n{statement}\n===\n"));
+    }
+}
+
+#[test]
+fn test_incomplete_headers_are_errors() {
+    assert_fails_with_errors("title: Start\ntags: rawText\ntrac");
+    assert_fails_with_errors("# file_tag\n#\ntitle: Start\n---\nHi\n===\n");
+    assert_fails_with_errors("foo: x\n---\n<<set $y to 1>>\n===\n");
+    assert_fails_with_errors("title:\n---\n<<set $y to 1>>\n===\n");
+}
+
+#[test]
+fn test_visited_without_arguments_is_an_error() {
+    let error = compile("title: Start\n---\n<<set $count = visited_count()>>\n===\n").unwrap_err();
+    assert!(error.0.has_errors());
+}
+
+#[test]
+fn test_every_prefix_of_a_file_compiles_without_panicking() {
+    let source = "\
+# file_tag
+title: Start
+tags: a b
+---
+<<declare $gold = 5 as number>>
+Guard: Hi {$gold} [b]you[/b]! #line:hi #mood
+<<set $gold += visited_count(\"Start\") * 2>>
+-> Pay <<if $gold >= 5>>
+    <<jump {\"Other\"}>>
+-> Leave #line:leave
+<<if not $gold == 0 and dice(6) > 3>>
+    <<wait 1>>
+<<elseif $gold < 0>>
+<<else>>
+<<endif>>
+===
+title: Other
+tags: rawText
+---
+Raw text
+===
+";
+    for (end, _) in source.char_indices() {
+        let prefix = &source[..end];
+        for source in [prefix.to_owned(), format!("{prefix}\n===\n")] {
+            if let Err(error) = compile(&source) {
+                assert!(error.0.has_errors(), "No error diagnostics for {source:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Not part of the original Yarn Spinner. Tests for [`DialogueMetrics`].
+
+use test_base::prelude::*;
+use yarnspinner::compiler::*;
+use yarnspinner::runtime::*;
+
+mod test_base;
+
+#[test]
+fn test_metrics_count_delivered_content() {
+    let source = "\
+title: Start
+---
+Hello
+<<wave>>
+-> Stay
+    Good choice
+-> Leave
+    <<jump End>>
+===
+title: End
+---
+Bye
+===
+";
+    let compilation = Compiler::new()
+        .add_file(File {
+            file_name: "metrics.yarn".to_owned(),
+            source: source.to_owned(),
+        })
+        .compile()
+        .unwrap();
+    let mut dialogue = TestBase::new().with_compilation(compilation).dialogue;
+    dialogue.set_node("Start").unwrap();
+    loop {
+        for event in dialogue.continue_().unwrap() {
+            if let DialogueEvent::Options(options) = event {
+                dialogue.set_selected_option(options[1].id).unwrap();
+            }
+        }
+        if !dialogue.is_active() {
+            break;
+        }
+    }
+
+    assert_eq!(
+        dialogue.metrics(),
+        DialogueMetrics {
+            lines_delivered: 2,
+            options_presented: 1,
+            options_chosen: 1,
+            commands_executed: 1,
+            nodes_visited: 2,
+        }
+    );
+    dialogue.reset_metrics();
+    assert_eq!(dialogue.metrics(), DialogueMetrics::default());
+}
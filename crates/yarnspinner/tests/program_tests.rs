@@ -0,0 +1,159 @@
+//! Not part of the original Yarn Spinner. Tests for building programs with [`ProgramBuilder`] and combining programs in a [`Dialogue`].
+
+use test_base::prelude::*;
+use yarnspinner::compiler::*;
+use yarnspinner::core::{LineId, NodeBuilder, ProgramBuildError, ProgramBuilder, YarnValue};
+use yarnspinner::runtime::*;
+
+mod test_base;
+
+#[test]
+fn test_built_programs_can_be_run() {
+    let program = ProgramBuilder::new()
+        .initial_value("$gold", 0)
+        .node(
+            NodeBuilder::new("Start")
+                .line("line:greeting")
+                .options(|options| {
+                    options
+                        .option("line:buy", |branch| {
+                            branch.set_variable("$gold", 10).line("line:thanks")
+                        })
+                        .option("line:leave", |branch| branch.jump_to_node("Farewell"))
+                })
+                .command("wave"),
+        )
+        .node(NodeBuilder::new("Farewell").line("line:bye"))
+        .build()
+        .unwrap();
+    let string_table = [
+        "line:greeting",
+        "line:buy",
+        "line:leave",
+        "line:thanks",
+        "line:bye",
+    ]
+    .into_iter()
+    .map(|id| {
+        let info = StringInfo {
+            text: id.to_owned(),
+            ..Default::default()
+        };
+        (LineId::from(id), info)
+    })
+    .collect();
+    let mut dialogue = TestBase::new()
+        .with_program(program)
+        .with_string_table(string_table)
+        .dialogue;
+    dialogue.set_node("Start").unwrap();
+
+    let mut line_ids = Vec::new();
+    let mut commands = Vec::new();
+    loop {
+        for event in dialogue.continue_().unwrap() {
+            match event {
+                DialogueEvent::Line(line) => line_ids.push(line.id.0),
+                DialogueEvent::Options(options) => {
+                    dialogue.set_selected_option(options[0].id).unwrap();
+                }
+                DialogueEvent::Command(command) => commands.push(command.name),
+                _ => {}
+            }
+        }
+        if !dialogue.is_active() {
+            break;
+        }
+    }
+
+    assert_eq!(line_ids, vec!["line:greeting", "line:thanks"]);
+    assert_eq!(commands, vec!["wave"]);
+    assert_eq!(
+        dialogue.variable_storage().get("$gold").unwrap(),
+        YarnValue::from(10)
+    );
+}
+
+#[test]
+fn test_program_builder_rejects_unknown_labels() {
+    let result = ProgramBuilder::new()
+        .node(NodeBuilder::new("Start").jump_to("nowhere"))
+        .build();
+    assert_eq!(
+        result,
+        Err(ProgramBuildError::UnknownLabel {
+            node: "Start".to_owned(),
+            label: "nowhere".to_owned(),
+        })
+    );
+}
+
+#[test]
+fn test_conflicting_programs_report_all_collisions() {
+    let program = ProgramBuilder::new()
+        .initial_value("$gold", 0)
+        .node(NodeBuilder::new("Start"))
+        .node(NodeBuilder::new("Shop"))
+        .build()
+        .unwrap();
+    let conflicting_program = ProgramBuilder::new()
+        .initial_value("$gold", 100)
+        .node(NodeBuilder::new("Start"))
+        .node(NodeBuilder::new("Shop"))
+        .build()
+        .unwrap();
+    let mut dialogue = TestBase::new().with_program(program).dialogue;
+
+    let error = dialogue
+        .try_add_program(conflicting_program)
+        .map(|_| ())
+        .unwrap_err();
+    let DialogueError::ProgramCombineError(error) = error else {
+        panic!("Expected a conflict, got {error}");
+    };
+    assert_eq!(error.duplicate_nodes, vec!["Shop", "Start"]);
+    assert_eq!(error.conflicting_initial_values, vec!["$gold"]);
+}
+
+#[test]
+fn test_namespaced_programs_can_reuse_node_names() {
+    let source = "\
+title: Start
+---
+<<jump Other>>
+===
+title: Other
+---
+<<if visited(\"Other\")>>
+Visited again
+<<else>>
+First visit
+<<endif>>
+===
+";
+    let compilation = Compiler::new()
+        .add_file(File {
+            file_name: "mod.yarn".to_owned(),
+            source: source.to_owned(),
+        })
+        .compile()
+        .unwrap();
+    let namespaced_program = compilation.program.clone().unwrap().with_namespace("dlc");
+    let mut dialogue = TestBase::new().with_compilation(compilation).dialogue;
+    dialogue.try_add_program(namespaced_program).unwrap();
+
+    let mut lines = Vec::new();
+    for _ in 0..2 {
+        dialogue.set_node("dlc_Start").unwrap();
+        for events in dialogue.by_ref() {
+            for event in events {
+                if let DialogueEvent::Line(line) = event {
+                    lines.push(line.text);
+                }
+            }
+        }
+    }
+
+    assert_eq!(lines, vec!["First visit", "Visited again"]);
+    assert!(dialogue.node_names().unwrap().any(|name| name == "Start"));
+}
@@ -0,0 +1,184 @@
+//! Not part of the original Yarn Spinner. Tests for [`Compiler::find_references`] and [`Compiler::rename`].
+
+use yarnspinner::compiler::*;
+use yarnspinner::core::Position;
+
+const SHOP: &str = "\
+title: Shop
+---
+<<declare $gold = 10>>
+Shopkeeper: You have {$gold} coins.
+-> Buy <<if $gold >= 5>>
+    <<set $gold to $gold - 5>>
+    <<jump Exit>>
+-> Leave
+    <<jump {\"Exit\"}>>
+===
+";
+
+const EXIT: &str = "\
+title: Exit
+---
+<<if visited(\"Shop\") and dice(6) > 3>>
+    You leave with {$gold} coins.
+<<endif>>
+<<jump Shop>>
+===
+";
+
+fn compiler() -> Compiler {
+    let mut compiler = Compiler::new();
+    compiler
+        .add_file(File {
+            file_name: "shop.yarn".to_owned(),
+            source: SHOP.to_owned(),
+        })
+        .add_file(File {
+            file_name: "exit.yarn".to_owned(),
+            source: EXIT.to_owned(),
+        });
+    compiler
+}
+
+fn position(line: usize, character: usize) -> Position {
+    Position { line, character }
+}
+
+#[test]
+fn test_finds_variable_references_across_files() {
+    let references = compiler().find_references(&Symbol::Variable("$gold".to_owned()));
+
+    let locations: Vec<_> = references
+        .iter()
+        .map(|reference| {
+            (
+                reference.file_name.as_str(),
+                reference.range.start,
+                reference.kind,
+            )
+        })
+        .collect();
+    assert_eq!(
+        locations,
+        vec![
+            ("shop.yarn", position(2, 10), ReferenceKind::Definition),
+            ("shop.yarn", position(3, 22), ReferenceKind::Usage),
+            ("shop.yarn", position(4, 12), ReferenceKind::Usage),
+            ("shop.yarn", position(5, 10), ReferenceKind::Usage),
+            ("shop.yarn", position(5, 19), ReferenceKind::Usage),
+            ("exit.yarn", position(3, 20), ReferenceKind::Usage),
+        ]
+    );
+    assert!(references
+        .iter()
+        .all(|reference| reference.range.end.character == reference.range.start.character + 5));
+}
+
+#[test]
+fn test_finds_node_references_in_titles_jumps_and_visited() {
+    let compiler = compiler();
+
+    let exit: Vec<_> = compiler
+        .find_references(&Symbol::Node("Exit".to_owned()))
+        .into_iter()
+        .map(|reference| (reference.file_name, reference.range, reference.kind))
+        .collect();
+    assert_eq!(
+        exit,
+        vec![
+            (
+                "shop.yarn".to_owned(),
+                position(6, 11)..position(6, 15),
+                ReferenceKind::Usage
+            ),
+            (
+                "shop.yarn".to_owned(),
+                position(8, 13)..position(8, 17),
+                ReferenceKind::Usage
+            ),
+            (
+                "exit.yarn".to_owned(),
+                position(0, 7)..position(0, 11),
+                ReferenceKind::Definition
+            ),
+        ]
+    );
+
+    let shop = compiler.find_references(&Symbol::Node("Shop".to_owned()));
+    let ranges: Vec<_> = shop
+        .iter()
+        .map(|reference| reference.range.clone())
+        .collect();
+    assert_eq!(
+        ranges,
+        vec![
+            position(0, 7)..position(0, 11),
+            position(2, 14)..position(2, 18),
+            position(5, 7)..position(5, 11),
+        ]
+    );
+}
+
+#[test]
+fn test_finds_function_calls() {
+    let references = compiler().find_references(&Symbol::Function("dice".to_owned()));
+
+    assert_eq!(references.len(), 1);
+    assert_eq!(references[0].file_name, "exit.yarn");
+    assert_eq!(references[0].range, position(2, 25)..position(2, 29));
+    assert_eq!(references[0].kind, ReferenceKind::Usage);
+}
+
+#[test]
+fn test_finds_nothing_for_unknown_symbols() {
+    let compiler = compiler();
+
+    assert!(compiler
+        .find_references(&Symbol::Variable("$silver".to_owned()))
+        .is_empty());
+    // A node name is not a variable name
+    assert!(compiler
+        .find_references(&Symbol::Variable("Shop".to_owned()))
+        .is_empty());
+}
+
+#[test]
+fn test_rename_edits_every_reference() {
+    let mut compiler = compiler();
+
+    let mut edits = compiler.rename(&Symbol::Node("Exit".to_owned()), "Farewell");
+    edits.extend(compiler.rename(&Symbol::Variable("$gold".to_owned()), "coins"));
+    for file in &mut compiler.files {
+        file.apply_text_edits(&edits);
+    }
+
+    assert_eq!(
+        compiler.files[0].source,
+        SHOP.replace("Exit", "Farewell").replace("$gold", "$coins")
+    );
+    assert_eq!(
+        compiler.files[1].source,
+        EXIT.replace("Exit", "Farewell").replace("$gold", "$coins")
+    );
+    compiler.compile().unwrap();
+}
+
+#[test]
+fn test_rename_handles_multibyte_characters_and_byte_order_marks() {
+    let mut file = File {
+        file_name: "ünïcödé.yarn".to_owned(),
+        source: "\u{feff}title: Stärt\n---\nÜber {$größe} <<jump Stärt>>\n===\n".to_owned(),
+    };
+    let mut compiler = Compiler::new();
+    compiler.add_file(file.clone());
+
+    let edits = compiler.rename(&Symbol::Variable("$größe".to_owned()), "$size");
+    file.apply_text_edits(&edits);
+
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].range, position(2, 6)..position(2, 12));
+    assert_eq!(
+        file.source,
+        "\u{feff}title: Stärt\n---\nÜber {$size} <<jump Stärt>>\n===\n"
+    );
+}
@@ -0,0 +1,50 @@
+//! Not part of the original Yarn Spinner. Tests for [`StoryState`].
+
+use test_base::prelude::*;
+use yarnspinner::compiler::*;
+
+mod test_base;
+
+#[test]
+fn test_story_state_reflects_dialogue_progress() {
+    let source = "\
+title: Start
+---
+<<declare $met_sally = false>>
+<<declare $reputation = 0>>
+<<set $met_sally to true>>
+<<set $reputation to $reputation + 2>>
+<<if visited(\"Sally\")>>
+Welcome back!
+<<endif>>
+<<jump Sally>>
+===
+title: Sally
+---
+Hi!
+===
+";
+    let compilation = Compiler::new()
+        .add_file(File {
+            file_name: "story.yarn".to_owned(),
+            source: source.to_owned(),
+        })
+        .compile()
+        .unwrap();
+    let mut dialogue = TestBase::new().with_compilation(compilation).dialogue;
+    let mut state = dialogue.story_state();
+    state.increment("reputation", 10.0).unwrap();
+    let snapshot = state.snapshot();
+
+    dialogue.set_node("Start").unwrap();
+    for _events in dialogue.by_ref() {}
+
+    assert!(state.flag("met_sally"));
+    assert_eq!(state.counter("$reputation"), 12.0);
+    assert!(state.visited("Sally"));
+    assert_eq!(state.visit_count("Sally"), 1);
+
+    state.restore(snapshot).unwrap();
+    assert!(!dialogue.story_state().flag("met_sally"));
+    assert_eq!(dialogue.story_state().counter("reputation"), 10.0);
+}
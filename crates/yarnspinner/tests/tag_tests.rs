@@ -171,6 +171,55 @@ fn test_line_is_last_before_another_node_not_tagged() {
     assert!(!contains_last_line_tag(info));
 }
 
+#[test]
+fn test_options_are_linked_to_line_before_them() {
+    let result = Compiler::from_test_source(
+        "title: Start\n---\nprompt #line:0\n-> option 1 #line:1\n    nested prompt\n    -> nested option #line:2\n-> option 2 #line:3\n<<wait 1>>\n-> interrupted option #line:4\n===\n",
+    )
+    .compile()
+    .unwrap();
+
+    let parent = |line_id: &str| result.string_table[&line_id.into()].parent_line_id.clone();
+    assert_eq!(Some("line:0".into()), parent("line:1"));
+    assert_eq!(Some("line:0".into()), parent("line:3"));
+    assert_eq!(None, parent("line:0"));
+    assert_eq!(None, parent("line:4"));
+
+    // The nested prompt has an implicit line ID
+    let nested_prompt = parent("line:2").unwrap();
+    assert_eq!("nested prompt", result.string_table[&nested_prompt].text);
+}
+
+#[test]
+fn test_last_line_tag_can_be_renamed() {
+    let result =
+        Compiler::from_test_source("title: Start\n---\nprompt #line:0\n-> option #line:1\n===\n")
+            .with_last_line_tag("prompt")
+            .compile()
+            .unwrap();
+
+    let info = &result.string_table[&"line:0".into()];
+    assert!(!contains_last_line_tag(info));
+    assert!(info.metadata.contains(&"prompt".to_owned()));
+}
+
+#[test]
+fn test_last_line_tag_can_be_disabled() {
+    let result =
+        Compiler::from_test_source("title: Start\n---\nprompt #line:0\n-> option #line:1\n===\n")
+            .without_last_line_tag()
+            .compile()
+            .unwrap();
+
+    assert!(!contains_last_line_tag(
+        &result.string_table[&"line:0".into()]
+    ));
+    assert_eq!(
+        Some("line:0".into()),
+        result.string_table[&"line:1".into()].parent_line_id
+    );
+}
+
 fn contains_last_line_tag(info: &StringInfo) -> bool {
     info.metadata.contains(&"lastline".to_owned())
 }
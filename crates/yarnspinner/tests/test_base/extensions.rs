@@ -2,6 +2,7 @@
 
 use crate::prelude::*;
 use yarnspinner_compiler::prelude::*;
+use yarnspinner_runtime::prelude::{Dialogue, DialogueError};
 
 pub trait TestCompiler {
     fn from_test_source(source: &str) -> Self;
@@ -19,3 +20,19 @@ impl TestCompiler for Compiler {
         compiler
     }
 }
+
+pub trait TestDialogue {
+    /// Continues the dialogue until it is no longer active. Fails on the first runtime error.
+    fn run_to_completion(&mut self) -> std::result::Result<(), DialogueError>;
+}
+
+impl TestDialogue for Dialogue {
+    fn run_to_completion(&mut self) -> std::result::Result<(), DialogueError> {
+        loop {
+            self.continue_()?;
+            if !self.is_active() {
+                return Ok(());
+            }
+        }
+    }
+}
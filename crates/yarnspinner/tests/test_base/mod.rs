@@ -26,8 +26,6 @@ use yarnspinner::runtime::*;
 mod extensions;
 mod logger;
 mod paths;
-mod step;
-mod test_plan;
 mod text_provider;
 use logger::*;
 pub use text_provider::SharedTextProvider;
@@ -35,7 +33,8 @@ use yarnspinner::log::{self, LevelFilter, SetLoggerError};
 
 pub mod prelude {
     #[allow(unused_imports)] // False positive
-    pub use crate::test_base::{extensions::*, paths::*, step::*, test_plan::*, *};
+    pub use crate::test_base::{extensions::*, paths::*, *};
+    pub use yarnspinner::yarnspinner_test::*;
 }
 
 pub fn init_logger(runtime_errors_cause_failure: Arc<AtomicBool>) -> Result<(), SetLoggerError> {
@@ -92,7 +91,11 @@ impl TestBase {
     /// Sets the current test plan to one loaded from a given path.
     #[must_use]
     pub fn read_test_plan(self, path: impl AsRef<Path>) -> Self {
-        self.with_test_plan(TestPlan::read(path))
+        let path = path.as_ref();
+        let test_plan = to_rust_serialization(&fs::read_to_string(path).unwrap())
+            .parse()
+            .unwrap_or_else(|error| panic!("Failed to read {}: {error}", path.display()));
+        self.with_test_plan(test_plan)
     }
 
     #[must_use]
@@ -145,108 +148,31 @@ impl TestBase {
     /// Executes the named node, and checks any assertions made during
     /// execution. Fails the test if an assertion made in Yarn fails.
     pub fn run_standard_testcase(&mut self) -> &mut Self {
-        self.dialogue.set_node("Start").unwrap();
+        if let Some(test_plan) = self.test_plan.as_ref() {
+            if let Err(failure) = test_plan.run(&mut self.dialogue, "Start") {
+                panic!("{failure}");
+            }
+            return self;
+        }
 
+        self.dialogue.set_node("Start").unwrap();
         while let Some(events) = self.dialogue.next() {
             for event in events {
                 match event {
-                    DialogueEvent::Line(line) => {
-                        println!("Line: {}", line.text);
-                        let Some(test_plan) = self.test_plan.as_mut() else {
-                            continue;
-                        };
-                        test_plan.next();
-
-                        assert_eq!(
-                            ExpectedStepType::Line,
-                            test_plan.next_expected_step,
-                            "Received line {}, but was expecting a {:?}",
-                            line.text,
-                            test_plan.next_expected_step
-                        );
-                        assert_eq!(
-                            test_plan.next_step_value,
-                            Some(StepValue::String(line.text))
-                        );
-                    }
+                    DialogueEvent::Line(line) => println!("Line: {}", line.text),
                     DialogueEvent::Options(options) => {
                         println!("Options:");
-
-                        let options: Vec<_> = options
-                            .into_iter()
-                            .map(|option| ProcessedOption {
-                                line: option.line.text,
-                                enabled: option.is_available,
-                            })
-                            .collect();
                         for option in &options {
-                            println!(" - {} (available: {})", option.line, option.enabled);
-                        }
-                        let Some(test_plan) = self.test_plan.as_mut() else {
-                            continue;
-                        };
-
-                        test_plan.next();
-                        assert_eq!(
-                            ExpectedStepType::Select,
-                            test_plan.next_expected_step,
-                            "Received {} options, but wasn't expecting them (was expecting {:?})",
-                            options.len(),
-                            test_plan.next_expected_step
-                        );
-
-                        assert_eq!(test_plan.next_expected_options, options);
-
-                        if let Some(StepValue::Number(selection)) = test_plan.next_step_value {
-                            let selection = selection - 1; // 1-indexed for test plan, 0-indexed in the code
-                            println!("[Selecting option {}]", selection);
-                            self.dialogue
-                                .set_selected_option(OptionId(selection))
-                                .unwrap();
-                        } else {
-                            println!("[Selecting option 0 implicitly]");
-                            self.dialogue.set_selected_option(OptionId(0)).unwrap();
+                            println!(
+                                " - {} (available: {})",
+                                option.line.text, option.is_available
+                            );
                         }
+                        println!("[Selecting option 0 implicitly]");
+                        self.dialogue.set_selected_option(OptionId(0)).unwrap();
                     }
-                    DialogueEvent::Command(command) => {
-                        println!("Command: {}", command.raw);
-                        let Some(test_plan) = self.test_plan.as_mut() else {
-                            continue;
-                        };
-                        test_plan.next();
-                        assert_eq!(
-                        ExpectedStepType::Command,
-                        test_plan.next_expected_step,
-                        "Received command {}, but wasn't expecting to select one (was expecting {:?})",
-                        command.raw,
-                        test_plan.next_expected_step
-                    );
-
-                        // We don't need to get the composed string for a
-                        // command because it's been done for us in the
-                        // virtual machine. The VM can do this because
-                        // commands are not localised, so we don't need to
-                        // refer to the string table to get the text.
-                        assert_eq!(
-                            test_plan.next_step_value,
-                            Some(StepValue::String(command.raw))
-                        );
-                    }
-                    DialogueEvent::NodeComplete(_) => {}
-                    DialogueEvent::NodeStart(_) => {}
-                    DialogueEvent::LineHints(_) => {}
-                    DialogueEvent::DialogueComplete => {
-                        let Some(test_plan) = self.test_plan.as_mut() else {
-                            continue;
-                        };
-                        test_plan.next();
-                        assert_eq!(
-                        ExpectedStepType::Stop,
-                        test_plan.next_expected_step,
-                        "Stopped dialogue, but wasn't expecting to select it (was expecting {:?})",
-                        test_plan.next_expected_step
-                    );
-                    }
+                    DialogueEvent::Command(command) => println!("Command: {}", command.raw),
+                    _ => {}
                 }
             }
         }
@@ -280,3 +206,9 @@ impl TestBase {
             .map(move |entry| subdir.join(entry.file_name()))
     }
 }
+
+fn to_rust_serialization(test_plan: &str) -> String {
+    // Need to do this because in Rust, booleans are not capitalized when converted to strings.
+    // But in C# and hence our test plans, they are: https://stackoverflow.com/questions/491334/why-does-boolean-tostring-output-true-and-not-true
+    test_plan.replace("True", "true").replace("False", "false")
+}
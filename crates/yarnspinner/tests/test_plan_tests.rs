@@ -0,0 +1,197 @@
+//! Not part of the original Yarn Spinner, which only uses test plans internally.
+
+use test_base::prelude::*;
+use yarnspinner::compiler::*;
+
+mod test_base;
+
+const GUARD: &str = "\
+Guard: Halt!
+<<play_sound horn>>
+-> Pay the toll
+    Guard: Thank you.
+-> Bribe the guard <<if false>>
+-> Run away
+    Guard: Come back!
+";
+
+#[test]
+fn test_parsing_test_plan() {
+    let test_plan: TestPlan = "\
+# A comment
+line: Guard: Halt!
+
+command: play_sound horn
+option: Pay the toll
+option: Bribe the guard [disabled]
+option: *
+select: 3
+line: *
+stop
+"
+    .parse()
+    .unwrap();
+
+    let steps: Vec<_> = test_plan
+        .steps()
+        .iter()
+        .map(|step| {
+            (
+                step.expected_step_type,
+                step.value.clone(),
+                step.expect_option_enabled,
+                step.line_number,
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            (
+                ExpectedStepType::Line,
+                Some("Guard: Halt!".into()),
+                true,
+                Some(2)
+            ),
+            (
+                ExpectedStepType::Command,
+                Some("play_sound horn".into()),
+                true,
+                Some(4)
+            ),
+            (
+                ExpectedStepType::Option,
+                Some("Pay the toll".into()),
+                true,
+                Some(5)
+            ),
+            (
+                ExpectedStepType::Option,
+                Some("Bribe the guard".into()),
+                false,
+                Some(6)
+            ),
+            (ExpectedStepType::Option, Some("*".into()), true, Some(7)),
+            (ExpectedStepType::Select, Some(3.into()), true, Some(8)),
+            (ExpectedStepType::Line, None, true, Some(9)),
+            (ExpectedStepType::Stop, None, true, Some(10)),
+        ],
+        steps
+    );
+}
+
+#[test]
+fn test_parsing_invalid_test_plan_reports_line() {
+    let result = "line: Hello\n\nwait: 3\n".parse::<TestPlan>();
+    assert!(matches!(
+        result,
+        Err(TestPlanError::Parse { line_number: 3, .. })
+    ));
+    assert_eq!(
+        "Invalid test plan step on line 3: Unknown step \"wait\", expected line, option, select, command or stop",
+        result.unwrap_err().to_string()
+    );
+
+    let result = "select: 0".parse::<TestPlan>();
+    assert!(matches!(
+        result,
+        Err(TestPlanError::Parse { line_number: 1, .. })
+    ));
+}
+
+#[test]
+fn test_running_passing_test_plan() {
+    let result = Compiler::from_test_source(GUARD).compile().unwrap();
+    let test_plan: TestPlan = "\
+line: Guard: Halt!
+command: play_sound horn
+option: Pay the toll
+option: Bribe the guard [disabled]
+option: *
+select: 3
+line: *
+"
+    .parse::<TestPlan>()
+    .unwrap()
+    .with_wildcards_enabled(true);
+
+    test_plan.run_compilation(&result, "Start").unwrap();
+    // Running a plan does not use it up
+    test_plan.run_compilation(&result, "Start").unwrap();
+}
+
+#[test]
+fn test_running_test_plan_without_wildcards_matches_text_exactly() {
+    let result = Compiler::from_test_source(GUARD).compile().unwrap();
+    let test_plan: TestPlan = "line: *".parse().unwrap();
+
+    let error = test_plan.run_compilation(&result, "Start").unwrap_err();
+    assert_eq!(
+        "Test plan line 1: Expected line \"*\", but got line \"Guard: Halt!\"",
+        error.to_string()
+    );
+}
+
+#[test]
+fn test_running_built_test_plan() {
+    let result = Compiler::from_test_source(GUARD).compile().unwrap();
+    let test_plan = TestPlan::new()
+        .expect_line("Guard: Halt!")
+        .expect_command("play_sound horn")
+        .expect_option("Pay the toll")
+        .expect_disabled_option("Bribe the guard")
+        .expect_option("Run away")
+        .then_select(1)
+        .expect_line("Guard: Thank you.")
+        .expect_stop();
+
+    test_plan.run_compilation(&result, "Start").unwrap();
+}
+
+#[test]
+fn test_running_test_plan_reports_mismatch_with_line() {
+    let result = Compiler::from_test_source(GUARD).compile().unwrap();
+    let test_plan: TestPlan = "\
+line: Guard: Halt!
+command: play_sound horn
+option: Pay the toll
+option: Bribe the guard
+option: Run away
+select: 1
+"
+    .parse()
+    .unwrap();
+
+    let Err(TestPlanFailure::Mismatch(mismatch)) = test_plan.run_compilation(&result, "Start")
+    else {
+        panic!("Expected the test plan to fail");
+    };
+    assert_eq!(Some(6), mismatch.line_number);
+    assert_eq!(
+        "Test plan line 6: Expected options [\"Pay the toll\", \"Bribe the guard\", \"Run away\"] and selecting option 1, \
+        but got options [\"Pay the toll\", \"Bribe the guard\" [disabled], \"Run away\"]",
+        mismatch.to_string()
+    );
+}
+
+#[test]
+fn test_running_test_plan_reports_unexpected_end_of_plan() {
+    let result = Compiler::from_test_source("Hello\nGoodbye\n")
+        .compile()
+        .unwrap();
+    let test_plan = TestPlan::new().expect_line("Hello");
+
+    let error = test_plan.run_compilation(&result, "Start").unwrap_err();
+    assert_eq!(
+        "Expected the end of the dialogue, but got line \"Goodbye\"",
+        error.to_string()
+    );
+}
+
+#[test]
+fn test_running_test_plan_reports_missing_node() {
+    let result = Compiler::from_test_source("Hello\n").compile().unwrap();
+    let error = TestPlan::new()
+        .run_compilation(&result, "Missing")
+        .unwrap_err();
+    assert!(matches!(error, TestPlanFailure::Dialogue(_)));
+}
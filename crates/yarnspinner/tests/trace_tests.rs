@@ -0,0 +1,128 @@
+//! Not part of the original Yarn Spinner. Tests for recording and replaying a [`DialogueTrace`].
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{self, Waker};
+use test_base::prelude::*;
+use yarnspinner::compiler::*;
+use yarnspinner::runtime::*;
+
+mod test_base;
+
+#[test]
+fn test_replaying_trace_reproduces_recorded_run() {
+    let roll = Arc::new(AtomicUsize::new(5));
+    let test_base = TestBase::new().extend_library(|library| {
+        let roll = roll.clone();
+        library.add_function("roll", move || roll.load(Ordering::Relaxed) as f32);
+    });
+    let source = "\
+    <<declare $gold = 0>>
+    <<if roll() > 2>>
+    High {$gold}
+    <<else>>
+    Low
+    <<endif>>
+    -> Buy
+        Bought
+    -> Leave
+        Left
+    ";
+    let result = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .compile()
+        .unwrap();
+    let mut test_base = test_base.with_compilation(result);
+    let dialogue = &mut test_base.dialogue;
+    let line_texts = |events: &[DialogueEvent]| -> Vec<String> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                DialogueEvent::Line(line) => Some(line.text.clone()),
+                _ => None,
+            })
+            .collect()
+    };
+
+    dialogue.start_recording_trace();
+    dialogue.set_node("Start").unwrap();
+    let mut recorded_events = dialogue.continue_().unwrap();
+    while dialogue.is_active() {
+        if dialogue.is_waiting_for_option_selection() {
+            dialogue.set_selected_option(OptionId(1)).unwrap();
+        }
+        recorded_events.extend(dialogue.continue_().unwrap());
+    }
+    let trace = dialogue.stop_recording_trace().unwrap();
+    assert_eq!(vec!["High 0", "Left"], line_texts(&recorded_events));
+
+    roll.store(1, Ordering::Relaxed);
+    dialogue
+        .variable_storage_mut()
+        .set("$gold".to_string(), 100.into())
+        .unwrap();
+    let replayed_events = dialogue.replay_trace(trace.clone()).unwrap();
+    assert_eq!(recorded_events, replayed_events);
+
+    let mut tampered_trace = trace;
+    tampered_trace
+        .steps
+        .retain(|step| !matches!(step, TraceStep::FunctionCalled { .. }));
+    assert!(matches!(
+        dialogue.replay_trace(tampered_trace),
+        Err(DialogueError::TraceMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_replaying_trace_with_async_function_reproduces_recorded_run() {
+    let gold = Arc::new(AtomicUsize::new(42));
+    let test_base = TestBase::new().extend_library(|library| {
+        let gold = gold.clone();
+        library.add_function("load_gold", move || {
+            std::future::ready(gold.load(Ordering::Relaxed) as f32)
+        });
+    });
+    let source = "\
+    <<declare $gold = 0>>
+    Before
+    <<set $gold = load_gold()>>
+    After {$gold}
+    -> Buy
+        Bought
+    -> Leave
+        Left
+    ";
+    let result = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .compile()
+        .unwrap();
+    let mut test_base = test_base.with_compilation(result);
+    let dialogue = &mut test_base.dialogue;
+    let mut context = task::Context::from_waker(Waker::noop());
+
+    dialogue.start_recording_trace();
+    dialogue.set_node("Start").unwrap();
+    let mut recorded_events = dialogue.continue_().unwrap();
+    while dialogue.is_active() {
+        if dialogue.is_waiting_for_function_call() {
+            assert!(dialogue.poll_pending_function_call(&mut context).is_ready());
+        }
+        if dialogue.is_waiting_for_option_selection() {
+            dialogue.set_selected_option(OptionId(0)).unwrap();
+        }
+        recorded_events.extend(dialogue.continue_().unwrap());
+    }
+    let trace = dialogue.stop_recording_trace().unwrap();
+    assert!(trace
+        .steps
+        .iter()
+        .any(|step| matches!(step, TraceStep::FunctionCalled { name, .. } if name == "load_gold")));
+
+    gold.store(0, Ordering::Relaxed);
+    let replayed_events = dialogue.replay_trace(trace).unwrap();
+    assert_eq!(recorded_events, replayed_events);
+    assert!(replayed_events
+        .iter()
+        .any(|event| matches!(event, DialogueEvent::Line(line) if line.text == "After 42")));
+}
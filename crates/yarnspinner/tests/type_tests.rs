@@ -175,6 +175,45 @@ fn test_expressions_disallow_mismatched_types() {
         .any(|d| d.message == "$int (Number) cannot be assigned a String"));
 }
 
+#[test]
+fn test_expressions_disallow_implicit_conversions() {
+    for (source, expected_message) in [
+        (
+            "<<set $name = \"Bob\">>\n<<set $bool = $name == 3>>",
+            "All terms of == must be the same, not String, Number",
+        ),
+        (
+            "<<set $str = \"Gold: \" + 5>>",
+            "All terms of + must be the same, not String, Number",
+        ),
+    ] {
+        let result = Compiler::from_test_source(source).compile().unwrap_err();
+
+        assert!(result.0.iter().any(|d| d.message == expected_message));
+    }
+}
+
+#[test]
+fn test_implicitly_inferred_types_are_warned_about() {
+    let compilation =
+        Compiler::from_test_source("<<set $name = \"Bob\">>\n<<set $gold = 5 + bonus()>>")
+            .compile()
+            .unwrap();
+
+    for expected_message in [
+        "Variable $name is not declared, so its type was implicitly inferred as String",
+        "Variable $gold is not declared, so its type was implicitly inferred as Number",
+        "Function \"bonus\" is not declared, so its return type was implicitly inferred as Number",
+    ] {
+        let warning = compilation
+            .warnings
+            .iter()
+            .find(|d| d.message == expected_message)
+            .unwrap_or_else(|| panic!("Missing warning \"{expected_message}\""));
+        assert_eq!(DiagnosticSeverity::Warning, warning.severity);
+    }
+}
+
 #[test]
 fn test_expressions_allows_using_undeclared_variable() {
     for source in [
@@ -774,3 +813,94 @@ fn test_if_statement_expressions_must_be_boolean() {
         .message
         .contains("Terms of 'if statement' must be Bool, not String")));
 }
+
+#[test]
+fn test_imported_manifest_type_checks_function_calls() {
+    let mut library = Library::new();
+    library.add_function("func_int_bool", |_i: i32| true);
+    let manifest = SignatureManifest::from_library(&library);
+
+    let result = Compiler::from_test_source("<<set $bool = func_int_bool(1)>>")
+        .import_manifest(manifest.clone())
+        .compile()
+        .unwrap();
+    assert!(result
+        .declarations
+        .iter()
+        .any(|d| d.name == "$bool" && d.r#type == Type::Boolean));
+
+    let result = Compiler::from_test_source("<<set $bool = func_int_bool(\"one\")>>")
+        .import_manifest(manifest)
+        .compile()
+        .unwrap_err();
+    assert!(result
+        .0
+        .iter()
+        .any(|d| d.message.contains("expects a Number, not a String")));
+}
+
+#[test]
+fn test_declaration_file_type_checks_function_calls() {
+    let declaration_file = File {
+        file_name: "engine.yarn".to_owned(),
+        source: "<<declare function func_int_bool(number) -> bool>>\n<<declare command shake>>\n"
+            .to_owned(),
+    };
+
+    let result = Compiler::from_test_source("<<set $bool = func_int_bool(1)>>")
+        .add_file(declaration_file.clone())
+        .compile()
+        .unwrap();
+    assert!(result
+        .declarations
+        .iter()
+        .any(|d| d.name == "$bool" && d.r#type == Type::Boolean));
+    assert!(!result
+        .string_table
+        .values()
+        .any(|s| s.file_name == "engine.yarn"));
+
+    let result = Compiler::from_test_source("<<set $bool = func_int_bool(\"one\")>>")
+        .add_file(declaration_file)
+        .compile()
+        .unwrap_err();
+    assert!(result
+        .0
+        .iter()
+        .any(|d| d.message.contains("expects a Number, not a String")));
+}
+
+#[test]
+fn test_invalid_declaration_file_is_reported() {
+    let result = Compiler::from_test_source("<<set $bool = true>>")
+        .add_file(File {
+            file_name: "engine.yarn".to_owned(),
+            source: "<<declare function broken(number)>>\n".to_owned(),
+        })
+        .compile()
+        .unwrap_err();
+    assert!(result.0.iter().any(|d| {
+        d.file_name.as_deref() == Some("engine.yarn")
+            && d.message.contains("missing its return type")
+    }));
+}
+
+#[test]
+fn test_values_can_be_used_as_map_keys() {
+    let mut visits = std::collections::HashMap::new();
+    visits.insert(YarnValue::from(0.0), "zero");
+    visits.insert(YarnValue::from(f64::NAN), "nan");
+    visits.insert(YarnList::from(vec!["a", "b"]).into(), "list");
+
+    assert_eq!(visits.get(&YarnValue::from(-0.0)), Some(&"zero"));
+    assert_eq!(visits.get(&YarnValue::from(f64::NAN)), Some(&"nan"));
+    assert_eq!(
+        visits.get(&YarnValue::List(vec!["a".into(), "b".into()])),
+        Some(&"list")
+    );
+    assert_eq!(visits.get(&YarnValue::from("0")), None);
+
+    assert!(YarnValue::from("apple") < YarnValue::from("banana"));
+    assert!(YarnValue::from(false) < YarnValue::from(true));
+    assert_eq!(YarnValue::from(true).partial_cmp(&YarnValue::from(1)), None);
+}
@@ -0,0 +1,111 @@
+//! Not part of the original Yarn Spinner. Tests for the values passed between Yarn scripts and Rust functions.
+
+use test_base::prelude::*;
+use yarnspinner::compiler::*;
+use yarnspinner::core::{YarnList, YarnValue};
+use yarnspinner::runtime::*;
+
+mod test_base;
+
+#[test]
+fn test_numbers_keep_double_precision() {
+    let test_base = TestBase::new();
+    let source = "\
+    <<declare $timestamp = 1700000000.25>>
+    <<set $timestamp = $timestamp + 1>>
+    ";
+    let result = Compiler::from_test_source(source).compile().unwrap();
+    let mut dialogue = test_base.with_compilation(result).dialogue;
+    dialogue.set_node("Start").unwrap();
+    dialogue.run_to_completion().unwrap();
+
+    assert_eq!(
+        dialogue.variable_storage().get("$timestamp").unwrap(),
+        YarnValue::Number(1_700_000_001.25)
+    );
+}
+
+#[test]
+fn test_fractional_number_passed_as_integer_returns_error() {
+    let test_base = TestBase::new().extend_library(|library| {
+        library.add_function("repeat", |text: &str, times: u32| {
+            text.repeat(times as usize)
+        });
+    });
+    let source = "\
+    <<declare $text = \"\">>
+    <<set $text = repeat(\"ab\", 3)>>
+    <<set $text = repeat(\"ab\", 2.5)>>
+    ";
+    let result = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .compile()
+        .unwrap();
+    let mut dialogue = test_base.with_compilation(result).dialogue;
+    dialogue.set_node("Start").unwrap();
+
+    let error = dialogue.continue_().unwrap_err();
+    assert!(
+        matches!(&error, DialogueError::FunctionCallError { function_name, .. } if function_name == "repeat"),
+        "Unexpected error: {error}"
+    );
+    assert_eq!(
+        dialogue.variable_storage().get("$text").unwrap(),
+        YarnValue::from("ababab")
+    );
+}
+
+#[test]
+fn test_list_functions() {
+    let test_base = TestBase::new();
+    let source = "\
+    <<declare $inventory = list(\"sword\") as list>>
+    <<declare $count = 0>>
+    <<declare $has_shield = false>>
+    <<declare $summary = \"\">>
+    <<set $inventory = append($inventory, \"shield\")>>
+    <<set $count = count($inventory)>>
+    <<set $has_shield = contains($inventory, \"shield\")>>
+    <<set $summary = join($inventory + list(\"potion\"), \", \")>>
+    ";
+    let result = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .compile()
+        .unwrap();
+    let mut dialogue = test_base.with_compilation(result).dialogue;
+    dialogue.set_node("Start").unwrap();
+    dialogue.run_to_completion().unwrap();
+
+    let storage = dialogue.variable_storage();
+    assert_eq!(
+        storage.get("$inventory").unwrap(),
+        YarnList::from(vec!["sword", "shield"]).into()
+    );
+    assert_eq!(storage.get("$count").unwrap(), YarnValue::from(2));
+    assert_eq!(storage.get("$has_shield").unwrap(), YarnValue::from(true));
+    assert_eq!(
+        storage.get("$summary").unwrap(),
+        YarnValue::from("sword, shield, potion")
+    );
+}
+
+#[test]
+fn test_list_index_out_of_bounds_returns_error() {
+    let test_base = TestBase::new();
+    let source = "\
+    <<declare $inventory = list() as list>>
+    {item_at($inventory, 0)}
+    ";
+    let result = Compiler::from_test_source(source)
+        .extend_library(test_base.dialogue.library().clone())
+        .compile()
+        .unwrap();
+    let mut dialogue = test_base.with_compilation(result).dialogue;
+    dialogue.set_node("Start").unwrap();
+
+    let error = dialogue.continue_().unwrap_err();
+    assert!(
+        matches!(&error, DialogueError::FunctionCallError { function_name, .. } if function_name == "item_at"),
+        "Unexpected error: {error}"
+    );
+}
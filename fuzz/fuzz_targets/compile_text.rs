@@ -0,0 +1,24 @@
+//! Feeds arbitrary text to the compiler, which mostly exercises the lexer and the parser's error recovery.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use yarnspinner::compiler::*;
+
+fuzz_target!(|source: &str| {
+    for compilation_type in [
+        CompilationType::FullCompilation,
+        CompilationType::StringsOnly,
+    ] {
+        let result = Compiler::new()
+            .with_compilation_type(compilation_type)
+            .add_file(File {
+                file_name: "fuzz.yarn".to_owned(),
+                source: source.to_owned(),
+            })
+            .compile();
+        if let Err(error) = result {
+            assert!(error.0.has_errors(), "Compilation failed without an error");
+        }
+    }
+});
@@ -0,0 +1,19 @@
+//! Feeds Yarn-like files built by `YarnSource` to the compiler, which gets further into type checking and code generation.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use yarnspinner::compiler::*;
+
+fuzz_target!(|files: Vec<File>| {
+    let mut compiler = Compiler::new();
+    compiler.add_files(files);
+    for compilation_type in [
+        CompilationType::FullCompilation,
+        CompilationType::StringsOnly,
+    ] {
+        if let Err(error) = compiler.with_compilation_type(compilation_type).compile() {
+            assert!(error.0.has_errors(), "Compilation failed without an error");
+        }
+    }
+});